@@ -0,0 +1,19 @@
+-- Envelope encryption for will documents and their backups.
+-- Each row carries its own data key, wrapped by the master key version
+-- recorded in key_version. Rows with a NULL key_version predate envelope
+-- encryption and are migrated by the background re-wrap job.
+
+ALTER TABLE will_documents
+    ADD COLUMN wrapped_data_key BYTEA,
+    ADD COLUMN data_key_nonce   BYTEA,
+    ADD COLUMN key_version      INTEGER;
+
+ALTER TABLE document_backups
+    ADD COLUMN wrapped_data_key BYTEA,
+    ADD COLUMN data_key_nonce   BYTEA,
+    ADD COLUMN key_version      INTEGER;
+
+CREATE INDEX idx_will_documents_key_version
+    ON will_documents(key_version) WHERE is_encrypted = TRUE;
+CREATE INDEX idx_document_backups_key_version
+    ON document_backups(key_version);
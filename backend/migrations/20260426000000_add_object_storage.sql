@@ -0,0 +1,26 @@
+-- Content-addressed object storage for legacy content and document backups.
+-- Blobs live in the configured object store; this table is the registry the
+-- integrity scrubber walks to re-hash each object.
+
+CREATE TABLE stored_objects (
+    object_key       TEXT PRIMARY KEY,
+    sha256           VARCHAR(64) NOT NULL,
+    size_bytes       BIGINT NOT NULL,
+    scrub_status     VARCHAR(20) NOT NULL DEFAULT 'unverified'
+        CHECK (scrub_status IN ('unverified', 'healthy', 'repaired', 'missing', 'corrupt')),
+    last_scrubbed_at TIMESTAMP WITH TIME ZONE,
+    created_at       TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_stored_objects_last_scrubbed_at
+    ON stored_objects(last_scrubbed_at NULLS FIRST);
+
+-- New backups keep their ciphertext in object storage and only reference it
+-- by key; rows created before this migration keep their inline ciphertext.
+ALTER TABLE document_backups
+    ALTER COLUMN encrypted_content DROP NOT NULL,
+    ADD COLUMN storage_key TEXT;
+
+ALTER TABLE document_backups
+    ADD CONSTRAINT document_backups_content_location
+    CHECK (encrypted_content IS NOT NULL OR storage_key IS NOT NULL);
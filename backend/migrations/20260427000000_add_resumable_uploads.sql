@@ -0,0 +1,39 @@
+-- Resumable (tus-style) upload sessions for large legacy content. Chunks are
+-- staged in object storage and assembled into a content-addressed object
+-- when the session is finalized.
+
+CREATE TABLE upload_sessions (
+    id                UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner_user_id     UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    original_filename VARCHAR(255) NOT NULL,
+    content_type      VARCHAR(100) NOT NULL,
+    description       TEXT,
+    total_size        BIGINT NOT NULL CHECK (total_size > 0),
+    upload_offset     BIGINT NOT NULL DEFAULT 0 CHECK (upload_offset >= 0),
+    status            VARCHAR(20) NOT NULL DEFAULT 'active'
+        CHECK (status IN ('active', 'completed', 'failed', 'aborted', 'expired')),
+    content_id        UUID REFERENCES legacy_content(id) ON DELETE SET NULL,
+    expires_at        TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at        TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at        TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    CHECK (upload_offset <= total_size)
+);
+
+CREATE INDEX idx_upload_sessions_owner_status ON upload_sessions(owner_user_id, status);
+CREATE INDEX idx_upload_sessions_expiry ON upload_sessions(expires_at) WHERE status = 'active';
+
+CREATE TABLE upload_chunks (
+    session_id   UUID NOT NULL REFERENCES upload_sessions(id) ON DELETE CASCADE,
+    chunk_offset BIGINT NOT NULL,
+    size_bytes   BIGINT NOT NULL CHECK (size_bytes > 0),
+    sha256       VARCHAR(64) NOT NULL,
+    storage_key  TEXT NOT NULL,
+    created_at   TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (session_id, chunk_offset)
+);
+
+-- Resumable uploads raise the per-file ceiling from 500MB to 2GiB.
+ALTER TABLE legacy_content DROP CONSTRAINT check_file_size;
+ALTER TABLE legacy_content
+    ADD CONSTRAINT check_file_size
+    CHECK (file_size > 0 AND file_size <= 2147483648);
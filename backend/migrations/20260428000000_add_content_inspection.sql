@@ -0,0 +1,28 @@
+-- Post-upload inspection of legacy content: magic-byte sniffing, malware
+-- scanning, image metadata stripping and preview generation. Content is only
+-- delivered once inspection_status is 'passed'.
+
+ALTER TABLE legacy_content
+    ADD COLUMN inspection_status  VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (inspection_status IN ('pending', 'passed', 'rejected', 'infected', 'error')),
+    ADD COLUMN inspection_details JSONB,
+    ADD COLUMN thumbnail_key      TEXT,
+    ADD COLUMN inspected_at       TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX idx_legacy_content_inspection_pending
+    ON legacy_content(created_at) WHERE inspection_status = 'pending';
+
+-- Images are accepted now that EXIF/GPS metadata is stripped on inspection.
+ALTER TABLE legacy_content DROP CONSTRAINT check_content_type;
+ALTER TABLE legacy_content
+    ADD CONSTRAINT check_content_type
+    CHECK (content_type IN (
+        'video/mp4', 'video/mpeg', 'video/quicktime', 'video/x-msvideo', 'video/webm',
+        'audio/mpeg', 'audio/wav', 'audio/ogg', 'audio/mp4', 'audio/webm',
+        'text/plain', 'text/markdown', 'text/html',
+        'application/pdf', 'application/msword',
+        'application/vnd.openxmlformats-officedocument.wordprocessingml.document',
+        'application/vnd.ms-excel',
+        'application/vnd.openxmlformats-officedocument.spreadsheetml.sheet',
+        'image/jpeg', 'image/png'
+    ));
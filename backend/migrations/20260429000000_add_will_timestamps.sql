@@ -0,0 +1,16 @@
+-- RFC 3161 timestamp tokens over finalized will hashes.
+-- token holds the DER-encoded TimeStampToken exactly as issued by the TSA;
+-- the remaining columns are extracted from it when it is validated.
+
+CREATE TABLE will_timestamps (
+    id                          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    document_id                 UUID NOT NULL UNIQUE REFERENCES will_documents(id) ON DELETE CASCADE,
+    will_hash                   VARCHAR(64) NOT NULL,
+    tsa_url                     TEXT NOT NULL,
+    token                       BYTEA NOT NULL,
+    serial_number               VARCHAR(128) NOT NULL,
+    policy_oid                  VARCHAR(128) NOT NULL,
+    gen_time                    TIMESTAMP WITH TIME ZONE NOT NULL,
+    signer_certificate_sha256   VARCHAR(64) NOT NULL,
+    created_at                  TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,36 @@
+-- End-to-end encrypted legacy messages.
+-- Beneficiaries register an X25519 key bound to their Stellar address. Messages
+-- in 'e2e' mode are sealed to that key; the server keeps only the ciphertext,
+-- nonce and ephemeral public key and never writes a decrypted payload.
+
+CREATE TABLE message_recipient_keys (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    stellar_address     VARCHAR(56) NOT NULL,
+    public_key          BYTEA NOT NULL CHECK (octet_length(public_key) = 32),
+    binding_signature   BYTEA NOT NULL,
+    status              VARCHAR(20) NOT NULL DEFAULT 'active'
+                            CHECK (status IN ('active', 'replaced')),
+    created_at          TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    replaced_at         TIMESTAMP WITH TIME ZONE
+);
+
+CREATE UNIQUE INDEX idx_message_recipient_keys_active
+    ON message_recipient_keys(stellar_address) WHERE status = 'active';
+
+ALTER TABLE legacy_messages
+    ALTER COLUMN key_version DROP NOT NULL,
+    ADD COLUMN encryption_mode VARCHAR(10) NOT NULL DEFAULT 'server'
+        CHECK (encryption_mode IN ('server', 'e2e')),
+    ADD COLUMN recipient_stellar_address VARCHAR(56),
+    ADD COLUMN recipient_key_id UUID REFERENCES message_recipient_keys(id),
+    ADD COLUMN ephemeral_public_key BYTEA,
+    ADD CONSTRAINT check_legacy_message_encryption CHECK (
+        (encryption_mode = 'server' AND key_version IS NOT NULL)
+        OR (encryption_mode = 'e2e'
+            AND recipient_stellar_address IS NOT NULL
+            AND recipient_key_id IS NOT NULL
+            AND ephemeral_public_key IS NOT NULL)
+    );
+
+ALTER TABLE legacy_message_deliveries
+    ALTER COLUMN decrypted_payload DROP NOT NULL;
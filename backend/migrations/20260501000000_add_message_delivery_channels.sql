@@ -0,0 +1,25 @@
+-- Delivery channels for unlocked legacy messages.
+-- Each message is delivered by email, webhook or to the beneficiary's in-app
+-- inbox. Failed attempts are retried with exponential backoff until
+-- next_attempt_at; the message only becomes 'failed' once attempts run out.
+-- Every delivery carries a hashed receipt token for read confirmations.
+
+ALTER TABLE legacy_messages
+    ADD COLUMN delivery_channel VARCHAR(20) NOT NULL DEFAULT 'email'
+        CHECK (delivery_channel IN ('email', 'webhook', 'inbox')),
+    ADD COLUMN delivery_attempts INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN next_attempt_at TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN last_delivery_error TEXT;
+
+CREATE INDEX idx_legacy_messages_due
+    ON legacy_messages(status, next_attempt_at, unlock_at);
+
+ALTER TABLE legacy_message_deliveries
+    ADD COLUMN channel VARCHAR(20) NOT NULL DEFAULT 'email',
+    ADD COLUMN receipt TEXT,
+    ADD COLUMN receipt_token_hash VARCHAR(64),
+    ADD COLUMN read_at TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX idx_legacy_message_deliveries_inbox
+    ON legacy_message_deliveries(LOWER(beneficiary_contact), delivered_at DESC)
+    WHERE channel = 'inbox';
@@ -0,0 +1,51 @@
+-- M-of-N trustee release of legacy message keys.
+-- An admin records a verified death event for a user. Messages in 'trustee'
+-- mode are encrypted under a random content key that is split with Shamir
+-- secret sharing; each share is sealed to one trustee's message key and the
+-- content key itself is never stored. Once a death event is verified and
+-- trustee_threshold trustees have submitted their shares, the key is
+-- reconstructed, the message is re-encrypted under the server key and handed
+-- to the normal delivery worker.
+
+CREATE TABLE death_verifications (
+    id                      UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id                 UUID NOT NULL UNIQUE REFERENCES users(id) ON DELETE CASCADE,
+    verified_by_admin_id    UUID NOT NULL REFERENCES admins(id),
+    evidence                TEXT NOT NULL,
+    date_of_death           DATE,
+    verified_at             TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE legacy_messages
+    DROP CONSTRAINT legacy_messages_encryption_mode_check,
+    DROP CONSTRAINT check_legacy_message_encryption,
+    ADD COLUMN trustee_threshold SMALLINT;
+
+ALTER TABLE legacy_messages
+    ADD CONSTRAINT legacy_messages_encryption_mode_check
+        CHECK (encryption_mode IN ('server', 'e2e', 'trustee')),
+    ADD CONSTRAINT check_legacy_message_encryption CHECK (
+        (encryption_mode = 'server' AND key_version IS NOT NULL)
+        OR (encryption_mode = 'e2e'
+            AND recipient_stellar_address IS NOT NULL
+            AND recipient_key_id IS NOT NULL
+            AND ephemeral_public_key IS NOT NULL)
+        OR (encryption_mode = 'trustee' AND trustee_threshold IS NOT NULL)
+    );
+
+CREATE TABLE message_trustee_shares (
+    id                      UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    message_id              UUID NOT NULL REFERENCES legacy_messages(id) ON DELETE CASCADE,
+    trustee_stellar_address VARCHAR(56) NOT NULL,
+    recipient_key_id        UUID NOT NULL REFERENCES message_recipient_keys(id),
+    share_index             SMALLINT NOT NULL,
+    sealed_share            JSONB NOT NULL,
+    share_hash              VARCHAR(64) NOT NULL,
+    submitted_share         BYTEA,
+    submitted_at            TIMESTAMP WITH TIME ZONE,
+    created_at              TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE (message_id, trustee_stellar_address)
+);
+
+CREATE INDEX idx_message_trustee_shares_trustee
+    ON message_trustee_shares(trustee_stellar_address);
@@ -0,0 +1,12 @@
+-- Posthumous delivery schedules for legacy messages.
+-- A scheduled message waits in status 'scheduled' until the owner's death is
+-- verified. The delivery worker then resolves its first delivery time into
+-- unlock_at and moves it to 'pending'. Recurring schedules return to
+-- 'pending' with the next occurrence after each delivery.
+
+ALTER TABLE legacy_messages
+    ADD COLUMN schedule JSONB,
+    ADD COLUMN occurrences_delivered INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX idx_legacy_messages_scheduled
+    ON legacy_messages(owner_user_id) WHERE status = 'scheduled';
@@ -0,0 +1,53 @@
+-- Remote electronic notarization of will documents.
+-- A notary is a user with a commission registered by an admin. A testator
+-- opens a session on one of their will documents; the notary verifies the
+-- testator's identity and applies an Ed25519 seal over the will hash. Every
+-- notarial act is appended to the notary's hash-chained journal.
+
+CREATE TABLE notaries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL UNIQUE REFERENCES users(id) ON DELETE CASCADE,
+    full_name VARCHAR(255) NOT NULL,
+    commission_number VARCHAR(100) NOT NULL,
+    commission_jurisdiction VARCHAR(50) NOT NULL,
+    commission_expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    seal_stellar_address VARCHAR(56) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'active'
+        CONSTRAINT notaries_status_check CHECK (status IN ('active', 'suspended')),
+    created_by_admin_id UUID REFERENCES admins(id),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE (commission_jurisdiction, commission_number)
+);
+
+CREATE TABLE notarization_sessions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    document_id UUID NOT NULL REFERENCES will_documents(id) ON DELETE CASCADE,
+    testator_user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    notary_id UUID NOT NULL REFERENCES notaries(id),
+    status VARCHAR(30) NOT NULL DEFAULT 'requested'
+        CONSTRAINT notarization_sessions_status_check
+        CHECK (status IN ('requested', 'identity_verified', 'sealed')),
+    identity_method VARCHAR(50),
+    identity_evidence_hash VARCHAR(64),
+    identity_verified_at TIMESTAMP WITH TIME ZONE,
+    seal_signature VARCHAR(128),
+    certificate JSONB,
+    sealed_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_notarization_sessions_document ON notarization_sessions(document_id);
+CREATE INDEX idx_notarization_sessions_notary ON notarization_sessions(notary_id, status);
+
+CREATE TABLE notary_journal_entries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    notary_id UUID NOT NULL REFERENCES notaries(id) ON DELETE CASCADE,
+    session_id UUID REFERENCES notarization_sessions(id) ON DELETE SET NULL,
+    sequence BIGINT NOT NULL,
+    action VARCHAR(50) NOT NULL,
+    details JSONB NOT NULL DEFAULT '{}',
+    previous_hash VARCHAR(64) NOT NULL,
+    entry_hash VARCHAR(64) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE (notary_id, sequence)
+);
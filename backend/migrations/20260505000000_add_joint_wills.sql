@@ -0,0 +1,36 @@
+-- Joint, mutual and mirror wills signed by several testators.
+-- Each testator is a party to the document and signs through their own
+-- signing challenge. The document moves draft -> signing ->
+-- awaiting_witnesses -> signed as parties and witnesses sign, and can only
+-- be finalized once signed. Revocation by one party invalidates signatures
+-- according to the will type.
+
+ALTER TABLE will_documents
+    ADD COLUMN will_type VARCHAR(20) NOT NULL DEFAULT 'single'
+        CONSTRAINT will_documents_will_type_check
+        CHECK (will_type IN ('single', 'joint', 'mutual', 'mirror')),
+    ADD COLUMN required_witnesses INTEGER NOT NULL DEFAULT 0;
+
+CREATE TABLE will_document_parties (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    document_id     UUID NOT NULL REFERENCES will_documents(id) ON DELETE CASCADE,
+    user_id         UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    wallet_address  VARCHAR(255) NOT NULL,
+    status          VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CONSTRAINT will_document_parties_status_check
+        CHECK (status IN ('pending', 'signed', 'revoked', 'invalidated')),
+    signed_at       TIMESTAMP WITH TIME ZONE,
+    revoked_at      TIMESTAMP WITH TIME ZONE,
+    created_at      TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE (document_id, user_id),
+    UNIQUE (document_id, wallet_address)
+);
+
+CREATE INDEX idx_will_document_parties_user_id ON will_document_parties(user_id);
+
+ALTER TABLE will_signing_challenges
+    ADD COLUMN party_id UUID REFERENCES will_document_parties(id) ON DELETE CASCADE;
+
+ALTER TABLE will_signatures
+    ADD COLUMN party_id UUID REFERENCES will_document_parties(id) ON DELETE SET NULL,
+    ADD COLUMN invalidated_at TIMESTAMP WITH TIME ZONE;
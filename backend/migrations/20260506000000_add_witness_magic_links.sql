@@ -0,0 +1,48 @@
+-- Email witnesses without a wallet sign through a single-use invitation link.
+-- The witness attests to their identity and presence, and the attestation is
+-- signed either with a key the server generates and holds for that witness or
+-- with a key generated in the witness's browser. IP and time evidence is kept
+-- with the attestation.
+
+CREATE TABLE will_witness_invitations (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    witness_id      UUID NOT NULL REFERENCES will_witnesses(id) ON DELETE CASCADE,
+    email           VARCHAR(255) NOT NULL,
+    expires_at      TIMESTAMP WITH TIME ZONE NOT NULL,
+    opened_at       TIMESTAMP WITH TIME ZONE,
+    used_at         TIMESTAMP WITH TIME ZONE,
+    superseded_at   TIMESTAMP WITH TIME ZONE,
+    created_at      TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_will_witness_invitations_witness_id ON will_witness_invitations(witness_id);
+
+-- Server-managed witness signing keys, encrypted under the message data key.
+CREATE TABLE will_witness_keys (
+    witness_id              UUID PRIMARY KEY REFERENCES will_witnesses(id) ON DELETE CASCADE,
+    public_key_hex          VARCHAR(64) NOT NULL,
+    encrypted_private_key   BYTEA NOT NULL,
+    key_nonce               BYTEA NOT NULL,
+    key_version             INTEGER NOT NULL,
+    created_at              TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE will_witness_attestations (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    witness_id      UUID NOT NULL UNIQUE REFERENCES will_witnesses(id) ON DELETE CASCADE,
+    invitation_id   UUID NOT NULL REFERENCES will_witness_invitations(id) ON DELETE CASCADE,
+    document_id     UUID NOT NULL REFERENCES will_documents(id) ON DELETE CASCADE,
+    full_name       VARCHAR(255) NOT NULL,
+    postal_address  TEXT NOT NULL,
+    statement       TEXT NOT NULL,
+    signing_method  VARCHAR(20) NOT NULL
+        CONSTRAINT will_witness_attestations_signing_method_check
+        CHECK (signing_method IN ('server_key', 'browser_key')),
+    public_key_hex  VARCHAR(64) NOT NULL,
+    signature_hex   VARCHAR(128) NOT NULL,
+    ip_address      INET,
+    user_agent      TEXT,
+    attested_at     TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_will_witness_attestations_document_id ON will_witness_attestations(document_id);
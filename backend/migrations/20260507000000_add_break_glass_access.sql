@@ -0,0 +1,28 @@
+-- Break-glass emergency access: an emergency contact with an InheritX account
+-- requests access to the owner's plan, the owner is notified and may veto, and
+-- the grant only takes effect once the owner's waiting period has passed.
+
+-- Break-glass grants are requested by a contact rather than granted by an admin.
+ALTER TABLE emergency_access_grants ALTER COLUMN granted_by DROP NOT NULL;
+
+ALTER TABLE emergency_access_grants
+    ADD COLUMN emergency_contact_id UUID REFERENCES emergency_contacts(id) ON DELETE CASCADE,
+    ADD COLUMN effective_at         TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN vetoed_at            TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN veto_reason          TEXT;
+
+CREATE INDEX idx_emergency_access_grants_pending
+    ON emergency_access_grants(effective_at) WHERE status = 'pending';
+
+-- Per-owner break-glass waiting period.
+CREATE TABLE emergency_access_settings (
+    user_id                 UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    break_glass_delay_hours INTEGER NOT NULL DEFAULT 72
+        CONSTRAINT emergency_access_settings_delay_check
+        CHECK (break_glass_delay_hours BETWEEN 1 AND 720),
+    updated_at              TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- NotificationService stores a type and message but no title; let the owner's
+-- break-glass alerts (and every other in-app notification) be created.
+ALTER TABLE notifications ALTER COLUMN title DROP NOT NULL;
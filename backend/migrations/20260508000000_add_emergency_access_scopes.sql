@@ -0,0 +1,37 @@
+-- Least-privilege emergency access. Each grant lists the permissions it
+-- carries (view_documents, read_messages, pay_bills, trigger_inheritance).
+-- Bill payments are limited to the named payees and a total spending cap.
+ALTER TABLE emergency_access_grants
+    ADD COLUMN permissions    TEXT[] NOT NULL DEFAULT ARRAY['view_documents'],
+    ADD COLUMN allowed_payees TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN spending_cap   NUMERIC(20, 8),
+    ADD COLUMN spent_amount   NUMERIC(20, 8) NOT NULL DEFAULT 0;
+
+-- The permission an audited action relied on.
+ALTER TABLE emergency_access_audit_logs ADD COLUMN permission VARCHAR(50);
+
+-- Bills an emergency contact has approved for payment. The contact settles
+-- each one on-chain through the contract's emergency_withdraw.
+CREATE TABLE emergency_bill_payments (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    grant_id        UUID NOT NULL REFERENCES emergency_access_grants(id) ON DELETE CASCADE,
+    plan_id         UUID NOT NULL REFERENCES plans(id) ON DELETE CASCADE,
+    requested_by    UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    payee           VARCHAR(255) NOT NULL,
+    amount          NUMERIC(20, 8) NOT NULL CHECK (amount > 0),
+    reference       TEXT,
+    created_at      TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_emergency_bill_payments_grant_id ON emergency_bill_payments(grant_id);
+
+-- A plan's inheritance is triggered at most once. `source` records what
+-- triggered it, e.g. 'emergency_access'.
+CREATE TABLE inheritance_triggers (
+    plan_id              UUID PRIMARY KEY REFERENCES plans(id) ON DELETE CASCADE,
+    source               VARCHAR(30) NOT NULL,
+    triggered_by_user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    grant_id             UUID REFERENCES emergency_access_grants(id) ON DELETE SET NULL,
+    reason               TEXT,
+    triggered_at         TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,92 @@
+-- Death verification cases.
+-- A beneficiary or executor opens a case for a user and uploads the death
+-- certificate, which is stored under the same envelope encryption as will
+-- documents. Two different admins must verify the certificate, after which an
+-- objection window opens and every contact of the user is notified. A case
+-- that clears the window without an upheld objection records the verified
+-- death, triggers inheritance for the user's plans and unlocks their legacy
+-- messages.
+
+CREATE TABLE death_verification_cases (
+    id                          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    subject_user_id             UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    opened_by                   UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    opener_role                 VARCHAR(20) NOT NULL
+        CHECK (opener_role IN ('beneficiary', 'executor')),
+    statement                   TEXT NOT NULL,
+    date_of_death               DATE,
+    status                      VARCHAR(30) NOT NULL DEFAULT 'awaiting_certificate'
+        CHECK (status IN ('awaiting_certificate', 'under_review', 'objection_window',
+                          'disputed', 'approved', 'rejected')),
+    objection_window_ends_at    TIMESTAMP WITH TIME ZONE,
+    decided_at                  TIMESTAMP WITH TIME ZONE,
+    rejection_reason            TEXT,
+    created_at                  TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at                  TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- One case in progress per user.
+CREATE UNIQUE INDEX idx_death_verification_cases_open
+    ON death_verification_cases(subject_user_id)
+    WHERE status NOT IN ('approved', 'rejected');
+CREATE INDEX idx_death_verification_cases_window
+    ON death_verification_cases(objection_window_ends_at)
+    WHERE status = 'objection_window';
+
+CREATE TABLE death_certificates (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    case_id             UUID NOT NULL UNIQUE
+        REFERENCES death_verification_cases(id) ON DELETE CASCADE,
+    uploaded_by         UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    filename            VARCHAR(255) NOT NULL,
+    content_type        VARCHAR(100) NOT NULL,
+    size_bytes          BIGINT NOT NULL,
+    sha256              VARCHAR(64) NOT NULL,
+    encrypted_content   BYTEA NOT NULL,
+    encryption_nonce    BYTEA NOT NULL,
+    wrapped_data_key    BYTEA NOT NULL,
+    data_key_nonce      BYTEA NOT NULL,
+    key_version         INTEGER NOT NULL,
+    created_at          TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE death_verification_reviews (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    case_id     UUID NOT NULL REFERENCES death_verification_cases(id) ON DELETE CASCADE,
+    admin_id    UUID NOT NULL REFERENCES admins(id),
+    decision    VARCHAR(10) NOT NULL CHECK (decision IN ('verify', 'reject')),
+    notes       TEXT,
+    created_at  TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE (case_id, admin_id)
+);
+
+CREATE TABLE death_verification_objections (
+    id                      UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    case_id                 UUID NOT NULL REFERENCES death_verification_cases(id) ON DELETE CASCADE,
+    raised_by               UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    reason                  TEXT NOT NULL,
+    status                  VARCHAR(20) NOT NULL DEFAULT 'open'
+        CHECK (status IN ('open', 'dismissed', 'upheld')),
+    resolved_by_admin_id    UUID REFERENCES admins(id),
+    resolution_note         TEXT,
+    resolved_at             TIMESTAMP WITH TIME ZONE,
+    created_at              TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_death_verification_objections_case
+    ON death_verification_objections(case_id);
+
+-- Triggers now also come from death verification cases, and are submitted to
+-- the inheritance contract through the contract relayer.
+ALTER TABLE inheritance_triggers
+    ADD COLUMN case_id UUID REFERENCES death_verification_cases(id) ON DELETE SET NULL,
+    ADD COLUMN chain_status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (chain_status IN ('pending', 'submitted', 'failed', 'not_deployed')),
+    ADD COLUMN chain_tx_hash VARCHAR(128),
+    ADD COLUMN chain_attempts INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN chain_error TEXT,
+    ADD COLUMN chain_submitted_at TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX idx_inheritance_triggers_chain_pending
+    ON inheritance_triggers(triggered_at)
+    WHERE chain_status = 'pending';
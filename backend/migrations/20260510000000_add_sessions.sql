@@ -0,0 +1,29 @@
+-- Login sessions.
+-- Every login opens a session. Access tokens are short-lived JWTs that carry
+-- the session id; the session's refresh token is stored only as a SHA-256
+-- hash and is rotated on each use. The previous hash is kept so that replaying
+-- an already rotated refresh token can be detected and the session revoked.
+
+CREATE TABLE sessions (
+    id                           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id                      UUID REFERENCES users(id) ON DELETE CASCADE,
+    admin_id                     UUID REFERENCES admins(id) ON DELETE CASCADE,
+    refresh_token_hash           VARCHAR(64) NOT NULL UNIQUE,
+    previous_refresh_token_hash  VARCHAR(64),
+    device_name                  VARCHAR(100),
+    user_agent                   TEXT,
+    ip_address                   INET,
+    created_at                   TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    last_used_at                 TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    expires_at                   TIMESTAMP WITH TIME ZONE NOT NULL,
+    revoked_at                   TIMESTAMP WITH TIME ZONE,
+    revoked_reason               VARCHAR(50),
+    revoked_by_admin_id          UUID REFERENCES admins(id) ON DELETE SET NULL,
+    CHECK ((user_id IS NULL) <> (admin_id IS NULL))
+);
+
+CREATE INDEX idx_sessions_user_active ON sessions(user_id) WHERE revoked_at IS NULL;
+CREATE INDEX idx_sessions_admin_active ON sessions(admin_id) WHERE revoked_at IS NULL;
+CREATE INDEX idx_sessions_previous_refresh_token
+    ON sessions(previous_refresh_token_hash)
+    WHERE previous_refresh_token_hash IS NOT NULL;
@@ -0,0 +1,47 @@
+-- Admin roles.
+-- Each admin has exactly one role, and a role grants a set of permissions
+-- such as 'kyc:review' or 'insurance:payout'. The '*' permission grants
+-- everything. Built-in roles are seeded here and cannot be deleted; the
+-- 'admin' role keeps every permission except role management so existing
+-- admin accounts behave as before.
+
+CREATE TABLE admin_roles (
+    name         VARCHAR(50) PRIMARY KEY,
+    description  TEXT NOT NULL DEFAULT '',
+    permissions  TEXT[] NOT NULL DEFAULT '{}',
+    built_in     BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at   TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at   TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO admin_roles (name, description, permissions, built_in) VALUES
+    ('super_admin', 'Full access, including role management', ARRAY['*'], TRUE),
+    ('admin', 'Every operational permission except role management', ARRAY[
+        'kyc:review', 'risk:pause', 'risk:simulate', 'keys:rotate',
+        'insurance:read', 'insurance:payout', 'audit:read', 'analytics:read',
+        'plans:read', 'sessions:revoke', 'death:review', 'messages:deliver',
+        'emergency:manage', 'loans:manage', 'governance:manage',
+        'notary:manage', 'prices:manage'
+    ], TRUE),
+    ('kyc_reviewer', 'Reviews KYC submissions', ARRAY['kyc:review'], TRUE),
+    ('risk_officer', 'Pauses plans and runs stress tests', ARRAY[
+        'risk:pause', 'risk:simulate', 'analytics:read', 'plans:read'
+    ], TRUE),
+    ('security_officer', 'Rotates keys and revokes sessions', ARRAY[
+        'keys:rotate', 'sessions:revoke', 'audit:read'
+    ], TRUE),
+    ('insurance_officer', 'Processes and pays out insurance claims', ARRAY[
+        'insurance:read', 'insurance:payout'
+    ], TRUE),
+    ('auditor', 'Read-only access to audit logs and reports', ARRAY[
+        'audit:read', 'analytics:read', 'insurance:read', 'plans:read'
+    ], TRUE);
+
+-- Roles already in use keep working, without permissions, until assigned some.
+INSERT INTO admin_roles (name, description)
+SELECT DISTINCT role, 'Pre-existing role' FROM admins
+ON CONFLICT (name) DO NOTHING;
+
+ALTER TABLE admins
+    ADD CONSTRAINT fk_admins_role FOREIGN KEY (role)
+    REFERENCES admin_roles(name) ON UPDATE CASCADE;
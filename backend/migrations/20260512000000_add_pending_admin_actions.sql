@@ -0,0 +1,32 @@
+-- Four-eyes approval of high-risk admin actions.
+-- When an action type requires approval, the admin who requests it only
+-- records a proposal here. A second, different admin holding the same
+-- permission must approve it before it expires; only then is it executed.
+-- Every step is also written to action_logs with entity_type
+-- 'pending_action'.
+
+CREATE TABLE pending_admin_actions (
+    id                   UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    action_type          VARCHAR(50) NOT NULL,
+    payload              JSONB NOT NULL DEFAULT 'null',
+    required_permission  VARCHAR(50) NOT NULL,
+    status               VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'approved', 'executed', 'failed', 'rejected', 'expired')),
+    proposed_by          UUID NOT NULL REFERENCES admins(id),
+    proposed_at          TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    expires_at           TIMESTAMP WITH TIME ZONE NOT NULL,
+    decided_by           UUID REFERENCES admins(id),
+    decided_at           TIMESTAMP WITH TIME ZONE,
+    decision_reason      TEXT,
+    executed_at          TIMESTAMP WITH TIME ZONE,
+    result               JSONB,
+    error                TEXT,
+    -- Proposers may withdraw their own proposal but never approve it.
+    CHECK (status = 'rejected' OR decided_by IS NULL OR decided_by <> proposed_by)
+);
+
+CREATE INDEX idx_pending_admin_actions_pending
+    ON pending_admin_actions(expires_at)
+    WHERE status = 'pending';
+CREATE INDEX idx_pending_admin_actions_proposed_at
+    ON pending_admin_actions(proposed_at DESC);
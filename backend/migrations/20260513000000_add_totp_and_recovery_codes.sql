@@ -0,0 +1,37 @@
+-- Authenticator-app two-factor authentication.
+-- A TOTP credential (RFC 6238) belongs to a user or an admin. Its shared
+-- secret is encrypted under the active message data key and only becomes
+-- usable once confirmed with a first code. last_used_step stops a code from
+-- being replayed within its validity window. Recovery codes are stored as
+-- SHA-256 hashes and can each be used once.
+
+CREATE TABLE totp_credentials (
+    id                 UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id            UUID REFERENCES users(id) ON DELETE CASCADE,
+    admin_id           UUID REFERENCES admins(id) ON DELETE CASCADE,
+    key_version        INTEGER NOT NULL,
+    encrypted_secret   BYTEA NOT NULL,
+    secret_nonce       BYTEA NOT NULL,
+    confirmed_at       TIMESTAMP WITH TIME ZONE,
+    last_used_step     BIGINT,
+    failed_attempts    INTEGER NOT NULL DEFAULT 0,
+    locked_until       TIMESTAMP WITH TIME ZONE,
+    created_at         TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    CHECK ((user_id IS NULL) <> (admin_id IS NULL))
+);
+
+CREATE UNIQUE INDEX idx_totp_credentials_user ON totp_credentials(user_id) WHERE user_id IS NOT NULL;
+CREATE UNIQUE INDEX idx_totp_credentials_admin ON totp_credentials(admin_id) WHERE admin_id IS NOT NULL;
+
+CREATE TABLE recovery_codes (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id     UUID REFERENCES users(id) ON DELETE CASCADE,
+    admin_id    UUID REFERENCES admins(id) ON DELETE CASCADE,
+    code_hash   VARCHAR(64) NOT NULL UNIQUE,
+    used_at     TIMESTAMP WITH TIME ZONE,
+    created_at  TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    CHECK ((user_id IS NULL) <> (admin_id IS NULL))
+);
+
+CREATE INDEX idx_recovery_codes_user ON recovery_codes(user_id) WHERE used_at IS NULL;
+CREATE INDEX idx_recovery_codes_admin ON recovery_codes(admin_id) WHERE used_at IS NULL;
@@ -0,0 +1,44 @@
+-- WebAuthn (passkey) credentials for users and admins.
+-- public_key holds the credential's COSE key as the authenticator sent it.
+-- sign_count is the last signature counter seen; a counter that fails to
+-- advance means the credential was probably cloned, so it is flagged in
+-- clone_detected_at and refused from then on.
+
+CREATE TABLE webauthn_credentials (
+    id                 UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id            UUID REFERENCES users(id) ON DELETE CASCADE,
+    admin_id           UUID REFERENCES admins(id) ON DELETE CASCADE,
+    credential_id      BYTEA NOT NULL UNIQUE,
+    public_key         BYTEA NOT NULL,
+    algorithm          INTEGER NOT NULL,
+    sign_count         BIGINT NOT NULL DEFAULT 0,
+    aaguid             UUID,
+    transports         TEXT[] NOT NULL DEFAULT '{}',
+    backup_eligible    BOOLEAN NOT NULL DEFAULT FALSE,
+    name               VARCHAR(100),
+    created_at         TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    last_used_at       TIMESTAMP WITH TIME ZONE,
+    clone_detected_at  TIMESTAMP WITH TIME ZONE,
+    CHECK ((user_id IS NULL) <> (admin_id IS NULL))
+);
+
+CREATE INDEX idx_webauthn_credentials_user ON webauthn_credentials(user_id) WHERE user_id IS NOT NULL;
+CREATE INDEX idx_webauthn_credentials_admin ON webauthn_credentials(admin_id) WHERE admin_id IS NOT NULL;
+
+-- Outstanding ceremony challenges. Each is single use and short lived.
+-- Sign-in challenges may be issued before the account is known
+-- (discoverable credentials), so user_id and admin_id are both optional.
+CREATE TABLE webauthn_challenges (
+    challenge     VARCHAR(64) PRIMARY KEY,
+    ceremony      VARCHAR(20) NOT NULL CHECK (ceremony IN ('registration', 'authentication')),
+    account_type  VARCHAR(10) NOT NULL CHECK (account_type IN ('user', 'admin')),
+    user_id       UUID REFERENCES users(id) ON DELETE CASCADE,
+    admin_id      UUID REFERENCES admins(id) ON DELETE CASCADE,
+    expires_at    TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at    TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_webauthn_challenges_expires ON webauthn_challenges(expires_at);
+
+-- How each session was signed in, e.g. {pwd, otp} or {passkey}.
+ALTER TABLE sessions ADD COLUMN auth_methods TEXT[] NOT NULL DEFAULT '{}';
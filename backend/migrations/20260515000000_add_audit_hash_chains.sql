@@ -0,0 +1,77 @@
+-- Tamper-evident audit logs.
+--
+-- Each audit table is a stream whose entries are hash-chained:
+--   entry_hash = SHA-256(prev_hash || content_hash)
+-- where prev_hash is the previous entry's entry_hash. chain_seq numbers the
+-- entries of a stream without gaps; the head row below is locked by every
+-- insert, so entries are chained in commit order. Rows written before this
+-- migration stay unchained.
+
+CREATE TABLE audit_chain_heads (
+    stream      VARCHAR(32) PRIMARY KEY,
+    last_seq    BIGINT NOT NULL DEFAULT 0,
+    last_hash   BYTEA NOT NULL DEFAULT decode(repeat('00', 32), 'hex'),
+    prev_hash   BYTEA NOT NULL DEFAULT decode(repeat('00', 32), 'hex')
+);
+
+INSERT INTO audit_chain_heads (stream) VALUES
+    ('actions'), ('will_events'), ('message_access'), ('emergency_access');
+
+ALTER TABLE action_logs
+    ADD COLUMN chain_seq BIGINT,
+    ADD COLUMN prev_hash BYTEA,
+    ADD COLUMN entry_hash BYTEA;
+ALTER TABLE will_event_log
+    ADD COLUMN chain_seq BIGINT,
+    ADD COLUMN prev_hash BYTEA,
+    ADD COLUMN entry_hash BYTEA;
+ALTER TABLE message_access_logs
+    ADD COLUMN chain_seq BIGINT,
+    ADD COLUMN prev_hash BYTEA,
+    ADD COLUMN entry_hash BYTEA;
+ALTER TABLE emergency_access_audit_logs
+    ADD COLUMN chain_seq BIGINT,
+    ADD COLUMN prev_hash BYTEA,
+    ADD COLUMN entry_hash BYTEA;
+
+CREATE UNIQUE INDEX idx_action_logs_chain_seq ON action_logs(chain_seq);
+CREATE UNIQUE INDEX idx_will_event_log_chain_seq ON will_event_log(chain_seq);
+CREATE UNIQUE INDEX idx_message_access_logs_chain_seq ON message_access_logs(chain_seq);
+CREATE UNIQUE INDEX idx_emergency_access_audit_logs_chain_seq
+    ON emergency_access_audit_logs(chain_seq);
+
+-- Audit entries must not change once written, so deleting the user, admin,
+-- message or grant they refer to no longer nulls or cascades into them.
+ALTER TABLE action_logs DROP CONSTRAINT IF EXISTS action_logs_user_id_fkey;
+ALTER TABLE action_logs DROP CONSTRAINT IF EXISTS action_logs_admin_id_fkey;
+ALTER TABLE message_access_logs DROP CONSTRAINT IF EXISTS message_access_logs_message_id_fkey;
+ALTER TABLE emergency_access_audit_logs
+    DROP CONSTRAINT IF EXISTS emergency_access_audit_logs_grant_id_fkey;
+ALTER TABLE emergency_access_audit_logs
+    DROP CONSTRAINT IF EXISTS emergency_access_audit_logs_user_id_fkey;
+ALTER TABLE emergency_access_audit_logs
+    DROP CONSTRAINT IF EXISTS emergency_access_audit_logs_emergency_contact_id_fkey;
+
+-- Merkle roots over contiguous runs of a stream, anchored on-chain through
+-- the contract relayer. The contract only accepts each stream's runs in
+-- order, so a failed submission is retried rather than skipped.
+CREATE TABLE audit_anchors (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    stream              VARCHAR(32) NOT NULL REFERENCES audit_chain_heads(stream),
+    first_seq           BIGINT NOT NULL,
+    last_seq            BIGINT NOT NULL,
+    leaf_count          INT NOT NULL,
+    merkle_root         VARCHAR(64) NOT NULL,
+    chain_status        VARCHAR(20) NOT NULL DEFAULT 'pending'
+                        CHECK (chain_status IN ('pending', 'submitted')),
+    chain_tx_hash       VARCHAR(128),
+    chain_attempts      INT NOT NULL DEFAULT 0,
+    chain_error         TEXT,
+    chain_submitted_at  TIMESTAMP WITH TIME ZONE,
+    created_at          TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE (stream, first_seq),
+    CHECK (last_seq >= first_seq)
+);
+
+CREATE INDEX idx_audit_anchors_pending ON audit_anchors(created_at)
+    WHERE chain_status = 'pending';
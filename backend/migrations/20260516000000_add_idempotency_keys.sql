@@ -0,0 +1,26 @@
+-- Idempotency keys for money-moving POST endpoints.
+--
+-- A client may send an Idempotency-Key header with a request; the first
+-- request with a key claims it and its response is stored, so retries with
+-- the same key and the same request replay that response instead of acting
+-- again. Keys are scoped to the calling user or admin and expire.
+
+CREATE TABLE idempotency_keys (
+    principal               VARCHAR(64) NOT NULL,
+    idempotency_key         VARCHAR(255) NOT NULL,
+    request_method          VARCHAR(10) NOT NULL,
+    request_path            TEXT NOT NULL,
+    -- SHA-256 (hex) over the method, path and body of the first request.
+    request_fingerprint     VARCHAR(64) NOT NULL,
+    status                  VARCHAR(20) NOT NULL DEFAULT 'in_progress'
+                            CHECK (status IN ('in_progress', 'completed')),
+    response_status         SMALLINT,
+    response_content_type   TEXT,
+    response_body           BYTEA,
+    created_at              TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    completed_at            TIMESTAMP WITH TIME ZONE,
+    expires_at              TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (principal, idempotency_key)
+);
+
+CREATE INDEX idx_idempotency_keys_expires_at ON idempotency_keys(expires_at);
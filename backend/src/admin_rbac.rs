@@ -0,0 +1,589 @@
+//! Admin roles and permissions.
+//!
+//! Every admin has one role, stored in `admins.role`, and each role in
+//! `admin_roles` grants a set of permissions such as `kyc:review`. Admin
+//! handlers take a [`RequirePermission<P>`] extractor naming the permission
+//! they need; it authenticates the admin like [`AuthenticatedAdmin`] and then
+//! checks the admin's *current* role, so role changes apply without waiting
+//! for tokens to expire. Denials are written to the audit log.
+//!
+//! Permissions are resolved through [`PermissionRegistry`], which caches each
+//! admin's grant for [`PERMISSION_CACHE_TTL`]. Role changes made through this
+//! instance clear the cache; other instances pick them up within the TTL.
+
+use crate::api_error::ApiError;
+use crate::auth::{AdminClaims, AuthenticatedAdmin};
+use crate::notifications::{audit_action, entity_type, AuditLogService};
+use axum::extract::FromRequestParts;
+use axum::http::{request::Parts, Method};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+use uuid::Uuid;
+
+/// How long an admin's resolved permissions are trusted before re-reading.
+pub const PERMISSION_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Grants every permission.
+pub const WILDCARD_PERMISSION: &str = "*";
+
+/// The role that may not be edited, so there is always a way back in.
+pub const SUPER_ADMIN_ROLE: &str = "super_admin";
+
+/// Permissions that let their holder grant themselves anything else, so only
+/// a [`SUPER_ADMIN_ROLE`] admin may hand them out.
+pub const PRIVILEGED_PERMISSIONS: &[&str] =
+    &[WILDCARD_PERMISSION, <perm::RolesManage as Permission>::NAME];
+
+/// A permission an admin route can require.
+pub trait Permission: Send + Sync + 'static {
+    const NAME: &'static str;
+}
+
+macro_rules! permissions {
+    ($($(#[$doc:meta])* $ty:ident => $name:literal,)*) => {
+        /// Marker types for [`RequirePermission`].
+        pub mod perm {
+            $(
+                $(#[$doc])*
+                pub struct $ty;
+
+                impl super::Permission for $ty {
+                    const NAME: &'static str = $name;
+                }
+            )*
+        }
+
+        /// Every permission a role can be granted.
+        pub const ALL_PERMISSIONS: &[&str] = &[$($name),*];
+    };
+}
+
+permissions! {
+    /// Review and decide KYC submissions.
+    KycReview => "kyc:review",
+    /// Pause and unpause plans, and override risk flags.
+    RiskPause => "risk:pause",
+    /// Run stress-test simulations.
+    RiskSimulate => "risk:simulate",
+    /// Rotate message and document encryption keys.
+    KeysRotate => "keys:rotate",
+    /// View insurance funds and claims.
+    InsuranceRead => "insurance:read",
+    /// File, process and pay out insurance claims.
+    InsurancePayout => "insurance:payout",
+    /// Read audit logs.
+    AuditRead => "audit:read",
+    /// Read platform analytics and metrics.
+    AnalyticsRead => "analytics:read",
+    /// View plans across all users.
+    PlansRead => "plans:read",
+    /// Force other principals' sessions to end.
+    SessionsRevoke => "sessions:revoke",
+    /// Review death verification cases.
+    DeathReview => "death:review",
+    /// Trigger legacy message delivery.
+    MessagesDeliver => "messages:deliver",
+    /// Grant and revoke emergency access.
+    EmergencyManage => "emergency:manage",
+    /// Liquidate loans and mark them overdue.
+    LoansManage => "loans:manage",
+    /// Create governance proposals and change protocol parameters.
+    GovernanceManage => "governance:manage",
+    /// Register notaries and verify notary journals.
+    NotaryManage => "notary:manage",
+    /// Register and update price feeds.
+    PricesManage => "prices:manage",
+    /// Manage roles and assign them to admins.
+    RolesManage => "roles:manage",
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
+pub struct AdminRole {
+    pub name: String,
+    pub description: String,
+    pub permissions: Vec<String>,
+    pub built_in: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CreateRoleRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct UpdateRoleRequest {
+    pub description: Option<String>,
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AssignRoleRequest {
+    pub role: String,
+}
+
+/// An admin's current role and what it grants.
+#[derive(Debug, Clone)]
+pub struct PermissionGrant {
+    pub role: String,
+    pub permissions: Vec<String>,
+}
+
+impl PermissionGrant {
+    pub fn allows(&self, permission: &str) -> bool {
+        self.permissions
+            .iter()
+            .any(|p| p == permission || p == WILDCARD_PERMISSION)
+    }
+}
+
+fn validate_role_name(name: &str) -> Result<(), ApiError> {
+    let valid = !name.is_empty()
+        && name.len() <= 50
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !valid {
+        return Err(ApiError::BadRequest(
+            "Role names are 1-50 characters of a-z, 0-9 and _".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn normalize_permissions(permissions: Vec<String>) -> Result<Vec<String>, ApiError> {
+    let mut normalized: Vec<String> = Vec::with_capacity(permissions.len());
+    for permission in permissions {
+        if permission != WILDCARD_PERMISSION && !ALL_PERMISSIONS.contains(&permission.as_str()) {
+            return Err(ApiError::BadRequest(format!(
+                "Unknown permission '{permission}'"
+            )));
+        }
+        if !normalized.contains(&permission) {
+            normalized.push(permission);
+        }
+    }
+    normalized.sort();
+    Ok(normalized)
+}
+
+/// Refuse to let a caller who is not a super admin hand out
+/// [`PRIVILEGED_PERMISSIONS`].
+fn ensure_may_grant(caller_role: &str, permissions: &[String]) -> Result<(), ApiError> {
+    if caller_role == SUPER_ADMIN_ROLE {
+        return Ok(());
+    }
+    match permissions
+        .iter()
+        .find(|p| PRIVILEGED_PERMISSIONS.contains(&p.as_str()))
+    {
+        Some(permission) => Err(ApiError::Forbidden(format!(
+            "Only the {SUPER_ADMIN_ROLE} role may grant '{permission}'"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// The acting admin's current role.
+async fn caller_role(conn: &mut sqlx::PgConnection, admin_id: Uuid) -> Result<String, ApiError> {
+    sqlx::query_scalar("SELECT role FROM admins WHERE id = $1")
+        .bind(admin_id)
+        .fetch_optional(conn)
+        .await?
+        .ok_or(ApiError::Unauthorized)
+}
+
+pub struct AdminRoleService;
+
+impl AdminRoleService {
+    pub async fn list_roles(db: &PgPool) -> Result<Vec<AdminRole>, ApiError> {
+        let roles = sqlx::query_as::<_, AdminRole>("SELECT * FROM admin_roles ORDER BY name")
+            .fetch_all(db)
+            .await?;
+        Ok(roles)
+    }
+
+    pub async fn create_role(
+        db: &PgPool,
+        registry: &PermissionRegistry,
+        admin_id: Uuid,
+        req: CreateRoleRequest,
+    ) -> Result<AdminRole, ApiError> {
+        validate_role_name(&req.name)?;
+        let permissions = normalize_permissions(req.permissions)?;
+        let mut tx = db.begin().await?;
+        ensure_may_grant(&caller_role(&mut tx, admin_id).await?, &permissions)?;
+        let role = sqlx::query_as::<_, AdminRole>(
+            "INSERT INTO admin_roles (name, description, permissions) VALUES ($1, $2, $3) \
+             ON CONFLICT (name) DO NOTHING RETURNING *",
+        )
+        .bind(&req.name)
+        .bind(req.description.unwrap_or_default())
+        .bind(&permissions)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| ApiError::Conflict(format!("Role '{}' already exists", req.name)))?;
+        AuditLogService::log(
+            &mut *tx,
+            None,
+            Some(admin_id),
+            audit_action::ADMIN_ROLE_CREATED,
+            None,
+            Some(entity_type::ADMIN_ROLE),
+            None,
+            None,
+            Some(json!({ "role": role.name, "permissions": role.permissions })),
+        )
+        .await?;
+        tx.commit().await?;
+        registry.invalidate();
+        Ok(role)
+    }
+
+    /// Change a role's permissions. Admins cannot change the role they hold.
+    pub async fn update_role(
+        db: &PgPool,
+        registry: &PermissionRegistry,
+        admin_id: Uuid,
+        name: &str,
+        req: UpdateRoleRequest,
+    ) -> Result<AdminRole, ApiError> {
+        if name == SUPER_ADMIN_ROLE {
+            return Err(ApiError::Forbidden(format!(
+                "The {SUPER_ADMIN_ROLE} role cannot be changed"
+            )));
+        }
+        let permissions = normalize_permissions(req.permissions)?;
+        let mut tx = db.begin().await?;
+        let caller_role = caller_role(&mut tx, admin_id).await?;
+        if caller_role == name {
+            return Err(ApiError::Forbidden(
+                "Admins cannot change their own role".to_string(),
+            ));
+        }
+        ensure_may_grant(&caller_role, &permissions)?;
+        let previous: Vec<String> =
+            sqlx::query_scalar("SELECT permissions FROM admin_roles WHERE name = $1 FOR UPDATE")
+                .bind(name)
+                .fetch_optional(&mut *tx)
+                .await?
+                .ok_or_else(|| ApiError::NotFound(format!("Role '{name}' not found")))?;
+        let role = sqlx::query_as::<_, AdminRole>(
+            "UPDATE admin_roles \
+             SET description = COALESCE($2, description), permissions = $3, updated_at = NOW() \
+             WHERE name = $1 RETURNING *",
+        )
+        .bind(name)
+        .bind(req.description)
+        .bind(&permissions)
+        .fetch_one(&mut *tx)
+        .await?;
+        AuditLogService::log(
+            &mut *tx,
+            None,
+            Some(admin_id),
+            audit_action::ADMIN_ROLE_UPDATED,
+            None,
+            Some(entity_type::ADMIN_ROLE),
+            Some(&previous.join(",")),
+            Some(&permissions.join(",")),
+            Some(json!({ "role": name })),
+        )
+        .await?;
+        tx.commit().await?;
+        registry.invalidate();
+        Ok(role)
+    }
+
+    /// Delete a custom role that no admin holds.
+    pub async fn delete_role(
+        db: &PgPool,
+        registry: &PermissionRegistry,
+        admin_id: Uuid,
+        name: &str,
+    ) -> Result<(), ApiError> {
+        let mut tx = db.begin().await?;
+        let built_in: bool =
+            sqlx::query_scalar("SELECT built_in FROM admin_roles WHERE name = $1 FOR UPDATE")
+                .bind(name)
+                .fetch_optional(&mut *tx)
+                .await?
+                .ok_or_else(|| ApiError::NotFound(format!("Role '{name}' not found")))?;
+        if built_in {
+            return Err(ApiError::Forbidden(format!(
+                "Built-in role '{name}' cannot be deleted"
+            )));
+        }
+        let holders: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM admins WHERE role = $1")
+            .bind(name)
+            .fetch_one(&mut *tx)
+            .await?;
+        if holders > 0 {
+            return Err(ApiError::Conflict(format!(
+                "Role '{name}' is still assigned to {holders} admin(s)"
+            )));
+        }
+        sqlx::query("DELETE FROM admin_roles WHERE name = $1")
+            .bind(name)
+            .execute(&mut *tx)
+            .await?;
+        AuditLogService::log(
+            &mut *tx,
+            None,
+            Some(admin_id),
+            audit_action::ADMIN_ROLE_DELETED,
+            None,
+            Some(entity_type::ADMIN_ROLE),
+            None,
+            None,
+            Some(json!({ "role": name })),
+        )
+        .await?;
+        tx.commit().await?;
+        registry.invalidate();
+        Ok(())
+    }
+
+    /// Give another admin a role. Admins cannot change their own role, and
+    /// only super admins may assign roles carrying [`PRIVILEGED_PERMISSIONS`].
+    pub async fn assign_role(
+        db: &PgPool,
+        registry: &PermissionRegistry,
+        admin_id: Uuid,
+        target_admin_id: Uuid,
+        role: &str,
+    ) -> Result<(), ApiError> {
+        if admin_id == target_admin_id {
+            return Err(ApiError::Forbidden(
+                "Admins cannot change their own role".to_string(),
+            ));
+        }
+        let mut tx = db.begin().await?;
+        let granted: Vec<String> =
+            sqlx::query_scalar("SELECT permissions FROM admin_roles WHERE name = $1")
+                .bind(role)
+                .fetch_optional(&mut *tx)
+                .await?
+                .ok_or_else(|| ApiError::BadRequest(format!("Role '{role}' does not exist")))?;
+        ensure_may_grant(&caller_role(&mut tx, admin_id).await?, &granted)?;
+        let previous: String =
+            sqlx::query_scalar("SELECT role FROM admins WHERE id = $1 FOR UPDATE")
+                .bind(target_admin_id)
+                .fetch_optional(&mut *tx)
+                .await?
+                .ok_or_else(|| ApiError::NotFound(format!("Admin {target_admin_id} not found")))?;
+        sqlx::query("UPDATE admins SET role = $2, updated_at = NOW() WHERE id = $1")
+            .bind(target_admin_id)
+            .bind(role)
+            .execute(&mut *tx)
+            .await?;
+        AuditLogService::log(
+            &mut *tx,
+            None,
+            Some(admin_id),
+            audit_action::ADMIN_ROLE_ASSIGNED,
+            Some(target_admin_id),
+            Some(entity_type::ADMIN),
+            Some(&previous),
+            Some(role),
+            None,
+        )
+        .await?;
+        tx.commit().await?;
+        registry.invalidate();
+        Ok(())
+    }
+}
+
+type CachedGrant = (Option<PermissionGrant>, Instant);
+
+/// Resolves admins' current permissions for [`RequirePermission`].
+pub struct PermissionRegistry {
+    db: PgPool,
+    ttl: std::time::Duration,
+    cache: RwLock<HashMap<Uuid, CachedGrant>>,
+}
+
+impl PermissionRegistry {
+    pub fn new(db: PgPool) -> Self {
+        Self::with_ttl(db, PERMISSION_CACHE_TTL)
+    }
+
+    pub fn with_ttl(db: PgPool, ttl: std::time::Duration) -> Self {
+        Self {
+            db,
+            ttl,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// The admin's role and permissions, or `None` if the admin no longer
+    /// exists or is not active.
+    pub async fn grant_for(&self, admin_id: Uuid) -> Result<Option<PermissionGrant>, ApiError> {
+        if let Some((grant, checked_at)) = self
+            .cache
+            .read()
+            .expect("permission cache poisoned")
+            .get(&admin_id)
+        {
+            if checked_at.elapsed() < self.ttl {
+                return Ok(grant.clone());
+            }
+        }
+
+        let grant = sqlx::query_as::<_, (String, Vec<String>)>(
+            "SELECT a.role, r.permissions FROM admins a \
+             JOIN admin_roles r ON r.name = a.role \
+             WHERE a.id = $1 AND a.status = 'active'",
+        )
+        .bind(admin_id)
+        .fetch_optional(&self.db)
+        .await?
+        .map(|(role, permissions)| PermissionGrant { role, permissions });
+        self.cache
+            .write()
+            .expect("permission cache poisoned")
+            .insert(admin_id, (grant.clone(), Instant::now()));
+        Ok(grant)
+    }
+
+    /// Forget every cached grant, after a role or assignment changes.
+    pub fn invalidate(&self) {
+        self.cache
+            .write()
+            .expect("permission cache poisoned")
+            .clear();
+    }
+
+    /// Allow the request if the admin's role grants `permission`; otherwise
+    /// audit the denial and return `Forbidden`.
+    pub async fn authorize(
+        &self,
+        claims: &AdminClaims,
+        permission: &str,
+        method: &Method,
+        path: &str,
+    ) -> Result<(), ApiError> {
+        let grant = self.grant_for(claims.admin_id).await?;
+        if grant.as_ref().is_some_and(|g| g.allows(permission)) {
+            return Ok(());
+        }
+        // Only attribute the entry to admins that still exist.
+        let admin_id = grant.as_ref().map(|_| claims.admin_id);
+        AuditLogService::log(
+            &self.db,
+            None,
+            admin_id,
+            audit_action::ADMIN_PERMISSION_DENIED,
+            Some(claims.admin_id),
+            Some(entity_type::ADMIN),
+            None,
+            None,
+            Some(json!({
+                "permission": permission,
+                "role": grant.as_ref().map(|g| g.role.as_str()),
+                "method": method.as_str(),
+                "path": path,
+            })),
+        )
+        .await?;
+        Err(ApiError::Forbidden(format!(
+            "Missing permission '{permission}'"
+        )))
+    }
+}
+
+/// An authenticated admin whose role grants permission `P`.
+pub struct RequirePermission<P: Permission>(pub AdminClaims, pub PhantomData<P>);
+
+#[async_trait::async_trait]
+impl<S, P> FromRequestParts<S> for RequirePermission<P>
+where
+    S: Send + Sync,
+    P: Permission,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let AuthenticatedAdmin(claims) =
+            AuthenticatedAdmin::from_request_parts(parts, state).await?;
+        let registry = parts
+            .extensions
+            .get::<Arc<PermissionRegistry>>()
+            .cloned()
+            .ok_or_else(|| {
+                ApiError::Internal(anyhow::anyhow!(
+                    "Permission registry not found in extensions"
+                ))
+            })?;
+
+        registry
+            .authorize(&claims, P::NAME, &parts.method, parts.uri.path())
+            .await?;
+        Ok(RequirePermission(claims, PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grants_match_exact_permissions_or_the_wildcard() {
+        let reviewer = PermissionGrant {
+            role: "kyc_reviewer".to_string(),
+            permissions: vec!["kyc:review".to_string()],
+        };
+        assert!(reviewer.allows(<perm::KycReview as Permission>::NAME));
+        assert!(!reviewer.allows(<perm::InsurancePayout as Permission>::NAME));
+
+        let super_admin = PermissionGrant {
+            role: SUPER_ADMIN_ROLE.to_string(),
+            permissions: vec![WILDCARD_PERMISSION.to_string()],
+        };
+        assert!(super_admin.allows(<perm::RolesManage as Permission>::NAME));
+    }
+
+    #[test]
+    fn role_permissions_are_validated_and_deduplicated() {
+        let normalized = normalize_permissions(vec![
+            "risk:pause".to_string(),
+            "audit:read".to_string(),
+            "risk:pause".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(normalized, vec!["audit:read", "risk:pause"]);
+        assert!(normalize_permissions(vec!["kyc:approve_everything".to_string()]).is_err());
+        assert!(validate_role_name("kyc_reviewer_2").is_ok());
+        assert!(validate_role_name("KYC Reviewer").is_err());
+    }
+
+    #[test]
+    fn only_super_admins_grant_privileged_permissions() {
+        let wildcard = vec![WILDCARD_PERMISSION.to_string()];
+        let manage = vec![<perm::RolesManage as Permission>::NAME.to_string()];
+        let review = vec![<perm::KycReview as Permission>::NAME.to_string()];
+
+        assert!(ensure_may_grant(SUPER_ADMIN_ROLE, &wildcard).is_ok());
+        assert!(ensure_may_grant(SUPER_ADMIN_ROLE, &manage).is_ok());
+        assert!(matches!(
+            ensure_may_grant("role_manager", &wildcard),
+            Err(ApiError::Forbidden(_))
+        ));
+        assert!(matches!(
+            ensure_may_grant("role_manager", &manage),
+            Err(ApiError::Forbidden(_))
+        ));
+        assert!(ensure_may_grant("role_manager", &review).is_ok());
+    }
+}
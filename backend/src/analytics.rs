@@ -1,6 +1,6 @@
+use crate::admin_rbac::{perm, RequirePermission};
 use crate::api_error::ApiError;
 use crate::app::AppState;
-use crate::auth::AuthenticatedAdmin;
 use crate::service::{
     AdminService, ClaimMetricsService, EmergencyAccessMetricsService, LendingMonitoringService,
     PlanStatisticsService, RevenueMetricsService, UserMetricsService, YieldReportFilters,
@@ -11,18 +11,19 @@ use axum::{
     routing::get,
     Json, Router,
 };
+use schemars::JsonSchema;
 use serde::Deserialize;
 use serde_json::{json, Value};
 use std::sync::Arc;
 use uuid::Uuid;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct RevenueRangeQuery {
     #[serde(default = "default_range")]
     pub range: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct YieldSummaryQuery {
     pub asset_code: Option<String>,
@@ -30,7 +31,7 @@ pub struct YieldSummaryQuery {
     pub plan_id: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct YieldHistoryQuery {
     #[serde(default = "default_range")]
@@ -74,7 +75,7 @@ fn build_yield_filters(
 /// Returns high-level protocol metrics: total revenue, plans, claims, users.
 async fn get_overview(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::AnalyticsRead>,
 ) -> Result<Json<Value>, ApiError> {
     let metrics = AdminService::get_metrics_overview(&state.db).await?;
     Ok(Json(json!({
@@ -93,7 +94,7 @@ async fn get_overview(
 /// Returns user growth metrics: total, new (7d/30d), active.
 async fn get_user_metrics(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::AnalyticsRead>,
 ) -> Result<Json<Value>, ApiError> {
     let metrics = UserMetricsService::get_user_growth_metrics(&state.db).await?;
     Ok(Json(json!({
@@ -106,7 +107,7 @@ async fn get_user_metrics(
 /// Returns plan statistics broken down by status.
 async fn get_plan_metrics(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::AnalyticsRead>,
 ) -> Result<Json<Value>, ApiError> {
     let stats = PlanStatisticsService::get_plan_statistics(&state.db).await?;
     Ok(Json(json!({
@@ -119,7 +120,7 @@ async fn get_plan_metrics(
 /// Returns claim processing statistics.
 async fn get_claim_metrics(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::AnalyticsRead>,
 ) -> Result<Json<Value>, ApiError> {
     let stats = ClaimMetricsService::get_claim_statistics(&state.db).await?;
     Ok(Json(json!({
@@ -132,7 +133,7 @@ async fn get_claim_metrics(
 /// Returns time-series revenue breakdown. Defaults to monthly.
 async fn get_revenue_metrics(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::AnalyticsRead>,
     Query(params): Query<RevenueRangeQuery>,
 ) -> Result<Json<Value>, ApiError> {
     let breakdown = RevenueMetricsService::get_revenue_breakdown(&state.db, &params.range).await?;
@@ -146,7 +147,7 @@ async fn get_revenue_metrics(
 /// Returns DeFi lending pool metrics: TVL, utilization rate, active loans.
 async fn get_lending_metrics(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::AnalyticsRead>,
 ) -> Result<Json<Value>, ApiError> {
     let metrics = LendingMonitoringService::get_lending_metrics(&state.db).await?;
     Ok(Json(json!({
@@ -159,7 +160,7 @@ async fn get_lending_metrics(
 /// Returns vault yield and APY aggregated by asset-level vault.
 async fn get_yield_summary(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::AnalyticsRead>,
     Query(params): Query<YieldSummaryQuery>,
 ) -> Result<Json<Value>, ApiError> {
     let filters = build_yield_filters(params.asset_code, params.user_id, params.plan_id)?;
@@ -177,7 +178,7 @@ async fn get_yield_summary(
 /// Returns earnings history from realized interest accruals.
 async fn get_earnings_history(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::AnalyticsRead>,
     Query(params): Query<YieldHistoryQuery>,
 ) -> Result<Json<Value>, ApiError> {
     let filters = build_yield_filters(params.asset_code, params.user_id, params.plan_id)?;
@@ -194,7 +195,7 @@ async fn get_earnings_history(
 /// Returns emergency access usage metrics and trends.
 async fn get_emergency_access_metrics(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::AnalyticsRead>,
     Query(params): Query<RevenueRangeQuery>,
 ) -> Result<Json<Value>, ApiError> {
     let metrics: crate::service::EmergencyAccessMetrics =
@@ -209,7 +210,7 @@ async fn get_emergency_access_metrics(
 /// GET /api/admin/analytics/dashboard
 async fn get_dashboard(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::AnalyticsRead>,
 ) -> Result<Json<Value>, ApiError> {
     let (overview, users, plans, claims, lending) = tokio::try_join!(
         AdminService::get_metrics_overview(&state.db),
@@ -243,7 +244,7 @@ async fn get_dashboard(
 /// Returns flat metrics object (no status wrapper)
 async fn get_overview_legacy(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::AnalyticsRead>,
 ) -> Result<Json<serde_json::Map<String, Value>>, ApiError> {
     let metrics = AdminService::get_metrics_overview(&state.db).await?;
     let mut map = serde_json::Map::new();
@@ -259,7 +260,7 @@ async fn get_overview_legacy(
 /// Returns revenue metrics with range field at root (no status wrapper)
 async fn get_revenue_metrics_legacy(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::AnalyticsRead>,
     Query(params): Query<RevenueRangeQuery>,
 ) -> Result<Json<serde_json::Map<String, Value>>, ApiError> {
     let breakdown = RevenueMetricsService::get_revenue_breakdown(&state.db, &params.range).await?;
@@ -273,7 +274,7 @@ async fn get_revenue_metrics_legacy(
 /// Returns claim metrics wrapped in status/data (for consistency with new endpoints)
 async fn get_claim_metrics_legacy(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::AnalyticsRead>,
 ) -> Result<Json<Value>, ApiError> {
     let stats = ClaimMetricsService::get_claim_statistics(&state.db).await?;
     Ok(Json(json!({
@@ -286,7 +287,7 @@ async fn get_claim_metrics_legacy(
 /// Returns user metrics wrapped in status/data (for consistency with new endpoints)
 async fn get_user_metrics_legacy(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::AnalyticsRead>,
 ) -> Result<Json<Value>, ApiError> {
     let metrics = UserMetricsService::get_user_growth_metrics(&state.db).await?;
     Ok(Json(json!({
@@ -299,7 +300,7 @@ async fn get_user_metrics_legacy(
 /// Returns plan statistics wrapped in status/data (for consistency with new endpoints)
 async fn get_plan_metrics_legacy(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::AnalyticsRead>,
 ) -> Result<Json<Value>, ApiError> {
     let stats = PlanStatisticsService::get_plan_statistics(&state.db).await?;
     Ok(Json(json!({
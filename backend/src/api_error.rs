@@ -31,40 +31,50 @@ pub enum ApiError {
 
     #[error("Too Many Requests")]
     TooManyRequests(String),
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
 }
 
-impl IntoResponse for ApiError {
-    fn into_response(self) -> Response {
-        let (status, message) = match self {
-            Self::Internal(ref e) => {
-                tracing::error!("Internal error: {:?}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, self.to_string())
-            }
-            Self::Database(ref e) => {
-                tracing::error!("Database error: {:?}", e);
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "Internal Server Error".to_string(),
-                )
+impl ApiError {
+    /// The status code the error is reported with.
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Internal(_) | Self::Database(_) | Self::Migration(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
             }
-            Self::Migration(ref e) => {
-                tracing::error!("Migration error: {:?}", e);
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "Migration failed".to_string(),
-                )
-            }
-            Self::Unauthorized => (StatusCode::UNAUTHORIZED, self.to_string()),
-            Self::NotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
-            Self::BadRequest(_) => (StatusCode::BAD_REQUEST, self.to_string()),
-            Self::Forbidden(_) => (StatusCode::FORBIDDEN, self.to_string()),
-            Self::TooManyRequests(_) => (StatusCode::TOO_MANY_REQUESTS, self.to_string()),
-        };
+            Self::Unauthorized => StatusCode::UNAUTHORIZED,
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Self::Forbidden(_) => StatusCode::FORBIDDEN,
+            Self::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            Self::Conflict(_) => StatusCode::CONFLICT,
+        }
+    }
 
-        let body = Json(json!({
+    /// The JSON body sent to the client. Database and migration details are
+    /// never exposed.
+    pub fn body(&self) -> serde_json::Value {
+        let message = match self {
+            Self::Database(_) => "Internal Server Error".to_string(),
+            Self::Migration(_) => "Migration failed".to_string(),
+            _ => self.to_string(),
+        };
+        json!({
             "error": message,
-        }));
+        })
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Internal(ref e) => tracing::error!("Internal error: {:?}", e),
+            Self::Database(ref e) => tracing::error!("Database error: {:?}", e),
+            Self::Migration(ref e) => tracing::error!("Migration error: {:?}", e),
+            _ => {}
+        }
 
-        (status, body).into_response()
+        (self.status_code(), Json(self.body())).into_response()
     }
 }
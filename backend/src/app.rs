@@ -1,9 +1,12 @@
 use axum::{
-    extract::{Path, Query, State},
+    extract::{OriginalUri, Path, Query, State},
+    http::{Method, StatusCode},
     middleware,
+    response::{IntoResponse, Response},
     routing::{delete, get, post, put},
     Json, Router,
 };
+use schemars::JsonSchema;
 use serde_json::{json, Value};
 use sqlx::PgPool;
 use std::sync::Arc;
@@ -15,33 +18,74 @@ use tower_http::{
     trace::TraceLayer,
 };
 
+use crate::metrics::{http_metrics_middleware, MetricsPolicy};
 use crate::middleware::{
     request_id_middleware, request_logging_middleware, request_timeout_middleware,
     security_headers_middleware,
 };
 use uuid::Uuid;
 
+use crate::admin_rbac::{
+    perm, AdminRoleService, AssignRoleRequest, CreateRoleRequest, PermissionRegistry,
+    RequirePermission, UpdateRoleRequest, ALL_PERMISSIONS,
+};
 use crate::analytics::analytics_router;
 use crate::api_error::ApiError;
+use crate::audit_chain::{AuditChainService, AuditStream};
 use crate::auth::{AuthenticatedAdmin, AuthenticatedUser};
 use crate::beneficiary_sync::{BeneficiarySyncService, DocumentBeneficiary};
+use crate::break_glass::{
+    BreakGlassRequest, BreakGlassService, BreakGlassSettings, VetoBreakGlassRequest,
+};
 use crate::collateral_management::{
     AddCollateralRequest, CollateralManagementService, RemoveCollateralRequest,
     SwapCollateralRequest,
 };
 use crate::config::Config;
+use crate::content_inspection::{ensure_deliverable, ContentInspectionService};
 use crate::contingent_beneficiary::{
     AddContingentBeneficiaryRequest, ContingentBeneficiaryService, PromoteContingentRequest,
     RemoveContingentBeneficiaryRequest, SetContingencyConditionsRequest,
 };
-use crate::document_storage::DocumentStorageService;
+use crate::death_verification::{DeathVerificationService, RecordDeathVerificationRequest};
+use crate::death_verification_case::{
+    CaseListFilters, DeathVerificationCaseService, ObjectionRequest, OpenCaseRequest,
+    ResolveObjectionRequest, ReviewCaseRequest,
+};
+use crate::document_storage::{
+    DocumentKeyRing, DocumentKeyRotationService, DocumentStorageService,
+};
+use crate::emergency_scope::{
+    EmergencyScopeService, PayBillRequest, ScopeRequest, TriggerInheritanceRequest,
+};
+use crate::four_eyes::{
+    FourEyesPolicy, FourEyesService, HighRiskAction, PendingAction, PendingActionFilters,
+    RejectPendingActionRequest, Submission,
+};
 use crate::governance::{
     CreateProposalRequest, GovernanceService, ParameterUpdateRequest, Proposal, VoteRequest,
 };
+use crate::idempotency::{idempotency_middleware, IdempotencyPolicy};
 use crate::insurance_fund::{CreateInsuranceClaimRequest, ProcessInsuranceClaimRequest};
+use crate::joint_will::{ConfigureTestatorsRequest, JointWillService, RevokeWillRequest};
 use crate::legacy_content::{ContentListFilters, LegacyContentService};
 use crate::loan_lifecycle::{CreateLoanRequest, LoanLifecycleService, LoanListFilters};
 use crate::message_access_audit::{MessageAccessAuditService, MessageAuditFilters};
+use crate::message_delivery::{MessageChannels, MessageReceiptService};
+use crate::message_schedule::MessageScheduleService;
+use crate::notarization::{
+    NotarizationService, NotaryService, RegisterNotaryRequest, RequestNotarizationRequest,
+    SealRequest, VerifyIdentityRequest,
+};
+use crate::object_storage::{ObjectIntegrityScrubService, ObjectStorageService, ObjectStore};
+use crate::resumable_upload::{
+    CreateUploadSessionRequest, FinalizeUploadRequest, ResumableUploadService, UploadSession,
+    UploadSessionCleanupService,
+};
+use crate::sealed_messages::{
+    CreateSealedMessageRequest, RecipientKeyService, RegisterRecipientKeyRequest,
+    ReleaseSealedMessageRequest, SealedMessageService,
+};
 use crate::secure_messages::{
     CreateLegacyMessageRequest, LegacyMessageDeliveryService, MessageEncryptionService,
     MessageKeyService,
@@ -54,14 +98,25 @@ use crate::service::{
     RevokeEmergencyAccessGrantRequest, RiskOverrideRequest, StartSessionRequest,
     UnpausePlanRequest, UpdateEmergencyContactRequest,
 };
+use crate::sessions::{revoke_reason, Principal, SessionRegistry, SessionService};
 use crate::stress_testing::StressTestingEngine;
+use crate::totp::RequireStepUp;
+use crate::trustee_release::{
+    CreateTrusteeMessageRequest, SubmitTrusteeShareRequest, TrusteeReleaseService,
+    TrusteeShareRequest,
+};
+use crate::webauthn::WebAuthnConfig;
 use crate::will_compliance::{ValidationResult, WillComplianceService};
 use crate::will_pdf::{WillDocumentInput, WillPdfService, WillTemplate};
 use crate::will_signature::{
     SigningChallengeRequest, SubmitSignatureRequest, WillSignatureService,
 };
+use crate::will_timestamp::{TimestampClient, WillTimestampService};
 use crate::will_version::{PaginatedVersions, PaginationParams, WillVersionService};
 use crate::witness::{InviteWitnessRequest, WitnessService, WitnessSignRequest};
+use crate::witness_link::{
+    ClientEvidence, WitnessAttestationRequest, WitnessInvitationService, WitnessLinkSigner,
+};
 use crate::yield_service::{DefaultOnChainYieldService, OnChainYieldService};
 use base64::Engine as _;
 
@@ -71,9 +126,21 @@ pub struct AppState {
     pub yield_service: Arc<dyn OnChainYieldService>,
     pub stress_testing_engine: Arc<StressTestingEngine>,
     pub insurance_fund_service: Arc<crate::insurance_fund::InsuranceFundService>,
+    pub document_keyring: Arc<DocumentKeyRing>,
+    pub object_store: Arc<dyn ObjectStore>,
+    pub timestamp_client: Option<Arc<TimestampClient>>,
+    pub message_channels: Arc<MessageChannels>,
+    pub sessions: Arc<SessionRegistry>,
+    pub permissions: Arc<PermissionRegistry>,
+    pub four_eyes: FourEyesPolicy,
+    pub webauthn: WebAuthnConfig,
+    pub idempotency: IdempotencyPolicy,
+    pub metrics: MetricsPolicy,
 }
 
 pub async fn create_app(db: PgPool, config: Config) -> Result<Router, ApiError> {
+    config.validate()?;
+
     let price_feed = Arc::new(crate::price_feed::DefaultPriceFeedService::new(
         db.clone(),
         3600,
@@ -101,12 +168,54 @@ pub async fn create_app(db: PgPool, config: Config) -> Result<Router, ApiError>
         Arc::new(crate::insurance_fund::InsuranceFundService::new(db.clone()));
     insurance_fund_service.clone().start();
 
+    let document_keyring = Arc::new(DocumentKeyRing::new(
+        crate::secrets::build_secrets_provider(),
+    ));
+
+    let object_store = crate::object_storage::build_object_store()?;
+    let integrity_scrub_service = Arc::new(ObjectIntegrityScrubService::new(
+        db.clone(),
+        object_store.clone(),
+    ));
+    integrity_scrub_service.start();
+
+    let upload_cleanup_service = Arc::new(UploadSessionCleanupService::new(
+        db.clone(),
+        object_store.clone(),
+    ));
+    upload_cleanup_service.start();
+
+    let content_inspection_service = Arc::new(ContentInspectionService::new(
+        db.clone(),
+        object_store.clone(),
+        crate::content_inspection::build_malware_scanner(),
+    ));
+    content_inspection_service.start();
+
+    let timestamp_client = crate::will_timestamp::build_timestamp_client()?;
+    if let Some(client) = &timestamp_client {
+        let timestamp_service = Arc::new(WillTimestampService::new(db.clone(), client.clone()));
+        timestamp_service.start();
+    }
+
+    let message_channels = crate::message_delivery::build_message_channels()?;
+
     let state = Arc::new(AppState {
         db: db.clone(),
         config: config.clone(),
         yield_service,
         stress_testing_engine,
         insurance_fund_service,
+        document_keyring,
+        object_store,
+        timestamp_client,
+        message_channels,
+        sessions: Arc::new(SessionRegistry::new(db.clone())),
+        permissions: Arc::new(PermissionRegistry::new(db.clone())),
+        four_eyes: FourEyesPolicy::from_env()?,
+        webauthn: WebAuthnConfig::from_env(),
+        idempotency: IdempotencyPolicy::from_env()?,
+        metrics: MetricsPolicy::from_env(),
     });
 
     // Rate limiting configuration
@@ -153,10 +262,35 @@ pub async fn create_app(db: PgPool, config: Config) -> Result<Router, ApiError>
         .unwrap_or(30);
     let timeout_duration = Duration::from_secs(timeout_secs);
 
+    // Money-moving POSTs honour the Idempotency-Key header.
+    let idempotent = middleware::from_fn_with_state(state.clone(), idempotency_middleware);
+
     let app = Router::new()
         .route("/health", get(health_check))
         .route("/health/db", get(db_health_check))
         .route("/admin/login", post(crate::auth::login_admin))
+        .route("/api/auth/login", post(crate::auth::login_user))
+        .route(
+            "/api/auth/nonce/:wallet_address",
+            get(crate::auth::generate_nonce),
+        )
+        .route("/api/auth/wallet-login", post(crate::auth::wallet_login))
+        .route(
+            "/api/auth/passkeys/login/options",
+            post(crate::auth::user_passkey_login_options),
+        )
+        .route(
+            "/api/auth/passkeys/login",
+            post(crate::auth::login_user_with_passkey),
+        )
+        .route(
+            "/admin/passkeys/login/options",
+            post(crate::auth::admin_passkey_login_options),
+        )
+        .route(
+            "/admin/passkeys/login",
+            post(crate::auth::login_admin_with_passkey),
+        )
         .layer(
             ServiceBuilder::new()
                 .layer(TraceLayer::new_for_http())
@@ -164,6 +298,96 @@ pub async fn create_app(db: PgPool, config: Config) -> Result<Router, ApiError>
                     config: governor_conf,
                 }),
         )
+        .route("/api/openapi.json", get(crate::openapi::openapi_document))
+        .route("/metrics", get(crate::metrics::metrics_handler))
+        .route("/api/auth/refresh", post(crate::auth::refresh_session))
+        .route("/api/auth/logout", post(crate::auth::logout_user))
+        .route("/admin/logout", post(crate::auth::logout_admin))
+        .route(
+            "/api/auth/2fa/totp/enroll",
+            post(crate::auth::enroll_user_totp),
+        )
+        .route(
+            "/api/auth/2fa/totp/confirm",
+            post(crate::auth::confirm_user_totp),
+        )
+        .route("/api/auth/2fa/totp", delete(crate::auth::disable_user_totp))
+        .route(
+            "/api/auth/2fa/recovery-codes",
+            post(crate::auth::regenerate_user_recovery_codes),
+        )
+        .route(
+            "/api/auth/2fa/status",
+            get(crate::auth::user_two_factor_status),
+        )
+        .route("/api/auth/2fa/step-up", post(crate::auth::step_up_user))
+        .route(
+            "/admin/2fa/totp/enroll",
+            post(crate::auth::enroll_admin_totp),
+        )
+        .route(
+            "/admin/2fa/totp/confirm",
+            post(crate::auth::confirm_admin_totp),
+        )
+        .route(
+            "/api/auth/passkeys/register/options",
+            post(crate::auth::user_passkey_registration_options),
+        )
+        .route(
+            "/api/auth/passkeys/register",
+            post(crate::auth::register_user_passkey),
+        )
+        .route("/api/auth/passkeys", get(crate::auth::list_user_passkeys))
+        .route(
+            "/api/auth/passkeys/:passkey_id",
+            delete(crate::auth::remove_user_passkey),
+        )
+        .route(
+            "/admin/passkeys/register/options",
+            post(crate::auth::admin_passkey_registration_options),
+        )
+        .route(
+            "/admin/passkeys/register",
+            post(crate::auth::register_admin_passkey),
+        )
+        .route("/admin/passkeys", get(crate::auth::list_admin_passkeys))
+        .route(
+            "/admin/passkeys/:passkey_id",
+            delete(crate::auth::remove_admin_passkey),
+        )
+        .route("/api/auth/sessions", get(list_my_sessions))
+        .route("/api/auth/sessions/:session_id", delete(revoke_my_session))
+        .route(
+            "/api/admin/users/:user_id/sessions/revoke",
+            post(force_logout_user),
+        )
+        .route(
+            "/api/admin/admins/:admin_id/sessions/revoke",
+            post(force_logout_admin),
+        )
+        .route("/api/admin/permissions", get(list_admin_permissions))
+        .route(
+            "/api/admin/roles",
+            get(list_admin_roles).post(create_admin_role),
+        )
+        .route(
+            "/api/admin/roles/:role_name",
+            put(update_admin_role).delete(delete_admin_role),
+        )
+        .route("/api/admin/admins/:admin_id/role", put(assign_admin_role))
+        .route("/api/admin/pending-actions", get(list_pending_actions))
+        .route(
+            "/api/admin/pending-actions/:action_id",
+            get(get_pending_action),
+        )
+        .route(
+            "/api/admin/pending-actions/:action_id/approve",
+            post(approve_pending_action),
+        )
+        .route(
+            "/api/admin/pending-actions/:action_id/reject",
+            post(reject_pending_action),
+        )
         .route(
             "/api/plans/due-for-claim",
             get(get_all_due_for_claim_plans_user),
@@ -172,9 +396,12 @@ pub async fn create_app(db: PgPool, config: Config) -> Result<Router, ApiError>
             "/api/plans/due-for-claim/:plan_id",
             get(get_due_for_claim_plan),
         )
-        .route("/api/plans/:plan_id/claim", post(claim_plan))
+        .route(
+            "/api/plans/:plan_id/claim",
+            post(claim_plan).layer(idempotent.clone()),
+        )
         .route("/api/plans/:plan_id", get(get_plan))
-        .route("/api/plans", post(create_plan))
+        .route("/api/plans", post(create_plan).layer(idempotent.clone()))
         .route(
             "/api/messages/legacy",
             post(create_legacy_message).get(list_legacy_messages),
@@ -183,12 +410,95 @@ pub async fn create_app(db: PgPool, config: Config) -> Result<Router, ApiError>
             "/api/messages/legacy/vault/:vault_id",
             get(list_vault_legacy_messages),
         )
+        .route(
+            "/api/messages/legacy/timeline",
+            get(get_legacy_message_timeline),
+        )
+        .route("/api/messages/legacy/sealed", post(create_sealed_message))
+        .route(
+            "/api/messages/legacy/:message_id/release",
+            post(release_sealed_message),
+        )
+        .route("/api/messages/legacy/trustee", post(create_trustee_message))
+        .route(
+            "/api/messages/legacy/:message_id/trustees",
+            get(get_trustee_release_status),
+        )
+        .route(
+            "/api/messages/legacy/:message_id/trustee-share",
+            post(fetch_trustee_share),
+        )
+        .route(
+            "/api/messages/legacy/:message_id/trustee-shares",
+            post(submit_trustee_share),
+        )
+        .route(
+            "/api/admin/users/:user_id/death-verification",
+            post(record_death_verification),
+        )
+        .route(
+            "/api/death-verification/cases",
+            post(open_death_verification_case).get(list_my_death_verification_cases),
+        )
+        .route(
+            "/api/death-verification/cases/:case_id",
+            get(get_my_death_verification_case),
+        )
+        .route(
+            "/api/death-verification/cases/:case_id/certificate",
+            post(upload_death_certificate).layer(axum::extract::DefaultBodyLimit::max(
+                crate::death_verification_case::MAX_CERTIFICATE_SIZE + 64 * 1024,
+            )),
+        )
+        .route(
+            "/api/death-verification/cases/:case_id/objections",
+            post(object_to_death_verification_case),
+        )
+        .route(
+            "/api/admin/death-verification/cases",
+            get(admin_list_death_verification_cases),
+        )
+        .route(
+            "/api/admin/death-verification/cases/finalize",
+            post(finalize_death_verification_cases),
+        )
+        .route(
+            "/api/admin/death-verification/cases/:case_id",
+            get(admin_get_death_verification_case),
+        )
+        .route(
+            "/api/admin/death-verification/cases/:case_id/certificate",
+            get(admin_download_death_certificate),
+        )
+        .route(
+            "/api/admin/death-verification/cases/:case_id/reviews",
+            post(review_death_verification_case),
+        )
+        .route(
+            "/api/admin/death-verification/cases/:case_id/objections/:objection_id/resolve",
+            post(resolve_death_verification_objection),
+        )
+        .route("/api/messages/recipient-keys", post(register_recipient_key))
+        .route(
+            "/api/messages/recipient-keys/:stellar_address",
+            get(get_recipient_key),
+        )
         .route("/api/admin/messages/keys", get(list_message_keys))
         .route("/api/admin/messages/keys/rotate", post(rotate_message_key))
         .route(
             "/api/admin/messages/delivery/process",
             post(process_legacy_message_delivery),
         )
+        .route(
+            "/api/admin/messages/legacy/:message_id/deliver",
+            post(deliver_legacy_message_now),
+        )
+        .route("/api/messages/inbox", get(list_message_inbox))
+        .route("/api/messages/inbox/:delivery_id", get(open_inbox_message))
+        .route(
+            "/api/messages/deliveries/:delivery_id/read",
+            get(confirm_message_read),
+        )
         .route("/api/admin/messages/audit", get(get_message_audit_logs))
         .route(
             "/api/admin/messages/audit/summary",
@@ -251,6 +561,43 @@ pub async fn create_app(db: PgPool, config: Config) -> Result<Router, ApiError>
             "/api/emergency/access/sessions/:session_id/end",
             put(end_emergency_session),
         )
+        // Break-glass emergency access
+        .route(
+            "/api/emergency/break-glass",
+            get(list_break_glass_requests).post(request_break_glass),
+        )
+        .route(
+            "/api/emergency/break-glass/settings",
+            get(get_break_glass_settings).put(update_break_glass_settings),
+        )
+        .route(
+            "/api/emergency/break-glass/:grant_id/veto",
+            post(veto_break_glass),
+        )
+        .route(
+            "/api/emergency/break-glass/:grant_id/plan",
+            get(view_break_glass_plan),
+        )
+        .route(
+            "/api/emergency/break-glass/:grant_id/scope",
+            put(update_emergency_scope),
+        )
+        .route(
+            "/api/emergency/break-glass/:grant_id/documents",
+            get(list_emergency_documents),
+        )
+        .route(
+            "/api/emergency/break-glass/:grant_id/messages",
+            get(read_emergency_messages),
+        )
+        .route(
+            "/api/emergency/break-glass/:grant_id/bill-payments",
+            post(pay_emergency_bill),
+        )
+        .route(
+            "/api/emergency/break-glass/:grant_id/trigger-inheritance",
+            post(trigger_emergency_inheritance),
+        )
         // Loan Simulation endpoints
         .route("/api/loans/simulate", post(simulate_loan))
         .route("/api/loans/simulations", get(get_user_simulations))
@@ -261,7 +608,10 @@ pub async fn create_app(db: PgPool, config: Config) -> Result<Router, ApiError>
         .route("/api/loans/lifecycle", get(list_lifecycle_loans))
         .route("/api/loans/lifecycle/summary", get(get_lifecycle_summary))
         .route("/api/loans/lifecycle/:id", get(get_lifecycle_loan))
-        .route("/api/loans/lifecycle/:id/repay", post(repay_lifecycle_loan))
+        .route(
+            "/api/loans/lifecycle/:id/repay",
+            post(repay_lifecycle_loan).layer(idempotent.clone()),
+        )
         .route(
             "/api/admin/loans/lifecycle/:id/liquidate",
             post(liquidate_lifecycle_loan),
@@ -273,15 +623,15 @@ pub async fn create_app(db: PgPool, config: Config) -> Result<Router, ApiError>
         // ── Collateral Management ──────────────────────────────────────────────
         .route(
             "/api/loans/lifecycle/:id/collateral/add",
-            post(add_collateral),
+            post(add_collateral).layer(idempotent.clone()),
         )
         .route(
             "/api/loans/lifecycle/:id/collateral/remove",
-            post(remove_collateral),
+            post(remove_collateral).layer(idempotent.clone()),
         )
         .route(
             "/api/loans/lifecycle/:id/collateral/swap",
-            post(swap_collateral),
+            post(swap_collateral).layer(idempotent.clone()),
         )
         .route(
             "/api/loans/lifecycle/:id/collateral/value",
@@ -394,7 +744,7 @@ pub async fn create_app(db: PgPool, config: Config) -> Result<Router, ApiError>
         )
         .route(
             "/api/admin/insurance-fund/claims/:claim_id/payout",
-            post(payout_insurance_claim),
+            post(payout_insurance_claim).layer(idempotent.clone()),
         )
         .merge(analytics_router())
         // ── Will PDF & Template Engine (Tasks 1 & 2) ─────────────────────────
@@ -457,6 +807,15 @@ pub async fn create_app(db: PgPool, config: Config) -> Result<Router, ApiError>
             "/api/will/documents/:document_id/signatures",
             get(get_will_signatures),
         )
+        // -- Joint and Mirror Wills --------------------------------------------
+        .route(
+            "/api/will/documents/:document_id/testators",
+            post(configure_will_testators).get(get_will_testators),
+        )
+        .route(
+            "/api/will/documents/:document_id/revoke",
+            post(revoke_will_document),
+        )
         // -- Encrypted Document Storage (Issue #328) --
         .route(
             "/api/will/documents/:document_id/encrypt",
@@ -474,6 +833,10 @@ pub async fn create_app(db: PgPool, config: Config) -> Result<Router, ApiError>
             "/api/will/documents/:document_id/backups",
             get(list_document_backups),
         )
+        .route(
+            "/api/admin/documents/keys/rewrap",
+            post(rewrap_document_keys),
+        )
         // -- Will Compliance Validation (Issue #330) --
         .route("/api/will/validate", post(validate_will_compliance))
         .route("/api/will/jurisdictions", get(list_jurisdictions))
@@ -498,6 +861,45 @@ pub async fn create_app(db: PgPool, config: Config) -> Result<Router, ApiError>
             "/api/will/witnesses/:witness_id/decline",
             post(decline_witness),
         )
+        .route(
+            "/api/will/witnesses/:witness_id/invitation",
+            post(send_witness_invitation),
+        )
+        .route(
+            "/api/will/witness-invitations/:token",
+            get(open_witness_invitation),
+        )
+        .route(
+            "/api/will/witness-invitations/:token/attest",
+            post(attest_as_witness),
+        )
+        .route(
+            "/api/will/documents/:document_id/attestations",
+            get(list_witness_attestations),
+        )
+        // -- Remote Notarization ----------------------------------------------
+        .route(
+            "/api/will/documents/:document_id/notarizations",
+            post(request_notarization).get(list_document_notarizations),
+        )
+        .route("/api/notary/sessions", get(list_notary_sessions))
+        .route(
+            "/api/notary/sessions/:session_id/identity",
+            post(verify_notarization_identity),
+        )
+        .route(
+            "/api/notary/sessions/:session_id/seal",
+            post(seal_notarization),
+        )
+        .route("/api/notary/journal", get(get_notary_journal))
+        .route(
+            "/api/admin/notaries",
+            post(register_notary).get(list_notaries),
+        )
+        .route(
+            "/api/admin/notaries/:notary_id/journal/verify",
+            get(verify_notary_journal),
+        )
         // -- Legal Document Integrity Check (Issue #332) ----------------------
         .route(
             "/api/will/documents/:document_id/verify",
@@ -557,25 +959,57 @@ pub async fn create_app(db: PgPool, config: Config) -> Result<Router, ApiError>
             get(get_plan_audit_summary),
         )
         .route("/api/will/audit/my-activity", get(get_my_audit_activity))
+        // -- Tamper-Evident Audit Logs ----------------------------------------
+        .route(
+            "/api/admin/audit/:stream/:entry_id/verify",
+            get(verify_audit_entry),
+        )
         // -- Legacy Content Upload (Issue #XXX) -------------------------------
-        .route("/api/content/upload", post(upload_legacy_content))
+        .route(
+            "/api/content/upload",
+            post(upload_legacy_content).layer(axum::extract::DefaultBodyLimit::max(
+                crate::legacy_content::MAX_FILE_SIZE + 64 * 1024,
+            )),
+        )
         .route("/api/content", get(list_user_content))
         .route(
             "/api/content/:content_id",
             get(get_content_by_id).delete(delete_content),
         )
         .route("/api/content/:content_id/download", get(download_content))
+        .route(
+            "/api/content/:content_id/thumbnail",
+            get(download_thumbnail),
+        )
         .route("/api/content/stats", get(get_storage_stats))
+        // -- Resumable Content Uploads --------------------------------------
+        .route("/api/content/uploads", post(create_upload_session))
+        .route(
+            "/api/content/uploads/:upload_id",
+            get(get_upload_session)
+                .patch(upload_chunk)
+                .delete(abort_upload_session)
+                .layer(axum::extract::DefaultBodyLimit::max(
+                    crate::resumable_upload::MAX_CHUNK_SIZE + 64 * 1024,
+                )),
+        )
+        .route(
+            "/api/content/uploads/:upload_id/finalize",
+            post(finalize_upload_session),
+        )
         .layer(axum::Extension(config.clone()))
+        .layer(axum::Extension(state.sessions.clone()))
+        .layer(axum::Extension(state.permissions.clone()))
         // ── Middleware stack (Issues #408, #409) ──────────────────────────────
         .layer(middleware::from_fn(security_headers_middleware))
         .layer(middleware::from_fn(request_logging_middleware))
+        .layer(middleware::from_fn(http_metrics_middleware))
         .layer(middleware::from_fn(request_id_middleware))
         .layer(middleware::from_fn(move |req, next| {
             request_timeout_middleware(req, next, timeout_duration)
         }))
         .layer(cors_layer)
-        .with_state(state);
+        .with_state(state.clone());
 
     // Add price feed routes with separate state
     let price_feed_state = (
@@ -615,9 +1049,23 @@ pub async fn create_app(db: PgPool, config: Config) -> Result<Router, ApiError>
             "/api/admin/prices/feeds",
             get(crate::price_feed_handlers::get_active_feeds),
         )
+        .layer(axum::Extension(config.clone()))
+        .layer(axum::Extension(state.sessions.clone()))
+        .layer(axum::Extension(state.permissions.clone()))
         .with_state(price_feed_state);
+    let app = app.merge(price_routes);
 
-    Ok(app.merge(price_routes))
+    if !config.test_auth {
+        return Ok(app);
+    }
+    tracing::warn!("Test authentication is enabled; tokens can be issued for any account");
+    let test_auth_routes = Router::new()
+        .route(
+            "/api/test-auth/token",
+            post(crate::test_auth::issue_test_token),
+        )
+        .with_state(state);
+    Ok(app.merge(test_auth_routes))
 }
 
 async fn health_check() -> Json<Value> {
@@ -635,7 +1083,7 @@ async fn db_health_check(
 
 async fn create_plan(
     State(state): State<Arc<AppState>>,
-    AuthenticatedUser(user): AuthenticatedUser,
+    RequireStepUp(user): RequireStepUp,
     Json(req): Json<CreatePlanRequest>,
 ) -> Result<Json<Value>, ApiError> {
     let plan = PlanService::create_plan(&state.db, user.user_id, &req).await?;
@@ -663,7 +1111,7 @@ async fn get_plan(
 async fn claim_plan(
     State(state): State<Arc<AppState>>,
     Path(plan_id): Path<Uuid>,
-    AuthenticatedUser(user): AuthenticatedUser,
+    RequireStepUp(user): RequireStepUp,
     Json(req): Json<ClaimPlanRequest>,
 ) -> Result<Json<Value>, ApiError> {
     let plan = PlanService::claim_plan(&state.db, plan_id, user.user_id, &req).await?;
@@ -724,115 +1172,698 @@ async fn list_vault_legacy_messages(
     ))
 }
 
-async fn list_message_keys(
+/// User: What each legacy message will deliver and when
+async fn get_legacy_message_timeline(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    AuthenticatedUser(user): AuthenticatedUser,
 ) -> Result<Json<Value>, ApiError> {
-    let keys = MessageKeyService::list_keys(&state.db).await?;
+    let timeline = MessageScheduleService::timeline(&state.db, user.user_id).await?;
     Ok(Json(
-        json!({ "status": "success", "data": keys, "count": keys.len() }),
+        json!({ "status": "success", "data": timeline, "count": timeline.len() }),
     ))
 }
 
-async fn rotate_message_key(
+async fn create_sealed_message(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<CreateSealedMessageRequest>,
 ) -> Result<Json<Value>, ApiError> {
-    let key = MessageKeyService::rotate_active_key(&state.db, admin.admin_id).await?;
-    Ok(Json(json!({
-        "status": "success",
-        "message": "Message encryption key rotated",
-        "data": key
-    })))
+    let message = SealedMessageService::create(&state.db, user.user_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": message })))
 }
 
-async fn process_legacy_message_delivery(
+/// Recipients authenticate with a signature from their Stellar key rather
+/// than a session: beneficiaries need not have an account.
+async fn release_sealed_message(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    Path(message_id): Path<Uuid>,
+    Json(req): Json<ReleaseSealedMessageRequest>,
 ) -> Result<Json<Value>, ApiError> {
-    let delivery_service = LegacyMessageDeliveryService::new(state.db.clone());
-    let result = delivery_service.process_due_messages().await?;
-    Ok(Json(json!({ "status": "success", "data": result })))
+    let released = SealedMessageService::release(&state.db, message_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": released })))
 }
 
-// ─── Message Access Audit Handlers ───────────────────────────────────────────
-
-async fn get_message_audit_logs(
+async fn create_trustee_message(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
-    Query(filters): Query<MessageAuditFilters>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<CreateTrusteeMessageRequest>,
 ) -> Result<Json<Value>, ApiError> {
-    let logs = MessageAccessAuditService::get_logs(&state.db, &filters).await?;
-    Ok(Json(
-        json!({ "status": "success", "data": logs, "count": logs.len() }),
-    ))
+    let message = TrusteeReleaseService::create(&state.db, user.user_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": message })))
 }
 
-async fn get_message_audit_summary(
+async fn get_trustee_release_status(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    Path(message_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
 ) -> Result<Json<Value>, ApiError> {
-    let summary = MessageAccessAuditService::get_summary(&state.db).await?;
-    Ok(Json(json!({ "status": "success", "data": summary })))
+    let status = TrusteeReleaseService::status(&state.db, user.user_id, message_id).await?;
+    Ok(Json(json!({ "status": "success", "data": status })))
 }
 
-async fn search_message_audit_logs(
+/// Trustees, like recipients, authenticate with a signature from their
+/// Stellar key.
+async fn fetch_trustee_share(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
-    Query(params): Query<SearchAuditParams>,
+    Path(message_id): Path<Uuid>,
+    Json(req): Json<TrusteeShareRequest>,
 ) -> Result<Json<Value>, ApiError> {
-    let limit = params.limit.unwrap_or(100);
-    let logs = MessageAccessAuditService::search_logs(&state.db, &params.q, limit).await?;
-    Ok(Json(
-        json!({ "status": "success", "data": logs, "count": logs.len() }),
-    ))
+    let share = TrusteeReleaseService::fetch_share(&state.db, message_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": share })))
 }
 
-#[derive(Debug, serde::Deserialize)]
-struct SearchAuditParams {
-    q: String,
-    limit: Option<i64>,
+async fn submit_trustee_share(
+    State(state): State<Arc<AppState>>,
+    Path(message_id): Path<Uuid>,
+    Json(req): Json<SubmitTrusteeShareRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let status = TrusteeReleaseService::submit_share(&state.db, message_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": status })))
 }
 
-async fn get_message_access_history(
+/// User: List the user's active sessions
+async fn list_my_sessions(
     State(state): State<Arc<AppState>>,
-    AuthenticatedUser(_user): AuthenticatedUser,
-    Path(message_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
 ) -> Result<Json<Value>, ApiError> {
-    let logs = MessageAccessAuditService::get_message_logs(&state.db, message_id, None).await?;
+    let sessions = SessionService::list_for_user(&state.db, user.user_id, user.sid).await?;
     Ok(Json(
-        json!({ "status": "success", "data": logs, "count": logs.len() }),
+        json!({ "status": "success", "data": sessions, "count": sessions.len() }),
     ))
 }
 
-async fn get_my_message_activity(
+/// User: Sign out one of the user's devices
+async fn revoke_my_session(
     State(state): State<Arc<AppState>>,
+    Path(session_id): Path<Uuid>,
     AuthenticatedUser(user): AuthenticatedUser,
 ) -> Result<Json<Value>, ApiError> {
-    let activity = MessageAccessAuditService::get_user_activity(&state.db, user.user_id).await?;
-    Ok(Json(json!({ "status": "success", "data": activity })))
+    SessionService::revoke(
+        &state.db,
+        &state.sessions,
+        Principal::User(user.user_id),
+        session_id,
+        revoke_reason::REVOKED_BY_USER,
+    )
+    .await?;
+    Ok(Json(
+        json!({ "status": "success", "message": "Session revoked" }),
+    ))
 }
 
-async fn get_all_due_for_claim_plans_user(
+/// Admin: Force a user to log out everywhere
+async fn force_logout_user(
     State(state): State<Arc<AppState>>,
-    AuthenticatedUser(user): AuthenticatedUser,
+    Path(user_id): Path<Uuid>,
+    RequirePermission(admin, _): RequirePermission<perm::SessionsRevoke>,
 ) -> Result<Json<Value>, ApiError> {
-    let plans = PlanService::get_all_due_for_claim_plans_for_user(&state.db, user.user_id).await?;
-
-    Ok(Json(json!({
-        "status": "success",
-        "data": plans,
-        "count": plans.len()
-    })))
+    let revoked = SessionService::force_logout(
+        &state.db,
+        &state.sessions,
+        admin.admin_id,
+        Principal::User(user_id),
+    )
+    .await?;
+    Ok(Json(
+        json!({ "status": "success", "data": { "sessions_revoked": revoked } }),
+    ))
 }
 
-async fn get_all_due_for_claim_plans_admin(
+/// Admin: Force another admin to log out everywhere
+async fn force_logout_admin(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    Path(target_admin_id): Path<Uuid>,
+    RequirePermission(admin, _): RequirePermission<perm::SessionsRevoke>,
 ) -> Result<Json<Value>, ApiError> {
-    let plans = PlanService::get_all_due_for_claim_plans_admin(&state.db).await?;
-
-    Ok(Json(json!({
+    let revoked = SessionService::force_logout(
+        &state.db,
+        &state.sessions,
+        admin.admin_id,
+        Principal::Admin(target_admin_id),
+    )
+    .await?;
+    Ok(Json(
+        json!({ "status": "success", "data": { "sessions_revoked": revoked } }),
+    ))
+}
+
+/// Admin: List every permission a role can grant
+async fn list_admin_permissions(
+    RequirePermission(_admin, _): RequirePermission<perm::RolesManage>,
+) -> Result<Json<Value>, ApiError> {
+    Ok(Json(json!({
+        "status": "success",
+        "data": ALL_PERMISSIONS,
+        "count": ALL_PERMISSIONS.len()
+    })))
+}
+
+/// Admin: List admin roles and their permissions
+async fn list_admin_roles(
+    State(state): State<Arc<AppState>>,
+    RequirePermission(_admin, _): RequirePermission<perm::RolesManage>,
+) -> Result<Json<Value>, ApiError> {
+    let roles = AdminRoleService::list_roles(&state.db).await?;
+    Ok(Json(json!({
+        "status": "success",
+        "data": roles,
+        "count": roles.len()
+    })))
+}
+
+/// Admin: Create a custom role
+async fn create_admin_role(
+    State(state): State<Arc<AppState>>,
+    RequirePermission(admin, _): RequirePermission<perm::RolesManage>,
+    Json(req): Json<CreateRoleRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let role =
+        AdminRoleService::create_role(&state.db, &state.permissions, admin.admin_id, req).await?;
+    Ok(Json(json!({ "status": "success", "data": role })))
+}
+
+/// Admin: Replace a role's permissions
+async fn update_admin_role(
+    State(state): State<Arc<AppState>>,
+    Path(role_name): Path<String>,
+    RequirePermission(admin, _): RequirePermission<perm::RolesManage>,
+    Json(req): Json<UpdateRoleRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let role = AdminRoleService::update_role(
+        &state.db,
+        &state.permissions,
+        admin.admin_id,
+        &role_name,
+        req,
+    )
+    .await?;
+    Ok(Json(json!({ "status": "success", "data": role })))
+}
+
+/// Admin: Delete an unused custom role
+async fn delete_admin_role(
+    State(state): State<Arc<AppState>>,
+    Path(role_name): Path<String>,
+    RequirePermission(admin, _): RequirePermission<perm::RolesManage>,
+) -> Result<Json<Value>, ApiError> {
+    AdminRoleService::delete_role(&state.db, &state.permissions, admin.admin_id, &role_name)
+        .await?;
+    Ok(Json(
+        json!({ "status": "success", "message": "Role deleted" }),
+    ))
+}
+
+/// Admin: Assign a role to another admin
+async fn assign_admin_role(
+    State(state): State<Arc<AppState>>,
+    Path(target_admin_id): Path<Uuid>,
+    RequirePermission(admin, _): RequirePermission<perm::RolesManage>,
+    Json(req): Json<AssignRoleRequest>,
+) -> Result<Json<Value>, ApiError> {
+    AdminRoleService::assign_role(
+        &state.db,
+        &state.permissions,
+        admin.admin_id,
+        target_admin_id,
+        &req.role,
+    )
+    .await?;
+    Ok(Json(json!({
+        "status": "success",
+        "data": { "admin_id": target_admin_id, "role": req.role }
+    })))
+}
+
+/// Response for an action that now awaits a second admin's approval.
+fn pending_approval_response(pending: Box<PendingAction>) -> Response {
+    (
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "status": "pending_approval",
+            "message": "Awaiting approval by a second admin",
+            "data": pending
+        })),
+    )
+        .into_response()
+}
+
+/// Admin: List pending actions the admin is allowed to decide
+async fn list_pending_actions(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+    Query(filters): Query<PendingActionFilters>,
+) -> Result<Json<Value>, ApiError> {
+    let grant = state
+        .permissions
+        .grant_for(admin.admin_id)
+        .await?
+        .ok_or_else(|| ApiError::Forbidden("Admin is not active".to_string()))?;
+    let actions: Vec<PendingAction> = FourEyesService::list(&state.db, filters.status.as_deref())
+        .await?
+        .into_iter()
+        .filter(|a| grant.allows(&a.required_permission))
+        .collect();
+    Ok(Json(json!({
+        "status": "success",
+        "data": actions,
+        "count": actions.len()
+    })))
+}
+
+/// Admin: Get a pending action and its audit trail
+async fn get_pending_action(
+    State(state): State<Arc<AppState>>,
+    Path(action_id): Path<Uuid>,
+    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+    method: Method,
+    OriginalUri(uri): OriginalUri,
+) -> Result<Json<Value>, ApiError> {
+    let action = FourEyesService::get(&state.db, action_id).await?;
+    state
+        .permissions
+        .authorize(&admin, &action.required_permission, &method, uri.path())
+        .await?;
+    let audit_trail = crate::notifications::AuditLogService::list_for_entity(
+        &state.db,
+        crate::notifications::entity_type::PENDING_ACTION,
+        action_id,
+    )
+    .await?;
+    Ok(Json(json!({
+        "status": "success",
+        "data": { "action": action, "audit_trail": audit_trail }
+    })))
+}
+
+/// Admin: Approve and execute another admin's pending action
+async fn approve_pending_action(
+    State(state): State<Arc<AppState>>,
+    Path(action_id): Path<Uuid>,
+    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+    method: Method,
+    OriginalUri(uri): OriginalUri,
+) -> Result<Json<Value>, ApiError> {
+    let action = FourEyesService::get(&state.db, action_id).await?;
+    state
+        .permissions
+        .authorize(&admin, &action.required_permission, &method, uri.path())
+        .await?;
+    let action = FourEyesService::approve(&state, action_id, admin.admin_id).await?;
+    Ok(Json(json!({ "status": "success", "data": action })))
+}
+
+/// Admin: Reject a pending action, or withdraw one's own
+async fn reject_pending_action(
+    State(state): State<Arc<AppState>>,
+    Path(action_id): Path<Uuid>,
+    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+    method: Method,
+    OriginalUri(uri): OriginalUri,
+    Json(req): Json<RejectPendingActionRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let action = FourEyesService::get(&state.db, action_id).await?;
+    state
+        .permissions
+        .authorize(&admin, &action.required_permission, &method, uri.path())
+        .await?;
+    let action = FourEyesService::reject(&state.db, action_id, admin.admin_id, req.reason).await?;
+    Ok(Json(json!({ "status": "success", "data": action })))
+}
+
+/// Admin: Record a verified death event for a user
+async fn record_death_verification(
+    State(state): State<Arc<AppState>>,
+    Path(user_id): Path<Uuid>,
+    RequirePermission(admin, _): RequirePermission<perm::DeathReview>,
+    Json(req): Json<RecordDeathVerificationRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let verification =
+        DeathVerificationService::record(&state.db, admin.admin_id, user_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": verification })))
+}
+
+/// User: Open a death verification case as a beneficiary or executor
+async fn open_death_verification_case(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<OpenCaseRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let case = DeathVerificationCaseService::open(&state.db, user.user_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": case })))
+}
+
+/// User: Cases the user opened or is a contact on
+async fn list_my_death_verification_cases(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let cases = DeathVerificationCaseService::list_for_user(&state.db, user.user_id).await?;
+    Ok(Json(
+        json!({ "status": "success", "data": cases, "count": cases.len() }),
+    ))
+}
+
+async fn get_my_death_verification_case(
+    State(state): State<Arc<AppState>>,
+    Path(case_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let case = DeathVerificationCaseService::get_for_user(&state.db, user.user_id, case_id).await?;
+    Ok(Json(json!({ "status": "success", "data": case })))
+}
+
+/// User: Upload the death certificate for a case
+///
+/// `POST /api/death-verification/cases/:case_id/certificate` (multipart `file`)
+async fn upload_death_certificate(
+    State(state): State<Arc<AppState>>,
+    Path(case_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    mut multipart: axum::extract::Multipart,
+) -> Result<Json<Value>, ApiError> {
+    let mut file: Option<(String, String, axum::body::Bytes)> = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("Invalid multipart body: {}", e)))?
+    {
+        if field.name() == Some("file") {
+            let filename = field
+                .file_name()
+                .map(str::to_string)
+                .ok_or_else(|| ApiError::BadRequest("File name is required".to_string()))?;
+            let content_type = field
+                .content_type()
+                .map(str::to_string)
+                .ok_or_else(|| ApiError::BadRequest("Content type is required".to_string()))?;
+            let bytes = field
+                .bytes()
+                .await
+                .map_err(|e| ApiError::BadRequest(format!("Failed to read file: {}", e)))?;
+            file = Some((filename, content_type, bytes));
+        }
+    }
+    let (filename, content_type, bytes) =
+        file.ok_or_else(|| ApiError::BadRequest("Missing file field".to_string()))?;
+
+    let certificate = DeathVerificationCaseService::upload_certificate(
+        &state.db,
+        &state.document_keyring,
+        user.user_id,
+        case_id,
+        &filename,
+        &content_type,
+        &bytes,
+    )
+    .await?;
+    Ok(Json(json!({ "status": "success", "data": certificate })))
+}
+
+/// User: Object to a case during its objection window
+async fn object_to_death_verification_case(
+    State(state): State<Arc<AppState>>,
+    Path(case_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<ObjectionRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let objection =
+        DeathVerificationCaseService::object(&state.db, user.user_id, case_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": objection })))
+}
+
+/// Admin: List death verification cases, optionally by status
+async fn admin_list_death_verification_cases(
+    State(state): State<Arc<AppState>>,
+    RequirePermission(_admin, _): RequirePermission<perm::DeathReview>,
+    Query(filters): Query<CaseListFilters>,
+) -> Result<Json<Value>, ApiError> {
+    let cases = DeathVerificationCaseService::list_for_admin(&state.db, &filters).await?;
+    Ok(Json(
+        json!({ "status": "success", "data": cases, "count": cases.len() }),
+    ))
+}
+
+async fn admin_get_death_verification_case(
+    State(state): State<Arc<AppState>>,
+    Path(case_id): Path<Uuid>,
+    RequirePermission(_admin, _): RequirePermission<perm::DeathReview>,
+) -> Result<Json<Value>, ApiError> {
+    let case = DeathVerificationCaseService::get_for_admin(&state.db, case_id).await?;
+    Ok(Json(json!({ "status": "success", "data": case })))
+}
+
+/// Admin: Download the decrypted death certificate for review
+async fn admin_download_death_certificate(
+    State(state): State<Arc<AppState>>,
+    Path(case_id): Path<Uuid>,
+    RequirePermission(admin, _): RequirePermission<perm::DeathReview>,
+) -> Result<axum::response::Response, ApiError> {
+    let (certificate, bytes) = DeathVerificationCaseService::certificate_for_admin(
+        &state.db,
+        &state.document_keyring,
+        admin.admin_id,
+        case_id,
+    )
+    .await?;
+
+    use axum::body::Body;
+    use axum::http::{header, Response, StatusCode};
+
+    let content_disposition = format!("attachment; filename=\"{}\"", certificate.filename);
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, &certificate.content_type)
+        .header(header::CONTENT_DISPOSITION, content_disposition)
+        .header(header::CACHE_CONTROL, "no-cache, no-store, must-revalidate")
+        .body(Body::from(bytes))
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to build response: {}", e)))
+}
+
+/// Admin: Verify or reject a case's certificate
+async fn review_death_verification_case(
+    State(state): State<Arc<AppState>>,
+    Path(case_id): Path<Uuid>,
+    RequirePermission(admin, _): RequirePermission<perm::DeathReview>,
+    Json(req): Json<ReviewCaseRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let case = DeathVerificationCaseService::review(
+        &state.db,
+        &state.message_channels,
+        admin.admin_id,
+        case_id,
+        &req,
+    )
+    .await?;
+    Ok(Json(json!({ "status": "success", "data": case })))
+}
+
+/// Admin: Dismiss or uphold an objection
+async fn resolve_death_verification_objection(
+    State(state): State<Arc<AppState>>,
+    Path((case_id, objection_id)): Path<(Uuid, Uuid)>,
+    RequirePermission(admin, _): RequirePermission<perm::DeathReview>,
+    Json(req): Json<ResolveObjectionRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let case = DeathVerificationCaseService::resolve_objection(
+        &state.db,
+        admin.admin_id,
+        case_id,
+        objection_id,
+        &req,
+    )
+    .await?;
+    Ok(Json(json!({ "status": "success", "data": case })))
+}
+
+/// Admin: Approve cases whose objection window has ended now
+async fn finalize_death_verification_cases(
+    State(state): State<Arc<AppState>>,
+    RequirePermission(_admin, _): RequirePermission<perm::DeathReview>,
+) -> Result<Json<Value>, ApiError> {
+    let approved = DeathVerificationCaseService::finalize_due(&state.db).await?;
+    Ok(Json(
+        json!({ "status": "success", "data": { "approved": approved } }),
+    ))
+}
+
+async fn register_recipient_key(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<RegisterRecipientKeyRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let key = RecipientKeyService::register(&state.db, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": key })))
+}
+
+async fn get_recipient_key(
+    State(state): State<Arc<AppState>>,
+    Path(stellar_address): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let key = RecipientKeyService::get_active(&state.db, &stellar_address).await?;
+    Ok(Json(json!({ "status": "success", "data": key })))
+}
+
+async fn list_message_keys(
+    State(state): State<Arc<AppState>>,
+    RequirePermission(_admin, _): RequirePermission<perm::KeysRotate>,
+) -> Result<Json<Value>, ApiError> {
+    let keys = MessageKeyService::list_keys(&state.db).await?;
+    Ok(Json(
+        json!({ "status": "success", "data": keys, "count": keys.len() }),
+    ))
+}
+
+async fn rotate_message_key(
+    State(state): State<Arc<AppState>>,
+    RequirePermission(admin, _): RequirePermission<perm::KeysRotate>,
+) -> Result<Response, ApiError> {
+    match FourEyesService::submit(&state, admin.admin_id, HighRiskAction::RotateMessageKey).await? {
+        Submission::Executed(key) => Ok(Json(json!({
+            "status": "success",
+            "message": "Message encryption key rotated",
+            "data": key
+        }))
+        .into_response()),
+        Submission::Pending(pending) => Ok(pending_approval_response(pending)),
+    }
+}
+
+async fn process_legacy_message_delivery(
+    State(state): State<Arc<AppState>>,
+    RequirePermission(_admin, _): RequirePermission<perm::MessagesDeliver>,
+) -> Result<Json<Value>, ApiError> {
+    let delivery_service =
+        LegacyMessageDeliveryService::new(state.db.clone(), state.message_channels.clone());
+    let result = delivery_service.process_due_messages().await?;
+    Ok(Json(json!({ "status": "success", "data": result })))
+}
+
+/// Admin: Retry a message now instead of waiting for its backoff
+async fn deliver_legacy_message_now(
+    State(state): State<Arc<AppState>>,
+    Path(message_id): Path<Uuid>,
+    RequirePermission(_admin, _): RequirePermission<perm::MessagesDeliver>,
+) -> Result<Json<Value>, ApiError> {
+    let delivery_service =
+        LegacyMessageDeliveryService::new(state.db.clone(), state.message_channels.clone());
+    let message = delivery_service.deliver_now(message_id).await?;
+    Ok(Json(json!({ "status": "success", "data": message })))
+}
+
+async fn list_message_inbox(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let messages = MessageReceiptService::list_inbox(&state.db, user.user_id).await?;
+    Ok(Json(
+        json!({ "status": "success", "data": messages, "count": messages.len() }),
+    ))
+}
+
+async fn open_inbox_message(
+    State(state): State<Arc<AppState>>,
+    Path(delivery_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let message =
+        MessageReceiptService::open_inbox_message(&state.db, user.user_id, delivery_id).await?;
+    Ok(Json(json!({ "status": "success", "data": message })))
+}
+
+#[derive(Debug, serde::Deserialize, JsonSchema)]
+pub(crate) struct ReadConfirmationQuery {
+    token: String,
+}
+
+/// Read confirmation link sent with email and webhook deliveries
+async fn confirm_message_read(
+    State(state): State<Arc<AppState>>,
+    Path(delivery_id): Path<Uuid>,
+    Query(query): Query<ReadConfirmationQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let confirmation =
+        MessageReceiptService::confirm_read(&state.db, delivery_id, &query.token).await?;
+    Ok(Json(json!({ "status": "success", "data": confirmation })))
+}
+
+// ─── Message Access Audit Handlers ───────────────────────────────────────────
+
+async fn get_message_audit_logs(
+    State(state): State<Arc<AppState>>,
+    RequirePermission(_admin, _): RequirePermission<perm::AuditRead>,
+    Query(filters): Query<MessageAuditFilters>,
+) -> Result<Json<Value>, ApiError> {
+    let logs = MessageAccessAuditService::get_logs(&state.db, &filters).await?;
+    Ok(Json(
+        json!({ "status": "success", "data": logs, "count": logs.len() }),
+    ))
+}
+
+async fn get_message_audit_summary(
+    State(state): State<Arc<AppState>>,
+    RequirePermission(_admin, _): RequirePermission<perm::AuditRead>,
+) -> Result<Json<Value>, ApiError> {
+    let summary = MessageAccessAuditService::get_summary(&state.db).await?;
+    Ok(Json(json!({ "status": "success", "data": summary })))
+}
+
+async fn search_message_audit_logs(
+    State(state): State<Arc<AppState>>,
+    RequirePermission(_admin, _): RequirePermission<perm::AuditRead>,
+    Query(params): Query<SearchAuditParams>,
+) -> Result<Json<Value>, ApiError> {
+    let limit = params.limit.unwrap_or(100);
+    let logs = MessageAccessAuditService::search_logs(&state.db, &params.q, limit).await?;
+    Ok(Json(
+        json!({ "status": "success", "data": logs, "count": logs.len() }),
+    ))
+}
+
+#[derive(Debug, serde::Deserialize, JsonSchema)]
+pub(crate) struct SearchAuditParams {
+    q: String,
+    limit: Option<i64>,
+}
+
+async fn get_message_access_history(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(_user): AuthenticatedUser,
+    Path(message_id): Path<Uuid>,
+) -> Result<Json<Value>, ApiError> {
+    let logs = MessageAccessAuditService::get_message_logs(&state.db, message_id, None).await?;
+    Ok(Json(
+        json!({ "status": "success", "data": logs, "count": logs.len() }),
+    ))
+}
+
+async fn get_my_message_activity(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let activity = MessageAccessAuditService::get_user_activity(&state.db, user.user_id).await?;
+    Ok(Json(json!({ "status": "success", "data": activity })))
+}
+
+async fn get_all_due_for_claim_plans_user(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let plans = PlanService::get_all_due_for_claim_plans_for_user(&state.db, user.user_id).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": plans,
+        "count": plans.len()
+    })))
+}
+
+async fn get_all_due_for_claim_plans_admin(
+    State(state): State<Arc<AppState>>,
+    RequirePermission(_admin, _): RequirePermission<perm::PlansRead>,
+) -> Result<Json<Value>, ApiError> {
+    let plans = PlanService::get_all_due_for_claim_plans_admin(&state.db).await?;
+
+    Ok(Json(json!({
         "status": "success",
         "data": plans,
         "count": plans.len()
@@ -881,7 +1912,7 @@ async fn delete_emergency_contact(
 
 async fn create_emergency_access_grant(
     State(state): State<Arc<AppState>>,
-    AuthenticatedUser(user): AuthenticatedUser,
+    RequireStepUp(user): RequireStepUp,
     Json(req): Json<CreateEmergencyAccessGrantRequest>,
 ) -> Result<Json<Value>, ApiError> {
     let result = EmergencyAccessService::grant_access(&state.db, user.user_id, &req).await?;
@@ -973,14 +2004,141 @@ async fn list_active_emergency_sessions(
     ))
 }
 
-#[derive(serde::Deserialize)]
+// ─── Break-Glass Emergency Access Handlers ──────────────────────────────────
+
+async fn request_break_glass(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<BreakGlassRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let grant = BreakGlassService::request(
+        &state.db,
+        &state.message_channels,
+        user.user_id,
+        &user.email,
+        &req,
+    )
+    .await?;
+    Ok(Json(json!({ "status": "success", "data": grant })))
+}
+
+/// Requests on the caller's plans (`incoming`) and requests the caller has
+/// made as someone's emergency contact (`outgoing`).
+async fn list_break_glass_requests(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let incoming = BreakGlassService::list_for_owner(&state.db, user.user_id).await?;
+    let outgoing = BreakGlassService::list_for_requester(&state.db, user.user_id).await?;
+    Ok(Json(json!({
+        "status": "success",
+        "data": { "incoming": incoming, "outgoing": outgoing },
+    })))
+}
+
+async fn veto_break_glass(
+    State(state): State<Arc<AppState>>,
+    Path(grant_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<VetoBreakGlassRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let grant = BreakGlassService::veto(&state.db, user.user_id, grant_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": grant })))
+}
+
+async fn view_break_glass_plan(
+    State(state): State<Arc<AppState>>,
+    Path(grant_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let plan = BreakGlassService::view_plan(&state.db, user.user_id, grant_id).await?;
+    Ok(Json(json!({ "status": "success", "data": plan })))
+}
+
+async fn get_break_glass_settings(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let settings = BreakGlassService::settings(&state.db, user.user_id).await?;
+    Ok(Json(json!({ "status": "success", "data": settings })))
+}
+
+async fn update_break_glass_settings(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<BreakGlassSettings>,
+) -> Result<Json<Value>, ApiError> {
+    let settings = BreakGlassService::update_settings(&state.db, user.user_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": settings })))
+}
+
+async fn update_emergency_scope(
+    State(state): State<Arc<AppState>>,
+    Path(grant_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<ScopeRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let grant =
+        EmergencyScopeService::update_scope(&state.db, user.user_id, grant_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": grant })))
+}
+
+async fn list_emergency_documents(
+    State(state): State<Arc<AppState>>,
+    Path(grant_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let documents =
+        EmergencyScopeService::list_documents(&state.db, user.user_id, grant_id).await?;
+    Ok(Json(json!({
+        "status": "success",
+        "data": documents,
+        "count": documents.len(),
+    })))
+}
+
+async fn read_emergency_messages(
+    State(state): State<Arc<AppState>>,
+    Path(grant_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let messages = EmergencyScopeService::read_messages(&state.db, user.user_id, grant_id).await?;
+    Ok(Json(json!({
+        "status": "success",
+        "data": messages,
+        "count": messages.len(),
+    })))
+}
+
+async fn pay_emergency_bill(
+    State(state): State<Arc<AppState>>,
+    Path(grant_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<PayBillRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let payment = EmergencyScopeService::pay_bill(&state.db, user.user_id, grant_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": payment })))
+}
+
+async fn trigger_emergency_inheritance(
+    State(state): State<Arc<AppState>>,
+    Path(grant_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<TriggerInheritanceRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let trigger =
+        EmergencyScopeService::trigger_inheritance(&state.db, user.user_id, grant_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": trigger })))
+}
+
+#[derive(serde::Deserialize, JsonSchema)]
 pub struct KycUpdateRequest {
     pub user_id: Uuid,
 }
 
 async fn get_kyc_status(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::KycReview>,
     Path(user_id): Path<Uuid>,
 ) -> Result<Json<KycRecord>, ApiError> {
     let status = KycService::get_kyc_status(&state.db, user_id).await?;
@@ -989,22 +2147,21 @@ async fn get_kyc_status(
 
 async fn approve_kyc(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+    RequirePermission(admin, _): RequirePermission<perm::KycReview>,
     Json(payload): Json<KycUpdateRequest>,
-) -> Result<Json<KycRecord>, ApiError> {
-    let status = KycService::update_kyc_status(
-        &state.db,
-        admin.admin_id,
-        payload.user_id,
-        KycStatus::Approved,
-    )
-    .await?;
-    Ok(Json(status))
+) -> Result<Response, ApiError> {
+    let action = HighRiskAction::ApproveKyc {
+        user_id: payload.user_id,
+    };
+    match FourEyesService::submit(&state, admin.admin_id, action).await? {
+        Submission::Executed(status) => Ok(Json(status).into_response()),
+        Submission::Pending(pending) => Ok(pending_approval_response(pending)),
+    }
 }
 
 async fn reject_kyc(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+    RequirePermission(admin, _): RequirePermission<perm::KycReview>,
     Json(payload): Json<KycUpdateRequest>,
 ) -> Result<Json<KycRecord>, ApiError> {
     let status = KycService::update_kyc_status(
@@ -1143,8 +2300,8 @@ async fn get_lifecycle_loan(
 /// the principal the loan transitions to `repaid`.
 ///
 /// `POST /api/loans/lifecycle/:id/repay`
-#[derive(serde::Deserialize)]
-struct RepayRequest {
+#[derive(serde::Deserialize, JsonSchema)]
+pub(crate) struct RepayRequest {
     amount: rust_decimal::Decimal,
 }
 
@@ -1164,7 +2321,7 @@ async fn repay_lifecycle_loan(
 async fn liquidate_lifecycle_loan(
     State(state): State<Arc<AppState>>,
     Path(id): Path<Uuid>,
-    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+    RequirePermission(admin, _): RequirePermission<perm::LoansManage>,
 ) -> Result<Json<Value>, ApiError> {
     let record = LoanLifecycleService::liquidate_loan(&state.db, id, admin.admin_id).await?;
     Ok(Json(json!({ "status": "success", "data": record })))
@@ -1176,7 +2333,7 @@ async fn liquidate_lifecycle_loan(
 /// `POST /api/admin/loans/lifecycle/mark-overdue`
 async fn mark_overdue_loans(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::LoansManage>,
 ) -> Result<Json<Value>, ApiError> {
     let marked_ids = LoanLifecycleService::mark_overdue_loans(&state.db).await?;
     Ok(Json(json!({
@@ -1198,7 +2355,7 @@ use crate::emergency_access::{
 /// `POST /api/admin/emergency-access/grant`
 async fn grant_emergency_access(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+    RequirePermission(admin, _): RequirePermission<perm::EmergencyManage>,
     Json(req): Json<GrantEmergencyAccessRequest>,
 ) -> Result<Json<Value>, ApiError> {
     let response =
@@ -1214,7 +2371,7 @@ async fn grant_emergency_access(
 /// `POST /api/admin/emergency-access/revoke`
 async fn revoke_emergency_access(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+    RequirePermission(admin, _): RequirePermission<perm::EmergencyManage>,
     Json(req): Json<RevokeEmergencyAccessRequest>,
 ) -> Result<Json<Value>, ApiError> {
     let response =
@@ -1230,7 +2387,7 @@ async fn revoke_emergency_access(
 /// `GET /api/admin/emergency-access/all`
 async fn get_all_emergency_access(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::EmergencyManage>,
 ) -> Result<Json<Value>, ApiError> {
     let access_records = LegacyEmergencyAccessService::get_all_access(&state.db).await?;
     Ok(Json(json!({
@@ -1246,7 +2403,7 @@ async fn get_all_emergency_access(
 async fn get_plan_emergency_access(
     State(state): State<Arc<AppState>>,
     Path(plan_id): Path<Uuid>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::EmergencyManage>,
 ) -> Result<Json<Value>, ApiError> {
     let access_records =
         LegacyEmergencyAccessService::get_active_access_for_plan(&state.db, plan_id).await?;
@@ -1262,7 +2419,7 @@ async fn get_plan_emergency_access(
 /// `GET /api/admin/emergency-access/active-sessions`
 async fn get_active_emergency_sessions(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::EmergencyManage>,
 ) -> Result<Json<Value>, ApiError> {
     let active_sessions = LegacyEmergencyAccessService::get_active_sessions(&state.db).await?;
     Ok(Json(json!({
@@ -1276,34 +2433,48 @@ async fn get_active_emergency_sessions(
 
 async fn pause_plan(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+    RequirePermission(admin, _): RequirePermission<perm::RiskPause>,
     Json(req): Json<PausePlanRequest>,
-) -> Result<Json<Value>, ApiError> {
-    let result = EmergencyAdminService::pause_plan(&state.db, admin.admin_id, &req).await?;
-    Ok(Json(json!({ "status": "success", "data": result })))
+) -> Result<Response, ApiError> {
+    match FourEyesService::submit(&state, admin.admin_id, HighRiskAction::PausePlan(req)).await? {
+        Submission::Executed(result) => {
+            Ok(Json(json!({ "status": "success", "data": result })).into_response())
+        }
+        Submission::Pending(pending) => Ok(pending_approval_response(pending)),
+    }
 }
 
 async fn unpause_plan(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+    RequirePermission(admin, _): RequirePermission<perm::RiskPause>,
     Json(req): Json<UnpausePlanRequest>,
-) -> Result<Json<Value>, ApiError> {
-    let result = EmergencyAdminService::unpause_plan(&state.db, admin.admin_id, &req).await?;
-    Ok(Json(json!({ "status": "success", "data": result })))
+) -> Result<Response, ApiError> {
+    match FourEyesService::submit(&state, admin.admin_id, HighRiskAction::UnpausePlan(req)).await? {
+        Submission::Executed(result) => {
+            Ok(Json(json!({ "status": "success", "data": result })).into_response())
+        }
+        Submission::Pending(pending) => Ok(pending_approval_response(pending)),
+    }
 }
 
 async fn set_risk_override(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+    RequirePermission(admin, _): RequirePermission<perm::RiskPause>,
     Json(req): Json<RiskOverrideRequest>,
-) -> Result<Json<Value>, ApiError> {
-    let result = EmergencyAdminService::set_risk_override(&state.db, admin.admin_id, &req).await?;
-    Ok(Json(json!({ "status": "success", "data": result })))
+) -> Result<Response, ApiError> {
+    match FourEyesService::submit(&state, admin.admin_id, HighRiskAction::SetRiskOverride(req))
+        .await?
+    {
+        Submission::Executed(result) => {
+            Ok(Json(json!({ "status": "success", "data": result })).into_response())
+        }
+        Submission::Pending(pending) => Ok(pending_approval_response(pending)),
+    }
 }
 
 async fn get_paused_plans(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::RiskPause>,
 ) -> Result<Json<Value>, ApiError> {
     let plans = EmergencyAdminService::get_paused_plans(&state.db).await?;
     Ok(Json(
@@ -1313,7 +2484,7 @@ async fn get_paused_plans(
 
 async fn get_risk_override_plans(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::RiskPause>,
 ) -> Result<Json<Value>, ApiError> {
     let plans = EmergencyAdminService::get_risk_override_plans(&state.db).await?;
     Ok(Json(
@@ -1322,13 +2493,13 @@ async fn get_risk_override_plans(
 }
 // Stress Testing Endpoints
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, JsonSchema)]
 pub struct PriceCrashRequest {
     pub asset_code: String,
     pub drop_percentage: rust_decimal::Decimal,
 }
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, JsonSchema)]
 pub struct LiquidityDrainRequest {
     pub asset_code: String,
     pub amount: rust_decimal::Decimal,
@@ -1336,7 +2507,7 @@ pub struct LiquidityDrainRequest {
 
 async fn simulate_price_crash(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::RiskSimulate>,
     Json(req): Json<PriceCrashRequest>,
 ) -> Result<Json<Value>, ApiError> {
     state
@@ -1350,7 +2521,7 @@ async fn simulate_price_crash(
 
 async fn simulate_mass_default(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::RiskSimulate>,
 ) -> Result<Json<Value>, ApiError> {
     state.stress_testing_engine.simulate_mass_default().await?;
     Ok(Json(
@@ -1360,7 +2531,7 @@ async fn simulate_mass_default(
 
 async fn simulate_liquidity_drain(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::RiskSimulate>,
     Json(req): Json<LiquidityDrainRequest>,
 ) -> Result<Json<Value>, ApiError> {
     state
@@ -1376,7 +2547,7 @@ async fn simulate_liquidity_drain(
 
 async fn create_governance_proposal(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+    RequirePermission(admin, _): RequirePermission<perm::GovernanceManage>,
     Json(req): Json<CreateProposalRequest>,
 ) -> Result<Json<Proposal>, ApiError> {
     let proposal = GovernanceService::create_proposal(&state.db, admin.admin_id, &req).await?;
@@ -1404,7 +2575,7 @@ async fn vote_on_governance_proposal(
 
 async fn update_protocol_parameter(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+    RequirePermission(admin, _): RequirePermission<perm::GovernanceManage>,
     Json(req): Json<ParameterUpdateRequest>,
 ) -> Result<Json<Value>, ApiError> {
     GovernanceService::update_parameter(&state.db, admin.admin_id, &req).await?;
@@ -1415,8 +2586,8 @@ async fn update_protocol_parameter(
 
 // ─── Will PDF & Template Engine Handlers (Tasks 1 & 2) ───────────────────────
 
-#[derive(serde::Deserialize)]
-struct GenerateWillRequest {
+#[derive(serde::Deserialize, JsonSchema)]
+pub(crate) struct GenerateWillRequest {
     owner_name: String,
     owner_wallet: String,
     vault_id: String,
@@ -1520,16 +2691,21 @@ async fn finalize_will_version(
     Path((plan_id, version_number)): Path<(Uuid, u32)>,
     AuthenticatedUser(user): AuthenticatedUser,
 ) -> Result<Json<Value>, ApiError> {
-    let version =
-        WillVersionService::finalize_version(&state.db, plan_id, user.user_id, version_number)
-            .await?;
+    let version = WillVersionService::finalize_version(
+        &state.db,
+        state.timestamp_client.as_deref(),
+        plan_id,
+        user.user_id,
+        version_number,
+    )
+    .await?;
     Ok(Json(json!({ "status": "success", "data": version })))
 }
 
 // ─── Beneficiary Sync Handler (Task 3) ───────────────────────────────────────
 
-#[derive(serde::Deserialize)]
-struct SyncBeneficiariesRequest {
+#[derive(serde::Deserialize, JsonSchema)]
+pub(crate) struct SyncBeneficiariesRequest {
     document_beneficiaries: Vec<DocumentBeneficiary>,
 }
 
@@ -1559,7 +2735,7 @@ async fn create_signing_challenge(
     if req.wallet_address.is_empty() {
         req.wallet_address = user.email.clone();
     }
-    let challenge = WillSignatureService::create_challenge(&state.db, &req).await?;
+    let challenge = WillSignatureService::create_challenge(&state.db, user.user_id, &req).await?;
     Ok(Json(json!({ "status": "success", "data": challenge })))
 }
 
@@ -1585,6 +2761,37 @@ async fn get_will_signatures(
     ))
 }
 
+// -- Joint and Mirror Will Handlers -------------------------------------------
+
+async fn configure_will_testators(
+    State(state): State<Arc<AppState>>,
+    Path(document_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<ConfigureTestatorsRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let status = JointWillService::configure(&state.db, user.user_id, document_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": status })))
+}
+
+async fn get_will_testators(
+    State(state): State<Arc<AppState>>,
+    Path(document_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let status = JointWillService::status(&state.db, user.user_id, document_id).await?;
+    Ok(Json(json!({ "status": "success", "data": status })))
+}
+
+async fn revoke_will_document(
+    State(state): State<Arc<AppState>>,
+    Path(document_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<RevokeWillRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let status = JointWillService::revoke(&state.db, user.user_id, document_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": status })))
+}
+
 // -- Encrypted Document Storage Handlers (Issue #328) -------------------------
 
 async fn encrypt_document(
@@ -1597,8 +2804,14 @@ async fn encrypt_document(
         .decode(&doc.pdf_base64)
         .map_err(|e| ApiError::Internal(anyhow::anyhow!("Base64 decode error: {e}")))?;
 
-    DocumentStorageService::store_encrypted(&state.db, user.user_id, document_id, &content_bytes)
-        .await?;
+    DocumentStorageService::store_encrypted(
+        &state.db,
+        &state.document_keyring,
+        user.user_id,
+        document_id,
+        &content_bytes,
+    )
+    .await?;
 
     Ok(Json(json!({
         "status": "success",
@@ -1612,8 +2825,13 @@ async fn decrypt_document(
     Path(document_id): Path<Uuid>,
     AuthenticatedUser(user): AuthenticatedUser,
 ) -> Result<Json<Value>, ApiError> {
-    let plaintext =
-        DocumentStorageService::retrieve_decrypted(&state.db, user.user_id, document_id).await?;
+    let plaintext = DocumentStorageService::retrieve_decrypted(
+        &state.db,
+        &state.document_keyring,
+        user.user_id,
+        document_id,
+    )
+    .await?;
     let encoded = base64::engine::general_purpose::STANDARD.encode(&plaintext);
 
     Ok(Json(json!({
@@ -1628,8 +2846,13 @@ async fn create_document_backup(
     Path(document_id): Path<Uuid>,
     AuthenticatedUser(user): AuthenticatedUser,
 ) -> Result<Json<Value>, ApiError> {
-    let backup =
-        DocumentStorageService::create_backup(&state.db, user.user_id, document_id).await?;
+    let backup = DocumentStorageService::create_backup(
+        &state.db,
+        state.object_store.as_ref(),
+        user.user_id,
+        document_id,
+    )
+    .await?;
     Ok(Json(json!({ "status": "success", "data": backup })))
 }
 
@@ -1645,20 +2868,41 @@ async fn list_document_backups(
     ))
 }
 
+async fn rewrap_document_keys(
+    State(state): State<Arc<AppState>>,
+    RequirePermission(_admin, _): RequirePermission<perm::KeysRotate>,
+) -> Result<Json<Value>, ApiError> {
+    let rotation_service =
+        DocumentKeyRotationService::new(state.db.clone(), state.document_keyring.clone());
+    let result = rotation_service.rewrap_outdated_keys().await?;
+    Ok(Json(json!({ "status": "success", "data": result })))
+}
+
 // -- Will Compliance Validation Handlers (Issue #330) --
 
-#[derive(serde::Deserialize)]
-struct ValidateWillRequest {
+#[derive(serde::Deserialize, JsonSchema)]
+pub(crate) struct ValidateWillRequest {
     #[serde(flatten)]
     input: WillDocumentInput,
     witness_count: u32,
+    /// Generated will document whose notarization counts towards the check
+    #[serde(default)]
+    document_id: Option<Uuid>,
 }
 
 async fn validate_will_compliance(
-    AuthenticatedUser(_user): AuthenticatedUser,
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
     Json(req): Json<ValidateWillRequest>,
 ) -> Result<Json<Value>, ApiError> {
-    let result: ValidationResult = WillComplianceService::validate(&req.input, req.witness_count);
+    let notarized = match req.document_id {
+        Some(document_id) => {
+            NotarizationService::is_notarized(&state.db, user.user_id, document_id).await?
+        }
+        None => false,
+    };
+    let result: ValidationResult =
+        WillComplianceService::validate(&req.input, req.witness_count, notarized);
     Ok(Json(json!({ "status": "success", "data": result })))
 }
 
@@ -1695,6 +2939,22 @@ async fn invite_witness(
         req.email,
     )
     .await?;
+
+    // Witnesses without a wallet sign through an emailed link. A failed send
+    // leaves the witness pending; the owner can resend the invitation.
+    if record.wallet_address.is_none() {
+        if let Err(e) = WitnessInvitationService::send(
+            &state.db,
+            &state.message_channels,
+            &WitnessLinkSigner::new(state.config.jwt_secret.as_bytes()),
+            user.user_id,
+            record.id,
+        )
+        .await
+        {
+            tracing::warn!("Failed to email invitation to witness {}: {}", record.id, e);
+        }
+    }
     Ok(Json(json!({ "status": "success", "data": record })))
 }
 
@@ -1741,6 +3001,179 @@ async fn decline_witness(
     Ok(Json(json!({ "status": "success", "data": record })))
 }
 
+async fn send_witness_invitation(
+    State(state): State<Arc<AppState>>,
+    Path(witness_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let invitation = WitnessInvitationService::send(
+        &state.db,
+        &state.message_channels,
+        &WitnessLinkSigner::new(state.config.jwt_secret.as_bytes()),
+        user.user_id,
+        witness_id,
+    )
+    .await?;
+    Ok(Json(json!({ "status": "success", "data": invitation })))
+}
+
+async fn open_witness_invitation(
+    State(state): State<Arc<AppState>>,
+    Path(token): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let view = WitnessInvitationService::open(
+        &state.db,
+        &WitnessLinkSigner::new(state.config.jwt_secret.as_bytes()),
+        &token,
+    )
+    .await?;
+    Ok(Json(json!({ "status": "success", "data": view })))
+}
+
+async fn attest_as_witness(
+    State(state): State<Arc<AppState>>,
+    Path(token): Path<String>,
+    headers: axum::http::HeaderMap,
+    Json(req): Json<WitnessAttestationRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let attestation = WitnessInvitationService::attest(
+        &state.db,
+        &WitnessLinkSigner::new(state.config.jwt_secret.as_bytes()),
+        &token,
+        &req,
+        &client_evidence(&headers),
+    )
+    .await?;
+    Ok(Json(json!({ "status": "success", "data": attestation })))
+}
+
+async fn list_witness_attestations(
+    State(state): State<Arc<AppState>>,
+    Path(document_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let attestations =
+        WitnessInvitationService::list_for_document(&state.db, user.user_id, document_id).await?;
+    Ok(Json(
+        json!({ "status": "success", "data": attestations, "count": attestations.len() }),
+    ))
+}
+
+/// Client IP and user agent as reported by the proxy in front of the API.
+pub(crate) fn client_evidence(headers: &axum::http::HeaderMap) -> ClientEvidence {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    };
+    let ip_address = header("x-forwarded-for")
+        .and_then(|v| v.split(',').next())
+        .or_else(|| header("x-real-ip"))
+        .and_then(|ip| ip.trim().parse::<std::net::IpAddr>().ok())
+        .map(|ip| ip.to_string());
+    ClientEvidence {
+        ip_address,
+        user_agent: header("user-agent").map(str::to_string),
+    }
+}
+
+// -- Remote Notarization -----------------------------------------------------
+
+async fn request_notarization(
+    State(state): State<Arc<AppState>>,
+    Path(document_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<RequestNotarizationRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let session = NotarizationService::request(&state.db, user.user_id, document_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": session })))
+}
+
+async fn list_document_notarizations(
+    State(state): State<Arc<AppState>>,
+    Path(document_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let sessions =
+        NotarizationService::list_for_document(&state.db, user.user_id, document_id).await?;
+    Ok(Json(
+        json!({ "status": "success", "data": sessions, "count": sessions.len() }),
+    ))
+}
+
+async fn list_notary_sessions(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let notary = NotaryService::for_user(&state.db, user.user_id).await?;
+    let sessions = NotarizationService::list_for_notary(&state.db, notary.id).await?;
+    Ok(Json(
+        json!({ "status": "success", "data": sessions, "count": sessions.len() }),
+    ))
+}
+
+async fn verify_notarization_identity(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<VerifyIdentityRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let session =
+        NotarizationService::verify_identity(&state.db, user.user_id, session_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": session })))
+}
+
+async fn seal_notarization(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<SealRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let session = NotarizationService::seal(&state.db, user.user_id, session_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": session })))
+}
+
+async fn get_notary_journal(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let notary = NotaryService::for_user(&state.db, user.user_id).await?;
+    let entries = NotaryService::journal(&state.db, notary.id).await?;
+    Ok(Json(
+        json!({ "status": "success", "data": entries, "count": entries.len() }),
+    ))
+}
+
+async fn register_notary(
+    State(state): State<Arc<AppState>>,
+    RequirePermission(admin, _): RequirePermission<perm::NotaryManage>,
+    Json(req): Json<RegisterNotaryRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let notary = NotaryService::register(&state.db, admin.admin_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": notary })))
+}
+
+async fn list_notaries(
+    State(state): State<Arc<AppState>>,
+    RequirePermission(_admin, _): RequirePermission<perm::NotaryManage>,
+) -> Result<Json<Value>, ApiError> {
+    let notaries = NotaryService::list(&state.db).await?;
+    Ok(Json(
+        json!({ "status": "success", "data": notaries, "count": notaries.len() }),
+    ))
+}
+
+async fn verify_notary_journal(
+    State(state): State<Arc<AppState>>,
+    Path(notary_id): Path<Uuid>,
+    RequirePermission(_admin, _): RequirePermission<perm::NotaryManage>,
+) -> Result<Json<Value>, ApiError> {
+    let verification = NotaryService::verify_journal(&state.db, notary_id).await?;
+    Ok(Json(json!({ "status": "success", "data": verification })))
+}
+
 // -- Legal Document Integrity Check (Issue #332) -------------------------------
 
 async fn verify_document_integrity(
@@ -1758,8 +3191,8 @@ async fn verify_document_integrity(
     Ok(Json(json!({ "status": "success", "data": result })))
 }
 
-#[derive(serde::Deserialize)]
-struct VerifyHashRequest {
+#[derive(serde::Deserialize, JsonSchema)]
+pub(crate) struct VerifyHashRequest {
     hash: String,
     version: Option<u32>,
 }
@@ -1779,8 +3212,8 @@ async fn verify_document_hash(
     Ok(Json(json!({ "status": "success", "data": result })))
 }
 
-#[derive(serde::Deserialize)]
-struct VerifyContentRequest {
+#[derive(serde::Deserialize, JsonSchema)]
+pub(crate) struct VerifyContentRequest {
     content: String,
     version: Option<u32>,
 }
@@ -1962,7 +3395,7 @@ use crate::will_audit::{AuditLogFilters, WillAuditService};
 /// `GET /api/admin/will/audit/logs?document_id=...&plan_id=...&user_id=...&event_type=...&start_date=...&end_date=...&limit=...&offset=...`
 async fn get_admin_audit_logs(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::AuditRead>,
     Query(filters): Query<AuditLogFilters>,
 ) -> Result<Json<Value>, ApiError> {
     let logs = WillAuditService::get_audit_logs(&state.db, &filters).await?;
@@ -1978,7 +3411,7 @@ async fn get_admin_audit_logs(
 /// `GET /api/admin/will/audit/statistics`
 async fn get_admin_audit_statistics(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::AuditRead>,
 ) -> Result<Json<Value>, ApiError> {
     let stats = WillAuditService::get_admin_statistics(&state.db).await?;
     Ok(Json(json!({
@@ -1992,7 +3425,7 @@ async fn get_admin_audit_statistics(
 /// `GET /api/admin/will/audit/event-types`
 async fn get_admin_event_types(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::AuditRead>,
 ) -> Result<Json<Value>, ApiError> {
     let event_types = WillAuditService::get_event_types(&state.db).await?;
     Ok(Json(json!({
@@ -2005,15 +3438,15 @@ async fn get_admin_event_types(
 /// Admin: Search audit logs
 ///
 /// `GET /api/admin/will/audit/search?q=...&limit=...`
-#[derive(serde::Deserialize)]
-struct SearchQuery {
+#[derive(serde::Deserialize, JsonSchema)]
+pub(crate) struct SearchQuery {
     q: String,
     limit: Option<i64>,
 }
 
 async fn search_admin_audit_logs(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::AuditRead>,
     Query(query): Query<SearchQuery>,
 ) -> Result<Json<Value>, ApiError> {
     let limit = query.limit.unwrap_or(100);
@@ -2031,7 +3464,7 @@ async fn search_admin_audit_logs(
 async fn get_user_audit_activity(
     State(state): State<Arc<AppState>>,
     Path(user_id): Path<Uuid>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::AuditRead>,
 ) -> Result<Json<Value>, ApiError> {
     let activity = WillAuditService::get_user_activity_summary(&state.db, user_id).await?;
     Ok(Json(json!({
@@ -2069,6 +3502,25 @@ async fn get_my_audit_activity(
     })))
 }
 
+// -- Tamper-Evident Audit Logs -------------------------------------------------
+
+/// Admin: Prove an audit entry is unaltered and included in an anchored root
+///
+/// `GET /api/admin/audit/:stream/:entry_id/verify` where `stream` is one of
+/// `actions`, `will_events`, `message_access` or `emergency_access`.
+async fn verify_audit_entry(
+    State(state): State<Arc<AppState>>,
+    Path((stream, entry_id)): Path<(String, Uuid)>,
+    RequirePermission(_admin, _): RequirePermission<perm::AuditRead>,
+) -> Result<Json<Value>, ApiError> {
+    let stream: AuditStream = stream.parse()?;
+    let verification = AuditChainService::verify(&state.db, stream, entry_id).await?;
+    Ok(Json(json!({
+        "status": "success",
+        "data": verification
+    })))
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Insurance Fund Monitoring (Issue #249)
 // ─────────────────────────────────────────────────────────────────────────────
@@ -2078,7 +3530,7 @@ async fn get_my_audit_activity(
 /// `GET /api/admin/insurance-fund`
 async fn get_insurance_fund_dashboard(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::InsuranceRead>,
 ) -> Result<Json<Value>, ApiError> {
     let fund = state.insurance_fund_service.get_primary_fund().await?;
     let dashboard = state.insurance_fund_service.get_dashboard(fund.id).await?;
@@ -2094,7 +3546,7 @@ async fn get_insurance_fund_dashboard(
 /// `GET /api/admin/insurance-funds`
 async fn get_all_insurance_funds(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::InsuranceRead>,
 ) -> Result<Json<Value>, ApiError> {
     let funds = state.insurance_fund_service.get_all_funds().await?;
 
@@ -2111,7 +3563,7 @@ async fn get_all_insurance_funds(
 async fn get_insurance_fund(
     State(state): State<Arc<AppState>>,
     Path(fund_id): Path<Uuid>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::InsuranceRead>,
 ) -> Result<Json<Value>, ApiError> {
     let fund = state.insurance_fund_service.get_fund_by_id(fund_id).await?;
 
@@ -2124,8 +3576,8 @@ async fn get_insurance_fund(
 /// Admin: Get insurance fund metrics history
 ///
 /// `GET /api/admin/insurance-fund/:fund_id/metrics?days=30`
-#[derive(serde::Deserialize)]
-struct MetricsHistoryQuery {
+#[derive(serde::Deserialize, JsonSchema)]
+pub(crate) struct MetricsHistoryQuery {
     days: Option<i64>,
 }
 
@@ -2133,7 +3585,7 @@ async fn get_insurance_fund_metrics_history(
     State(state): State<Arc<AppState>>,
     Path(fund_id): Path<Uuid>,
     Query(query): Query<MetricsHistoryQuery>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::InsuranceRead>,
 ) -> Result<Json<Value>, ApiError> {
     let days = query.days.unwrap_or(30);
     let history = state
@@ -2151,8 +3603,8 @@ async fn get_insurance_fund_metrics_history(
 /// Admin: Get insurance fund transactions
 ///
 /// `GET /api/admin/insurance-fund/:fund_id/transactions?limit=50`
-#[derive(serde::Deserialize)]
-struct TransactionsQuery {
+#[derive(serde::Deserialize, JsonSchema)]
+pub(crate) struct TransactionsQuery {
     limit: Option<i64>,
 }
 
@@ -2160,7 +3612,7 @@ async fn get_insurance_fund_transactions(
     State(state): State<Arc<AppState>>,
     Path(fund_id): Path<Uuid>,
     Query(query): Query<TransactionsQuery>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::InsuranceRead>,
 ) -> Result<Json<Value>, ApiError> {
     let limit = query.limit.unwrap_or(50);
 
@@ -2183,8 +3635,8 @@ async fn get_insurance_fund_transactions(
 /// Admin: Get insurance claims
 ///
 /// `GET /api/admin/insurance-fund/:fund_id/claims?status=pending&limit=50`
-#[derive(serde::Deserialize)]
-struct ClaimsQuery {
+#[derive(serde::Deserialize, JsonSchema)]
+pub(crate) struct ClaimsQuery {
     status: Option<String>,
     limit: Option<i64>,
 }
@@ -2193,7 +3645,7 @@ async fn get_insurance_claims(
     State(state): State<Arc<AppState>>,
     Path(fund_id): Path<Uuid>,
     Query(query): Query<ClaimsQuery>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::InsuranceRead>,
 ) -> Result<Json<Value>, ApiError> {
     let limit = query.limit.unwrap_or(50);
 
@@ -2230,7 +3682,7 @@ async fn get_insurance_claims(
 async fn get_insurance_claim(
     State(state): State<Arc<AppState>>,
     Path(claim_id): Path<Uuid>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::InsuranceRead>,
 ) -> Result<Json<Value>, ApiError> {
     let claim = sqlx::query_as::<_, crate::insurance_fund::InsuranceClaim>(
         "SELECT * FROM insurance_claims WHERE id = $1",
@@ -2253,7 +3705,7 @@ async fn get_insurance_claim(
 async fn create_insurance_claim(
     State(state): State<Arc<AppState>>,
     Path(fund_id): Path<Uuid>,
-    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+    RequirePermission(admin, _): RequirePermission<perm::InsurancePayout>,
     Json(req): Json<CreateInsuranceClaimRequest>,
 ) -> Result<Json<Value>, ApiError> {
     let claim = state
@@ -2273,7 +3725,7 @@ async fn create_insurance_claim(
 async fn process_insurance_claim(
     State(state): State<Arc<AppState>>,
     Path(claim_id): Path<Uuid>,
-    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+    RequirePermission(admin, _): RequirePermission<perm::InsurancePayout>,
     Json(req): Json<ProcessInsuranceClaimRequest>,
 ) -> Result<Json<Value>, ApiError> {
     let claim = state
@@ -2293,56 +3745,90 @@ async fn process_insurance_claim(
 async fn payout_insurance_claim(
     State(state): State<Arc<AppState>>,
     Path(claim_id): Path<Uuid>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
-) -> Result<Json<Value>, ApiError> {
-    state.insurance_fund_service.payout_claim(claim_id).await?;
-
-    Ok(Json(json!({
-        "status": "success",
-        "message": "Claim paid out successfully"
-    })))
+    RequirePermission(admin, _): RequirePermission<perm::InsurancePayout>,
+) -> Result<Response, ApiError> {
+    let action = HighRiskAction::PayoutInsuranceClaim { claim_id };
+    match FourEyesService::submit(&state, admin.admin_id, action).await? {
+        Submission::Executed(_) => Ok(Json(json!({
+            "status": "success",
+            "message": "Claim paid out successfully"
+        }))
+        .into_response()),
+        Submission::Pending(pending) => Ok(pending_approval_response(pending)),
+    }
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Legacy Content Handlers (Issue #XXX)
 // ─────────────────────────────────────────────────────────────────────────────
 
-#[derive(serde::Deserialize)]
-pub struct UploadContentRequest {
-    pub original_filename: String,
-    pub content_type: String,
-    pub description: Option<String>,
-}
-
 /// User: Upload legacy content
 ///
-/// `POST /api/content/upload`
+/// `POST /api/content/upload` (multipart/form-data)
+///
+/// Fields: `file` (required, with filename and content type) and an optional
+/// `description`. The file is stored under its content-addressed key.
 async fn upload_legacy_content(
     State(state): State<Arc<AppState>>,
     AuthenticatedUser(user): AuthenticatedUser,
-    Json(req): Json<UploadContentRequest>,
+    mut multipart: axum::extract::Multipart,
 ) -> Result<Json<Value>, ApiError> {
-    // Validate the content type
-    LegacyContentService::validate_content_type(&req.content_type)?;
+    let mut file: Option<(String, String, axum::body::Bytes)> = None;
+    let mut description: Option<String> = None;
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("Invalid multipart body: {}", e)))?
+    {
+        match field.name() {
+            Some("file") => {
+                let original_filename = field
+                    .file_name()
+                    .map(str::to_string)
+                    .ok_or_else(|| ApiError::BadRequest("File name is required".to_string()))?;
+                let content_type = field
+                    .content_type()
+                    .map(str::to_string)
+                    .ok_or_else(|| ApiError::BadRequest("Content type is required".to_string()))?;
+                // Validate the content type before buffering the body
+                LegacyContentService::validate_content_type(&content_type)?;
+                let bytes = field
+                    .bytes()
+                    .await
+                    .map_err(|e| ApiError::BadRequest(format!("Failed to read file: {}", e)))?;
+                file = Some((original_filename, content_type, bytes));
+            }
+            Some("description") => {
+                description =
+                    Some(field.text().await.map_err(|e| {
+                        ApiError::BadRequest(format!("Invalid description: {}", e))
+                    })?);
+            }
+            _ => {}
+        }
+    }
+
+    let (original_filename, content_type, bytes) =
+        file.ok_or_else(|| ApiError::BadRequest("Missing file field".to_string()))?;
+    LegacyContentService::validate_file_size(bytes.len())?;
 
-    // For now, we'll create a metadata record. Full implementation would handle file upload.
     let metadata = crate::legacy_content::UploadMetadata {
-        original_filename: req.original_filename,
-        content_type: req.content_type.clone(),
-        file_size: 0, // Would be set from actual file upload
-        description: req.description,
+        original_filename,
+        content_type,
+        file_size: bytes.len(),
+        description,
     };
 
-    let storage_path =
-        LegacyContentService::generate_storage_path(user.user_id, &metadata.original_filename);
-    let file_hash = "pending".to_string(); // Would be calculated from file content
+    let object =
+        ObjectStorageService::store(&state.db, state.object_store.as_ref(), &bytes).await?;
 
     let content = LegacyContentService::create_content_record(
         &state.db,
         user.user_id,
         &metadata,
-        storage_path,
-        file_hash,
+        object.object_key,
+        object.sha256,
     )
     .await?;
 
@@ -2410,9 +3896,15 @@ async fn download_content(
 ) -> Result<axum::response::Response, ApiError> {
     let content =
         LegacyContentService::get_content_by_id(&state.db, content_id, user.user_id).await?;
+    ensure_deliverable(&content.inspection_status, content.inspected_at)?;
+
+    let bytes = ObjectStorageService::fetch_verified(
+        state.object_store.as_ref(),
+        &content.storage_path,
+        &content.file_hash,
+    )
+    .await?;
 
-    // In a full implementation, this would read from the FileStorageService
-    // For now, return a placeholder response
     use axum::body::Body;
     use axum::http::{header, Response, StatusCode};
 
@@ -2423,7 +3915,35 @@ async fn download_content(
         .header(header::CONTENT_TYPE, &content.content_type)
         .header(header::CONTENT_DISPOSITION, content_disposition)
         .header(header::CACHE_CONTROL, "no-cache, no-store, must-revalidate")
-        .body(Body::empty())
+        .body(Body::from(bytes))
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to build response: {}", e)))
+}
+
+/// User: Download the preview thumbnail generated during inspection
+///
+/// `GET /api/content/:content_id/thumbnail`
+async fn download_thumbnail(
+    State(state): State<Arc<AppState>>,
+    Path(content_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<axum::response::Response, ApiError> {
+    let content =
+        LegacyContentService::get_content_by_id(&state.db, content_id, user.user_id).await?;
+    ensure_deliverable(&content.inspection_status, content.inspected_at)?;
+    let thumbnail_key = content
+        .thumbnail_key
+        .ok_or_else(|| ApiError::NotFound("Content has no thumbnail".to_string()))?;
+
+    let bytes = state.object_store.get(&thumbnail_key).await?;
+
+    use axum::body::Body;
+    use axum::http::{header, Response, StatusCode};
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "image/jpeg")
+        .header(header::CACHE_CONTROL, "private, max-age=3600")
+        .body(Body::from(bytes))
         .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to build response: {}", e)))
 }
 
@@ -2441,6 +3961,137 @@ async fn get_storage_stats(
     })))
 }
 
+/// Progress view of an upload session, with the `Upload-Offset` header a
+/// tus-style client resumes from.
+fn upload_session_response(
+    session: UploadSession,
+) -> (
+    [(axum::http::HeaderName, axum::http::HeaderValue); 1],
+    Json<Value>,
+) {
+    let progress_percent = session.progress_percent();
+    (
+        [(
+            axum::http::HeaderName::from_static("upload-offset"),
+            axum::http::HeaderValue::from(session.upload_offset),
+        )],
+        Json(json!({
+            "status": "success",
+            "data": {
+                "session": session,
+                "progress_percent": progress_percent
+            }
+        })),
+    )
+}
+
+/// User: Create a resumable upload session
+///
+/// `POST /api/content/uploads`
+async fn create_upload_session(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<CreateUploadSessionRequest>,
+) -> Result<impl axum::response::IntoResponse, ApiError> {
+    let session = ResumableUploadService::create_session(&state.db, user.user_id, &req).await?;
+    Ok(upload_session_response(session))
+}
+
+/// User: Query upload progress
+///
+/// `GET /api/content/uploads/:upload_id`
+async fn get_upload_session(
+    State(state): State<Arc<AppState>>,
+    Path(upload_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<impl axum::response::IntoResponse, ApiError> {
+    let session = ResumableUploadService::get_session(&state.db, user.user_id, upload_id).await?;
+    Ok(upload_session_response(session))
+}
+
+/// User: Upload a chunk
+///
+/// `PATCH /api/content/uploads/:upload_id`
+///
+/// Headers: `Upload-Offset` (byte offset of this chunk) and `Upload-Checksum`
+/// (`sha256 <base64 digest>` of this chunk). Body: the raw chunk bytes.
+async fn upload_chunk(
+    State(state): State<Arc<AppState>>,
+    Path(upload_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    headers: axum::http::HeaderMap,
+    body: axum::body::Bytes,
+) -> Result<impl axum::response::IntoResponse, ApiError> {
+    let offset = headers
+        .get("upload-offset")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<i64>().ok())
+        .ok_or_else(|| {
+            ApiError::BadRequest("Missing or invalid Upload-Offset header".to_string())
+        })?;
+    let checksum = headers
+        .get("upload-checksum")
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| ApiError::BadRequest("Missing Upload-Checksum header".to_string()))?;
+    let checksum = crate::resumable_upload::parse_upload_checksum(checksum)?;
+
+    let session = ResumableUploadService::append_chunk(
+        &state.db,
+        state.object_store.as_ref(),
+        user.user_id,
+        upload_id,
+        offset,
+        &checksum,
+        &body,
+    )
+    .await?;
+    Ok(upload_session_response(session))
+}
+
+/// User: Finalize a resumable upload
+///
+/// `POST /api/content/uploads/:upload_id/finalize`
+async fn finalize_upload_session(
+    State(state): State<Arc<AppState>>,
+    Path(upload_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<FinalizeUploadRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let content = ResumableUploadService::finalize(
+        &state.db,
+        state.object_store.as_ref(),
+        user.user_id,
+        upload_id,
+        &req.sha256,
+    )
+    .await?;
+    Ok(Json(json!({
+        "status": "success",
+        "data": content
+    })))
+}
+
+/// User: Abort a resumable upload
+///
+/// `DELETE /api/content/uploads/:upload_id`
+async fn abort_upload_session(
+    State(state): State<Arc<AppState>>,
+    Path(upload_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    ResumableUploadService::abort(
+        &state.db,
+        state.object_store.as_ref(),
+        user.user_id,
+        upload_id,
+    )
+    .await?;
+    Ok(Json(json!({
+        "status": "success",
+        "message": "Upload aborted"
+    })))
+}
+
 /// User: Get notifications
 async fn get_notifications(
     State(state): State<Arc<AppState>>,
@@ -2457,7 +4108,7 @@ async fn get_notifications(
 /// Admin: Get audit logs
 async fn get_admin_logs(
     State(state): State<Arc<AppState>>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::AuditRead>,
 ) -> Result<Json<Value>, ApiError> {
     let logs = crate::notifications::AuditLogService::list_all(&state.db).await?;
     Ok(Json(json!({
@@ -0,0 +1,632 @@
+//! Tamper-evident audit logs.
+//!
+//! Each audit table is a stream whose entries form a hash chain:
+//! `entry_hash = SHA-256(prev_hash || content_hash)`, where `content_hash`
+//! is the SHA-256 of the entry's canonical JSON and `prev_hash` is the
+//! previous entry's `entry_hash` (32 zero bytes for the first entry). Editing
+//! or deleting a row breaks the chain from that point on.
+//!
+//! [`AuditAnchorJob`] periodically seals the entries written since the last
+//! anchor under a Merkle root and records it in the inheritance contract
+//! through the contract relayer, so the history cannot be rewritten wholesale
+//! either. [`AuditChainService::verify`] proves that an entry is unaltered
+//! and included under an anchored root.
+
+use crate::api_error::ApiError;
+use crate::contract_relayer::ContractRelayer;
+use crate::message_access_audit::MessageAccessLog;
+use crate::notifications::ActionLog;
+use crate::service::EmergencyAccessAuditLog;
+use crate::will_audit::AuditLogEntry;
+use chrono::{DateTime, SubsecRound, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::{FromRow, PgPool};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Most entries covered by one anchor; a longer backlog is sealed over
+/// several anchors.
+const ANCHOR_BATCH_SIZE: i64 = 1024;
+
+const GENESIS_HASH: [u8; 32] = [0; 32];
+
+const ANCHOR_COLUMNS: &str = "id, stream, first_seq, last_seq, leaf_count, merkle_root, \
+     chain_status, chain_tx_hash, chain_attempts, chain_error, chain_submitted_at, created_at";
+
+/// An audit log stream: one audit table with its own hash chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditStream {
+    /// `action_logs`, written by [`crate::notifications::AuditLogService`].
+    Actions,
+    /// `will_event_log`, read by [`crate::will_audit::WillAuditService`].
+    WillEvents,
+    /// `message_access_logs`.
+    MessageAccess,
+    /// `emergency_access_audit_logs`.
+    EmergencyAccess,
+}
+
+impl AuditStream {
+    pub const ALL: [AuditStream; 4] = [
+        Self::Actions,
+        Self::WillEvents,
+        Self::MessageAccess,
+        Self::EmergencyAccess,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Actions => "actions",
+            Self::WillEvents => "will_events",
+            Self::MessageAccess => "message_access",
+            Self::EmergencyAccess => "emergency_access",
+        }
+    }
+
+    fn table(&self) -> &'static str {
+        match self {
+            Self::Actions => "action_logs",
+            Self::WillEvents => "will_event_log",
+            Self::MessageAccess => "message_access_logs",
+            Self::EmergencyAccess => "emergency_access_audit_logs",
+        }
+    }
+
+    /// The columns an entry's content hash covers, as read back for
+    /// verification. IP addresses are read without their prefix length.
+    fn content_columns(&self) -> &'static str {
+        match self {
+            Self::Actions => {
+                "id, user_id, admin_id, action, entity_id, entity_type, old_value, new_value, \
+                 metadata, timestamp"
+            }
+            Self::WillEvents => {
+                "id, event_type, document_id, plan_id, vault_id, user_id, event_data, \
+                 host(ip_address) AS ip_address, user_agent, created_at"
+            }
+            Self::MessageAccess => {
+                "id, message_id, user_id, action, host(ip_address) AS ip_address, user_agent, \
+                 metadata, created_at"
+            }
+            Self::EmergencyAccess => {
+                "id, grant_id, user_id, emergency_contact_id, action, permission, metadata, \
+                 created_at"
+            }
+        }
+    }
+}
+
+impl FromStr for AuditStream {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|stream| stream.as_str() == s)
+            .ok_or_else(|| ApiError::BadRequest(format!("Unknown audit stream '{}'", s)))
+    }
+}
+
+/// The timestamp to store on a new audit entry. Postgres keeps microseconds,
+/// so the hashed value must not carry more precision than is read back.
+pub fn entry_timestamp(at: DateTime<Utc>) -> DateTime<Utc> {
+    at.trunc_subsecs(6)
+}
+
+/// SHA-256 of an entry's canonical JSON (object keys sorted).
+pub fn content_hash<T: Serialize>(entry: &T) -> Result<[u8; 32], ApiError> {
+    let canonical = serde_json::to_value(entry)
+        .and_then(|value| serde_json::to_vec(&value))
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to encode audit entry: {}", e)))?;
+    Ok(Sha256::digest(&canonical).into())
+}
+
+pub fn chain_hash(prev_hash: &[u8], content_hash: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(prev_hash);
+    hasher.update(content_hash);
+    hasher.finalize().into()
+}
+
+/// SQL inserting one chained entry into `stream`'s table. `values` are the
+/// placeholders for `columns`, numbered from `$2`: the entry's content hash
+/// is bound as `$1`. Advancing the stream's head row locks it until the
+/// surrounding transaction ends, which keeps `chain_seq` gapless and in
+/// commit order.
+pub(crate) fn chained_insert_sql(stream: AuditStream, columns: &str, values: &str) -> String {
+    format!(
+        "WITH head AS ( \
+             UPDATE audit_chain_heads \
+             SET last_seq = last_seq + 1, prev_hash = last_hash, \
+                 last_hash = sha256(last_hash || $1) \
+             WHERE stream = '{stream}' \
+             RETURNING last_seq, prev_hash, last_hash \
+         ) \
+         INSERT INTO {table} ({columns}, chain_seq, prev_hash, entry_hash) \
+         SELECT {values}, last_seq, prev_hash, last_hash FROM head",
+        stream = stream.as_str(),
+        table = stream.table(),
+    )
+}
+
+// ─── Merkle Trees ────────────────────────────────────────────────────────────
+//
+// Leaves and inner nodes are hashed with distinct prefixes so that a leaf can
+// never be passed off as an inner node. A node without a sibling is carried
+// up to the next level unchanged.
+
+fn leaf_hash(entry_hash: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([0x00]);
+    hasher.update(entry_hash);
+    hasher.finalize().into()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([0x01]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => node_hash(left, right),
+            [single] => *single,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+/// Merkle root over entry hashes in chain order.
+pub fn merkle_root(entry_hashes: &[[u8; 32]]) -> [u8; 32] {
+    let mut level: Vec<[u8; 32]> = entry_hashes.iter().map(|h| leaf_hash(h)).collect();
+    if level.is_empty() {
+        return GENESIS_HASH;
+    }
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProofSide {
+    Left,
+    Right,
+}
+
+/// A sibling on the path from a leaf to the root, hex encoded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct MerkleProofStep {
+    pub sibling: String,
+    pub side: ProofSide,
+}
+
+/// Siblings needed to recompute the root from the leaf at `index`.
+pub fn merkle_proof(entry_hashes: &[[u8; 32]], mut index: usize) -> Vec<MerkleProofStep> {
+    let mut level: Vec<[u8; 32]> = entry_hashes.iter().map(|h| leaf_hash(h)).collect();
+    let mut proof = Vec::new();
+    while level.len() > 1 {
+        let sibling = index ^ 1;
+        if sibling < level.len() {
+            proof.push(MerkleProofStep {
+                sibling: hex::encode(level[sibling]),
+                side: if sibling < index {
+                    ProofSide::Left
+                } else {
+                    ProofSide::Right
+                },
+            });
+        }
+        level = next_level(&level);
+        index /= 2;
+    }
+    proof
+}
+
+/// Recompute a Merkle root from an entry hash and its proof.
+pub fn root_from_proof(entry_hash: &[u8], proof: &[MerkleProofStep]) -> Option<[u8; 32]> {
+    let mut node = leaf_hash(entry_hash);
+    for step in proof {
+        let sibling: [u8; 32] = hex::decode(&step.sibling).ok()?.try_into().ok()?;
+        node = match step.side {
+            ProofSide::Left => node_hash(&sibling, &node),
+            ProofSide::Right => node_hash(&node, &sibling),
+        };
+    }
+    Some(node)
+}
+
+// ─── Anchors and Verification ────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, JsonSchema)]
+pub struct AuditAnchor {
+    pub id: Uuid,
+    pub stream: String,
+    pub first_seq: i64,
+    pub last_seq: i64,
+    pub leaf_count: i32,
+    /// Hex-encoded Merkle root over the entries `first_seq..=last_seq`.
+    pub merkle_root: String,
+    /// `pending` until the contract relayer has submitted it, then `submitted`.
+    pub chain_status: String,
+    pub chain_tx_hash: Option<String>,
+    pub chain_attempts: i32,
+    pub chain_error: Option<String>,
+    pub chain_submitted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow)]
+struct ChainLink {
+    chain_seq: Option<i64>,
+    prev_hash: Option<Vec<u8>>,
+    entry_hash: Option<Vec<u8>>,
+}
+
+/// The outcome of checking one audit entry against its chain and anchor.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EntryVerification {
+    pub stream: AuditStream,
+    pub entry_id: Uuid,
+    pub chain_seq: i64,
+    /// Hash of the entry's current content.
+    pub content_hash: String,
+    pub prev_hash: String,
+    pub entry_hash: String,
+    /// The stored entry hash matches the entry's current content.
+    pub unaltered: bool,
+    /// The previous entry is present and its hash is the one chained into
+    /// this entry.
+    pub linked_to_previous: bool,
+    /// The anchor covering the entry, once one has been sealed.
+    pub anchor: Option<AuditAnchor>,
+    pub merkle_proof: Vec<MerkleProofStep>,
+    /// The entry's current content hashes up to the anchor's root.
+    pub included: bool,
+    pub verified: bool,
+}
+
+pub struct AuditChainService;
+
+impl AuditChainService {
+    /// Check that an audit entry is unaltered, still linked to its
+    /// predecessor and included under an anchored Merkle root.
+    pub async fn verify(
+        db: &PgPool,
+        stream: AuditStream,
+        entry_id: Uuid,
+    ) -> Result<EntryVerification, ApiError> {
+        let row = sqlx::query(&format!(
+            "SELECT {}, chain_seq, prev_hash, entry_hash FROM {} WHERE id = $1",
+            stream.content_columns(),
+            stream.table()
+        ))
+        .bind(entry_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Audit entry {} not found", entry_id)))?;
+
+        let content = match stream {
+            AuditStream::Actions => content_hash(&ActionLog::from_row(&row)?)?,
+            AuditStream::WillEvents => content_hash(&AuditLogEntry::from_row(&row)?)?,
+            AuditStream::MessageAccess => content_hash(&MessageAccessLog::from_row(&row)?)?,
+            AuditStream::EmergencyAccess => {
+                content_hash(&EmergencyAccessAuditLog::from_row(&row)?)?
+            }
+        };
+        let link = ChainLink::from_row(&row)?;
+        let (Some(chain_seq), Some(prev_hash), Some(stored_hash)) =
+            (link.chain_seq, link.prev_hash, link.entry_hash)
+        else {
+            return Err(ApiError::BadRequest(format!(
+                "Audit entry {} predates the hash chain and cannot be verified",
+                entry_id
+            )));
+        };
+
+        let entry_hash = chain_hash(&prev_hash, &content);
+        let unaltered = entry_hash.as_slice() == stored_hash.as_slice();
+
+        let linked_to_previous = if chain_seq == 1 {
+            prev_hash == GENESIS_HASH
+        } else {
+            let previous: Option<Vec<u8>> = sqlx::query_scalar(&format!(
+                "SELECT entry_hash FROM {} WHERE chain_seq = $1",
+                stream.table()
+            ))
+            .bind(chain_seq - 1)
+            .fetch_optional(db)
+            .await?
+            .flatten();
+            previous.as_deref() == Some(prev_hash.as_slice())
+        };
+
+        let anchor = sqlx::query_as::<_, AuditAnchor>(&format!(
+            "SELECT {ANCHOR_COLUMNS} FROM audit_anchors \
+             WHERE stream = $1 AND first_seq <= $2 AND last_seq >= $2"
+        ))
+        .bind(stream.as_str())
+        .bind(chain_seq)
+        .fetch_optional(db)
+        .await?;
+
+        let (merkle_proof, included) = match &anchor {
+            Some(anchor) => {
+                let leaves: Vec<(i64, Vec<u8>)> = sqlx::query_as(&format!(
+                    "SELECT chain_seq, entry_hash FROM {} \
+                     WHERE chain_seq BETWEEN $1 AND $2 ORDER BY chain_seq",
+                    stream.table()
+                ))
+                .bind(anchor.first_seq)
+                .bind(anchor.last_seq)
+                .fetch_all(db)
+                .await?;
+                // A deleted entry leaves a gap that no proof can bridge.
+                let complete = leaves.len() == anchor.leaf_count as usize
+                    && leaves
+                        .iter()
+                        .zip(anchor.first_seq..)
+                        .all(|((seq, _), expected)| *seq == expected);
+                let hashes: Option<Vec<[u8; 32]>> = leaves
+                    .into_iter()
+                    .map(|(seq, hash)| {
+                        if seq == chain_seq {
+                            Some(entry_hash)
+                        } else {
+                            hash.try_into().ok()
+                        }
+                    })
+                    .collect();
+                match hashes {
+                    Some(hashes) if complete => {
+                        let proof = merkle_proof(&hashes, (chain_seq - anchor.first_seq) as usize);
+                        let included = root_from_proof(&entry_hash, &proof)
+                            .is_some_and(|root| hex::encode(root) == anchor.merkle_root);
+                        (proof, included)
+                    }
+                    _ => (Vec::new(), false),
+                }
+            }
+            None => (Vec::new(), false),
+        };
+
+        Ok(EntryVerification {
+            stream,
+            entry_id,
+            chain_seq,
+            content_hash: hex::encode(content),
+            prev_hash: hex::encode(&prev_hash),
+            entry_hash: hex::encode(&stored_hash),
+            unaltered,
+            linked_to_previous,
+            anchor,
+            merkle_proof,
+            included,
+            verified: unaltered && linked_to_previous && included,
+        })
+    }
+
+    /// Seal the entries of `stream` written since its last anchor under a new
+    /// Merkle root. Returns `None` when there is nothing new to seal.
+    pub async fn seal(db: &PgPool, stream: AuditStream) -> Result<Option<AuditAnchor>, ApiError> {
+        let anchored: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(MAX(last_seq), 0) FROM audit_anchors WHERE stream = $1",
+        )
+        .bind(stream.as_str())
+        .fetch_one(db)
+        .await?;
+
+        let rows: Vec<(i64, Option<Vec<u8>>)> = sqlx::query_as(&format!(
+            "SELECT chain_seq, entry_hash FROM {} WHERE chain_seq > $1 \
+             ORDER BY chain_seq LIMIT $2",
+            stream.table()
+        ))
+        .bind(anchored)
+        .bind(ANCHOR_BATCH_SIZE)
+        .fetch_all(db)
+        .await?;
+
+        // Entries are chained without gaps, so a gap means an entry was
+        // deleted. Anchor up to it and leave the rest for an operator.
+        let mut hashes = Vec::with_capacity(rows.len());
+        for ((seq, hash), expected) in rows.into_iter().zip(anchored + 1..) {
+            match hash.and_then(|h| <[u8; 32]>::try_from(h).ok()) {
+                Some(hash) if seq == expected => hashes.push(hash),
+                _ => {
+                    error!(
+                        "Audit stream {} is broken at entry {}; not anchoring past it",
+                        stream.as_str(),
+                        expected
+                    );
+                    break;
+                }
+            }
+        }
+        if hashes.is_empty() {
+            return Ok(None);
+        }
+
+        let anchor = sqlx::query_as::<_, AuditAnchor>(&format!(
+            "INSERT INTO audit_anchors (stream, first_seq, last_seq, leaf_count, merkle_root) \
+             VALUES ($1, $2, $3, $4, $5) \
+             ON CONFLICT (stream, first_seq) DO NOTHING \
+             RETURNING {ANCHOR_COLUMNS}"
+        ))
+        .bind(stream.as_str())
+        .bind(anchored + 1)
+        .bind(anchored + hashes.len() as i64)
+        .bind(hashes.len() as i32)
+        .bind(hex::encode(merkle_root(&hashes)))
+        .fetch_optional(db)
+        .await?;
+        Ok(anchor)
+    }
+}
+
+/// Background job that seals new audit entries under Merkle roots and, when
+/// a contract relayer is configured, anchors the roots on-chain.
+pub struct AuditAnchorJob {
+    db: PgPool,
+    relayer: Option<Arc<ContractRelayer>>,
+}
+
+impl AuditAnchorJob {
+    pub fn new(db: PgPool, relayer: Option<Arc<ContractRelayer>>) -> Self {
+        Self { db, relayer }
+    }
+
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(600));
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.seal_pending().await {
+                    error!("Audit anchor sealing error: {}", e);
+                }
+                if let Err(e) = self.submit_pending().await {
+                    error!("Audit anchor submission error: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Seal every stream's unanchored entries. Returns the new anchors.
+    pub async fn seal_pending(&self) -> Result<Vec<AuditAnchor>, ApiError> {
+        let mut anchors = Vec::new();
+        for stream in AuditStream::ALL {
+            if let Some(anchor) = AuditChainService::seal(&self.db, stream).await? {
+                info!(
+                    "Sealed audit entries {}..={} of {} under {}",
+                    anchor.first_seq, anchor.last_seq, anchor.stream, anchor.merkle_root
+                );
+                anchors.push(anchor);
+            }
+        }
+        Ok(anchors)
+    }
+
+    /// Submit the oldest pending anchor of each stream through the contract
+    /// relayer. Returns how many were submitted.
+    pub async fn submit_pending(&self) -> Result<usize, ApiError> {
+        let Some(relayer) = &self.relayer else {
+            return Ok(0);
+        };
+        let pending = sqlx::query_as::<_, AuditAnchor>(&format!(
+            "SELECT DISTINCT ON (stream) {ANCHOR_COLUMNS} FROM audit_anchors \
+             WHERE chain_status = 'pending' ORDER BY stream, first_seq"
+        ))
+        .fetch_all(&self.db)
+        .await?;
+
+        let mut submitted = 0;
+        for anchor in pending {
+            let result = relayer
+                .invoke(
+                    "anchor_audit_root",
+                    serde_json::json!({
+                        "stream": anchor.stream,
+                        "first_seq": anchor.first_seq,
+                        "last_seq": anchor.last_seq,
+                        "root": anchor.merkle_root,
+                    }),
+                )
+                .await;
+            match result {
+                Ok(tx_hash) => {
+                    sqlx::query(
+                        "UPDATE audit_anchors \
+                         SET chain_status = 'submitted', chain_tx_hash = $2, \
+                             chain_attempts = chain_attempts + 1, chain_error = NULL, \
+                             chain_submitted_at = NOW() \
+                         WHERE id = $1",
+                    )
+                    .bind(anchor.id)
+                    .bind(&tx_hash)
+                    .execute(&self.db)
+                    .await?;
+                    info!(
+                        "Anchored audit entries {}..={} of {}: {}",
+                        anchor.first_seq, anchor.last_seq, anchor.stream, tx_hash
+                    );
+                    submitted += 1;
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to anchor audit entries {}..={} of {}: {}",
+                        anchor.first_seq, anchor.last_seq, anchor.stream, e
+                    );
+                    sqlx::query(
+                        "UPDATE audit_anchors \
+                         SET chain_attempts = chain_attempts + 1, chain_error = $2 \
+                         WHERE id = $1",
+                    )
+                    .bind(anchor.id)
+                    .bind(e.to_string())
+                    .execute(&self.db)
+                    .await?;
+                }
+            }
+        }
+        Ok(submitted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hashes(n: u8) -> Vec<[u8; 32]> {
+        (0..n).map(|i| [i; 32]).collect()
+    }
+
+    #[test]
+    fn every_leaf_proves_into_the_root() {
+        for n in 1..=9 {
+            let leaves = hashes(n);
+            let root = merkle_root(&leaves);
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = merkle_proof(&leaves, index);
+                assert_eq!(root_from_proof(leaf, &proof), Some(root), "n={n} i={index}");
+            }
+        }
+    }
+
+    #[test]
+    fn altered_leaves_do_not_prove_into_the_root() {
+        let leaves = hashes(5);
+        let root = merkle_root(&leaves);
+        let proof = merkle_proof(&leaves, 2);
+        assert_ne!(root_from_proof(&[9; 32], &proof), Some(root));
+        // A leaf cannot stand in for the inner node above it.
+        let inner = node_hash(&leaf_hash(&leaves[0]), &leaf_hash(&leaves[1]));
+        assert_ne!(merkle_root(&[inner, leaves[2], leaves[3], leaves[4]]), root);
+    }
+
+    #[test]
+    fn content_hashes_ignore_key_order() {
+        let a = serde_json::json!({ "b": 1, "a": { "y": true, "x": null } });
+        let b: serde_json::Value =
+            serde_json::from_str(r#"{ "a": { "x": null, "y": true }, "b": 1 }"#).unwrap();
+        assert_eq!(content_hash(&a).unwrap(), content_hash(&b).unwrap());
+    }
+
+    #[test]
+    fn streams_round_trip_through_their_names() {
+        for stream in AuditStream::ALL {
+            assert_eq!(stream.as_str().parse::<AuditStream>().unwrap(), stream);
+        }
+        assert!("action_logs".parse::<AuditStream>().is_err());
+    }
+}
@@ -1,19 +1,33 @@
 use crate::api_error::ApiError;
-use crate::app::AppState;
+use crate::app::{client_evidence, AppState};
 use crate::config::Config;
 use crate::notifications::{audit_action, entity_type, AuditLogService};
-use axum::{extract::State, Json};
+use crate::sessions::{
+    revoke_reason, Principal, RefreshRequest, SessionRegistry, SessionService,
+    ACCESS_TOKEN_TTL_MINUTES,
+};
+use crate::totp::{RequireStepUp, TotpCodeRequest, TotpService, STEP_UP_WINDOW_MINUTES};
+use crate::webauthn::{
+    AccountType, AuthenticationCredential, PasskeyLoginOptionsRequest, PasskeyService,
+    RegistrationCredential,
+};
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    Json,
+};
 use bcrypt::verify;
 use chrono::{DateTime, Duration, Utc};
 use hex;
 use jsonwebtoken::{encode, EncodingKey, Header};
 use ring::signature;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use std::sync::Arc;
 use stellar_strkey::Strkey;
 use uuid::Uuid;
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct NonceResponse {
     pub nonce: String,
 }
@@ -23,7 +37,7 @@ pub struct NonceRequest {
     pub wallet_address: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Web3LoginRequest {
     pub wallet_address: String,
     pub signature: String,
@@ -31,15 +45,26 @@ pub struct Web3LoginRequest {
 
 pub type WalletLoginRequest = Web3LoginRequest;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct LoginRequest {
     pub email: String,
     pub password: String,
+    /// Optional label for the session, e.g. "Alice's iPhone".
+    #[serde(default)]
+    pub device_name: Option<String>,
+    /// TOTP or recovery code, required for admins who have enrolled 2FA.
+    #[serde(default)]
+    pub otp: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct LoginResponse {
+    /// Short-lived access token.
     pub token: String,
+    /// Single-use token for `/api/auth/refresh`.
+    pub refresh_token: String,
+    /// Seconds until `token` expires.
+    pub expires_in: i64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -84,6 +109,7 @@ pub async fn get_nonce(
 
 pub async fn web3_login(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     Json(payload): Json<Web3LoginRequest>,
 ) -> Result<Json<LoginResponse>, ApiError> {
     let mut tx = state.db.begin().await?;
@@ -163,26 +189,7 @@ pub async fn web3_login(
         }
     };
 
-    // 5. Generate JWT
-    let expiration = Utc::now()
-        .checked_add_signed(Duration::hours(24))
-        .expect("valid timestamp")
-        .timestamp();
-
-    let claims = UserClaims {
-        user_id,
-        email,
-        exp: expiration as usize,
-    };
-
-    let token = encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(state.config.jwt_secret.as_bytes()),
-    )
-    .map_err(|e| ApiError::Internal(anyhow::anyhow!(e)))?;
-
-    // 6. Invalidate nonce
+    // 5. Invalidate nonce
     let delete_result = sqlx::query("DELETE FROM nonces WHERE wallet_address = $1 AND nonce = $2")
         .bind(&payload.wallet_address)
         .bind(&nonce_val)
@@ -195,7 +202,18 @@ pub async fn web3_login(
 
     tx.commit().await?;
 
-    Ok(Json(LoginResponse { token }))
+    // 6. Open a session
+    let response = start_user_session(
+        &state,
+        user_id,
+        email,
+        &headers,
+        None,
+        &[auth_method::WALLET],
+        None,
+    )
+    .await?;
+    Ok(Json(response))
 }
 
 #[derive(Debug, FromRow)]
@@ -216,6 +234,7 @@ struct User {
 
 pub async fn login_user(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     Json(payload): Json<LoginRequest>,
 ) -> Result<Json<LoginResponse>, ApiError> {
     let user =
@@ -236,25 +255,17 @@ pub async fn login_user(
         return Err(ApiError::Unauthorized);
     }
 
-    let expiration = Utc::now()
-        .checked_add_signed(Duration::hours(24))
-        .expect("valid timestamp")
-        .timestamp();
-
-    let claims = UserClaims {
-        user_id: user.id,
-        email: user.email,
-        exp: expiration as usize,
-    };
-
-    let token = encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(state.config.jwt_secret.as_bytes()),
+    let response = start_user_session(
+        &state,
+        user.id,
+        user.email,
+        &headers,
+        payload.device_name.as_deref(),
+        &[auth_method::PASSWORD],
+        None,
     )
-    .map_err(|e| ApiError::Internal(anyhow::anyhow!(e)))?;
-
-    Ok(Json(LoginResponse { token }))
+    .await?;
+    Ok(Json(response))
 }
 #[derive(sqlx::FromRow)]
 struct UserRow {
@@ -264,6 +275,7 @@ struct UserRow {
 
 pub async fn login_admin(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     Json(payload): Json<LoginRequest>,
 ) -> Result<Json<LoginResponse>, ApiError> {
     let admin = sqlx::query_as::<_, Admin>(
@@ -289,26 +301,587 @@ pub async fn login_admin(
         return Err(ApiError::Unauthorized);
     }
 
-    let expiration = Utc::now()
-        .checked_add_signed(Duration::hours(24))
-        .expect("valid timestamp")
-        .timestamp();
+    // Once passkeys are required, a password only lets an admin in to
+    // register their first one.
+    if state.config.require_admin_passkeys
+        && PasskeyService::has_passkey(&state.db, Principal::Admin(admin.id)).await?
+    {
+        return Err(ApiError::Forbidden(
+            "Admins must sign in with a passkey".to_string(),
+        ));
+    }
 
-    let claims = AdminClaims {
-        admin_id: admin.id,
-        email: admin.email,
-        role: admin.role,
-        exp: expiration as usize,
+    // Admins who have enrolled an authenticator must present a code.
+    let mut methods = vec![auth_method::PASSWORD];
+    let mfa_at = if TotpService::is_enabled(&state.db, Principal::Admin(admin.id)).await? {
+        let otp = payload
+            .otp
+            .as_deref()
+            .ok_or_else(|| ApiError::Forbidden("Two-factor code required".to_string()))?;
+        TotpService::verify(&state.db, Principal::Admin(admin.id), otp).await?;
+        methods.push(auth_method::OTP);
+        Some(Utc::now().timestamp())
+    } else {
+        None
+    };
+
+    let response = start_admin_session(
+        &state,
+        admin,
+        &headers,
+        payload.device_name.as_deref(),
+        &methods,
+        mfa_at,
+    )
+    .await?;
+    Ok(Json(response))
+}
+
+/// Exchange a refresh token for a new access token and refresh token.
+pub async fn refresh_session(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(payload): Json<RefreshRequest>,
+) -> Result<Json<LoginResponse>, ApiError> {
+    let (session, refresh_token) = SessionService::rotate(
+        &state.db,
+        &state.sessions,
+        &payload.refresh_token,
+        &client_evidence(&headers),
+    )
+    .await?;
+
+    let token = match session.principal() {
+        Principal::User(user_id) => {
+            let user = sqlx::query_as::<_, UserRow>("SELECT id, email FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_optional(&state.db)
+                .await?
+                .ok_or(ApiError::Unauthorized)?;
+            user_access_token(&state.config, user.id, user.email, Some(session.id), None)?
+        }
+        Principal::Admin(admin_id) => {
+            let admin = sqlx::query_as::<_, Admin>(
+                "SELECT id, email, password_hash, role, status FROM admins WHERE id = $1",
+            )
+            .bind(admin_id)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or(ApiError::Unauthorized)?;
+            if admin.status == "locked" {
+                return Err(ApiError::Forbidden("Account is locked".to_string()));
+            }
+            admin_access_token(
+                &state.config,
+                admin.id,
+                admin.email,
+                admin.role,
+                Some(session.id),
+                None,
+                session.auth_methods.clone(),
+            )?
+        }
+    };
+
+    Ok(Json(LoginResponse {
+        token,
+        refresh_token,
+        expires_in: ACCESS_TOKEN_TTL_MINUTES * 60,
+    }))
+}
+
+/// End the session the request's access token belongs to.
+pub async fn logout_user(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let session_id = user
+        .sid
+        .ok_or_else(|| ApiError::BadRequest("This token is not bound to a session".to_string()))?;
+    SessionService::revoke(
+        &state.db,
+        &state.sessions,
+        Principal::User(user.user_id),
+        session_id,
+        revoke_reason::LOGOUT,
+    )
+    .await?;
+    Ok(Json(
+        serde_json::json!({ "status": "success", "message": "Logged out" }),
+    ))
+}
+
+pub async fn logout_admin(
+    State(state): State<Arc<AppState>>,
+    AdminSession(admin): AdminSession,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let session_id = admin
+        .sid
+        .ok_or_else(|| ApiError::BadRequest("This token is not bound to a session".to_string()))?;
+    SessionService::revoke(
+        &state.db,
+        &state.sessions,
+        Principal::Admin(admin.admin_id),
+        session_id,
+        revoke_reason::LOGOUT,
+    )
+    .await?;
+    Ok(Json(
+        serde_json::json!({ "status": "success", "message": "Logged out" }),
+    ))
+}
+
+/// Start authenticator-app enrollment for the signed-in user.
+pub async fn enroll_user_totp(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let enrollment =
+        TotpService::enroll(&state.db, Principal::User(user.user_id), &user.email).await?;
+    Ok(Json(
+        serde_json::json!({ "status": "success", "data": enrollment }),
+    ))
+}
+
+/// Confirm enrollment with a first code and receive the recovery codes.
+pub async fn confirm_user_totp(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(payload): Json<TotpCodeRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let recovery_codes =
+        TotpService::confirm(&state.db, Principal::User(user.user_id), &payload.code).await?;
+    Ok(Json(serde_json::json!({
+        "status": "success",
+        "data": { "recovery_codes": recovery_codes }
+    })))
+}
+
+pub async fn disable_user_totp(
+    State(state): State<Arc<AppState>>,
+    RequireStepUp(user): RequireStepUp,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    TotpService::disable(&state.db, Principal::User(user.user_id)).await?;
+    Ok(Json(serde_json::json!({
+        "status": "success",
+        "message": "Two-factor authentication disabled"
+    })))
+}
+
+pub async fn regenerate_user_recovery_codes(
+    State(state): State<Arc<AppState>>,
+    RequireStepUp(user): RequireStepUp,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let recovery_codes =
+        TotpService::regenerate_recovery_codes(&state.db, Principal::User(user.user_id)).await?;
+    Ok(Json(serde_json::json!({
+        "status": "success",
+        "data": { "recovery_codes": recovery_codes }
+    })))
+}
+
+pub async fn user_two_factor_status(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let status = TotpService::status(&state.db, Principal::User(user.user_id)).await?;
+    Ok(Json(
+        serde_json::json!({ "status": "success", "data": status }),
+    ))
+}
+
+/// Verify a TOTP or recovery code and reissue the access token with a fresh
+/// `mfa_at`, for routes guarded by [`RequireStepUp`].
+pub async fn step_up_user(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(payload): Json<TotpCodeRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    TotpService::verify(&state.db, Principal::User(user.user_id), &payload.code).await?;
+    let mfa_at = Utc::now().timestamp();
+    let token = user_access_token(
+        &state.config,
+        user.user_id,
+        user.email,
+        user.sid,
+        Some(mfa_at),
+    )?;
+    AuditLogService::log(
+        &state.db,
+        Some(user.user_id),
+        None,
+        audit_action::STEP_UP_COMPLETED,
+        Some(user.user_id),
+        Some(entity_type::USER),
+        None,
+        None,
+        None,
+    )
+    .await?;
+    Ok(Json(serde_json::json!({
+        "status": "success",
+        "data": {
+            "token": token,
+            "expires_in": ACCESS_TOKEN_TTL_MINUTES * 60,
+            "step_up_expires_in": STEP_UP_WINDOW_MINUTES * 60
+        }
+    })))
+}
+
+pub async fn enroll_admin_totp(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let enrollment =
+        TotpService::enroll(&state.db, Principal::Admin(admin.admin_id), &admin.email).await?;
+    Ok(Json(
+        serde_json::json!({ "status": "success", "data": enrollment }),
+    ))
+}
+
+/// Once confirmed, `/admin/login` requires a code from this admin.
+pub async fn confirm_admin_totp(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+    Json(payload): Json<TotpCodeRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let recovery_codes =
+        TotpService::confirm(&state.db, Principal::Admin(admin.admin_id), &payload.code).await?;
+    Ok(Json(serde_json::json!({
+        "status": "success",
+        "data": { "recovery_codes": recovery_codes }
+    })))
+}
+
+/// Options for registering a passkey on the signed-in user's account.
+pub async fn user_passkey_registration_options(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let options = PasskeyService::registration_options(
+        &state.db,
+        &state.webauthn,
+        Principal::User(user.user_id),
+        &user.email,
+    )
+    .await?;
+    Ok(Json(
+        serde_json::json!({ "status": "success", "data": options }),
+    ))
+}
+
+pub async fn register_user_passkey(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(credential): Json<RegistrationCredential>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let passkey = PasskeyService::register(
+        &state.db,
+        &state.webauthn,
+        Principal::User(user.user_id),
+        &credential,
+    )
+    .await?;
+    Ok(Json(
+        serde_json::json!({ "status": "success", "data": passkey }),
+    ))
+}
+
+pub async fn list_user_passkeys(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let passkeys = PasskeyService::list(&state.db, Principal::User(user.user_id)).await?;
+    Ok(Json(serde_json::json!({
+        "status": "success",
+        "data": passkeys,
+        "count": passkeys.len()
+    })))
+}
+
+pub async fn remove_user_passkey(
+    State(state): State<Arc<AppState>>,
+    Path(passkey_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    PasskeyService::remove(&state.db, Principal::User(user.user_id), passkey_id, false).await?;
+    Ok(Json(
+        serde_json::json!({ "status": "success", "message": "Passkey removed" }),
+    ))
+}
+
+pub async fn user_passkey_login_options(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<PasskeyLoginOptionsRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let options = PasskeyService::authentication_options(
+        &state.db,
+        &state.webauthn,
+        AccountType::User,
+        payload.email.as_deref(),
+    )
+    .await?;
+    Ok(Json(
+        serde_json::json!({ "status": "success", "data": options }),
+    ))
+}
+
+/// Sign a user in with a passkey. A user-verifying authenticator also counts
+/// as a step-up.
+pub async fn login_user_with_passkey(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(credential): Json<AuthenticationCredential>,
+) -> Result<Json<LoginResponse>, ApiError> {
+    let assertion =
+        PasskeyService::authenticate(&state.db, &state.webauthn, AccountType::User, &credential)
+            .await?;
+    let Principal::User(user_id) = assertion.principal else {
+        return Err(ApiError::Unauthorized);
+    };
+    let user = sqlx::query_as::<_, UserRow>("SELECT id, email FROM users WHERE id = $1")
+        .bind(user_id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or(ApiError::Unauthorized)?;
+    let mfa_at = assertion.user_verified.then(|| Utc::now().timestamp());
+    let response = start_user_session(
+        &state,
+        user.id,
+        user.email,
+        &headers,
+        None,
+        &[auth_method::PASSKEY],
+        mfa_at,
+    )
+    .await?;
+    Ok(Json(response))
+}
+
+/// Options for registering a passkey. Reachable with a password session even
+/// when `ADMIN_PASSKEYS_REQUIRED` is set, so admins can enroll.
+pub async fn admin_passkey_registration_options(
+    State(state): State<Arc<AppState>>,
+    AdminSession(admin): AdminSession,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let options = PasskeyService::registration_options(
+        &state.db,
+        &state.webauthn,
+        Principal::Admin(admin.admin_id),
+        &admin.email,
+    )
+    .await?;
+    Ok(Json(
+        serde_json::json!({ "status": "success", "data": options }),
+    ))
+}
+
+pub async fn register_admin_passkey(
+    State(state): State<Arc<AppState>>,
+    AdminSession(admin): AdminSession,
+    Json(credential): Json<RegistrationCredential>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let passkey = PasskeyService::register(
+        &state.db,
+        &state.webauthn,
+        Principal::Admin(admin.admin_id),
+        &credential,
+    )
+    .await?;
+    Ok(Json(
+        serde_json::json!({ "status": "success", "data": passkey }),
+    ))
+}
+
+pub async fn list_admin_passkeys(
+    State(state): State<Arc<AppState>>,
+    AdminSession(admin): AdminSession,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let passkeys = PasskeyService::list(&state.db, Principal::Admin(admin.admin_id)).await?;
+    Ok(Json(serde_json::json!({
+        "status": "success",
+        "data": passkeys,
+        "count": passkeys.len()
+    })))
+}
+
+pub async fn remove_admin_passkey(
+    State(state): State<Arc<AppState>>,
+    Path(passkey_id): Path<Uuid>,
+    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    PasskeyService::remove(
+        &state.db,
+        Principal::Admin(admin.admin_id),
+        passkey_id,
+        state.config.require_admin_passkeys,
+    )
+    .await?;
+    Ok(Json(
+        serde_json::json!({ "status": "success", "message": "Passkey removed" }),
+    ))
+}
+
+pub async fn admin_passkey_login_options(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<PasskeyLoginOptionsRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let options = PasskeyService::authentication_options(
+        &state.db,
+        &state.webauthn,
+        AccountType::Admin,
+        payload.email.as_deref(),
+    )
+    .await?;
+    Ok(Json(
+        serde_json::json!({ "status": "success", "data": options }),
+    ))
+}
+
+pub async fn login_admin_with_passkey(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(credential): Json<AuthenticationCredential>,
+) -> Result<Json<LoginResponse>, ApiError> {
+    let assertion =
+        PasskeyService::authenticate(&state.db, &state.webauthn, AccountType::Admin, &credential)
+            .await?;
+    let Principal::Admin(admin_id) = assertion.principal else {
+        return Err(ApiError::Unauthorized);
     };
+    let admin = sqlx::query_as::<_, Admin>(
+        "SELECT id, email, password_hash, role, status FROM admins WHERE id = $1",
+    )
+    .bind(admin_id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(ApiError::Unauthorized)?;
+    if admin.status == "locked" {
+        return Err(ApiError::Forbidden("Account is locked".to_string()));
+    }
+    let mfa_at = assertion.user_verified.then(|| Utc::now().timestamp());
+    let response = start_admin_session(
+        &state,
+        admin,
+        &headers,
+        None,
+        &[auth_method::PASSKEY],
+        mfa_at,
+    )
+    .await?;
+    Ok(Json(response))
+}
 
-    let token = encode(
+async fn start_user_session(
+    state: &AppState,
+    user_id: Uuid,
+    email: String,
+    headers: &HeaderMap,
+    device_name: Option<&str>,
+    auth_methods: &[&str],
+    mfa_at: Option<i64>,
+) -> Result<LoginResponse, ApiError> {
+    let (session, refresh_token) = SessionService::create(
+        &state.db,
+        Principal::User(user_id),
+        &client_evidence(headers),
+        device_name,
+        auth_methods,
+    )
+    .await?;
+    let token = user_access_token(&state.config, user_id, email, Some(session.id), mfa_at)?;
+    Ok(LoginResponse {
+        token,
+        refresh_token,
+        expires_in: ACCESS_TOKEN_TTL_MINUTES * 60,
+    })
+}
+
+async fn start_admin_session(
+    state: &AppState,
+    admin: Admin,
+    headers: &HeaderMap,
+    device_name: Option<&str>,
+    auth_methods: &[&str],
+    mfa_at: Option<i64>,
+) -> Result<LoginResponse, ApiError> {
+    let (session, refresh_token) = SessionService::create(
+        &state.db,
+        Principal::Admin(admin.id),
+        &client_evidence(headers),
+        device_name,
+        auth_methods,
+    )
+    .await?;
+    let token = admin_access_token(
+        &state.config,
+        admin.id,
+        admin.email,
+        admin.role,
+        Some(session.id),
+        mfa_at,
+        session.auth_methods,
+    )?;
+    Ok(LoginResponse {
+        token,
+        refresh_token,
+        expires_in: ACCESS_TOKEN_TTL_MINUTES * 60,
+    })
+}
+
+fn access_token_expiry() -> usize {
+    Utc::now()
+        .checked_add_signed(Duration::minutes(ACCESS_TOKEN_TTL_MINUTES))
+        .expect("valid timestamp")
+        .timestamp() as usize
+}
+
+pub fn user_access_token(
+    config: &Config,
+    user_id: Uuid,
+    email: String,
+    sid: Option<Uuid>,
+    mfa_at: Option<i64>,
+) -> Result<String, ApiError> {
+    let claims = UserClaims {
+        user_id,
+        email,
+        exp: access_token_expiry(),
+        sid,
+        mfa_at,
+    };
+    encode(
         &Header::default(),
         &claims,
-        &EncodingKey::from_secret(state.config.jwt_secret.as_bytes()),
+        &EncodingKey::from_secret(config.jwt_secret.as_bytes()),
     )
-    .map_err(|e| ApiError::Internal(anyhow::anyhow!(e)))?;
+    .map_err(|e| ApiError::Internal(anyhow::anyhow!(e)))
+}
 
-    Ok(Json(LoginResponse { token }))
+pub fn admin_access_token(
+    config: &Config,
+    admin_id: Uuid,
+    email: String,
+    role: String,
+    sid: Option<Uuid>,
+    mfa_at: Option<i64>,
+    amr: Vec<String>,
+) -> Result<String, ApiError> {
+    let claims = AdminClaims {
+        admin_id,
+        email,
+        role,
+        exp: access_token_expiry(),
+        sid,
+        mfa_at,
+        amr,
+    };
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(config.jwt_secret.as_bytes()),
+    )
+    .map_err(|e| ApiError::Internal(anyhow::anyhow!(e)))
 }
 
 pub async fn generate_nonce(
@@ -320,9 +893,10 @@ pub async fn generate_nonce(
 
 pub async fn wallet_login(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     Json(payload): Json<WalletLoginRequest>,
 ) -> Result<Json<LoginResponse>, ApiError> {
-    web3_login(State(state), Json(payload)).await
+    web3_login(State(state), headers, Json(payload)).await
 }
 
 pub async fn send_2fa(
@@ -474,6 +1048,14 @@ pub struct UserClaims {
     pub user_id: uuid::Uuid,
     pub email: String,
     pub exp: usize,
+    /// Session the token was issued for. Tokens that name a session stop
+    /// working as soon as the session is revoked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<uuid::Uuid>,
+    /// Unix time of the last two-factor verification, see
+    /// [`crate::totp::RequireStepUp`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mfa_at: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -482,12 +1064,59 @@ pub struct AdminClaims {
     pub email: String,
     pub role: String,
     pub exp: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<uuid::Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mfa_at: Option<i64>,
+    /// How the session was signed in; see [`auth_method`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub amr: Vec<String>,
+}
+
+/// Sign-in methods recorded on sessions and in admin tokens' `amr` claim.
+pub mod auth_method {
+    pub const PASSWORD: &str = "pwd";
+    pub const OTP: &str = "otp";
+    pub const WALLET: &str = "wallet";
+    pub const PASSKEY: &str = "passkey";
+}
+
+/// Reject tokens whose session has been revoked or has expired. Tokens
+/// without a session cannot be revoked, so they are only accepted from the
+/// test issuer while test authentication is enabled.
+async fn ensure_session_active(
+    parts: &Parts,
+    config: &Config,
+    sid: Option<Uuid>,
+) -> Result<(), ApiError> {
+    let Some(sid) = sid else {
+        return if config.test_auth {
+            Ok(())
+        } else {
+            Err(ApiError::Unauthorized)
+        };
+    };
+    let registry = parts
+        .extensions
+        .get::<Arc<SessionRegistry>>()
+        .ok_or_else(|| {
+            ApiError::Internal(anyhow::anyhow!("Session registry not found in extensions"))
+        })?;
+    if !registry.is_active(sid).await? {
+        return Err(ApiError::Unauthorized);
+    }
+    Ok(())
 }
 
 pub struct AuthenticatedUser(pub UserClaims);
 
 pub struct AuthenticatedAdmin(pub AdminClaims);
 
+/// An admin with a valid token that may not yet satisfy
+/// `ADMIN_PASSKEYS_REQUIRED`. Only for registering a passkey and signing out;
+/// everything else takes [`AuthenticatedAdmin`].
+pub struct AdminSession(pub AdminClaims);
+
 #[async_trait::async_trait]
 impl<S> FromRequestParts<S> for AuthenticatedUser
 where
@@ -518,19 +1147,10 @@ where
             )
             .map_err(|_| ApiError::Unauthorized)?
             .claims;
+            ensure_session_active(parts, config, claims.sid).await?;
             return Ok(AuthenticatedUser(claims));
         }
 
-        if let Some(user_id_str) = parts.headers.get("X-User-Id").and_then(|h| h.to_str().ok()) {
-            if let Ok(user_id) = uuid::Uuid::parse_str(user_id_str) {
-                return Ok(AuthenticatedUser(UserClaims {
-                    user_id,
-                    email: "legacy-test@example.com".to_string(),
-                    exp: 0,
-                }));
-            }
-        }
-
         Err(ApiError::Unauthorized)
     }
 }
@@ -542,6 +1162,30 @@ where
 {
     type Rejection = ApiError;
 
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let AdminSession(claims) = AdminSession::from_request_parts(parts, state).await?;
+        let config = parts
+            .extensions
+            .get::<Config>()
+            .ok_or_else(|| ApiError::Internal(anyhow::anyhow!("Config not found in extensions")))?;
+        if config.require_admin_passkeys && !claims.amr.iter().any(|m| m == auth_method::PASSKEY) {
+            return Err(ApiError::Forbidden(
+                "Admins must sign in with a passkey; register one at \
+                 /admin/passkeys/register/options"
+                    .to_string(),
+            ));
+        }
+        Ok(AuthenticatedAdmin(claims))
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> FromRequestParts<S> for AdminSession
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
     async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
         let config = parts
             .extensions
@@ -565,22 +1209,8 @@ where
             )
             .map_err(|_| ApiError::Unauthorized)?
             .claims;
-            return Ok(AuthenticatedAdmin(claims));
-        }
-
-        if let Some(admin_id_str) = parts
-            .headers
-            .get("X-Admin-Id")
-            .and_then(|h| h.to_str().ok())
-        {
-            if let Ok(admin_id) = uuid::Uuid::parse_str(admin_id_str) {
-                return Ok(AuthenticatedAdmin(AdminClaims {
-                    admin_id,
-                    email: "legacy-admin@example.com".to_string(),
-                    role: "super_admin".to_string(),
-                    exp: 0,
-                }));
-            }
+            ensure_session_active(parts, config, claims.sid).await?;
+            return Ok(AdminSession(claims));
         }
 
         Err(ApiError::Unauthorized)
@@ -5,6 +5,7 @@
 
 use crate::api_error::ApiError;
 use rust_decimal::Decimal;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use uuid::Uuid;
@@ -17,20 +18,20 @@ pub struct ContractBeneficiary {
     pub allocation_percent: Decimal,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DocumentBeneficiary {
     pub wallet_address: String,
     pub allocation_percent: Decimal,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum SyncStatus {
     Matched,
     Mismatched,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MismatchDetail {
     pub wallet_address: String,
     pub field: String,
@@ -38,7 +39,7 @@ pub struct MismatchDetail {
     pub document_value: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BeneficiarySyncResult {
     pub plan_id: Uuid,
     pub status: SyncStatus,
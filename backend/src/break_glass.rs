@@ -0,0 +1,610 @@
+//! Time-delayed break-glass emergency access.
+//!
+//! An emergency contact who has an InheritX account (matched on the contact's
+//! email) can request access to the owner's plan. The request is stored as a
+//! `pending` grant whose `effective_at` is the owner's waiting period from now
+//! (72 hours unless the owner changes it). The owner is told on every channel
+//! we have for them and can veto the request until then; once the waiting
+//! period has passed without a veto the grant becomes `active`.
+//!
+//! What a grant lets the contact do is limited by its scope (see
+//! [`crate::emergency_scope`]). Every step is written to the emergency access
+//! audit log.
+
+use crate::api_error::ApiError;
+use crate::emergency_scope::{
+    EmergencyPermission, EmergencyScope, EmergencyScopeService, ScopeRequest,
+};
+use crate::message_delivery::MessageChannels;
+use crate::notifications::{
+    audit_action, entity_type, notif_type, AuditLogService, NotificationService,
+};
+use crate::service::EmergencyAccessService;
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+/// Waiting period applied when the owner has not chosen one.
+pub const DEFAULT_DELAY_HOURS: i32 = 72;
+
+/// Longest waiting period an owner can choose (30 days).
+pub const MAX_DELAY_HOURS: i32 = 720;
+
+/// `access_type` of grants created through break-glass requests.
+pub const ACCESS_TYPE: &str = "break_glass";
+
+/// Emergency access audit log actions.
+pub mod action {
+    pub const REQUESTED: &str = "break_glass_requested";
+    pub const OWNER_NOTIFIED: &str = "break_glass_owner_notified";
+    pub const VETOED: &str = "break_glass_vetoed";
+    pub const EFFECTIVE: &str = "break_glass_effective";
+    pub const PLAN_VIEWED: &str = "break_glass_plan_viewed";
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
+pub struct BreakGlassGrant {
+    pub id: Uuid,
+    pub plan_id: Uuid,
+    pub owner_id: Uuid,
+    pub requested_by: Option<Uuid>,
+    pub emergency_contact_id: Uuid,
+    pub reason: String,
+    pub status: String,
+    pub requested_at: DateTime<Utc>,
+    pub effective_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub vetoed_at: Option<DateTime<Utc>>,
+    pub veto_reason: Option<String>,
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub scope: EmergencyScope,
+}
+
+/// What an emergency contact sees of the owner's plan once access is in force.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct BreakGlassPlanView {
+    pub id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: String,
+    pub is_active: Option<bool>,
+    pub is_paused: Option<bool>,
+    pub net_amount: Decimal,
+    pub currency_preference: Option<String>,
+    pub beneficiary_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct BreakGlassRequest {
+    pub plan_id: Uuid,
+    pub reason: String,
+    /// How long access lasts once effective; open-ended if omitted.
+    pub expires_in_hours: Option<i64>,
+    /// What the contact asks to be able to do; the owner can narrow it.
+    #[serde(flatten)]
+    pub scope: ScopeRequest,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct VetoBreakGlassRequest {
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BreakGlassSettings {
+    pub delay_hours: i32,
+}
+
+const GRANT_COLUMNS: &str =
+    "g.id, g.plan_id, p.user_id AS owner_id, g.granted_to AS requested_by, \
+     g.emergency_contact_id, g.reason, g.status, g.granted_at AS requested_at, g.effective_at, \
+     g.expires_at, g.vetoed_at, g.veto_reason, \
+     g.permissions, g.allowed_payees, g.spending_cap, g.spent_amount";
+
+pub struct BreakGlassService;
+
+impl BreakGlassService {
+    pub async fn settings(db: &PgPool, user_id: Uuid) -> Result<BreakGlassSettings, ApiError> {
+        let delay_hours: Option<i32> = sqlx::query_scalar(
+            "SELECT break_glass_delay_hours FROM emergency_access_settings WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(db)
+        .await?;
+        Ok(BreakGlassSettings {
+            delay_hours: delay_hours.unwrap_or(DEFAULT_DELAY_HOURS),
+        })
+    }
+
+    /// Change the owner's waiting period. Requests already pending keep theirs.
+    pub async fn update_settings(
+        db: &PgPool,
+        user_id: Uuid,
+        settings: &BreakGlassSettings,
+    ) -> Result<BreakGlassSettings, ApiError> {
+        if !(1..=MAX_DELAY_HOURS).contains(&settings.delay_hours) {
+            return Err(ApiError::BadRequest(format!(
+                "delay_hours must be between 1 and {MAX_DELAY_HOURS}"
+            )));
+        }
+        sqlx::query(
+            "INSERT INTO emergency_access_settings (user_id, break_glass_delay_hours) \
+             VALUES ($1, $2) \
+             ON CONFLICT (user_id) DO UPDATE \
+             SET break_glass_delay_hours = EXCLUDED.break_glass_delay_hours, updated_at = NOW()",
+        )
+        .bind(user_id)
+        .bind(settings.delay_hours)
+        .execute(db)
+        .await?;
+        Ok(BreakGlassSettings {
+            delay_hours: settings.delay_hours,
+        })
+    }
+
+    /// Request break-glass access to another user's plan as one of their
+    /// emergency contacts, and notify the owner.
+    pub async fn request(
+        db: &PgPool,
+        channels: &MessageChannels,
+        requester_id: Uuid,
+        requester_email: &str,
+        req: &BreakGlassRequest,
+    ) -> Result<BreakGlassGrant, ApiError> {
+        if req.reason.trim().is_empty() {
+            return Err(ApiError::BadRequest("A reason is required".to_string()));
+        }
+        if req.expires_in_hours.is_some_and(|hours| hours <= 0) {
+            return Err(ApiError::BadRequest(
+                "expires_in_hours must be positive".to_string(),
+            ));
+        }
+        let permissions = req.scope.validate()?;
+        let owner_id: Uuid = sqlx::query_scalar("SELECT user_id FROM plans WHERE id = $1")
+            .bind(req.plan_id)
+            .fetch_optional(db)
+            .await?
+            .ok_or_else(|| ApiError::NotFound(format!("Plan {} not found", req.plan_id)))?;
+        if owner_id == requester_id {
+            return Err(ApiError::BadRequest(
+                "You cannot request emergency access to your own plan".to_string(),
+            ));
+        }
+        let (contact_id, contact_name): (Uuid, String) = sqlx::query_as(
+            "SELECT id, name FROM emergency_contacts \
+             WHERE user_id = $1 AND LOWER(email) = LOWER($2) \
+             ORDER BY created_at LIMIT 1",
+        )
+        .bind(owner_id)
+        .bind(requester_email)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| {
+            ApiError::Forbidden("You are not an emergency contact for this plan".to_string())
+        })?;
+
+        let delay_hours = Self::settings(db, owner_id).await?.delay_hours;
+        let effective_at = Utc::now() + Duration::hours(i64::from(delay_hours));
+        let expires_at = req
+            .expires_in_hours
+            .map(|hours| effective_at + Duration::hours(hours));
+
+        let mut tx = db.begin().await?;
+        Self::activate_due_for_plan(&mut tx, req.plan_id).await?;
+        let open: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM emergency_access_grants \
+             WHERE plan_id = $1 AND emergency_contact_id = $2 \
+               AND status IN ('pending', 'active') \
+               AND (expires_at IS NULL OR expires_at > NOW()))",
+        )
+        .bind(req.plan_id)
+        .bind(contact_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if open {
+            return Err(ApiError::Conflict(
+                "A break-glass request for this plan is already pending or active".to_string(),
+            ));
+        }
+
+        let grant_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO emergency_access_grants \
+                 (plan_id, granted_to, emergency_contact_id, access_type, reason, \
+                  expires_at, effective_at, status, permissions, allowed_payees, spending_cap) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, 'pending', $8, $9, $10) \
+             RETURNING id",
+        )
+        .bind(req.plan_id)
+        .bind(requester_id)
+        .bind(contact_id)
+        .bind(ACCESS_TYPE)
+        .bind(req.reason.trim())
+        .bind(expires_at)
+        .bind(effective_at)
+        .bind(&permissions)
+        .bind(req.scope.payees())
+        .bind(req.scope.spending_cap)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        EmergencyAccessService::log_action(
+            &mut *tx,
+            grant_id,
+            owner_id,
+            contact_id,
+            action::REQUESTED,
+            json!({
+                "requested_by": requester_id,
+                "reason": req.reason.trim(),
+                "delay_hours": delay_hours,
+                "effective_at": effective_at,
+                "permissions": permissions,
+            }),
+        )
+        .await?;
+        AuditLogService::log(
+            &mut *tx,
+            Some(requester_id),
+            None,
+            audit_action::EMERGENCY_ACCESS_REQUESTED,
+            Some(req.plan_id),
+            Some(entity_type::PLAN),
+            None,
+            None,
+            Some(json!({ "grant_id": grant_id, "effective_at": effective_at })),
+        )
+        .await?;
+
+        let message = format!(
+            "{contact_name} has requested emergency access to your plan ({}): \"{}\". \
+             Access will be granted at {} unless you veto the request before then.",
+            permissions.join(", "),
+            req.reason.trim(),
+            effective_at.format("%Y-%m-%d %H:%M UTC")
+        );
+        NotificationService::create(
+            &mut tx,
+            owner_id,
+            notif_type::EMERGENCY_ACCESS_REQUESTED,
+            message.clone(),
+        )
+        .await?;
+        let grant = Self::fetch(&mut tx, grant_id).await?;
+        tx.commit().await?;
+
+        Self::notify_owner(db, channels, &grant, &message).await;
+        Ok(grant)
+    }
+
+    /// Email the owner about a new request and record which channels reached
+    /// them. The in-app notification was created with the request itself.
+    async fn notify_owner(
+        db: &PgPool,
+        channels: &MessageChannels,
+        grant: &BreakGlassGrant,
+        message: &str,
+    ) {
+        let mut reached = vec!["in_app"];
+        let mut failed = Vec::new();
+        match sqlx::query_scalar::<_, String>("SELECT email FROM users WHERE id = $1")
+            .bind(grant.owner_id)
+            .fetch_one(db)
+            .await
+        {
+            Ok(email) => {
+                let body = format!(
+                    "{message}\n\nIf you did not expect this, veto the request from your \
+                     InheritX emergency access settings.\n"
+                );
+                match channels
+                    .send_email(&email, "Emergency access has been requested", &body)
+                    .await
+                {
+                    Ok(_) => reached.push("email"),
+                    Err(e) => {
+                        tracing::warn!("Failed to email break-glass request {}: {}", grant.id, e);
+                        failed.push("email");
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Failed to look up owner of break-glass request: {}", e);
+                failed.push("email");
+            }
+        }
+
+        if let Err(e) = EmergencyAccessService::log_action(
+            db,
+            grant.id,
+            grant.owner_id,
+            grant.emergency_contact_id,
+            action::OWNER_NOTIFIED,
+            json!({ "channels": reached, "failed_channels": failed }),
+        )
+        .await
+        {
+            tracing::warn!("Failed to log break-glass notification: {}", e);
+        }
+    }
+
+    /// Veto a pending request. Only possible until its waiting period ends;
+    /// after that the owner must revoke the access instead.
+    pub async fn veto(
+        db: &PgPool,
+        owner_id: Uuid,
+        grant_id: Uuid,
+        req: &VetoBreakGlassRequest,
+    ) -> Result<BreakGlassGrant, ApiError> {
+        let mut tx = db.begin().await?;
+        let grant = Self::fetch_for_update(&mut tx, grant_id).await?;
+        if grant.owner_id != owner_id {
+            return Err(ApiError::NotFound(format!(
+                "Break-glass request {grant_id} not found"
+            )));
+        }
+        if grant.status != "pending" {
+            return Err(ApiError::Conflict(format!(
+                "Break-glass request is already {}",
+                grant.status
+            )));
+        }
+        if grant.effective_at <= Utc::now() {
+            return Err(ApiError::Conflict(
+                "The waiting period has ended; revoke the access instead".to_string(),
+            ));
+        }
+
+        let reason = req
+            .reason
+            .as_deref()
+            .map(str::trim)
+            .filter(|reason| !reason.is_empty());
+        sqlx::query(
+            "UPDATE emergency_access_grants \
+             SET status = 'vetoed', vetoed_at = NOW(), veto_reason = $2 \
+             WHERE id = $1",
+        )
+        .bind(grant_id)
+        .bind(reason)
+        .execute(&mut *tx)
+        .await?;
+
+        EmergencyAccessService::log_action(
+            &mut *tx,
+            grant_id,
+            owner_id,
+            grant.emergency_contact_id,
+            action::VETOED,
+            json!({ "reason": reason, "effective_at": grant.effective_at }),
+        )
+        .await?;
+        AuditLogService::log(
+            &mut *tx,
+            Some(owner_id),
+            None,
+            audit_action::EMERGENCY_ACCESS_VETOED,
+            Some(grant.plan_id),
+            Some(entity_type::PLAN),
+            None,
+            None,
+            Some(json!({ "grant_id": grant_id, "reason": reason })),
+        )
+        .await?;
+        if let Some(requested_by) = grant.requested_by {
+            NotificationService::create(
+                &mut tx,
+                requested_by,
+                notif_type::EMERGENCY_ACCESS_VETOED,
+                "The plan owner vetoed your emergency access request.",
+            )
+            .await?;
+        }
+
+        let grant = Self::fetch(&mut tx, grant_id).await?;
+        tx.commit().await?;
+        Ok(grant)
+    }
+
+    /// Requests on the owner's plans, newest first.
+    pub async fn list_for_owner(
+        db: &PgPool,
+        owner_id: Uuid,
+    ) -> Result<Vec<BreakGlassGrant>, ApiError> {
+        Self::activate_due(db).await?;
+        let grants = sqlx::query_as::<_, BreakGlassGrant>(&format!(
+            "SELECT {GRANT_COLUMNS} FROM emergency_access_grants g \
+             JOIN plans p ON p.id = g.plan_id \
+             WHERE p.user_id = $1 AND g.access_type = $2 \
+             ORDER BY g.granted_at DESC"
+        ))
+        .bind(owner_id)
+        .bind(ACCESS_TYPE)
+        .fetch_all(db)
+        .await?;
+        Ok(grants)
+    }
+
+    /// Requests the user has made as an emergency contact, newest first.
+    pub async fn list_for_requester(
+        db: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<BreakGlassGrant>, ApiError> {
+        Self::activate_due(db).await?;
+        let grants = sqlx::query_as::<_, BreakGlassGrant>(&format!(
+            "SELECT {GRANT_COLUMNS} FROM emergency_access_grants g \
+             JOIN plans p ON p.id = g.plan_id \
+             WHERE g.granted_to = $1 AND g.access_type = $2 \
+             ORDER BY g.granted_at DESC"
+        ))
+        .bind(user_id)
+        .bind(ACCESS_TYPE)
+        .fetch_all(db)
+        .await?;
+        Ok(grants)
+    }
+
+    /// The owner's plan, for the contact holding an effective grant that
+    /// includes `view_documents`.
+    pub async fn view_plan(
+        db: &PgPool,
+        user_id: Uuid,
+        grant_id: Uuid,
+    ) -> Result<BreakGlassPlanView, ApiError> {
+        let mut tx = db.begin().await?;
+        let permission = EmergencyPermission::ViewDocuments;
+        let grant =
+            EmergencyScopeService::authorize(&mut tx, user_id, grant_id, permission).await?;
+        let plan = sqlx::query_as::<_, BreakGlassPlanView>(
+            "SELECT id, title, description, status, is_active, is_paused, net_amount, \
+                    currency_preference, beneficiary_name, created_at \
+             FROM plans WHERE id = $1",
+        )
+        .bind(grant.plan_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        EmergencyScopeService::record_use(
+            &mut *tx,
+            &grant,
+            Some(permission),
+            action::PLAN_VIEWED,
+            json!({ "viewed_by": user_id }),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(plan)
+    }
+
+    /// The requester's grant if it is in force: not vetoed, revoked or
+    /// expired, and past its waiting period.
+    pub async fn effective_grant(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        grant_id: Uuid,
+    ) -> Result<BreakGlassGrant, ApiError> {
+        let grant = Self::fetch(conn, grant_id).await?;
+        if grant.requested_by != Some(user_id) {
+            return Err(ApiError::NotFound(format!(
+                "Break-glass request {grant_id} not found"
+            )));
+        }
+        Self::activate_due_for_plan(conn, grant.plan_id).await?;
+        let grant = Self::fetch(conn, grant_id).await?;
+        match grant.status.as_str() {
+            "active" if grant.expires_at.is_none_or(|at| at > Utc::now()) => Ok(grant),
+            "active" => Err(ApiError::Forbidden(
+                "Emergency access has expired".to_string(),
+            )),
+            "pending" => Err(ApiError::Forbidden(format!(
+                "Emergency access is in its waiting period until {}",
+                grant.effective_at.format("%Y-%m-%d %H:%M UTC")
+            ))),
+            status => Err(ApiError::Forbidden(format!(
+                "Emergency access was {status}"
+            ))),
+        }
+    }
+
+    /// Activate every pending request whose waiting period has passed.
+    /// Called before access checks and by the emergency access job.
+    pub async fn activate_due(db: &PgPool) -> Result<u64, ApiError> {
+        let mut tx = db.begin().await?;
+        let activated = Self::activate_where(&mut tx, None).await?;
+        tx.commit().await?;
+        Ok(activated)
+    }
+
+    async fn activate_due_for_plan(
+        conn: &mut PgConnection,
+        plan_id: Uuid,
+    ) -> Result<u64, ApiError> {
+        Self::activate_where(conn, Some(plan_id)).await
+    }
+
+    async fn activate_where(
+        conn: &mut PgConnection,
+        plan_id: Option<Uuid>,
+    ) -> Result<u64, ApiError> {
+        let due: Vec<(Uuid, Uuid, Uuid, Option<Uuid>, Uuid)> = sqlx::query_as(
+            "UPDATE emergency_access_grants g SET status = 'active' \
+             FROM plans p \
+             WHERE p.id = g.plan_id AND g.status = 'pending' AND g.effective_at <= NOW() \
+               AND ($1::uuid IS NULL OR g.plan_id = $1) \
+             RETURNING g.id, p.user_id, g.emergency_contact_id, g.granted_to, g.plan_id",
+        )
+        .bind(plan_id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        for (grant_id, owner_id, contact_id, requested_by, plan_id) in &due {
+            EmergencyAccessService::log_action(
+                &mut *conn,
+                *grant_id,
+                *owner_id,
+                *contact_id,
+                action::EFFECTIVE,
+                json!({ "activated_at": Utc::now() }),
+            )
+            .await?;
+            AuditLogService::log(
+                &mut *conn,
+                *requested_by,
+                None,
+                audit_action::EMERGENCY_ACCESS_GRANTED,
+                Some(*plan_id),
+                Some(entity_type::PLAN),
+                None,
+                None,
+                Some(json!({ "grant_id": grant_id, "access_type": ACCESS_TYPE })),
+            )
+            .await?;
+            NotificationService::create(
+                &mut *conn,
+                *owner_id,
+                notif_type::EMERGENCY_ACCESS_GRANTED,
+                "The waiting period passed without a veto, so your emergency contact now \
+                 has access to your plan.",
+            )
+            .await?;
+        }
+        Ok(due.len() as u64)
+    }
+
+    pub(crate) async fn fetch(
+        conn: &mut PgConnection,
+        grant_id: Uuid,
+    ) -> Result<BreakGlassGrant, ApiError> {
+        sqlx::query_as::<_, BreakGlassGrant>(&format!(
+            "SELECT {GRANT_COLUMNS} FROM emergency_access_grants g \
+             JOIN plans p ON p.id = g.plan_id \
+             WHERE g.id = $1 AND g.access_type = $2"
+        ))
+        .bind(grant_id)
+        .bind(ACCESS_TYPE)
+        .fetch_optional(conn)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Break-glass request {grant_id} not found")))
+    }
+
+    pub(crate) async fn fetch_for_update(
+        conn: &mut PgConnection,
+        grant_id: Uuid,
+    ) -> Result<BreakGlassGrant, ApiError> {
+        sqlx::query_as::<_, BreakGlassGrant>(&format!(
+            "SELECT {GRANT_COLUMNS} FROM emergency_access_grants g \
+             JOIN plans p ON p.id = g.plan_id \
+             WHERE g.id = $1 AND g.access_type = $2 \
+             FOR UPDATE OF g"
+        ))
+        .bind(grant_id)
+        .bind(ACCESS_TYPE)
+        .fetch_optional(conn)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Break-glass request {grant_id} not found")))
+    }
+}
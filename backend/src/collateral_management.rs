@@ -13,6 +13,7 @@ use crate::loan_lifecycle::{LoanLifecycleRecord, LoanLifecycleService};
 use crate::notifications::{audit_action, entity_type, AuditLogService};
 use crate::price_feed::PriceFeedService;
 use rust_decimal::Decimal;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use std::sync::Arc;
@@ -23,7 +24,7 @@ use uuid::Uuid;
 // ─────────────────────────────────────────────────────────────────────────────
 
 /// Request to add collateral to an existing loan
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AddCollateralRequest {
     pub loan_id: Uuid,
@@ -33,7 +34,7 @@ pub struct AddCollateralRequest {
 }
 
 /// Request to remove collateral from an existing loan
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RemoveCollateralRequest {
     pub loan_id: Uuid,
@@ -43,7 +44,7 @@ pub struct RemoveCollateralRequest {
 }
 
 /// Request to swap collateral type
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SwapCollateralRequest {
     pub loan_id: Uuid,
@@ -54,7 +55,7 @@ pub struct SwapCollateralRequest {
 }
 
 /// Collateral information response
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CollateralInfo {
     pub loan_id: Uuid,
@@ -65,7 +66,7 @@ pub struct CollateralInfo {
 }
 
 /// Collateral requirements response
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CollateralRequirements {
     pub loan_id: Uuid,
@@ -80,7 +81,7 @@ pub struct CollateralRequirements {
 }
 
 /// Safe withdrawal calculation response
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SafeWithdrawalInfo {
     pub loan_id: Uuid,
@@ -1,4 +1,5 @@
 use crate::api_error::ApiError;
+use crate::metrics::observe_job;
 use crate::notifications::{
     audit_action, entity_type, notif_type, AuditLogService, NotificationService,
 };
@@ -36,7 +37,9 @@ impl ComplianceEngine {
             let mut interval = tokio::time::interval(Duration::from_secs(300)); // Every 5 minutes
             loop {
                 interval.tick().await;
-                if let Err(e) = self.scan_suspicious_activity().await {
+                if let Err(e) =
+                    observe_job("compliance_engine", self.scan_suspicious_activity()).await
+                {
                     error!("Compliance Engine error: {}", e);
                 }
             }
@@ -1,11 +1,23 @@
 use crate::api_error::ApiError;
 use serde::Deserialize;
 
+/// `APP_ENV` profiles in which test authentication may be enabled.
+pub const TEST_AUTH_PROFILES: &[&str] = &["development", "test"];
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct Config {
     pub database_url: String,
     pub port: u16,
     pub jwt_secret: String,
+    /// Deployment profile from `APP_ENV`, such as `development`, `test`,
+    /// `staging` or `production`; `None` when unset.
+    pub environment: Option<String>,
+    /// Enables the test token issuer (`TEST_AUTH_ENABLED`). Only allowed in
+    /// the [`TEST_AUTH_PROFILES`]; see [`crate::test_auth`].
+    pub test_auth: bool,
+    /// Admins must sign in with a passkey (`ADMIN_PASSKEYS_REQUIRED`); see
+    /// [`crate::webauthn`].
+    pub require_admin_passkeys: bool,
 }
 
 impl Config {
@@ -23,10 +35,51 @@ impl Config {
         let jwt_secret = std::env::var("JWT_SECRET")
             .map_err(|_| ApiError::Internal(anyhow::anyhow!("JWT_SECRET must be set")))?;
 
-        Ok(Config {
+        let environment = std::env::var("APP_ENV")
+            .ok()
+            .map(|env| env.trim().to_string())
+            .filter(|env| !env.is_empty());
+
+        let test_auth = env_flag("TEST_AUTH_ENABLED");
+        let require_admin_passkeys = env_flag("ADMIN_PASSKEYS_REQUIRED");
+
+        let config = Config {
             database_url,
             port,
             jwt_secret,
+            environment,
+            test_auth,
+            require_admin_passkeys,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Whether `APP_ENV` names a profile that may enable test authentication.
+    /// An unset or unrecognised profile does not, so a deployment that forgets
+    /// `APP_ENV` cannot serve test tokens.
+    pub fn allows_test_auth(&self) -> bool {
+        self.environment.as_deref().is_some_and(|env| {
+            TEST_AUTH_PROFILES
+                .iter()
+                .any(|profile| env.eq_ignore_ascii_case(profile))
         })
     }
+
+    /// Reject settings that must never reach production.
+    pub fn validate(&self) -> Result<(), ApiError> {
+        if self.test_auth && !self.allows_test_auth() {
+            return Err(ApiError::Internal(anyhow::anyhow!(
+                "TEST_AUTH_ENABLED requires APP_ENV to be one of: {}",
+                TEST_AUTH_PROFILES.join(", ")
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn env_flag(name: &str) -> bool {
+    std::env::var(name)
+        .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
 }
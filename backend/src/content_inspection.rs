@@ -0,0 +1,840 @@
+//! Post-upload inspection pipeline for legacy content.
+//!
+//! Every uploaded file starts with `inspection_status = 'pending'` and cannot
+//! be downloaded until [`ContentInspectionService`] has passed it:
+//!
+//!   1. Magic-byte sniffing – the bytes must match the client-declared type.
+//!   2. Malware scan        – through the pluggable [`MalwareScanner`] hook;
+//!      [`ClamdScanner`] speaks the clamd `INSTREAM` protocol.
+//!   3. Metadata stripping  – EXIF (including GPS), XMP and text chunks are
+//!      removed from JPEG and PNG images, and the sanitized bytes replace the
+//!      stored object.
+//!   4. Previews            – thumbnails for images, page count and a text
+//!      excerpt for PDFs.
+//!
+//! The scanner is selected by `MALWARE_SCANNER` (`clamd` or `none`, default
+//! `none`) with `CLAMD_ADDRESS` (default `127.0.0.1:3310`).
+
+use crate::api_error::ApiError;
+use crate::object_storage::{ObjectStorageService, ObjectStore};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+const INSPECTION_BATCH_SIZE: i64 = 20;
+const THUMBNAIL_MAX_DIMENSION: u32 = 256;
+const PDF_EXCERPT_CHARS: usize = 500;
+const CLAMD_CHUNK_SIZE: usize = 64 * 1024;
+
+// ── Magic-byte sniffing ───────────────────────────────────────────────────────
+
+/// Container formats recognisable from their leading bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SniffedFormat {
+    /// ISO base media (MP4, QuickTime, M4A): `ftyp` box at offset 4.
+    IsoBmff,
+    MpegProgramStream,
+    Avi,
+    /// Matroska / WebM.
+    Ebml,
+    Mp3,
+    Wav,
+    Ogg,
+    Pdf,
+    /// OLE compound file (legacy Word and Excel).
+    Ole,
+    /// ZIP container (OOXML documents).
+    Zip,
+    Jpeg,
+    Png,
+    /// UTF-8 text without NUL bytes.
+    Text,
+}
+
+impl SniffedFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::IsoBmff => "iso-bmff",
+            Self::MpegProgramStream => "mpeg-ps",
+            Self::Avi => "avi",
+            Self::Ebml => "ebml",
+            Self::Mp3 => "mp3",
+            Self::Wav => "wav",
+            Self::Ogg => "ogg",
+            Self::Pdf => "pdf",
+            Self::Ole => "ole",
+            Self::Zip => "zip",
+            Self::Jpeg => "jpeg",
+            Self::Png => "png",
+            Self::Text => "text",
+        }
+    }
+
+    /// The format a declared MIME type must sniff as.
+    pub fn expected_for(content_type: &str) -> Option<Self> {
+        Some(match content_type {
+            "video/mp4" | "video/quicktime" | "audio/mp4" => Self::IsoBmff,
+            "video/mpeg" => Self::MpegProgramStream,
+            "video/x-msvideo" => Self::Avi,
+            "video/webm" | "audio/webm" => Self::Ebml,
+            "audio/mpeg" => Self::Mp3,
+            "audio/wav" => Self::Wav,
+            "audio/ogg" => Self::Ogg,
+            "text/plain" | "text/markdown" | "text/html" => Self::Text,
+            "application/pdf" => Self::Pdf,
+            "application/msword" | "application/vnd.ms-excel" => Self::Ole,
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+            | "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet" => Self::Zip,
+            "image/jpeg" => Self::Jpeg,
+            "image/png" => Self::Png,
+            _ => return None,
+        })
+    }
+}
+
+/// Identify a file's format from its leading bytes.
+pub fn sniff_format(bytes: &[u8]) -> Option<SniffedFormat> {
+    let starts = |prefix: &[u8]| bytes.starts_with(prefix);
+    let riff = |form: &[u8]| starts(b"RIFF") && bytes.get(8..12) == Some(form);
+
+    let format = if starts(&[0xFF, 0xD8, 0xFF]) {
+        SniffedFormat::Jpeg
+    } else if starts(b"\x89PNG\r\n\x1a\n") {
+        SniffedFormat::Png
+    } else if starts(b"%PDF-") {
+        SniffedFormat::Pdf
+    } else if starts(&[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1]) {
+        SniffedFormat::Ole
+    } else if starts(b"PK\x03\x04") {
+        SniffedFormat::Zip
+    } else if bytes.get(4..8) == Some(b"ftyp") {
+        SniffedFormat::IsoBmff
+    } else if starts(&[0x00, 0x00, 0x01, 0xBA]) || starts(&[0x00, 0x00, 0x01, 0xB3]) {
+        SniffedFormat::MpegProgramStream
+    } else if riff(b"AVI ") {
+        SniffedFormat::Avi
+    } else if riff(b"WAVE") {
+        SniffedFormat::Wav
+    } else if starts(&[0x1A, 0x45, 0xDF, 0xA3]) {
+        SniffedFormat::Ebml
+    } else if starts(b"OggS") {
+        SniffedFormat::Ogg
+    } else if starts(b"ID3") || (bytes.len() > 1 && bytes[0] == 0xFF && bytes[1] & 0xE0 == 0xE0) {
+        SniffedFormat::Mp3
+    } else if !bytes.contains(&0) && std::str::from_utf8(bytes).is_ok() {
+        SniffedFormat::Text
+    } else {
+        return None;
+    };
+    Some(format)
+}
+
+// ── Metadata stripping ────────────────────────────────────────────────────────
+
+/// Remove EXIF/XMP (APP1), IPTC (APP13) and comment segments from a JPEG.
+/// Returns the sanitized bytes and the names of the removed segments.
+pub fn strip_jpeg_metadata(bytes: &[u8]) -> Result<(Vec<u8>, Vec<&'static str>), ApiError> {
+    let malformed = || ApiError::BadRequest("Malformed JPEG".to_string());
+    if !bytes.starts_with(&[0xFF, 0xD8]) {
+        return Err(malformed());
+    }
+
+    let mut out = Vec::with_capacity(bytes.len());
+    out.extend_from_slice(&bytes[..2]);
+    let mut removed = Vec::new();
+    let mut i = 2;
+
+    while i < bytes.len() {
+        if bytes[i] != 0xFF {
+            return Err(malformed());
+        }
+        // Skip fill bytes between segments.
+        let mut marker_at = i + 1;
+        while bytes.get(marker_at) == Some(&0xFF) {
+            marker_at += 1;
+        }
+        let marker = *bytes.get(marker_at).ok_or_else(malformed)?;
+        let segment_start = marker_at - 1;
+
+        match marker {
+            // Start of scan: the entropy-coded image data follows; copy the rest.
+            0xDA => {
+                out.extend_from_slice(&bytes[segment_start..]);
+                return Ok((out, removed));
+            }
+            // Markers without a length field.
+            0x01 | 0xD0..=0xD7 | 0xD9 => {
+                out.extend_from_slice(&bytes[segment_start..=marker_at]);
+                i = marker_at + 1;
+            }
+            _ => {
+                let len_bytes = bytes
+                    .get(marker_at + 1..marker_at + 3)
+                    .ok_or_else(malformed)?;
+                let len = u16::from_be_bytes([len_bytes[0], len_bytes[1]]) as usize;
+                let end = marker_at + 1 + len;
+                if len < 2 || end > bytes.len() {
+                    return Err(malformed());
+                }
+                let segment = &bytes[segment_start..end];
+                match marker {
+                    0xE1 => removed.push(if segment[4..].starts_with(b"Exif\0") {
+                        "exif"
+                    } else {
+                        "xmp"
+                    }),
+                    0xED => removed.push("iptc"),
+                    0xFE => removed.push("comment"),
+                    _ => out.extend_from_slice(segment),
+                }
+                i = end;
+            }
+        }
+    }
+
+    Ok((out, removed))
+}
+
+/// Remove `eXIf`, textual (`tEXt`, `zTXt`, `iTXt`) and `tIME` chunks from a PNG.
+pub fn strip_png_metadata(bytes: &[u8]) -> Result<(Vec<u8>, Vec<&'static str>), ApiError> {
+    let malformed = || ApiError::BadRequest("Malformed PNG".to_string());
+    if !bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return Err(malformed());
+    }
+
+    let mut out = Vec::with_capacity(bytes.len());
+    out.extend_from_slice(&bytes[..8]);
+    let mut removed = Vec::new();
+    let mut i = 8;
+
+    while i < bytes.len() {
+        let header = bytes.get(i..i + 8).ok_or_else(malformed)?;
+        let len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        let end = i
+            .checked_add(12)
+            .and_then(|n| n.checked_add(len))
+            .filter(|end| *end <= bytes.len())
+            .ok_or_else(malformed)?;
+
+        match &header[4..8] {
+            b"eXIf" => removed.push("exif"),
+            b"tEXt" | b"zTXt" | b"iTXt" => removed.push("text"),
+            b"tIME" => removed.push("time"),
+            _ => out.extend_from_slice(&bytes[i..end]),
+        }
+        i = end;
+    }
+
+    Ok((out, removed))
+}
+
+// ── Previews ──────────────────────────────────────────────────────────────────
+
+/// Downscale an image to a JPEG thumbnail that fits in a 256px square.
+pub fn generate_thumbnail(bytes: &[u8]) -> Result<Vec<u8>, ApiError> {
+    let image = image::load_from_memory(bytes)
+        .map_err(|e| ApiError::BadRequest(format!("Image could not be decoded: {}", e)))?;
+    let thumbnail = image
+        .thumbnail(THUMBNAIL_MAX_DIMENSION, THUMBNAIL_MAX_DIMENSION)
+        .to_rgb8();
+
+    let mut out = std::io::Cursor::new(Vec::new());
+    thumbnail
+        .write_to(&mut out, image::ImageFormat::Jpeg)
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to encode thumbnail: {}", e)))?;
+    Ok(out.into_inner())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PdfPreview {
+    pub page_count: usize,
+    pub text_excerpt: String,
+}
+
+/// Page count and an excerpt of the first page's text.
+pub fn generate_pdf_preview(bytes: &[u8]) -> Result<PdfPreview, ApiError> {
+    let document = lopdf::Document::load_mem(bytes)
+        .map_err(|e| ApiError::BadRequest(format!("PDF could not be parsed: {}", e)))?;
+    let page_count = document.get_pages().len();
+    let text = if page_count > 0 {
+        document.extract_text(&[1]).unwrap_or_default()
+    } else {
+        String::new()
+    };
+    let text_excerpt = text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(PDF_EXCERPT_CHARS)
+        .collect();
+
+    Ok(PdfPreview {
+        page_count,
+        text_excerpt,
+    })
+}
+
+// ── Malware scanning ──────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanVerdict {
+    Clean,
+    Infected(String),
+}
+
+#[async_trait]
+pub trait MalwareScanner: Send + Sync {
+    async fn scan(&self, bytes: &[u8]) -> Result<ScanVerdict, ApiError>;
+
+    /// Scanner name recorded in inspection results.
+    fn name(&self) -> &'static str;
+}
+
+/// Used when no scanner is configured; every file is reported clean.
+pub struct DisabledScanner;
+
+#[async_trait]
+impl MalwareScanner for DisabledScanner {
+    async fn scan(&self, _bytes: &[u8]) -> Result<ScanVerdict, ApiError> {
+        Ok(ScanVerdict::Clean)
+    }
+
+    fn name(&self) -> &'static str {
+        "disabled"
+    }
+}
+
+/// Streams files to a clamd daemon using the `zINSTREAM` command.
+pub struct ClamdScanner {
+    address: String,
+    timeout: Duration,
+}
+
+impl ClamdScanner {
+    pub fn new(address: String) -> Self {
+        Self {
+            address,
+            timeout: Duration::from_secs(60),
+        }
+    }
+
+    async fn scan_stream(&self, bytes: &[u8]) -> Result<String, std::io::Error> {
+        let mut stream = tokio::net::TcpStream::connect(&self.address).await?;
+        stream.write_all(b"zINSTREAM\0").await?;
+        for chunk in bytes.chunks(CLAMD_CHUNK_SIZE) {
+            stream
+                .write_all(&(chunk.len() as u32).to_be_bytes())
+                .await?;
+            stream.write_all(chunk).await?;
+        }
+        stream.write_all(&0u32.to_be_bytes()).await?;
+        stream.flush().await?;
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).await?;
+        Ok(String::from_utf8_lossy(&response)
+            .trim_end_matches(['\0', '\n'])
+            .to_string())
+    }
+}
+
+/// Parse a clamd reply such as `stream: OK` or `stream: Eicar-Signature FOUND`.
+pub fn parse_clamd_response(response: &str) -> Result<ScanVerdict, ApiError> {
+    let result = response.strip_prefix("stream: ").unwrap_or(response).trim();
+    if result == "OK" {
+        Ok(ScanVerdict::Clean)
+    } else if let Some(signature) = result.strip_suffix(" FOUND") {
+        Ok(ScanVerdict::Infected(signature.to_string()))
+    } else {
+        Err(ApiError::Internal(anyhow::anyhow!(
+            "Unexpected clamd response: {}",
+            response
+        )))
+    }
+}
+
+#[async_trait]
+impl MalwareScanner for ClamdScanner {
+    async fn scan(&self, bytes: &[u8]) -> Result<ScanVerdict, ApiError> {
+        let response = tokio::time::timeout(self.timeout, self.scan_stream(bytes))
+            .await
+            .map_err(|_| ApiError::Internal(anyhow::anyhow!("clamd scan timed out")))?
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("clamd scan failed: {}", e)))?;
+        parse_clamd_response(&response)
+    }
+
+    fn name(&self) -> &'static str {
+        "clamd"
+    }
+}
+
+/// Constructs the scanner selected by `MALWARE_SCANNER`.
+pub fn build_malware_scanner() -> Arc<dyn MalwareScanner> {
+    match std::env::var("MALWARE_SCANNER").as_deref() {
+        Ok("clamd") => {
+            let address =
+                std::env::var("CLAMD_ADDRESS").unwrap_or_else(|_| "127.0.0.1:3310".to_string());
+            info!("Using clamd malware scanner at {}", address);
+            Arc::new(ClamdScanner::new(address))
+        }
+        _ => {
+            warn!("No malware scanner configured; uploaded content will not be scanned");
+            Arc::new(DisabledScanner)
+        }
+    }
+}
+
+// ── Pipeline ──────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InspectionStatus {
+    Pending,
+    Passed,
+    Rejected,
+    Infected,
+    Error,
+}
+
+impl InspectionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Passed => "passed",
+            Self::Rejected => "rejected",
+            Self::Infected => "infected",
+            Self::Error => "error",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InspectionBatchResult {
+    pub inspected: usize,
+    pub passed: usize,
+    pub rejected: usize,
+    pub infected: usize,
+    pub errors: usize,
+}
+
+#[derive(sqlx::FromRow)]
+struct PendingContent {
+    id: Uuid,
+    content_type: String,
+    storage_path: String,
+    file_hash: String,
+}
+
+/// Background job that inspects newly uploaded content.
+pub struct ContentInspectionService {
+    db: PgPool,
+    store: Arc<dyn ObjectStore>,
+    scanner: Arc<dyn MalwareScanner>,
+}
+
+impl ContentInspectionService {
+    pub fn new(db: PgPool, store: Arc<dyn ObjectStore>, scanner: Arc<dyn MalwareScanner>) -> Self {
+        Self { db, store, scanner }
+    }
+
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(15));
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.inspect_pending().await {
+                    error!("Content inspection error: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Inspect one batch of pending uploads, oldest first.
+    pub async fn inspect_pending(&self) -> Result<InspectionBatchResult, ApiError> {
+        let ids: Vec<Uuid> = sqlx::query_scalar(
+            "SELECT id FROM legacy_content \
+             WHERE inspection_status = 'pending' AND status = 'active' \
+             ORDER BY created_at ASC LIMIT $1",
+        )
+        .bind(INSPECTION_BATCH_SIZE)
+        .fetch_all(&self.db)
+        .await?;
+
+        let mut result = InspectionBatchResult::default();
+        for id in ids {
+            result.inspected += 1;
+            match self.inspect_content(id).await {
+                Ok(InspectionStatus::Passed) => result.passed += 1,
+                Ok(InspectionStatus::Rejected) => result.rejected += 1,
+                Ok(InspectionStatus::Infected) => result.infected += 1,
+                Ok(_) => result.errors += 1,
+                Err(e) => {
+                    // Left pending so the next run retries it.
+                    result.errors += 1;
+                    warn!("Failed to inspect content {}: {}", id, e);
+                }
+            }
+        }
+
+        if result.rejected + result.infected > 0 {
+            info!(
+                "Content inspection: {} passed, {} rejected, {} infected",
+                result.passed, result.rejected, result.infected
+            );
+        }
+        Ok(result)
+    }
+
+    /// Run the pipeline for a single upload and record the outcome.
+    pub async fn inspect_content(&self, content_id: Uuid) -> Result<InspectionStatus, ApiError> {
+        let content = sqlx::query_as::<_, PendingContent>(
+            "SELECT id, content_type, storage_path, file_hash FROM legacy_content \
+             WHERE id = $1 AND inspection_status = 'pending'",
+        )
+        .bind(content_id)
+        .fetch_optional(&self.db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Pending content not found".to_string()))?;
+
+        let bytes = match ObjectStorageService::fetch_verified(
+            self.store.as_ref(),
+            &content.storage_path,
+            &content.file_hash,
+        )
+        .await
+        {
+            Ok(bytes) => bytes,
+            Err(ApiError::NotFound(_)) => {
+                return self
+                    .record(
+                        &content,
+                        InspectionStatus::Error,
+                        json!({ "reason": "Stored content is missing" }),
+                        None,
+                    )
+                    .await;
+            }
+            Err(e) => return Err(e),
+        };
+
+        // 1. The bytes must be what the client said they are.
+        let sniffed = sniff_format(&bytes);
+        let expected = SniffedFormat::expected_for(&content.content_type);
+        if sniffed.is_none() || sniffed != expected {
+            return self
+                .record(
+                    &content,
+                    InspectionStatus::Rejected,
+                    json!({
+                        "reason": "File contents do not match the declared content type",
+                        "declared_type": content.content_type,
+                        "detected_format": sniffed.map(|f| f.as_str()),
+                    }),
+                    None,
+                )
+                .await;
+        }
+
+        // 2. Malware scan of the bytes as uploaded.
+        if let ScanVerdict::Infected(signature) = self.scanner.scan(&bytes).await? {
+            return self
+                .record(
+                    &content,
+                    InspectionStatus::Infected,
+                    json!({ "scanner": self.scanner.name(), "signature": signature }),
+                    None,
+                )
+                .await;
+        }
+
+        let mut details = json!({
+            "scanner": self.scanner.name(),
+            "detected_format": sniffed.map(|f| f.as_str()),
+        });
+
+        // 3. Strip location and other identifying metadata from images.
+        let stripped = match sniffed {
+            Some(SniffedFormat::Jpeg) => Some(strip_jpeg_metadata(&bytes)),
+            Some(SniffedFormat::Png) => Some(strip_png_metadata(&bytes)),
+            _ => None,
+        };
+        let bytes = match stripped {
+            Some(Err(e)) => {
+                return self
+                    .record(
+                        &content,
+                        InspectionStatus::Rejected,
+                        json!({ "reason": e.to_string() }),
+                        None,
+                    )
+                    .await;
+            }
+            Some(Ok((sanitized, removed))) if !removed.is_empty() => {
+                self.replace_content(&content, &sanitized).await?;
+                details["metadata_removed"] = json!(removed);
+                details["original_hash"] = json!(content.file_hash);
+                sanitized
+            }
+            _ => bytes,
+        };
+
+        // 4. Previews.
+        let mut thumbnail_key = None;
+        match sniffed {
+            Some(SniffedFormat::Jpeg | SniffedFormat::Png) => match generate_thumbnail(&bytes) {
+                Ok(thumbnail) => {
+                    let object =
+                        ObjectStorageService::store(&self.db, self.store.as_ref(), &thumbnail)
+                            .await?;
+                    thumbnail_key = Some(object.object_key);
+                }
+                Err(e) => {
+                    return self
+                        .record(
+                            &content,
+                            InspectionStatus::Rejected,
+                            json!({ "reason": e.to_string() }),
+                            None,
+                        )
+                        .await;
+                }
+            },
+            Some(SniffedFormat::Pdf) => match generate_pdf_preview(&bytes) {
+                Ok(preview) => details["preview"] = json!(preview),
+                Err(e) => details["preview_error"] = json!(e.to_string()),
+            },
+            _ => {}
+        }
+
+        self.record(&content, InspectionStatus::Passed, details, thumbnail_key)
+            .await
+    }
+
+    /// Point the content row at sanitized bytes and drop the original object
+    /// if nothing else references it.
+    async fn replace_content(
+        &self,
+        content: &PendingContent,
+        sanitized: &[u8],
+    ) -> Result<(), ApiError> {
+        let object = ObjectStorageService::store(&self.db, self.store.as_ref(), sanitized).await?;
+        sqlx::query(
+            "UPDATE legacy_content \
+             SET storage_path = $1, file_hash = $2, file_size = $3, updated_at = NOW() \
+             WHERE id = $4",
+        )
+        .bind(&object.object_key)
+        .bind(&object.sha256)
+        .bind(object.size_bytes)
+        .bind(content.id)
+        .execute(&self.db)
+        .await?;
+
+        let still_referenced: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM legacy_content WHERE storage_path = $1)",
+        )
+        .bind(&content.storage_path)
+        .fetch_one(&self.db)
+        .await?;
+        if !still_referenced {
+            self.store.delete(&content.storage_path).await?;
+            sqlx::query("DELETE FROM stored_objects WHERE object_key = $1")
+                .bind(&content.storage_path)
+                .execute(&self.db)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn record(
+        &self,
+        content: &PendingContent,
+        status: InspectionStatus,
+        details: serde_json::Value,
+        thumbnail_key: Option<String>,
+    ) -> Result<InspectionStatus, ApiError> {
+        sqlx::query(
+            "UPDATE legacy_content \
+             SET inspection_status = $1, inspection_details = $2, thumbnail_key = $3, \
+                 inspected_at = NOW(), updated_at = NOW() \
+             WHERE id = $4",
+        )
+        .bind(status.as_str())
+        .bind(details)
+        .bind(thumbnail_key)
+        .bind(content.id)
+        .execute(&self.db)
+        .await?;
+        Ok(status)
+    }
+}
+
+/// Refuse delivery of content that has not passed inspection.
+pub fn ensure_deliverable(
+    inspection_status: &str,
+    inspected_at: Option<DateTime<Utc>>,
+) -> Result<(), ApiError> {
+    match inspection_status {
+        "passed" => Ok(()),
+        "pending" => Err(ApiError::Conflict(
+            "Content is still being inspected".to_string(),
+        )),
+        other => Err(ApiError::Forbidden(format!(
+            "Content failed inspection ({}){}",
+            other,
+            inspected_at
+                .map(|at| format!(" at {}", at.to_rfc3339()))
+                .unwrap_or_default()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jpeg_with_exif() -> Vec<u8> {
+        let image = image::RgbImage::from_pixel(32, 16, image::Rgb([200, 30, 30]));
+        let mut encoded = std::io::Cursor::new(Vec::new());
+        image
+            .write_to(&mut encoded, image::ImageFormat::Jpeg)
+            .unwrap();
+        let encoded = encoded.into_inner();
+
+        // Splice an APP1 Exif segment carrying a fake GPS IFD after SOI.
+        let payload = b"Exif\0\0MM\0*\0\0\0\x08GPSLatitude=51.5N";
+        let mut out = vec![0xFF, 0xD8, 0xFF, 0xE1];
+        out.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+        out.extend_from_slice(payload);
+        out.extend_from_slice(&encoded[2..]);
+        out
+    }
+
+    fn png_with_text() -> Vec<u8> {
+        let image = image::RgbImage::from_pixel(8, 8, image::Rgb([0, 0, 255]));
+        let mut encoded = std::io::Cursor::new(Vec::new());
+        image
+            .write_to(&mut encoded, image::ImageFormat::Png)
+            .unwrap();
+        let encoded = encoded.into_inner();
+
+        // Insert a tEXt chunk right after IHDR (8 + 25 bytes).
+        let data = b"Location\0Home address";
+        let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+        chunk.extend_from_slice(b"tEXt");
+        chunk.extend_from_slice(data);
+        chunk.extend_from_slice(&[0, 0, 0, 0]);
+        let mut out = encoded[..33].to_vec();
+        out.extend_from_slice(&chunk);
+        out.extend_from_slice(&encoded[33..]);
+        out
+    }
+
+    #[test]
+    fn test_sniff_common_formats() {
+        assert_eq!(sniff_format(b"%PDF-1.7\n"), Some(SniffedFormat::Pdf));
+        assert_eq!(
+            sniff_format(b"\0\0\0\x18ftypmp42"),
+            Some(SniffedFormat::IsoBmff)
+        );
+        assert_eq!(
+            sniff_format(b"RIFF\0\0\0\0WAVEfmt "),
+            Some(SniffedFormat::Wav)
+        );
+        assert_eq!(sniff_format(b"ID3\x04"), Some(SniffedFormat::Mp3));
+        assert_eq!(sniff_format(b"PK\x03\x04"), Some(SniffedFormat::Zip));
+        assert_eq!(sniff_format(b"hello heirs"), Some(SniffedFormat::Text));
+        assert_eq!(sniff_format(&jpeg_with_exif()), Some(SniffedFormat::Jpeg));
+        assert_eq!(sniff_format(b"MZ\x90\0\x03\0"), None);
+    }
+
+    #[test]
+    fn test_expected_format_for_declared_types() {
+        assert_eq!(
+            SniffedFormat::expected_for("video/quicktime"),
+            Some(SniffedFormat::IsoBmff)
+        );
+        assert_eq!(
+            SniffedFormat::expected_for("application/vnd.ms-excel"),
+            Some(SniffedFormat::Ole)
+        );
+        assert_eq!(SniffedFormat::expected_for("application/exe"), None);
+    }
+
+    #[test]
+    fn test_strip_jpeg_removes_exif_and_keeps_image() {
+        let original = jpeg_with_exif();
+        let (stripped, removed) = strip_jpeg_metadata(&original).unwrap();
+        assert_eq!(removed, vec!["exif"]);
+        assert!(!stripped
+            .windows(b"GPSLatitude".len())
+            .any(|w| w == b"GPSLatitude"));
+        assert!(image::load_from_memory(&stripped).is_ok());
+    }
+
+    #[test]
+    fn test_strip_jpeg_rejects_truncated_segment() {
+        assert!(strip_jpeg_metadata(&[0xFF, 0xD8, 0xFF, 0xE1, 0x00, 0x40, 0x00]).is_err());
+    }
+
+    #[test]
+    fn test_strip_png_removes_text_chunks() {
+        let original = png_with_text();
+        let (stripped, removed) = strip_png_metadata(&original).unwrap();
+        assert_eq!(removed, vec!["text"]);
+        assert_eq!(stripped.len(), original.len() - (12 + 21));
+        assert!(image::load_from_memory(&stripped).is_ok());
+    }
+
+    #[test]
+    fn test_thumbnail_fits_bounds() {
+        let image = image::RgbImage::from_pixel(1024, 512, image::Rgb([10, 200, 10]));
+        let mut encoded = std::io::Cursor::new(Vec::new());
+        image
+            .write_to(&mut encoded, image::ImageFormat::Png)
+            .unwrap();
+
+        let thumbnail = generate_thumbnail(&encoded.into_inner()).unwrap();
+        let decoded = image::load_from_memory(&thumbnail).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (256, 128));
+    }
+
+    #[test]
+    fn test_thumbnail_rejects_garbage() {
+        assert!(generate_thumbnail(b"\xFF\xD8\xFFnot really a jpeg").is_err());
+    }
+
+    #[test]
+    fn test_parse_clamd_response() {
+        assert_eq!(
+            parse_clamd_response("stream: OK").unwrap(),
+            ScanVerdict::Clean
+        );
+        assert_eq!(
+            parse_clamd_response("stream: Eicar-Test-Signature FOUND").unwrap(),
+            ScanVerdict::Infected("Eicar-Test-Signature".to_string())
+        );
+        assert!(parse_clamd_response("INSTREAM size limit exceeded. ERROR").is_err());
+    }
+
+    #[test]
+    fn test_ensure_deliverable() {
+        assert!(ensure_deliverable("passed", None).is_ok());
+        assert!(matches!(
+            ensure_deliverable("pending", None),
+            Err(ApiError::Conflict(_))
+        ));
+        assert!(matches!(
+            ensure_deliverable("infected", Some(Utc::now())),
+            Err(ApiError::Forbidden(_))
+        ));
+    }
+}
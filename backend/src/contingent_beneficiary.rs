@@ -9,6 +9,7 @@ use crate::notifications::{
 };
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use std::fmt;
@@ -104,7 +105,7 @@ impl FromStr for ContingencyCondition {
 // ─────────────────────────────────────────────────────────────────────────────
 
 /// Contingent beneficiary record
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ContingentBeneficiary {
     pub id: Uuid,
@@ -123,7 +124,7 @@ pub struct ContingentBeneficiary {
 }
 
 /// Request to add a contingent beneficiary
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AddContingentBeneficiaryRequest {
     pub plan_id: Uuid,
@@ -142,7 +143,7 @@ pub struct RemoveContingentBeneficiaryRequest {
 }
 
 /// Request to set contingency conditions
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SetContingencyConditionsRequest {
     pub plan_id: Uuid,
@@ -153,7 +154,7 @@ pub struct SetContingencyConditionsRequest {
 }
 
 /// Request to promote contingent to primary
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PromoteContingentRequest {
     pub beneficiary_id: Uuid,
@@ -161,7 +162,7 @@ pub struct PromoteContingentRequest {
 }
 
 /// Contingency configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ContingencyConfig {
     pub id: Uuid,
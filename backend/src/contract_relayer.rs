@@ -0,0 +1,98 @@
+//! Client for the contract relayer.
+//!
+//! The backend does not hold a Stellar signing key. Contract calls that the
+//! platform makes as the inheritance contract's admin are handed to a relayer
+//! service that holds the admin key, builds and signs the Soroban transaction,
+//! and reports the transaction hash once it is submitted.
+//!
+//! The relayer is configured with:
+//!   - `CONTRACT_RELAYER_URL`   – base URL of the relayer
+//!   - `CONTRACT_RELAYER_TOKEN` – optional bearer token sent with each call
+
+use crate::api_error::ApiError;
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+#[derive(Deserialize)]
+struct InvokeResponse {
+    tx_hash: String,
+}
+
+pub struct ContractRelayer {
+    url: String,
+    token: Option<String>,
+    client: reqwest::Client,
+}
+
+impl ContractRelayer {
+    pub fn new(url: String) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            token: None,
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(60))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn with_token(mut self, token: String) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Invoke `function` on the inheritance contract with named `args` and
+    /// return the hash of the submitted transaction.
+    pub async fn invoke(
+        &self,
+        function: &str,
+        args: serde_json::Value,
+    ) -> Result<String, ApiError> {
+        let mut request = self
+            .client
+            .post(format!("{}/invoke", self.url))
+            .json(&json!({ "function": function, "args": args }));
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await.map_err(|e| {
+            ApiError::Internal(anyhow::anyhow!("Contract relayer request failed: {}", e))
+        })?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ApiError::Internal(anyhow::anyhow!(
+                "Contract relayer returned HTTP {} for {}: {}",
+                status,
+                function,
+                body
+            )));
+        }
+        let body: InvokeResponse = response.json().await.map_err(|e| {
+            ApiError::Internal(anyhow::anyhow!("Invalid contract relayer response: {}", e))
+        })?;
+        Ok(body.tx_hash)
+    }
+}
+
+/// Constructs the relayer selected by `CONTRACT_RELAYER_URL`, or `None` when
+/// no relayer is configured.
+pub fn build_contract_relayer() -> Option<Arc<ContractRelayer>> {
+    let Ok(url) = std::env::var("CONTRACT_RELAYER_URL") else {
+        warn!("No contract relayer configured; contract calls will stay queued");
+        return None;
+    };
+    let mut relayer = ContractRelayer::new(url);
+    if let Ok(token) = std::env::var("CONTRACT_RELAYER_TOKEN") {
+        relayer = relayer.with_token(token);
+    }
+    info!("Submitting contract calls through {}", relayer.url());
+    Some(Arc::new(relayer))
+}
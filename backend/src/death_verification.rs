@@ -0,0 +1,129 @@
+use crate::api_error::ApiError;
+use crate::notifications::{audit_action, entity_type, AuditLogService};
+use chrono::{DateTime, NaiveDate, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
+pub struct DeathVerification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub verified_by_admin_id: Uuid,
+    pub evidence: String,
+    pub date_of_death: Option<NaiveDate>,
+    pub verified_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RecordDeathVerificationRequest {
+    pub evidence: String,
+    pub date_of_death: Option<NaiveDate>,
+}
+
+// ─── Service ─────────────────────────────────────────────────────────────────
+
+/// Verified death events. A user has at most one; features that only unlock
+/// after death (such as trustee key release) check for it here.
+pub struct DeathVerificationService;
+
+impl DeathVerificationService {
+    pub async fn record(
+        db: &PgPool,
+        admin_id: Uuid,
+        user_id: Uuid,
+        req: &RecordDeathVerificationRequest,
+    ) -> Result<DeathVerification, ApiError> {
+        if req.evidence.trim().is_empty() {
+            return Err(ApiError::BadRequest("evidence cannot be empty".to_string()));
+        }
+        if req
+            .date_of_death
+            .is_some_and(|d| d > Utc::now().date_naive())
+        {
+            return Err(ApiError::BadRequest(
+                "date_of_death cannot be in the future".to_string(),
+            ));
+        }
+
+        let user_exists: bool =
+            sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)")
+                .bind(user_id)
+                .fetch_one(db)
+                .await?;
+        if !user_exists {
+            return Err(ApiError::NotFound(format!("User {user_id} not found")));
+        }
+
+        let mut tx = db.begin().await?;
+        let verification = Self::record_in(
+            &mut tx,
+            admin_id,
+            user_id,
+            req.evidence.trim(),
+            req.date_of_death,
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(verification)
+    }
+
+    /// Record a verified death inside the caller's transaction. Used directly
+    /// when a death verification case is approved.
+    pub async fn record_in(
+        conn: &mut PgConnection,
+        admin_id: Uuid,
+        user_id: Uuid,
+        evidence: &str,
+        date_of_death: Option<NaiveDate>,
+    ) -> Result<DeathVerification, ApiError> {
+        let verification = sqlx::query_as::<_, DeathVerification>(
+            "INSERT INTO death_verifications (user_id, verified_by_admin_id, evidence, date_of_death) \
+             VALUES ($1, $2, $3, $4) \
+             ON CONFLICT (user_id) DO NOTHING \
+             RETURNING id, user_id, verified_by_admin_id, evidence, date_of_death, verified_at",
+        )
+        .bind(user_id)
+        .bind(admin_id)
+        .bind(evidence)
+        .bind(date_of_death)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| ApiError::Conflict("Death has already been verified".to_string()))?;
+
+        AuditLogService::log(
+            &mut *conn,
+            Some(user_id),
+            Some(admin_id),
+            audit_action::DEATH_VERIFIED,
+            Some(user_id),
+            Some(entity_type::USER),
+            None,
+            None,
+            Some(serde_json::json!({
+                "verification_id": verification.id,
+                "date_of_death": verification.date_of_death,
+            })),
+        )
+        .await?;
+
+        Ok(verification)
+    }
+
+    /// When the user's death was verified, if it has been.
+    pub async fn verified_at(
+        db: impl sqlx::PgExecutor<'_>,
+        user_id: Uuid,
+    ) -> Result<Option<DateTime<Utc>>, ApiError> {
+        let verified_at =
+            sqlx::query_scalar("SELECT verified_at FROM death_verifications WHERE user_id = $1")
+                .bind(user_id)
+                .fetch_optional(db)
+                .await?;
+        Ok(verified_at)
+    }
+}
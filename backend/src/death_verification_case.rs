@@ -0,0 +1,1024 @@
+//! Death verification cases.
+//!
+//! A beneficiary (a user whose wallet is a beneficiary of one of the plans) or
+//! an executor (an emergency contact recorded with the relationship
+//! "executor") opens a case and uploads the death certificate, which is kept
+//! under the document envelope encryption. The case then moves through:
+//!
+//! 1. `under_review` – two different admins must verify the certificate; a
+//!    single rejection closes the case.
+//! 2. `objection_window` – the user and every contact is notified and may
+//!    object until the window ends. An objection puts the case into
+//!    `disputed` until an admin dismisses or upholds it.
+//! 3. `approved` – the death is recorded in [`crate::death_verification`],
+//!    inheritance is triggered for each of the user's plans and their legacy
+//!    messages are unlocked.
+//!
+//! Every step is written to the audit log against the case.
+
+use crate::api_error::ApiError;
+use crate::death_verification::DeathVerificationService;
+use crate::document_storage::{DeathCertificateRecord, DocumentKeyRing, DocumentStorageService};
+use crate::inheritance_trigger::{self, InheritanceTriggerService};
+use crate::message_delivery::MessageChannels;
+use crate::notifications::{
+    audit_action, entity_type, notif_type, AuditLogService, NotificationService,
+};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::{PgConnection, PgPool};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Distinct admins who must verify a certificate.
+pub const REQUIRED_APPROVALS: i64 = 2;
+
+/// How long contacts have to object once the certificate is verified.
+pub const OBJECTION_WINDOW_HOURS: i64 = 72;
+
+/// Largest accepted death certificate upload (10 MB).
+pub const MAX_CERTIFICATE_SIZE: usize = 10 * 1024 * 1024;
+
+const CERTIFICATE_CONTENT_TYPES: &[&str] = &["application/pdf", "image/jpeg", "image/png"];
+
+pub mod status {
+    pub const AWAITING_CERTIFICATE: &str = "awaiting_certificate";
+    pub const UNDER_REVIEW: &str = "under_review";
+    pub const OBJECTION_WINDOW: &str = "objection_window";
+    pub const DISPUTED: &str = "disputed";
+    pub const APPROVED: &str = "approved";
+    pub const REJECTED: &str = "rejected";
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
+pub struct DeathVerificationCase {
+    pub id: Uuid,
+    pub subject_user_id: Uuid,
+    pub opened_by: Uuid,
+    pub opener_role: String,
+    pub statement: String,
+    pub date_of_death: Option<NaiveDate>,
+    pub status: String,
+    pub objection_window_ends_at: Option<DateTime<Utc>>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub rejection_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CaseReview {
+    pub id: Uuid,
+    pub case_id: Uuid,
+    pub admin_id: Uuid,
+    pub decision: String,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
+pub struct CaseObjection {
+    pub id: Uuid,
+    pub case_id: Uuid,
+    pub raised_by: Uuid,
+    pub reason: String,
+    pub status: String,
+    pub resolved_by_admin_id: Option<Uuid>,
+    pub resolution_note: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A case with its certificate metadata, reviews and objections.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeathVerificationCaseDetail {
+    #[serde(flatten)]
+    pub case: DeathVerificationCase,
+    pub certificate: Option<DeathCertificateRecord>,
+    pub reviews: Vec<CaseReview>,
+    pub objections: Vec<CaseObjection>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct OpenCaseRequest {
+    /// Email of the user whose death is being reported.
+    pub subject_email: String,
+    pub statement: String,
+    pub date_of_death: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewDecision {
+    Verify,
+    Reject,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReviewCaseRequest {
+    pub decision: ReviewDecision,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ObjectionRequest {
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectionResolution {
+    /// The objection is unfounded; the case goes back to its objection window.
+    Dismiss,
+    /// The objection stands; the case is rejected.
+    Uphold,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ResolveObjectionRequest {
+    pub decision: ObjectionResolution,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default, JsonSchema)]
+pub struct CaseListFilters {
+    pub status: Option<String>,
+}
+
+const CASE_COLUMNS: &str = "c.id, c.subject_user_id, c.opened_by, c.opener_role, c.statement, \
+     c.date_of_death, c.status, c.objection_window_ends_at, c.decided_at, c.rejection_reason, \
+     c.created_at, c.updated_at";
+
+/// Whether user `u` is the subject of case `c` or one of the subject's
+/// contacts: an emergency contact with the user's email, or a beneficiary
+/// with the user's wallet.
+const IS_PARTY: &str = "(c.subject_user_id = u.id \
+     OR EXISTS (SELECT 1 FROM emergency_contacts ec \
+                WHERE ec.user_id = c.subject_user_id AND LOWER(ec.email) = LOWER(u.email)) \
+     OR EXISTS (SELECT 1 FROM plan_beneficiaries pb JOIN plans p ON p.id = pb.plan_id \
+                WHERE p.user_id = c.subject_user_id AND pb.wallet_address = u.wallet_address))";
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|v| !v.is_empty())
+}
+
+pub struct DeathVerificationCaseService;
+
+impl DeathVerificationCaseService {
+    /// Open a case for the user registered under `subject_email`. The opener
+    /// must be one of their beneficiaries or their executor.
+    pub async fn open(
+        db: &PgPool,
+        user_id: Uuid,
+        req: &OpenCaseRequest,
+    ) -> Result<DeathVerificationCase, ApiError> {
+        let statement = req.statement.trim();
+        if statement.is_empty() {
+            return Err(ApiError::BadRequest("A statement is required".to_string()));
+        }
+        if req
+            .date_of_death
+            .is_some_and(|d| d > Utc::now().date_naive())
+        {
+            return Err(ApiError::BadRequest(
+                "date_of_death cannot be in the future".to_string(),
+            ));
+        }
+        let subject_id: Uuid =
+            sqlx::query_scalar("SELECT id FROM users WHERE LOWER(email) = LOWER($1)")
+                .bind(req.subject_email.trim())
+                .fetch_optional(db)
+                .await?
+                .ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
+        if subject_id == user_id {
+            return Err(ApiError::BadRequest(
+                "You cannot open a death verification case for yourself".to_string(),
+            ));
+        }
+
+        let opener_role: Option<String> = sqlx::query_scalar(
+            "SELECT CASE \
+                 WHEN EXISTS (SELECT 1 FROM plan_beneficiaries pb JOIN plans p ON p.id = pb.plan_id \
+                              WHERE p.user_id = $2 AND pb.wallet_address = u.wallet_address) \
+                     THEN 'beneficiary' \
+                 WHEN EXISTS (SELECT 1 FROM emergency_contacts ec \
+                              WHERE ec.user_id = $2 AND LOWER(ec.email) = LOWER(u.email) \
+                                AND LOWER(ec.relationship) = 'executor') \
+                     THEN 'executor' \
+             END \
+             FROM users u WHERE u.id = $1",
+        )
+        .bind(user_id)
+        .bind(subject_id)
+        .fetch_optional(db)
+        .await?
+        .flatten();
+        let opener_role = opener_role.ok_or_else(|| {
+            ApiError::Forbidden(
+                "Only a beneficiary or the executor can open a death verification case".to_string(),
+            )
+        })?;
+        if DeathVerificationService::verified_at(db, subject_id)
+            .await?
+            .is_some()
+        {
+            return Err(ApiError::Conflict(
+                "Death has already been verified".to_string(),
+            ));
+        }
+
+        let mut tx = db.begin().await?;
+        let case = sqlx::query_as::<_, DeathVerificationCase>(&format!(
+            "INSERT INTO death_verification_cases AS c \
+                 (subject_user_id, opened_by, opener_role, statement, date_of_death) \
+             VALUES ($1, $2, $3, $4, $5) \
+             ON CONFLICT (subject_user_id) WHERE status NOT IN ('approved', 'rejected') \
+             DO NOTHING \
+             RETURNING {CASE_COLUMNS}"
+        ))
+        .bind(subject_id)
+        .bind(user_id)
+        .bind(&opener_role)
+        .bind(statement)
+        .bind(req.date_of_death)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| {
+            ApiError::Conflict("A death verification case is already in progress".to_string())
+        })?;
+
+        Self::audit(
+            &mut tx,
+            Some(user_id),
+            None,
+            audit_action::DEATH_CASE_OPENED,
+            &case,
+            json!({ "subject_user_id": subject_id, "opener_role": opener_role }),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(case)
+    }
+
+    /// Attach the death certificate to a case the user opened and send it
+    /// for review.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn upload_certificate(
+        db: &PgPool,
+        keyring: &DocumentKeyRing,
+        user_id: Uuid,
+        case_id: Uuid,
+        filename: &str,
+        content_type: &str,
+        content: &[u8],
+    ) -> Result<DeathCertificateRecord, ApiError> {
+        if !CERTIFICATE_CONTENT_TYPES.contains(&content_type) {
+            return Err(ApiError::BadRequest(format!(
+                "Certificates must be one of: {}",
+                CERTIFICATE_CONTENT_TYPES.join(", ")
+            )));
+        }
+        if content.is_empty() || content.len() > MAX_CERTIFICATE_SIZE {
+            return Err(ApiError::BadRequest(format!(
+                "Certificates must be between 1 byte and {MAX_CERTIFICATE_SIZE} bytes"
+            )));
+        }
+
+        let mut tx = db.begin().await?;
+        let case = Self::fetch_for_update(&mut tx, case_id).await?;
+        if case.opened_by != user_id {
+            return Err(ApiError::NotFound(format!("Case {case_id} not found")));
+        }
+        if case.status != status::AWAITING_CERTIFICATE {
+            return Err(ApiError::Conflict(format!(
+                "Case is already {}",
+                case.status
+            )));
+        }
+
+        let certificate = DocumentStorageService::store_death_certificate(
+            &mut tx,
+            keyring,
+            case_id,
+            user_id,
+            filename,
+            content_type,
+            content,
+        )
+        .await?;
+        Self::set_status(&mut tx, case_id, status::UNDER_REVIEW).await?;
+        Self::audit(
+            &mut tx,
+            Some(user_id),
+            None,
+            audit_action::DEATH_CERTIFICATE_UPLOADED,
+            &case,
+            json!({
+                "certificate_id": certificate.id,
+                "sha256": certificate.sha256,
+                "size_bytes": certificate.size_bytes,
+            }),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(certificate)
+    }
+
+    /// A case as seen by its opener, its subject or one of the subject's
+    /// contacts.
+    pub async fn get_for_user(
+        db: &PgPool,
+        user_id: Uuid,
+        case_id: Uuid,
+    ) -> Result<DeathVerificationCaseDetail, ApiError> {
+        let case = sqlx::query_as::<_, DeathVerificationCase>(&format!(
+            "SELECT {CASE_COLUMNS} FROM death_verification_cases c, users u \
+             WHERE c.id = $1 AND u.id = $2 AND (c.opened_by = u.id OR {IS_PARTY})"
+        ))
+        .bind(case_id)
+        .bind(user_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Case {case_id} not found")))?;
+        Self::detail(db, case).await
+    }
+
+    /// Cases the user opened or is concerned by, newest first.
+    pub async fn list_for_user(
+        db: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<DeathVerificationCase>, ApiError> {
+        let cases = sqlx::query_as::<_, DeathVerificationCase>(&format!(
+            "SELECT {CASE_COLUMNS} FROM death_verification_cases c, users u \
+             WHERE u.id = $1 AND (c.opened_by = u.id OR {IS_PARTY}) \
+             ORDER BY c.created_at DESC"
+        ))
+        .bind(user_id)
+        .fetch_all(db)
+        .await?;
+        Ok(cases)
+    }
+
+    pub async fn list_for_admin(
+        db: &PgPool,
+        filters: &CaseListFilters,
+    ) -> Result<Vec<DeathVerificationCase>, ApiError> {
+        let cases = sqlx::query_as::<_, DeathVerificationCase>(&format!(
+            "SELECT {CASE_COLUMNS} FROM death_verification_cases c \
+             WHERE ($1::text IS NULL OR c.status = $1) \
+             ORDER BY c.created_at ASC LIMIT 200"
+        ))
+        .bind(non_empty(filters.status.as_deref()))
+        .fetch_all(db)
+        .await?;
+        Ok(cases)
+    }
+
+    pub async fn get_for_admin(
+        db: &PgPool,
+        case_id: Uuid,
+    ) -> Result<DeathVerificationCaseDetail, ApiError> {
+        let case = Self::fetch(db, case_id).await?;
+        Self::detail(db, case).await
+    }
+
+    /// Decrypt a case's certificate for a reviewing admin.
+    pub async fn certificate_for_admin(
+        db: &PgPool,
+        keyring: &DocumentKeyRing,
+        admin_id: Uuid,
+        case_id: Uuid,
+    ) -> Result<(DeathCertificateRecord, Vec<u8>), ApiError> {
+        let case = Self::fetch(db, case_id).await?;
+        let (certificate, content) =
+            DocumentStorageService::retrieve_death_certificate(db, keyring, case_id).await?;
+        let mut conn = db.acquire().await?;
+        Self::audit(
+            &mut conn,
+            None,
+            Some(admin_id),
+            audit_action::DEATH_CERTIFICATE_VIEWED,
+            &case,
+            json!({ "certificate_id": certificate.id }),
+        )
+        .await?;
+        Ok((certificate, content))
+    }
+
+    /// Record an admin's review. The second verification by a different
+    /// admin opens the objection window; any rejection closes the case.
+    pub async fn review(
+        db: &PgPool,
+        channels: &MessageChannels,
+        admin_id: Uuid,
+        case_id: Uuid,
+        req: &ReviewCaseRequest,
+    ) -> Result<DeathVerificationCaseDetail, ApiError> {
+        let notes = non_empty(req.notes.as_deref());
+        if req.decision == ReviewDecision::Reject && notes.is_none() {
+            return Err(ApiError::BadRequest(
+                "A rejection must include notes".to_string(),
+            ));
+        }
+
+        let mut tx = db.begin().await?;
+        let case = Self::fetch_for_update(&mut tx, case_id).await?;
+        if case.status != status::UNDER_REVIEW {
+            return Err(ApiError::Conflict(format!(
+                "Case is {}, not under review",
+                case.status
+            )));
+        }
+        let decision = match req.decision {
+            ReviewDecision::Verify => "verify",
+            ReviewDecision::Reject => "reject",
+        };
+        sqlx::query(
+            "INSERT INTO death_verification_reviews (case_id, admin_id, decision, notes) \
+             VALUES ($1, $2, $3, $4) \
+             ON CONFLICT (case_id, admin_id) DO NOTHING \
+             RETURNING id",
+        )
+        .bind(case_id)
+        .bind(admin_id)
+        .bind(decision)
+        .bind(notes)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| {
+            ApiError::Conflict(
+                "You have already reviewed this case; another admin must review it".to_string(),
+            )
+        })?;
+        Self::audit(
+            &mut tx,
+            None,
+            Some(admin_id),
+            audit_action::DEATH_CASE_REVIEWED,
+            &case,
+            json!({ "decision": decision, "notes": notes }),
+        )
+        .await?;
+
+        let mut opened_window = false;
+        match req.decision {
+            ReviewDecision::Reject => {
+                Self::reject(&mut tx, &case, Some(admin_id), notes.unwrap_or_default()).await?;
+            }
+            ReviewDecision::Verify => {
+                let approvals: i64 = sqlx::query_scalar(
+                    "SELECT COUNT(*) FROM death_verification_reviews \
+                     WHERE case_id = $1 AND decision = 'verify'",
+                )
+                .bind(case_id)
+                .fetch_one(&mut *tx)
+                .await?;
+                if approvals >= REQUIRED_APPROVALS {
+                    Self::open_objection_window(&mut tx, &case).await?;
+                    opened_window = true;
+                }
+            }
+        }
+        tx.commit().await?;
+
+        if opened_window {
+            Self::email_contacts(db, channels, &case).await;
+        }
+        Self::get_for_admin(db, case_id).await
+    }
+
+    /// Object to a case during its objection window. Open to the subject and
+    /// their contacts, but not to whoever opened the case.
+    pub async fn object(
+        db: &PgPool,
+        user_id: Uuid,
+        case_id: Uuid,
+        req: &ObjectionRequest,
+    ) -> Result<CaseObjection, ApiError> {
+        let reason = req.reason.trim();
+        if reason.is_empty() {
+            return Err(ApiError::BadRequest("A reason is required".to_string()));
+        }
+
+        let mut tx = db.begin().await?;
+        let case = Self::fetch_for_update(&mut tx, case_id).await?;
+        let is_party: bool = sqlx::query_scalar(&format!(
+            "SELECT EXISTS(SELECT 1 FROM death_verification_cases c, users u \
+                           WHERE c.id = $1 AND u.id = $2 AND {IS_PARTY})"
+        ))
+        .bind(case_id)
+        .bind(user_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if !is_party || case.opened_by == user_id {
+            return Err(ApiError::NotFound(format!("Case {case_id} not found")));
+        }
+        let window_open = case.status == status::DISPUTED
+            || (case.status == status::OBJECTION_WINDOW
+                && case
+                    .objection_window_ends_at
+                    .is_some_and(|at| at > Utc::now()));
+        if !window_open {
+            return Err(ApiError::Conflict(
+                "This case is not open to objections".to_string(),
+            ));
+        }
+
+        let objection = sqlx::query_as::<_, CaseObjection>(
+            "INSERT INTO death_verification_objections (case_id, raised_by, reason) \
+             VALUES ($1, $2, $3) \
+             RETURNING id, case_id, raised_by, reason, status, resolved_by_admin_id, \
+                       resolution_note, resolved_at, created_at",
+        )
+        .bind(case_id)
+        .bind(user_id)
+        .bind(reason)
+        .fetch_one(&mut *tx)
+        .await?;
+        Self::set_status(&mut tx, case_id, status::DISPUTED).await?;
+        Self::audit(
+            &mut tx,
+            Some(user_id),
+            None,
+            audit_action::DEATH_OBJECTION_RAISED,
+            &case,
+            json!({ "objection_id": objection.id, "reason": reason }),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(objection)
+    }
+
+    /// Dismiss or uphold an open objection. Upholding rejects the case;
+    /// dismissing the last open objection returns it to its objection window.
+    pub async fn resolve_objection(
+        db: &PgPool,
+        admin_id: Uuid,
+        case_id: Uuid,
+        objection_id: Uuid,
+        req: &ResolveObjectionRequest,
+    ) -> Result<DeathVerificationCaseDetail, ApiError> {
+        let note = non_empty(req.note.as_deref());
+        let mut tx = db.begin().await?;
+        let case = Self::fetch_for_update(&mut tx, case_id).await?;
+        if case.status != status::DISPUTED {
+            return Err(ApiError::Conflict(format!(
+                "Case is {}, not disputed",
+                case.status
+            )));
+        }
+        let resolution = match req.decision {
+            ObjectionResolution::Dismiss => "dismissed",
+            ObjectionResolution::Uphold => "upheld",
+        };
+        sqlx::query(
+            "UPDATE death_verification_objections \
+             SET status = $3, resolved_by_admin_id = $4, resolution_note = $5, resolved_at = NOW() \
+             WHERE id = $1 AND case_id = $2 AND status = 'open' \
+             RETURNING id",
+        )
+        .bind(objection_id)
+        .bind(case_id)
+        .bind(resolution)
+        .bind(admin_id)
+        .bind(note)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Open objection {objection_id} not found")))?;
+        Self::audit(
+            &mut tx,
+            None,
+            Some(admin_id),
+            audit_action::DEATH_OBJECTION_RESOLVED,
+            &case,
+            json!({ "objection_id": objection_id, "resolution": resolution, "note": note }),
+        )
+        .await?;
+
+        match req.decision {
+            ObjectionResolution::Uphold => {
+                let reason = format!("Objection upheld: {}", note.unwrap_or("no note given"));
+                Self::reject(&mut tx, &case, Some(admin_id), &reason).await?;
+            }
+            ObjectionResolution::Dismiss => {
+                let open: bool = sqlx::query_scalar(
+                    "SELECT EXISTS(SELECT 1 FROM death_verification_objections \
+                                   WHERE case_id = $1 AND status = 'open')",
+                )
+                .bind(case_id)
+                .fetch_one(&mut *tx)
+                .await?;
+                if !open {
+                    Self::set_status(&mut tx, case_id, status::OBJECTION_WINDOW).await?;
+                }
+            }
+        }
+        tx.commit().await?;
+        Self::get_for_admin(db, case_id).await
+    }
+
+    /// Approve every case whose objection window has ended without an open
+    /// objection. Returns how many were approved.
+    pub async fn finalize_due(db: &PgPool) -> Result<usize, ApiError> {
+        let due: Vec<Uuid> = sqlx::query_scalar(
+            "SELECT id FROM death_verification_cases \
+             WHERE status = 'objection_window' AND objection_window_ends_at <= NOW() \
+             ORDER BY objection_window_ends_at ASC LIMIT 100",
+        )
+        .fetch_all(db)
+        .await?;
+
+        let mut approved = 0;
+        for case_id in due {
+            match Self::finalize(db, case_id).await {
+                Ok(true) => approved += 1,
+                Ok(false) => {}
+                Err(e) => warn!(
+                    "Failed to finalize death verification case {}: {}",
+                    case_id, e
+                ),
+            }
+        }
+        Ok(approved)
+    }
+
+    /// Approve one case: record the death, trigger inheritance for each of
+    /// the subject's plans and unlock their pending legacy messages.
+    async fn finalize(db: &PgPool, case_id: Uuid) -> Result<bool, ApiError> {
+        let mut tx = db.begin().await?;
+        let case = Self::fetch_for_update(&mut tx, case_id).await?;
+        if case.status != status::OBJECTION_WINDOW
+            || case
+                .objection_window_ends_at
+                .is_none_or(|at| at > Utc::now())
+        {
+            return Ok(false);
+        }
+
+        // The last admin to verify the certificate stands as the verifier.
+        let (verified_by, sha256): (Uuid, String) = sqlx::query_as(
+            "SELECT r.admin_id, d.sha256 FROM death_verification_reviews r \
+             JOIN death_certificates d ON d.case_id = r.case_id \
+             WHERE r.case_id = $1 AND r.decision = 'verify' \
+             ORDER BY r.created_at DESC LIMIT 1",
+        )
+        .bind(case_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        let evidence = format!("Death verification case {case_id}; certificate sha256 {sha256}");
+        match DeathVerificationService::record_in(
+            &mut tx,
+            verified_by,
+            case.subject_user_id,
+            &evidence,
+            case.date_of_death,
+        )
+        .await
+        {
+            Ok(_) | Err(ApiError::Conflict(_)) => {}
+            Err(e) => return Err(e),
+        }
+
+        let plan_ids: Vec<Uuid> = sqlx::query_scalar(
+            "SELECT p.id FROM plans p \
+             WHERE p.user_id = $1 \
+               AND NOT EXISTS (SELECT 1 FROM inheritance_triggers t WHERE t.plan_id = p.id) \
+             ORDER BY p.created_at",
+        )
+        .bind(case.subject_user_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        for plan_id in &plan_ids {
+            InheritanceTriggerService::trigger(
+                &mut tx,
+                *plan_id,
+                inheritance_trigger::source::DEATH_VERIFICATION,
+                None,
+                None,
+                Some(case_id),
+                Some("Death verified"),
+            )
+            .await?;
+        }
+
+        let messages_unlocked = sqlx::query(
+            "UPDATE legacy_messages SET unlock_at = NOW(), updated_at = NOW() \
+             WHERE owner_user_id = $1 AND status = 'pending' AND unlock_at > NOW()",
+        )
+        .bind(case.subject_user_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        sqlx::query(
+            "UPDATE death_verification_cases \
+             SET status = 'approved', decided_at = NOW(), updated_at = NOW() \
+             WHERE id = $1",
+        )
+        .bind(case_id)
+        .execute(&mut *tx)
+        .await?;
+        Self::audit(
+            &mut tx,
+            None,
+            Some(verified_by),
+            audit_action::DEATH_CASE_APPROVED,
+            &case,
+            json!({
+                "plans_triggered": plan_ids,
+                "messages_unlocked": messages_unlocked,
+            }),
+        )
+        .await?;
+        NotificationService::create(
+            &mut tx,
+            case.opened_by,
+            notif_type::DEATH_VERIFICATION_DECIDED,
+            "The death verification case you opened has been approved.",
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn open_objection_window(
+        conn: &mut PgConnection,
+        case: &DeathVerificationCase,
+    ) -> Result<(), ApiError> {
+        let ends_at = Utc::now() + Duration::hours(OBJECTION_WINDOW_HOURS);
+        sqlx::query(
+            "UPDATE death_verification_cases \
+             SET status = 'objection_window', objection_window_ends_at = $2, updated_at = NOW() \
+             WHERE id = $1",
+        )
+        .bind(case.id)
+        .bind(ends_at)
+        .execute(&mut *conn)
+        .await?;
+
+        let recipients: Vec<Uuid> = sqlx::query_scalar(&format!(
+            "SELECT u.id FROM death_verification_cases c, users u \
+             WHERE c.id = $1 AND (u.id = c.opened_by OR {IS_PARTY})"
+        ))
+        .bind(case.id)
+        .fetch_all(&mut *conn)
+        .await?;
+        let message = format!(
+            "A death verification case has been opened for an InheritX user you are connected \
+             to (case {}). If this is wrong, object before {}.",
+            case.id,
+            ends_at.format("%Y-%m-%d %H:%M UTC")
+        );
+        for user_id in &recipients {
+            NotificationService::create(
+                &mut *conn,
+                *user_id,
+                notif_type::DEATH_VERIFICATION_OBJECTION_WINDOW,
+                message.clone(),
+            )
+            .await?;
+        }
+
+        Self::audit(
+            conn,
+            None,
+            None,
+            audit_action::DEATH_OBJECTION_WINDOW_OPENED,
+            case,
+            json!({ "ends_at": ends_at, "notified_users": recipients.len() }),
+        )
+        .await
+    }
+
+    /// Email the subject and every emergency contact with an address about a
+    /// newly opened objection window, and audit who was reached.
+    async fn email_contacts(db: &PgPool, channels: &MessageChannels, case: &DeathVerificationCase) {
+        let addresses: Vec<String> = match sqlx::query_scalar(
+            "SELECT email FROM users WHERE id = $1 \
+             UNION \
+             SELECT email FROM emergency_contacts WHERE user_id = $1 AND email IS NOT NULL",
+        )
+        .bind(case.subject_user_id)
+        .fetch_all(db)
+        .await
+        {
+            Ok(addresses) => addresses,
+            Err(e) => {
+                warn!("Failed to look up contacts for case {}: {}", case.id, e);
+                return;
+            }
+        };
+
+        let body = format!(
+            "A death verification case has been opened on InheritX for someone who listed you \
+             as a contact (case {}).\n\nIf you believe this is a mistake, sign in to InheritX \
+             and object within {} hours.\n",
+            case.id, OBJECTION_WINDOW_HOURS
+        );
+        let mut reached = 0;
+        let mut failed = 0;
+        for address in &addresses {
+            match channels
+                .send_email(address, "A death verification case has been opened", &body)
+                .await
+            {
+                Ok(_) => reached += 1,
+                Err(e) => {
+                    warn!("Failed to email contact for case {}: {}", case.id, e);
+                    failed += 1;
+                }
+            }
+        }
+
+        if let Err(e) = AuditLogService::log(
+            db,
+            None,
+            None,
+            audit_action::DEATH_OBJECTION_WINDOW_OPENED,
+            Some(case.id),
+            Some(entity_type::DEATH_VERIFICATION_CASE),
+            None,
+            None,
+            Some(json!({ "emails_sent": reached, "emails_failed": failed })),
+        )
+        .await
+        {
+            warn!("Failed to audit contact emails for case {}: {}", case.id, e);
+        }
+    }
+
+    async fn reject(
+        conn: &mut PgConnection,
+        case: &DeathVerificationCase,
+        admin_id: Option<Uuid>,
+        reason: &str,
+    ) -> Result<(), ApiError> {
+        sqlx::query(
+            "UPDATE death_verification_cases \
+             SET status = 'rejected', rejection_reason = $2, decided_at = NOW(), updated_at = NOW() \
+             WHERE id = $1",
+        )
+        .bind(case.id)
+        .bind(reason)
+        .execute(&mut *conn)
+        .await?;
+        // A rejected case is final, so objections still open against it are
+        // closed with it rather than left to reopen the case later.
+        sqlx::query(
+            "UPDATE death_verification_objections \
+             SET status = 'upheld', resolved_by_admin_id = $2, resolution_note = $3, \
+                 resolved_at = NOW() \
+             WHERE case_id = $1 AND status = 'open'",
+        )
+        .bind(case.id)
+        .bind(admin_id)
+        .bind(format!("Case rejected: {reason}"))
+        .execute(&mut *conn)
+        .await?;
+        Self::audit(
+            &mut *conn,
+            None,
+            admin_id,
+            audit_action::DEATH_CASE_REJECTED,
+            case,
+            json!({ "reason": reason }),
+        )
+        .await?;
+        NotificationService::create(
+            conn,
+            case.opened_by,
+            notif_type::DEATH_VERIFICATION_DECIDED,
+            format!("The death verification case you opened was rejected: {reason}"),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    async fn detail(
+        db: &PgPool,
+        case: DeathVerificationCase,
+    ) -> Result<DeathVerificationCaseDetail, ApiError> {
+        let certificate = sqlx::query_as::<_, DeathCertificateRecord>(
+            "SELECT id, case_id, uploaded_by, filename, content_type, size_bytes, sha256, \
+                    key_version, created_at \
+             FROM death_certificates WHERE case_id = $1",
+        )
+        .bind(case.id)
+        .fetch_optional(db)
+        .await?;
+        let reviews = sqlx::query_as::<_, CaseReview>(
+            "SELECT id, case_id, admin_id, decision, notes, created_at \
+             FROM death_verification_reviews WHERE case_id = $1 ORDER BY created_at",
+        )
+        .bind(case.id)
+        .fetch_all(db)
+        .await?;
+        let objections = sqlx::query_as::<_, CaseObjection>(
+            "SELECT id, case_id, raised_by, reason, status, resolved_by_admin_id, \
+                    resolution_note, resolved_at, created_at \
+             FROM death_verification_objections WHERE case_id = $1 ORDER BY created_at",
+        )
+        .bind(case.id)
+        .fetch_all(db)
+        .await?;
+        Ok(DeathVerificationCaseDetail {
+            case,
+            certificate,
+            reviews,
+            objections,
+        })
+    }
+
+    async fn audit(
+        conn: &mut PgConnection,
+        user_id: Option<Uuid>,
+        admin_id: Option<Uuid>,
+        action: &str,
+        case: &DeathVerificationCase,
+        metadata: serde_json::Value,
+    ) -> Result<(), ApiError> {
+        AuditLogService::log(
+            conn,
+            user_id,
+            admin_id,
+            action,
+            Some(case.id),
+            Some(entity_type::DEATH_VERIFICATION_CASE),
+            Some(&case.status),
+            None,
+            Some(metadata),
+        )
+        .await
+    }
+
+    async fn set_status(
+        conn: &mut PgConnection,
+        case_id: Uuid,
+        status: &str,
+    ) -> Result<(), ApiError> {
+        sqlx::query(
+            "UPDATE death_verification_cases SET status = $2, updated_at = NOW() WHERE id = $1",
+        )
+        .bind(case_id)
+        .bind(status)
+        .execute(conn)
+        .await?;
+        Ok(())
+    }
+
+    async fn fetch(
+        db: impl sqlx::PgExecutor<'_>,
+        case_id: Uuid,
+    ) -> Result<DeathVerificationCase, ApiError> {
+        sqlx::query_as::<_, DeathVerificationCase>(&format!(
+            "SELECT {CASE_COLUMNS} FROM death_verification_cases c WHERE c.id = $1"
+        ))
+        .bind(case_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Case {case_id} not found")))
+    }
+
+    async fn fetch_for_update(
+        conn: &mut PgConnection,
+        case_id: Uuid,
+    ) -> Result<DeathVerificationCase, ApiError> {
+        sqlx::query_as::<_, DeathVerificationCase>(&format!(
+            "SELECT {CASE_COLUMNS} FROM death_verification_cases c WHERE c.id = $1 FOR UPDATE"
+        ))
+        .bind(case_id)
+        .fetch_optional(conn)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Case {case_id} not found")))
+    }
+}
+
+/// Background job that approves cases whose objection window has ended.
+pub struct DeathVerificationCaseJob {
+    db: PgPool,
+}
+
+impl DeathVerificationCaseJob {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(300));
+            loop {
+                interval.tick().await;
+                match DeathVerificationCaseService::finalize_due(&self.db).await {
+                    Ok(count) if count > 0 => {
+                        info!("Approved {} death verification cases", count)
+                    }
+                    Ok(_) => {}
+                    Err(e) => error!("Death verification case job error: {}", e),
+                }
+            }
+        });
+    }
+}
@@ -1,47 +1,86 @@
 //! Encrypted document storage with backup support.
 //!
-//! Provides AES-256-GCM encryption for will documents at rest,
-//! per-user access control, and a backup mechanism.
+//! Will documents are protected with envelope encryption: every document gets
+//! its own random AES-256-GCM data key, and that data key is wrapped by a
+//! versioned master key loaded through the [`SecretsProvider`] trait. The
+//! master key version is stored next to each wrapped key so that rotating the
+//! master key only requires re-wrapping data keys, never re-encrypting content.
+//!
+//! Master key secrets:
+//!   - `DOCUMENT_MASTER_KEY_VERSION` – the version new documents are wrapped with
+//!   - `DOCUMENT_MASTER_KEY_V{n}`    – the key material for version `n`
+//!
+//! Rows written before envelope encryption (no `key_version`) were encrypted
+//! directly with a key derived from `DOCUMENT_ENCRYPTION_KEY`; they remain
+//! readable while that secret is configured and are migrated to the envelope
+//! scheme by [`DocumentKeyRotationService`].
+//!
+//! Backups keep their ciphertext in the configured [`ObjectStore`] under a
+//! content-addressed key rather than in Postgres.
+//!
+//! Death certificates uploaded to death verification cases use the same
+//! envelope scheme and are re-wrapped along with documents and backups.
 
 use crate::api_error::ApiError;
+use crate::object_storage::{ObjectStorageService, ObjectStore};
+use crate::secrets::SecretsProvider;
 use chrono::{DateTime, Utc};
 use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM};
-use ring::digest::{digest, SHA256};
 use ring::hkdf::{Salt, HKDF_SHA256};
 use ring::rand::{SecureRandom, SystemRandom};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sha2::{Digest, Sha256};
+use sqlx::{PgConnection, PgPool};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
 use uuid::Uuid;
 
 const NONCE_LEN: usize = 12;
 const KEY_LEN: usize = 32;
 
+/// Secret holding the master key version used for new documents.
+pub const MASTER_KEY_VERSION_SECRET: &str = "DOCUMENT_MASTER_KEY_VERSION";
+/// Prefix of the per-version master key secrets (`DOCUMENT_MASTER_KEY_V1`, ...).
+pub const MASTER_KEY_SECRET_PREFIX: &str = "DOCUMENT_MASTER_KEY_V";
+/// Pre-envelope single key, only read to decrypt and migrate legacy rows.
+const LEGACY_KEY_SECRET: &str = "DOCUMENT_ENCRYPTION_KEY";
+/// Minimum accepted length of a master key secret, in bytes.
+const MIN_MASTER_KEY_LEN: usize = 32;
+
 // ---------------------------------------------------------------------------
 // Encryption helpers
 // ---------------------------------------------------------------------------
 
-fn derive_key(secret: &[u8]) -> Result<LessSafeKey, ApiError> {
+fn derive_key(secret: &[u8], info: &'static [u8]) -> Result<LessSafeKey, ApiError> {
     let salt = Salt::new(HKDF_SHA256, b"inheritx-document-encryption");
     let prk = salt.extract(secret);
+    let info = [info];
     let okm = prk
-        .expand(&[b"aes-256-gcm-key"], &AES_256_GCM)
+        .expand(&info, &AES_256_GCM)
         .map_err(|_| ApiError::Internal(anyhow::anyhow!("Key derivation failed")))?;
     let mut key_bytes = [0u8; KEY_LEN];
     okm.fill(&mut key_bytes)
         .map_err(|_| ApiError::Internal(anyhow::anyhow!("Key material extraction failed")))?;
-    let unbound = UnboundKey::new(&AES_256_GCM, &key_bytes)
+    key_from_bytes(&key_bytes)
+}
+
+fn key_from_bytes(key_bytes: &[u8]) -> Result<LessSafeKey, ApiError> {
+    let unbound = UnboundKey::new(&AES_256_GCM, key_bytes)
         .map_err(|_| ApiError::Internal(anyhow::anyhow!("Failed to create encryption key")))?;
     Ok(LessSafeKey::new(unbound))
 }
 
-fn load_encryption_secret() -> Vec<u8> {
-    std::env::var("DOCUMENT_ENCRYPTION_KEY")
-        .unwrap_or_default()
-        .into_bytes()
+fn generate_data_key() -> Result<[u8; KEY_LEN], ApiError> {
+    let mut data_key = [0u8; KEY_LEN];
+    SystemRandom::new()
+        .fill(&mut data_key)
+        .map_err(|_| ApiError::Internal(anyhow::anyhow!("Failed to generate data key")))?;
+    Ok(data_key)
 }
 
-fn encrypt_bytes(plaintext: &[u8], secret: &[u8]) -> Result<(Vec<u8>, Vec<u8>), ApiError> {
-    let key = derive_key(secret)?;
+fn seal(key: &LessSafeKey, plaintext: &[u8], aad: &[u8]) -> Result<(Vec<u8>, Vec<u8>), ApiError> {
     let rng = SystemRandom::new();
     let mut nonce_bytes = [0u8; NONCE_LEN];
     rng.fill(&mut nonce_bytes)
@@ -49,18 +88,18 @@ fn encrypt_bytes(plaintext: &[u8], secret: &[u8]) -> Result<(Vec<u8>, Vec<u8>),
     let nonce = Nonce::assume_unique_for_key(nonce_bytes);
 
     let mut in_out = plaintext.to_vec();
-    key.seal_in_place_append_tag(nonce, Aad::empty(), &mut in_out)
+    key.seal_in_place_append_tag(nonce, Aad::from(aad), &mut in_out)
         .map_err(|_| ApiError::Internal(anyhow::anyhow!("Encryption failed")))?;
 
     Ok((in_out, nonce_bytes.to_vec()))
 }
 
-fn decrypt_bytes(
+fn open(
+    key: &LessSafeKey,
     ciphertext: &[u8],
     nonce_bytes: &[u8],
-    secret: &[u8],
+    aad: &[u8],
 ) -> Result<Vec<u8>, ApiError> {
-    let key = derive_key(secret)?;
     let mut nonce_arr = [0u8; NONCE_LEN];
     if nonce_bytes.len() != NONCE_LEN {
         return Err(ApiError::Internal(anyhow::anyhow!("Invalid nonce length")));
@@ -70,24 +109,247 @@ fn decrypt_bytes(
 
     let mut in_out = ciphertext.to_vec();
     let plaintext = key
-        .open_in_place(nonce, Aad::empty(), &mut in_out)
+        .open_in_place(nonce, Aad::from(aad), &mut in_out)
         .map_err(|_| ApiError::Internal(anyhow::anyhow!("Decryption failed")))?;
     Ok(plaintext.to_vec())
 }
 
+/// Ciphertext plus the wrapped data key needed to open it.
+struct SealedDocument {
+    ciphertext: Vec<u8>,
+    content_nonce: Vec<u8>,
+    wrapped_data_key: Vec<u8>,
+    data_key_nonce: Vec<u8>,
+}
+
+/// Encrypt `plaintext` under a fresh data key and wrap that key with `master`.
+/// Both layers are bound to the document id through the AEAD associated data.
+fn seal_document(
+    document_id: Uuid,
+    plaintext: &[u8],
+    master: &LessSafeKey,
+) -> Result<SealedDocument, ApiError> {
+    let data_key = generate_data_key()?;
+    let (ciphertext, content_nonce) = seal(
+        &key_from_bytes(&data_key)?,
+        plaintext,
+        document_id.as_bytes(),
+    )?;
+    let (wrapped_data_key, data_key_nonce) = seal(master, &data_key, document_id.as_bytes())?;
+    Ok(SealedDocument {
+        ciphertext,
+        content_nonce,
+        wrapped_data_key,
+        data_key_nonce,
+    })
+}
+
+fn open_document(
+    document_id: Uuid,
+    sealed: &SealedDocument,
+    master: &LessSafeKey,
+) -> Result<Vec<u8>, ApiError> {
+    let data_key = open(
+        master,
+        &sealed.wrapped_data_key,
+        &sealed.data_key_nonce,
+        document_id.as_bytes(),
+    )?;
+    open(
+        &key_from_bytes(&data_key)?,
+        &sealed.ciphertext,
+        &sealed.content_nonce,
+        document_id.as_bytes(),
+    )
+}
+
+/// Unwrap a data key with `old_master` and wrap it again with `new_master`.
+fn rewrap_data_key(
+    document_id: Uuid,
+    wrapped_data_key: &[u8],
+    data_key_nonce: &[u8],
+    old_master: &LessSafeKey,
+    new_master: &LessSafeKey,
+) -> Result<(Vec<u8>, Vec<u8>), ApiError> {
+    let data_key = open(
+        old_master,
+        wrapped_data_key,
+        data_key_nonce,
+        document_id.as_bytes(),
+    )?;
+    seal(new_master, &data_key, document_id.as_bytes())
+}
+
+/// Decrypt content written before envelope encryption was introduced.
+fn open_legacy(ciphertext: &[u8], nonce: &[u8], legacy_secret: &[u8]) -> Result<Vec<u8>, ApiError> {
+    let key = derive_key(legacy_secret, b"aes-256-gcm-key")?;
+    open(&key, ciphertext, nonce, &[])
+}
+
+// ---------------------------------------------------------------------------
+// Master key ring
+// ---------------------------------------------------------------------------
+
+/// Resolves versioned document master keys from a [`SecretsProvider`].
+pub struct DocumentKeyRing {
+    provider: Arc<dyn SecretsProvider>,
+}
+
+impl DocumentKeyRing {
+    pub fn new(provider: Arc<dyn SecretsProvider>) -> Self {
+        Self { provider }
+    }
+
+    /// The master key version that new documents are wrapped with.
+    pub async fn current_version(&self) -> Result<i32, ApiError> {
+        let raw = self
+            .provider
+            .get_secret(MASTER_KEY_VERSION_SECRET)
+            .await
+            .map_err(|_| {
+                ApiError::Internal(anyhow::anyhow!(
+                    "{MASTER_KEY_VERSION_SECRET} is not configured"
+                ))
+            })?;
+        let version: i32 = raw.trim().parse().map_err(|_| {
+            ApiError::Internal(anyhow::anyhow!(
+                "{MASTER_KEY_VERSION_SECRET} must be a positive integer"
+            ))
+        })?;
+        if version < 1 {
+            return Err(ApiError::Internal(anyhow::anyhow!(
+                "{MASTER_KEY_VERSION_SECRET} must be a positive integer"
+            )));
+        }
+        Ok(version)
+    }
+
+    async fn master_key(&self, version: i32) -> Result<LessSafeKey, ApiError> {
+        let name = format!("{MASTER_KEY_SECRET_PREFIX}{version}");
+        let secret = self.provider.get_secret(&name).await.map_err(|_| {
+            ApiError::Internal(anyhow::anyhow!(
+                "Document master key version {version} is not configured"
+            ))
+        })?;
+        if secret.len() < MIN_MASTER_KEY_LEN {
+            return Err(ApiError::Internal(anyhow::anyhow!(
+                "{name} must be at least {MIN_MASTER_KEY_LEN} bytes"
+            )));
+        }
+        derive_key(secret.as_bytes(), b"wrap-document-data-key")
+    }
+
+    async fn current_master_key(&self) -> Result<(i32, LessSafeKey), ApiError> {
+        let version = self.current_version().await?;
+        Ok((version, self.master_key(version).await?))
+    }
+
+    async fn legacy_secret(&self) -> Result<Vec<u8>, ApiError> {
+        let secret = self
+            .provider
+            .get_secret(LEGACY_KEY_SECRET)
+            .await
+            .unwrap_or_default();
+        if secret.is_empty() {
+            return Err(ApiError::Internal(anyhow::anyhow!(
+                "{LEGACY_KEY_SECRET} is required to read documents encrypted before key versioning"
+            )));
+        }
+        Ok(secret.into_bytes())
+    }
+
+    /// Startup check: fails unless the current master key version and its key
+    /// material are available. The server must not start without them, since
+    /// every encrypt/decrypt call would otherwise fail at request time.
+    pub async fn ensure_configured(&self) -> Result<i32, ApiError> {
+        let (version, _) = self.current_master_key().await?;
+        tracing::info!(key_version = version, "Document master key validated");
+        Ok(version)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Data types
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BackupRecord {
     pub id: Uuid,
     pub document_id: Uuid,
     pub user_id: Uuid,
     pub backup_hash: String,
+    pub key_version: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyRotationResult {
+    pub current_key_version: i32,
+    pub documents_rewrapped: usize,
+    pub backups_rewrapped: usize,
+    pub certificates_rewrapped: usize,
+    pub failed: usize,
+}
+
+/// An encrypted death certificate, without its content.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
+pub struct DeathCertificateRecord {
+    pub id: Uuid,
+    pub case_id: Uuid,
+    pub uploaded_by: Uuid,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub sha256: String,
+    pub key_version: i32,
     pub created_at: DateTime<Utc>,
 }
 
+const CERTIFICATE_COLUMNS: &str =
+    "id, case_id, uploaded_by, filename, content_type, size_bytes, sha256, key_version, created_at";
+
+#[derive(sqlx::FromRow)]
+struct EncryptedRow {
+    encrypted_content: Option<Vec<u8>>,
+    encryption_nonce: Option<Vec<u8>>,
+    wrapped_data_key: Option<Vec<u8>>,
+    data_key_nonce: Option<Vec<u8>>,
+    key_version: Option<i32>,
+}
+
+/// Decrypt a row from `will_documents` or `document_backups`, using the
+/// envelope scheme when a key version is present and the legacy key otherwise.
+async fn decrypt_row(
+    keyring: &DocumentKeyRing,
+    document_id: Uuid,
+    row: EncryptedRow,
+) -> Result<Vec<u8>, ApiError> {
+    let ciphertext = row
+        .encrypted_content
+        .ok_or_else(|| ApiError::Internal(anyhow::anyhow!("Missing encrypted content")))?;
+    let nonce = row
+        .encryption_nonce
+        .ok_or_else(|| ApiError::Internal(anyhow::anyhow!("Missing encryption nonce")))?;
+
+    match row.key_version {
+        Some(version) => {
+            let sealed = SealedDocument {
+                ciphertext,
+                content_nonce: nonce,
+                wrapped_data_key: row.wrapped_data_key.ok_or_else(|| {
+                    ApiError::Internal(anyhow::anyhow!("Missing wrapped data key"))
+                })?,
+                data_key_nonce: row
+                    .data_key_nonce
+                    .ok_or_else(|| ApiError::Internal(anyhow::anyhow!("Missing data key nonce")))?,
+            };
+            let master = keyring.master_key(version).await?;
+            open_document(document_id, &sealed, &master)
+        }
+        None => open_legacy(&ciphertext, &nonce, &keyring.legacy_secret().await?),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Service
 // ---------------------------------------------------------------------------
@@ -95,9 +357,11 @@ pub struct BackupRecord {
 pub struct DocumentStorageService;
 
 impl DocumentStorageService {
-    /// Encrypt an existing document's content and store the ciphertext.
+    /// Encrypt an existing document's content under a fresh data key and store
+    /// the ciphertext together with the wrapped key and its master key version.
     pub async fn store_encrypted(
         db: &PgPool,
+        keyring: &DocumentKeyRing,
         user_id: Uuid,
         document_id: Uuid,
         content_bytes: &[u8],
@@ -117,22 +381,20 @@ impl DocumentStorageService {
             ));
         }
 
-        let secret = load_encryption_secret();
-        if secret.is_empty() {
-            return Err(ApiError::Internal(anyhow::anyhow!(
-                "DOCUMENT_ENCRYPTION_KEY is not configured"
-            )));
-        }
-
-        let (ciphertext, nonce) = encrypt_bytes(content_bytes, &secret)?;
+        let (key_version, master) = keyring.current_master_key().await?;
+        let sealed = seal_document(document_id, content_bytes, &master)?;
 
         sqlx::query(
             "UPDATE will_documents \
-             SET encrypted_content = $1, encryption_nonce = $2, is_encrypted = TRUE \
-             WHERE id = $3 AND user_id = $4",
+             SET encrypted_content = $1, encryption_nonce = $2, wrapped_data_key = $3, \
+                 data_key_nonce = $4, key_version = $5, is_encrypted = TRUE \
+             WHERE id = $6 AND user_id = $7",
         )
-        .bind(&ciphertext)
-        .bind(&nonce)
+        .bind(&sealed.ciphertext)
+        .bind(&sealed.content_nonce)
+        .bind(&sealed.wrapped_data_key)
+        .bind(&sealed.data_key_nonce)
+        .bind(key_version)
         .bind(document_id)
         .bind(user_id)
         .execute(db)
@@ -144,18 +406,20 @@ impl DocumentStorageService {
     /// Retrieve and decrypt a document's encrypted content.
     pub async fn retrieve_decrypted(
         db: &PgPool,
+        keyring: &DocumentKeyRing,
         user_id: Uuid,
         document_id: Uuid,
     ) -> Result<Vec<u8>, ApiError> {
         #[derive(sqlx::FromRow)]
         struct Row {
-            encrypted_content: Option<Vec<u8>>,
-            encryption_nonce: Option<Vec<u8>>,
             is_encrypted: bool,
+            #[sqlx(flatten)]
+            encrypted: EncryptedRow,
         }
 
         let row = sqlx::query_as::<_, Row>(
-            "SELECT encrypted_content, encryption_nonce, is_encrypted \
+            "SELECT is_encrypted, encrypted_content, encryption_nonce, \
+                    wrapped_data_key, data_key_nonce, key_version \
              FROM will_documents WHERE id = $1 AND user_id = $2",
         )
         .bind(document_id)
@@ -170,38 +434,29 @@ impl DocumentStorageService {
             ));
         }
 
-        let ciphertext = row
-            .encrypted_content
-            .ok_or_else(|| ApiError::Internal(anyhow::anyhow!("Missing encrypted content")))?;
-        let nonce = row
-            .encryption_nonce
-            .ok_or_else(|| ApiError::Internal(anyhow::anyhow!("Missing encryption nonce")))?;
-
-        let secret = load_encryption_secret();
-        if secret.is_empty() {
-            return Err(ApiError::Internal(anyhow::anyhow!(
-                "DOCUMENT_ENCRYPTION_KEY is not configured"
-            )));
-        }
-
-        decrypt_bytes(&ciphertext, &nonce, &secret)
+        decrypt_row(keyring, document_id, row.encrypted).await
     }
 
-    /// Create an encrypted backup of a document.
+    /// Create an encrypted backup of a document. The ciphertext is written to
+    /// object storage under its content-addressed key; the backup row carries
+    /// its own copy of the wrapped data key so it stays readable, and
+    /// rotatable, independently of the live document row.
     pub async fn create_backup(
         db: &PgPool,
+        store: &dyn ObjectStore,
         user_id: Uuid,
         document_id: Uuid,
     ) -> Result<BackupRecord, ApiError> {
         #[derive(sqlx::FromRow)]
         struct DocRow {
-            encrypted_content: Option<Vec<u8>>,
-            encryption_nonce: Option<Vec<u8>>,
             is_encrypted: bool,
+            #[sqlx(flatten)]
+            encrypted: EncryptedRow,
         }
 
         let doc = sqlx::query_as::<_, DocRow>(
-            "SELECT encrypted_content, encryption_nonce, is_encrypted \
+            "SELECT is_encrypted, encrypted_content, encryption_nonce, \
+                    wrapped_data_key, data_key_nonce, key_version \
              FROM will_documents WHERE id = $1 AND user_id = $2",
         )
         .bind(document_id)
@@ -216,30 +471,42 @@ impl DocumentStorageService {
             ));
         }
 
-        let ciphertext = doc
+        let encrypted = doc.encrypted;
+        if encrypted.key_version.is_none() {
+            // Legacy ciphertext is re-encrypted by the rotation job, which would
+            // orphan an object written now. Back it up once it has migrated.
+            return Err(ApiError::BadRequest(
+                "Document is pending key migration; retry the backup shortly".to_string(),
+            ));
+        }
+
+        let ciphertext = encrypted
             .encrypted_content
             .ok_or_else(|| ApiError::Internal(anyhow::anyhow!("Missing encrypted content")))?;
-        let nonce = doc
+        let nonce = encrypted
             .encryption_nonce
             .ok_or_else(|| ApiError::Internal(anyhow::anyhow!("Missing encryption nonce")))?;
 
-        let hash_bytes = digest(&SHA256, &ciphertext);
-        let backup_hash = hex::encode(hash_bytes.as_ref());
+        let object = ObjectStorageService::store(db, store, &ciphertext).await?;
 
         let backup_id = Uuid::new_v4();
         let created_at = Utc::now();
 
         sqlx::query(
             "INSERT INTO document_backups \
-             (id, document_id, user_id, backup_hash, encrypted_content, encryption_nonce, created_at) \
-             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+             (id, document_id, user_id, backup_hash, storage_key, encryption_nonce, \
+              wrapped_data_key, data_key_nonce, key_version, created_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
         )
         .bind(backup_id)
         .bind(document_id)
         .bind(user_id)
-        .bind(&backup_hash)
-        .bind(&ciphertext)
+        .bind(&object.sha256)
+        .bind(&object.object_key)
         .bind(&nonce)
+        .bind(&encrypted.wrapped_data_key)
+        .bind(&encrypted.data_key_nonce)
+        .bind(encrypted.key_version)
         .bind(created_at)
         .execute(db)
         .await?;
@@ -248,7 +515,8 @@ impl DocumentStorageService {
             id: backup_id,
             document_id,
             user_id,
-            backup_hash,
+            backup_hash: object.sha256,
+            key_version: encrypted.key_version,
             created_at,
         })
     }
@@ -280,11 +548,12 @@ impl DocumentStorageService {
             document_id: Uuid,
             user_id: Uuid,
             backup_hash: String,
+            key_version: Option<i32>,
             created_at: DateTime<Utc>,
         }
 
         let rows = sqlx::query_as::<_, Row>(
-            "SELECT id, document_id, user_id, backup_hash, created_at \
+            "SELECT id, document_id, user_id, backup_hash, key_version, created_at \
              FROM document_backups \
              WHERE document_id = $1 AND user_id = $2 \
              ORDER BY created_at DESC",
@@ -301,10 +570,362 @@ impl DocumentStorageService {
                 document_id: r.document_id,
                 user_id: r.user_id,
                 backup_hash: r.backup_hash,
+                key_version: r.key_version,
                 created_at: r.created_at,
             })
             .collect())
     }
+
+    /// Encrypt a death certificate under a fresh data key and store it for a
+    /// death verification case. A case holds a single certificate.
+    pub async fn store_death_certificate(
+        conn: &mut PgConnection,
+        keyring: &DocumentKeyRing,
+        case_id: Uuid,
+        uploaded_by: Uuid,
+        filename: &str,
+        content_type: &str,
+        content: &[u8],
+    ) -> Result<DeathCertificateRecord, ApiError> {
+        let certificate_id = Uuid::new_v4();
+        let (key_version, master) = keyring.current_master_key().await?;
+        let sealed = seal_document(certificate_id, content, &master)?;
+
+        let record = sqlx::query_as::<_, DeathCertificateRecord>(&format!(
+            "INSERT INTO death_certificates \
+                 (id, case_id, uploaded_by, filename, content_type, size_bytes, sha256, \
+                  encrypted_content, encryption_nonce, wrapped_data_key, data_key_nonce, key_version) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12) \
+             ON CONFLICT (case_id) DO NOTHING \
+             RETURNING {CERTIFICATE_COLUMNS}"
+        ))
+        .bind(certificate_id)
+        .bind(case_id)
+        .bind(uploaded_by)
+        .bind(filename)
+        .bind(content_type)
+        .bind(content.len() as i64)
+        .bind(hex::encode(Sha256::digest(content)))
+        .bind(&sealed.ciphertext)
+        .bind(&sealed.content_nonce)
+        .bind(&sealed.wrapped_data_key)
+        .bind(&sealed.data_key_nonce)
+        .bind(key_version)
+        .fetch_optional(conn)
+        .await?
+        .ok_or_else(|| {
+            ApiError::Conflict("A death certificate has already been uploaded".to_string())
+        })?;
+        Ok(record)
+    }
+
+    /// The death certificate stored for a case, decrypted.
+    pub async fn retrieve_death_certificate(
+        db: &PgPool,
+        keyring: &DocumentKeyRing,
+        case_id: Uuid,
+    ) -> Result<(DeathCertificateRecord, Vec<u8>), ApiError> {
+        #[derive(sqlx::FromRow)]
+        struct Row {
+            #[sqlx(flatten)]
+            record: DeathCertificateRecord,
+            #[sqlx(flatten)]
+            encrypted: EncryptedRow,
+        }
+
+        let row = sqlx::query_as::<_, Row>(&format!(
+            "SELECT {CERTIFICATE_COLUMNS}, encrypted_content, encryption_nonce, \
+                    wrapped_data_key, data_key_nonce \
+             FROM death_certificates WHERE case_id = $1"
+        ))
+        .bind(case_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("No death certificate has been uploaded".to_string()))?;
+
+        let content = decrypt_row(keyring, row.record.id, row.encrypted).await?;
+        Ok((row.record, content))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Key rotation job
+// ---------------------------------------------------------------------------
+
+const REWRAP_BATCH_SIZE: i64 = 100;
+
+#[derive(Clone, Copy)]
+enum RewrapTable {
+    Documents,
+    Backups,
+    Certificates,
+}
+
+impl RewrapTable {
+    /// One page of rows whose data key is not wrapped with the current master
+    /// key, ordered by `(created_at, id)` and starting after the cursor in
+    /// `$3, $4` (no cursor when `$3` is NULL).
+    fn select_sql(self) -> &'static str {
+        match self {
+            Self::Documents => {
+                "SELECT id, id AS document_id, created_at, encrypted_content, encryption_nonce, \
+                        wrapped_data_key, data_key_nonce, key_version \
+                 FROM will_documents \
+                 WHERE is_encrypted = TRUE AND encrypted_content IS NOT NULL \
+                   AND (key_version IS NULL OR key_version <> $1) \
+                   AND ($3::timestamptz IS NULL OR (created_at, id) > ($3, $4)) \
+                 ORDER BY created_at ASC, id ASC LIMIT $2"
+            }
+            Self::Backups => {
+                "SELECT id, document_id, created_at, encrypted_content, encryption_nonce, \
+                        wrapped_data_key, data_key_nonce, key_version \
+                 FROM document_backups \
+                 WHERE (key_version IS NULL OR key_version <> $1) \
+                   AND ($3::timestamptz IS NULL OR (created_at, id) > ($3, $4)) \
+                 ORDER BY created_at ASC, id ASC LIMIT $2"
+            }
+            Self::Certificates => {
+                "SELECT id, id AS document_id, created_at, encrypted_content, encryption_nonce, \
+                        wrapped_data_key, data_key_nonce, key_version \
+                 FROM death_certificates \
+                 WHERE key_version <> $1 \
+                   AND ($3::timestamptz IS NULL OR (created_at, id) > ($3, $4)) \
+                 ORDER BY created_at ASC, id ASC LIMIT $2"
+            }
+        }
+    }
+
+    /// Key-only update for rows already under envelope encryption. The
+    /// ciphertext is untouched, which keeps backup objects in storage valid.
+    /// Guarded on the previous key version so a concurrent writer wins.
+    fn rewrap_sql(self) -> &'static str {
+        match self {
+            Self::Documents => {
+                "UPDATE will_documents \
+                 SET wrapped_data_key = $1, data_key_nonce = $2, key_version = $3 \
+                 WHERE id = $4 AND key_version IS NOT DISTINCT FROM $5"
+            }
+            Self::Backups => {
+                "UPDATE document_backups \
+                 SET wrapped_data_key = $1, data_key_nonce = $2, key_version = $3 \
+                 WHERE id = $4 AND key_version IS NOT DISTINCT FROM $5"
+            }
+            Self::Certificates => {
+                "UPDATE death_certificates \
+                 SET wrapped_data_key = $1, data_key_nonce = $2, key_version = $3 \
+                 WHERE id = $4 AND key_version IS NOT DISTINCT FROM $5"
+            }
+        }
+    }
+
+    /// Full re-encryption of a legacy row, which always holds its ciphertext
+    /// inline. A backup's hash covers its ciphertext, so it is recomputed in
+    /// the same statement. Guarded the same way as [`Self::rewrap_sql`].
+    fn reseal_sql(self) -> &'static str {
+        match self {
+            Self::Documents => {
+                "UPDATE will_documents \
+                 SET encrypted_content = $1, encryption_nonce = $2, wrapped_data_key = $3, \
+                     data_key_nonce = $4, key_version = $5 \
+                 WHERE id = $6 AND key_version IS NOT DISTINCT FROM $7"
+            }
+            Self::Backups => {
+                "UPDATE document_backups \
+                 SET encrypted_content = $1, backup_hash = encode(sha256($1), 'hex'), \
+                     encryption_nonce = $2, wrapped_data_key = $3, data_key_nonce = $4, \
+                     key_version = $5 \
+                 WHERE id = $6 AND key_version IS NOT DISTINCT FROM $7"
+            }
+            Self::Certificates => {
+                "UPDATE death_certificates \
+                 SET encrypted_content = $1, encryption_nonce = $2, wrapped_data_key = $3, \
+                     data_key_nonce = $4, key_version = $5 \
+                 WHERE id = $6 AND key_version IS NOT DISTINCT FROM $7"
+            }
+        }
+    }
+}
+
+/// Outcome of migrating one row to the current master key.
+enum Rewrapped {
+    /// Only the data key was re-wrapped.
+    Key {
+        wrapped_data_key: Vec<u8>,
+        data_key_nonce: Vec<u8>,
+    },
+    /// A legacy row was re-encrypted under a fresh data key.
+    Resealed(SealedDocument),
+}
+
+/// Background job that re-wraps document data keys under the current master
+/// key version, and migrates legacy single-key rows to envelope encryption.
+pub struct DocumentKeyRotationService {
+    db: PgPool,
+    keyring: Arc<DocumentKeyRing>,
+}
+
+impl DocumentKeyRotationService {
+    pub fn new(db: PgPool, keyring: Arc<DocumentKeyRing>) -> Self {
+        Self { db, keyring }
+    }
+
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(300));
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.rewrap_outdated_keys().await {
+                    error!("Document key rotation error: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Process every document, backup and death certificate whose data key is
+    /// not wrapped with the current master key version.
+    pub async fn rewrap_outdated_keys(&self) -> Result<KeyRotationResult, ApiError> {
+        let (current_version, current_master) = self.keyring.current_master_key().await?;
+
+        let (documents_rewrapped, documents_failed) = self
+            .rewrap_table(RewrapTable::Documents, current_version, &current_master)
+            .await?;
+        let (backups_rewrapped, backups_failed) = self
+            .rewrap_table(RewrapTable::Backups, current_version, &current_master)
+            .await?;
+        let (certificates_rewrapped, certificates_failed) = self
+            .rewrap_table(RewrapTable::Certificates, current_version, &current_master)
+            .await?;
+
+        if documents_rewrapped + backups_rewrapped + certificates_rewrapped > 0 {
+            info!(
+                key_version = current_version,
+                "Re-wrapped {} document keys, {} backup keys and {} certificate keys",
+                documents_rewrapped,
+                backups_rewrapped,
+                certificates_rewrapped
+            );
+        }
+
+        Ok(KeyRotationResult {
+            current_key_version: current_version,
+            documents_rewrapped,
+            backups_rewrapped,
+            certificates_rewrapped,
+            failed: documents_failed + backups_failed + certificates_failed,
+        })
+    }
+
+    /// Walk every outdated row of `table` in pages. The cursor moves past
+    /// rows that fail, so they are retried on the next run without holding
+    /// back the rows behind them. Only rows whose guarded update applied are
+    /// counted; a row changed concurrently is left to its writer.
+    async fn rewrap_table(
+        &self,
+        table: RewrapTable,
+        current_version: i32,
+        current_master: &LessSafeKey,
+    ) -> Result<(usize, usize), ApiError> {
+        #[derive(sqlx::FromRow)]
+        struct Row {
+            id: Uuid,
+            document_id: Uuid,
+            created_at: DateTime<Utc>,
+            #[sqlx(flatten)]
+            encrypted: EncryptedRow,
+        }
+
+        let mut rewrapped = 0usize;
+        let mut failed = 0usize;
+        let mut cursor: Option<(DateTime<Utc>, Uuid)> = None;
+
+        loop {
+            let rows = sqlx::query_as::<_, Row>(table.select_sql())
+                .bind(current_version)
+                .bind(REWRAP_BATCH_SIZE)
+                .bind(cursor.map(|(created_at, _)| created_at))
+                .bind(cursor.map(|(_, id)| id))
+                .fetch_all(&self.db)
+                .await?;
+            let batch_len = rows.len();
+
+            for row in rows {
+                cursor = Some((row.created_at, row.id));
+                let previous_version = row.encrypted.key_version;
+                let update = match self
+                    .rewrap_row(row.document_id, row.encrypted, current_master)
+                    .await
+                {
+                    Ok(Rewrapped::Key {
+                        wrapped_data_key,
+                        data_key_nonce,
+                    }) => {
+                        sqlx::query(table.rewrap_sql())
+                            .bind(wrapped_data_key)
+                            .bind(data_key_nonce)
+                            .bind(current_version)
+                            .bind(row.id)
+                            .bind(previous_version)
+                            .execute(&self.db)
+                            .await?
+                    }
+                    Ok(Rewrapped::Resealed(sealed)) => {
+                        sqlx::query(table.reseal_sql())
+                            .bind(sealed.ciphertext)
+                            .bind(sealed.content_nonce)
+                            .bind(sealed.wrapped_data_key)
+                            .bind(sealed.data_key_nonce)
+                            .bind(current_version)
+                            .bind(row.id)
+                            .bind(previous_version)
+                            .execute(&self.db)
+                            .await?
+                    }
+                    Err(e) => {
+                        failed += 1;
+                        warn!("Failed to re-wrap data key for row {}: {}", row.id, e);
+                        continue;
+                    }
+                };
+                if update.rows_affected() == 1 {
+                    rewrapped += 1;
+                }
+            }
+
+            if batch_len < REWRAP_BATCH_SIZE as usize {
+                break;
+            }
+        }
+
+        Ok((rewrapped, failed))
+    }
+
+    async fn rewrap_row(
+        &self,
+        document_id: Uuid,
+        row: EncryptedRow,
+        current_master: &LessSafeKey,
+    ) -> Result<Rewrapped, ApiError> {
+        match (row.key_version, &row.wrapped_data_key, &row.data_key_nonce) {
+            (Some(version), Some(wrapped), Some(nonce)) => {
+                let old_master = self.keyring.master_key(version).await?;
+                let (wrapped_data_key, data_key_nonce) =
+                    rewrap_data_key(document_id, wrapped, nonce, &old_master, current_master)?;
+                Ok(Rewrapped::Key {
+                    wrapped_data_key,
+                    data_key_nonce,
+                })
+            }
+            (None, _, _) => {
+                // Legacy row: content was encrypted directly with the shared
+                // key, so it has to be re-encrypted under a fresh data key.
+                let plaintext = decrypt_row(&self.keyring, document_id, row).await?;
+                seal_document(document_id, &plaintext, current_master).map(Rewrapped::Resealed)
+            }
+            _ => Err(ApiError::Internal(anyhow::anyhow!(
+                "Incomplete envelope encryption metadata"
+            ))),
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -314,59 +935,136 @@ impl DocumentStorageService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
 
     fn test_secret() -> Vec<u8> {
         b"test-encryption-key-for-unit-tests".to_vec()
     }
 
+    fn test_master(secret: &[u8]) -> LessSafeKey {
+        derive_key(secret, b"wrap-document-data-key").unwrap()
+    }
+
+    struct StaticSecrets(HashMap<String, String>);
+
+    #[async_trait::async_trait]
+    impl SecretsProvider for StaticSecrets {
+        async fn get_secret(&self, name: &str) -> Result<String, ApiError> {
+            self.0
+                .get(name)
+                .cloned()
+                .ok_or_else(|| ApiError::Internal(anyhow::anyhow!("missing {name}")))
+        }
+
+        async fn rotate_secret(&self, _name: &str, _new_value: &str) -> Result<(), ApiError> {
+            Ok(())
+        }
+    }
+
+    fn keyring(entries: &[(&str, &str)]) -> DocumentKeyRing {
+        let map = entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        DocumentKeyRing::new(Arc::new(StaticSecrets(map)))
+    }
+
     #[test]
     fn test_encrypt_decrypt_roundtrip() {
         let plaintext = b"Sensitive will document content for testing.";
-        let secret = test_secret();
+        let master = test_master(&test_secret());
+        let document_id = Uuid::new_v4();
 
-        let (ciphertext, nonce) = encrypt_bytes(plaintext, &secret).unwrap();
-        assert_ne!(ciphertext.as_slice(), plaintext);
-        assert_eq!(nonce.len(), NONCE_LEN);
+        let sealed = seal_document(document_id, plaintext, &master).unwrap();
+        assert_ne!(sealed.ciphertext.as_slice(), plaintext);
+        assert_eq!(sealed.content_nonce.len(), NONCE_LEN);
+        assert_eq!(sealed.data_key_nonce.len(), NONCE_LEN);
 
-        let decrypted = decrypt_bytes(&ciphertext, &nonce, &secret).unwrap();
+        let decrypted = open_document(document_id, &sealed, &master).unwrap();
         assert_eq!(decrypted, plaintext);
     }
 
     #[test]
     fn test_decrypt_with_wrong_key_fails() {
-        let plaintext = b"Secret data";
-        let secret = test_secret();
-        let wrong_secret = b"wrong-key-that-should-not-work!!".to_vec();
+        let master = test_master(&test_secret());
+        let wrong_master = test_master(b"wrong-key-that-should-not-work!!");
+        let document_id = Uuid::new_v4();
 
-        let (ciphertext, nonce) = encrypt_bytes(plaintext, &secret).unwrap();
-        let result = decrypt_bytes(&ciphertext, &nonce, &wrong_secret);
-        assert!(result.is_err());
+        let sealed = seal_document(document_id, b"Secret data", &master).unwrap();
+        assert!(open_document(document_id, &sealed, &wrong_master).is_err());
+    }
+
+    #[test]
+    fn test_decrypt_with_other_document_id_fails() {
+        let master = test_master(&test_secret());
+        let sealed = seal_document(Uuid::new_v4(), b"Secret data", &master).unwrap();
+        assert!(open_document(Uuid::new_v4(), &sealed, &master).is_err());
     }
 
     #[test]
     fn test_decrypt_with_invalid_nonce_fails() {
-        let plaintext = b"Secret data";
-        let secret = test_secret();
+        let master = test_master(&test_secret());
+        let document_id = Uuid::new_v4();
 
-        let (ciphertext, _) = encrypt_bytes(plaintext, &secret).unwrap();
-        let bad_nonce = vec![0u8; NONCE_LEN];
-        let result = decrypt_bytes(&ciphertext, &bad_nonce, &secret);
-        assert!(result.is_err());
+        let mut sealed = seal_document(document_id, b"Secret data", &master).unwrap();
+        sealed.content_nonce = vec![0u8; NONCE_LEN];
+        assert!(open_document(document_id, &sealed, &master).is_err());
     }
 
     #[test]
     fn test_invalid_nonce_length_fails() {
-        let secret = test_secret();
-        let result = decrypt_bytes(&[0u8; 32], &[0u8; 5], &secret);
+        let key = test_master(&test_secret());
+        let result = open(&key, &[0u8; 32], &[0u8; 5], &[]);
         assert!(result.is_err());
     }
 
     #[test]
-    fn test_different_plaintexts_produce_different_ciphertexts() {
+    fn test_each_document_gets_its_own_data_key() {
+        let master = test_master(&test_secret());
+        let document_id = Uuid::new_v4();
+        let a = seal_document(document_id, b"Document A", &master).unwrap();
+        let b = seal_document(document_id, b"Document A", &master).unwrap();
+        assert_ne!(a.ciphertext, b.ciphertext);
+        assert_ne!(a.wrapped_data_key, b.wrapped_data_key);
+    }
+
+    #[test]
+    fn test_rewrap_preserves_content() {
+        let old_master = test_master(b"old-master-key-material-0123456789");
+        let new_master = test_master(b"new-master-key-material-0123456789");
+        let document_id = Uuid::new_v4();
+        let plaintext = b"Content that must survive rotation";
+
+        let sealed = seal_document(document_id, plaintext, &old_master).unwrap();
+        let (wrapped_data_key, data_key_nonce) = rewrap_data_key(
+            document_id,
+            &sealed.wrapped_data_key,
+            &sealed.data_key_nonce,
+            &old_master,
+            &new_master,
+        )
+        .unwrap();
+        let rotated = SealedDocument {
+            wrapped_data_key,
+            data_key_nonce,
+            ..sealed
+        };
+
+        assert_eq!(
+            open_document(document_id, &rotated, &new_master).unwrap(),
+            plaintext
+        );
+        assert!(open_document(document_id, &rotated, &old_master).is_err());
+    }
+
+    #[test]
+    fn test_legacy_ciphertext_still_opens() {
         let secret = test_secret();
-        let (ct1, _) = encrypt_bytes(b"Document A", &secret).unwrap();
-        let (ct2, _) = encrypt_bytes(b"Document B", &secret).unwrap();
-        assert_ne!(ct1, ct2);
+        let legacy_key = derive_key(&secret, b"aes-256-gcm-key").unwrap();
+        let (ciphertext, nonce) = seal(&legacy_key, b"Legacy document", &[]).unwrap();
+
+        let plaintext = open_legacy(&ciphertext, &nonce, &secret).unwrap();
+        assert_eq!(plaintext, b"Legacy document");
     }
 
     #[test]
@@ -375,8 +1073,8 @@ mod tests {
         let plaintext = b"Deterministic test";
 
         // Encrypt twice with same nonce to verify key derivation consistency
-        let key1 = derive_key(&secret).unwrap();
-        let key2 = derive_key(&secret).unwrap();
+        let key1 = test_master(&secret);
+        let key2 = test_master(&secret);
 
         let nonce_bytes = [1u8; NONCE_LEN];
 
@@ -398,4 +1096,42 @@ mod tests {
 
         assert_eq!(buf1, buf2);
     }
+
+    #[tokio::test]
+    async fn test_keyring_refuses_missing_configuration() {
+        assert!(keyring(&[]).ensure_configured().await.is_err());
+        assert!(keyring(&[(MASTER_KEY_VERSION_SECRET, "1")])
+            .ensure_configured()
+            .await
+            .is_err());
+        assert!(keyring(&[(MASTER_KEY_VERSION_SECRET, "zero")])
+            .ensure_configured()
+            .await
+            .is_err());
+        assert!(keyring(&[
+            (MASTER_KEY_VERSION_SECRET, "1"),
+            ("DOCUMENT_MASTER_KEY_V1", "too-short"),
+        ])
+        .ensure_configured()
+        .await
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_keyring_resolves_current_version() {
+        let ring = keyring(&[
+            (MASTER_KEY_VERSION_SECRET, "2"),
+            (
+                "DOCUMENT_MASTER_KEY_V1",
+                "first-master-key-material-0123456789",
+            ),
+            (
+                "DOCUMENT_MASTER_KEY_V2",
+                "second-master-key-material-0123456789",
+            ),
+        ]);
+        assert_eq!(ring.ensure_configured().await.unwrap(), 2);
+        assert!(ring.master_key(1).await.is_ok());
+        assert!(ring.master_key(3).await.is_err());
+    }
 }
@@ -3,9 +3,13 @@
 //! Provides verification that a legal will document matches the hash stored on-chain,
 //! ensuring the document has not been altered. Supports version-specific verification
 //! and enables trustless verification for courts, executors, and beneficiaries.
+//! Finalized documents also carry their RFC 3161 timestamp evidence, re-validated
+//! on every request.
 
 use crate::api_error::ApiError;
+use crate::will_timestamp::{TimestampEvidence, WillTimestampService};
 use ring::digest::{digest, SHA256};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use uuid::Uuid;
@@ -40,7 +44,7 @@ pub struct DocumentContentVerificationRequest {
     pub version: Option<u32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct VerificationResult {
     /// Whether the document matches the stored hash
     pub is_valid: bool,
@@ -58,9 +62,11 @@ pub struct VerificationResult {
     pub verified_at: chrono::DateTime<chrono::Utc>,
     /// Additional verification details
     pub details: VerificationDetails,
+    /// RFC 3161 timestamp over the stored hash, if the document has one
+    pub timestamp: Option<TimestampEvidence>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct VerificationDetails {
     /// Whether this is the active version
     pub is_active_version: bool,
@@ -93,6 +99,8 @@ impl DocumentVerificationService {
 
         let is_valid = computed_hash == doc.will_hash;
 
+        let timestamp = WillTimestampService::evidence(db, doc.id, &doc.will_hash).await?;
+
         Ok(VerificationResult {
             is_valid,
             document_id: doc.id,
@@ -108,6 +116,7 @@ impl DocumentVerificationService {
                 generated_at: doc.generated_at,
                 is_encrypted: doc.is_encrypted,
             },
+            timestamp,
         })
     }
 
@@ -127,6 +136,8 @@ impl DocumentVerificationService {
 
         let is_valid = provided_hash_normalized == stored_hash_normalized;
 
+        let timestamp = WillTimestampService::evidence(db, doc.id, &doc.will_hash).await?;
+
         Ok(VerificationResult {
             is_valid,
             document_id: doc.id,
@@ -142,6 +153,7 @@ impl DocumentVerificationService {
                 generated_at: doc.generated_at,
                 is_encrypted: doc.is_encrypted,
             },
+            timestamp,
         })
     }
 
@@ -160,6 +172,8 @@ impl DocumentVerificationService {
 
         let is_valid = computed_hash == doc.will_hash;
 
+        let timestamp = WillTimestampService::evidence(db, doc.id, &doc.will_hash).await?;
+
         Ok(VerificationResult {
             is_valid,
             document_id: doc.id,
@@ -175,6 +189,7 @@ impl DocumentVerificationService {
                 generated_at: doc.generated_at,
                 is_encrypted: doc.is_encrypted,
             },
+            timestamp,
         })
     }
 
@@ -190,6 +205,7 @@ impl DocumentVerificationService {
         for doc in versions {
             let computed_hash = Self::compute_document_hash(&doc.pdf_base64)?;
             let is_valid = computed_hash == doc.will_hash;
+            let timestamp = WillTimestampService::evidence(db, doc.id, &doc.will_hash).await?;
 
             results.push(VerificationResult {
                 is_valid,
@@ -206,6 +222,7 @@ impl DocumentVerificationService {
                     generated_at: doc.generated_at,
                     is_encrypted: doc.is_encrypted,
                 },
+                timestamp,
             });
         }
 
@@ -3,6 +3,7 @@ use crate::notifications::{
     audit_action, entity_type, notif_type, AuditLogService, NotificationService,
 };
 use chrono::{DateTime, Duration, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use uuid::Uuid;
@@ -27,7 +28,7 @@ pub struct EmergencyAccess {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct GrantEmergencyAccessRequest {
     pub plan_id: Uuid,
     pub access_type: String, // 'admin_override', 'temporary_access', etc.
@@ -35,7 +36,7 @@ pub struct GrantEmergencyAccessRequest {
     pub expires_in_hours: Option<i64>, // If provided, access expires after N hours
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct RevokeEmergencyAccessRequest {
     pub access_id: Uuid,
     pub reason: String,
@@ -1,3 +1,4 @@
+use crate::break_glass::BreakGlassService;
 use crate::emergency_access::EmergencyAccessService;
 use sqlx::PgPool;
 use std::sync::Arc;
@@ -44,6 +45,18 @@ impl EmergencyAccessJobService {
                         error!("Error marking expired emergency access: {}", e);
                     }
                 }
+
+                // Grant break-glass requests whose waiting period has passed
+                match BreakGlassService::activate_due(&db).await {
+                    Ok(count) => {
+                        if count > 0 {
+                            info!("Activated {} break-glass emergency access requests", count);
+                        }
+                    }
+                    Err(e) => {
+                        error!("Error activating break-glass emergency access: {}", e);
+                    }
+                }
             }
         });
     }
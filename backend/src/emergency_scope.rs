@@ -0,0 +1,490 @@
+//! Least-privilege scopes for emergency access grants.
+//!
+//! A grant carries a set of [`EmergencyPermission`]s instead of all-or-nothing
+//! access to the owner's plan. Each emergency-contact route requires one
+//! permission, checked by [`EmergencyScopeService::authorize`], and every use
+//! is written to the emergency access audit log with the permission it relied
+//! on. Bill payments are further limited to the payees the grant names and to
+//! its spending cap; the contract's `emergency_withdraw` enforces the same
+//! limits on-chain.
+
+use crate::api_error::ApiError;
+use crate::audit_chain;
+use crate::break_glass::{BreakGlassGrant, BreakGlassService};
+use crate::inheritance_trigger::{self, InheritanceTrigger, InheritanceTriggerService};
+use crate::message_access_audit::{MessageAccessAction, MessageAccessAuditService};
+use crate::secure_messages::{LegacyMessage, MessageEncryptionService};
+use crate::service::{EmergencyAccessAuditLog, EmergencyAccessService};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+/// Emergency access audit log actions recorded with a permission.
+pub mod action {
+    pub const SCOPE_UPDATED: &str = "emergency_scope_updated";
+    pub const DOCUMENTS_VIEWED: &str = "emergency_documents_viewed";
+    pub const MESSAGES_READ: &str = "emergency_messages_read";
+    pub const BILL_PAID: &str = "emergency_bill_paid";
+    pub const INHERITANCE_TRIGGERED: &str = "emergency_inheritance_triggered";
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EmergencyPermission {
+    /// See the plan and its will documents.
+    ViewDocuments,
+    /// Read the owner's server-encrypted legacy messages.
+    ReadMessages,
+    /// Pay the grant's named payees, up to its spending cap.
+    PayBills,
+    /// Trigger inheritance for the plan.
+    TriggerInheritance,
+}
+
+impl EmergencyPermission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ViewDocuments => "view_documents",
+            Self::ReadMessages => "read_messages",
+            Self::PayBills => "pay_bills",
+            Self::TriggerInheritance => "trigger_inheritance",
+        }
+    }
+}
+
+/// The permissions and bill-payment limits of a grant.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
+pub struct EmergencyScope {
+    pub permissions: Vec<String>,
+    pub allowed_payees: Vec<String>,
+    pub spending_cap: Option<Decimal>,
+    pub spent_amount: Decimal,
+}
+
+impl EmergencyScope {
+    pub fn allows(&self, permission: EmergencyPermission) -> bool {
+        self.permissions.iter().any(|p| p == permission.as_str())
+    }
+}
+
+/// A requested scope. Without `permissions` a grant may only view documents.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct ScopeRequest {
+    #[serde(default = "default_permissions")]
+    pub permissions: Vec<EmergencyPermission>,
+    #[serde(default)]
+    pub allowed_payees: Vec<String>,
+    pub spending_cap: Option<Decimal>,
+}
+
+fn default_permissions() -> Vec<EmergencyPermission> {
+    vec![EmergencyPermission::ViewDocuments]
+}
+
+impl ScopeRequest {
+    /// Check the scope is coherent and return its permissions as stored.
+    pub fn validate(&self) -> Result<Vec<String>, ApiError> {
+        if self.permissions.is_empty() {
+            return Err(ApiError::BadRequest(
+                "At least one permission is required".to_string(),
+            ));
+        }
+        let pays_bills = self.permissions.contains(&EmergencyPermission::PayBills);
+        if pays_bills {
+            if self.allowed_payees.iter().all(|p| p.trim().is_empty()) {
+                return Err(ApiError::BadRequest(
+                    "pay_bills requires at least one allowed payee".to_string(),
+                ));
+            }
+            if self.spending_cap.is_none_or(|cap| cap <= Decimal::ZERO) {
+                return Err(ApiError::BadRequest(
+                    "pay_bills requires a positive spending_cap".to_string(),
+                ));
+            }
+        } else if !self.allowed_payees.is_empty() || self.spending_cap.is_some() {
+            return Err(ApiError::BadRequest(
+                "allowed_payees and spending_cap only apply to pay_bills".to_string(),
+            ));
+        }
+
+        let mut permissions: Vec<String> = self
+            .permissions
+            .iter()
+            .map(|p| p.as_str().to_string())
+            .collect();
+        permissions.sort();
+        permissions.dedup();
+        Ok(permissions)
+    }
+
+    pub fn payees(&self) -> Vec<String> {
+        self.allowed_payees
+            .iter()
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct EmergencyDocumentView {
+    pub id: Uuid,
+    pub version: i32,
+    pub template: String,
+    pub will_type: String,
+    pub will_hash: String,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EmergencyMessageView {
+    #[serde(flatten)]
+    pub message: LegacyMessage,
+    /// The message text; `None` for end-to-end sealed messages.
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PayBillRequest {
+    pub payee: String,
+    pub amount: Decimal,
+    pub reference: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct EmergencyBillPayment {
+    pub id: Uuid,
+    pub grant_id: Uuid,
+    pub plan_id: Uuid,
+    pub requested_by: Uuid,
+    pub payee: String,
+    pub amount: Decimal,
+    pub reference: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TriggerInheritanceRequest {
+    pub reason: String,
+}
+
+pub struct EmergencyScopeService;
+
+impl EmergencyScopeService {
+    /// The caller's effective grant, provided it carries `permission`.
+    pub async fn authorize(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        grant_id: Uuid,
+        permission: EmergencyPermission,
+    ) -> Result<BreakGlassGrant, ApiError> {
+        let grant = BreakGlassService::effective_grant(conn, user_id, grant_id).await?;
+        if !grant.scope.allows(permission) {
+            return Err(ApiError::Forbidden(format!(
+                "This emergency access grant does not include the {} permission",
+                permission.as_str()
+            )));
+        }
+        Ok(grant)
+    }
+
+    /// Write an emergency access audit log entry for an action performed
+    /// under `permission`.
+    pub async fn record_use(
+        executor: impl sqlx::PgExecutor<'_>,
+        grant: &BreakGlassGrant,
+        permission: Option<EmergencyPermission>,
+        action: &str,
+        metadata: serde_json::Value,
+    ) -> Result<(), ApiError> {
+        let entry = EmergencyAccessAuditLog {
+            id: Uuid::new_v4(),
+            grant_id: grant.id,
+            user_id: grant.owner_id,
+            emergency_contact_id: grant.emergency_contact_id,
+            action: action.to_string(),
+            permission: permission.map(|p| p.as_str().to_string()),
+            metadata,
+            created_at: audit_chain::entry_timestamp(Utc::now()),
+        };
+        EmergencyAccessService::write_audit_log(executor, &entry).await
+    }
+
+    /// Replace the scope of a pending or active grant on the owner's plan.
+    /// Bill payments already made still count towards the new cap.
+    pub async fn update_scope(
+        db: &PgPool,
+        owner_id: Uuid,
+        grant_id: Uuid,
+        req: &ScopeRequest,
+    ) -> Result<BreakGlassGrant, ApiError> {
+        let permissions = req.validate()?;
+        let mut tx = db.begin().await?;
+        let grant = BreakGlassService::fetch_for_update(&mut tx, grant_id).await?;
+        if grant.owner_id != owner_id {
+            return Err(ApiError::NotFound(format!(
+                "Break-glass request {grant_id} not found"
+            )));
+        }
+        if !matches!(grant.status.as_str(), "pending" | "active") {
+            return Err(ApiError::Conflict(format!(
+                "Break-glass request is already {}",
+                grant.status
+            )));
+        }
+
+        sqlx::query(
+            "UPDATE emergency_access_grants \
+             SET permissions = $2, allowed_payees = $3, spending_cap = $4 \
+             WHERE id = $1",
+        )
+        .bind(grant_id)
+        .bind(&permissions)
+        .bind(req.payees())
+        .bind(req.spending_cap)
+        .execute(&mut *tx)
+        .await?;
+        Self::record_use(
+            &mut *tx,
+            &grant,
+            None,
+            action::SCOPE_UPDATED,
+            json!({
+                "old_permissions": grant.scope.permissions,
+                "permissions": permissions,
+                "allowed_payees": req.payees(),
+                "spending_cap": req.spending_cap,
+            }),
+        )
+        .await?;
+
+        let grant = BreakGlassService::fetch(&mut tx, grant_id).await?;
+        tx.commit().await?;
+        Ok(grant)
+    }
+
+    /// The owner's will documents for the granted plan.
+    pub async fn list_documents(
+        db: &PgPool,
+        user_id: Uuid,
+        grant_id: Uuid,
+    ) -> Result<Vec<EmergencyDocumentView>, ApiError> {
+        let permission = EmergencyPermission::ViewDocuments;
+        let mut tx = db.begin().await?;
+        let grant = Self::authorize(&mut tx, user_id, grant_id, permission).await?;
+        let documents = sqlx::query_as::<_, EmergencyDocumentView>(
+            "SELECT id, version, template, will_type, will_hash, status, created_at \
+             FROM will_documents WHERE plan_id = $1 ORDER BY version DESC",
+        )
+        .bind(grant.plan_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        Self::record_use(
+            &mut *tx,
+            &grant,
+            Some(permission),
+            action::DOCUMENTS_VIEWED,
+            json!({ "viewed_by": user_id, "documents": documents.len() }),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(documents)
+    }
+
+    /// The owner's legacy messages, with the text of those the server can
+    /// decrypt. Each decryption is also written to the message access log.
+    pub async fn read_messages(
+        db: &PgPool,
+        user_id: Uuid,
+        grant_id: Uuid,
+    ) -> Result<Vec<EmergencyMessageView>, ApiError> {
+        let permission = EmergencyPermission::ReadMessages;
+        let mut conn = db.acquire().await?;
+        let grant = Self::authorize(&mut conn, user_id, grant_id, permission).await?;
+        drop(conn);
+
+        let messages = MessageEncryptionService::list_owner_messages(db, grant.owner_id).await?;
+        let mut views = Vec::with_capacity(messages.len());
+        for message in messages {
+            let body =
+                MessageEncryptionService::decrypt_owner_message(db, grant.owner_id, message.id)
+                    .await?;
+            if body.is_some() {
+                MessageAccessAuditService::log_access(
+                    db,
+                    Some(message.id),
+                    user_id,
+                    MessageAccessAction::Decrypted,
+                    None,
+                    None,
+                    json!({ "emergency_grant_id": grant.id, "permission": permission.as_str() }),
+                )
+                .await?;
+            }
+            views.push(EmergencyMessageView { message, body });
+        }
+
+        Self::record_use(
+            db,
+            &grant,
+            Some(permission),
+            action::MESSAGES_READ,
+            json!({
+                "read_by": user_id,
+                "messages": views.len(),
+                "decrypted": views.iter().filter(|v| v.body.is_some()).count(),
+            }),
+        )
+        .await?;
+        Ok(views)
+    }
+
+    /// Approve a bill payment to one of the grant's payees, counting it
+    /// against the spending cap.
+    pub async fn pay_bill(
+        db: &PgPool,
+        user_id: Uuid,
+        grant_id: Uuid,
+        req: &PayBillRequest,
+    ) -> Result<EmergencyBillPayment, ApiError> {
+        if req.amount <= Decimal::ZERO {
+            return Err(ApiError::BadRequest("amount must be positive".to_string()));
+        }
+        let permission = EmergencyPermission::PayBills;
+        let payee = req.payee.trim();
+        let mut tx = db.begin().await?;
+        let grant = Self::authorize(&mut tx, user_id, grant_id, permission).await?;
+        if !grant.scope.allowed_payees.iter().any(|p| p == payee) {
+            return Err(ApiError::Forbidden(format!(
+                "{payee} is not a payee this grant may pay"
+            )));
+        }
+
+        let spent: Option<Decimal> = sqlx::query_scalar(
+            "UPDATE emergency_access_grants SET spent_amount = spent_amount + $2 \
+             WHERE id = $1 AND spent_amount + $2 <= spending_cap \
+             RETURNING spent_amount",
+        )
+        .bind(grant_id)
+        .bind(req.amount)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let spent = spent.ok_or_else(|| {
+            ApiError::Forbidden(format!(
+                "Payment would exceed the spending cap of {}",
+                grant.scope.spending_cap.unwrap_or_default()
+            ))
+        })?;
+
+        let payment = sqlx::query_as::<_, EmergencyBillPayment>(
+            "INSERT INTO emergency_bill_payments \
+                 (grant_id, plan_id, requested_by, payee, amount, reference) \
+             VALUES ($1, $2, $3, $4, $5, $6) \
+             RETURNING id, grant_id, plan_id, requested_by, payee, amount, reference, created_at",
+        )
+        .bind(grant_id)
+        .bind(grant.plan_id)
+        .bind(user_id)
+        .bind(payee)
+        .bind(req.amount)
+        .bind(req.reference.as_deref())
+        .fetch_one(&mut *tx)
+        .await?;
+        Self::record_use(
+            &mut *tx,
+            &grant,
+            Some(permission),
+            action::BILL_PAID,
+            json!({
+                "payment_id": payment.id,
+                "payee": payee,
+                "amount": req.amount,
+                "spent_amount": spent,
+                "spending_cap": grant.scope.spending_cap,
+            }),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(payment)
+    }
+
+    pub async fn trigger_inheritance(
+        db: &PgPool,
+        user_id: Uuid,
+        grant_id: Uuid,
+        req: &TriggerInheritanceRequest,
+    ) -> Result<InheritanceTrigger, ApiError> {
+        if req.reason.trim().is_empty() {
+            return Err(ApiError::BadRequest("A reason is required".to_string()));
+        }
+        let permission = EmergencyPermission::TriggerInheritance;
+        let mut tx = db.begin().await?;
+        let grant = Self::authorize(&mut tx, user_id, grant_id, permission).await?;
+        let trigger = InheritanceTriggerService::trigger(
+            &mut tx,
+            grant.plan_id,
+            inheritance_trigger::source::EMERGENCY_ACCESS,
+            Some(user_id),
+            Some(grant_id),
+            None,
+            Some(req.reason.trim()),
+        )
+        .await?;
+        Self::record_use(
+            &mut *tx,
+            &grant,
+            Some(permission),
+            action::INHERITANCE_TRIGGERED,
+            json!({ "triggered_by": user_id, "reason": req.reason.trim() }),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(trigger)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scope(json: serde_json::Value) -> ScopeRequest {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn scope_defaults_to_viewing_documents() {
+        assert_eq!(scope(json!({})).validate().unwrap(), ["view_documents"]);
+    }
+
+    #[test]
+    fn bill_payments_need_payees_and_a_cap() {
+        assert!(
+            scope(json!({ "permissions": ["pay_bills"], "spending_cap": "100" }))
+                .validate()
+                .is_err()
+        );
+        assert!(
+            scope(json!({ "permissions": ["pay_bills"], "allowed_payees": ["GUTIL"] }))
+                .validate()
+                .is_err()
+        );
+        assert!(
+            scope(json!({ "permissions": ["view_documents"], "spending_cap": "100" }))
+                .validate()
+                .is_err()
+        );
+        let permissions = scope(json!({
+            "permissions": ["pay_bills", "read_messages", "pay_bills"],
+            "allowed_payees": ["GUTIL"],
+            "spending_cap": "100",
+        }))
+        .validate()
+        .unwrap();
+        assert_eq!(permissions, ["pay_bills", "read_messages"]);
+    }
+}
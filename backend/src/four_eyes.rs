@@ -0,0 +1,593 @@
+//! Four-eyes approval of high-risk admin actions.
+//!
+//! Pausing plans, overriding risk flags, rotating the message key, paying out
+//! insurance claims and approving KYC can each be put behind a second pair of
+//! eyes. When [`FourEyesPolicy`] requires it for an action, the requesting
+//! admin only records a [`PendingAction`]; a different admin holding the same
+//! permission approves or rejects it, and the action runs on approval. Pending
+//! actions that nobody decides expire.
+//!
+//! Which actions need approval comes from `FOUR_EYES_ACTIONS`, a
+//! comma-separated list of action types, `all` (the default) or `none`.
+//! `FOUR_EYES_EXPIRY_HOURS` sets how long a proposal stays open (default 24).
+//!
+//! Each step is audited against the pending action, so
+//! [`AuditLogService::list_for_entity`] returns the full chain.
+
+use crate::admin_rbac::{perm, Permission};
+use crate::api_error::ApiError;
+use crate::app::AppState;
+use crate::notifications::{audit_action, entity_type, AuditLogService};
+use crate::secure_messages::MessageKeyService;
+use crate::service::{
+    EmergencyAdminService, KycService, KycStatus, PausePlanRequest, RiskOverrideRequest,
+    UnpausePlanRequest,
+};
+use chrono::{DateTime, Duration, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{error, info};
+use uuid::Uuid;
+
+/// How long a proposal stays open unless `FOUR_EYES_EXPIRY_HOURS` says
+/// otherwise.
+pub const DEFAULT_EXPIRY_HOURS: i64 = 24;
+
+pub mod action_status {
+    pub const PENDING: &str = "pending";
+    pub const APPROVED: &str = "approved";
+    pub const EXECUTED: &str = "executed";
+    pub const FAILED: &str = "failed";
+    pub const REJECTED: &str = "rejected";
+    pub const EXPIRED: &str = "expired";
+}
+
+/// Every action type that can be put behind four-eyes approval.
+pub const ACTION_TYPES: &[&str] = &[
+    "pause_plan",
+    "unpause_plan",
+    "set_risk_override",
+    "rotate_message_key",
+    "payout_insurance_claim",
+    "approve_kyc",
+];
+
+/// A high-risk admin action and its payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "action_type", content = "payload", rename_all = "snake_case")]
+pub enum HighRiskAction {
+    PausePlan(PausePlanRequest),
+    UnpausePlan(UnpausePlanRequest),
+    SetRiskOverride(RiskOverrideRequest),
+    RotateMessageKey,
+    PayoutInsuranceClaim { claim_id: Uuid },
+    ApproveKyc { user_id: Uuid },
+}
+
+impl HighRiskAction {
+    pub fn action_type(&self) -> &'static str {
+        match self {
+            HighRiskAction::PausePlan(_) => "pause_plan",
+            HighRiskAction::UnpausePlan(_) => "unpause_plan",
+            HighRiskAction::SetRiskOverride(_) => "set_risk_override",
+            HighRiskAction::RotateMessageKey => "rotate_message_key",
+            HighRiskAction::PayoutInsuranceClaim { .. } => "payout_insurance_claim",
+            HighRiskAction::ApproveKyc { .. } => "approve_kyc",
+        }
+    }
+
+    /// The permission both the proposer and the approver must hold.
+    pub fn required_permission(&self) -> &'static str {
+        match self {
+            HighRiskAction::PausePlan(_)
+            | HighRiskAction::UnpausePlan(_)
+            | HighRiskAction::SetRiskOverride(_) => perm::RiskPause::NAME,
+            HighRiskAction::RotateMessageKey => perm::KeysRotate::NAME,
+            HighRiskAction::PayoutInsuranceClaim { .. } => perm::InsurancePayout::NAME,
+            HighRiskAction::ApproveKyc { .. } => perm::KycReview::NAME,
+        }
+    }
+
+    fn payload(&self) -> Result<Value, ApiError> {
+        let mut tagged = serde_json::to_value(self).map_err(|e| ApiError::Internal(e.into()))?;
+        Ok(tagged
+            .as_object_mut()
+            .and_then(|obj| obj.remove("payload"))
+            .unwrap_or(Value::Null))
+    }
+
+    fn from_parts(action_type: &str, payload: Value) -> Result<Self, ApiError> {
+        serde_json::from_value(json!({ "action_type": action_type, "payload": payload }))
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Invalid pending action: {e}")))
+    }
+
+    /// Run the action on behalf of `admin_id`, returning its result.
+    async fn execute(&self, state: &AppState, admin_id: Uuid) -> Result<Value, ApiError> {
+        let result = match self {
+            HighRiskAction::PausePlan(req) => serde_json::to_value(
+                EmergencyAdminService::pause_plan(&state.db, admin_id, req).await?,
+            ),
+            HighRiskAction::UnpausePlan(req) => serde_json::to_value(
+                EmergencyAdminService::unpause_plan(&state.db, admin_id, req).await?,
+            ),
+            HighRiskAction::SetRiskOverride(req) => serde_json::to_value(
+                EmergencyAdminService::set_risk_override(&state.db, admin_id, req).await?,
+            ),
+            HighRiskAction::RotateMessageKey => serde_json::to_value(
+                MessageKeyService::rotate_active_key(&state.db, admin_id).await?,
+            ),
+            HighRiskAction::PayoutInsuranceClaim { claim_id } => {
+                state.insurance_fund_service.payout_claim(*claim_id).await?;
+                Ok(json!({ "claim_id": claim_id }))
+            }
+            HighRiskAction::ApproveKyc { user_id } => serde_json::to_value(
+                KycService::update_kyc_status(&state.db, admin_id, *user_id, KycStatus::Approved)
+                    .await?,
+            ),
+        };
+        result.map_err(|e| ApiError::Internal(e.into()))
+    }
+}
+
+/// Which action types need a second admin, and for how long proposals stay
+/// open.
+#[derive(Debug, Clone)]
+pub struct FourEyesPolicy {
+    required: HashSet<&'static str>,
+    expiry: Duration,
+}
+
+impl FourEyesPolicy {
+    pub fn new(required: &[&str], expiry: Duration) -> Result<Self, ApiError> {
+        let mut set = HashSet::new();
+        for action_type in required {
+            let known = ACTION_TYPES
+                .iter()
+                .find(|known| *known == action_type)
+                .ok_or_else(|| {
+                    ApiError::Internal(anyhow::anyhow!(
+                        "Unknown four-eyes action type '{action_type}'"
+                    ))
+                })?;
+            set.insert(*known);
+        }
+        Ok(Self {
+            required: set,
+            expiry,
+        })
+    }
+
+    pub fn from_env() -> Result<Self, ApiError> {
+        let actions = std::env::var("FOUR_EYES_ACTIONS").unwrap_or_else(|_| "all".to_string());
+        let expiry_hours = match std::env::var("FOUR_EYES_EXPIRY_HOURS") {
+            Ok(hours) => hours
+                .trim()
+                .parse::<i64>()
+                .ok()
+                .filter(|h| *h > 0)
+                .ok_or_else(|| {
+                    ApiError::Internal(anyhow::anyhow!(
+                        "FOUR_EYES_EXPIRY_HOURS must be a positive number of hours"
+                    ))
+                })?,
+            Err(_) => DEFAULT_EXPIRY_HOURS,
+        };
+        Self::parse(&actions, Duration::hours(expiry_hours))
+    }
+
+    fn parse(actions: &str, expiry: Duration) -> Result<Self, ApiError> {
+        match actions.trim() {
+            "all" => Self::new(ACTION_TYPES, expiry),
+            "none" | "" => Self::new(&[], expiry),
+            list => {
+                let required: Vec<&str> = list
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .collect();
+                Self::new(&required, expiry)
+            }
+        }
+    }
+
+    pub fn requires(&self, action_type: &str) -> bool {
+        self.required.contains(action_type)
+    }
+
+    pub fn expiry(&self) -> Duration {
+        self.expiry
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
+pub struct PendingAction {
+    pub id: Uuid,
+    pub action_type: String,
+    pub payload: Value,
+    pub required_permission: String,
+    pub status: String,
+    pub proposed_by: Uuid,
+    pub proposed_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub decided_by: Option<Uuid>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub decision_reason: Option<String>,
+    pub executed_at: Option<DateTime<Utc>>,
+    pub result: Option<Value>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RejectPendingActionRequest {
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PendingActionFilters {
+    pub status: Option<String>,
+}
+
+/// What happened to a submitted action.
+pub enum Submission {
+    /// The action did not need approval and has run.
+    Executed(Value),
+    /// The action is waiting for a second admin.
+    Pending(Box<PendingAction>),
+}
+
+pub struct FourEyesService;
+
+impl FourEyesService {
+    /// Run `action` now, or record it for approval if the policy requires a
+    /// second admin.
+    pub async fn submit(
+        state: &AppState,
+        admin_id: Uuid,
+        action: HighRiskAction,
+    ) -> Result<Submission, ApiError> {
+        if !state.four_eyes.requires(action.action_type()) {
+            return Ok(Submission::Executed(action.execute(state, admin_id).await?));
+        }
+
+        let mut tx = state.db.begin().await?;
+        let pending = sqlx::query_as::<_, PendingAction>(
+            "INSERT INTO pending_admin_actions \
+                 (action_type, payload, required_permission, proposed_by, expires_at) \
+             VALUES ($1, $2, $3, $4, $5) RETURNING *",
+        )
+        .bind(action.action_type())
+        .bind(action.payload()?)
+        .bind(action.required_permission())
+        .bind(admin_id)
+        .bind(Utc::now() + state.four_eyes.expiry())
+        .fetch_one(&mut *tx)
+        .await?;
+        AuditLogService::log(
+            &mut *tx,
+            None,
+            Some(admin_id),
+            audit_action::PENDING_ACTION_PROPOSED,
+            Some(pending.id),
+            Some(entity_type::PENDING_ACTION),
+            None,
+            None,
+            Some(json!({
+                "action_type": pending.action_type,
+                "payload": pending.payload,
+                "expires_at": pending.expires_at,
+            })),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(Submission::Pending(Box::new(pending)))
+    }
+
+    pub async fn list(db: &PgPool, status: Option<&str>) -> Result<Vec<PendingAction>, ApiError> {
+        let actions = sqlx::query_as::<_, PendingAction>(
+            "SELECT * FROM pending_admin_actions \
+             WHERE $1::text IS NULL OR status = $1 \
+             ORDER BY proposed_at DESC",
+        )
+        .bind(status)
+        .fetch_all(db)
+        .await?;
+        Ok(actions)
+    }
+
+    pub async fn get(db: &PgPool, id: Uuid) -> Result<PendingAction, ApiError> {
+        sqlx::query_as::<_, PendingAction>("SELECT * FROM pending_admin_actions WHERE id = $1")
+            .bind(id)
+            .fetch_optional(db)
+            .await?
+            .ok_or_else(|| ApiError::NotFound(format!("Pending action {id} not found")))
+    }
+
+    /// Approve and execute a pending action. The approver must not be the
+    /// proposer; the caller checks the approver's permission.
+    pub async fn approve(
+        state: &AppState,
+        id: Uuid,
+        admin_id: Uuid,
+    ) -> Result<PendingAction, ApiError> {
+        let mut tx = state.db.begin().await?;
+        let pending = Self::lock_open(&mut tx, id).await?;
+        let Some(pending) = pending else {
+            // Expired while waiting; record that instead.
+            tx.commit().await?;
+            return Err(ApiError::Conflict(format!(
+                "Pending action {id} has expired"
+            )));
+        };
+        if pending.proposed_by == admin_id {
+            return Err(ApiError::Forbidden(
+                "A pending action must be approved by a different admin".to_string(),
+            ));
+        }
+        let action = HighRiskAction::from_parts(&pending.action_type, pending.payload.clone())?;
+        sqlx::query(
+            "UPDATE pending_admin_actions \
+             SET status = $2, decided_by = $3, decided_at = NOW() WHERE id = $1",
+        )
+        .bind(id)
+        .bind(action_status::APPROVED)
+        .bind(admin_id)
+        .execute(&mut *tx)
+        .await?;
+        AuditLogService::log(
+            &mut *tx,
+            None,
+            Some(admin_id),
+            audit_action::PENDING_ACTION_APPROVED,
+            Some(id),
+            Some(entity_type::PENDING_ACTION),
+            Some(action_status::PENDING),
+            Some(action_status::APPROVED),
+            Some(json!({ "action_type": pending.action_type, "proposed_by": pending.proposed_by })),
+        )
+        .await?;
+        tx.commit().await?;
+
+        // The services run their own transactions, so execution happens after
+        // the approval is committed; its outcome is recorded separately.
+        match action.execute(state, pending.proposed_by).await {
+            Ok(result) => {
+                let mut tx = state.db.begin().await?;
+                let executed = sqlx::query_as::<_, PendingAction>(
+                    "UPDATE pending_admin_actions \
+                     SET status = $2, result = $3, executed_at = NOW() \
+                     WHERE id = $1 RETURNING *",
+                )
+                .bind(id)
+                .bind(action_status::EXECUTED)
+                .bind(&result)
+                .fetch_one(&mut *tx)
+                .await?;
+                AuditLogService::log(
+                    &mut *tx,
+                    None,
+                    Some(admin_id),
+                    audit_action::PENDING_ACTION_EXECUTED,
+                    Some(id),
+                    Some(entity_type::PENDING_ACTION),
+                    Some(action_status::APPROVED),
+                    Some(action_status::EXECUTED),
+                    Some(json!({ "action_type": pending.action_type, "result": result })),
+                )
+                .await?;
+                tx.commit().await?;
+                Ok(executed)
+            }
+            Err(e) => {
+                let mut tx = state.db.begin().await?;
+                sqlx::query(
+                    "UPDATE pending_admin_actions SET status = $2, error = $3 WHERE id = $1",
+                )
+                .bind(id)
+                .bind(action_status::FAILED)
+                .bind(e.to_string())
+                .execute(&mut *tx)
+                .await?;
+                AuditLogService::log(
+                    &mut *tx,
+                    None,
+                    Some(admin_id),
+                    audit_action::PENDING_ACTION_FAILED,
+                    Some(id),
+                    Some(entity_type::PENDING_ACTION),
+                    Some(action_status::APPROVED),
+                    Some(action_status::FAILED),
+                    Some(json!({ "action_type": pending.action_type, "error": e.to_string() })),
+                )
+                .await?;
+                tx.commit().await?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Reject a pending action. Proposers may reject (withdraw) their own.
+    pub async fn reject(
+        db: &PgPool,
+        id: Uuid,
+        admin_id: Uuid,
+        reason: Option<String>,
+    ) -> Result<PendingAction, ApiError> {
+        let mut tx = db.begin().await?;
+        let Some(pending) = Self::lock_open(&mut tx, id).await? else {
+            tx.commit().await?;
+            return Err(ApiError::Conflict(format!(
+                "Pending action {id} has expired"
+            )));
+        };
+        let rejected = sqlx::query_as::<_, PendingAction>(
+            "UPDATE pending_admin_actions \
+             SET status = $2, decided_by = $3, decided_at = NOW(), decision_reason = $4 \
+             WHERE id = $1 RETURNING *",
+        )
+        .bind(id)
+        .bind(action_status::REJECTED)
+        .bind(admin_id)
+        .bind(&reason)
+        .fetch_one(&mut *tx)
+        .await?;
+        AuditLogService::log(
+            &mut *tx,
+            None,
+            Some(admin_id),
+            audit_action::PENDING_ACTION_REJECTED,
+            Some(id),
+            Some(entity_type::PENDING_ACTION),
+            Some(action_status::PENDING),
+            Some(action_status::REJECTED),
+            Some(json!({
+                "action_type": pending.action_type,
+                "proposed_by": pending.proposed_by,
+                "reason": reason,
+            })),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(rejected)
+    }
+
+    /// Expire every pending action past its deadline. Returns how many
+    /// expired.
+    pub async fn expire_due(db: &PgPool) -> Result<usize, ApiError> {
+        let mut tx = db.begin().await?;
+        let expired: Vec<(Uuid, String)> = sqlx::query_as(
+            "UPDATE pending_admin_actions SET status = $1 \
+             WHERE status = $2 AND expires_at <= NOW() \
+             RETURNING id, action_type",
+        )
+        .bind(action_status::EXPIRED)
+        .bind(action_status::PENDING)
+        .fetch_all(&mut *tx)
+        .await?;
+        for (id, action_type) in &expired {
+            Self::log_expired(&mut tx, *id, action_type).await?;
+        }
+        tx.commit().await?;
+        Ok(expired.len())
+    }
+
+    /// Lock a pending action that is still open for a decision. Returns
+    /// `None`, having marked it expired, if its deadline has passed.
+    async fn lock_open(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        id: Uuid,
+    ) -> Result<Option<PendingAction>, ApiError> {
+        let pending = sqlx::query_as::<_, PendingAction>(
+            "SELECT * FROM pending_admin_actions WHERE id = $1 FOR UPDATE",
+        )
+        .bind(id)
+        .fetch_optional(&mut **tx)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Pending action {id} not found")))?;
+        if pending.status != action_status::PENDING {
+            return Err(ApiError::Conflict(format!(
+                "Pending action {id} is already {}",
+                pending.status
+            )));
+        }
+        if pending.expires_at <= Utc::now() {
+            sqlx::query("UPDATE pending_admin_actions SET status = $2 WHERE id = $1")
+                .bind(id)
+                .bind(action_status::EXPIRED)
+                .execute(&mut **tx)
+                .await?;
+            Self::log_expired(tx, id, &pending.action_type).await?;
+            return Ok(None);
+        }
+        Ok(Some(pending))
+    }
+
+    async fn log_expired(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        id: Uuid,
+        action_type: &str,
+    ) -> Result<(), ApiError> {
+        AuditLogService::log(
+            &mut **tx,
+            None,
+            None,
+            audit_action::PENDING_ACTION_EXPIRED,
+            Some(id),
+            Some(entity_type::PENDING_ACTION),
+            Some(action_status::PENDING),
+            Some(action_status::EXPIRED),
+            Some(json!({ "action_type": action_type })),
+        )
+        .await
+    }
+}
+
+/// Background job that expires undecided pending actions.
+pub struct PendingActionExpiryJob {
+    db: PgPool,
+}
+
+impl PendingActionExpiryJob {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(300));
+            loop {
+                interval.tick().await;
+                match FourEyesService::expire_due(&self.db).await {
+                    Ok(count) if count > 0 => info!("Expired {} pending admin actions", count),
+                    Ok(_) => {}
+                    Err(e) => error!("Pending action expiry job error: {}", e),
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn actions_round_trip_through_their_stored_parts() {
+        let actions = [
+            HighRiskAction::PausePlan(PausePlanRequest {
+                plan_id: Uuid::new_v4(),
+                reason: "suspicious activity".to_string(),
+            }),
+            HighRiskAction::RotateMessageKey,
+            HighRiskAction::ApproveKyc {
+                user_id: Uuid::new_v4(),
+            },
+        ];
+        for action in actions {
+            let payload = action.payload().unwrap();
+            let restored = HighRiskAction::from_parts(action.action_type(), payload).unwrap();
+            assert_eq!(restored.action_type(), action.action_type());
+            assert!(ACTION_TYPES.contains(&action.action_type()));
+        }
+    }
+
+    #[test]
+    fn policy_lists_known_action_types() {
+        let all = FourEyesPolicy::parse("all", Duration::hours(1)).unwrap();
+        assert!(ACTION_TYPES.iter().all(|a| all.requires(a)));
+
+        let some =
+            FourEyesPolicy::parse(" approve_kyc, payout_insurance_claim ", Duration::hours(1))
+                .unwrap();
+        assert!(some.requires("approve_kyc"));
+        assert!(!some.requires("pause_plan"));
+
+        assert!(!FourEyesPolicy::parse("none", Duration::hours(1))
+            .unwrap()
+            .requires("approve_kyc"));
+        assert!(FourEyesPolicy::parse("approve_everything", Duration::hours(1)).is_err());
+    }
+}
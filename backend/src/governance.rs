@@ -1,11 +1,12 @@
 use crate::api_error::ApiError;
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use tracing::{info, warn};
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
 pub struct Proposal {
     pub id: Uuid,
     pub title: String,
@@ -18,19 +19,19 @@ pub struct Proposal {
     pub expires_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct CreateProposalRequest {
     pub title: String,
     pub description: String,
     pub duration_days: i64,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct VoteRequest {
     pub supports: bool,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct ParameterUpdateRequest {
     pub parameter_name: String,
     pub parameter_value: String,
@@ -0,0 +1,449 @@
+//! Idempotency keys for money-moving POST endpoints.
+//!
+//! Creating and claiming plans, repaying loans, moving collateral and paying
+//! out insurance claims must not happen twice because a client retried after
+//! a timeout. Clients send an `Idempotency-Key` header (any visible ASCII, at
+//! most 255 characters) with such requests. The first request with a key
+//! claims it for the calling user or admin and stores its response; a retry
+//! with the same key and the same method, path and body replays that response
+//! with `Idempotent-Replayed: true` instead of running the handler again.
+//!
+//! Reusing a key for a different request, or while the first request is still
+//! running, is a conflict. Responses that say nothing about the request
+//! itself, such as server errors, authentication and step-up rejections and
+//! rate limiting, release the key instead of being stored, so the client can
+//! fix the cause and retry with the same key. Keys expire after `IDEMPOTENCY_KEY_TTL_HOURS` (default 24).
+//! Requests without the header are not affected.
+
+use crate::api_error::ApiError;
+use crate::app::AppState;
+use crate::auth::{AuthenticatedAdmin, AuthenticatedUser};
+use axum::{
+    body::Body,
+    extract::{FromRequestParts, Request, State},
+    http::{header, request::Parts, HeaderName, HeaderValue, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, Duration, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::sync::Arc;
+use tracing::{error, info};
+
+pub static IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+pub static IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+/// How long a key is kept unless `IDEMPOTENCY_KEY_TTL_HOURS` says otherwise.
+pub const DEFAULT_TTL_HOURS: i64 = 24;
+
+pub const MAX_KEY_LEN: usize = 255;
+
+/// Largest request or response body buffered for fingerprinting and replay,
+/// matching axum's default request body limit.
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct IdempotencyPolicy {
+    ttl: Duration,
+}
+
+impl IdempotencyPolicy {
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl }
+    }
+
+    pub fn from_env() -> Result<Self, ApiError> {
+        let ttl_hours = match std::env::var("IDEMPOTENCY_KEY_TTL_HOURS") {
+            Ok(hours) => hours
+                .trim()
+                .parse::<i64>()
+                .ok()
+                .filter(|h| *h > 0)
+                .ok_or_else(|| {
+                    ApiError::Internal(anyhow::anyhow!(
+                        "IDEMPOTENCY_KEY_TTL_HOURS must be a positive number of hours"
+                    ))
+                })?,
+            Err(_) => DEFAULT_TTL_HOURS,
+        };
+        Ok(Self::new(Duration::hours(ttl_hours)))
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+}
+
+/// A response stored against a completed key.
+#[derive(Debug, Clone)]
+pub struct StoredResponse {
+    pub response_status: i16,
+    pub response_content_type: Option<String>,
+    pub response_body: Vec<u8>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct IdempotencyRecord {
+    request_fingerprint: String,
+    status: String,
+    response_status: Option<i16>,
+    response_content_type: Option<String>,
+    response_body: Option<Vec<u8>>,
+}
+
+/// The outcome of presenting an idempotency key.
+#[derive(Debug)]
+pub enum KeyClaim {
+    /// The key was free; the caller must run the request and then complete
+    /// or release the key.
+    Acquired,
+    /// The same request already completed under this key.
+    Replay(StoredResponse),
+}
+
+/// SHA-256 (hex) over a request's method, path and body.
+pub fn request_fingerprint(method: &str, path: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(path.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    hex::encode(hasher.finalize())
+}
+
+fn validate_key(key: &HeaderValue) -> Result<&str, ApiError> {
+    key.to_str()
+        .ok()
+        .filter(|k| !k.is_empty() && k.len() <= MAX_KEY_LEN)
+        .filter(|k| k.bytes().all(|b| b.is_ascii_graphic()))
+        .ok_or_else(|| {
+            ApiError::BadRequest(format!(
+                "Idempotency-Key must be 1 to {} visible ASCII characters",
+                MAX_KEY_LEN
+            ))
+        })
+}
+
+pub struct IdempotencyService;
+
+impl IdempotencyService {
+    /// Claim `key` for a request, or find out how an earlier request with the
+    /// same key went. Expired keys are claimed afresh.
+    pub async fn claim(
+        db: &PgPool,
+        principal: &str,
+        key: &str,
+        method: &str,
+        path: &str,
+        fingerprint: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<KeyClaim, ApiError> {
+        let acquired = sqlx::query(
+            r#"
+            INSERT INTO idempotency_keys (
+                principal, idempotency_key, request_method, request_path,
+                request_fingerprint, expires_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (principal, idempotency_key) DO UPDATE
+            SET request_method = EXCLUDED.request_method,
+                request_path = EXCLUDED.request_path,
+                request_fingerprint = EXCLUDED.request_fingerprint,
+                status = 'in_progress',
+                response_status = NULL,
+                response_content_type = NULL,
+                response_body = NULL,
+                created_at = NOW(),
+                completed_at = NULL,
+                expires_at = EXCLUDED.expires_at
+            WHERE idempotency_keys.expires_at <= NOW()
+            "#,
+        )
+        .bind(principal)
+        .bind(key)
+        .bind(method)
+        .bind(path)
+        .bind(fingerprint)
+        .bind(expires_at)
+        .execute(db)
+        .await?
+        .rows_affected()
+            == 1;
+        if acquired {
+            return Ok(KeyClaim::Acquired);
+        }
+
+        let record = sqlx::query_as::<_, IdempotencyRecord>(
+            r#"
+            SELECT request_fingerprint, status, response_status,
+                   response_content_type, response_body
+            FROM idempotency_keys
+            WHERE principal = $1 AND idempotency_key = $2
+            "#,
+        )
+        .bind(principal)
+        .bind(key)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| {
+            ApiError::Conflict("Idempotency-Key expired while in use; retry the request".into())
+        })?;
+
+        if record.request_fingerprint != fingerprint {
+            return Err(ApiError::Conflict(
+                "Idempotency-Key has already been used for a different request".into(),
+            ));
+        }
+        match (record.status.as_str(), record.response_status) {
+            ("completed", Some(response_status)) => Ok(KeyClaim::Replay(StoredResponse {
+                response_status,
+                response_content_type: record.response_content_type,
+                response_body: record.response_body.unwrap_or_default(),
+            })),
+            _ => Err(ApiError::Conflict(
+                "A request with this Idempotency-Key is still in progress".into(),
+            )),
+        }
+    }
+
+    /// Store the response of the request that claimed `key`.
+    pub async fn complete(
+        db: &PgPool,
+        principal: &str,
+        key: &str,
+        response: &StoredResponse,
+    ) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            UPDATE idempotency_keys
+            SET status = 'completed', response_status = $3, response_content_type = $4,
+                response_body = $5, completed_at = NOW()
+            WHERE principal = $1 AND idempotency_key = $2
+            "#,
+        )
+        .bind(principal)
+        .bind(key)
+        .bind(response.response_status)
+        .bind(&response.response_content_type)
+        .bind(&response.response_body)
+        .execute(db)
+        .await?;
+        Ok(())
+    }
+
+    /// Give up a claimed key so the request can be retried with it.
+    pub async fn release(db: &PgPool, principal: &str, key: &str) -> Result<(), ApiError> {
+        sqlx::query(
+            "DELETE FROM idempotency_keys \
+             WHERE principal = $1 AND idempotency_key = $2 AND status = 'in_progress'",
+        )
+        .bind(principal)
+        .bind(key)
+        .execute(db)
+        .await?;
+        Ok(())
+    }
+
+    /// Delete expired keys. Returns how many were deleted.
+    pub async fn purge_expired(db: &PgPool) -> Result<u64, ApiError> {
+        let result = sqlx::query("DELETE FROM idempotency_keys WHERE expires_at <= NOW()")
+            .execute(db)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+/// Who a key belongs to: keys are never shared between callers.
+async fn principal(parts: &mut Parts) -> Option<String> {
+    if let Ok(AuthenticatedUser(claims)) = AuthenticatedUser::from_request_parts(parts, &()).await {
+        return Some(format!("user:{}", claims.user_id));
+    }
+    if let Ok(AuthenticatedAdmin(claims)) = AuthenticatedAdmin::from_request_parts(parts, &()).await
+    {
+        return Some(format!("admin:{}", claims.admin_id));
+    }
+    None
+}
+
+fn replay(stored: StoredResponse) -> Response {
+    let mut response = Response::new(Body::from(stored.response_body));
+    *response.status_mut() =
+        StatusCode::from_u16(stored.response_status as u16).unwrap_or(StatusCode::OK);
+    if let Some(content_type) = stored
+        .response_content_type
+        .and_then(|ct| HeaderValue::from_str(&ct).ok())
+    {
+        response
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, content_type);
+    }
+    response.headers_mut().insert(
+        IDEMPOTENT_REPLAYED.clone(),
+        HeaderValue::from_static("true"),
+    );
+    response
+}
+
+/// Whether a response leaves the key free for a retry rather than being
+/// stored for replay.
+fn releases_key(status: StatusCode) -> bool {
+    status.is_server_error()
+        || matches!(
+            status,
+            StatusCode::UNAUTHORIZED
+                | StatusCode::FORBIDDEN
+                | StatusCode::CONFLICT
+                | StatusCode::TOO_MANY_REQUESTS
+        )
+}
+
+/// Honours `Idempotency-Key` on the routes it is layered onto. Requests
+/// without the header, or without valid credentials, pass straight through.
+pub async fn idempotency_middleware(
+    State(state): State<Arc<AppState>>,
+    req: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let Some(key) = req.headers().get(&IDEMPOTENCY_KEY) else {
+        return Ok(next.run(req).await);
+    };
+    let key = validate_key(key)?.to_string();
+
+    let (mut parts, body) = req.into_parts();
+    let Some(principal) = principal(&mut parts).await else {
+        return Ok(next.run(Request::from_parts(parts, body)).await);
+    };
+    let body = axum::body::to_bytes(body, MAX_BODY_BYTES)
+        .await
+        .map_err(|_| ApiError::BadRequest("Request body is too large".into()))?;
+
+    let method = parts.method.as_str().to_string();
+    let path = parts.uri.path().to_string();
+    let fingerprint = request_fingerprint(&method, &path, &body);
+    let expires_at = Utc::now() + state.idempotency.ttl();
+    match IdempotencyService::claim(
+        &state.db,
+        &principal,
+        &key,
+        &method,
+        &path,
+        &fingerprint,
+        expires_at,
+    )
+    .await?
+    {
+        KeyClaim::Replay(stored) => return Ok(replay(stored)),
+        KeyClaim::Acquired => {}
+    }
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    if releases_key(response.status()) {
+        IdempotencyService::release(&state.db, &principal, &key).await?;
+        return Ok(response);
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match axum::body::to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(body) => body,
+        Err(e) => {
+            IdempotencyService::release(&state.db, &principal, &key).await?;
+            return Err(ApiError::Internal(anyhow::anyhow!(
+                "Failed to buffer response for Idempotency-Key: {}",
+                e
+            )));
+        }
+    };
+    let stored = StoredResponse {
+        response_status: parts.status.as_u16() as i16,
+        response_content_type: parts
+            .headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|ct| ct.to_str().ok())
+            .map(str::to_string),
+        response_body: body.to_vec(),
+    };
+    IdempotencyService::complete(&state.db, &principal, &key, &stored).await?;
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+/// Background job that deletes expired idempotency keys.
+pub struct IdempotencyKeyExpiryJob {
+    db: PgPool,
+}
+
+impl IdempotencyKeyExpiryJob {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+            loop {
+                interval.tick().await;
+                match IdempotencyService::purge_expired(&self.db).await {
+                    Ok(count) if count > 0 => info!("Purged {} expired idempotency keys", count),
+                    Ok(_) => {}
+                    Err(e) => error!("Idempotency key expiry job error: {}", e),
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprints_cover_method_path_and_body() {
+        let base = request_fingerprint("POST", "/api/plans", br#"{"fee":"10.00"}"#);
+        assert_eq!(
+            base,
+            request_fingerprint("POST", "/api/plans", br#"{"fee":"10.00"}"#)
+        );
+        assert_ne!(
+            base,
+            request_fingerprint("POST", "/api/plans", br#"{"fee":"11.00"}"#)
+        );
+        assert_ne!(
+            base,
+            request_fingerprint("POST", "/api/plans/x", br#"{"fee":"10.00"}"#)
+        );
+        assert_ne!(
+            base,
+            request_fingerprint("PUT", "/api/plans", br#"{"fee":"10.00"}"#)
+        );
+    }
+
+    #[test]
+    fn keys_must_be_short_visible_ascii() {
+        assert!(validate_key(&HeaderValue::from_static("retry-1f3a")).is_ok());
+        assert!(validate_key(&HeaderValue::from_static("")).is_err());
+        assert!(validate_key(&HeaderValue::from_static("has space")).is_err());
+        let long = "k".repeat(MAX_KEY_LEN + 1);
+        assert!(validate_key(&HeaderValue::from_str(&long).unwrap()).is_err());
+    }
+
+    #[test]
+    fn rejections_release_the_key_and_outcomes_are_stored() {
+        for status in [
+            StatusCode::UNAUTHORIZED,
+            StatusCode::FORBIDDEN,
+            StatusCode::CONFLICT,
+            StatusCode::TOO_MANY_REQUESTS,
+            StatusCode::SERVICE_UNAVAILABLE,
+        ] {
+            assert!(releases_key(status), "{status}");
+        }
+        for status in [
+            StatusCode::OK,
+            StatusCode::BAD_REQUEST,
+            StatusCode::NOT_FOUND,
+        ] {
+            assert!(!releases_key(status), "{status}");
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use crate::api_error::ApiError;
+use crate::contract_relayer::ContractRelayer;
+use crate::notifications::{audit_action, entity_type, AuditLogService};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgConnection, PgPool};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// What triggered a plan's inheritance.
+pub mod source {
+    pub const EMERGENCY_ACCESS: &str = "emergency_access";
+    pub const DEATH_VERIFICATION: &str = "death_verification";
+}
+
+/// Submissions to the contract give up after this many failed attempts.
+const MAX_SUBMIT_ATTEMPTS: i32 = 5;
+
+const SUBMIT_BATCH_SIZE: i64 = 50;
+
+const TRIGGER_COLUMNS: &str = "plan_id, source, triggered_by_user_id, grant_id, case_id, reason, \
+     triggered_at, chain_status, chain_tx_hash, chain_attempts, chain_error, chain_submitted_at";
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
+pub struct InheritanceTrigger {
+    pub plan_id: Uuid,
+    pub source: String,
+    pub triggered_by_user_id: Option<Uuid>,
+    pub grant_id: Option<Uuid>,
+    pub case_id: Option<Uuid>,
+    pub reason: Option<String>,
+    pub triggered_at: DateTime<Utc>,
+    /// `pending`, `submitted`, `failed`, or `not_deployed` for plans that
+    /// have no contract plan.
+    pub chain_status: String,
+    pub chain_tx_hash: Option<String>,
+    pub chain_attempts: i32,
+    pub chain_error: Option<String>,
+    pub chain_submitted_at: Option<DateTime<Utc>>,
+}
+
+/// Records that a plan's inheritance has been triggered. A plan is triggered
+/// at most once; the contract's `trigger_inheritance` is then submitted
+/// on-chain against the same plan by [`InheritanceTriggerSubmitter`].
+pub struct InheritanceTriggerService;
+
+impl InheritanceTriggerService {
+    pub async fn trigger(
+        conn: &mut PgConnection,
+        plan_id: Uuid,
+        source: &str,
+        triggered_by_user_id: Option<Uuid>,
+        grant_id: Option<Uuid>,
+        case_id: Option<Uuid>,
+        reason: Option<&str>,
+    ) -> Result<InheritanceTrigger, ApiError> {
+        let trigger = sqlx::query_as::<_, InheritanceTrigger>(&format!(
+            "INSERT INTO inheritance_triggers \
+                 (plan_id, source, triggered_by_user_id, grant_id, case_id, reason, chain_status) \
+             SELECT p.id, $2, $3, $4, $5, $6, \
+                    CASE WHEN p.contract_plan_id IS NULL THEN 'not_deployed' ELSE 'pending' END \
+             FROM plans p WHERE p.id = $1 \
+             ON CONFLICT (plan_id) DO NOTHING \
+             RETURNING {TRIGGER_COLUMNS}"
+        ))
+        .bind(plan_id)
+        .bind(source)
+        .bind(triggered_by_user_id)
+        .bind(grant_id)
+        .bind(case_id)
+        .bind(reason)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| {
+            ApiError::Conflict("Inheritance has already been triggered for this plan".to_string())
+        })?;
+
+        AuditLogService::log(
+            &mut *conn,
+            triggered_by_user_id,
+            None,
+            audit_action::INHERITANCE_TRIGGERED,
+            Some(plan_id),
+            Some(entity_type::PLAN),
+            None,
+            None,
+            Some(serde_json::json!({
+                "source": source,
+                "grant_id": grant_id,
+                "case_id": case_id,
+                "chain_status": trigger.chain_status,
+            })),
+        )
+        .await?;
+
+        Ok(trigger)
+    }
+
+    pub async fn get(
+        db: impl sqlx::PgExecutor<'_>,
+        plan_id: Uuid,
+    ) -> Result<Option<InheritanceTrigger>, ApiError> {
+        let trigger = sqlx::query_as::<_, InheritanceTrigger>(&format!(
+            "SELECT {TRIGGER_COLUMNS} FROM inheritance_triggers WHERE plan_id = $1"
+        ))
+        .bind(plan_id)
+        .fetch_optional(db)
+        .await?;
+        Ok(trigger)
+    }
+}
+
+/// Background job that submits pending triggers to the inheritance contract
+/// through the contract relayer.
+pub struct InheritanceTriggerSubmitter {
+    db: PgPool,
+    relayer: Arc<ContractRelayer>,
+}
+
+impl InheritanceTriggerSubmitter {
+    pub fn new(db: PgPool, relayer: Arc<ContractRelayer>) -> Self {
+        Self { db, relayer }
+    }
+
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.submit_pending().await {
+                    error!("Inheritance trigger submission error: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Submit one batch of pending triggers. Returns how many were submitted.
+    pub async fn submit_pending(&self) -> Result<usize, ApiError> {
+        let rows: Vec<(Uuid, i64)> = sqlx::query_as(
+            "SELECT t.plan_id, p.contract_plan_id FROM inheritance_triggers t \
+             JOIN plans p ON p.id = t.plan_id \
+             WHERE t.chain_status = 'pending' AND p.contract_plan_id IS NOT NULL \
+             ORDER BY t.triggered_at ASC LIMIT $1",
+        )
+        .bind(SUBMIT_BATCH_SIZE)
+        .fetch_all(&self.db)
+        .await?;
+
+        let mut submitted = 0;
+        for (plan_id, contract_plan_id) in rows {
+            let result = self
+                .relayer
+                .invoke(
+                    "trigger_inheritance",
+                    serde_json::json!({ "plan_id": contract_plan_id }),
+                )
+                .await;
+            match result {
+                Ok(tx_hash) => {
+                    sqlx::query(
+                        "UPDATE inheritance_triggers \
+                         SET chain_status = 'submitted', chain_tx_hash = $2, \
+                             chain_attempts = chain_attempts + 1, chain_error = NULL, \
+                             chain_submitted_at = NOW() \
+                         WHERE plan_id = $1",
+                    )
+                    .bind(plan_id)
+                    .bind(&tx_hash)
+                    .execute(&self.db)
+                    .await?;
+                    info!(
+                        "Submitted inheritance trigger for plan {}: {}",
+                        plan_id, tx_hash
+                    );
+                    submitted += 1;
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to submit inheritance trigger for plan {}: {}",
+                        plan_id, e
+                    );
+                    sqlx::query(
+                        "UPDATE inheritance_triggers \
+                         SET chain_attempts = chain_attempts + 1, chain_error = $2, \
+                             chain_status = CASE WHEN chain_attempts + 1 >= $3 \
+                                                 THEN 'failed' ELSE chain_status END \
+                         WHERE plan_id = $1",
+                    )
+                    .bind(plan_id)
+                    .bind(e.to_string())
+                    .bind(MAX_SUBMIT_ATTEMPTS)
+                    .execute(&self.db)
+                    .await?;
+                }
+            }
+        }
+        Ok(submitted)
+    }
+}
@@ -4,6 +4,7 @@ use crate::notifications::{
 };
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use std::str::FromStr;
@@ -131,7 +132,7 @@ pub struct InsuranceClaim {
 }
 
 /// Request to create insurance claim
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct CreateInsuranceClaimRequest {
     pub claim_type: String,
     pub claimed_amount: Decimal,
@@ -142,7 +143,7 @@ pub struct CreateInsuranceClaimRequest {
 }
 
 /// Request to process insurance claim
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct ProcessInsuranceClaimRequest {
     pub approved: bool,
     pub approved_amount: Option<Decimal>,
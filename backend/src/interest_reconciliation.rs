@@ -1,4 +1,5 @@
 use crate::api_error::ApiError;
+use crate::metrics::observe_job;
 use crate::notifications::AuditLogService;
 use crate::yield_service::OnChainYieldService;
 use rust_decimal::Decimal;
@@ -31,10 +32,17 @@ impl InterestReconciliationService {
             let mut interval = tokio::time::interval(Duration::from_secs(60));
             loop {
                 interval.tick().await;
-                if let Err(e) = self.reconcile_yields().await {
+                if let Err(e) =
+                    observe_job("interest_reconciliation_yields", self.reconcile_yields()).await
+                {
                     error!("Interest Reconciliation Engine error (yields): {}", e);
                 }
-                if let Err(e) = self.reconcile_vault_balances().await {
+                if let Err(e) = observe_job(
+                    "interest_reconciliation_vaults",
+                    self.reconcile_vault_balances(),
+                )
+                .await
+                {
                     error!("Interest Reconciliation Engine error (vaults): {}", e);
                 }
             }
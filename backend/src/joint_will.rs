@@ -0,0 +1,516 @@
+//! # Joint and Mirror Wills
+//!
+//! A will document can have several testators, e.g. a couple making joint,
+//! mutual or mirror wills. Each testator is a party to the document and
+//! signs through their own challenge in [`crate::will_signature`].
+//!
+//! Multi-party documents follow a state machine:
+//! `draft` → `signing` → `awaiting_witnesses` → `signed` → `finalized`.
+//! A document reaches `signed` only once every party and the required number
+//! of witnesses have signed, and only then can it be finalized.
+//!
+//! Revocation depends on the will type: joint and mutual wills are revoked
+//! as a whole and every signature is invalidated, while a mirror will only
+//! loses the revoking party's signature (`partially_revoked`).
+
+use crate::api_error::ApiError;
+use crate::will_events::{WillEvent, WillEventService};
+use crate::will_signature::WillSignatureService;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Most co-testators a document can have, besides the owner.
+const MAX_CO_TESTATORS: usize = 4;
+
+// --- Types -------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WillType {
+    Single,
+    /// One instrument for all testators.
+    Joint,
+    /// Reciprocal wills the testators agree not to revoke unilaterally.
+    Mutual,
+    /// Separate, mirrored provisions for each testator.
+    Mirror,
+}
+
+impl WillType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WillType::Single => "single",
+            WillType::Joint => "joint",
+            WillType::Mutual => "mutual",
+            WillType::Mirror => "mirror",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, ApiError> {
+        match value {
+            "single" => Ok(WillType::Single),
+            "joint" => Ok(WillType::Joint),
+            "mutual" => Ok(WillType::Mutual),
+            "mirror" => Ok(WillType::Mirror),
+            other => Err(ApiError::Internal(anyhow::anyhow!(
+                "Unknown will type {other}"
+            ))),
+        }
+    }
+
+    /// Whether one party's revocation invalidates every party's signature.
+    pub fn revokes_all_parties(self) -> bool {
+        !matches!(self, WillType::Mirror)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct CoTestator {
+    pub user_id: Uuid,
+    pub wallet_address: String,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct ConfigureTestatorsRequest {
+    pub will_type: WillType,
+    /// The owner's signing wallet.
+    pub wallet_address: String,
+    pub co_testators: Vec<CoTestator>,
+    pub required_witnesses: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct RevokeWillRequest {
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
+pub struct WillParty {
+    pub id: Uuid,
+    pub document_id: Uuid,
+    pub user_id: Uuid,
+    pub wallet_address: String,
+    pub status: String,
+    pub signed_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct JointWillStatus {
+    pub document_id: Uuid,
+    pub will_type: WillType,
+    pub status: String,
+    pub required_witnesses: i32,
+    pub signed_witnesses: i64,
+    pub parties: Vec<WillParty>,
+}
+
+#[derive(sqlx::FromRow)]
+struct DocumentRow {
+    user_id: Uuid,
+    plan_id: Uuid,
+    status: String,
+    will_type: String,
+    required_witnesses: i32,
+}
+
+/// Next document status from the signing progress, or `None` when the
+/// document has left the signing stages.
+fn next_status(
+    current: &str,
+    signed_parties: usize,
+    total_parties: usize,
+    signed_witnesses: i64,
+    required_witnesses: i32,
+) -> Option<&'static str> {
+    if !matches!(current, "draft" | "signing" | "awaiting_witnesses") {
+        return None;
+    }
+    Some(if signed_parties < total_parties {
+        if signed_parties == 0 {
+            "draft"
+        } else {
+            "signing"
+        }
+    } else if signed_witnesses < i64::from(required_witnesses) {
+        "awaiting_witnesses"
+    } else {
+        "signed"
+    })
+}
+
+const PARTY_COLUMNS: &str =
+    "id, document_id, user_id, wallet_address, status, signed_at, revoked_at";
+
+// --- Service -----------------------------------------------------------------
+
+pub struct JointWillService;
+
+impl JointWillService {
+    async fn document(db: &PgPool, document_id: Uuid) -> Result<DocumentRow, ApiError> {
+        sqlx::query_as::<_, DocumentRow>(
+            "SELECT user_id, plan_id, status, will_type, required_witnesses \
+             FROM will_documents WHERE id = $1",
+        )
+        .bind(document_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Will document {document_id} not found")))
+    }
+
+    async fn parties(db: &PgPool, document_id: Uuid) -> Result<Vec<WillParty>, ApiError> {
+        let parties = sqlx::query_as::<_, WillParty>(&format!(
+            "SELECT {PARTY_COLUMNS} FROM will_document_parties \
+             WHERE document_id = $1 ORDER BY created_at, id"
+        ))
+        .bind(document_id)
+        .fetch_all(db)
+        .await?;
+        Ok(parties)
+    }
+
+    /// Turn the owner's draft document into a multi-party will.
+    pub async fn configure(
+        db: &PgPool,
+        owner_user_id: Uuid,
+        document_id: Uuid,
+        req: &ConfigureTestatorsRequest,
+    ) -> Result<JointWillStatus, ApiError> {
+        let document = Self::document(db, document_id).await?;
+        if document.user_id != owner_user_id {
+            return Err(ApiError::NotFound(format!(
+                "Will document {document_id} not found"
+            )));
+        }
+        if req.will_type == WillType::Single {
+            return Err(ApiError::BadRequest(
+                "will_type must be joint, mutual or mirror".to_string(),
+            ));
+        }
+        if req.co_testators.is_empty() || req.co_testators.len() > MAX_CO_TESTATORS {
+            return Err(ApiError::BadRequest(format!(
+                "Between 1 and {MAX_CO_TESTATORS} co-testators are required"
+            )));
+        }
+        if document.status != "draft" || document.will_type != "single" {
+            return Err(ApiError::Conflict(
+                "Testators can only be set on a draft single-testator document".to_string(),
+            ));
+        }
+        let signed: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM will_signatures WHERE document_id = $1)",
+        )
+        .bind(document_id)
+        .fetch_one(db)
+        .await?;
+        if signed {
+            return Err(ApiError::Conflict(
+                "Document has already been signed".to_string(),
+            ));
+        }
+
+        let mut testators = vec![(owner_user_id, req.wallet_address.trim())];
+        testators.extend(
+            req.co_testators
+                .iter()
+                .map(|t| (t.user_id, t.wallet_address.trim())),
+        );
+        let mut users = HashSet::new();
+        let mut wallets = HashSet::new();
+        for (user_id, wallet) in &testators {
+            WillSignatureService::decode_public_key(wallet)?;
+            if !users.insert(*user_id) || !wallets.insert(wallet.to_lowercase()) {
+                return Err(ApiError::BadRequest(
+                    "Each testator needs a distinct user and wallet".to_string(),
+                ));
+            }
+        }
+        let user_ids: Vec<Uuid> = users.into_iter().collect();
+        let known: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE id = ANY($1)")
+            .bind(&user_ids)
+            .fetch_one(db)
+            .await?;
+        if known != user_ids.len() as i64 {
+            return Err(ApiError::NotFound("Co-testator not found".to_string()));
+        }
+
+        let mut tx = db.begin().await?;
+        let updated = sqlx::query(
+            "UPDATE will_documents SET will_type = $2, required_witnesses = $3 \
+             WHERE id = $1 AND will_type = 'single' AND status = 'draft'",
+        )
+        .bind(document_id)
+        .bind(req.will_type.as_str())
+        .bind(req.required_witnesses as i32)
+        .execute(&mut *tx)
+        .await?;
+        if updated.rows_affected() == 0 {
+            return Err(ApiError::Conflict(
+                "Testators can only be set on a draft single-testator document".to_string(),
+            ));
+        }
+        for (user_id, wallet) in &testators {
+            sqlx::query(
+                "INSERT INTO will_document_parties (document_id, user_id, wallet_address) \
+                 VALUES ($1, $2, $3)",
+            )
+            .bind(document_id)
+            .bind(user_id)
+            .bind(wallet)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Self::status(db, owner_user_id, document_id).await
+    }
+
+    /// Signing progress of a multi-party document, for any of its testators.
+    pub async fn status(
+        db: &PgPool,
+        user_id: Uuid,
+        document_id: Uuid,
+    ) -> Result<JointWillStatus, ApiError> {
+        let document = Self::document(db, document_id).await?;
+        let parties = Self::parties(db, document_id).await?;
+        if document.user_id != user_id && !parties.iter().any(|p| p.user_id == user_id) {
+            return Err(ApiError::NotFound(format!(
+                "Will document {document_id} not found"
+            )));
+        }
+        let signed_witnesses: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM will_witnesses WHERE document_id = $1 AND status = 'signed'",
+        )
+        .bind(document_id)
+        .fetch_one(db)
+        .await?;
+
+        Ok(JointWillStatus {
+            document_id,
+            will_type: WillType::parse(&document.will_type)?,
+            status: document.status,
+            required_witnesses: document.required_witnesses,
+            signed_witnesses,
+            parties,
+        })
+    }
+
+    /// The party a signing challenge is issued for. `None` for single-testator
+    /// documents, which keep the original owner-only flow.
+    pub async fn party_for_signing(
+        db: &PgPool,
+        document_id: Uuid,
+        user_id: Uuid,
+        wallet_address: &str,
+    ) -> Result<Option<Uuid>, ApiError> {
+        let document = Self::document(db, document_id).await?;
+        if document.will_type == "single" {
+            return Ok(None);
+        }
+        let party = Self::parties(db, document_id)
+            .await?
+            .into_iter()
+            .find(|p| p.user_id == user_id)
+            .ok_or_else(|| {
+                ApiError::Forbidden("User is not a testator of this will".to_string())
+            })?;
+        if party.status != "pending" {
+            return Err(ApiError::Conflict(format!(
+                "Testator has already {}",
+                party.status
+            )));
+        }
+        if party.wallet_address.to_lowercase() != wallet_address.to_lowercase() {
+            return Err(ApiError::Forbidden(
+                "Wallet does not match the testator's registered wallet".to_string(),
+            ));
+        }
+        Ok(Some(party.id))
+    }
+
+    /// Move a multi-party document along as parties and witnesses sign.
+    pub async fn advance(db: &PgPool, document_id: Uuid) -> Result<(), ApiError> {
+        let document = Self::document(db, document_id).await?;
+        if document.will_type == "single" {
+            return Ok(());
+        }
+        let parties = Self::parties(db, document_id).await?;
+        let signed_parties = parties.iter().filter(|p| p.status == "signed").count();
+        let signed_witnesses: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM will_witnesses WHERE document_id = $1 AND status = 'signed'",
+        )
+        .bind(document_id)
+        .fetch_one(db)
+        .await?;
+
+        if let Some(status) = next_status(
+            &document.status,
+            signed_parties,
+            parties.len(),
+            signed_witnesses,
+            document.required_witnesses,
+        ) {
+            sqlx::query(
+                "UPDATE will_documents SET status = $2 \
+                 WHERE id = $1 AND status IN ('draft', 'signing', 'awaiting_witnesses')",
+            )
+            .bind(document_id)
+            .bind(status)
+            .execute(db)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Refuse to finalize a multi-party document before everyone has signed.
+    pub async fn ensure_ready_to_finalize(db: &PgPool, document_id: Uuid) -> Result<(), ApiError> {
+        let document = Self::document(db, document_id).await?;
+        if document.will_type == "single" || document.status == "signed" {
+            return Ok(());
+        }
+        Err(ApiError::BadRequest(format!(
+            "A {} will can only be finalized once every testator and {} witness(es) have signed \
+             (currently {})",
+            document.will_type, document.required_witnesses, document.status
+        )))
+    }
+
+    /// Revoke the caller's participation in a multi-party will.
+    pub async fn revoke(
+        db: &PgPool,
+        user_id: Uuid,
+        document_id: Uuid,
+        req: &RevokeWillRequest,
+    ) -> Result<JointWillStatus, ApiError> {
+        let document = Self::document(db, document_id).await?;
+        let will_type = WillType::parse(&document.will_type)?;
+        let party = Self::parties(db, document_id)
+            .await?
+            .into_iter()
+            .find(|p| p.user_id == user_id)
+            .ok_or_else(|| ApiError::NotFound(format!("Will document {document_id} not found")))?;
+        if document.status == "revoked" || party.status == "revoked" {
+            return Err(ApiError::Conflict(
+                "Will has already been revoked".to_string(),
+            ));
+        }
+
+        let mut tx = db.begin().await?;
+        sqlx::query("SELECT id FROM will_documents WHERE id = $1 FOR UPDATE")
+            .bind(document_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let invalidated = if will_type.revokes_all_parties() {
+            let invalidated = sqlx::query(
+                "UPDATE will_signatures SET invalidated_at = NOW() \
+                 WHERE document_id = $1 AND invalidated_at IS NULL",
+            )
+            .bind(document_id)
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query(
+                "UPDATE will_document_parties SET status = 'invalidated' \
+                 WHERE document_id = $1 AND id <> $2 AND status <> 'revoked'",
+            )
+            .bind(document_id)
+            .bind(party.id)
+            .execute(&mut *tx)
+            .await?;
+            invalidated
+        } else {
+            sqlx::query(
+                "UPDATE will_signatures SET invalidated_at = NOW() \
+                 WHERE party_id = $1 AND invalidated_at IS NULL",
+            )
+            .bind(party.id)
+            .execute(&mut *tx)
+            .await?
+        };
+
+        sqlx::query(
+            "UPDATE will_document_parties SET status = 'revoked', revoked_at = NOW() WHERE id = $1",
+        )
+        .bind(party.id)
+        .execute(&mut *tx)
+        .await?;
+
+        let remaining: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM will_document_parties \
+             WHERE document_id = $1 AND status NOT IN ('revoked', 'invalidated')",
+        )
+        .bind(document_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        let status = if remaining == 0 {
+            "revoked"
+        } else {
+            "partially_revoked"
+        };
+        sqlx::query("UPDATE will_documents SET status = $2 WHERE id = $1")
+            .bind(document_id)
+            .bind(status)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        let vault_id: String =
+            sqlx::query_scalar("SELECT COALESCE(title, id::text) FROM plans WHERE id = $1")
+                .bind(document.plan_id)
+                .fetch_one(db)
+                .await?;
+        let event = WillEvent::WillRevoked {
+            vault_id,
+            document_id,
+            plan_id: document.plan_id,
+            revoked_by: user_id,
+            will_type: will_type.as_str().to_string(),
+            invalidated_signatures: invalidated.rows_affected() as u32,
+            reason: req.reason.clone(),
+            timestamp: Utc::now(),
+        };
+        if let Err(e) = WillEventService::emit(db, event).await {
+            tracing::warn!("Failed to emit WillRevoked event: {}", e);
+        }
+
+        Self::status(db, user_id, document_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_waits_for_every_party_then_witnesses() {
+        assert_eq!(next_status("draft", 0, 2, 0, 1), Some("draft"));
+        assert_eq!(next_status("draft", 1, 2, 0, 1), Some("signing"));
+        assert_eq!(
+            next_status("signing", 2, 2, 0, 1),
+            Some("awaiting_witnesses")
+        );
+        assert_eq!(
+            next_status("awaiting_witnesses", 2, 2, 1, 1),
+            Some("signed")
+        );
+        assert_eq!(next_status("draft", 2, 2, 0, 0), Some("signed"));
+    }
+
+    #[test]
+    fn test_status_is_frozen_outside_signing_stages() {
+        assert_eq!(next_status("signed", 2, 2, 5, 1), None);
+        assert_eq!(next_status("finalized", 2, 2, 1, 1), None);
+        assert_eq!(next_status("partially_revoked", 1, 2, 1, 1), None);
+    }
+
+    #[test]
+    fn test_only_mirror_wills_revoke_a_single_party() {
+        assert!(WillType::Joint.revokes_all_parties());
+        assert!(WillType::Mutual.revokes_all_parties());
+        assert!(!WillType::Mirror.revokes_all_parties());
+    }
+}
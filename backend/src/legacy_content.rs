@@ -1,11 +1,11 @@
 use crate::api_error::ApiError;
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
-use std::path::PathBuf;
 use uuid::Uuid;
 
-const MAX_FILE_SIZE: usize = 524_288_000; // 500MB
+pub const MAX_FILE_SIZE: usize = 524_288_000; // 500MB
 const ALLOWED_VIDEO_TYPES: &[&str] = &[
     "video/mp4",
     "video/mpeg",
@@ -21,6 +21,8 @@ const ALLOWED_AUDIO_TYPES: &[&str] = &[
     "audio/webm",
 ];
 const ALLOWED_TEXT_TYPES: &[&str] = &["text/plain", "text/markdown", "text/html"];
+// Images have EXIF/GPS metadata stripped during content inspection.
+const ALLOWED_IMAGE_TYPES: &[&str] = &["image/jpeg", "image/png"];
 const ALLOWED_DOCUMENT_TYPES: &[&str] = &[
     "application/pdf",
     "application/msword",
@@ -29,7 +31,7 @@ const ALLOWED_DOCUMENT_TYPES: &[&str] = &[
     "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
 ];
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
 pub struct LegacyContent {
     pub id: Uuid,
     pub owner_user_id: Uuid,
@@ -43,6 +45,10 @@ pub struct LegacyContent {
     pub encryption_key_version: Option<i32>,
     pub status: String,
     pub metadata: Option<serde_json::Value>,
+    pub inspection_status: String,
+    pub inspection_details: Option<serde_json::Value>,
+    pub thumbnail_key: Option<String>,
+    pub inspected_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -55,7 +61,7 @@ pub struct UploadMetadata {
     pub description: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ContentListFilters {
     pub content_type_prefix: Option<String>,
     pub limit: Option<i64>,
@@ -72,12 +78,13 @@ impl LegacyContentService {
             .chain(ALLOWED_AUDIO_TYPES.iter())
             .chain(ALLOWED_TEXT_TYPES.iter())
             .chain(ALLOWED_DOCUMENT_TYPES.iter())
+            .chain(ALLOWED_IMAGE_TYPES.iter())
             .copied()
             .collect();
 
         if !allowed_types.contains(&content_type) {
             return Err(ApiError::BadRequest(format!(
-                "Unsupported content type: {}. Allowed types: video, audio, text, documents",
+                "Unsupported content type: {}. Allowed types: video, audio, text, documents, images",
                 content_type
             )));
         }
@@ -140,6 +147,7 @@ impl LegacyContentService {
                 id, owner_user_id, filename, original_filename, content_type,
                 file_size, storage_path, file_hash, encrypted,
                 encryption_key_version, status, metadata,
+                inspection_status, inspection_details, thumbnail_key, inspected_at,
                 created_at, updated_at
             "#,
         )
@@ -173,6 +181,7 @@ impl LegacyContentService {
                     id, owner_user_id, filename, original_filename, content_type,
                     file_size, storage_path, file_hash, encrypted,
                     encryption_key_version, status, metadata,
+                    inspection_status, inspection_details, thumbnail_key, inspected_at,
                     created_at, updated_at
                 FROM legacy_content
                 WHERE owner_user_id = $1
@@ -195,6 +204,7 @@ impl LegacyContentService {
                     id, owner_user_id, filename, original_filename, content_type,
                     file_size, storage_path, file_hash, encrypted,
                     encryption_key_version, status, metadata,
+                    inspection_status, inspection_details, thumbnail_key, inspected_at,
                     created_at, updated_at
                 FROM legacy_content
                 WHERE owner_user_id = $1 AND status = 'active'
@@ -224,6 +234,7 @@ impl LegacyContentService {
                 id, owner_user_id, filename, original_filename, content_type,
                 file_size, storage_path, file_hash, encrypted,
                 encryption_key_version, status, metadata,
+                inspection_status, inspection_details, thumbnail_key, inspected_at,
                 created_at, updated_at
             FROM legacy_content
             WHERE id = $1 AND owner_user_id = $2 AND status = 'active'
@@ -289,7 +300,7 @@ impl LegacyContentService {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
 pub struct StorageStats {
     pub total_files: i64,
     pub total_size: i64,
@@ -299,54 +310,6 @@ pub struct StorageStats {
     pub document_count: i64,
 }
 
-/// File storage handler (filesystem-based)
-pub struct FileStorageService {
-    base_path: PathBuf,
-}
-
-impl FileStorageService {
-    pub fn new(base_path: PathBuf) -> Self {
-        Self { base_path }
-    }
-
-    /// Save file to disk
-    pub async fn save_file(&self, storage_path: &str, content: &[u8]) -> Result<(), ApiError> {
-        let full_path = self.base_path.join(storage_path);
-
-        if let Some(parent) = full_path.parent() {
-            tokio::fs::create_dir_all(parent).await.map_err(|e| {
-                ApiError::Internal(anyhow::anyhow!("Failed to create directory: {}", e))
-            })?;
-        }
-
-        tokio::fs::write(&full_path, content)
-            .await
-            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to write file: {}", e)))?;
-
-        Ok(())
-    }
-
-    /// Read file from disk
-    pub async fn read_file(&self, storage_path: &str) -> Result<Vec<u8>, ApiError> {
-        let full_path = self.base_path.join(storage_path);
-
-        tokio::fs::read(&full_path)
-            .await
-            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to read file: {}", e)))
-    }
-
-    /// Delete file from disk
-    pub async fn delete_file(&self, storage_path: &str) -> Result<(), ApiError> {
-        let full_path = self.base_path.join(storage_path);
-
-        tokio::fs::remove_file(&full_path)
-            .await
-            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to delete file: {}", e)))?;
-
-        Ok(())
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -363,6 +326,12 @@ mod tests {
         assert!(LegacyContentService::validate_content_type("audio/wav").is_ok());
     }
 
+    #[test]
+    fn test_validate_image_types() {
+        assert!(LegacyContentService::validate_content_type("image/jpeg").is_ok());
+        assert!(LegacyContentService::validate_content_type("image/png").is_ok());
+    }
+
     #[test]
     fn test_validate_document_types() {
         assert!(LegacyContentService::validate_content_type("application/pdf").is_ok());
@@ -372,7 +341,7 @@ mod tests {
     #[test]
     fn test_reject_invalid_type() {
         assert!(LegacyContentService::validate_content_type("application/exe").is_err());
-        assert!(LegacyContentService::validate_content_type("image/png").is_err());
+        assert!(LegacyContentService::validate_content_type("image/svg+xml").is_err());
     }
 
     #[test]
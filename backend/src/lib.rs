@@ -1,49 +1,76 @@
+pub mod admin_rbac;
 pub mod alert_provider;
 pub mod analytics;
 pub mod api_error;
 pub mod app;
+pub mod audit_chain;
 pub mod auth;
 pub mod beneficiary_sync;
+pub mod break_glass;
 pub mod collateral_management;
 pub mod compliance;
 pub mod config;
+pub mod content_inspection;
 pub mod contingent_beneficiary;
+pub mod contract_relayer;
 pub mod db;
+pub mod death_verification;
+pub mod death_verification_case;
 pub mod document_storage;
 pub mod document_verification;
 pub mod emergency_access;
 pub mod emergency_access_jobs;
+pub mod emergency_scope;
 pub mod event_handlers;
 pub mod events;
 pub mod external_price_fetcher;
+pub mod four_eyes;
 pub mod governance;
+pub mod idempotency;
+pub mod inheritance_trigger;
 pub mod insurance_fund;
 pub mod interest_reconciliation;
+pub mod joint_will;
 pub mod legacy_content;
 pub mod lending_notification_service;
 pub mod liquidation_bot;
 pub mod loan_lifecycle;
 pub mod message_access_audit;
+pub mod message_delivery;
+pub mod message_schedule;
+pub mod metrics;
 pub mod middleware;
+pub mod notarization;
 pub mod notifications;
+pub mod object_storage;
+pub mod openapi;
 pub mod price_feed;
 pub mod price_feed_handlers;
 pub mod reputation;
+pub mod resumable_upload;
 pub mod risk_engine;
 pub mod safe_math;
+pub mod sealed_messages;
 pub mod secrets;
 pub mod secure_messages;
 pub mod service;
+pub mod sessions;
 pub mod stress_testing;
 pub mod telemetry;
+pub mod test_auth;
+pub mod totp;
+pub mod trustee_release;
 pub mod validation;
+pub mod webauthn;
 pub mod will_audit;
 pub mod will_compliance;
 pub mod will_events;
 pub mod will_pdf;
 pub mod will_signature;
+pub mod will_timestamp;
 pub mod will_version;
 pub mod witness;
+pub mod witness_link;
 pub mod yield_service;
 
 pub use api_error::ApiError;
@@ -1,5 +1,6 @@
 use crate::api_error::ApiError;
 use crate::events::{EventService, LiquidationMetadata};
+use crate::metrics::observe_job;
 use crate::notifications::{
     audit_action, entity_type, notif_type, AuditLogService, NotificationService,
 };
@@ -28,7 +29,7 @@ impl LiquidationBotService {
             let mut interval = tokio::time::interval(Duration::from_secs(60));
             loop {
                 interval.tick().await;
-                if let Err(e) = self.process_liquidations().await {
+                if let Err(e) = observe_job("liquidation_bot", self.process_liquidations()).await {
                     error!("Liquidation Bot error: {}", e);
                 }
             }
@@ -25,6 +25,7 @@ use crate::api_error::ApiError;
 use crate::notifications::{audit_action, entity_type, AuditLogService};
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use std::fmt;
@@ -83,7 +84,7 @@ impl FromStr for LoanStatus {
 // ─────────────────────────────────────────────────────────────────────────────
 
 /// Full record returned from the `loan_lifecycle` table.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct LoanLifecycleRecord {
     pub id: Uuid,
@@ -153,7 +154,7 @@ impl From<LoanLifecycleRow> for LoanLifecycleRecord {
 // ─────────────────────────────────────────────────────────────────────────────
 
 /// Payload required to open a new loan.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateLoanRequest {
     pub user_id: Uuid,
@@ -172,7 +173,7 @@ pub struct CreateLoanRequest {
 }
 
 /// Filter parameters for listing loans.
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct LoanListFilters {
     pub user_id: Option<Uuid>,
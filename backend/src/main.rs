@@ -1,5 +1,12 @@
+use inheritx_backend::audit_chain::AuditAnchorJob;
+use inheritx_backend::contract_relayer::build_contract_relayer;
+use inheritx_backend::death_verification_case::DeathVerificationCaseJob;
+use inheritx_backend::document_storage::{DocumentKeyRing, DocumentKeyRotationService};
+use inheritx_backend::four_eyes::PendingActionExpiryJob;
+use inheritx_backend::idempotency::IdempotencyKeyExpiryJob;
+use inheritx_backend::inheritance_trigger::InheritanceTriggerSubmitter;
 use inheritx_backend::{
-    create_app, db, telemetry, Config, LegacyMessageDeliveryService, MessageKeyService,
+    create_app, db, secrets, telemetry, Config, LegacyMessageDeliveryService, MessageKeyService,
 };
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -13,6 +20,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Load configuration
     let config = Config::load()?;
 
+    // Refuse to start without a document master key: encrypted wills would
+    // otherwise be unreadable (or written unencrypted) at request time.
+    let document_keyring = Arc::new(DocumentKeyRing::new(secrets::build_secrets_provider()));
+    document_keyring.ensure_configured().await?;
+
     // Initialize database
     let db_pool = db::create_pool(&config.database_url).await?;
 
@@ -49,10 +61,43 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     lending_notification_service.start();
 
     // Start legacy message delivery worker.
-    let legacy_message_delivery_service =
-        Arc::new(LegacyMessageDeliveryService::new(db_pool.clone()));
+    let legacy_message_delivery_service = Arc::new(LegacyMessageDeliveryService::new(
+        db_pool.clone(),
+        inheritx_backend::message_delivery::build_message_channels()?,
+    ));
     legacy_message_delivery_service.start();
 
+    // Re-wrap document data keys after a master key rotation.
+    let document_key_rotation_service = Arc::new(DocumentKeyRotationService::new(
+        db_pool.clone(),
+        document_keyring,
+    ));
+    document_key_rotation_service.start();
+
+    // Approve death verification cases once their objection window ends.
+    let death_verification_case_job = Arc::new(DeathVerificationCaseJob::new(db_pool.clone()));
+    death_verification_case_job.start();
+
+    // Expire four-eyes proposals that nobody approved in time.
+    let pending_action_expiry_job = Arc::new(PendingActionExpiryJob::new(db_pool.clone()));
+    pending_action_expiry_job.start();
+
+    // Delete idempotency keys once they expire.
+    let idempotency_key_expiry_job = Arc::new(IdempotencyKeyExpiryJob::new(db_pool.clone()));
+    idempotency_key_expiry_job.start();
+
+    // Submit inheritance triggers on-chain when a contract relayer is configured.
+    let relayer = build_contract_relayer();
+    if let Some(relayer) = relayer.clone() {
+        let trigger_submitter =
+            Arc::new(InheritanceTriggerSubmitter::new(db_pool.clone(), relayer));
+        trigger_submitter.start();
+    }
+
+    // Seal new audit log entries under Merkle roots and anchor them on-chain.
+    let audit_anchor_job = Arc::new(AuditAnchorJob::new(db_pool.clone(), relayer));
+    audit_anchor_job.start();
+
     // Start server
     let addr = SocketAddr::from(([0, 0, 0, 0], config.port));
     info!("Starting INHERITX backend server on {}", addr);
@@ -64,5 +109,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     )
     .await?;
 
+    telemetry::shutdown_tracing();
     Ok(())
 }
@@ -4,7 +4,9 @@
 //! delivery, key rotation, and administrative actions on legacy messages.
 
 use crate::api_error::ApiError;
+use crate::audit_chain::{self, AuditStream};
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, PgPool, Row};
 use uuid::Uuid;
@@ -18,10 +20,17 @@ pub enum MessageAccessAction {
     Viewed,
     Decrypted,
     Delivered,
+    Released,
+    Read,
     DeliveryFailed,
     KeyRotated,
     KeyListed,
     Deleted,
+    SharesDistributed,
+    ShareFetched,
+    ShareSubmitted,
+    TrusteesReleased,
+    Scheduled,
 }
 
 impl MessageAccessAction {
@@ -31,17 +40,24 @@ impl MessageAccessAction {
             Self::Viewed => "viewed",
             Self::Decrypted => "decrypted",
             Self::Delivered => "delivered",
+            Self::Released => "released",
+            Self::Read => "read",
             Self::DeliveryFailed => "delivery_failed",
             Self::KeyRotated => "key_rotated",
             Self::KeyListed => "key_listed",
             Self::Deleted => "deleted",
+            Self::SharesDistributed => "shares_distributed",
+            Self::ShareFetched => "share_fetched",
+            Self::ShareSubmitted => "share_submitted",
+            Self::TrusteesReleased => "trustees_released",
+            Self::Scheduled => "scheduled",
         }
     }
 }
 
 // ─── Audit Log Entry ─────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MessageAccessLog {
     pub id: Uuid,
     pub message_id: Option<Uuid>,
@@ -70,7 +86,7 @@ impl<'r> FromRow<'r, sqlx::postgres::PgRow> for MessageAccessLog {
 
 // ─── Query Filters ───────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 pub struct MessageAuditFilters {
     pub message_id: Option<Uuid>,
     pub user_id: Option<Uuid>,
@@ -83,7 +99,7 @@ pub struct MessageAuditFilters {
 
 // ─── Audit Summary ───────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MessageAuditSummary {
     pub total_events: i64,
     pub action_counts: Vec<ActionCount>,
@@ -93,7 +109,7 @@ pub struct MessageAuditSummary {
     pub last_event_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, JsonSchema)]
 pub struct ActionCount {
     pub action: String,
     pub count: i64,
@@ -101,7 +117,7 @@ pub struct ActionCount {
 
 // ─── User Message Activity ───────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct UserMessageActivity {
     pub user_id: Uuid,
     pub total_actions: i64,
@@ -127,27 +143,42 @@ impl MessageAccessAuditService {
         user_agent: Option<String>,
         metadata: serde_json::Value,
     ) -> Result<MessageAccessLog, ApiError> {
-        let row = sqlx::query(
-            r#"
-            INSERT INTO message_access_logs
-                (message_id, user_id, action, ip_address, user_agent, metadata)
-            VALUES ($1, $2, $3, $4, $5, $6)
-            RETURNING id, message_id, user_id, action,
-                      CAST(ip_address AS TEXT) as ip_address,
-                      user_agent, metadata, created_at
-            "#,
-        )
-        .bind(message_id)
-        .bind(user_id)
-        .bind(action.as_str())
-        .bind(ip_address)
-        .bind(user_agent)
-        .bind(&metadata)
-        .fetch_one(db)
+        let ip_address = ip_address
+            .map(|ip| {
+                ip.parse::<std::net::IpAddr>()
+                    .map(|ip| ip.to_string())
+                    .map_err(|_| ApiError::BadRequest(format!("Invalid IP address '{}'", ip)))
+            })
+            .transpose()?;
+        let entry = MessageAccessLog {
+            id: Uuid::new_v4(),
+            message_id,
+            user_id,
+            action: action.as_str().to_string(),
+            ip_address,
+            user_agent,
+            metadata,
+            created_at: audit_chain::entry_timestamp(Utc::now()),
+        };
+        let content_hash = audit_chain::content_hash(&entry)?;
+
+        sqlx::query(&audit_chain::chained_insert_sql(
+            AuditStream::MessageAccess,
+            "id, message_id, user_id, action, ip_address, user_agent, metadata, created_at",
+            "$2, $3, $4, $5, $6::inet, $7, $8, $9",
+        ))
+        .bind(content_hash.as_slice())
+        .bind(entry.id)
+        .bind(entry.message_id)
+        .bind(entry.user_id)
+        .bind(&entry.action)
+        .bind(&entry.ip_address)
+        .bind(&entry.user_agent)
+        .bind(&entry.metadata)
+        .bind(entry.created_at)
+        .execute(db)
         .await?;
 
-        let entry = MessageAccessLog::from_row(&row)?;
-
         tracing::info!(
             audit_id = %entry.id,
             action = %entry.action,
@@ -369,10 +400,17 @@ mod tests {
             (MessageAccessAction::Viewed, "viewed"),
             (MessageAccessAction::Decrypted, "decrypted"),
             (MessageAccessAction::Delivered, "delivered"),
+            (MessageAccessAction::Released, "released"),
+            (MessageAccessAction::Read, "read"),
             (MessageAccessAction::DeliveryFailed, "delivery_failed"),
             (MessageAccessAction::KeyRotated, "key_rotated"),
             (MessageAccessAction::KeyListed, "key_listed"),
             (MessageAccessAction::Deleted, "deleted"),
+            (MessageAccessAction::SharesDistributed, "shares_distributed"),
+            (MessageAccessAction::ShareFetched, "share_fetched"),
+            (MessageAccessAction::ShareSubmitted, "share_submitted"),
+            (MessageAccessAction::TrusteesReleased, "trustees_released"),
+            (MessageAccessAction::Scheduled, "scheduled"),
         ];
 
         for (action, expected) in actions {
@@ -0,0 +1,338 @@
+//! Posthumous delivery schedules for legacy messages.
+//!
+//! A message with a [`MessageSchedule`] is held in status `scheduled` until
+//! the owner's death is verified (see [`crate::death_verification`]). The
+//! delivery worker then resolves the first occurrence against the time of
+//! verification and queues the message as `pending`; recurring schedules are
+//! re-queued with their next occurrence after each delivery.
+
+use crate::api_error::ApiError;
+use crate::message_access_audit::{MessageAccessAction, MessageAccessAuditService};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Longest delay allowed for a relative trigger.
+const MAX_DAYS_AFTER_DEATH: u32 = 36_500;
+/// Future occurrences of a recurring schedule shown on the timeline.
+const TIMELINE_UPCOMING: u32 = 5;
+
+/// When a posthumous message is delivered.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MessageSchedule {
+    /// On a fixed date, e.g. a 21st birthday. If the death is verified after
+    /// the date, the message is delivered as soon as it is.
+    OnDate { at: DateTime<Utc> },
+    /// A number of days after the death is verified.
+    AfterDeath { days: u32 },
+    /// Every year on a month and day (midnight UTC) after the death is
+    /// verified, optionally for a limited number of years. February 29 falls
+    /// back to February 28 in common years.
+    Annually {
+        month: u32,
+        day: u32,
+        #[serde(default)]
+        occurrences: Option<u32>,
+    },
+}
+
+fn anniversary(year: i32, month: u32, day: u32) -> Option<DateTime<Utc>> {
+    let date = NaiveDate::from_ymd_opt(year, month, day).or_else(|| {
+        (month == 2 && day == 29)
+            .then(|| NaiveDate::from_ymd_opt(year, 2, 28))
+            .flatten()
+    })?;
+    Some(Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0)?))
+}
+
+impl MessageSchedule {
+    pub fn validate(&self) -> Result<(), ApiError> {
+        match self {
+            Self::OnDate { at } if *at <= Utc::now() => Err(ApiError::BadRequest(
+                "schedule date must be in the future".to_string(),
+            )),
+            Self::AfterDeath { days } if *days > MAX_DAYS_AFTER_DEATH => Err(ApiError::BadRequest(
+                format!("days must be at most {MAX_DAYS_AFTER_DEATH}"),
+            )),
+            Self::Annually { month, day, .. }
+                if NaiveDate::from_ymd_opt(2000, *month, *day).is_none() =>
+            {
+                Err(ApiError::BadRequest(
+                    "month and day must form a valid date".to_string(),
+                ))
+            }
+            Self::Annually {
+                occurrences: Some(0),
+                ..
+            } => Err(ApiError::BadRequest(
+                "occurrences must be at least 1".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn is_recurring(&self) -> bool {
+        matches!(self, Self::Annually { .. })
+    }
+
+    /// The `n`th delivery (counting from zero) for an owner whose death was
+    /// verified at `death_verified_at`, or `None` once the schedule is spent.
+    pub fn occurrence(&self, death_verified_at: DateTime<Utc>, n: u32) -> Option<DateTime<Utc>> {
+        match self {
+            Self::OnDate { at } => (n == 0).then(|| (*at).max(death_verified_at)),
+            Self::AfterDeath { days } => {
+                (n == 0).then(|| death_verified_at + Duration::days(i64::from(*days)))
+            }
+            Self::Annually {
+                month,
+                day,
+                occurrences,
+            } => {
+                if occurrences.is_some_and(|limit| n >= limit) {
+                    return None;
+                }
+                let year = death_verified_at.year();
+                let first_year = match anniversary(year, *month, *day) {
+                    Some(date) if date > death_verified_at => year,
+                    _ => year + 1,
+                };
+                anniversary(first_year + i32::try_from(n).ok()?, *month, *day)
+            }
+        }
+    }
+}
+
+// ── Timeline ─────────────────────────────────────────────────────────────────
+
+/// One message on an owner's delivery timeline.
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineEntry {
+    pub message_id: Uuid,
+    pub beneficiary_contact: String,
+    pub delivery_channel: String,
+    pub status: String,
+    pub schedule: Option<MessageSchedule>,
+    /// `None` when the delivery time depends on a death not yet verified, or
+    /// nothing is left to deliver.
+    pub next_delivery_at: Option<DateTime<Utc>>,
+    /// Further occurrences of a recurring schedule after the next one.
+    pub upcoming: Vec<DateTime<Utc>>,
+    pub occurrences_delivered: i32,
+    pub awaiting_death_verification: bool,
+}
+
+#[derive(sqlx::FromRow)]
+struct ScheduleRow {
+    id: Uuid,
+    owner_user_id: Uuid,
+    beneficiary_contact: String,
+    delivery_channel: String,
+    status: String,
+    unlock_at: DateTime<Utc>,
+    schedule: Option<serde_json::Value>,
+    occurrences_delivered: i32,
+    death_verified_at: Option<DateTime<Utc>>,
+}
+
+impl ScheduleRow {
+    fn schedule(&self) -> Result<Option<MessageSchedule>, ApiError> {
+        self.schedule
+            .clone()
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Invalid message schedule: {e}")))
+    }
+}
+
+const SCHEDULE_SELECT: &str = "SELECT m.id, m.owner_user_id, m.beneficiary_contact, m.delivery_channel, \
+     m.status, m.unlock_at, m.schedule, m.occurrences_delivered, d.verified_at AS death_verified_at \
+     FROM legacy_messages m LEFT JOIN death_verifications d ON d.user_id = m.owner_user_id";
+
+pub struct MessageScheduleService;
+
+impl MessageScheduleService {
+    /// Queue scheduled messages whose owner's death has been verified.
+    /// Returns how many were queued.
+    pub async fn activate_scheduled(db: &PgPool) -> Result<usize, ApiError> {
+        let rows = sqlx::query_as::<_, ScheduleRow>(&format!(
+            "{SCHEDULE_SELECT} WHERE m.status = 'scheduled' AND d.verified_at IS NOT NULL LIMIT 100"
+        ))
+        .fetch_all(db)
+        .await?;
+
+        let mut activated = 0;
+        for row in &rows {
+            let (Some(schedule), Some(death_verified_at)) =
+                (row.schedule()?, row.death_verified_at)
+            else {
+                continue;
+            };
+            let Some(unlock_at) = schedule.occurrence(death_verified_at, 0) else {
+                continue;
+            };
+            sqlx::query(
+                "UPDATE legacy_messages SET status = 'pending', unlock_at = $2, updated_at = NOW() \
+                 WHERE id = $1 AND status = 'scheduled'",
+            )
+            .bind(row.id)
+            .bind(unlock_at)
+            .execute(db)
+            .await?;
+
+            MessageAccessAuditService::log_access(
+                db,
+                Some(row.id),
+                row.owner_user_id,
+                MessageAccessAction::Scheduled,
+                None,
+                None,
+                json!({ "unlock_at": unlock_at, "death_verified_at": death_verified_at }),
+            )
+            .await?;
+            activated += 1;
+        }
+        Ok(activated)
+    }
+
+    /// When each of the owner's messages will be delivered, soonest first.
+    pub async fn timeline(
+        db: &PgPool,
+        owner_user_id: Uuid,
+    ) -> Result<Vec<TimelineEntry>, ApiError> {
+        let rows = sqlx::query_as::<_, ScheduleRow>(&format!(
+            "{SCHEDULE_SELECT} WHERE m.owner_user_id = $1 ORDER BY m.created_at"
+        ))
+        .bind(owner_user_id)
+        .fetch_all(db)
+        .await?;
+
+        let mut entries = Vec::with_capacity(rows.len());
+        for row in rows {
+            let schedule = row.schedule()?;
+            let awaiting_death_verification = row.status == "scheduled";
+            let next_delivery_at = match (&schedule, row.status.as_str()) {
+                (Some(MessageSchedule::OnDate { at }), "scheduled") => Some(*at),
+                (_, "scheduled") => None,
+                (_, "pending") => Some(row.unlock_at),
+                _ => None,
+            };
+            let upcoming = match (&schedule, row.death_verified_at) {
+                (Some(schedule), Some(death_verified_at))
+                    if schedule.is_recurring() && next_delivery_at.is_some() =>
+                {
+                    let next = row.occurrences_delivered.max(0) as u32 + 1;
+                    (next..next + TIMELINE_UPCOMING)
+                        .map_while(|n| schedule.occurrence(death_verified_at, n))
+                        .collect()
+                }
+                _ => Vec::new(),
+            };
+
+            entries.push(TimelineEntry {
+                message_id: row.id,
+                beneficiary_contact: row.beneficiary_contact,
+                delivery_channel: row.delivery_channel,
+                status: row.status,
+                schedule,
+                next_delivery_at,
+                upcoming,
+                occurrences_delivered: row.occurrences_delivered,
+                awaiting_death_verification,
+            });
+        }
+        entries.sort_by_key(|e| (e.next_delivery_at.is_none(), e.next_delivery_at));
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        anniversary(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn on_date_is_delivered_late_if_death_is_verified_after_it() {
+        let schedule = MessageSchedule::OnDate { at: at(2030, 6, 1) };
+        assert_eq!(schedule.occurrence(at(2029, 1, 1), 0), Some(at(2030, 6, 1)));
+        assert_eq!(schedule.occurrence(at(2031, 1, 1), 0), Some(at(2031, 1, 1)));
+        assert_eq!(schedule.occurrence(at(2029, 1, 1), 1), None);
+    }
+
+    #[test]
+    fn after_death_counts_days_from_verification() {
+        let schedule = MessageSchedule::AfterDeath { days: 30 };
+        assert_eq!(
+            schedule.occurrence(at(2030, 1, 1), 0),
+            Some(at(2030, 1, 31))
+        );
+        assert!(!schedule.is_recurring());
+    }
+
+    #[test]
+    fn annual_schedule_starts_at_the_next_anniversary() {
+        let schedule = MessageSchedule::Annually {
+            month: 3,
+            day: 15,
+            occurrences: Some(2),
+        };
+        assert_eq!(
+            schedule.occurrence(at(2030, 1, 1), 0),
+            Some(at(2030, 3, 15))
+        );
+        assert_eq!(
+            schedule.occurrence(at(2030, 3, 15), 0),
+            Some(at(2031, 3, 15))
+        );
+        assert_eq!(
+            schedule.occurrence(at(2030, 1, 1), 1),
+            Some(at(2031, 3, 15))
+        );
+        assert_eq!(schedule.occurrence(at(2030, 1, 1), 2), None);
+    }
+
+    #[test]
+    fn leap_day_anniversary_falls_back_in_common_years() {
+        let schedule = MessageSchedule::Annually {
+            month: 2,
+            day: 29,
+            occurrences: None,
+        };
+        assert_eq!(
+            schedule.occurrence(at(2031, 1, 1), 0),
+            Some(at(2031, 2, 28))
+        );
+        assert_eq!(
+            schedule.occurrence(at(2031, 1, 1), 1),
+            Some(at(2032, 2, 29))
+        );
+    }
+
+    #[test]
+    fn invalid_schedules_are_rejected() {
+        assert!(MessageSchedule::Annually {
+            month: 2,
+            day: 30,
+            occurrences: None
+        }
+        .validate()
+        .is_err());
+        assert!(MessageSchedule::Annually {
+            month: 5,
+            day: 1,
+            occurrences: Some(0)
+        }
+        .validate()
+        .is_err());
+        assert!(MessageSchedule::OnDate { at: at(2000, 1, 1) }
+            .validate()
+            .is_err());
+        assert!(MessageSchedule::AfterDeath { days: 365 }.validate().is_ok());
+    }
+}
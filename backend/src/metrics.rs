@@ -0,0 +1,351 @@
+//! Prometheus metrics, served at `/metrics`.
+//!
+//! HTTP requests (per matched route) and background job runs are recorded as
+//! they happen. Connection pool statistics and the domain gauges (active
+//! plans, TVL, insurance coverage) are read from the pool and the database
+//! when the endpoint is scraped. Set `METRICS_BEARER_TOKEN` to require the
+//! scraper to present it; without it the endpoint is open, so keep it off
+//! the public ingress.
+
+use crate::api_error::ApiError;
+use crate::app::AppState;
+use crate::service::{AdminService, LendingMonitoringService};
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{header, HeaderMap, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use prometheus::{
+    core::Collector, Encoder, Gauge, GaugeVec, HistogramOpts, HistogramVec, IntCounterVec,
+    IntGauge, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+use sqlx::PgPool;
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// Route label for requests that matched no route.
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Job run duration buckets, in seconds. Jobs run every 30 seconds to five
+/// minutes and may take a good part of that.
+const JOB_DURATION_BUCKETS: &[f64] =
+    &[0.01, 0.05, 0.1, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 300.0];
+
+pub struct Metrics {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_request_duration: HistogramVec,
+    db_pool_connections: IntGaugeVec,
+    db_pool_max_connections: IntGauge,
+    job_runs: IntCounterVec,
+    job_run_duration: HistogramVec,
+    job_last_success: GaugeVec,
+    active_plans: IntGauge,
+    active_loans: IntGauge,
+    total_value_locked: Gauge,
+    insurance_coverage_ratio: GaugeVec,
+}
+
+/// The process-wide metrics.
+pub fn metrics() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(|| Metrics::new().expect("metric definitions are valid"))
+}
+
+impl Metrics {
+    fn new() -> prometheus::Result<Self> {
+        let registry = Registry::new_custom(Some("inheritx".into()), None)?;
+        let metrics = Self {
+            http_requests: IntCounterVec::new(
+                Opts::new("http_requests_total", "HTTP requests handled"),
+                &["method", "route", "status"],
+            )?,
+            http_request_duration: HistogramVec::new(
+                HistogramOpts::new(
+                    "http_request_duration_seconds",
+                    "Time taken to handle HTTP requests",
+                ),
+                &["method", "route", "status"],
+            )?,
+            db_pool_connections: IntGaugeVec::new(
+                Opts::new("db_pool_connections", "Open database connections"),
+                &["state"],
+            )?,
+            db_pool_max_connections: IntGauge::new(
+                "db_pool_max_connections",
+                "Connection limit of the database pool",
+            )?,
+            job_runs: IntCounterVec::new(
+                Opts::new("job_runs_total", "Background job runs"),
+                &["job", "outcome"],
+            )?,
+            job_run_duration: HistogramVec::new(
+                HistogramOpts::new("job_run_duration_seconds", "Time taken by a job run")
+                    .buckets(JOB_DURATION_BUCKETS.to_vec()),
+                &["job"],
+            )?,
+            job_last_success: GaugeVec::new(
+                Opts::new(
+                    "job_last_success_timestamp_seconds",
+                    "Unix time the job last ran without error",
+                ),
+                &["job"],
+            )?,
+            active_plans: IntGauge::new(
+                "active_plans",
+                "Plans that are neither claimed nor deactivated",
+            )?,
+            active_loans: IntGauge::new(
+                "active_loans",
+                "Plans with outstanding borrowed principal",
+            )?,
+            total_value_locked: Gauge::new(
+                "total_value_locked",
+                "Total deposits into the lending pool",
+            )?,
+            insurance_coverage_ratio: GaugeVec::new(
+                Opts::new(
+                    "insurance_coverage_ratio",
+                    "Insurance fund reserves over covered liabilities",
+                ),
+                &["fund"],
+            )?,
+            registry,
+        };
+        let collectors: [Box<dyn Collector>; 11] = [
+            Box::new(metrics.http_requests.clone()),
+            Box::new(metrics.http_request_duration.clone()),
+            Box::new(metrics.db_pool_connections.clone()),
+            Box::new(metrics.db_pool_max_connections.clone()),
+            Box::new(metrics.job_runs.clone()),
+            Box::new(metrics.job_run_duration.clone()),
+            Box::new(metrics.job_last_success.clone()),
+            Box::new(metrics.active_plans.clone()),
+            Box::new(metrics.active_loans.clone()),
+            Box::new(metrics.total_value_locked.clone()),
+            Box::new(metrics.insurance_coverage_ratio.clone()),
+        ];
+        for collector in collectors {
+            metrics.registry.register(collector)?;
+        }
+        Ok(metrics)
+    }
+
+    pub fn observe_http(
+        &self,
+        method: &Method,
+        route: &str,
+        status: StatusCode,
+        elapsed: Duration,
+    ) {
+        let labels = [method.as_str(), route, status.as_str()];
+        self.http_requests.with_label_values(&labels).inc();
+        self.http_request_duration
+            .with_label_values(&labels)
+            .observe(elapsed.as_secs_f64());
+    }
+
+    pub fn observe_job(&self, job: &str, succeeded: bool, elapsed: Duration) {
+        let outcome = if succeeded { "success" } else { "failure" };
+        self.job_runs.with_label_values(&[job, outcome]).inc();
+        self.job_run_duration
+            .with_label_values(&[job])
+            .observe(elapsed.as_secs_f64());
+        if succeeded {
+            self.job_last_success
+                .with_label_values(&[job])
+                .set(chrono::Utc::now().timestamp() as f64);
+        }
+    }
+
+    fn observe_pool(&self, db: &PgPool) {
+        let open = i64::from(db.size());
+        let idle = db.num_idle() as i64;
+        self.db_pool_connections
+            .with_label_values(&["idle"])
+            .set(idle);
+        self.db_pool_connections
+            .with_label_values(&["in_use"])
+            .set((open - idle).max(0));
+        self.db_pool_max_connections
+            .set(i64::from(db.options().get_max_connections()));
+    }
+
+    /// Refresh the domain gauges. A failed query leaves its gauges at their
+    /// last value rather than failing the scrape.
+    async fn observe_domain(&self, db: &PgPool) {
+        let (overview, lending, funds) = tokio::join!(
+            AdminService::get_metrics_overview(db),
+            LendingMonitoringService::get_lending_metrics(db),
+            insurance_coverage(db),
+        );
+        match overview {
+            Ok(overview) => self.active_plans.set(overview.active_plans),
+            Err(e) => warn!("Metrics: could not count active plans: {}", e),
+        }
+        match lending {
+            Ok(lending) => {
+                self.active_loans.set(lending.active_loans_count);
+                self.total_value_locked.set(lending.total_value_locked);
+            }
+            Err(e) => warn!("Metrics: could not load lending metrics: {}", e),
+        }
+        match funds {
+            Ok(funds) => {
+                self.insurance_coverage_ratio.reset();
+                for (fund, ratio) in funds {
+                    self.insurance_coverage_ratio
+                        .with_label_values(&[&fund])
+                        .set(ratio);
+                }
+            }
+            Err(e) => warn!("Metrics: could not load insurance coverage: {}", e),
+        }
+    }
+
+    /// The registry in the Prometheus text format.
+    pub fn render(&self) -> Result<String, ApiError> {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to encode metrics: {e}")))?;
+        String::from_utf8(buffer)
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to encode metrics: {e}")))
+    }
+}
+
+/// `(fund name, coverage ratio)` for every insurance fund.
+async fn insurance_coverage(db: &PgPool) -> Result<Vec<(String, f64)>, ApiError> {
+    let funds = sqlx::query_as::<_, (String, f64)>(
+        "SELECT fund_name, coverage_ratio::FLOAT8 FROM insurance_fund",
+    )
+    .fetch_all(db)
+    .await?;
+    Ok(funds)
+}
+
+/// Run one iteration of a background job, recording how long it took and
+/// whether it failed.
+pub async fn observe_job<T, E>(job: &str, run: impl Future<Output = Result<T, E>>) -> Result<T, E> {
+    let started = Instant::now();
+    let result = run.await;
+    metrics().observe_job(job, result.is_ok(), started.elapsed());
+    result
+}
+
+/// Records the method, matched route, status and latency of each request.
+pub async fn http_metrics_middleware(req: Request, next: Next) -> Response {
+    let method = req.method().clone();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or(UNMATCHED_ROUTE, |path| path.as_str())
+        .to_owned();
+    let started = Instant::now();
+    let response = next.run(req).await;
+    metrics().observe_http(&method, &route, response.status(), started.elapsed());
+    response
+}
+
+/// Who may scrape `/metrics`.
+#[derive(Debug, Clone, Default)]
+pub struct MetricsPolicy {
+    bearer_token: Option<String>,
+}
+
+impl MetricsPolicy {
+    pub fn new(bearer_token: Option<String>) -> Self {
+        Self { bearer_token }
+    }
+
+    pub fn from_env() -> Self {
+        let bearer_token = std::env::var("METRICS_BEARER_TOKEN")
+            .ok()
+            .map(|token| token.trim().to_string())
+            .filter(|token| !token.is_empty());
+        Self::new(bearer_token)
+    }
+
+    pub fn authorize(&self, headers: &HeaderMap) -> Result<(), ApiError> {
+        let Some(expected) = &self.bearer_token else {
+            return Ok(());
+        };
+        let presented = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .unwrap_or_default();
+        if crate::totp::constant_time_eq(expected.as_bytes(), presented.as_bytes()) {
+            Ok(())
+        } else {
+            Err(ApiError::Unauthorized)
+        }
+    }
+}
+
+/// `GET /metrics`
+pub async fn metrics_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiError> {
+    state.metrics.authorize(&headers)?;
+    let metrics = metrics();
+    metrics.observe_pool(&state.db);
+    metrics.observe_domain(&state.db).await;
+    Ok((
+        [(header::CONTENT_TYPE, prometheus::TEXT_FORMAT)],
+        metrics.render()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn job_runs(job: &str, outcome: &str) -> u64 {
+        metrics().job_runs.with_label_values(&[job, outcome]).get()
+    }
+
+    #[tokio::test]
+    async fn job_runs_are_counted_by_outcome() {
+        let ok: Result<(), ApiError> = observe_job("metrics_test_job", async { Ok(()) }).await;
+        assert!(ok.is_ok());
+        let failed: Result<(), ApiError> =
+            observe_job("metrics_test_job", async { Err(ApiError::Unauthorized) }).await;
+        assert!(failed.is_err());
+
+        assert_eq!(job_runs("metrics_test_job", "success"), 1);
+        assert_eq!(job_runs("metrics_test_job", "failure"), 1);
+        let rendered = metrics().render().unwrap();
+        assert!(rendered
+            .contains(r#"inheritx_job_run_duration_seconds_count{job="metrics_test_job"} 2"#));
+        assert!(rendered
+            .contains(r#"inheritx_job_last_success_timestamp_seconds{job="metrics_test_job"}"#));
+    }
+
+    #[test]
+    fn bearer_token_is_required_only_when_configured() {
+        let mut headers = HeaderMap::new();
+        assert!(MetricsPolicy::new(None).authorize(&headers).is_ok());
+
+        let policy = MetricsPolicy::new(Some("scrape-secret".into()));
+        assert!(matches!(
+            policy.authorize(&headers),
+            Err(ApiError::Unauthorized)
+        ));
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer wrong-secret"),
+        );
+        assert!(policy.authorize(&headers).is_err());
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer scrape-secret"),
+        );
+        assert!(policy.authorize(&headers).is_ok());
+    }
+}
@@ -1,18 +1,22 @@
 use axum::{
     body::Body,
-    extract::Request,
+    extract::{MatchedPath, Request},
     http::{HeaderName, HeaderValue, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
 use std::time::Duration;
 use tokio::time::timeout;
+use tracing::Instrument;
 use uuid::Uuid;
 
 /// Request ID header name.
 pub static X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
 
 /// Injects a unique `x-request-id` into each request and propagates it to the response.
+///
+/// The request is handled inside an `http_request` span carrying the ID, so
+/// logs and exported traces (see [`crate::telemetry`]) can be matched to it.
 pub async fn request_id_middleware(mut req: Request, next: Next) -> Response {
     let request_id = Uuid::new_v4().to_string();
     req.headers_mut().insert(
@@ -20,7 +24,24 @@ pub async fn request_id_middleware(mut req: Request, next: Next) -> Response {
         HeaderValue::from_str(&request_id).unwrap(),
     );
 
-    let mut response = next.run(req).await;
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or("unmatched", |path| path.as_str())
+        .to_owned();
+    let span = tracing::info_span!(
+        "http_request",
+        otel.name = %format!("{} {}", req.method(), route),
+        otel.kind = "server",
+        request_id = %request_id,
+        http.request.method = %req.method(),
+        http.route = %route,
+        url.path = %req.uri().path(),
+        http.response.status_code = tracing::field::Empty,
+    );
+
+    let mut response = next.run(req).instrument(span.clone()).await;
+    span.record("http.response.status_code", response.status().as_u16());
     response.headers_mut().insert(
         X_REQUEST_ID.clone(),
         HeaderValue::from_str(&request_id).unwrap(),
@@ -0,0 +1,784 @@
+//! # Remote Electronic Notarization
+//!
+//! Admins register notaries with their commission details and the Stellar
+//! key they seal with. A testator opens a notarization session on one of
+//! their will documents; the notary verifies the testator's identity and
+//! seals the will hash with an Ed25519 signature over [`seal_message`]. The
+//! resulting [`NotaryCertificate`] is embedded into the will PDF.
+//!
+//! Every notarial act is appended to the notary's journal. Each entry hashes
+//! the previous one, so [`NotaryService::verify_journal`] detects entries
+//! that were edited, removed or reordered.
+
+use crate::api_error::ApiError;
+use crate::notifications::{audit_action, entity_type, AuditLogService};
+use crate::service::KycService;
+use crate::will_events::{WillEvent, WillEventService};
+use crate::will_pdf::embed_notary_certificate;
+use crate::will_signature::WillSignatureService;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+/// `previous_hash` of the first entry in a journal.
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// The statement a notary signs to seal a will document.
+pub fn seal_message(session_id: Uuid, will_hash: &str, commission_number: &str) -> String {
+    format!("INHERITX_NOTARY_SEAL:{session_id}:{will_hash}:{commission_number}")
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+fn journal_entry_hash(
+    previous_hash: &str,
+    sequence: i64,
+    action: &str,
+    session_id: Option<Uuid>,
+    details: &Value,
+    created_at: DateTime<Utc>,
+) -> String {
+    let session = session_id.map(|id| id.to_string()).unwrap_or_default();
+    sha256_hex(
+        format!(
+            "{previous_hash}|{sequence}|{action}|{session}|{details}|{}",
+            created_at.timestamp_micros()
+        )
+        .as_bytes(),
+    )
+}
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
+pub struct Notary {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub full_name: String,
+    pub commission_number: String,
+    pub commission_jurisdiction: String,
+    pub commission_expires_at: DateTime<Utc>,
+    pub seal_stellar_address: String,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Notary {
+    fn ensure_can_act(&self) -> Result<(), ApiError> {
+        if self.status != "active" {
+            return Err(ApiError::Forbidden("Notary is suspended".to_string()));
+        }
+        if self.commission_expires_at <= Utc::now() {
+            return Err(ApiError::Forbidden(
+                "Notary commission has expired".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RegisterNotaryRequest {
+    pub user_id: Uuid,
+    pub full_name: String,
+    pub commission_number: String,
+    pub commission_jurisdiction: String,
+    pub commission_expires_at: DateTime<Utc>,
+    pub seal_stellar_address: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
+pub struct NotarizationSession {
+    pub id: Uuid,
+    pub document_id: Uuid,
+    pub testator_user_id: Uuid,
+    pub notary_id: Uuid,
+    pub status: String,
+    pub identity_method: Option<String>,
+    pub identity_verified_at: Option<DateTime<Utc>>,
+    pub seal_signature: Option<String>,
+    pub certificate: Option<Value>,
+    pub sealed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RequestNotarizationRequest {
+    pub notary_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct VerifyIdentityRequest {
+    /// How the notary confirmed the testator's identity, e.g. `video_id_check`.
+    pub method: String,
+    /// Notes or a reference to the evidence; only its hash is stored.
+    pub evidence: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SealRequest {
+    /// Hex Ed25519 signature over [`seal_message`]
+    pub signature: String,
+}
+
+/// The notarial certificate embedded in a sealed will document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotaryCertificate {
+    pub session_id: Uuid,
+    pub document_id: Uuid,
+    pub will_hash: String,
+    pub notary_name: String,
+    pub commission_number: String,
+    pub commission_jurisdiction: String,
+    pub commission_expires_at: DateTime<Utc>,
+    pub seal_stellar_address: String,
+    pub seal_signature: String,
+    pub identity_method: String,
+    pub notarized_at: DateTime<Utc>,
+    /// Hash of the journal entry recording the seal.
+    pub journal_entry_hash: String,
+}
+
+impl NotaryCertificate {
+    fn render(&self) -> String {
+        format!(
+            "NOTARIAL CERTIFICATE\n\
+             ====================\n\n\
+             I, {}, a notary commissioned in {} (commission {},\n\
+             expiring {}), certify that on {} the testator\n\
+             appeared remotely, was identified by {}, and executed the\n\
+             will document {} bearing SHA-256 hash:\n\n\
+             {}\n\n\
+             Seal key:       {}\n\
+             Seal signature: {}\n\
+             Session:        {}\n\
+             Journal entry:  {}\n",
+            self.notary_name,
+            self.commission_jurisdiction,
+            self.commission_number,
+            self.commission_expires_at.format("%Y-%m-%d"),
+            self.notarized_at.format("%Y-%m-%d %H:%M:%S UTC"),
+            self.identity_method,
+            self.document_id,
+            self.will_hash,
+            self.seal_stellar_address,
+            self.seal_signature,
+            self.session_id,
+            self.journal_entry_hash,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
+pub struct NotaryJournalEntry {
+    pub id: Uuid,
+    pub notary_id: Uuid,
+    pub session_id: Option<Uuid>,
+    pub sequence: i64,
+    pub action: String,
+    pub details: Value,
+    pub previous_hash: String,
+    pub entry_hash: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JournalVerification {
+    pub notary_id: Uuid,
+    pub entries: usize,
+    pub valid: bool,
+    /// Sequence of the first entry whose hash or link does not check out.
+    pub first_invalid_sequence: Option<i64>,
+}
+
+const NOTARY_COLUMNS: &str = "id, user_id, full_name, commission_number, commission_jurisdiction, \
+     commission_expires_at, seal_stellar_address, status, created_at";
+
+const SESSION_COLUMNS: &str = "id, document_id, testator_user_id, notary_id, status, \
+     identity_method, identity_verified_at, seal_signature, certificate, sealed_at, created_at";
+
+// ─── Notaries and journal ────────────────────────────────────────────────────
+
+pub struct NotaryService;
+
+impl NotaryService {
+    pub async fn register(
+        db: &PgPool,
+        admin_id: Uuid,
+        req: &RegisterNotaryRequest,
+    ) -> Result<Notary, ApiError> {
+        for (field, value) in [
+            ("full_name", &req.full_name),
+            ("commission_number", &req.commission_number),
+            ("commission_jurisdiction", &req.commission_jurisdiction),
+        ] {
+            if value.trim().is_empty() {
+                return Err(ApiError::BadRequest(format!("{field} cannot be empty")));
+            }
+        }
+        if req.commission_expires_at <= Utc::now() {
+            return Err(ApiError::BadRequest(
+                "commission_expires_at must be in the future".to_string(),
+            ));
+        }
+        WillSignatureService::decode_public_key(&req.seal_stellar_address)?;
+
+        let user_exists: bool =
+            sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)")
+                .bind(req.user_id)
+                .fetch_one(db)
+                .await?;
+        if !user_exists {
+            return Err(ApiError::NotFound(format!(
+                "User {} not found",
+                req.user_id
+            )));
+        }
+
+        let mut tx = db.begin().await?;
+        let notary = sqlx::query_as::<_, Notary>(&format!(
+            "INSERT INTO notaries (user_id, full_name, commission_number, commission_jurisdiction, \
+             commission_expires_at, seal_stellar_address, created_by_admin_id) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7) \
+             ON CONFLICT DO NOTHING RETURNING {NOTARY_COLUMNS}"
+        ))
+        .bind(req.user_id)
+        .bind(req.full_name.trim())
+        .bind(req.commission_number.trim())
+        .bind(req.commission_jurisdiction.trim().to_uppercase())
+        .bind(req.commission_expires_at)
+        .bind(&req.seal_stellar_address)
+        .bind(admin_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| {
+            ApiError::Conflict(
+                "User is already a notary or the commission is already registered".to_string(),
+            )
+        })?;
+
+        Self::append_journal(
+            &mut tx,
+            notary.id,
+            None,
+            "commissioned",
+            json!({
+                "commission_number": notary.commission_number,
+                "commission_jurisdiction": notary.commission_jurisdiction,
+                "commission_expires_at": notary.commission_expires_at,
+                "seal_stellar_address": notary.seal_stellar_address,
+            }),
+        )
+        .await?;
+
+        AuditLogService::log(
+            &mut *tx,
+            Some(notary.user_id),
+            Some(admin_id),
+            audit_action::NOTARY_REGISTERED,
+            Some(notary.id),
+            Some(entity_type::NOTARY),
+            None,
+            None,
+            Some(json!({
+                "commission_number": notary.commission_number,
+                "commission_jurisdiction": notary.commission_jurisdiction,
+            })),
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(notary)
+    }
+
+    pub async fn list(db: &PgPool) -> Result<Vec<Notary>, ApiError> {
+        let notaries = sqlx::query_as::<_, Notary>(&format!(
+            "SELECT {NOTARY_COLUMNS} FROM notaries ORDER BY created_at DESC"
+        ))
+        .fetch_all(db)
+        .await?;
+        Ok(notaries)
+    }
+
+    /// The notary profile of a user, for notary-only endpoints.
+    pub async fn for_user(db: &PgPool, user_id: Uuid) -> Result<Notary, ApiError> {
+        sqlx::query_as::<_, Notary>(&format!(
+            "SELECT {NOTARY_COLUMNS} FROM notaries WHERE user_id = $1"
+        ))
+        .bind(user_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| ApiError::Forbidden("User is not a registered notary".to_string()))
+    }
+
+    pub async fn journal(
+        db: &PgPool,
+        notary_id: Uuid,
+    ) -> Result<Vec<NotaryJournalEntry>, ApiError> {
+        let entries = sqlx::query_as::<_, NotaryJournalEntry>(
+            "SELECT id, notary_id, session_id, sequence, action, details, previous_hash, \
+             entry_hash, created_at FROM notary_journal_entries \
+             WHERE notary_id = $1 ORDER BY sequence",
+        )
+        .bind(notary_id)
+        .fetch_all(db)
+        .await?;
+        Ok(entries)
+    }
+
+    /// Recompute the journal's hash chain.
+    pub async fn verify_journal(
+        db: &PgPool,
+        notary_id: Uuid,
+    ) -> Result<JournalVerification, ApiError> {
+        let entries = Self::journal(db, notary_id).await?;
+        let mut previous_hash = GENESIS_HASH.to_string();
+        let mut first_invalid_sequence = None;
+        for (expected_sequence, entry) in (1_i64..).zip(&entries) {
+            let recomputed = journal_entry_hash(
+                &entry.previous_hash,
+                entry.sequence,
+                &entry.action,
+                entry.session_id,
+                &entry.details,
+                entry.created_at,
+            );
+            if entry.sequence != expected_sequence
+                || entry.previous_hash != previous_hash
+                || entry.entry_hash != recomputed
+            {
+                first_invalid_sequence = Some(entry.sequence);
+                break;
+            }
+            previous_hash = entry.entry_hash.clone();
+        }
+
+        Ok(JournalVerification {
+            notary_id,
+            entries: entries.len(),
+            valid: first_invalid_sequence.is_none(),
+            first_invalid_sequence,
+        })
+    }
+
+    /// Append an entry to the notary's journal. The notary row is locked so
+    /// concurrent acts are chained one after the other.
+    async fn append_journal(
+        conn: &mut PgConnection,
+        notary_id: Uuid,
+        session_id: Option<Uuid>,
+        action: &str,
+        details: Value,
+    ) -> Result<NotaryJournalEntry, ApiError> {
+        sqlx::query("SELECT id FROM notaries WHERE id = $1 FOR UPDATE")
+            .bind(notary_id)
+            .execute(&mut *conn)
+            .await?;
+        let last: Option<(i64, String)> = sqlx::query_as(
+            "SELECT sequence, entry_hash FROM notary_journal_entries \
+             WHERE notary_id = $1 ORDER BY sequence DESC LIMIT 1",
+        )
+        .bind(notary_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+        let (sequence, previous_hash) = match last {
+            Some((sequence, hash)) => (sequence + 1, hash),
+            None => (1, GENESIS_HASH.to_string()),
+        };
+
+        // Postgres keeps microseconds; truncate so the stored time hashes the same.
+        let created_at = DateTime::<Utc>::from_timestamp_micros(Utc::now().timestamp_micros())
+            .unwrap_or_else(Utc::now);
+        let entry_hash = journal_entry_hash(
+            &previous_hash,
+            sequence,
+            action,
+            session_id,
+            &details,
+            created_at,
+        );
+
+        let entry = sqlx::query_as::<_, NotaryJournalEntry>(
+            "INSERT INTO notary_journal_entries \
+             (notary_id, session_id, sequence, action, details, previous_hash, entry_hash, created_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+             RETURNING id, notary_id, session_id, sequence, action, details, previous_hash, \
+             entry_hash, created_at",
+        )
+        .bind(notary_id)
+        .bind(session_id)
+        .bind(sequence)
+        .bind(action)
+        .bind(&details)
+        .bind(&previous_hash)
+        .bind(&entry_hash)
+        .bind(created_at)
+        .fetch_one(&mut *conn)
+        .await?;
+        Ok(entry)
+    }
+}
+
+// ─── Notarization sessions ───────────────────────────────────────────────────
+
+pub struct NotarizationService;
+
+impl NotarizationService {
+    /// Open a session asking `notary_id` to notarize the testator's document.
+    pub async fn request(
+        db: &PgPool,
+        testator_user_id: Uuid,
+        document_id: Uuid,
+        req: &RequestNotarizationRequest,
+    ) -> Result<NotarizationSession, ApiError> {
+        let owned: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM will_documents WHERE id = $1 AND user_id = $2)",
+        )
+        .bind(document_id)
+        .bind(testator_user_id)
+        .fetch_one(db)
+        .await?;
+        if !owned {
+            return Err(ApiError::NotFound(format!(
+                "Will document {document_id} not found"
+            )));
+        }
+
+        let notary = sqlx::query_as::<_, Notary>(&format!(
+            "SELECT {NOTARY_COLUMNS} FROM notaries WHERE id = $1"
+        ))
+        .bind(req.notary_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Notary {} not found", req.notary_id)))?;
+        if notary.user_id == testator_user_id {
+            return Err(ApiError::BadRequest(
+                "A notary cannot notarize their own will".to_string(),
+            ));
+        }
+        notary
+            .ensure_can_act()
+            .map_err(|e| ApiError::BadRequest(format!("Notary unavailable: {e}")))?;
+
+        if Self::is_notarized(db, testator_user_id, document_id).await? {
+            return Err(ApiError::Conflict(
+                "Will document is already notarized".to_string(),
+            ));
+        }
+
+        let mut tx = db.begin().await?;
+        let session = sqlx::query_as::<_, NotarizationSession>(&format!(
+            "INSERT INTO notarization_sessions (document_id, testator_user_id, notary_id) \
+             VALUES ($1, $2, $3) RETURNING {SESSION_COLUMNS}"
+        ))
+        .bind(document_id)
+        .bind(testator_user_id)
+        .bind(notary.id)
+        .fetch_one(&mut *tx)
+        .await?;
+        NotaryService::append_journal(
+            &mut tx,
+            notary.id,
+            Some(session.id),
+            "session_requested",
+            json!({ "document_id": document_id, "testator_user_id": testator_user_id }),
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(session)
+    }
+
+    pub async fn list_for_document(
+        db: &PgPool,
+        testator_user_id: Uuid,
+        document_id: Uuid,
+    ) -> Result<Vec<NotarizationSession>, ApiError> {
+        let sessions = sqlx::query_as::<_, NotarizationSession>(&format!(
+            "SELECT {SESSION_COLUMNS} FROM notarization_sessions \
+             WHERE document_id = $1 AND testator_user_id = $2 ORDER BY created_at DESC"
+        ))
+        .bind(document_id)
+        .bind(testator_user_id)
+        .fetch_all(db)
+        .await?;
+        Ok(sessions)
+    }
+
+    pub async fn list_for_notary(
+        db: &PgPool,
+        notary_id: Uuid,
+    ) -> Result<Vec<NotarizationSession>, ApiError> {
+        let sessions = sqlx::query_as::<_, NotarizationSession>(&format!(
+            "SELECT {SESSION_COLUMNS} FROM notarization_sessions \
+             WHERE notary_id = $1 ORDER BY created_at DESC"
+        ))
+        .bind(notary_id)
+        .fetch_all(db)
+        .await?;
+        Ok(sessions)
+    }
+
+    /// Whether the testator's document carries a sealed notary certificate.
+    pub async fn is_notarized(
+        db: &PgPool,
+        testator_user_id: Uuid,
+        document_id: Uuid,
+    ) -> Result<bool, ApiError> {
+        let sealed: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM notarization_sessions \
+             WHERE document_id = $1 AND testator_user_id = $2 AND status = 'sealed')",
+        )
+        .bind(document_id)
+        .bind(testator_user_id)
+        .fetch_one(db)
+        .await?;
+        Ok(sealed)
+    }
+
+    async fn session_for_notary(
+        db: &PgPool,
+        notary: &Notary,
+        session_id: Uuid,
+    ) -> Result<NotarizationSession, ApiError> {
+        sqlx::query_as::<_, NotarizationSession>(&format!(
+            "SELECT {SESSION_COLUMNS} FROM notarization_sessions WHERE id = $1 AND notary_id = $2"
+        ))
+        .bind(session_id)
+        .bind(notary.id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Notarization session {session_id} not found")))
+    }
+
+    /// Record the notary's identity check of the testator. The testator must
+    /// have passed KYC; the notary's evidence is kept only as a hash.
+    pub async fn verify_identity(
+        db: &PgPool,
+        notary_user_id: Uuid,
+        session_id: Uuid,
+        req: &VerifyIdentityRequest,
+    ) -> Result<NotarizationSession, ApiError> {
+        let notary = NotaryService::for_user(db, notary_user_id).await?;
+        notary.ensure_can_act()?;
+        let session = Self::session_for_notary(db, &notary, session_id).await?;
+        if session.status != "requested" {
+            return Err(ApiError::Conflict(format!(
+                "Session is already {}",
+                session.status
+            )));
+        }
+        if req.method.trim().is_empty() || req.evidence.trim().is_empty() {
+            return Err(ApiError::BadRequest(
+                "method and evidence are required".to_string(),
+            ));
+        }
+
+        let kyc = KycService::get_kyc_status(db, session.testator_user_id).await?;
+        if kyc.status != "approved" {
+            return Err(ApiError::Forbidden(
+                "Testator KYC not approved: identity cannot be verified".to_string(),
+            ));
+        }
+
+        let evidence_hash = sha256_hex(req.evidence.as_bytes());
+        let mut tx = db.begin().await?;
+        let session = sqlx::query_as::<_, NotarizationSession>(&format!(
+            "UPDATE notarization_sessions SET status = 'identity_verified', identity_method = $2, \
+             identity_evidence_hash = $3, identity_verified_at = NOW() \
+             WHERE id = $1 AND status = 'requested' RETURNING {SESSION_COLUMNS}"
+        ))
+        .bind(session.id)
+        .bind(req.method.trim())
+        .bind(&evidence_hash)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| ApiError::Conflict("Session changed concurrently".to_string()))?;
+        NotaryService::append_journal(
+            &mut tx,
+            notary.id,
+            Some(session.id),
+            "identity_verified",
+            json!({
+                "testator_user_id": session.testator_user_id,
+                "method": session.identity_method,
+                "evidence_hash": evidence_hash,
+                "kyc_status": kyc.status,
+            }),
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(session)
+    }
+
+    /// Verify the notary's seal over the will hash, journal it and embed the
+    /// certificate into the will document.
+    pub async fn seal(
+        db: &PgPool,
+        notary_user_id: Uuid,
+        session_id: Uuid,
+        req: &SealRequest,
+    ) -> Result<NotarizationSession, ApiError> {
+        let notary = NotaryService::for_user(db, notary_user_id).await?;
+        notary.ensure_can_act()?;
+        let session = Self::session_for_notary(db, &notary, session_id).await?;
+        if session.status != "identity_verified" {
+            return Err(ApiError::BadRequest(
+                "The testator's identity must be verified before sealing".to_string(),
+            ));
+        }
+
+        let (plan_id, will_hash, pdf_base64): (Uuid, String, String) = sqlx::query_as(
+            "SELECT plan_id, will_hash, pdf_base64 FROM will_documents WHERE id = $1",
+        )
+        .bind(session.document_id)
+        .fetch_one(db)
+        .await?;
+
+        WillSignatureService::verify_signature(
+            &notary.seal_stellar_address,
+            &seal_message(session.id, &will_hash, &notary.commission_number),
+            &req.signature,
+        )?;
+
+        let pdf = BASE64
+            .decode(&pdf_base64)
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Stored PDF is not base64: {e}")))?;
+
+        let mut tx = db.begin().await?;
+        let entry = NotaryService::append_journal(
+            &mut tx,
+            notary.id,
+            Some(session.id),
+            "seal_applied",
+            json!({
+                "document_id": session.document_id,
+                "will_hash": will_hash,
+                "seal_signature": req.signature,
+            }),
+        )
+        .await?;
+
+        let certificate = NotaryCertificate {
+            session_id: session.id,
+            document_id: session.document_id,
+            will_hash,
+            notary_name: notary.full_name.clone(),
+            commission_number: notary.commission_number.clone(),
+            commission_jurisdiction: notary.commission_jurisdiction.clone(),
+            commission_expires_at: notary.commission_expires_at,
+            seal_stellar_address: notary.seal_stellar_address.clone(),
+            seal_signature: req.signature.clone(),
+            identity_method: session.identity_method.clone().unwrap_or_default(),
+            notarized_at: entry.created_at,
+            journal_entry_hash: entry.entry_hash,
+        };
+        let certificate_json = serde_json::to_value(&certificate)
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!(e)))?;
+        let sealed_pdf =
+            embed_notary_certificate(&pdf, &certificate.render(), &certificate_json.to_string())?;
+
+        sqlx::query("UPDATE will_documents SET pdf_base64 = $2 WHERE id = $1")
+            .bind(session.document_id)
+            .bind(BASE64.encode(sealed_pdf))
+            .execute(&mut *tx)
+            .await?;
+        let session = sqlx::query_as::<_, NotarizationSession>(&format!(
+            "UPDATE notarization_sessions SET status = 'sealed', seal_signature = $2, \
+             certificate = $3, sealed_at = $4 \
+             WHERE id = $1 AND status = 'identity_verified' RETURNING {SESSION_COLUMNS}"
+        ))
+        .bind(session.id)
+        .bind(&req.signature)
+        .bind(&certificate_json)
+        .bind(certificate.notarized_at)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| ApiError::Conflict("Session changed concurrently".to_string()))?;
+
+        AuditLogService::log(
+            &mut *tx,
+            Some(session.testator_user_id),
+            None,
+            audit_action::WILL_NOTARIZED,
+            Some(session.document_id),
+            Some(entity_type::WILL_DOCUMENT),
+            None,
+            None,
+            Some(json!({
+                "session_id": session.id,
+                "notary_id": notary.id,
+                "commission_number": notary.commission_number,
+            })),
+        )
+        .await?;
+        tx.commit().await?;
+
+        let vault_id: String =
+            sqlx::query_scalar("SELECT COALESCE(title, id::text) FROM plans WHERE id = $1")
+                .bind(plan_id)
+                .fetch_one(db)
+                .await?;
+        let event = WillEvent::WillNotarized {
+            vault_id,
+            document_id: session.document_id,
+            plan_id,
+            session_id: session.id,
+            notary_id: notary.id,
+            commission_number: notary.commission_number,
+            timestamp: certificate.notarized_at,
+        };
+        if let Err(e) = WillEventService::emit(db, event).await {
+            tracing::warn!("Failed to emit WillNotarized event: {}", e);
+        }
+
+        Ok(session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn journal_hash_covers_every_field() {
+        let at = Utc::now();
+        let details = json!({ "document_id": "abc" });
+        let base = journal_entry_hash(GENESIS_HASH, 1, "seal_applied", None, &details, at);
+        assert_eq!(
+            base,
+            journal_entry_hash(GENESIS_HASH, 1, "seal_applied", None, &details, at)
+        );
+        assert_ne!(
+            base,
+            journal_entry_hash(GENESIS_HASH, 2, "seal_applied", None, &details, at)
+        );
+        assert_ne!(
+            base,
+            journal_entry_hash(GENESIS_HASH, 1, "identity_verified", None, &details, at)
+        );
+        assert_ne!(
+            base,
+            journal_entry_hash(GENESIS_HASH, 1, "seal_applied", None, &json!({}), at)
+        );
+        assert_ne!(
+            base,
+            journal_entry_hash(
+                GENESIS_HASH,
+                1,
+                "seal_applied",
+                Some(Uuid::nil()),
+                &details,
+                at
+            )
+        );
+    }
+}
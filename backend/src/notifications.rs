@@ -1,6 +1,8 @@
 use crate::alert_provider::AlertProvider;
 use crate::api_error::ApiError;
+use crate::audit_chain::{self, AuditStream};
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use uuid::Uuid;
@@ -30,7 +32,12 @@ pub mod notif_type {
     pub const EMERGENCY_ACCESS_GRANTED: &str = "emergency_access_granted";
     pub const EMERGENCY_ACCESS_REVOKED: &str = "emergency_access_revoked";
     pub const EMERGENCY_ACCESS_EXPIRING: &str = "emergency_access_expiring";
+    pub const EMERGENCY_ACCESS_REQUESTED: &str = "emergency_access_requested";
+    pub const EMERGENCY_ACCESS_VETOED: &str = "emergency_access_vetoed";
     pub const SUSPICIOUS_ACTIVITY_FLAGGED: &str = "suspicious_activity_flagged";
+    // Death verification cases
+    pub const DEATH_VERIFICATION_OBJECTION_WINDOW: &str = "death_verification_objection_window";
+    pub const DEATH_VERIFICATION_DECIDED: &str = "death_verification_decided";
     // Insurance fund monitoring (Issue #249)
     pub const ADMIN_ALERT: &str = "admin_alert";
     pub const FUND_STATUS_CHANGE: &str = "fund_status_change";
@@ -38,7 +45,7 @@ pub mod notif_type {
 
 // ─── Notification ────────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
 pub struct Notification {
     pub id: Uuid,
     pub user_id: Uuid,
@@ -265,6 +272,9 @@ pub mod audit_action {
     pub const EMERGENCY_ACCESS_GRANTED: &str = "emergency_access_granted";
     pub const EMERGENCY_ACCESS_REVOKED: &str = "emergency_access_revoked";
     pub const EMERGENCY_ACCESS_EXPIRED: &str = "emergency_access_expired";
+    pub const EMERGENCY_ACCESS_REQUESTED: &str = "emergency_access_requested";
+    pub const EMERGENCY_ACCESS_VETOED: &str = "emergency_access_vetoed";
+    pub const INHERITANCE_TRIGGERED: &str = "inheritance_triggered";
     // Insurance fund monitoring (Issue #249)
     pub const FUND_STATUS_CHANGE: &str = "fund_status_change";
     pub const REPAYMENT_REMINDER_SENT: &str = "repayment_reminder_sent";
@@ -273,6 +283,49 @@ pub mod audit_action {
     pub const INSURANCE_CLAIM_CREATED: &str = "insurance_claim_created";
     pub const INSURANCE_CLAIM_PROCESSED: &str = "insurance_claim_processed";
     pub const INSURANCE_CLAIM_PAID: &str = "insurance_claim_paid";
+    // Death verification
+    pub const DEATH_VERIFIED: &str = "death_verified";
+    pub const DEATH_CASE_OPENED: &str = "death_case_opened";
+    pub const DEATH_CERTIFICATE_UPLOADED: &str = "death_certificate_uploaded";
+    pub const DEATH_CERTIFICATE_VIEWED: &str = "death_certificate_viewed";
+    pub const DEATH_CASE_REVIEWED: &str = "death_case_reviewed";
+    pub const DEATH_OBJECTION_WINDOW_OPENED: &str = "death_objection_window_opened";
+    pub const DEATH_OBJECTION_RAISED: &str = "death_objection_raised";
+    pub const DEATH_OBJECTION_RESOLVED: &str = "death_objection_resolved";
+    pub const DEATH_CASE_APPROVED: &str = "death_case_approved";
+    pub const DEATH_CASE_REJECTED: &str = "death_case_rejected";
+    // Remote notarization
+    pub const NOTARY_REGISTERED: &str = "notary_registered";
+    pub const WILL_NOTARIZED: &str = "will_notarized";
+    // Sessions
+    pub const SESSION_CREATED: &str = "session_created";
+    pub const SESSION_REVOKED: &str = "session_revoked";
+    pub const SESSION_REFRESH_REUSED: &str = "session_refresh_reused";
+    pub const SESSIONS_FORCE_REVOKED: &str = "sessions_force_revoked";
+    // Admin roles and permissions
+    pub const ADMIN_PERMISSION_DENIED: &str = "admin_permission_denied";
+    pub const ADMIN_ROLE_CREATED: &str = "admin_role_created";
+    pub const ADMIN_ROLE_UPDATED: &str = "admin_role_updated";
+    pub const ADMIN_ROLE_DELETED: &str = "admin_role_deleted";
+    pub const ADMIN_ROLE_ASSIGNED: &str = "admin_role_assigned";
+    // Four-eyes approval of high-risk admin actions
+    pub const PENDING_ACTION_PROPOSED: &str = "pending_action_proposed";
+    pub const PENDING_ACTION_APPROVED: &str = "pending_action_approved";
+    pub const PENDING_ACTION_REJECTED: &str = "pending_action_rejected";
+    pub const PENDING_ACTION_EXPIRED: &str = "pending_action_expired";
+    pub const PENDING_ACTION_EXECUTED: &str = "pending_action_executed";
+    pub const PENDING_ACTION_FAILED: &str = "pending_action_failed";
+    // Authenticator-app 2FA
+    pub const TOTP_ENABLED: &str = "totp_enabled";
+    pub const TOTP_DISABLED: &str = "totp_disabled";
+    pub const TOTP_VERIFICATION_FAILED: &str = "totp_verification_failed";
+    pub const RECOVERY_CODE_USED: &str = "recovery_code_used";
+    pub const RECOVERY_CODES_REGENERATED: &str = "recovery_codes_regenerated";
+    pub const STEP_UP_COMPLETED: &str = "step_up_completed";
+    // Passkeys
+    pub const PASSKEY_REGISTERED: &str = "passkey_registered";
+    pub const PASSKEY_REMOVED: &str = "passkey_removed";
+    pub const PASSKEY_CLONE_DETECTED: &str = "passkey_clone_detected";
 }
 
 /// Entity type constants — stored in `entity_type` column of `action_logs`.
@@ -283,9 +336,17 @@ pub mod entity_type {
     // Insurance fund monitoring (Issue #249)
     pub const INSURANCE_FUND: &str = "insurance_fund";
     pub const INSURANCE_CLAIM: &str = "insurance_claim";
+    pub const NOTARY: &str = "notary";
+    pub const WILL_DOCUMENT: &str = "will_document";
+    pub const DEATH_VERIFICATION_CASE: &str = "death_verification_case";
+    pub const SESSION: &str = "session";
+    pub const ADMIN: &str = "admin";
+    pub const ADMIN_ROLE: &str = "admin_role";
+    pub const PENDING_ACTION: &str = "pending_action";
+    pub const PASSKEY: &str = "passkey";
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
 pub struct ActionLog {
     pub id: Uuid,
     pub user_id: Option<Uuid>,
@@ -314,23 +375,37 @@ impl AuditLogService {
         new_value: Option<&str>,
         metadata: Option<serde_json::Value>,
     ) -> Result<(), ApiError> {
-        sqlx::query(
-            r#"
-            INSERT INTO action_logs (
-                user_id, admin_id, action, entity_id, entity_type, 
-                old_value, new_value, metadata
-            )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-            "#,
-        )
-        .bind(user_id)
-        .bind(admin_id)
-        .bind(action)
-        .bind(entity_id)
-        .bind(entity_type)
-        .bind(old_value)
-        .bind(new_value)
-        .bind(metadata)
+        let entry = ActionLog {
+            id: Uuid::new_v4(),
+            user_id,
+            admin_id,
+            action: action.to_string(),
+            entity_id,
+            entity_type: entity_type.map(str::to_string),
+            old_value: old_value.map(str::to_string),
+            new_value: new_value.map(str::to_string),
+            metadata,
+            timestamp: audit_chain::entry_timestamp(Utc::now()),
+        };
+        let content_hash = audit_chain::content_hash(&entry)?;
+
+        sqlx::query(&audit_chain::chained_insert_sql(
+            AuditStream::Actions,
+            "id, user_id, admin_id, action, entity_id, entity_type, old_value, new_value, \
+             metadata, timestamp",
+            "$2, $3, $4, $5, $6, $7, $8, $9, $10, $11",
+        ))
+        .bind(content_hash.as_slice())
+        .bind(entry.id)
+        .bind(entry.user_id)
+        .bind(entry.admin_id)
+        .bind(&entry.action)
+        .bind(entry.entity_id)
+        .bind(&entry.entity_type)
+        .bind(&entry.old_value)
+        .bind(&entry.new_value)
+        .bind(&entry.metadata)
+        .bind(entry.timestamp)
         .execute(executor)
         .await?;
 
@@ -423,6 +498,29 @@ impl AuditLogService {
 
         Ok(rows)
     }
+
+    /// Return audit log entries about one entity, oldest first.
+    pub async fn list_for_entity(
+        db: &PgPool,
+        entity_type: &str,
+        entity_id: Uuid,
+    ) -> Result<Vec<ActionLog>, ApiError> {
+        let rows = sqlx::query_as::<_, ActionLog>(
+            r#"
+            SELECT id, user_id, admin_id, action, entity_id, entity_type, 
+                   old_value, new_value, metadata, timestamp
+            FROM action_logs
+            WHERE entity_type = $1 AND entity_id = $2
+            ORDER BY timestamp ASC
+            "#,
+        )
+        .bind(entity_type)
+        .bind(entity_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(rows)
+    }
 }
 // ─── Tests ───────────────────────────────────────────────────────────────────
 
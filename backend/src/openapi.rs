@@ -0,0 +1,2172 @@
+//! OpenAPI 3.1 description of the HTTP API, served at `/api/openapi.json`.
+//!
+//! Every route registered in [`crate::app::create_app`] has an entry in
+//! [`operations`]. `tests/openapi_tests.rs` sends each documented operation
+//! through the router and fails when one is not routed, when a documented
+//! path answers a method that is not documented, or when the documented
+//! security differs from what the handler's extractor enforces. Request,
+//! query, path and response schemas are not written by hand. They come from
+//! each type's `JsonSchema` derive through [`SchemaRegistry`], so renamed,
+//! added and optional fields show up without anyone editing this file. Error
+//! responses follow [`ApiError`]'s `IntoResponse` impl.
+
+use crate::admin_rbac::{
+    perm, AdminRole, AssignRoleRequest, CreateRoleRequest, Permission, UpdateRoleRequest,
+};
+use crate::analytics::{RevenueRangeQuery, YieldHistoryQuery, YieldSummaryQuery};
+use crate::api_error::ApiError;
+use crate::app::{
+    ClaimsQuery, GenerateWillRequest, KycUpdateRequest, LiquidityDrainRequest, MetricsHistoryQuery,
+    PriceCrashRequest, ReadConfirmationQuery, RepayRequest, SearchAuditParams, SearchQuery,
+    SyncBeneficiariesRequest, TransactionsQuery, ValidateWillRequest, VerifyContentRequest,
+    VerifyHashRequest,
+};
+use crate::audit_chain::EntryVerification;
+use crate::auth::{LoginRequest, LoginResponse, NonceResponse, WalletLoginRequest};
+use crate::beneficiary_sync::BeneficiarySyncResult;
+use crate::break_glass::{
+    BreakGlassGrant, BreakGlassRequest, BreakGlassSettings, VetoBreakGlassRequest,
+};
+use crate::collateral_management::{
+    AddCollateralRequest, CollateralInfo, CollateralRequirements, RemoveCollateralRequest,
+    SafeWithdrawalInfo, SwapCollateralRequest,
+};
+use crate::contingent_beneficiary::{
+    AddContingentBeneficiaryRequest, ContingencyConfig, ContingentBeneficiary,
+    PromoteContingentRequest, SetContingencyConditionsRequest,
+};
+use crate::death_verification::{DeathVerification, RecordDeathVerificationRequest};
+use crate::death_verification_case::{
+    CaseListFilters, CaseObjection, DeathVerificationCase, ObjectionRequest, OpenCaseRequest,
+    ResolveObjectionRequest, ReviewCaseRequest,
+};
+use crate::document_storage::{BackupRecord, DeathCertificateRecord};
+use crate::document_verification::VerificationResult;
+use crate::emergency_access::{GrantEmergencyAccessRequest, RevokeEmergencyAccessRequest};
+use crate::emergency_scope::{PayBillRequest, ScopeRequest, TriggerInheritanceRequest};
+use crate::four_eyes::{PendingAction, PendingActionFilters, RejectPendingActionRequest};
+use crate::governance::{CreateProposalRequest, ParameterUpdateRequest, Proposal, VoteRequest};
+use crate::idempotency::{IDEMPOTENCY_KEY, IDEMPOTENT_REPLAYED, MAX_KEY_LEN};
+use crate::inheritance_trigger::InheritanceTrigger;
+use crate::insurance_fund::{CreateInsuranceClaimRequest, ProcessInsuranceClaimRequest};
+use crate::joint_will::{ConfigureTestatorsRequest, JointWillStatus, RevokeWillRequest};
+use crate::legacy_content::{ContentListFilters, LegacyContent, StorageStats};
+use crate::loan_lifecycle::{CreateLoanRequest, LoanLifecycleRecord, LoanListFilters};
+use crate::message_access_audit::{
+    MessageAccessLog, MessageAuditFilters, MessageAuditSummary, UserMessageActivity,
+};
+use crate::message_delivery::InboxMessage;
+use crate::notarization::{
+    NotarizationSession, Notary, NotaryJournalEntry, RegisterNotaryRequest,
+    RequestNotarizationRequest, SealRequest, VerifyIdentityRequest,
+};
+use crate::notifications::{ActionLog, Notification};
+use crate::price_feed_handlers::{RegisterFeedRequest, UpdatePriceRequest};
+use crate::reputation::BorrowerReputation;
+use crate::resumable_upload::{CreateUploadSessionRequest, FinalizeUploadRequest};
+use crate::sealed_messages::{
+    CreateSealedMessageRequest, RecipientKey, RegisterRecipientKeyRequest,
+    ReleaseSealedMessageRequest,
+};
+use crate::secure_messages::{CreateLegacyMessageRequest, LegacyMessage, MessageEncryptionKey};
+use crate::service::{
+    ClaimPlanRequest, CreateEmergencyAccessGrantRequest, CreateEmergencyContactRequest,
+    CreatePlanRequest, DueForClaimPlan, EarningsHistoryResponse, EmergencyAccessAuditLog,
+    EmergencyAccessAuditLogFilters, EmergencyAccessDashboardResponse, EmergencyAccessMetrics,
+    EmergencyAccessRiskAlert, EmergencyAccessSession, EmergencyContact, KycRecord,
+    LoanSimulationRecord, LoanSimulationRequest, LoanSimulationResult, PausePlanRequest,
+    PlanStatistics, PlanWithBeneficiary, RevenueMetricsResponse, RevokeEmergencyAccessGrantRequest,
+    RiskOverrideRequest, StartSessionRequest, UnpausePlanRequest, UpdateEmergencyContactRequest,
+    YieldSummaryResponse,
+};
+use crate::sessions::RefreshRequest;
+use crate::totp::TotpCodeRequest;
+use crate::totp::STEP_UP_WINDOW_MINUTES;
+use crate::trustee_release::{
+    CreateTrusteeMessageRequest, SubmitTrusteeShareRequest, TrusteeShareRequest,
+};
+use crate::webauthn::{
+    AuthenticationCredential, PasskeyLoginOptionsRequest, RegistrationCredential,
+};
+use crate::will_audit::{
+    AdminAuditStatistics, AuditLogEntry, AuditLogFilters, AuditLogSummary, UserActivitySummary,
+};
+use crate::will_compliance::ValidationResult;
+use crate::will_pdf::GeneratedWillDocument;
+use crate::will_signature::{
+    SigningChallenge, SigningChallengeRequest, SubmitSignatureRequest, WillSignatureRecord,
+};
+use crate::will_version::{PaginationParams, WillVersionSummary};
+use crate::witness::{
+    InviteWitnessRequest, WitnessRecord, WitnessSignRequest, WitnessStatusSummary,
+};
+use crate::witness_link::{WitnessAttestation, WitnessAttestationRequest, WitnessInvitation};
+use axum::{http::header, response::IntoResponse};
+use schemars::generate::{SchemaGenerator, SchemaSettings};
+use schemars::JsonSchema;
+use serde_json::{json, Map, Value};
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+/// JSON schemas derived by `schemars` from the types handlers extract and
+/// return.
+///
+/// Named structs and enums become components referenced by `$ref`, so
+/// renamed, added and optional fields show up without anyone editing this
+/// file. Schemas describe what the types accept, following their serde
+/// attributes: fields are required unless they are `Option`s or have a
+/// default.
+pub struct SchemaRegistry {
+    generator: SchemaGenerator,
+}
+
+impl Default for SchemaRegistry {
+    fn default() -> Self {
+        let settings = SchemaSettings::draft2020_12().with(|s| {
+            s.definitions_path = "/components/schemas".into();
+            s.meta_schema = None;
+        });
+        Self {
+            generator: settings.into_generator(),
+        }
+    }
+}
+
+impl SchemaRegistry {
+    /// Schema for `T`, registering the named types it contains.
+    pub fn schema_of<T: JsonSchema>(&mut self) -> Value {
+        self.generator.subschema_for::<T>().to_value()
+    }
+
+    /// The schema a `$ref` points at, or `schema` itself.
+    fn resolve<'a>(&'a self, schema: &'a Value) -> &'a Value {
+        schema["$ref"]
+            .as_str()
+            .and_then(|r| r.strip_prefix("#/components/schemas/"))
+            .and_then(|name| self.generator.definitions().get(name))
+            .unwrap_or(schema)
+    }
+
+    pub fn into_components(mut self) -> Map<String, Value> {
+        self.generator.take_definitions(true)
+    }
+}
+
+type SchemaFn = fn(&mut SchemaRegistry) -> Value;
+
+/// Who may call an operation.
+#[derive(Debug, Clone, Copy)]
+enum Access {
+    Public,
+    User,
+    /// A user token with a TOTP step-up from the last few minutes.
+    StepUp,
+    Admin,
+    /// An admin whose role grants the named permission.
+    Permission(&'static str),
+    /// Open unless `METRICS_BEARER_TOKEN` is set.
+    Scraper,
+}
+
+enum RequestBody {
+    Json(SchemaFn),
+    Multipart,
+    Binary,
+}
+
+enum Success {
+    /// `{"status": "success", "data": ...}`, with `data` typed when known.
+    Envelope(Option<SchemaFn>),
+    /// The type itself, without the envelope.
+    Json(SchemaFn),
+    /// File contents.
+    Download,
+    /// Prometheus text exposition format.
+    Metrics,
+}
+
+/// One method on one route.
+pub struct ApiOperation {
+    method: &'static str,
+    path: &'static str,
+    summary: &'static str,
+    access: Access,
+    path_params: Option<SchemaFn>,
+    query: Option<SchemaFn>,
+    body: Option<RequestBody>,
+    success: Success,
+    four_eyes: bool,
+    idempotent: bool,
+}
+
+impl ApiOperation {
+    fn new(method: &'static str, path: &'static str, summary: &'static str) -> Self {
+        Self {
+            method,
+            path,
+            summary,
+            access: Access::Public,
+            path_params: None,
+            query: None,
+            body: None,
+            success: Success::Envelope(None),
+            four_eyes: false,
+            idempotent: false,
+        }
+    }
+
+    fn get(path: &'static str, summary: &'static str) -> Self {
+        Self::new("get", path, summary)
+    }
+
+    fn post(path: &'static str, summary: &'static str) -> Self {
+        Self::new("post", path, summary)
+    }
+
+    fn put(path: &'static str, summary: &'static str) -> Self {
+        Self::new("put", path, summary)
+    }
+
+    fn patch(path: &'static str, summary: &'static str) -> Self {
+        Self::new("patch", path, summary)
+    }
+
+    fn delete(path: &'static str, summary: &'static str) -> Self {
+        Self::new("delete", path, summary)
+    }
+
+    fn user(mut self) -> Self {
+        self.access = Access::User;
+        self
+    }
+
+    fn step_up(mut self) -> Self {
+        self.access = Access::StepUp;
+        self
+    }
+
+    fn admin(mut self) -> Self {
+        self.access = Access::Admin;
+        self
+    }
+
+    fn permission<P: Permission>(mut self) -> Self {
+        self.access = Access::Permission(P::NAME);
+        self
+    }
+
+    /// Path parameters, as extracted with `Path<T>`.
+    fn path<T: JsonSchema>(mut self) -> Self {
+        self.path_params = Some(SchemaRegistry::schema_of::<T>);
+        self
+    }
+
+    fn query<T: JsonSchema>(mut self) -> Self {
+        self.query = Some(SchemaRegistry::schema_of::<T>);
+        self
+    }
+
+    fn body<T: JsonSchema>(mut self) -> Self {
+        self.body = Some(RequestBody::Json(SchemaRegistry::schema_of::<T>));
+        self
+    }
+
+    fn multipart(mut self) -> Self {
+        self.body = Some(RequestBody::Multipart);
+        self
+    }
+
+    fn binary(mut self) -> Self {
+        self.body = Some(RequestBody::Binary);
+        self
+    }
+
+    /// The `data` of the success envelope.
+    fn data<T: JsonSchema>(mut self) -> Self {
+        self.success = Success::Envelope(Some(SchemaRegistry::schema_of::<T>));
+        self
+    }
+
+    /// A response body that is `T` itself rather than an envelope.
+    fn returns<T: JsonSchema>(mut self) -> Self {
+        self.success = Success::Json(SchemaRegistry::schema_of::<T>);
+        self
+    }
+
+    fn download(mut self) -> Self {
+        self.success = Success::Download;
+        self
+    }
+
+    fn scraped(mut self) -> Self {
+        self.access = Access::Scraper;
+        self.success = Success::Metrics;
+        self
+    }
+
+    /// May answer 202 with a pending action for a second admin to approve.
+    fn four_eyes(mut self) -> Self {
+        self.four_eyes = true;
+        self
+    }
+
+    /// Honours `Idempotency-Key`.
+    fn idempotent(mut self) -> Self {
+        self.idempotent = true;
+        self
+    }
+
+    fn param_names(&self) -> Vec<&'static str> {
+        self.path
+            .split('/')
+            .filter_map(|segment| segment.strip_prefix(':'))
+            .collect()
+    }
+
+    /// The route in OpenAPI's template syntax, e.g. `/api/plans/{plan_id}`.
+    fn template(&self) -> String {
+        self.path
+            .split('/')
+            .map(|segment| match segment.strip_prefix(':') {
+                Some(name) => format!("{{{name}}}"),
+                None => segment.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    fn operation_id(&self) -> String {
+        let words: Vec<String> = std::iter::once(self.method)
+            .chain(self.path.split(['/', '-', '.']))
+            .map(|w| w.trim_start_matches(':'))
+            .filter(|w| !w.is_empty())
+            .map(|w| w.replace('_', ""))
+            .collect();
+        let mut id = String::new();
+        for (i, word) in words.iter().enumerate() {
+            let mut chars = word.chars();
+            if let Some(first) = chars.next() {
+                if i == 0 {
+                    id.push(first);
+                } else {
+                    id.extend(first.to_uppercase());
+                }
+                id.push_str(chars.as_str());
+            }
+        }
+        id
+    }
+
+    /// The first path segment after `/api` and `/admin`.
+    fn tag(&self) -> &'static str {
+        self.path
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .find(|s| !matches!(*s, "api" | "admin"))
+            .unwrap_or("admin")
+    }
+}
+
+fn parameters(registry: &mut SchemaRegistry, op: &ApiOperation) -> Vec<Value> {
+    let mut params = Vec::new();
+    let names = op.param_names();
+    let path_schema = op
+        .path_params
+        .map(|f| f(registry))
+        .unwrap_or_else(|| json!({ "type": "string" }));
+    let path_schema = registry.resolve(&path_schema).clone();
+    for (i, name) in names.iter().enumerate() {
+        let schema = if let Some(schema) = path_schema["properties"].get(*name) {
+            schema.clone()
+        } else if let Some(schema) = path_schema["prefixItems"].get(i) {
+            schema.clone()
+        } else if names.len() > 1 && path_schema.get("items").is_some() {
+            path_schema["items"].clone()
+        } else {
+            path_schema.clone()
+        };
+        params.push(json!({ "name": name, "in": "path", "required": true, "schema": schema }));
+    }
+
+    if let Some(query) = op.query {
+        let schema = query(registry);
+        let schema = registry.resolve(&schema).clone();
+        let required = schema["required"].as_array().cloned().unwrap_or_default();
+        if let Some(properties) = schema["properties"].as_object() {
+            for (name, schema) in properties {
+                params.push(json!({
+                    "name": name,
+                    "in": "query",
+                    "required": required.contains(&json!(name)),
+                    "schema": schema,
+                }));
+            }
+        }
+    }
+
+    if op.idempotent {
+        params.push(json!({
+            "name": IDEMPOTENCY_KEY.as_str(),
+            "in": "header",
+            "required": false,
+            "description": "Retries with the same key, method, path and body replay \
+                            the first response instead of running again.",
+            "schema": { "type": "string", "minLength": 1, "maxLength": MAX_KEY_LEN },
+        }));
+    }
+    params
+}
+
+fn json_content(schema: Value) -> Value {
+    json!({ "application/json": { "schema": schema } })
+}
+
+fn error_ref(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/responses/{name}") })
+}
+
+fn responses(registry: &mut SchemaRegistry, op: &ApiOperation) -> Value {
+    let mut responses = Map::new();
+    let mut ok = match op.success {
+        Success::Envelope(None) => json!({
+            "description": "Success",
+            "content": json_content(json!({ "$ref": "#/components/schemas/SuccessResponse" })),
+        }),
+        Success::Envelope(Some(data)) => json!({
+            "description": "Success",
+            "content": json_content(json!({
+                "type": "object",
+                "required": ["status", "data"],
+                "properties": {
+                    "status": { "const": "success" },
+                    "data": data(registry),
+                },
+            })),
+        }),
+        Success::Json(f) => json!({
+            "description": "Success",
+            "content": json_content(f(registry)),
+        }),
+        Success::Metrics => json!({
+            "description": "Current metric values",
+            "content": { "text/plain": { "schema": { "type": "string" } } },
+        }),
+        Success::Download => json!({
+            "description": "File contents",
+            "content": {
+                "application/octet-stream": {
+                    "schema": { "type": "string", "contentMediaType": "application/octet-stream" },
+                },
+            },
+        }),
+    };
+    if op.idempotent {
+        ok["headers"] = json!({
+            IDEMPOTENT_REPLAYED.as_str(): {
+                "description": "`true` when this is a stored response being replayed",
+                "schema": { "type": "string", "enum": ["true"] },
+            },
+        });
+    }
+    responses.insert("200".into(), ok);
+    if op.four_eyes {
+        responses.insert(
+            "202".into(),
+            json!({
+                "description": "Held for approval by a second admin",
+                "content": json_content(json!({ "$ref": "#/components/schemas/PendingApproval" })),
+            }),
+        );
+    }
+
+    let mut error = |status: &str, name: &str| {
+        responses.insert(status.into(), error_ref(name));
+    };
+    if !op.param_names().is_empty() || op.query.is_some() || op.body.is_some() {
+        error("400", "BadRequest");
+    }
+    match op.access {
+        Access::Public => {}
+        Access::Scraper => error("401", "Unauthorized"),
+        _ => {
+            error("401", "Unauthorized");
+            error("403", "Forbidden");
+        }
+    }
+    if !op.param_names().is_empty() {
+        error("404", "NotFound");
+    }
+    if op.idempotent {
+        error("409", "Conflict");
+    }
+    error("500", "InternalError");
+    Value::Object(responses)
+}
+
+fn operation(registry: &mut SchemaRegistry, op: &ApiOperation) -> Value {
+    let mut doc = json!({
+        "operationId": op.operation_id(),
+        "summary": op.summary,
+        "tags": [op.tag()],
+    });
+    let params = parameters(registry, op);
+    if !params.is_empty() {
+        doc["parameters"] = json!(params);
+    }
+    match op.body {
+        Some(RequestBody::Json(f)) => {
+            doc["requestBody"] = json!({ "required": true, "content": json_content(f(registry)) });
+        }
+        Some(RequestBody::Multipart) => {
+            doc["requestBody"] = json!({
+                "required": true,
+                "content": {
+                    "multipart/form-data": {
+                        "schema": {
+                            "type": "object",
+                            "properties": {
+                                "file": { "type": "string", "contentMediaType": "application/octet-stream" },
+                            },
+                        },
+                    },
+                },
+            });
+        }
+        Some(RequestBody::Binary) => {
+            doc["requestBody"] = json!({
+                "required": true,
+                "content": {
+                    "application/octet-stream": {
+                        "schema": { "type": "string", "contentMediaType": "application/octet-stream" },
+                    },
+                },
+            });
+        }
+        None => {}
+    }
+    doc["responses"] = responses(registry, op);
+
+    match op.access {
+        Access::Public => {}
+        Access::User => doc["security"] = json!([{ "userBearer": [] }]),
+        Access::StepUp => {
+            doc["security"] = json!([{ "userBearer": [] }]);
+            doc["description"] = json!(format!(
+                "Requires a token stepped up with `POST /api/auth/2fa/step-up` \
+                 in the last {STEP_UP_WINDOW_MINUTES} minutes."
+            ));
+            doc["x-step-up"] = json!(true);
+        }
+        Access::Admin => doc["security"] = json!([{ "adminBearer": [] }]),
+        Access::Scraper => {
+            doc["security"] = json!([{}, { "metricsBearer": [] }]);
+            doc["description"] =
+                json!("Requires `METRICS_BEARER_TOKEN` as a bearer token when it is set.");
+        }
+        Access::Permission(name) => {
+            doc["security"] = json!([{ "adminBearer": [] }]);
+            doc["description"] = json!(format!("Requires the `{name}` permission."));
+            doc["x-required-permission"] = json!(name);
+        }
+    }
+    doc
+}
+
+/// The error responses, with status codes and bodies from [`ApiError`].
+fn error_responses() -> Map<String, Value> {
+    let samples = [
+        (
+            "BadRequest",
+            ApiError::BadRequest("Invalid UUID format".to_string()),
+        ),
+        ("Unauthorized", ApiError::Unauthorized),
+        (
+            "Forbidden",
+            ApiError::Forbidden("Insufficient permissions".to_string()),
+        ),
+        ("NotFound", ApiError::NotFound("Plan not found".to_string())),
+        (
+            "Conflict",
+            ApiError::Conflict("Idempotency-Key is already in use".to_string()),
+        ),
+        (
+            "TooManyRequests",
+            ApiError::TooManyRequests("Too many attempts".to_string()),
+        ),
+        (
+            "InternalError",
+            ApiError::Internal(anyhow::anyhow!("unexpected failure")),
+        ),
+    ];
+    samples
+        .into_iter()
+        .map(|(name, error)| {
+            let status = error.status_code();
+            let response = json!({
+                "description": status.canonical_reason().unwrap_or(name),
+                "x-status": status.as_u16(),
+                "content": {
+                    "application/json": {
+                        "schema": { "$ref": "#/components/schemas/Error" },
+                        "example": error.body(),
+                    },
+                },
+            });
+            (name.to_string(), response)
+        })
+        .collect()
+}
+
+/// Build the document.
+pub fn build_document() -> Value {
+    let mut registry = SchemaRegistry::default();
+    let mut paths = Map::new();
+    for op in operations() {
+        let doc = operation(&mut registry, &op);
+        let item = paths.entry(op.template()).or_insert_with(|| json!({}));
+        item[op.method] = doc;
+    }
+
+    let pending_action = registry.schema_of::<PendingAction>();
+    let mut schemas = registry.into_components();
+    schemas.insert(
+        "Error".into(),
+        json!({
+            "type": "object",
+            "required": ["error"],
+            "properties": { "error": { "type": "string" } },
+        }),
+    );
+    schemas.insert(
+        "SuccessResponse".into(),
+        json!({
+            "type": "object",
+            "required": ["status"],
+            "properties": {
+                "status": { "const": "success" },
+                "message": { "type": "string" },
+                "data": {},
+            },
+        }),
+    );
+    schemas.insert(
+        "PendingApproval".into(),
+        json!({
+            "type": "object",
+            "required": ["status", "message", "data"],
+            "properties": {
+                "status": { "const": "pending_approval" },
+                "message": { "type": "string" },
+                "data": pending_action,
+            },
+        }),
+    );
+
+    json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": "InheritX API",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+        "components": {
+            "schemas": schemas,
+            "responses": error_responses(),
+            "securitySchemes": {
+                "userBearer": {
+                    "type": "http",
+                    "scheme": "bearer",
+                    "bearerFormat": "JWT",
+                    "description": "User access token from `/api/auth/login`, \
+                                    `/api/auth/wallet-login`, `/api/auth/passkeys/login` \
+                                    or `/api/auth/refresh`.",
+                },
+                "metricsBearer": {
+                    "type": "http",
+                    "scheme": "bearer",
+                    "description": "The `METRICS_BEARER_TOKEN` configured for Prometheus scrapers.",
+                },
+                "adminBearer": {
+                    "type": "http",
+                    "scheme": "bearer",
+                    "bearerFormat": "JWT",
+                    "description": "Admin access token from `/admin/login` or \
+                                    `/admin/passkeys/login`. With `ADMIN_PASSKEYS_REQUIRED` \
+                                    set, it must come from a passkey sign-in.",
+                },
+            },
+        },
+    })
+}
+
+/// `GET /api/openapi.json`
+pub async fn openapi_document() -> impl IntoResponse {
+    static DOCUMENT: OnceLock<String> = OnceLock::new();
+    let body = DOCUMENT.get_or_init(|| build_document().to_string());
+    ([(header::CONTENT_TYPE, "application/json")], body.as_str())
+}
+
+/// Every operation the API serves. Keep in step with `create_app`.
+pub fn operations() -> Vec<ApiOperation> {
+    vec![
+        ApiOperation::get("/health", "Liveness check"),
+        ApiOperation::get("/health/db", "Database connectivity check"),
+        ApiOperation::get("/api/openapi.json", "Get this OpenAPI document").returns::<Value>(),
+        ApiOperation::get("/metrics", "Prometheus metrics").scraped(),
+        ApiOperation::post(
+            "/admin/login",
+            "Sign in as an admin with email and password",
+        )
+        .body::<LoginRequest>()
+        .returns::<LoginResponse>(),
+        ApiOperation::post("/api/auth/login", "Sign in with email and password")
+            .body::<LoginRequest>()
+            .returns::<LoginResponse>(),
+        ApiOperation::get(
+            "/api/auth/nonce/:wallet_address",
+            "Get a nonce for wallet sign-in",
+        )
+        .returns::<NonceResponse>(),
+        ApiOperation::post("/api/auth/wallet-login", "Sign in with a wallet signature")
+            .body::<WalletLoginRequest>()
+            .returns::<LoginResponse>(),
+        ApiOperation::post(
+            "/api/auth/passkeys/login/options",
+            "Start a passkey sign-in",
+        )
+        .body::<PasskeyLoginOptionsRequest>(),
+        ApiOperation::post("/api/auth/passkeys/login", "Sign in with a passkey")
+            .body::<AuthenticationCredential>()
+            .returns::<LoginResponse>(),
+        ApiOperation::post(
+            "/admin/passkeys/login/options",
+            "Start an admin passkey sign-in",
+        )
+        .body::<PasskeyLoginOptionsRequest>(),
+        ApiOperation::post(
+            "/admin/passkeys/login",
+            "Sign in as an admin with a passkey",
+        )
+        .body::<AuthenticationCredential>()
+        .returns::<LoginResponse>(),
+        ApiOperation::post(
+            "/api/auth/refresh",
+            "Exchange a refresh token for new tokens",
+        )
+        .body::<RefreshRequest>()
+        .returns::<LoginResponse>(),
+        ApiOperation::post("/api/auth/logout", "Sign out").user(),
+        ApiOperation::post("/admin/logout", "Sign out as an admin").admin(),
+        ApiOperation::post("/api/auth/2fa/totp/enroll", "Start TOTP enrollment").user(),
+        ApiOperation::post("/api/auth/2fa/totp/confirm", "Confirm TOTP enrollment")
+            .user()
+            .body::<TotpCodeRequest>(),
+        ApiOperation::delete("/api/auth/2fa/totp", "Disable TOTP").step_up(),
+        ApiOperation::post("/api/auth/2fa/recovery-codes", "Replace recovery codes").step_up(),
+        ApiOperation::get("/api/auth/2fa/status", "Get two-factor status").user(),
+        ApiOperation::post(
+            "/api/auth/2fa/step-up",
+            "Step up with a TOTP or recovery code",
+        )
+        .user()
+        .body::<TotpCodeRequest>(),
+        ApiOperation::post("/admin/2fa/totp/enroll", "Start admin TOTP enrollment").admin(),
+        ApiOperation::post("/admin/2fa/totp/confirm", "Confirm admin TOTP enrollment")
+            .admin()
+            .body::<TotpCodeRequest>(),
+        ApiOperation::post(
+            "/api/auth/passkeys/register/options",
+            "Start passkey registration",
+        )
+        .user(),
+        ApiOperation::post("/api/auth/passkeys/register", "Register a passkey")
+            .user()
+            .body::<RegistrationCredential>(),
+        ApiOperation::get("/api/auth/passkeys", "List passkeys").user(),
+        ApiOperation::delete("/api/auth/passkeys/:passkey_id", "Remove a passkey")
+            .user()
+            .path::<Uuid>(),
+        ApiOperation::post(
+            "/admin/passkeys/register/options",
+            "Start admin passkey registration",
+        )
+        .admin(),
+        ApiOperation::post("/admin/passkeys/register", "Register an admin passkey")
+            .admin()
+            .body::<RegistrationCredential>(),
+        ApiOperation::get("/admin/passkeys", "List admin passkeys").admin(),
+        ApiOperation::delete("/admin/passkeys/:passkey_id", "Remove an admin passkey")
+            .admin()
+            .path::<Uuid>(),
+        ApiOperation::get("/api/auth/sessions", "List the user's active sessions").user(),
+        ApiOperation::delete(
+            "/api/auth/sessions/:session_id",
+            "Sign out one of the user's devices",
+        )
+        .user()
+        .path::<Uuid>(),
+        ApiOperation::post(
+            "/api/admin/users/:user_id/sessions/revoke",
+            "Force a user to log out everywhere",
+        )
+        .permission::<perm::SessionsRevoke>()
+        .path::<Uuid>(),
+        ApiOperation::post(
+            "/api/admin/admins/:admin_id/sessions/revoke",
+            "Force another admin to log out everywhere",
+        )
+        .permission::<perm::SessionsRevoke>()
+        .path::<Uuid>(),
+        ApiOperation::get(
+            "/api/admin/permissions",
+            "List every permission a role can grant",
+        )
+        .permission::<perm::RolesManage>(),
+        ApiOperation::get("/api/admin/roles", "List admin roles and their permissions")
+            .permission::<perm::RolesManage>()
+            .data::<Vec<AdminRole>>(),
+        ApiOperation::post("/api/admin/roles", "Create a custom role")
+            .permission::<perm::RolesManage>()
+            .body::<CreateRoleRequest>()
+            .data::<AdminRole>(),
+        ApiOperation::put(
+            "/api/admin/roles/:role_name",
+            "Replace a role's permissions",
+        )
+        .permission::<perm::RolesManage>()
+        .path::<String>()
+        .body::<UpdateRoleRequest>()
+        .data::<AdminRole>(),
+        ApiOperation::delete(
+            "/api/admin/roles/:role_name",
+            "Delete an unused custom role",
+        )
+        .permission::<perm::RolesManage>()
+        .path::<String>(),
+        ApiOperation::put(
+            "/api/admin/admins/:admin_id/role",
+            "Assign a role to another admin",
+        )
+        .permission::<perm::RolesManage>()
+        .path::<Uuid>()
+        .body::<AssignRoleRequest>(),
+        ApiOperation::get(
+            "/api/admin/pending-actions",
+            "List pending actions the admin is allowed to decide",
+        )
+        .admin()
+        .query::<PendingActionFilters>()
+        .data::<Vec<PendingAction>>(),
+        ApiOperation::get(
+            "/api/admin/pending-actions/:action_id",
+            "Get a pending action and its audit trail",
+        )
+        .admin()
+        .path::<Uuid>(),
+        ApiOperation::post(
+            "/api/admin/pending-actions/:action_id/approve",
+            "Approve and execute another admin's pending action",
+        )
+        .admin()
+        .path::<Uuid>()
+        .data::<PendingAction>(),
+        ApiOperation::post(
+            "/api/admin/pending-actions/:action_id/reject",
+            "Reject a pending action, or withdraw one's own",
+        )
+        .admin()
+        .path::<Uuid>()
+        .body::<RejectPendingActionRequest>()
+        .data::<PendingAction>(),
+        ApiOperation::get("/api/plans/due-for-claim", "List plans due for claim")
+            .user()
+            .data::<Vec<DueForClaimPlan>>(),
+        ApiOperation::get(
+            "/api/plans/due-for-claim/:plan_id",
+            "Get a plan due for claim",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<Option<DueForClaimPlan>>(),
+        ApiOperation::post("/api/plans/:plan_id/claim", "Claim a plan as a beneficiary")
+            .step_up()
+            .path::<Uuid>()
+            .body::<ClaimPlanRequest>()
+            .data::<PlanWithBeneficiary>()
+            .idempotent(),
+        ApiOperation::get("/api/plans/:plan_id", "Get a plan")
+            .user()
+            .path::<Uuid>(),
+        ApiOperation::post("/api/plans", "Create a plan")
+            .step_up()
+            .body::<CreatePlanRequest>()
+            .data::<PlanWithBeneficiary>()
+            .idempotent(),
+        ApiOperation::post("/api/messages/legacy", "Create a legacy message")
+            .user()
+            .body::<CreateLegacyMessageRequest>()
+            .data::<LegacyMessage>(),
+        ApiOperation::get("/api/messages/legacy", "List legacy messages")
+            .user()
+            .data::<Vec<LegacyMessage>>(),
+        ApiOperation::get(
+            "/api/messages/legacy/vault/:vault_id",
+            "List a vault's legacy messages",
+        )
+        .user()
+        .path::<i64>()
+        .data::<Vec<LegacyMessage>>(),
+        ApiOperation::get(
+            "/api/messages/legacy/timeline",
+            "What each legacy message will deliver and when",
+        )
+        .user(),
+        ApiOperation::post(
+            "/api/messages/legacy/sealed",
+            "Create a sealed legacy message",
+        )
+        .user()
+        .body::<CreateSealedMessageRequest>()
+        .data::<LegacyMessage>(),
+        ApiOperation::post(
+            "/api/messages/legacy/:message_id/release",
+            "Release a sealed message to its recipient",
+        )
+        .path::<Uuid>()
+        .body::<ReleaseSealedMessageRequest>(),
+        ApiOperation::post(
+            "/api/messages/legacy/trustee",
+            "Create a trustee-released legacy message",
+        )
+        .user()
+        .body::<CreateTrusteeMessageRequest>()
+        .data::<LegacyMessage>(),
+        ApiOperation::get(
+            "/api/messages/legacy/:message_id/trustees",
+            "Get a message's trustee release status",
+        )
+        .user()
+        .path::<Uuid>(),
+        ApiOperation::post(
+            "/api/messages/legacy/:message_id/trustee-share",
+            "Fetch a trustee's key share",
+        )
+        .path::<Uuid>()
+        .body::<TrusteeShareRequest>(),
+        ApiOperation::post(
+            "/api/messages/legacy/:message_id/trustee-shares",
+            "Submit a trustee's key share",
+        )
+        .path::<Uuid>()
+        .body::<SubmitTrusteeShareRequest>(),
+        ApiOperation::post(
+            "/api/admin/users/:user_id/death-verification",
+            "Record a verified death event for a user",
+        )
+        .permission::<perm::DeathReview>()
+        .path::<Uuid>()
+        .body::<RecordDeathVerificationRequest>()
+        .data::<DeathVerification>(),
+        ApiOperation::post(
+            "/api/death-verification/cases",
+            "Open a death verification case as a beneficiary or executor",
+        )
+        .user()
+        .body::<OpenCaseRequest>()
+        .data::<DeathVerificationCase>(),
+        ApiOperation::get(
+            "/api/death-verification/cases",
+            "Cases the user opened or is a contact on",
+        )
+        .user()
+        .data::<Vec<DeathVerificationCase>>(),
+        ApiOperation::get(
+            "/api/death-verification/cases/:case_id",
+            "Get a death verification case",
+        )
+        .user()
+        .path::<Uuid>(),
+        ApiOperation::post(
+            "/api/death-verification/cases/:case_id/certificate",
+            "Upload the death certificate for a case",
+        )
+        .user()
+        .path::<Uuid>()
+        .multipart()
+        .data::<DeathCertificateRecord>(),
+        ApiOperation::post(
+            "/api/death-verification/cases/:case_id/objections",
+            "Object to a case during its objection window",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<ObjectionRequest>()
+        .data::<CaseObjection>(),
+        ApiOperation::get(
+            "/api/admin/death-verification/cases",
+            "List death verification cases, optionally by status",
+        )
+        .permission::<perm::DeathReview>()
+        .query::<CaseListFilters>()
+        .data::<Vec<DeathVerificationCase>>(),
+        ApiOperation::post(
+            "/api/admin/death-verification/cases/finalize",
+            "Approve cases whose objection window has ended now",
+        )
+        .permission::<perm::DeathReview>(),
+        ApiOperation::get(
+            "/api/admin/death-verification/cases/:case_id",
+            "Get a death verification case",
+        )
+        .permission::<perm::DeathReview>()
+        .path::<Uuid>(),
+        ApiOperation::get(
+            "/api/admin/death-verification/cases/:case_id/certificate",
+            "Download the decrypted death certificate for review",
+        )
+        .permission::<perm::DeathReview>()
+        .path::<Uuid>()
+        .download(),
+        ApiOperation::post(
+            "/api/admin/death-verification/cases/:case_id/reviews",
+            "Verify or reject a case's certificate",
+        )
+        .permission::<perm::DeathReview>()
+        .path::<Uuid>()
+        .body::<ReviewCaseRequest>(),
+        ApiOperation::post(
+            "/api/admin/death-verification/cases/:case_id/objections/:objection_id/resolve",
+            "Dismiss or uphold an objection",
+        )
+        .permission::<perm::DeathReview>()
+        .path::<(Uuid, Uuid)>()
+        .body::<ResolveObjectionRequest>(),
+        ApiOperation::post(
+            "/api/messages/recipient-keys",
+            "Register a recipient encryption key",
+        )
+        .body::<RegisterRecipientKeyRequest>()
+        .data::<RecipientKey>(),
+        ApiOperation::get(
+            "/api/messages/recipient-keys/:stellar_address",
+            "Get a recipient's active key",
+        )
+        .path::<String>()
+        .data::<RecipientKey>(),
+        ApiOperation::get("/api/admin/messages/keys", "List message encryption keys")
+            .permission::<perm::KeysRotate>()
+            .data::<Vec<MessageEncryptionKey>>(),
+        ApiOperation::post(
+            "/api/admin/messages/keys/rotate",
+            "Rotate the message encryption key",
+        )
+        .permission::<perm::KeysRotate>()
+        .four_eyes(),
+        ApiOperation::post(
+            "/api/admin/messages/delivery/process",
+            "Deliver due legacy messages now",
+        )
+        .permission::<perm::MessagesDeliver>(),
+        ApiOperation::post(
+            "/api/admin/messages/legacy/:message_id/deliver",
+            "Retry a message now instead of waiting for its backoff",
+        )
+        .permission::<perm::MessagesDeliver>()
+        .path::<Uuid>(),
+        ApiOperation::get("/api/messages/inbox", "List delivered messages")
+            .user()
+            .data::<Vec<InboxMessage>>(),
+        ApiOperation::get(
+            "/api/messages/inbox/:delivery_id",
+            "Open a delivered message",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<InboxMessage>(),
+        ApiOperation::get(
+            "/api/messages/deliveries/:delivery_id/read",
+            "Confirm a message was read",
+        )
+        .path::<Uuid>()
+        .query::<ReadConfirmationQuery>(),
+        ApiOperation::get("/api/admin/messages/audit", "List message access logs")
+            .permission::<perm::AuditRead>()
+            .query::<MessageAuditFilters>()
+            .data::<Vec<MessageAccessLog>>(),
+        ApiOperation::get(
+            "/api/admin/messages/audit/summary",
+            "Summarize message access",
+        )
+        .permission::<perm::AuditRead>()
+        .data::<MessageAuditSummary>(),
+        ApiOperation::get(
+            "/api/admin/messages/audit/search",
+            "Search message access logs",
+        )
+        .permission::<perm::AuditRead>()
+        .query::<SearchAuditParams>()
+        .data::<Vec<MessageAccessLog>>(),
+        ApiOperation::get(
+            "/api/messages/:message_id/audit",
+            "Get a message's access history",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<Vec<MessageAccessLog>>(),
+        ApiOperation::get(
+            "/api/messages/audit/my-activity",
+            "Get the user's message activity",
+        )
+        .user()
+        .data::<UserMessageActivity>(),
+        ApiOperation::get("/api/emergency/contacts", "List emergency contacts")
+            .user()
+            .data::<Vec<EmergencyContact>>(),
+        ApiOperation::post("/api/emergency/contacts", "Add an emergency contact")
+            .user()
+            .body::<CreateEmergencyContactRequest>()
+            .data::<EmergencyContact>(),
+        ApiOperation::put(
+            "/api/emergency/contacts/:contact_id",
+            "Update an emergency contact",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<UpdateEmergencyContactRequest>()
+        .data::<EmergencyContact>(),
+        ApiOperation::delete(
+            "/api/emergency/contacts/:contact_id",
+            "Delete an emergency contact",
+        )
+        .user()
+        .path::<Uuid>(),
+        ApiOperation::post("/api/emergency/access/grants", "Grant emergency access")
+            .step_up()
+            .body::<CreateEmergencyAccessGrantRequest>(),
+        ApiOperation::post(
+            "/api/emergency/access/grants/:grant_id/revoke",
+            "Revoke an emergency access grant",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<RevokeEmergencyAccessGrantRequest>(),
+        ApiOperation::get(
+            "/api/emergency/access/audit-logs",
+            "List emergency access audit logs",
+        )
+        .user()
+        .query::<EmergencyAccessAuditLogFilters>()
+        .data::<Vec<EmergencyAccessAuditLog>>(),
+        ApiOperation::get(
+            "/api/emergency/access/risk-alerts",
+            "List emergency access risk alerts",
+        )
+        .user()
+        .data::<Vec<EmergencyAccessRiskAlert>>(),
+        ApiOperation::get(
+            "/api/emergency/access/dashboard",
+            "Get the emergency access dashboard",
+        )
+        .user()
+        .data::<EmergencyAccessDashboardResponse>(),
+        ApiOperation::post(
+            "/api/emergency/access/sessions",
+            "Start an emergency access session",
+        )
+        .user()
+        .body::<StartSessionRequest>()
+        .data::<EmergencyAccessSession>(),
+        ApiOperation::get(
+            "/api/emergency/access/sessions",
+            "List active emergency access sessions",
+        )
+        .user()
+        .data::<Vec<EmergencyAccessSession>>(),
+        ApiOperation::put(
+            "/api/emergency/access/sessions/:session_id/heartbeat",
+            "Keep an emergency access session alive",
+        )
+        .user()
+        .path::<Uuid>(),
+        ApiOperation::put(
+            "/api/emergency/access/sessions/:session_id/end",
+            "End an emergency access session",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<EmergencyAccessSession>(),
+        ApiOperation::get("/api/emergency/break-glass", "List break-glass requests").user(),
+        ApiOperation::post("/api/emergency/break-glass", "Request break-glass access")
+            .user()
+            .body::<BreakGlassRequest>()
+            .data::<BreakGlassGrant>(),
+        ApiOperation::get(
+            "/api/emergency/break-glass/settings",
+            "Get break-glass settings",
+        )
+        .user()
+        .data::<BreakGlassSettings>(),
+        ApiOperation::put(
+            "/api/emergency/break-glass/settings",
+            "Update break-glass settings",
+        )
+        .user()
+        .body::<BreakGlassSettings>()
+        .data::<BreakGlassSettings>(),
+        ApiOperation::post(
+            "/api/emergency/break-glass/:grant_id/veto",
+            "Veto a break-glass request",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<VetoBreakGlassRequest>()
+        .data::<BreakGlassGrant>(),
+        ApiOperation::get(
+            "/api/emergency/break-glass/:grant_id/plan",
+            "View a plan through break-glass access",
+        )
+        .user()
+        .path::<Uuid>(),
+        ApiOperation::put(
+            "/api/emergency/break-glass/:grant_id/scope",
+            "Change a break-glass grant's scope",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<ScopeRequest>()
+        .data::<BreakGlassGrant>(),
+        ApiOperation::get(
+            "/api/emergency/break-glass/:grant_id/documents",
+            "List documents through break-glass access",
+        )
+        .user()
+        .path::<Uuid>(),
+        ApiOperation::get(
+            "/api/emergency/break-glass/:grant_id/messages",
+            "Read messages through break-glass access",
+        )
+        .user()
+        .path::<Uuid>(),
+        ApiOperation::post(
+            "/api/emergency/break-glass/:grant_id/bill-payments",
+            "Pay a bill through break-glass access",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<PayBillRequest>(),
+        ApiOperation::post(
+            "/api/emergency/break-glass/:grant_id/trigger-inheritance",
+            "Trigger inheritance through break-glass access",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<TriggerInheritanceRequest>()
+        .data::<InheritanceTrigger>(),
+        ApiOperation::post("/api/loans/simulate", "Simulate a loan")
+            .user()
+            .body::<LoanSimulationRequest>()
+            .data::<LoanSimulationResult>(),
+        ApiOperation::get("/api/loans/simulations", "List loan simulations")
+            .user()
+            .data::<Vec<LoanSimulationRecord>>(),
+        ApiOperation::get(
+            "/api/loans/simulations/:simulation_id",
+            "Get a loan simulation",
+        )
+        .user()
+        .path::<Uuid>(),
+        ApiOperation::get("/api/reputation", "Get the borrower reputation")
+            .user()
+            .data::<BorrowerReputation>(),
+        ApiOperation::post("/api/loans/lifecycle", "Open a loan")
+            .user()
+            .body::<CreateLoanRequest>()
+            .data::<LoanLifecycleRecord>(),
+        ApiOperation::get("/api/loans/lifecycle", "List loans")
+            .user()
+            .query::<LoanListFilters>()
+            .data::<Vec<LoanLifecycleRecord>>(),
+        ApiOperation::get("/api/loans/lifecycle/summary", "Count loans by status").user(),
+        ApiOperation::get("/api/loans/lifecycle/:id", "Get a loan")
+            .user()
+            .path::<Uuid>()
+            .data::<LoanLifecycleRecord>(),
+        ApiOperation::post("/api/loans/lifecycle/:id/repay", "Repay a loan")
+            .user()
+            .path::<Uuid>()
+            .body::<RepayRequest>()
+            .data::<LoanLifecycleRecord>()
+            .idempotent(),
+        ApiOperation::post(
+            "/api/admin/loans/lifecycle/:id/liquidate",
+            "Liquidate a loan",
+        )
+        .permission::<perm::LoansManage>()
+        .path::<Uuid>()
+        .data::<LoanLifecycleRecord>(),
+        ApiOperation::post(
+            "/api/admin/loans/lifecycle/mark-overdue",
+            "Mark loans past their due date overdue",
+        )
+        .permission::<perm::LoansManage>(),
+        ApiOperation::post(
+            "/api/loans/lifecycle/:id/collateral/add",
+            "Add collateral to a loan",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<AddCollateralRequest>()
+        .data::<LoanLifecycleRecord>()
+        .idempotent(),
+        ApiOperation::post(
+            "/api/loans/lifecycle/:id/collateral/remove",
+            "Withdraw collateral from a loan",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<RemoveCollateralRequest>()
+        .data::<LoanLifecycleRecord>()
+        .idempotent(),
+        ApiOperation::post(
+            "/api/loans/lifecycle/:id/collateral/swap",
+            "Swap a loan's collateral asset",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<SwapCollateralRequest>()
+        .data::<LoanLifecycleRecord>()
+        .idempotent(),
+        ApiOperation::get(
+            "/api/loans/lifecycle/:id/collateral/value",
+            "Value a loan's collateral",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<CollateralInfo>(),
+        ApiOperation::get(
+            "/api/loans/lifecycle/:id/collateral/max-withdrawable",
+            "Get how much collateral can be safely withdrawn",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<SafeWithdrawalInfo>(),
+        ApiOperation::get(
+            "/api/loans/lifecycle/:id/collateral/requirements",
+            "Get a loan's collateral requirements",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<CollateralRequirements>(),
+        ApiOperation::get(
+            "/api/admin/plans/due-for-claim",
+            "List all plans due for claim",
+        )
+        .permission::<perm::PlansRead>()
+        .data::<Vec<DueForClaimPlan>>(),
+        ApiOperation::get("/api/admin/kyc/:user_id", "Get a user's KYC status")
+            .permission::<perm::KycReview>()
+            .path::<Uuid>()
+            .returns::<KycRecord>(),
+        ApiOperation::post("/api/admin/kyc/approve", "Approve a user's KYC")
+            .permission::<perm::KycReview>()
+            .body::<KycUpdateRequest>()
+            .four_eyes(),
+        ApiOperation::post("/api/admin/kyc/reject", "Reject a user's KYC")
+            .permission::<perm::KycReview>()
+            .body::<KycUpdateRequest>()
+            .returns::<KycRecord>(),
+        ApiOperation::post("/api/admin/emergency/pause", "Pause a plan")
+            .permission::<perm::RiskPause>()
+            .body::<PausePlanRequest>()
+            .four_eyes(),
+        ApiOperation::post("/api/admin/emergency/unpause", "Unpause a plan")
+            .permission::<perm::RiskPause>()
+            .body::<UnpausePlanRequest>()
+            .four_eyes(),
+        ApiOperation::post(
+            "/api/admin/emergency/risk-override",
+            "Set a plan's risk override",
+        )
+        .permission::<perm::RiskPause>()
+        .body::<RiskOverrideRequest>()
+        .four_eyes(),
+        ApiOperation::get("/api/admin/emergency/paused-plans", "List paused plans")
+            .permission::<perm::RiskPause>()
+            .data::<Vec<PlanWithBeneficiary>>(),
+        ApiOperation::get(
+            "/api/admin/emergency/risk-override-plans",
+            "List plans with a risk override",
+        )
+        .permission::<perm::RiskPause>()
+        .data::<Vec<PlanWithBeneficiary>>(),
+        ApiOperation::post(
+            "/api/admin/emergency-access/grant",
+            "Grant emergency access to a plan",
+        )
+        .permission::<perm::EmergencyManage>()
+        .body::<GrantEmergencyAccessRequest>(),
+        ApiOperation::post(
+            "/api/admin/emergency-access/revoke",
+            "Revoke emergency access",
+        )
+        .permission::<perm::EmergencyManage>()
+        .body::<RevokeEmergencyAccessRequest>(),
+        ApiOperation::get(
+            "/api/admin/emergency-access/all",
+            "List emergency access records",
+        )
+        .permission::<perm::EmergencyManage>(),
+        ApiOperation::get(
+            "/api/admin/emergency-access/active-sessions",
+            "List active emergency access sessions",
+        )
+        .permission::<perm::EmergencyManage>(),
+        ApiOperation::get(
+            "/api/admin/emergency-access/plan/:plan_id",
+            "List a plan's emergency access records",
+        )
+        .permission::<perm::EmergencyManage>()
+        .path::<Uuid>(),
+        ApiOperation::post(
+            "/api/admin/stress-test/price-crash",
+            "Simulate a price crash",
+        )
+        .permission::<perm::RiskSimulate>()
+        .body::<PriceCrashRequest>(),
+        ApiOperation::post(
+            "/api/admin/stress-test/mass-default",
+            "Simulate a mass default",
+        )
+        .permission::<perm::RiskSimulate>(),
+        ApiOperation::post(
+            "/api/admin/stress-test/liquidity-drain",
+            "Simulate a liquidity drain",
+        )
+        .permission::<perm::RiskSimulate>()
+        .body::<LiquidityDrainRequest>(),
+        ApiOperation::post(
+            "/api/admin/governance/proposals",
+            "Create a governance proposal",
+        )
+        .permission::<perm::GovernanceManage>()
+        .body::<CreateProposalRequest>()
+        .returns::<Proposal>(),
+        ApiOperation::get("/api/governance/proposals", "List governance proposals")
+            .returns::<Vec<Proposal>>(),
+        ApiOperation::post(
+            "/api/governance/proposals/:id/vote",
+            "Vote on a governance proposal",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<VoteRequest>(),
+        ApiOperation::post(
+            "/api/admin/governance/parameters/update",
+            "Update a protocol parameter",
+        )
+        .permission::<perm::GovernanceManage>()
+        .body::<ParameterUpdateRequest>(),
+        ApiOperation::get(
+            "/api/admin/insurance-fund",
+            "Get the insurance fund dashboard",
+        )
+        .permission::<perm::InsuranceRead>(),
+        ApiOperation::get("/api/admin/insurance-funds", "List insurance funds")
+            .permission::<perm::InsuranceRead>(),
+        ApiOperation::get(
+            "/api/admin/insurance-fund/:fund_id",
+            "Get an insurance fund",
+        )
+        .permission::<perm::InsuranceRead>()
+        .path::<Uuid>(),
+        ApiOperation::get(
+            "/api/admin/insurance-fund/:fund_id/metrics",
+            "Get an insurance fund's metrics history",
+        )
+        .permission::<perm::InsuranceRead>()
+        .path::<Uuid>()
+        .query::<MetricsHistoryQuery>(),
+        ApiOperation::get(
+            "/api/admin/insurance-fund/:fund_id/transactions",
+            "List an insurance fund's transactions",
+        )
+        .permission::<perm::InsuranceRead>()
+        .path::<Uuid>()
+        .query::<TransactionsQuery>(),
+        ApiOperation::post(
+            "/api/admin/insurance-fund/:fund_id/claims",
+            "File an insurance claim",
+        )
+        .permission::<perm::InsurancePayout>()
+        .path::<Uuid>()
+        .body::<CreateInsuranceClaimRequest>(),
+        ApiOperation::get(
+            "/api/admin/insurance-fund/:fund_id/claims",
+            "List an insurance fund's claims",
+        )
+        .permission::<perm::InsuranceRead>()
+        .path::<Uuid>()
+        .query::<ClaimsQuery>(),
+        ApiOperation::get(
+            "/api/admin/insurance-fund/claims/:claim_id",
+            "Get an insurance claim",
+        )
+        .permission::<perm::InsuranceRead>()
+        .path::<Uuid>(),
+        ApiOperation::post(
+            "/api/admin/insurance-fund/claims/:claim_id/process",
+            "Approve or reject an insurance claim",
+        )
+        .permission::<perm::InsurancePayout>()
+        .path::<Uuid>()
+        .body::<ProcessInsuranceClaimRequest>(),
+        ApiOperation::post(
+            "/api/admin/insurance-fund/claims/:claim_id/payout",
+            "Pay out an approved insurance claim",
+        )
+        .permission::<perm::InsurancePayout>()
+        .path::<Uuid>()
+        .four_eyes()
+        .idempotent(),
+        ApiOperation::post(
+            "/api/plans/:plan_id/will/generate",
+            "Generate a will document",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<GenerateWillRequest>()
+        .data::<GeneratedWillDocument>(),
+        ApiOperation::get("/api/will/documents/:document_id", "Get a will document")
+            .user()
+            .path::<Uuid>()
+            .data::<GeneratedWillDocument>(),
+        ApiOperation::get(
+            "/api/plans/:plan_id/will/documents",
+            "List a plan's will documents",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<Vec<GeneratedWillDocument>>(),
+        ApiOperation::get("/api/plans/:plan_id/will/versions", "List will versions")
+            .user()
+            .path::<Uuid>()
+            .query::<PaginationParams>(),
+        ApiOperation::get(
+            "/api/plans/:plan_id/will/versions/active",
+            "Get the active will version",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<WillVersionSummary>(),
+        ApiOperation::get(
+            "/api/plans/:plan_id/will/versions/:version_number",
+            "Get a will version",
+        )
+        .user()
+        .path::<(Uuid, u32)>()
+        .data::<GeneratedWillDocument>(),
+        ApiOperation::put(
+            "/api/plans/:plan_id/will/versions/:version_number/finalize",
+            "Finalize a will version",
+        )
+        .user()
+        .path::<(Uuid, u32)>()
+        .data::<WillVersionSummary>(),
+        ApiOperation::post(
+            "/api/plans/:plan_id/beneficiaries/sync",
+            "Sync and validate a plan's beneficiaries",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<SyncBeneficiariesRequest>()
+        .data::<BeneficiarySyncResult>(),
+        ApiOperation::post(
+            "/api/plans/:plan_id/beneficiaries/contingent",
+            "Add a contingent beneficiary",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<AddContingentBeneficiaryRequest>()
+        .data::<ContingentBeneficiary>(),
+        ApiOperation::get(
+            "/api/plans/:plan_id/beneficiaries/contingent",
+            "List contingent beneficiaries",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<Vec<ContingentBeneficiary>>(),
+        ApiOperation::delete(
+            "/api/plans/:plan_id/beneficiaries/contingent/:beneficiary_id",
+            "Remove a contingent beneficiary",
+        )
+        .user()
+        .path::<(Uuid, Uuid)>(),
+        ApiOperation::post(
+            "/api/plans/:plan_id/beneficiaries/contingent/:beneficiary_id/promote",
+            "Promote a contingent beneficiary",
+        )
+        .user()
+        .path::<(Uuid, Uuid)>()
+        .body::<PromoteContingentRequest>()
+        .data::<ContingentBeneficiary>(),
+        ApiOperation::post(
+            "/api/plans/:plan_id/contingency/conditions",
+            "Set contingency conditions",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<SetContingencyConditionsRequest>(),
+        ApiOperation::get(
+            "/api/plans/:plan_id/contingency/config",
+            "Get the contingency configuration",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<ContingencyConfig>(),
+        ApiOperation::post(
+            "/api/will/documents/:document_id/sign/challenge",
+            "Create a will signing challenge",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<SigningChallengeRequest>()
+        .data::<SigningChallenge>(),
+        ApiOperation::post("/api/will/sign", "Submit a will signature")
+            .user()
+            .body::<SubmitSignatureRequest>()
+            .data::<WillSignatureRecord>(),
+        ApiOperation::get(
+            "/api/will/documents/:document_id/signatures",
+            "List a will's signatures",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<Vec<WillSignatureRecord>>(),
+        ApiOperation::post(
+            "/api/will/documents/:document_id/testators",
+            "Configure joint testators",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<ConfigureTestatorsRequest>()
+        .data::<JointWillStatus>(),
+        ApiOperation::get(
+            "/api/will/documents/:document_id/testators",
+            "Get joint will status",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<JointWillStatus>(),
+        ApiOperation::post("/api/will/documents/:document_id/revoke", "Revoke a will")
+            .user()
+            .path::<Uuid>()
+            .body::<RevokeWillRequest>()
+            .data::<JointWillStatus>(),
+        ApiOperation::post(
+            "/api/will/documents/:document_id/encrypt",
+            "Encrypt a will document",
+        )
+        .user()
+        .path::<Uuid>(),
+        ApiOperation::get(
+            "/api/will/documents/:document_id/decrypt",
+            "Decrypt a will document",
+        )
+        .user()
+        .path::<Uuid>(),
+        ApiOperation::post(
+            "/api/will/documents/:document_id/backup",
+            "Back up a will document",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<BackupRecord>(),
+        ApiOperation::get(
+            "/api/will/documents/:document_id/backups",
+            "List a will document's backups",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<Vec<BackupRecord>>(),
+        ApiOperation::post(
+            "/api/admin/documents/keys/rewrap",
+            "Rewrap document keys under the current key",
+        )
+        .permission::<perm::KeysRotate>(),
+        ApiOperation::post(
+            "/api/will/validate",
+            "Validate a will against jurisdiction rules",
+        )
+        .user()
+        .body::<ValidateWillRequest>()
+        .data::<ValidationResult>(),
+        ApiOperation::get("/api/will/jurisdictions", "List supported jurisdictions").user(),
+        ApiOperation::get(
+            "/api/will/jurisdictions/:jurisdiction",
+            "Get a jurisdiction's will rules",
+        )
+        .user()
+        .path::<String>(),
+        ApiOperation::post(
+            "/api/will/documents/:document_id/witnesses",
+            "Invite a witness",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<InviteWitnessRequest>()
+        .data::<WitnessRecord>(),
+        ApiOperation::get(
+            "/api/will/documents/:document_id/witnesses",
+            "List a will's witnesses",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<Vec<WitnessRecord>>(),
+        ApiOperation::get(
+            "/api/will/documents/:document_id/witnesses/status",
+            "Summarize witness signatures",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<WitnessStatusSummary>(),
+        ApiOperation::post("/api/will/witnesses/:witness_id/sign", "Sign as a witness")
+            .path::<Uuid>()
+            .body::<WitnessSignRequest>()
+            .data::<WitnessRecord>(),
+        ApiOperation::post(
+            "/api/will/witnesses/:witness_id/decline",
+            "Decline to witness",
+        )
+        .path::<Uuid>()
+        .data::<WitnessRecord>(),
+        ApiOperation::post(
+            "/api/will/witnesses/:witness_id/invitation",
+            "Email a witness invitation link",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<WitnessInvitation>(),
+        ApiOperation::get(
+            "/api/will/witness-invitations/:token",
+            "Open a witness invitation",
+        )
+        .path::<String>(),
+        ApiOperation::post(
+            "/api/will/witness-invitations/:token/attest",
+            "Attest as a witness",
+        )
+        .path::<String>()
+        .body::<WitnessAttestationRequest>()
+        .data::<WitnessAttestation>(),
+        ApiOperation::get(
+            "/api/will/documents/:document_id/attestations",
+            "List witness attestations",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<Vec<WitnessAttestation>>(),
+        ApiOperation::post(
+            "/api/will/documents/:document_id/notarizations",
+            "Request notarization",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<RequestNotarizationRequest>()
+        .data::<NotarizationSession>(),
+        ApiOperation::get(
+            "/api/will/documents/:document_id/notarizations",
+            "List a will's notarizations",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<Vec<NotarizationSession>>(),
+        ApiOperation::get("/api/notary/sessions", "List the notary's sessions")
+            .user()
+            .data::<Vec<NotarizationSession>>(),
+        ApiOperation::post(
+            "/api/notary/sessions/:session_id/identity",
+            "Record the signer's identity check",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<VerifyIdentityRequest>()
+        .data::<NotarizationSession>(),
+        ApiOperation::post(
+            "/api/notary/sessions/:session_id/seal",
+            "Seal a notarization",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<SealRequest>()
+        .data::<NotarizationSession>(),
+        ApiOperation::get("/api/notary/journal", "Get the notary journal")
+            .user()
+            .data::<Vec<NotaryJournalEntry>>(),
+        ApiOperation::post("/api/admin/notaries", "Register a notary")
+            .permission::<perm::NotaryManage>()
+            .body::<RegisterNotaryRequest>()
+            .data::<Notary>(),
+        ApiOperation::get("/api/admin/notaries", "List notaries")
+            .permission::<perm::NotaryManage>()
+            .data::<Vec<Notary>>(),
+        ApiOperation::get(
+            "/api/admin/notaries/:notary_id/journal/verify",
+            "Verify a notary's journal chain",
+        )
+        .permission::<perm::NotaryManage>()
+        .path::<Uuid>(),
+        ApiOperation::get(
+            "/api/will/documents/:document_id/verify",
+            "Verify a will document's integrity",
+        )
+        .path::<Uuid>()
+        .query::<PaginationParams>()
+        .data::<VerificationResult>(),
+        ApiOperation::post(
+            "/api/will/documents/:document_id/verify/hash",
+            "Check a hash against a will document",
+        )
+        .path::<Uuid>()
+        .body::<VerifyHashRequest>()
+        .data::<VerificationResult>(),
+        ApiOperation::post(
+            "/api/will/documents/:document_id/verify/content",
+            "Check content against a will document",
+        )
+        .path::<Uuid>()
+        .body::<VerifyContentRequest>()
+        .data::<VerificationResult>(),
+        ApiOperation::get(
+            "/api/plans/:plan_id/will/verify-all",
+            "Verify every version of a plan's will",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<Vec<VerificationResult>>(),
+        ApiOperation::get(
+            "/api/will/documents/:document_id/events",
+            "List a will document's events",
+        )
+        .user()
+        .path::<Uuid>(),
+        ApiOperation::get(
+            "/api/plans/:plan_id/will/events",
+            "List a plan's will events",
+        )
+        .user()
+        .path::<Uuid>(),
+        ApiOperation::get(
+            "/api/will/vaults/:vault_id/events",
+            "List a vault's will events",
+        )
+        .user()
+        .path::<String>(),
+        ApiOperation::get(
+            "/api/plans/:plan_id/will/events/stats",
+            "Get will event statistics for a plan",
+        )
+        .user()
+        .path::<Uuid>(),
+        ApiOperation::get(
+            "/api/will/documents/:document_id/download",
+            "Download a will document",
+        )
+        .user()
+        .path::<Uuid>()
+        .download(),
+        ApiOperation::get(
+            "/api/plans/:plan_id/will/documents/:version/download",
+            "Download a will version",
+        )
+        .user()
+        .path::<(Uuid, u32)>()
+        .download(),
+        ApiOperation::get("/api/admin/will/audit/logs", "List will audit logs")
+            .permission::<perm::AuditRead>()
+            .query::<AuditLogFilters>()
+            .data::<Vec<AuditLogEntry>>(),
+        ApiOperation::get(
+            "/api/admin/will/audit/statistics",
+            "Get will audit statistics",
+        )
+        .permission::<perm::AuditRead>()
+        .data::<AdminAuditStatistics>(),
+        ApiOperation::get(
+            "/api/admin/will/audit/event-types",
+            "List will audit event types",
+        )
+        .permission::<perm::AuditRead>()
+        .data::<Vec<String>>(),
+        ApiOperation::get("/api/admin/will/audit/search", "Search will audit logs")
+            .permission::<perm::AuditRead>()
+            .query::<SearchQuery>()
+            .data::<Vec<AuditLogEntry>>(),
+        ApiOperation::get("/api/admin/logs", "List admin action logs")
+            .permission::<perm::AuditRead>()
+            .data::<Vec<ActionLog>>(),
+        ApiOperation::get("/api/notifications", "List notifications")
+            .user()
+            .data::<Vec<Notification>>(),
+        ApiOperation::get(
+            "/api/admin/will/audit/user/:user_id",
+            "Get a user's will activity",
+        )
+        .permission::<perm::AuditRead>()
+        .path::<Uuid>()
+        .data::<UserActivitySummary>(),
+        ApiOperation::get(
+            "/api/will/audit/plan/:plan_id/summary",
+            "Summarize a plan's will audit log",
+        )
+        .user()
+        .path::<Uuid>()
+        .data::<AuditLogSummary>(),
+        ApiOperation::get(
+            "/api/will/audit/my-activity",
+            "Get the user's will activity",
+        )
+        .user()
+        .data::<UserActivitySummary>(),
+        ApiOperation::get(
+            "/api/admin/audit/:stream/:entry_id/verify",
+            "Prove an audit entry is unaltered and included in an anchored root",
+        )
+        .permission::<perm::AuditRead>()
+        .path::<(String, Uuid)>()
+        .data::<EntryVerification>(),
+        ApiOperation::post("/api/content/upload", "Upload legacy content")
+            .user()
+            .multipart()
+            .data::<LegacyContent>(),
+        ApiOperation::get("/api/content", "List legacy content")
+            .user()
+            .query::<ContentListFilters>()
+            .data::<Vec<LegacyContent>>(),
+        ApiOperation::get("/api/content/:content_id", "Get content by ID")
+            .user()
+            .path::<Uuid>()
+            .data::<LegacyContent>(),
+        ApiOperation::delete("/api/content/:content_id", "Delete content (soft delete)")
+            .user()
+            .path::<Uuid>(),
+        ApiOperation::get("/api/content/:content_id/download", "Download content")
+            .user()
+            .path::<Uuid>()
+            .download(),
+        ApiOperation::get(
+            "/api/content/:content_id/thumbnail",
+            "Download the preview thumbnail generated during inspection",
+        )
+        .user()
+        .path::<Uuid>()
+        .download(),
+        ApiOperation::get("/api/content/stats", "Get storage statistics")
+            .user()
+            .data::<StorageStats>(),
+        ApiOperation::post("/api/content/uploads", "Create a resumable upload session")
+            .user()
+            .body::<CreateUploadSessionRequest>(),
+        ApiOperation::get("/api/content/uploads/:upload_id", "Query upload progress")
+            .user()
+            .path::<Uuid>(),
+        ApiOperation::patch("/api/content/uploads/:upload_id", "Upload a chunk")
+            .user()
+            .path::<Uuid>()
+            .binary(),
+        ApiOperation::delete(
+            "/api/content/uploads/:upload_id",
+            "Abort a resumable upload",
+        )
+        .user()
+        .path::<Uuid>(),
+        ApiOperation::post(
+            "/api/content/uploads/:upload_id/finalize",
+            "Finalize a resumable upload",
+        )
+        .user()
+        .path::<Uuid>()
+        .body::<FinalizeUploadRequest>()
+        .data::<LegacyContent>(),
+        ApiOperation::get("/api/prices/:asset_code", "Get an asset's price").path::<String>(),
+        ApiOperation::get(
+            "/api/prices/:asset_code/history",
+            "Get an asset's price history",
+        )
+        .path::<String>(),
+        ApiOperation::get(
+            "/api/prices/:asset_code/valuation/:amount",
+            "Value an amount of an asset",
+        )
+        .path::<(String, String)>(),
+        ApiOperation::get("/api/plans/:plan_id/valuation", "Value a plan's assets").path::<Uuid>(),
+        ApiOperation::post("/api/admin/prices/register", "Register a price feed")
+            .permission::<perm::PricesManage>()
+            .body::<RegisterFeedRequest>(),
+        ApiOperation::post(
+            "/api/admin/prices/:asset_code/update",
+            "Set an asset's price",
+        )
+        .permission::<perm::PricesManage>()
+        .path::<String>()
+        .body::<UpdatePriceRequest>(),
+        ApiOperation::post(
+            "/api/admin/prices/:asset_code/fetch",
+            "Fetch an asset's price from its source",
+        )
+        .permission::<perm::PricesManage>()
+        .path::<String>(),
+        ApiOperation::get("/api/admin/prices/feeds", "List active price feeds")
+            .permission::<perm::PricesManage>(),
+        ApiOperation::get("/api/admin/analytics/dashboard", "Get the admin dashboard")
+            .permission::<perm::AnalyticsRead>(),
+        ApiOperation::get("/api/admin/analytics/overview", "Get a platform overview")
+            .permission::<perm::AnalyticsRead>(),
+        ApiOperation::get("/api/admin/analytics/users", "Get user growth metrics")
+            .permission::<perm::AnalyticsRead>(),
+        ApiOperation::get("/api/admin/analytics/plans", "Get plan statistics")
+            .permission::<perm::AnalyticsRead>()
+            .data::<PlanStatistics>(),
+        ApiOperation::get("/api/admin/analytics/claims", "Get claim statistics")
+            .permission::<perm::AnalyticsRead>(),
+        ApiOperation::get("/api/admin/analytics/revenue", "Get revenue metrics")
+            .permission::<perm::AnalyticsRead>()
+            .query::<RevenueRangeQuery>()
+            .data::<RevenueMetricsResponse>(),
+        ApiOperation::get("/api/admin/analytics/lending", "Get lending metrics")
+            .permission::<perm::AnalyticsRead>(),
+        ApiOperation::get("/api/admin/analytics/yield", "Get a yield summary")
+            .permission::<perm::AnalyticsRead>()
+            .query::<YieldSummaryQuery>()
+            .data::<YieldSummaryResponse>(),
+        ApiOperation::get(
+            "/api/admin/analytics/yield/history",
+            "Get yield earnings history",
+        )
+        .permission::<perm::AnalyticsRead>()
+        .query::<YieldHistoryQuery>()
+        .data::<EarningsHistoryResponse>(),
+        ApiOperation::get(
+            "/api/admin/analytics/emergency-access",
+            "Get emergency access metrics",
+        )
+        .permission::<perm::AnalyticsRead>()
+        .query::<RevenueRangeQuery>()
+        .data::<EmergencyAccessMetrics>(),
+        ApiOperation::get(
+            "/admin/metrics/overview",
+            "Get a platform overview (legacy path)",
+        )
+        .permission::<perm::AnalyticsRead>(),
+        ApiOperation::get(
+            "/admin/metrics/revenue",
+            "Get revenue metrics (legacy path)",
+        )
+        .permission::<perm::AnalyticsRead>()
+        .query::<RevenueRangeQuery>(),
+        ApiOperation::get(
+            "/admin/metrics/claims",
+            "Get claim statistics (legacy path)",
+        )
+        .permission::<perm::AnalyticsRead>(),
+        ApiOperation::get(
+            "/admin/metrics/users",
+            "Get user growth metrics (legacy path)",
+        )
+        .permission::<perm::AnalyticsRead>(),
+        ApiOperation::get(
+            "/api/admin/metrics/plans",
+            "Get plan statistics (legacy path)",
+        )
+        .permission::<perm::AnalyticsRead>()
+        .data::<PlanStatistics>(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schemas_come_from_the_types() {
+        let document = build_document();
+        let schemas = &document["components"]["schemas"];
+
+        let plan = &schemas["CreatePlanRequest"];
+        assert_eq!(
+            plan["properties"]["fee"]["type"],
+            json!(["string", "number"])
+        );
+        assert!(plan["properties"]["fee"]["pattern"].is_string());
+        assert_eq!(
+            plan["properties"]["description"]["type"],
+            json!(["string", "null"])
+        );
+        let required = plan["required"].as_array().unwrap();
+        assert!(required.contains(&json!("title")));
+        assert!(!required.contains(&json!("description")));
+
+        // Renamed fields come through as serialized.
+        let loan = &schemas["LoanLifecycleRecord"]["properties"];
+        assert_eq!(loan["id"]["format"], "uuid");
+        assert_eq!(loan["dueDate"]["format"], "date-time");
+        assert_eq!(loan["interestRateBps"]["format"], "int32");
+        assert_eq!(
+            loan["repaidAt"]["type"],
+            json!(["string", "null"]),
+            "{loan}"
+        );
+
+        // Flattened fields are merged into the parent.
+        let break_glass = &schemas["BreakGlassRequest"];
+        assert_eq!(break_glass["properties"]["plan_id"]["format"], "uuid");
+        assert_eq!(
+            break_glass["properties"]["permissions"]["items"]["$ref"],
+            "#/components/schemas/EmergencyPermission"
+        );
+
+        let versions =
+            &document["paths"]["/api/plans/{plan_id}/will/versions/{version_number}"]["get"];
+        let params = versions["parameters"].as_array().unwrap();
+        assert_eq!(params[0]["name"], "plan_id");
+        assert_eq!(params[0]["schema"]["format"], "uuid");
+        assert_eq!(params[1]["name"], "version_number");
+        assert_eq!(params[1]["schema"]["format"], "uint32");
+        assert_eq!(
+            document["paths"]["/api/plans/{plan_id}/will/versions/active"]["get"]["responses"]
+                ["200"]["content"]["application/json"]["schema"]["properties"]["data"]["$ref"],
+            "#/components/schemas/WillVersionSummary"
+        );
+    }
+
+    #[test]
+    fn operations_describe_auth_and_errors() {
+        let document = build_document();
+        let claim = &document["paths"]["/api/plans/{plan_id}/claim"]["post"];
+        assert_eq!(claim["security"], json!([{ "userBearer": [] }]));
+        assert_eq!(
+            claim["responses"]["409"]["$ref"],
+            "#/components/responses/Conflict"
+        );
+        assert!(claim["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|p| p["in"] == "header" && p["name"] == "idempotency-key"));
+
+        let verify = &document["paths"]["/api/admin/audit/{stream}/{entry_id}/verify"]["get"];
+        assert_eq!(verify["security"], json!([{ "adminBearer": [] }]));
+        assert_eq!(verify["x-required-permission"], "audit:read");
+        assert!(document["paths"]["/health"]["get"]
+            .get("security")
+            .is_none());
+
+        // Error responses carry the status and body `ApiError` produces.
+        let not_found = ApiError::NotFound("Plan not found".to_string());
+        let response = &document["components"]["responses"]["NotFound"];
+        assert_eq!(response["x-status"], not_found.status_code().as_u16());
+        assert_eq!(
+            response["content"]["application/json"]["example"],
+            not_found.body()
+        );
+    }
+}
@@ -1,10 +1,11 @@
+use crate::admin_rbac::{perm, RequirePermission};
 use crate::api_error::ApiError;
-use crate::auth::AuthenticatedAdmin;
 use crate::notifications::AuditLogService;
 use crate::price_feed::{PriceFeedService, PriceFeedSource};
 use axum::extract::{Path, State};
 use axum::Json;
 use rust_decimal::Decimal;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use sqlx::PgPool;
@@ -34,14 +35,14 @@ pub struct PricePoint {
     pub source: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct RegisterFeedRequest {
     pub asset_code: String,
     pub source: String,
     pub feed_id: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct UpdatePriceRequest {
     pub price: Decimal,
 }
@@ -113,7 +114,7 @@ pub async fn get_price_history(
 /// Register a new price feed (admin only)
 pub async fn register_price_feed(
     State((_db, price_service)): State<(PgPool, Arc<dyn PriceFeedService>)>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::PricesManage>,
     Json(req): Json<RegisterFeedRequest>,
 ) -> Result<Json<Value>, ApiError> {
     let source = match req.source.to_lowercase().as_str() {
@@ -164,7 +165,7 @@ pub async fn register_price_feed(
 /// Update price for an asset (admin only)
 pub async fn update_price(
     State((_db, price_service)): State<(PgPool, Arc<dyn PriceFeedService>)>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::PricesManage>,
     Path(asset_code): Path<String>,
     Json(req): Json<UpdatePriceRequest>,
 ) -> Result<Json<Value>, ApiError> {
@@ -285,7 +286,7 @@ pub async fn get_plan_valuation(
 /// Get all active price feeds (admin only)
 pub async fn get_active_feeds(
     State((_db, price_service)): State<(PgPool, Arc<dyn PriceFeedService>)>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::PricesManage>,
 ) -> Result<Json<Value>, ApiError> {
     let feeds = price_service.get_active_feeds().await?;
 
@@ -299,7 +300,7 @@ pub async fn get_active_feeds(
 /// Fetch price from external source and update database (admin only)
 pub async fn fetch_and_update_price(
     State((_db, price_service)): State<(PgPool, Arc<dyn PriceFeedService>)>,
-    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    RequirePermission(_admin, _): RequirePermission<perm::PricesManage>,
     Path(asset_code): Path<String>,
 ) -> Result<Json<Value>, ApiError> {
     let asset_price = price_service.fetch_and_update_price(&asset_code).await?;
@@ -2,17 +2,20 @@ use crate::api_error::ApiError;
 use crate::events::EventType;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::{PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BorrowerReputation {
     pub user_id: Uuid,
     pub score: i32,
     #[serde(with = "rust_decimal::serde::str")]
+    #[schemars(with = "String")]
     pub total_borrowed: Decimal,
     #[serde(with = "rust_decimal::serde::str")]
+    #[schemars(with = "String")]
     pub total_repaid: Decimal,
     pub liquidation_count: i32,
     pub created_at: DateTime<Utc>,
@@ -0,0 +1,533 @@
+//! Resumable chunked uploads for large legacy content.
+//!
+//! Follows the shape of the tus protocol: a client creates an upload session
+//! declaring the total size, PATCHes chunks at explicit offsets with a
+//! per-chunk `Upload-Checksum`, can query the current offset after a dropped
+//! connection, and finalizes with the SHA-256 of the whole file. Chunks are
+//! staged in object storage under `uploads/{session_id}/...` and assembled
+//! into a content-addressed object on finalize.
+//!
+//! Sessions that are not finalized before `expires_at` are garbage-collected
+//! by [`UploadSessionCleanupService`]. Space for in-flight sessions is
+//! reserved against the per-user quota from the moment they are created.
+
+use crate::api_error::ApiError;
+use crate::legacy_content::{LegacyContent, LegacyContentService, UploadMetadata};
+use crate::object_storage::{ObjectStorageService, ObjectStore};
+use base64::Engine as _;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Largest file accepted through a resumable upload session (2 GiB).
+pub const MAX_RESUMABLE_FILE_SIZE: i64 = 2 * 1024 * 1024 * 1024;
+/// Largest single chunk accepted by a PATCH request (16 MiB).
+pub const MAX_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+/// Default per-user storage quota when `LEGACY_CONTENT_QUOTA_BYTES` is unset (10 GiB).
+pub const DEFAULT_USER_QUOTA_BYTES: i64 = 10 * 1024 * 1024 * 1024;
+/// Sessions expire this long after their last received chunk.
+const SESSION_TTL_HOURS: i32 = 24;
+const CLEANUP_BATCH_SIZE: i64 = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct UploadSession {
+    pub id: Uuid,
+    pub owner_user_id: Uuid,
+    pub original_filename: String,
+    pub content_type: String,
+    pub description: Option<String>,
+    pub total_size: i64,
+    pub upload_offset: i64,
+    pub status: String,
+    pub content_id: Option<Uuid>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl UploadSession {
+    pub fn progress_percent(&self) -> f64 {
+        if self.total_size == 0 {
+            return 0.0;
+        }
+        (self.upload_offset as f64 / self.total_size as f64 * 10_000.0).round() / 100.0
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CreateUploadSessionRequest {
+    pub original_filename: String,
+    pub content_type: String,
+    pub total_size: i64,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FinalizeUploadRequest {
+    /// Hex SHA-256 of the complete file.
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UploadCleanupResult {
+    pub sessions_expired: usize,
+    pub chunks_deleted: usize,
+}
+
+const SESSION_COLUMNS: &str = "id, owner_user_id, original_filename, content_type, description, \
+     total_size, upload_offset, status, content_id, expires_at, created_at, updated_at";
+
+fn chunk_key(session_id: Uuid, offset: i64) -> String {
+    // Zero-padded so chunk keys sort in offset order.
+    format!("uploads/{}/{:020}", session_id, offset)
+}
+
+/// Parse a tus `Upload-Checksum` value (`sha256 <base64 digest>`).
+pub fn parse_upload_checksum(header: &str) -> Result<Vec<u8>, ApiError> {
+    let (algorithm, digest) = header
+        .trim()
+        .split_once(' ')
+        .ok_or_else(|| ApiError::BadRequest("Malformed Upload-Checksum header".to_string()))?;
+    if !algorithm.eq_ignore_ascii_case("sha256") {
+        return Err(ApiError::BadRequest(format!(
+            "Unsupported checksum algorithm: {algorithm}"
+        )));
+    }
+    let digest = base64::engine::general_purpose::STANDARD
+        .decode(digest.trim())
+        .map_err(|_| ApiError::BadRequest("Upload-Checksum is not valid base64".to_string()))?;
+    if digest.len() != 32 {
+        return Err(ApiError::BadRequest(
+            "Upload-Checksum must be a SHA-256 digest".to_string(),
+        ));
+    }
+    Ok(digest)
+}
+
+/// Per-user quota, configurable via `LEGACY_CONTENT_QUOTA_BYTES`.
+pub fn user_quota_bytes() -> i64 {
+    std::env::var("LEGACY_CONTENT_QUOTA_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_USER_QUOTA_BYTES)
+}
+
+pub struct ResumableUploadService;
+
+impl ResumableUploadService {
+    /// Open an upload session after checking the declared size against the
+    /// user's remaining quota.
+    pub async fn create_session(
+        db: &PgPool,
+        owner_user_id: Uuid,
+        req: &CreateUploadSessionRequest,
+    ) -> Result<UploadSession, ApiError> {
+        LegacyContentService::validate_content_type(&req.content_type)?;
+        if req.original_filename.trim().is_empty() {
+            return Err(ApiError::BadRequest("File name is required".to_string()));
+        }
+        if req.total_size <= 0 {
+            return Err(ApiError::BadRequest("File is empty".to_string()));
+        }
+        if req.total_size > MAX_RESUMABLE_FILE_SIZE {
+            return Err(ApiError::BadRequest(format!(
+                "File size {} bytes exceeds maximum resumable upload size of {} bytes",
+                req.total_size, MAX_RESUMABLE_FILE_SIZE
+            )));
+        }
+
+        let stats = LegacyContentService::get_user_storage_stats(db, owner_user_id).await?;
+        let reserved: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(SUM(total_size), 0)::bigint FROM upload_sessions \
+             WHERE owner_user_id = $1 AND status = 'active' AND expires_at > NOW()",
+        )
+        .bind(owner_user_id)
+        .fetch_one(db)
+        .await?;
+
+        let quota = user_quota_bytes();
+        if stats.total_size + reserved + req.total_size > quota {
+            return Err(ApiError::Forbidden(format!(
+                "Storage quota exceeded: {} of {} bytes used or reserved",
+                stats.total_size + reserved,
+                quota
+            )));
+        }
+
+        let session = sqlx::query_as::<_, UploadSession>(&format!(
+            "INSERT INTO upload_sessions \
+             (owner_user_id, original_filename, content_type, description, total_size, expires_at) \
+             VALUES ($1, $2, $3, $4, $5, NOW() + make_interval(hours => $6)) \
+             RETURNING {SESSION_COLUMNS}"
+        ))
+        .bind(owner_user_id)
+        .bind(&req.original_filename)
+        .bind(&req.content_type)
+        .bind(&req.description)
+        .bind(req.total_size)
+        .bind(SESSION_TTL_HOURS)
+        .fetch_one(db)
+        .await?;
+
+        Ok(session)
+    }
+
+    /// Current state of a session, used by clients to resume after a failure.
+    pub async fn get_session(
+        db: &PgPool,
+        owner_user_id: Uuid,
+        session_id: Uuid,
+    ) -> Result<UploadSession, ApiError> {
+        sqlx::query_as::<_, UploadSession>(&format!(
+            "SELECT {SESSION_COLUMNS} FROM upload_sessions WHERE id = $1 AND owner_user_id = $2"
+        ))
+        .bind(session_id)
+        .bind(owner_user_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Upload session not found".to_string()))
+    }
+
+    /// Append a chunk at `offset`. The offset must equal the bytes already
+    /// received, and the chunk must match its declared SHA-256.
+    pub async fn append_chunk(
+        db: &PgPool,
+        store: &dyn ObjectStore,
+        owner_user_id: Uuid,
+        session_id: Uuid,
+        offset: i64,
+        checksum: &[u8],
+        bytes: &[u8],
+    ) -> Result<UploadSession, ApiError> {
+        if bytes.is_empty() {
+            return Err(ApiError::BadRequest("Chunk is empty".to_string()));
+        }
+        if bytes.len() > MAX_CHUNK_SIZE {
+            return Err(ApiError::BadRequest(format!(
+                "Chunk exceeds maximum size of {} bytes",
+                MAX_CHUNK_SIZE
+            )));
+        }
+        let digest = Sha256::digest(bytes);
+        if digest.as_slice() != checksum {
+            return Err(ApiError::BadRequest(
+                "Chunk checksum does not match Upload-Checksum".to_string(),
+            ));
+        }
+
+        let mut tx = db.begin().await?;
+        // Row lock serializes concurrent PATCHes against the same session.
+        let session = Self::lock_active_session(&mut tx, owner_user_id, session_id).await?;
+
+        if offset != session.upload_offset {
+            return Err(ApiError::Conflict(format!(
+                "Upload-Offset {} does not match current offset {}",
+                offset, session.upload_offset
+            )));
+        }
+        let new_offset = offset + bytes.len() as i64;
+        if new_offset > session.total_size {
+            return Err(ApiError::BadRequest(format!(
+                "Chunk would exceed declared upload size of {} bytes",
+                session.total_size
+            )));
+        }
+
+        let key = chunk_key(session_id, offset);
+        store.put(&key, bytes).await?;
+
+        sqlx::query(
+            "INSERT INTO upload_chunks (session_id, chunk_offset, size_bytes, sha256, storage_key) \
+             VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(session_id)
+        .bind(offset)
+        .bind(bytes.len() as i64)
+        .bind(hex::encode(digest))
+        .bind(&key)
+        .execute(&mut *tx)
+        .await?;
+
+        let session = sqlx::query_as::<_, UploadSession>(&format!(
+            "UPDATE upload_sessions \
+             SET upload_offset = $1, expires_at = NOW() + make_interval(hours => $2), \
+                 updated_at = NOW() \
+             WHERE id = $3 \
+             RETURNING {SESSION_COLUMNS}"
+        ))
+        .bind(new_offset)
+        .bind(SESSION_TTL_HOURS)
+        .bind(session_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(session)
+    }
+
+    /// Assemble the received chunks, check them against the whole-file hash
+    /// and register the result as legacy content.
+    pub async fn finalize(
+        db: &PgPool,
+        store: &dyn ObjectStore,
+        owner_user_id: Uuid,
+        session_id: Uuid,
+        expected_sha256: &str,
+    ) -> Result<LegacyContent, ApiError> {
+        let expected_sha256 = expected_sha256.trim().to_ascii_lowercase();
+
+        let mut tx = db.begin().await?;
+        let session = Self::lock_active_session(&mut tx, owner_user_id, session_id).await?;
+
+        if session.upload_offset != session.total_size {
+            return Err(ApiError::Conflict(format!(
+                "Upload incomplete: {} of {} bytes received",
+                session.upload_offset, session.total_size
+            )));
+        }
+
+        let chunks = Self::chunks(&mut *tx, session_id).await?;
+        let mut assembled = Vec::with_capacity(session.total_size as usize);
+        for chunk in &chunks {
+            let bytes =
+                ObjectStorageService::fetch_verified(store, &chunk.storage_key, &chunk.sha256)
+                    .await?;
+            assembled.extend_from_slice(&bytes);
+        }
+
+        let actual_sha256 = LegacyContentService::calculate_file_hash(&assembled);
+        if actual_sha256 != expected_sha256 {
+            // The staged bytes can never produce the declared file; discard them.
+            sqlx::query(
+                "UPDATE upload_sessions SET status = 'failed', updated_at = NOW() WHERE id = $1",
+            )
+            .bind(session_id)
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            Self::delete_chunks(db, store, session_id).await?;
+            return Err(ApiError::BadRequest(format!(
+                "File hash mismatch: expected {}, received {}",
+                expected_sha256, actual_sha256
+            )));
+        }
+
+        let object = ObjectStorageService::store(db, store, &assembled).await?;
+        let metadata = UploadMetadata {
+            original_filename: session.original_filename,
+            content_type: session.content_type,
+            file_size: assembled.len(),
+            description: session.description,
+        };
+        let content = LegacyContentService::create_content_record(
+            db,
+            owner_user_id,
+            &metadata,
+            object.object_key,
+            object.sha256,
+        )
+        .await?;
+
+        sqlx::query(
+            "UPDATE upload_sessions SET status = 'completed', content_id = $1, updated_at = NOW() \
+             WHERE id = $2",
+        )
+        .bind(content.id)
+        .bind(session_id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Self::delete_chunks(db, store, session_id).await?;
+        Ok(content)
+    }
+
+    /// Abandon a session and discard its staged chunks.
+    pub async fn abort(
+        db: &PgPool,
+        store: &dyn ObjectStore,
+        owner_user_id: Uuid,
+        session_id: Uuid,
+    ) -> Result<(), ApiError> {
+        let mut tx = db.begin().await?;
+        Self::lock_active_session(&mut tx, owner_user_id, session_id).await?;
+        sqlx::query(
+            "UPDATE upload_sessions SET status = 'aborted', updated_at = NOW() WHERE id = $1",
+        )
+        .bind(session_id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Self::delete_chunks(db, store, session_id).await?;
+        Ok(())
+    }
+
+    async fn lock_active_session(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        owner_user_id: Uuid,
+        session_id: Uuid,
+    ) -> Result<UploadSession, ApiError> {
+        let session = sqlx::query_as::<_, UploadSession>(&format!(
+            "SELECT {SESSION_COLUMNS} FROM upload_sessions \
+             WHERE id = $1 AND owner_user_id = $2 FOR UPDATE"
+        ))
+        .bind(session_id)
+        .bind(owner_user_id)
+        .fetch_optional(&mut **tx)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Upload session not found".to_string()))?;
+
+        if session.status != "active" {
+            return Err(ApiError::Conflict(format!(
+                "Upload session is {}",
+                session.status
+            )));
+        }
+        if session.expires_at <= Utc::now() {
+            return Err(ApiError::NotFound("Upload session has expired".to_string()));
+        }
+        Ok(session)
+    }
+
+    async fn chunks<'e, E>(executor: E, session_id: Uuid) -> Result<Vec<ChunkRow>, ApiError>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
+        Ok(sqlx::query_as::<_, ChunkRow>(
+            "SELECT storage_key, sha256 FROM upload_chunks \
+             WHERE session_id = $1 ORDER BY chunk_offset ASC",
+        )
+        .bind(session_id)
+        .fetch_all(executor)
+        .await?)
+    }
+
+    async fn delete_chunks(
+        db: &PgPool,
+        store: &dyn ObjectStore,
+        session_id: Uuid,
+    ) -> Result<usize, ApiError> {
+        let chunks = Self::chunks(db, session_id).await?;
+        for chunk in &chunks {
+            store.delete(&chunk.storage_key).await?;
+        }
+        sqlx::query("DELETE FROM upload_chunks WHERE session_id = $1")
+            .bind(session_id)
+            .execute(db)
+            .await?;
+        Ok(chunks.len())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ChunkRow {
+    storage_key: String,
+    sha256: String,
+}
+
+/// Background job that expires abandoned upload sessions and deletes the
+/// chunks they staged in object storage.
+pub struct UploadSessionCleanupService {
+    db: PgPool,
+    store: Arc<dyn ObjectStore>,
+}
+
+impl UploadSessionCleanupService {
+    pub fn new(db: PgPool, store: Arc<dyn ObjectStore>) -> Self {
+        Self { db, store }
+    }
+
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(600));
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.collect_expired().await {
+                    error!("Upload session cleanup error: {}", e);
+                }
+            }
+        });
+    }
+
+    pub async fn collect_expired(&self) -> Result<UploadCleanupResult, ApiError> {
+        let expired: Vec<Uuid> = sqlx::query_scalar(
+            "UPDATE upload_sessions SET status = 'expired', updated_at = NOW() \
+             WHERE id IN ( \
+                 SELECT id FROM upload_sessions \
+                 WHERE status = 'active' AND expires_at <= NOW() \
+                 ORDER BY expires_at ASC LIMIT $1 \
+                 FOR UPDATE SKIP LOCKED) \
+             RETURNING id",
+        )
+        .bind(CLEANUP_BATCH_SIZE)
+        .fetch_all(&self.db)
+        .await?;
+
+        let mut result = UploadCleanupResult {
+            sessions_expired: expired.len(),
+            ..Default::default()
+        };
+        for session_id in expired {
+            match ResumableUploadService::delete_chunks(&self.db, self.store.as_ref(), session_id)
+                .await
+            {
+                Ok(deleted) => result.chunks_deleted += deleted,
+                Err(e) => warn!(
+                    "Failed to delete chunks of expired upload {}: {}",
+                    session_id, e
+                ),
+            }
+        }
+
+        if result.sessions_expired > 0 {
+            info!(
+                "Expired {} upload sessions and deleted {} staged chunks",
+                result.sessions_expired, result.chunks_deleted
+            );
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_upload_checksum() {
+        let digest = Sha256::digest(b"chunk");
+        let header = format!(
+            "sha256 {}",
+            base64::engine::general_purpose::STANDARD.encode(digest)
+        );
+        assert_eq!(parse_upload_checksum(&header).unwrap(), digest.to_vec());
+    }
+
+    #[test]
+    fn test_parse_upload_checksum_rejects_bad_values() {
+        assert!(parse_upload_checksum("sha256").is_err());
+        assert!(parse_upload_checksum("md5 AAAA").is_err());
+        assert!(parse_upload_checksum("sha256 not-base64!").is_err());
+        assert!(parse_upload_checksum("sha256 AAAA").is_err());
+    }
+
+    #[test]
+    fn test_chunk_keys_sort_by_offset() {
+        let session = Uuid::new_v4();
+        let mut keys = [
+            chunk_key(session, 10_485_760),
+            chunk_key(session, 0),
+            chunk_key(session, 5_242_880),
+        ];
+        keys.sort();
+        assert_eq!(keys[0], chunk_key(session, 0));
+        assert_eq!(keys[2], chunk_key(session, 10_485_760));
+    }
+}
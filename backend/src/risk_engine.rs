@@ -1,4 +1,5 @@
 use crate::api_error::ApiError;
+use crate::metrics::observe_job;
 use crate::notifications::{
     audit_action, entity_type, notif_type, AuditLogService, NotificationService,
 };
@@ -33,7 +34,7 @@ impl RiskEngine {
             let mut interval = tokio::time::interval(Duration::from_secs(60));
             loop {
                 interval.tick().await;
-                if let Err(e) = self.check_all_loans().await {
+                if let Err(e) = observe_job("risk_engine", self.check_all_loans()).await {
                     error!("Risk Engine error checking loans: {}", e);
                 }
             }
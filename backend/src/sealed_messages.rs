@@ -0,0 +1,627 @@
+//! End-to-end encrypted legacy messages.
+//!
+//! Beneficiaries register an X25519 message key bound to their Stellar address
+//! by an Ed25519 signature from that address. Messages are sealed to the key
+//! either client-side or, for clients that cannot, server-side before the
+//! plaintext is discarded. Either way the server only ever stores and releases
+//! ciphertext: delivery marks the message as released without decrypting it,
+//! and the recipient fetches the sealed envelope with a signed request and
+//! opens it with [`RecipientKeyPair::open`].
+//!
+//! Sealing is ECIES over X25519: an ephemeral key agreement, HKDF-SHA256 bound
+//! to both public keys, and AES-256-GCM with the recipient's Stellar address as
+//! associated data. Recipients can derive their key pair deterministically from
+//! their Stellar secret seed with [`RecipientKeyPair::from_stellar_secret`].
+
+use crate::api_error::ApiError;
+use crate::message_access_audit::{MessageAccessAction, MessageAccessAuditService};
+use crate::message_delivery::DeliveryChannel;
+use crate::secure_messages::LegacyMessage;
+use crate::will_signature::WillSignatureService;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{DateTime, Duration, Utc};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM};
+use ring::hkdf::{Salt, HKDF_SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::PgPool;
+use stellar_strkey::Strkey;
+use uuid::Uuid;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+const TAG_LEN: usize = 16;
+const SEAL_INFO: &[u8] = b"inheritx-sealed-message-v1";
+const RECIPIENT_KEY_SALT: &[u8] = b"inheritx-recipient-message-key";
+/// How old a signed release request may be.
+const SIGNED_REQUEST_MAX_AGE_SECS: i64 = 300;
+
+// ── Sealing ───────────────────────────────────────────────────────────────────
+
+/// A message sealed to a recipient's X25519 key. All fields are base64.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SealedMessage {
+    pub ephemeral_public_key: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+/// A decoded [`SealedMessage`], in the form stored on `legacy_messages`.
+pub(crate) struct SealedParts {
+    pub(crate) ephemeral_public_key: [u8; KEY_LEN],
+    pub(crate) nonce: [u8; NONCE_LEN],
+    pub(crate) ciphertext: Vec<u8>,
+}
+
+impl SealedParts {
+    pub(crate) fn decode(sealed: &SealedMessage) -> Result<Self, ApiError> {
+        let field = |name: &str, value: &str| {
+            BASE64
+                .decode(value)
+                .map_err(|_| ApiError::BadRequest(format!("{name} must be base64")))
+        };
+        let ephemeral_public_key = field("ephemeral_public_key", &sealed.ephemeral_public_key)?
+            .try_into()
+            .map_err(|_| {
+                ApiError::BadRequest("ephemeral_public_key must be 32 bytes".to_string())
+            })?;
+        let nonce = field("nonce", &sealed.nonce)?
+            .try_into()
+            .map_err(|_| ApiError::BadRequest("nonce must be 12 bytes".to_string()))?;
+        let ciphertext = field("ciphertext", &sealed.ciphertext)?;
+        if ciphertext.len() <= TAG_LEN {
+            return Err(ApiError::BadRequest("ciphertext is too short".to_string()));
+        }
+        Ok(Self {
+            ephemeral_public_key,
+            nonce,
+            ciphertext,
+        })
+    }
+
+    fn encode(&self) -> SealedMessage {
+        SealedMessage {
+            ephemeral_public_key: BASE64.encode(self.ephemeral_public_key),
+            nonce: BASE64.encode(self.nonce),
+            ciphertext: BASE64.encode(&self.ciphertext),
+        }
+    }
+}
+
+fn random_bytes<const N: usize>() -> Result<[u8; N], ApiError> {
+    let mut bytes = [0u8; N];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| ApiError::Internal(anyhow::anyhow!("Failed to generate random bytes")))?;
+    Ok(bytes)
+}
+
+fn hkdf(salt: &[u8], secret: &[u8], info: &[u8]) -> Result<[u8; KEY_LEN], ApiError> {
+    let info = [info];
+    let prk = Salt::new(HKDF_SHA256, salt).extract(secret);
+    let okm = prk
+        .expand(&info, &AES_256_GCM)
+        .map_err(|_| ApiError::Internal(anyhow::anyhow!("Key derivation failed")))?;
+    let mut key = [0u8; KEY_LEN];
+    okm.fill(&mut key)
+        .map_err(|_| ApiError::Internal(anyhow::anyhow!("Unable to materialize key")))?;
+    Ok(key)
+}
+
+/// AES-256-GCM key for one ephemeral/recipient key pair.
+fn sealing_key(
+    shared_secret: &x25519_dalek::SharedSecret,
+    ephemeral_public_key: &[u8; KEY_LEN],
+    recipient_public_key: &[u8; KEY_LEN],
+) -> Result<LessSafeKey, ApiError> {
+    // An all-zero shared secret means a low-order public key was supplied.
+    if !shared_secret.was_contributory() {
+        return Err(ApiError::BadRequest(
+            "Invalid X25519 public key".to_string(),
+        ));
+    }
+    let salt = [&ephemeral_public_key[..], &recipient_public_key[..]].concat();
+    let key = hkdf(&salt, shared_secret.as_bytes(), SEAL_INFO)?;
+    let unbound = UnboundKey::new(&AES_256_GCM, &key)
+        .map_err(|_| ApiError::Internal(anyhow::anyhow!("Unable to create key")))?;
+    Ok(LessSafeKey::new(unbound))
+}
+
+/// Seal `plaintext` to a recipient's X25519 public key. `recipient_address`
+/// is bound as associated data, so the envelope only opens for that address.
+pub fn seal(
+    recipient_public_key: &[u8; KEY_LEN],
+    recipient_address: &str,
+    plaintext: &[u8],
+) -> Result<SealedMessage, ApiError> {
+    let ephemeral = StaticSecret::from(random_bytes::<KEY_LEN>()?);
+    let ephemeral_public_key = PublicKey::from(&ephemeral).to_bytes();
+    let shared = ephemeral.diffie_hellman(&PublicKey::from(*recipient_public_key));
+    let key = sealing_key(&shared, &ephemeral_public_key, recipient_public_key)?;
+
+    let nonce = random_bytes::<NONCE_LEN>()?;
+    let mut ciphertext = plaintext.to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::from(recipient_address.as_bytes()),
+        &mut ciphertext,
+    )
+    .map_err(|_| ApiError::Internal(anyhow::anyhow!("Encryption failed")))?;
+
+    Ok(SealedParts {
+        ephemeral_public_key,
+        nonce,
+        ciphertext,
+    }
+    .encode())
+}
+
+/// A beneficiary's message key pair. Only ever held by the recipient.
+pub struct RecipientKeyPair {
+    secret: StaticSecret,
+}
+
+impl RecipientKeyPair {
+    /// Derive the message key pair from a raw 32-byte Ed25519 seed.
+    pub fn from_seed(seed: &[u8; 32]) -> Result<Self, ApiError> {
+        let secret = hkdf(RECIPIENT_KEY_SALT, seed, SEAL_INFO)?;
+        Ok(Self {
+            secret: StaticSecret::from(secret),
+        })
+    }
+
+    /// Derive the message key pair from a Stellar secret seed (`S...`), so a
+    /// beneficiary can recover it from their wallet alone.
+    pub fn from_stellar_secret(secret_seed: &str) -> Result<Self, ApiError> {
+        match Strkey::from_string(secret_seed) {
+            Ok(Strkey::PrivateKeyEd25519(seed)) => Self::from_seed(&seed.0),
+            _ => Err(ApiError::BadRequest(
+                "Invalid Stellar secret seed".to_string(),
+            )),
+        }
+    }
+
+    pub fn public_key(&self) -> [u8; KEY_LEN] {
+        PublicKey::from(&self.secret).to_bytes()
+    }
+
+    /// Open a message sealed to this key for `recipient_address`.
+    pub fn open(
+        &self,
+        sealed: &SealedMessage,
+        recipient_address: &str,
+    ) -> Result<Vec<u8>, ApiError> {
+        let parts = SealedParts::decode(sealed)?;
+        let shared = self
+            .secret
+            .diffie_hellman(&PublicKey::from(parts.ephemeral_public_key));
+        let key = sealing_key(&shared, &parts.ephemeral_public_key, &self.public_key())?;
+
+        let mut in_out = parts.ciphertext;
+        let plaintext = key
+            .open_in_place(
+                Nonce::assume_unique_for_key(parts.nonce),
+                Aad::from(recipient_address.as_bytes()),
+                &mut in_out,
+            )
+            .map_err(|_| ApiError::BadRequest("Sealed message cannot be opened".to_string()))?;
+        Ok(plaintext.to_vec())
+    }
+}
+
+/// The statement a beneficiary signs with their Stellar key to bind an X25519
+/// message key to their address.
+pub fn key_binding_message(stellar_address: &str, public_key: &[u8; KEY_LEN]) -> String {
+    format!(
+        "INHERITX_MESSAGE_KEY:{stellar_address}:{}",
+        BASE64.encode(public_key)
+    )
+}
+
+/// The statement a beneficiary signs to collect a delivered sealed message.
+pub fn release_message(message_id: Uuid, signed_at: DateTime<Utc>) -> String {
+    format!(
+        "INHERITX_MESSAGE_RELEASE:{message_id}:{}",
+        signed_at.timestamp()
+    )
+}
+
+/// Check a request signed by a Stellar key: the signature must cover
+/// `statement` and `signed_at` must be recent, so captured requests cannot be
+/// replayed later.
+pub(crate) fn verify_signed_request(
+    stellar_address: &str,
+    statement: &str,
+    signed_at: DateTime<Utc>,
+    signature: &str,
+) -> Result<(), ApiError> {
+    let age = Utc::now() - signed_at;
+    if age > Duration::seconds(SIGNED_REQUEST_MAX_AGE_SECS) || age < Duration::seconds(-60) {
+        return Err(ApiError::Unauthorized);
+    }
+    WillSignatureService::verify_signature(stellar_address, statement, signature)
+}
+
+// ── Recipient keys ────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RegisterRecipientKeyRequest {
+    pub stellar_address: String,
+    /// Base64 X25519 public key
+    pub public_key: String,
+    /// Hex Ed25519 signature over [`key_binding_message`]
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RecipientKey {
+    pub id: Uuid,
+    pub stellar_address: String,
+    /// Base64 X25519 public key
+    pub public_key: String,
+    /// Hex Ed25519 signature binding the key to the address, so senders can
+    /// check the binding themselves before sealing.
+    pub binding_signature: String,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow)]
+pub(crate) struct RecipientKeyRow {
+    pub(crate) id: Uuid,
+    stellar_address: String,
+    public_key: Vec<u8>,
+    binding_signature: Vec<u8>,
+    status: String,
+    created_at: DateTime<Utc>,
+}
+
+impl RecipientKeyRow {
+    pub(crate) fn public_key_bytes(&self) -> Result<[u8; KEY_LEN], ApiError> {
+        self.public_key
+            .clone()
+            .try_into()
+            .map_err(|_| ApiError::Internal(anyhow::anyhow!("Stored message key is corrupt")))
+    }
+}
+
+impl From<RecipientKeyRow> for RecipientKey {
+    fn from(row: RecipientKeyRow) -> Self {
+        Self {
+            id: row.id,
+            stellar_address: row.stellar_address,
+            public_key: BASE64.encode(&row.public_key),
+            binding_signature: hex::encode(&row.binding_signature),
+            status: row.status,
+            created_at: row.created_at,
+        }
+    }
+}
+
+pub struct RecipientKeyService;
+
+impl RecipientKeyService {
+    /// Register a message key for a Stellar address, replacing any previous
+    /// one. Messages already sealed to the old key remain sealed to it.
+    pub async fn register(
+        db: &PgPool,
+        req: &RegisterRecipientKeyRequest,
+    ) -> Result<RecipientKey, ApiError> {
+        let public_key: [u8; KEY_LEN] = BASE64
+            .decode(&req.public_key)
+            .ok()
+            .and_then(|key| key.try_into().ok())
+            .ok_or_else(|| {
+                ApiError::BadRequest("public_key must be a base64 X25519 key".to_string())
+            })?;
+        if !req.stellar_address.starts_with('G') {
+            return Err(ApiError::BadRequest(
+                "stellar_address must be a Stellar account address".to_string(),
+            ));
+        }
+        WillSignatureService::verify_signature(
+            &req.stellar_address,
+            &key_binding_message(&req.stellar_address, &public_key),
+            &req.signature,
+        )?;
+        let signature = hex::decode(&req.signature)
+            .map_err(|_| ApiError::BadRequest("Invalid signature hex".to_string()))?;
+
+        let mut tx = db.begin().await?;
+        sqlx::query(
+            "UPDATE message_recipient_keys SET status = 'replaced', replaced_at = NOW() \
+             WHERE stellar_address = $1 AND status = 'active'",
+        )
+        .bind(&req.stellar_address)
+        .execute(&mut *tx)
+        .await?;
+        let row = sqlx::query_as::<_, RecipientKeyRow>(
+            "INSERT INTO message_recipient_keys (stellar_address, public_key, binding_signature) \
+             VALUES ($1, $2, $3) \
+             RETURNING id, stellar_address, public_key, binding_signature, status, created_at",
+        )
+        .bind(&req.stellar_address)
+        .bind(&public_key[..])
+        .bind(&signature)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(row.into())
+    }
+
+    pub async fn get_active(db: &PgPool, stellar_address: &str) -> Result<RecipientKey, ApiError> {
+        Ok(Self::active_row(db, stellar_address).await?.into())
+    }
+
+    pub(crate) async fn active_row(
+        db: &PgPool,
+        stellar_address: &str,
+    ) -> Result<RecipientKeyRow, ApiError> {
+        sqlx::query_as::<_, RecipientKeyRow>(
+            "SELECT id, stellar_address, public_key, binding_signature, status, created_at \
+             FROM message_recipient_keys WHERE stellar_address = $1 AND status = 'active'",
+        )
+        .bind(stellar_address)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!("No message key registered for {stellar_address}"))
+        })
+    }
+}
+
+// ── Sealed messages ───────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CreateSealedMessageRequest {
+    pub vault_id: Option<i64>,
+    pub beneficiary_contact: String,
+    pub recipient_stellar_address: String,
+    pub unlock_at: DateTime<Utc>,
+    /// Envelope sealed client-side to `recipient_key_id`.
+    pub sealed: Option<SealedMessage>,
+    pub recipient_key_id: Option<Uuid>,
+    /// Plaintext for server-side sealing to the recipient's active key. It is
+    /// sealed immediately and never stored.
+    pub message: Option<String>,
+    #[serde(default)]
+    pub delivery_channel: DeliveryChannel,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct ReleaseSealedMessageRequest {
+    pub stellar_address: String,
+    pub signed_at: DateTime<Utc>,
+    /// Hex Ed25519 signature over [`release_message`]
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReleasedMessage {
+    pub message_id: Uuid,
+    pub recipient_stellar_address: String,
+    pub recipient_key_id: Uuid,
+    pub sealed: SealedMessage,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+pub struct SealedMessageService;
+
+impl SealedMessageService {
+    pub async fn create(
+        db: &PgPool,
+        owner_user_id: Uuid,
+        req: &CreateSealedMessageRequest,
+    ) -> Result<LegacyMessage, ApiError> {
+        if req.unlock_at <= Utc::now() {
+            return Err(ApiError::BadRequest(
+                "unlock_at must be in the future".to_string(),
+            ));
+        }
+        if req.beneficiary_contact.trim().is_empty() {
+            return Err(ApiError::BadRequest(
+                "beneficiary_contact cannot be empty".to_string(),
+            ));
+        }
+        req.delivery_channel
+            .validate_contact(req.beneficiary_contact.trim())?;
+
+        let address = req.recipient_stellar_address.trim();
+        let (recipient_key_id, parts) = match (&req.sealed, &req.message) {
+            (Some(sealed), None) => {
+                let key_id = req.recipient_key_id.ok_or_else(|| {
+                    ApiError::BadRequest(
+                        "recipient_key_id is required for client-sealed messages".to_string(),
+                    )
+                })?;
+                let belongs: bool = sqlx::query_scalar(
+                    "SELECT EXISTS(SELECT 1 FROM message_recipient_keys \
+                     WHERE id = $1 AND stellar_address = $2 AND status = 'active')",
+                )
+                .bind(key_id)
+                .bind(address)
+                .fetch_one(db)
+                .await?;
+                if !belongs {
+                    return Err(ApiError::BadRequest(
+                        "recipient_key_id is not the recipient's active message key".to_string(),
+                    ));
+                }
+                (key_id, SealedParts::decode(sealed)?)
+            }
+            (None, Some(message)) => {
+                if message.trim().is_empty() {
+                    return Err(ApiError::BadRequest("message cannot be empty".to_string()));
+                }
+                let key = RecipientKeyService::active_row(db, address).await?;
+                let sealed = seal(&key.public_key_bytes()?, address, message.as_bytes())?;
+                (key.id, SealedParts::decode(&sealed)?)
+            }
+            _ => {
+                return Err(ApiError::BadRequest(
+                    "Provide exactly one of sealed or message".to_string(),
+                ))
+            }
+        };
+
+        let message = sqlx::query_as::<_, LegacyMessage>(
+            "INSERT INTO legacy_messages \
+             (owner_user_id, vault_id, beneficiary_contact, encrypted_payload, payload_nonce, \
+              unlock_at, status, encryption_mode, recipient_stellar_address, recipient_key_id, \
+              ephemeral_public_key, delivery_channel) \
+             VALUES ($1, $2, $3, $4, $5, $6, 'pending', 'e2e', $7, $8, $9, $10) \
+             RETURNING id, owner_user_id, vault_id, beneficiary_contact, key_version, \
+              encryption_mode, recipient_stellar_address, delivery_channel, \
+              delivery_attempts, next_attempt_at, unlock_at, status, delivered_at, created_at",
+        )
+        .bind(owner_user_id)
+        .bind(req.vault_id)
+        .bind(req.beneficiary_contact.trim())
+        .bind(&parts.ciphertext)
+        .bind(&parts.nonce[..])
+        .bind(req.unlock_at)
+        .bind(address)
+        .bind(recipient_key_id)
+        .bind(&parts.ephemeral_public_key[..])
+        .bind(req.delivery_channel.as_str())
+        .fetch_one(db)
+        .await?;
+
+        MessageAccessAuditService::log_access(
+            db,
+            Some(message.id),
+            owner_user_id,
+            MessageAccessAction::Created,
+            None,
+            None,
+            json!({ "encryption_mode": "e2e", "recipient_key_id": recipient_key_id }),
+        )
+        .await?;
+
+        Ok(message)
+    }
+
+    /// Hand a delivered sealed message to its recipient. The request must be
+    /// signed by the recipient's Stellar key within the last few minutes.
+    pub async fn release(
+        db: &PgPool,
+        message_id: Uuid,
+        req: &ReleaseSealedMessageRequest,
+    ) -> Result<ReleasedMessage, ApiError> {
+        verify_signed_request(
+            &req.stellar_address,
+            &release_message(message_id, req.signed_at),
+            req.signed_at,
+            &req.signature,
+        )?;
+
+        #[derive(sqlx::FromRow)]
+        struct Row {
+            owner_user_id: Uuid,
+            recipient_stellar_address: String,
+            recipient_key_id: Uuid,
+            encrypted_payload: Vec<u8>,
+            payload_nonce: Vec<u8>,
+            ephemeral_public_key: Vec<u8>,
+            status: String,
+            delivered_at: Option<DateTime<Utc>>,
+        }
+
+        let row = sqlx::query_as::<_, Row>(
+            "SELECT owner_user_id, recipient_stellar_address, recipient_key_id, encrypted_payload, \
+             payload_nonce, ephemeral_public_key, status, delivered_at \
+             FROM legacy_messages WHERE id = $1 AND encryption_mode = 'e2e'",
+        )
+        .bind(message_id)
+        .fetch_optional(db)
+        .await?
+        .filter(|row| row.recipient_stellar_address == req.stellar_address)
+        .ok_or_else(|| ApiError::NotFound(format!("Sealed message {message_id} not found")))?;
+
+        if row.status != "delivered" {
+            return Err(ApiError::Forbidden(
+                "Message has not been released yet".to_string(),
+            ));
+        }
+
+        MessageAccessAuditService::log_access(
+            db,
+            Some(message_id),
+            row.owner_user_id,
+            MessageAccessAction::Released,
+            None,
+            None,
+            json!({ "recipient_stellar_address": row.recipient_stellar_address }),
+        )
+        .await?;
+
+        Ok(ReleasedMessage {
+            message_id,
+            recipient_stellar_address: row.recipient_stellar_address,
+            recipient_key_id: row.recipient_key_id,
+            sealed: SealedMessage {
+                ephemeral_public_key: BASE64.encode(&row.ephemeral_public_key),
+                nonce: BASE64.encode(&row.payload_nonce),
+                ciphertext: BASE64.encode(&row.encrypted_payload),
+            },
+            delivered_at: row.delivered_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H";
+
+    #[test]
+    fn sealed_message_roundtrip() {
+        let recipient = RecipientKeyPair::from_seed(&[7u8; 32]).unwrap();
+        let sealed = seal(&recipient.public_key(), ADDRESS, b"to my daughter").unwrap();
+        assert_eq!(recipient.open(&sealed, ADDRESS).unwrap(), b"to my daughter");
+
+        // Each seal uses a fresh ephemeral key.
+        let again = seal(&recipient.public_key(), ADDRESS, b"to my daughter").unwrap();
+        assert_ne!(sealed.ephemeral_public_key, again.ephemeral_public_key);
+    }
+
+    #[test]
+    fn sealed_message_only_opens_for_its_recipient() {
+        let recipient = RecipientKeyPair::from_seed(&[7u8; 32]).unwrap();
+        let other = RecipientKeyPair::from_seed(&[8u8; 32]).unwrap();
+        let sealed = seal(&recipient.public_key(), ADDRESS, b"private").unwrap();
+
+        assert!(other.open(&sealed, ADDRESS).is_err());
+        assert!(recipient.open(&sealed, "GOTHERADDRESS").is_err());
+    }
+
+    #[test]
+    fn recipient_key_is_derived_deterministically() {
+        let a = RecipientKeyPair::from_seed(&[1u8; 32]).unwrap();
+        let b = RecipientKeyPair::from_seed(&[1u8; 32]).unwrap();
+        let c = RecipientKeyPair::from_seed(&[2u8; 32]).unwrap();
+        assert_eq!(a.public_key(), b.public_key());
+        assert_ne!(a.public_key(), c.public_key());
+    }
+
+    #[test]
+    fn low_order_public_keys_are_rejected() {
+        assert!(seal(&[0u8; 32], ADDRESS, b"data").is_err());
+    }
+
+    #[test]
+    fn malformed_envelopes_are_rejected() {
+        let recipient = RecipientKeyPair::from_seed(&[7u8; 32]).unwrap();
+        let mut sealed = seal(&recipient.public_key(), ADDRESS, b"data").unwrap();
+        sealed.nonce = BASE64.encode([0u8; 4]);
+        assert!(matches!(
+            recipient.open(&sealed, ADDRESS),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+}
@@ -1,9 +1,19 @@
 use crate::api_error::ApiError;
+use crate::death_verification::DeathVerificationService;
+use crate::message_access_audit::{MessageAccessAction, MessageAccessAuditService};
+use crate::message_delivery::{
+    new_receipt_token, retry_delay, DeliveryChannel, MessageChannels, OutgoingMessage,
+    MAX_DELIVERY_ATTEMPTS,
+};
+use crate::message_schedule::{MessageSchedule, MessageScheduleService};
+use crate::metrics::observe_job;
 use chrono::{DateTime, Utc};
 use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM};
 use ring::hkdf::{Salt, HKDF_SHA256};
 use ring::rand::{SecureRandom, SystemRandom};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use sqlx::PgPool;
 use std::sync::Arc;
 use std::time::Duration;
@@ -13,28 +23,45 @@ use uuid::Uuid;
 const NONCE_LEN: usize = 12;
 const KEY_LEN: usize = 32;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CreateLegacyMessageRequest {
     pub vault_id: Option<i64>,
     pub beneficiary_contact: String,
     pub message: String,
-    pub unlock_at: DateTime<Utc>,
+    /// Deliver at a fixed time. Exactly one of `unlock_at` and `schedule`.
+    #[serde(default)]
+    pub unlock_at: Option<DateTime<Utc>>,
+    /// Deliver after the owner's death is verified (see
+    /// [`crate::message_schedule`]).
+    #[serde(default)]
+    pub schedule: Option<MessageSchedule>,
+    #[serde(default)]
+    pub delivery_channel: DeliveryChannel,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
 pub struct LegacyMessage {
     pub id: Uuid,
     pub owner_user_id: Uuid,
     pub vault_id: Option<i64>,
     pub beneficiary_contact: String,
-    pub key_version: i32,
+    /// Server data key version; `None` for end-to-end sealed messages.
+    pub key_version: Option<i32>,
+    /// `server` or `e2e` (see [`crate::sealed_messages`]).
+    pub encryption_mode: String,
+    pub recipient_stellar_address: Option<String>,
+    /// `email`, `webhook` or `inbox` (see [`crate::message_delivery`]).
+    pub delivery_channel: String,
+    pub delivery_attempts: i32,
+    /// When a failed delivery will next be retried.
+    pub next_attempt_at: Option<DateTime<Utc>>,
     pub unlock_at: DateTime<Utc>,
     pub status: String,
     pub delivered_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MessageEncryptionKey {
     pub id: Uuid,
     pub key_version: i32,
@@ -48,6 +75,9 @@ pub struct MessageEncryptionKey {
 pub struct DeliveryResult {
     pub processed: usize,
     pub delivered: usize,
+    /// Failed attempts that will be retried.
+    pub retrying: usize,
+    /// Messages that used up their attempts.
     pub failed: usize,
 }
 
@@ -58,9 +88,18 @@ struct DueMessage {
     beneficiary_contact: String,
     encrypted_payload: Vec<u8>,
     payload_nonce: Vec<u8>,
-    key_version: i32,
+    key_version: Option<i32>,
+    encryption_mode: String,
+    recipient_stellar_address: Option<String>,
+    delivery_channel: String,
+    delivery_attempts: i32,
+    schedule: Option<serde_json::Value>,
+    occurrences_delivered: i32,
 }
 
+const DUE_MESSAGE_SELECT: &str = "SELECT id, owner_user_id, beneficiary_contact, encrypted_payload, payload_nonce, key_version, encryption_mode, recipient_stellar_address, delivery_channel, delivery_attempts, schedule, occurrences_delivered \
+     FROM legacy_messages";
+
 fn derive_key(secret: &[u8], context: &'static [u8]) -> Result<LessSafeKey, ApiError> {
     let salt = Salt::new(HKDF_SHA256, b"inheritx-message-encryption");
     let prk = salt.extract(secret);
@@ -297,16 +336,71 @@ impl MessageKeyService {
 pub struct MessageEncryptionService;
 
 impl MessageEncryptionService {
+    /// Encrypt a payload under the active server data key, returning the key
+    /// version, ciphertext and nonce to store with a 'server' mode message.
+    pub(crate) async fn encrypt_with_active_key(
+        db: &PgPool,
+        plaintext: &[u8],
+    ) -> Result<(i32, Vec<u8>, Vec<u8>), ApiError> {
+        Self::encrypt_for_purpose(db, b"legacy-message-payload-key", plaintext).await
+    }
+
+    /// Encrypt another server-held secret under the active data key, using a
+    /// key derived for `purpose` so it never shares a key with message payloads.
+    pub(crate) async fn encrypt_for_purpose(
+        db: &PgPool,
+        purpose: &'static [u8],
+        plaintext: &[u8],
+    ) -> Result<(i32, Vec<u8>, Vec<u8>), ApiError> {
+        MessageKeyService::ensure_active_key(db).await?;
+        let (key_version, data_key) = MessageKeyService::active_data_key_material(db).await?;
+        let key = derive_key(&data_key, purpose)?;
+        let (ciphertext, nonce) = encrypt_with_key(&key, plaintext)?;
+        Ok((key_version, ciphertext, nonce))
+    }
+
+    /// Decrypt a secret stored by [`Self::encrypt_for_purpose`].
+    pub(crate) async fn decrypt_for_purpose(
+        db: &PgPool,
+        purpose: &'static [u8],
+        key_version: i32,
+        ciphertext: &[u8],
+        nonce: &[u8],
+    ) -> Result<Vec<u8>, ApiError> {
+        let data_key = MessageKeyService::key_material_by_version(db, key_version).await?;
+        let key = derive_key(&data_key, purpose)?;
+        decrypt_with_key(&key, ciphertext, nonce)
+    }
+
     pub async fn create_encrypted_message(
         db: &PgPool,
         owner_user_id: Uuid,
         req: &CreateLegacyMessageRequest,
     ) -> Result<LegacyMessage, ApiError> {
-        if req.unlock_at <= Utc::now() {
-            return Err(ApiError::BadRequest(
-                "unlock_at must be in the future".to_string(),
-            ));
-        }
+        // Scheduled messages wait for a verified death; unlock_at is resolved
+        // from the schedule then.
+        let (status, unlock_at) = match (req.unlock_at, &req.schedule) {
+            (Some(unlock_at), None) => {
+                if unlock_at <= Utc::now() {
+                    return Err(ApiError::BadRequest(
+                        "unlock_at must be in the future".to_string(),
+                    ));
+                }
+                ("pending", unlock_at)
+            }
+            (None, Some(schedule)) => {
+                schedule.validate()?;
+                match schedule {
+                    MessageSchedule::OnDate { at } => ("scheduled", *at),
+                    _ => ("scheduled", Utc::now()),
+                }
+            }
+            _ => {
+                return Err(ApiError::BadRequest(
+                    "Provide exactly one of unlock_at or schedule".to_string(),
+                ))
+            }
+        };
         if req.message.trim().is_empty() {
             return Err(ApiError::BadRequest("message cannot be empty".to_string()));
         }
@@ -315,12 +409,11 @@ impl MessageEncryptionService {
                 "beneficiary_contact cannot be empty".to_string(),
             ));
         }
+        req.delivery_channel
+            .validate_contact(req.beneficiary_contact.trim())?;
 
-        MessageKeyService::ensure_active_key(db).await?;
-        let (key_version, data_key) = MessageKeyService::active_data_key_material(db).await?;
-        let payload_key = derive_key(&data_key, b"legacy-message-payload-key")?;
-        let (encrypted_payload, payload_nonce) =
-            encrypt_with_key(&payload_key, req.message.as_bytes())?;
+        let (key_version, encrypted_payload, payload_nonce) =
+            Self::encrypt_with_active_key(db, req.message.as_bytes()).await?;
 
         #[derive(sqlx::FromRow)]
         struct Row {
@@ -328,7 +421,12 @@ impl MessageEncryptionService {
             owner_user_id: Uuid,
             vault_id: Option<i64>,
             beneficiary_contact: String,
-            key_version: i32,
+            key_version: Option<i32>,
+            encryption_mode: String,
+            recipient_stellar_address: Option<String>,
+            delivery_channel: String,
+            delivery_attempts: i32,
+            next_attempt_at: Option<DateTime<Utc>>,
             unlock_at: DateTime<Utc>,
             status: String,
             delivered_at: Option<DateTime<Utc>>,
@@ -337,9 +435,9 @@ impl MessageEncryptionService {
 
         let row = sqlx::query_as::<_, Row>(
             "INSERT INTO legacy_messages \
-             (owner_user_id, vault_id, beneficiary_contact, encrypted_payload, payload_nonce, key_version, unlock_at, status) \
-             VALUES ($1, $2, $3, $4, $5, $6, $7, 'pending') \
-             RETURNING id, owner_user_id, vault_id, beneficiary_contact, key_version, unlock_at, status, delivered_at, created_at",
+             (owner_user_id, vault_id, beneficiary_contact, encrypted_payload, payload_nonce, key_version, unlock_at, status, delivery_channel, schedule) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) \
+             RETURNING id, owner_user_id, vault_id, beneficiary_contact, key_version, encryption_mode, recipient_stellar_address, delivery_channel, delivery_attempts, next_attempt_at, unlock_at, status, delivered_at, created_at",
         )
         .bind(owner_user_id)
         .bind(req.vault_id)
@@ -347,7 +445,16 @@ impl MessageEncryptionService {
         .bind(&encrypted_payload)
         .bind(&payload_nonce)
         .bind(key_version)
-        .bind(req.unlock_at)
+        .bind(unlock_at)
+        .bind(status)
+        .bind(req.delivery_channel.as_str())
+        .bind(
+            req.schedule
+                .as_ref()
+                .map(serde_json::to_value)
+                .transpose()
+                .map_err(|e| ApiError::Internal(e.into()))?,
+        )
         .fetch_one(db)
         .await?;
 
@@ -357,6 +464,11 @@ impl MessageEncryptionService {
             vault_id: row.vault_id,
             beneficiary_contact: row.beneficiary_contact,
             key_version: row.key_version,
+            encryption_mode: row.encryption_mode,
+            recipient_stellar_address: row.recipient_stellar_address,
+            delivery_channel: row.delivery_channel,
+            delivery_attempts: row.delivery_attempts,
+            next_attempt_at: row.next_attempt_at,
             unlock_at: row.unlock_at,
             status: row.status,
             delivered_at: row.delivered_at,
@@ -374,7 +486,12 @@ impl MessageEncryptionService {
             owner_user_id: Uuid,
             vault_id: Option<i64>,
             beneficiary_contact: String,
-            key_version: i32,
+            key_version: Option<i32>,
+            encryption_mode: String,
+            recipient_stellar_address: Option<String>,
+            delivery_channel: String,
+            delivery_attempts: i32,
+            next_attempt_at: Option<DateTime<Utc>>,
             unlock_at: DateTime<Utc>,
             status: String,
             delivered_at: Option<DateTime<Utc>>,
@@ -382,7 +499,7 @@ impl MessageEncryptionService {
         }
 
         let rows = sqlx::query_as::<_, Row>(
-            "SELECT id, owner_user_id, vault_id, beneficiary_contact, key_version, unlock_at, status, delivered_at, created_at \
+            "SELECT id, owner_user_id, vault_id, beneficiary_contact, key_version, encryption_mode, recipient_stellar_address, delivery_channel, delivery_attempts, next_attempt_at, unlock_at, status, delivered_at, created_at \
              FROM legacy_messages WHERE owner_user_id = $1 ORDER BY created_at DESC",
         )
         .bind(owner_user_id)
@@ -397,6 +514,11 @@ impl MessageEncryptionService {
                 vault_id: r.vault_id,
                 beneficiary_contact: r.beneficiary_contact,
                 key_version: r.key_version,
+                encryption_mode: r.encryption_mode,
+                recipient_stellar_address: r.recipient_stellar_address,
+                delivery_channel: r.delivery_channel,
+                delivery_attempts: r.delivery_attempts,
+                next_attempt_at: r.next_attempt_at,
                 unlock_at: r.unlock_at,
                 status: r.status,
                 delivered_at: r.delivered_at,
@@ -405,6 +527,43 @@ impl MessageEncryptionService {
             .collect())
     }
 
+    /// The plaintext of one of the owner's server-encrypted messages; `None`
+    /// for end-to-end sealed messages, which the server cannot open.
+    pub async fn decrypt_owner_message(
+        db: &PgPool,
+        owner_user_id: Uuid,
+        message_id: Uuid,
+    ) -> Result<Option<String>, ApiError> {
+        #[derive(sqlx::FromRow)]
+        struct Row {
+            key_version: Option<i32>,
+            encryption_mode: String,
+            encrypted_payload: Vec<u8>,
+            payload_nonce: Vec<u8>,
+        }
+
+        let row = sqlx::query_as::<_, Row>(
+            "SELECT key_version, encryption_mode, encrypted_payload, payload_nonce \
+             FROM legacy_messages WHERE id = $1 AND owner_user_id = $2",
+        )
+        .bind(message_id)
+        .bind(owner_user_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Message {message_id} not found")))?;
+        let key_version = match row.key_version {
+            Some(version) if row.encryption_mode == "server" => version,
+            _ => return Ok(None),
+        };
+
+        let key_material = MessageKeyService::key_material_by_version(db, key_version).await?;
+        let payload_key = derive_key(&key_material, b"legacy-message-payload-key")?;
+        let decrypted = decrypt_with_key(&payload_key, &row.encrypted_payload, &row.payload_nonce)?;
+        String::from_utf8(decrypted)
+            .map(Some)
+            .map_err(|_| ApiError::Internal(anyhow::anyhow!("Invalid UTF-8 payload")))
+    }
+
     pub async fn list_vault_messages(
         db: &PgPool,
         owner_user_id: Uuid,
@@ -416,7 +575,12 @@ impl MessageEncryptionService {
             owner_user_id: Uuid,
             vault_id: Option<i64>,
             beneficiary_contact: String,
-            key_version: i32,
+            key_version: Option<i32>,
+            encryption_mode: String,
+            recipient_stellar_address: Option<String>,
+            delivery_channel: String,
+            delivery_attempts: i32,
+            next_attempt_at: Option<DateTime<Utc>>,
             unlock_at: DateTime<Utc>,
             status: String,
             delivered_at: Option<DateTime<Utc>>,
@@ -424,7 +588,7 @@ impl MessageEncryptionService {
         }
 
         let rows = sqlx::query_as::<_, Row>(
-            "SELECT id, owner_user_id, vault_id, beneficiary_contact, key_version, unlock_at, status, delivered_at, created_at \
+            "SELECT id, owner_user_id, vault_id, beneficiary_contact, key_version, encryption_mode, recipient_stellar_address, delivery_channel, delivery_attempts, next_attempt_at, unlock_at, status, delivered_at, created_at \
              FROM legacy_messages WHERE owner_user_id = $1 AND vault_id = $2 ORDER BY created_at DESC",
         )
         .bind(owner_user_id)
@@ -440,6 +604,11 @@ impl MessageEncryptionService {
                 vault_id: r.vault_id,
                 beneficiary_contact: r.beneficiary_contact,
                 key_version: r.key_version,
+                encryption_mode: r.encryption_mode,
+                recipient_stellar_address: r.recipient_stellar_address,
+                delivery_channel: r.delivery_channel,
+                delivery_attempts: r.delivery_attempts,
+                next_attempt_at: r.next_attempt_at,
                 unlock_at: r.unlock_at,
                 status: r.status,
                 delivered_at: r.delivered_at,
@@ -451,11 +620,18 @@ impl MessageEncryptionService {
 
 pub struct LegacyMessageDeliveryService {
     db: PgPool,
+    channels: Arc<MessageChannels>,
+}
+
+enum DeliveryOutcome {
+    Delivered,
+    Retrying,
+    Failed,
 }
 
 impl LegacyMessageDeliveryService {
-    pub fn new(db: PgPool) -> Self {
-        Self { db }
+    pub fn new(db: PgPool, channels: Arc<MessageChannels>) -> Self {
+        Self { db, channels }
     }
 
     pub fn start(self: Arc<Self>) {
@@ -463,7 +639,9 @@ impl LegacyMessageDeliveryService {
             let mut interval = tokio::time::interval(Duration::from_secs(30));
             loop {
                 interval.tick().await;
-                if let Err(e) = self.process_due_messages().await {
+                if let Err(e) =
+                    observe_job("legacy_message_delivery", self.process_due_messages()).await
+                {
                     error!("Legacy Message Delivery Service error: {}", e);
                 }
             }
@@ -471,31 +649,29 @@ impl LegacyMessageDeliveryService {
     }
 
     pub async fn process_due_messages(&self) -> Result<DeliveryResult, ApiError> {
-        let due_messages = sqlx::query_as::<_, DueMessage>(
-            "SELECT id, owner_user_id, beneficiary_contact, encrypted_payload, payload_nonce, key_version \
-             FROM legacy_messages \
+        let activated = MessageScheduleService::activate_scheduled(&self.db).await?;
+        if activated > 0 {
+            info!("Queued {} scheduled legacy messages", activated);
+        }
+
+        let due_messages = sqlx::query_as::<_, DueMessage>(&format!(
+            "{DUE_MESSAGE_SELECT} \
              WHERE status = 'pending' AND unlock_at <= NOW() \
-             ORDER BY unlock_at ASC LIMIT 100",
-        )
+               AND (next_attempt_at IS NULL OR next_attempt_at <= NOW()) \
+             ORDER BY COALESCE(next_attempt_at, unlock_at) ASC LIMIT 100"
+        ))
         .fetch_all(&self.db)
         .await?;
 
         let mut delivered = 0usize;
+        let mut retrying = 0usize;
         let mut failed = 0usize;
 
         for row in &due_messages {
-            match self.deliver_single(row).await {
-                Ok(()) => {
-                    delivered += 1;
-                }
-                Err(e) => {
-                    failed += 1;
-                    warn!("Failed delivering legacy message {}: {}", row.id, e);
-                    sqlx::query("UPDATE legacy_messages SET status = 'failed', updated_at = NOW() WHERE id = $1")
-                        .bind(row.id)
-                        .execute(&self.db)
-                        .await?;
-                }
+            match self.attempt(row).await? {
+                DeliveryOutcome::Delivered => delivered += 1,
+                DeliveryOutcome::Retrying => retrying += 1,
+                DeliveryOutcome::Failed => failed += 1,
             }
         }
 
@@ -506,44 +682,216 @@ impl LegacyMessageDeliveryService {
         Ok(DeliveryResult {
             processed: due_messages.len(),
             delivered,
+            retrying,
             failed,
         })
     }
 
-    async fn deliver_single(&self, row: &DueMessage) -> Result<(), ApiError> {
-        let key_material =
-            MessageKeyService::key_material_by_version(&self.db, row.key_version).await?;
-        let payload_key = derive_key(&key_material, b"legacy-message-payload-key")?;
-        let decrypted = decrypt_with_key(&payload_key, &row.encrypted_payload, &row.payload_nonce)?;
-        let decrypted_payload = String::from_utf8(decrypted)
-            .map_err(|_| ApiError::Internal(anyhow::anyhow!("Invalid UTF-8 payload")))?;
+    /// Attempt one unlocked message now, regardless of its retry schedule.
+    /// A message that has used up its attempts gets one more.
+    pub async fn deliver_now(&self, message_id: Uuid) -> Result<LegacyMessage, ApiError> {
+        let row = sqlx::query_as::<_, DueMessage>(&format!(
+            "{DUE_MESSAGE_SELECT} \
+             WHERE id = $1 AND status IN ('pending', 'failed') AND unlock_at <= NOW()"
+        ))
+        .bind(message_id)
+        .fetch_optional(&self.db)
+        .await?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "No unlocked, undelivered legacy message {message_id}"
+            ))
+        })?;
+
+        self.attempt(&row).await?;
+
+        let message = sqlx::query_as::<_, LegacyMessage>(
+            "SELECT id, owner_user_id, vault_id, beneficiary_contact, key_version, encryption_mode, recipient_stellar_address, delivery_channel, delivery_attempts, next_attempt_at, unlock_at, status, delivered_at, created_at \
+             FROM legacy_messages WHERE id = $1",
+        )
+        .bind(message_id)
+        .fetch_one(&self.db)
+        .await?;
+        Ok(message)
+    }
+
+    async fn attempt(&self, row: &DueMessage) -> Result<DeliveryOutcome, ApiError> {
+        let attempt = row.delivery_attempts + 1;
+        match self.deliver_single(row, attempt).await {
+            Ok(()) => Ok(DeliveryOutcome::Delivered),
+            Err(e) => {
+                warn!(
+                    "Failed delivering legacy message {} (attempt {}): {}",
+                    row.id, attempt, e
+                );
+                self.record_failure(row, attempt, &e.to_string()).await
+            }
+        }
+    }
+
+    async fn deliver_single(&self, row: &DueMessage, attempt: i32) -> Result<(), ApiError> {
+        let channel = DeliveryChannel::from_db(&row.delivery_channel)?;
+
+        // End-to-end sealed messages are released as ciphertext; the server
+        // holds no key that could open them.
+        let decrypted_payload =
+            match row.key_version {
+                Some(key_version) if row.encryption_mode == "server" => {
+                    let key_material =
+                        MessageKeyService::key_material_by_version(&self.db, key_version).await?;
+                    let payload_key = derive_key(&key_material, b"legacy-message-payload-key")?;
+                    let decrypted =
+                        decrypt_with_key(&payload_key, &row.encrypted_payload, &row.payload_nonce)?;
+                    Some(String::from_utf8(decrypted).map_err(|_| {
+                        ApiError::Internal(anyhow::anyhow!("Invalid UTF-8 payload"))
+                    })?)
+                }
+                _ => None,
+            };
+
+        let delivery_id = Uuid::new_v4();
+        let (receipt_token, receipt_token_hash) = new_receipt_token()?;
+        let receipt = self
+            .channels
+            .deliver(
+                channel,
+                &OutgoingMessage {
+                    delivery_id,
+                    message_id: row.id,
+                    contact: &row.beneficiary_contact,
+                    body: decrypted_payload.as_deref(),
+                    recipient_stellar_address: row.recipient_stellar_address.as_deref(),
+                    receipt_token: &receipt_token,
+                },
+            )
+            .await?;
+
+        // Only the inbox serves the plaintext after delivery; email and
+        // webhook deliveries keep just the receipt.
+        let stored_payload = match channel {
+            DeliveryChannel::Inbox => decrypted_payload,
+            _ => None,
+        };
 
         let mut tx = self.db.begin().await?;
 
         sqlx::query(
             "INSERT INTO legacy_message_deliveries \
-             (message_id, owner_user_id, beneficiary_contact, decrypted_payload, delivered_at) \
-             VALUES ($1, $2, $3, $4, NOW())",
+             (id, message_id, owner_user_id, beneficiary_contact, decrypted_payload, delivered_at, \
+              channel, receipt, receipt_token_hash) \
+             VALUES ($1, $2, $3, $4, $5, NOW(), $6, $7, $8)",
         )
+        .bind(delivery_id)
         .bind(row.id)
         .bind(row.owner_user_id)
         .bind(&row.beneficiary_contact)
-        .bind(&decrypted_payload)
+        .bind(&stored_payload)
+        .bind(channel.as_str())
+        .bind(&receipt)
+        .bind(&receipt_token_hash)
         .execute(&mut *tx)
         .await?;
 
+        // Recurring schedules go back to pending with their next occurrence.
+        let next_occurrence = match &row.schedule {
+            Some(schedule) => {
+                let schedule: MessageSchedule = serde_json::from_value(schedule.clone())
+                    .map_err(|e| ApiError::Internal(e.into()))?;
+                match DeathVerificationService::verified_at(&mut *tx, row.owner_user_id).await? {
+                    Some(death_verified_at) if schedule.is_recurring() => schedule.occurrence(
+                        death_verified_at,
+                        row.occurrences_delivered.max(0) as u32 + 1,
+                    ),
+                    _ => None,
+                }
+            }
+            None => None,
+        };
+
         sqlx::query(
             "UPDATE legacy_messages \
-             SET status = 'delivered', delivered_at = NOW(), updated_at = NOW() \
+             SET status = CASE WHEN $3::timestamptz IS NULL THEN 'delivered' ELSE 'pending' END, \
+                 unlock_at = COALESCE($3, unlock_at), \
+                 delivery_attempts = CASE WHEN $3::timestamptz IS NULL THEN $2 ELSE 0 END, \
+                 occurrences_delivered = occurrences_delivered + 1, \
+                 delivered_at = NOW(), next_attempt_at = NULL, last_delivery_error = NULL, \
+                 updated_at = NOW() \
              WHERE id = $1",
         )
         .bind(row.id)
+        .bind(attempt)
+        .bind(next_occurrence)
         .execute(&mut *tx)
         .await?;
 
         tx.commit().await?;
+
+        MessageAccessAuditService::log_access(
+            &self.db,
+            Some(row.id),
+            row.owner_user_id,
+            MessageAccessAction::Delivered,
+            None,
+            None,
+            json!({
+                "delivery_id": delivery_id,
+                "channel": channel.as_str(),
+                "attempt": attempt,
+                "receipt": receipt,
+                "next_occurrence": next_occurrence,
+            }),
+        )
+        .await?;
         Ok(())
     }
+
+    /// Schedule the next attempt with exponential backoff, or give up once
+    /// the attempts are used up.
+    async fn record_failure(
+        &self,
+        row: &DueMessage,
+        attempt: i32,
+        error: &str,
+    ) -> Result<DeliveryOutcome, ApiError> {
+        let exhausted = attempt >= MAX_DELIVERY_ATTEMPTS;
+        let next_attempt_at = (!exhausted).then(|| Utc::now() + retry_delay(attempt));
+
+        sqlx::query(
+            "UPDATE legacy_messages \
+             SET status = $2, delivery_attempts = $3, next_attempt_at = $4, \
+                 last_delivery_error = $5, updated_at = NOW() \
+             WHERE id = $1",
+        )
+        .bind(row.id)
+        .bind(if exhausted { "failed" } else { "pending" })
+        .bind(attempt)
+        .bind(next_attempt_at)
+        .bind(error)
+        .execute(&self.db)
+        .await?;
+
+        MessageAccessAuditService::log_access(
+            &self.db,
+            Some(row.id),
+            row.owner_user_id,
+            MessageAccessAction::DeliveryFailed,
+            None,
+            None,
+            json!({
+                "channel": row.delivery_channel,
+                "attempt": attempt,
+                "error": error,
+                "next_attempt_at": next_attempt_at,
+            }),
+        )
+        .await?;
+
+        Ok(if exhausted {
+            DeliveryOutcome::Failed
+        } else {
+            DeliveryOutcome::Retrying
+        })
+    }
 }
 
 #[cfg(test)]
@@ -11,12 +11,15 @@ pub fn notify_plan_deactivated(_user_id: uuid::Uuid, _plan_id: uuid::Uuid) {
     // TODO: Implement email or in-app notification for plan deactivation
 }
 use crate::api_error::ApiError;
+use crate::audit_chain::{self, AuditStream};
+use crate::break_glass::BreakGlassService;
 use crate::notifications::{
     audit_action, entity_type, notif_type, AuditLogService, NotificationService,
 };
 use crate::yield_service::OnChainYieldService;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::{PgPool, Postgres, QueryBuilder};
 use std::collections::{BTreeMap, HashSet};
@@ -55,7 +58,7 @@ impl FromStr for CurrencyPreference {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct DueForClaimPlan {
     pub id: Uuid,
     pub user_id: Uuid,
@@ -81,7 +84,7 @@ pub struct DueForClaimPlan {
 }
 
 /// Plan details including beneficiary
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct PlanWithBeneficiary {
     pub id: Uuid,
     pub user_id: Uuid,
@@ -104,7 +107,7 @@ pub struct PlanWithBeneficiary {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, JsonSchema)]
 pub struct CreatePlanRequest {
     pub title: String,
     pub description: Option<String>,
@@ -117,7 +120,7 @@ pub struct CreatePlanRequest {
     pub two_fa_code: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct ClaimPlanRequest {
     pub beneficiary_email: String,
     pub two_fa_code: String,
@@ -129,8 +132,8 @@ struct PlanRowFull {
     user_id: Uuid,
     title: String,
     description: Option<String>,
-    fee: String,
-    net_amount: String,
+    fee: rust_decimal::Decimal,
+    net_amount: rust_decimal::Decimal,
     status: String,
     contract_plan_id: Option<i64>,
     distribution_method: Option<String>,
@@ -152,14 +155,8 @@ fn plan_row_to_plan_with_beneficiary(row: &PlanRowFull) -> Result<PlanWithBenefi
         user_id: row.user_id,
         title: row.title.clone(),
         description: row.description.clone(),
-        fee: row
-            .fee
-            .parse()
-            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to parse fee: {e}")))?,
-        net_amount: row
-            .net_amount
-            .parse()
-            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to parse net_amount: {e}")))?,
+        fee: row.fee,
+        net_amount: row.net_amount,
         status: row.status.clone(),
         contract_plan_id: row.contract_plan_id,
         distribution_method: row.distribution_method.clone(),
@@ -355,7 +352,8 @@ impl PlanService {
         )
         VALUES ($1, $2, $3, $4, $5, 'pending', $6, $7, $8, $9)
         RETURNING id, user_id, title, description, fee, net_amount, status,
-                  contract_plan_id, distribution_method, is_active, contract_created_at,
+                  contract_plan_id, distribution_method, is_active, is_paused,
+                  risk_override_enabled, contract_created_at,
                   beneficiary_name, bank_account_number, bank_name, currency_preference,
                   created_at, updated_at
         "#,
@@ -363,8 +361,8 @@ impl PlanService {
         .bind(user_id)
         .bind(&req.title)
         .bind(&req.description)
-        .bind(req.fee.to_string())
-        .bind(req.net_amount.to_string())
+        .bind(req.fee)
+        .bind(req.net_amount)
         .bind(&beneficiary_name)
         .bind(&bank_account_number)
         .bind(&bank_name)
@@ -1025,7 +1023,7 @@ impl FromStr for KycStatus {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
 pub struct KycRecord {
     pub user_id: Uuid,
     pub status: String,
@@ -1331,7 +1329,7 @@ impl UserMetricsService {
 
 // ── Plan Statistics ───────────────────────────────────────────────────────────
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct PlanStatistics {
     pub total_plans: i64,
     pub active_plans: i64,
@@ -1341,7 +1339,7 @@ pub struct PlanStatistics {
     pub by_status: Vec<PlanStatusCount>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct PlanStatusCount {
     pub status: String,
     pub count: i64,
@@ -1430,13 +1428,13 @@ impl PlanStatisticsService {
 
 // ── Revenue Metrics ───────────────────────────────────────────────────────────
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct RevenueMetric {
     pub date: String,
     pub amount: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct RevenueMetricsResponse {
     pub range: String,
     pub data: Vec<RevenueMetric>,
@@ -1560,7 +1558,7 @@ impl LendingMonitoringService {
 
 // ── Yield Reporting ──────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct YieldReportFilters {
     pub asset_code: Option<String>,
@@ -1568,7 +1566,7 @@ pub struct YieldReportFilters {
     pub plan_id: Option<Uuid>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct YieldVaultSummary {
     pub asset_code: String,
@@ -1580,7 +1578,7 @@ pub struct YieldVaultSummary {
     pub last_accrual_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct YieldSummaryResponse {
     pub filters: YieldReportFilters,
@@ -1591,7 +1589,7 @@ pub struct YieldSummaryResponse {
     pub generated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct EarningsHistoryPoint {
     pub period: String,
@@ -1600,7 +1598,7 @@ pub struct EarningsHistoryPoint {
     pub event_count: i64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct EarningsHistoryResponse {
     pub filters: YieldReportFilters,
@@ -1954,7 +1952,7 @@ impl FromStr for CollateralType {
 }
 
 /// Request to simulate a loan
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct LoanSimulationRequest {
     /// Amount the user wants to borrow in USDC
     pub loan_amount: rust_decimal::Decimal,
@@ -1967,7 +1965,7 @@ pub struct LoanSimulationRequest {
 }
 
 /// Response containing loan simulation results
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct LoanSimulationResult {
     /// Input parameters
     pub loan_amount: rust_decimal::Decimal,
@@ -1995,7 +1993,7 @@ pub struct LoanSimulationResult {
 }
 
 /// Record of a simulation stored in the database
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
 pub struct LoanSimulationRecord {
     pub id: Uuid,
     pub user_id: Uuid,
@@ -2617,25 +2615,25 @@ mod tests {
 
 // ── Emergency Admin Controls ──────────────────────────────────────────────────
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PausePlanRequest {
     pub plan_id: Uuid,
     pub reason: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct UnpausePlanRequest {
     pub plan_id: Uuid,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RiskOverrideRequest {
     pub plan_id: Uuid,
     pub enabled: bool,
     pub reason: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
 pub struct EmergencyContact {
     pub id: Uuid,
     pub user_id: Uuid,
@@ -2649,7 +2647,7 @@ pub struct EmergencyContact {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct CreateEmergencyContactRequest {
     pub name: String,
     pub relationship: String,
@@ -2659,7 +2657,7 @@ pub struct CreateEmergencyContactRequest {
     pub notes: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct UpdateEmergencyContactRequest {
     pub name: String,
     pub relationship: String,
@@ -2689,18 +2687,20 @@ pub struct EmergencyAccessGrant {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
 pub struct EmergencyAccessAuditLog {
     pub id: Uuid,
     pub grant_id: Uuid,
     pub user_id: Uuid,
     pub emergency_contact_id: Uuid,
     pub action: String,
+    /// The grant permission the action relied on, if any.
+    pub permission: Option<String>,
     pub metadata: serde_json::Value,
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize, Default, JsonSchema)]
 pub struct EmergencyAccessAuditLogFilters {
     pub action: Option<String>,
     pub grant_id: Option<Uuid>,
@@ -2708,14 +2708,14 @@ pub struct EmergencyAccessAuditLogFilters {
     pub limit: Option<u32>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct CreateEmergencyAccessGrantRequest {
     pub emergency_contact_id: Uuid,
     pub permissions: Vec<String>,
     pub expires_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct RevokeEmergencyAccessGrantRequest {
     pub reason: Option<String>,
 }
@@ -2727,7 +2727,7 @@ pub struct EmergencyAccessActionResponse {
     pub message: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
 pub struct EmergencyAccessRiskAlert {
     pub id: Uuid,
     pub grant_id: Uuid,
@@ -2740,7 +2740,7 @@ pub struct EmergencyAccessRiskAlert {
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct EmergencyAccessDashboardItem {
     pub grant_id: Uuid,
     pub emergency_contact_id: Uuid,
@@ -2751,7 +2751,7 @@ pub struct EmergencyAccessDashboardItem {
     pub expires_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct EmergencyAccessDashboardResponse {
     pub active_access_count: usize,
     pub grants: Vec<EmergencyAccessDashboardItem>,
@@ -2759,7 +2759,7 @@ pub struct EmergencyAccessDashboardResponse {
 
 // ─── Emergency Access Sessions (Issue #306) ─────────────────────────────────
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
 pub struct EmergencyAccessSession {
     pub id: Uuid,
     pub grant_id: Uuid,
@@ -2773,7 +2773,16 @@ pub struct EmergencyAccessSession {
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Deserialize)]
+/// A session heartbeat, with the scope the session currently holds so the
+/// client can reflect any change the owner made to the grant.
+#[derive(Debug, Clone, Serialize)]
+pub struct EmergencySessionHeartbeat {
+    #[serde(flatten)]
+    pub session: EmergencyAccessSession,
+    pub scope: crate::emergency_scope::EmergencyScope,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct StartSessionRequest {
     pub grant_id: Uuid,
     pub ip_address: Option<String>,
@@ -3176,7 +3185,7 @@ impl EmergencyAccessService {
         }
     }
 
-    async fn log_action(
+    pub(crate) async fn log_action(
         executor: impl sqlx::PgExecutor<'_>,
         grant_id: Uuid,
         user_id: Uuid,
@@ -3184,19 +3193,40 @@ impl EmergencyAccessService {
         action: &str,
         metadata: serde_json::Value,
     ) -> Result<(), ApiError> {
-        sqlx::query(
-            r#"
-            INSERT INTO emergency_access_audit_logs (
-                grant_id, user_id, emergency_contact_id, action, metadata
-            )
-            VALUES ($1, $2, $3, $4, $5)
-            "#,
-        )
-        .bind(grant_id)
-        .bind(user_id)
-        .bind(contact_id)
-        .bind(action)
-        .bind(metadata)
+        let entry = EmergencyAccessAuditLog {
+            id: Uuid::new_v4(),
+            grant_id,
+            user_id,
+            emergency_contact_id: contact_id,
+            action: action.to_string(),
+            permission: None,
+            metadata,
+            created_at: audit_chain::entry_timestamp(Utc::now()),
+        };
+        Self::write_audit_log(executor, &entry).await
+    }
+
+    /// Append an entry to the emergency access audit log's hash chain.
+    pub(crate) async fn write_audit_log(
+        executor: impl sqlx::PgExecutor<'_>,
+        entry: &EmergencyAccessAuditLog,
+    ) -> Result<(), ApiError> {
+        let content_hash = audit_chain::content_hash(entry)?;
+        sqlx::query(&audit_chain::chained_insert_sql(
+            AuditStream::EmergencyAccess,
+            "id, grant_id, user_id, emergency_contact_id, action, permission, metadata, \
+             created_at",
+            "$2, $3, $4, $5, $6, $7, $8, $9",
+        ))
+        .bind(content_hash.as_slice())
+        .bind(entry.id)
+        .bind(entry.grant_id)
+        .bind(entry.user_id)
+        .bind(entry.emergency_contact_id)
+        .bind(&entry.action)
+        .bind(&entry.permission)
+        .bind(&entry.metadata)
+        .bind(entry.created_at)
         .execute(executor)
         .await?;
 
@@ -3370,7 +3400,8 @@ impl EmergencyAccessService {
         let limit = filters.limit.unwrap_or(50).min(100) as i64;
 
         let mut query = QueryBuilder::<Postgres>::new(
-            "SELECT id, grant_id, user_id, emergency_contact_id, action, metadata, created_at \
+            "SELECT id, grant_id, user_id, emergency_contact_id, action, permission, metadata, \
+                    created_at \
              FROM emergency_access_audit_logs WHERE user_id = ",
         );
         query.push_bind(user_id);
@@ -3797,40 +3828,15 @@ impl EmergencyAdminService {
 pub struct EmergencySessionService;
 
 impl EmergencySessionService {
-    /// Start a new session for an active grant.
+    /// Start a new session for a grant in force that the user holds as
+    /// emergency contact.
     pub async fn start_session(
         pool: &PgPool,
         user_id: Uuid,
         req: &StartSessionRequest,
     ) -> Result<EmergencyAccessSession, ApiError> {
-        // Verify the grant exists, belongs to user, and is active
-        let grant = sqlx::query_as::<_, EmergencyAccessGrant>(
-            r#"
-            SELECT id, user_id, emergency_contact_id, permissions, expires_at, is_active,
-                   revoked_at, created_at, updated_at
-            FROM emergency_access_grants
-            WHERE id = $1 AND user_id = $2
-            "#,
-        )
-        .bind(req.grant_id)
-        .bind(user_id)
-        .fetch_optional(pool)
-        .await?
-        .ok_or_else(|| {
-            ApiError::NotFound(format!("Emergency access grant {} not found", req.grant_id))
-        })?;
-
-        if !grant.is_active {
-            return Err(ApiError::BadRequest(
-                "Cannot start session for an inactive grant".to_string(),
-            ));
-        }
-
-        if grant.expires_at <= Utc::now() {
-            return Err(ApiError::BadRequest(
-                "Cannot start session for an expired grant".to_string(),
-            ));
-        }
+        let mut conn = pool.acquire().await?;
+        let grant = BreakGlassService::effective_grant(&mut conn, user_id, req.grant_id).await?;
 
         let session = sqlx::query_as::<_, EmergencyAccessSession>(
             r#"
@@ -3847,18 +3853,21 @@ impl EmergencySessionService {
         .bind(grant.emergency_contact_id)
         .bind(&req.ip_address)
         .bind(&req.user_agent)
-        .fetch_one(pool)
+        .fetch_one(&mut *conn)
         .await?;
 
         Ok(session)
     }
 
-    /// Update the heartbeat timestamp for an active session.
+    /// Update the heartbeat timestamp for an active session and return the
+    /// grant's current scope. A session whose grant is no longer in force is
+    /// ended instead.
     pub async fn heartbeat(
         pool: &PgPool,
         user_id: Uuid,
         session_id: Uuid,
-    ) -> Result<EmergencyAccessSession, ApiError> {
+    ) -> Result<EmergencySessionHeartbeat, ApiError> {
+        let mut tx = pool.begin().await?;
         let session = sqlx::query_as::<_, EmergencyAccessSession>(
             r#"
             UPDATE emergency_access_sessions
@@ -3870,11 +3879,25 @@ impl EmergencySessionService {
         )
         .bind(session_id)
         .bind(user_id)
-        .fetch_optional(pool)
+        .fetch_optional(&mut *tx)
         .await?
         .ok_or_else(|| ApiError::NotFound(format!("Active session {} not found", session_id)))?;
 
-        Ok(session)
+        match BreakGlassService::effective_grant(&mut tx, user_id, session.grant_id).await {
+            Ok(grant) => {
+                tx.commit().await?;
+                Ok(EmergencySessionHeartbeat {
+                    session,
+                    scope: grant.scope,
+                })
+            }
+            Err(ApiError::Forbidden(message)) => {
+                Self::end_sessions_for_grant(&mut *tx, session.grant_id).await?;
+                tx.commit().await?;
+                Err(ApiError::Forbidden(message))
+            }
+            Err(e) => Err(e),
+        }
     }
 
     /// End an active session.
@@ -3944,14 +3967,14 @@ impl EmergencySessionService {
 
 // ── Emergency Access Analytics (Issue #306) ─────────────────────────────────
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct EmergencyAccessMetric {
     pub date: String,
     pub count: i64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct EmergencyAccessMetrics {
     pub total_grants: i64,
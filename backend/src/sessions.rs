@@ -0,0 +1,463 @@
+//! Login sessions and refresh tokens.
+//!
+//! Each login opens a row in `sessions`. The access token handed out is a
+//! short-lived JWT whose `sid` claim names the session; the session's refresh
+//! token is only ever stored as a SHA-256 hash and is rotated every time it is
+//! used. Presenting a refresh token that has already been rotated away means
+//! it was copied, so the whole session is revoked.
+//!
+//! Revoking a session (logout, the user revoking a device, or an admin forcing
+//! a logout) stops its access tokens at the next request. The auth extractors
+//! check `sid` against [`SessionRegistry`], which caches each session's state
+//! for [`REVOCATION_CACHE_TTL`] so the check does not cost a query per
+//! request. Revocations made through this instance take effect immediately;
+//! other instances notice within the cache TTL.
+
+use crate::api_error::ApiError;
+use crate::notifications::{audit_action, entity_type, AuditLogService};
+use crate::witness_link::ClientEvidence;
+use base64::Engine as _;
+use chrono::{DateTime, Duration, Utc};
+use ring::rand::{SecureRandom, SystemRandom};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::Instant;
+use uuid::Uuid;
+
+/// Lifetime of an access token.
+pub const ACCESS_TOKEN_TTL_MINUTES: i64 = 15;
+
+/// Lifetime of a session (and so of its refresh tokens).
+pub const REFRESH_TOKEN_TTL_DAYS: i64 = 30;
+
+/// How long a session's revocation state is trusted before it is re-read.
+pub const REVOCATION_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Cached entries beyond this are pruned of stale ones.
+const MAX_CACHED_SESSIONS: usize = 50_000;
+
+pub mod revoke_reason {
+    pub const LOGOUT: &str = "logout";
+    pub const REVOKED_BY_USER: &str = "revoked_by_user";
+    pub const REVOKED_BY_ADMIN: &str = "revoked_by_admin";
+    pub const REFRESH_TOKEN_REUSED: &str = "refresh_token_reused";
+}
+
+/// Who a session belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Principal {
+    User(Uuid),
+    Admin(Uuid),
+}
+
+impl Principal {
+    pub(crate) fn ids(self) -> (Option<Uuid>, Option<Uuid>) {
+        match self {
+            Principal::User(id) => (Some(id), None),
+            Principal::Admin(id) => (None, Some(id)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Session {
+    pub id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub admin_id: Option<Uuid>,
+    pub device_name: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub revoked_reason: Option<String>,
+    /// How the session was signed in; see [`crate::auth::auth_method`].
+    pub auth_methods: Vec<String>,
+}
+
+impl Session {
+    pub fn principal(&self) -> Principal {
+        match (self.user_id, self.admin_id) {
+            (Some(user_id), _) => Principal::User(user_id),
+            (None, Some(admin_id)) => Principal::Admin(admin_id),
+            (None, None) => unreachable!("sessions always belong to a user or an admin"),
+        }
+    }
+}
+
+/// A session as listed to its owner.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionView {
+    #[serde(flatten)]
+    pub session: Session,
+    /// Whether this is the session the request was made with.
+    pub current: bool,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
+const SESSION_COLUMNS: &str = "id, user_id, admin_id, device_name, user_agent, \
+     host(ip_address) AS ip_address, created_at, last_used_at, expires_at, \
+     revoked_at, revoked_reason, auth_methods";
+
+fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+fn generate_refresh_token() -> Result<String, ApiError> {
+    let mut bytes = [0u8; 32];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| ApiError::Internal(anyhow::anyhow!("Failed to generate refresh token")))?;
+    Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes))
+}
+
+fn device_name(name: Option<&str>) -> Option<String> {
+    name.map(str::trim)
+        .filter(|n| !n.is_empty())
+        .map(|n| n.chars().take(100).collect())
+}
+
+pub struct SessionService;
+
+impl SessionService {
+    /// Open a session and return it with its first refresh token.
+    pub async fn create(
+        db: &PgPool,
+        principal: Principal,
+        client: &ClientEvidence,
+        device: Option<&str>,
+        auth_methods: &[&str],
+    ) -> Result<(Session, String), ApiError> {
+        let refresh_token = generate_refresh_token()?;
+        let (user_id, admin_id) = principal.ids();
+        let session = sqlx::query_as::<_, Session>(&format!(
+            "INSERT INTO sessions \
+                 (user_id, admin_id, refresh_token_hash, device_name, user_agent, ip_address, \
+                  expires_at, auth_methods) \
+             VALUES ($1, $2, $3, $4, $5, CAST($6 AS INET), $7, $8) \
+             RETURNING {SESSION_COLUMNS}"
+        ))
+        .bind(user_id)
+        .bind(admin_id)
+        .bind(hash_token(&refresh_token))
+        .bind(device_name(device))
+        .bind(&client.user_agent)
+        .bind(&client.ip_address)
+        .bind(Utc::now() + Duration::days(REFRESH_TOKEN_TTL_DAYS))
+        .bind(auth_methods)
+        .fetch_one(db)
+        .await?;
+
+        AuditLogService::log(
+            db,
+            user_id,
+            admin_id,
+            audit_action::SESSION_CREATED,
+            Some(session.id),
+            Some(entity_type::SESSION),
+            None,
+            None,
+            Some(json!({
+                "device_name": session.device_name,
+                "user_agent": session.user_agent,
+                "ip_address": session.ip_address,
+                "auth_methods": session.auth_methods,
+            })),
+        )
+        .await?;
+        Ok((session, refresh_token))
+    }
+
+    /// Exchange a refresh token for a new one on the same session. Replaying
+    /// a token that has already been exchanged revokes the session.
+    pub async fn rotate(
+        db: &PgPool,
+        registry: &SessionRegistry,
+        refresh_token: &str,
+        client: &ClientEvidence,
+    ) -> Result<(Session, String), ApiError> {
+        #[derive(sqlx::FromRow)]
+        struct Row {
+            #[sqlx(flatten)]
+            session: Session,
+            reused: bool,
+        }
+
+        let token_hash = hash_token(refresh_token);
+        let mut tx = db.begin().await?;
+        let row = sqlx::query_as::<_, Row>(&format!(
+            "SELECT {SESSION_COLUMNS}, refresh_token_hash <> $1 AS reused \
+             FROM sessions \
+             WHERE refresh_token_hash = $1 OR previous_refresh_token_hash = $1 \
+             FOR UPDATE"
+        ))
+        .bind(&token_hash)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(ApiError::Unauthorized)?;
+        let session = row.session;
+        if session.revoked_at.is_some() || session.expires_at <= Utc::now() {
+            return Err(ApiError::Unauthorized);
+        }
+
+        if row.reused {
+            Self::mark_revoked(&mut tx, session.id, revoke_reason::REFRESH_TOKEN_REUSED).await?;
+            let (user_id, admin_id) = session.principal().ids();
+            AuditLogService::log(
+                &mut *tx,
+                user_id,
+                admin_id,
+                audit_action::SESSION_REFRESH_REUSED,
+                Some(session.id),
+                Some(entity_type::SESSION),
+                None,
+                None,
+                Some(json!({
+                    "user_agent": client.user_agent,
+                    "ip_address": client.ip_address,
+                })),
+            )
+            .await?;
+            tx.commit().await?;
+            registry.mark_revoked(session.id);
+            return Err(ApiError::Unauthorized);
+        }
+
+        let refresh_token = generate_refresh_token()?;
+        let session = sqlx::query_as::<_, Session>(&format!(
+            "UPDATE sessions \
+             SET previous_refresh_token_hash = refresh_token_hash, refresh_token_hash = $2, \
+                 last_used_at = NOW(), \
+                 user_agent = COALESCE($3, user_agent), \
+                 ip_address = COALESCE(CAST($4 AS INET), ip_address) \
+             WHERE id = $1 \
+             RETURNING {SESSION_COLUMNS}"
+        ))
+        .bind(session.id)
+        .bind(hash_token(&refresh_token))
+        .bind(&client.user_agent)
+        .bind(&client.ip_address)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok((session, refresh_token))
+    }
+
+    /// A user's sessions that are still usable, most recently used first.
+    pub async fn list_for_user(
+        db: &PgPool,
+        user_id: Uuid,
+        current: Option<Uuid>,
+    ) -> Result<Vec<SessionView>, ApiError> {
+        let sessions = sqlx::query_as::<_, Session>(&format!(
+            "SELECT {SESSION_COLUMNS} FROM sessions \
+             WHERE user_id = $1 AND revoked_at IS NULL AND expires_at > NOW() \
+             ORDER BY last_used_at DESC"
+        ))
+        .bind(user_id)
+        .fetch_all(db)
+        .await?;
+        Ok(sessions
+            .into_iter()
+            .map(|session| SessionView {
+                current: Some(session.id) == current,
+                session,
+            })
+            .collect())
+    }
+
+    /// Revoke one of the principal's own sessions.
+    pub async fn revoke(
+        db: &PgPool,
+        registry: &SessionRegistry,
+        principal: Principal,
+        session_id: Uuid,
+        reason: &str,
+    ) -> Result<(), ApiError> {
+        let (user_id, admin_id) = principal.ids();
+        let mut tx = db.begin().await?;
+        let revoked = sqlx::query(
+            "UPDATE sessions SET revoked_at = NOW(), revoked_reason = $4 \
+             WHERE id = $1 AND user_id IS NOT DISTINCT FROM $2 \
+               AND admin_id IS NOT DISTINCT FROM $3 AND revoked_at IS NULL",
+        )
+        .bind(session_id)
+        .bind(user_id)
+        .bind(admin_id)
+        .bind(reason)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if revoked == 0 {
+            return Err(ApiError::NotFound(format!(
+                "Session {session_id} not found"
+            )));
+        }
+        AuditLogService::log(
+            &mut *tx,
+            user_id,
+            admin_id,
+            audit_action::SESSION_REVOKED,
+            Some(session_id),
+            Some(entity_type::SESSION),
+            None,
+            None,
+            Some(json!({ "reason": reason })),
+        )
+        .await?;
+        tx.commit().await?;
+        registry.mark_revoked(session_id);
+        Ok(())
+    }
+
+    /// Admin: revoke every open session of a user or another admin. Returns
+    /// how many sessions were revoked.
+    pub async fn force_logout(
+        db: &PgPool,
+        registry: &SessionRegistry,
+        admin_id: Uuid,
+        principal: Principal,
+    ) -> Result<usize, ApiError> {
+        let (target_user, target_admin) = principal.ids();
+        let (entity_id, entity) = match principal {
+            Principal::User(id) => (id, entity_type::USER),
+            Principal::Admin(id) => (id, entity_type::ADMIN),
+        };
+        let mut tx = db.begin().await?;
+        let revoked: Vec<Uuid> = sqlx::query_scalar(
+            "UPDATE sessions \
+             SET revoked_at = NOW(), revoked_reason = $3, revoked_by_admin_id = $4 \
+             WHERE user_id IS NOT DISTINCT FROM $1 AND admin_id IS NOT DISTINCT FROM $2 \
+               AND revoked_at IS NULL \
+             RETURNING id",
+        )
+        .bind(target_user)
+        .bind(target_admin)
+        .bind(revoke_reason::REVOKED_BY_ADMIN)
+        .bind(admin_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        AuditLogService::log(
+            &mut *tx,
+            target_user,
+            Some(admin_id),
+            audit_action::SESSIONS_FORCE_REVOKED,
+            Some(entity_id),
+            Some(entity),
+            None,
+            None,
+            Some(json!({ "sessions": revoked })),
+        )
+        .await?;
+        tx.commit().await?;
+        for session_id in &revoked {
+            registry.mark_revoked(*session_id);
+        }
+        Ok(revoked.len())
+    }
+
+    async fn mark_revoked(
+        conn: &mut sqlx::PgConnection,
+        session_id: Uuid,
+        reason: &str,
+    ) -> Result<(), ApiError> {
+        sqlx::query("UPDATE sessions SET revoked_at = NOW(), revoked_reason = $2 WHERE id = $1")
+            .bind(session_id)
+            .bind(reason)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Cached view of which sessions are still active, consulted by the auth
+/// extractors on every request that carries a session-bound token.
+pub struct SessionRegistry {
+    db: PgPool,
+    ttl: std::time::Duration,
+    cache: RwLock<HashMap<Uuid, (bool, Instant)>>,
+}
+
+impl SessionRegistry {
+    pub fn new(db: PgPool) -> Self {
+        Self::with_ttl(db, REVOCATION_CACHE_TTL)
+    }
+
+    pub fn with_ttl(db: PgPool, ttl: std::time::Duration) -> Self {
+        Self {
+            db,
+            ttl,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Whether the session exists and has been neither revoked nor expired.
+    pub async fn is_active(&self, session_id: Uuid) -> Result<bool, ApiError> {
+        if let Some((active, checked_at)) = self
+            .cache
+            .read()
+            .expect("session cache poisoned")
+            .get(&session_id)
+        {
+            if checked_at.elapsed() < self.ttl {
+                return Ok(*active);
+            }
+        }
+
+        let active: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM sessions \
+                           WHERE id = $1 AND revoked_at IS NULL AND expires_at > NOW())",
+        )
+        .bind(session_id)
+        .fetch_one(&self.db)
+        .await?;
+        self.remember(session_id, active);
+        Ok(active)
+    }
+
+    /// Record a revocation made by this instance so it applies immediately.
+    pub fn mark_revoked(&self, session_id: Uuid) {
+        self.remember(session_id, false);
+    }
+
+    fn remember(&self, session_id: Uuid, active: bool) {
+        let mut cache = self.cache.write().expect("session cache poisoned");
+        if cache.len() >= MAX_CACHED_SESSIONS {
+            let ttl = self.ttl;
+            cache.retain(|_, (_, checked_at)| checked_at.elapsed() < ttl);
+        }
+        cache.insert(session_id, (active, Instant::now()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refresh_tokens_are_random_and_hashed() {
+        let first = generate_refresh_token().unwrap();
+        let second = generate_refresh_token().unwrap();
+        assert_ne!(first, second);
+        assert_eq!(first.len(), 43);
+        assert_eq!(hash_token(&first).len(), 64);
+        assert_ne!(hash_token(&first), hash_token(&second));
+    }
+
+    #[test]
+    fn device_names_are_trimmed_and_bounded() {
+        assert_eq!(device_name(Some("  ")), None);
+        assert_eq!(device_name(Some(" Pixel 8 ")), Some("Pixel 8".to_string()));
+        assert_eq!(device_name(Some(&"x".repeat(300))).unwrap().len(), 100);
+    }
+}
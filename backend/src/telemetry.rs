@@ -1,14 +1,68 @@
+//! Logging and trace export.
+//!
+//! Logs go to stdout. When `OTEL_EXPORTER_OTLP_ENDPOINT` is set, spans are
+//! also exported over OTLP/gRPC to that collector, including the per-request
+//! span opened by [`crate::middleware::request_id_middleware`], so traces can
+//! be found by the `x-request-id` a client reports. The service is named
+//! `inheritx-backend` unless `OTEL_SERVICE_NAME` says otherwise; the other
+//! standard `OTEL_*` exporter variables are honoured too.
+
 use crate::api_error::ApiError;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::{runtime, trace, Resource};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+const DEFAULT_SERVICE_NAME: &str = "inheritx-backend";
+
+/// Install the global subscriber. Must run inside the Tokio runtime when OTLP
+/// export is enabled, as spans are exported in batches from a background task.
 pub fn init_tracing() -> Result<(), ApiError> {
+    let otel_layer = match otlp_tracer_provider()? {
+        Some(provider) => {
+            let tracer = provider.tracer(DEFAULT_SERVICE_NAME);
+            opentelemetry::global::set_tracer_provider(provider);
+            Some(tracing_opentelemetry::layer().with_tracer(tracer))
+        }
+        None => None,
+    };
+
     let _ = tracing_subscriber::registry()
         .with(
             tracing_subscriber::EnvFilter::try_from_default_env()
                 .unwrap_or_else(|_| "inheritx_backend=debug,tower_http=debug".into()),
         )
         .with(tracing_subscriber::fmt::layer())
+        .with(otel_layer)
         .try_init();
 
     Ok(())
 }
+
+/// Flush spans that have not been exported yet.
+pub fn shutdown_tracing() {
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
+fn otlp_tracer_provider() -> Result<Option<trace::TracerProvider>, ApiError> {
+    let endpoint_set = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+        .is_ok_and(|endpoint| !endpoint.trim().is_empty());
+    if !endpoint_set {
+        return Ok(None);
+    }
+
+    let service_name =
+        std::env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| DEFAULT_SERVICE_NAME.to_string());
+    let resource = Resource::default().merge(&Resource::new([KeyValue::new(
+        "service.name",
+        service_name,
+    )]));
+
+    let provider = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(opentelemetry_otlp::new_exporter().tonic())
+        .with_trace_config(trace::Config::default().with_resource(resource))
+        .install_batch(runtime::Tokio)
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to set up OTLP export: {e}")))?;
+    Ok(Some(provider))
+}
@@ -0,0 +1,137 @@
+//! Test authentication mode.
+//!
+//! Integration and end-to-end tests need to act as users and admins they have
+//! created straight in the database, without going through a login. With
+//! `TEST_AUTH_ENABLED=true` the backend lets them mint ordinary access tokens
+//! for those accounts, either in-process through [`TestTokenIssuer`] or over
+//! HTTP at `POST /api/test-auth/token`.
+//!
+//! The mode is off unless explicitly enabled, the route is not mounted when it
+//! is off, and [`Config::validate`] refuses to start with it enabled unless
+//! `APP_ENV` is explicitly `development` or `test`.
+
+use crate::api_error::ApiError;
+use crate::app::AppState;
+use crate::auth::{admin_access_token, user_access_token};
+use crate::config::Config;
+use axum::{extract::State, Json};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Issues access tokens for arbitrary accounts. Only constructible from a
+/// configuration with test auth enabled.
+pub struct TestTokenIssuer {
+    config: Config,
+    mfa_at: Option<i64>,
+}
+
+impl TestTokenIssuer {
+    pub fn from_config(config: &Config) -> Result<Self, ApiError> {
+        config.validate()?;
+        if !config.test_auth {
+            return Err(ApiError::Forbidden(
+                "Test authentication is not enabled".to_string(),
+            ));
+        }
+        Ok(Self {
+            config: config.clone(),
+            mfa_at: None,
+        })
+    }
+
+    /// Issue tokens recording a second-factor check at `mfa_at` (a Unix
+    /// timestamp), as if the account had just completed a step-up.
+    pub fn with_mfa_at(mut self, mfa_at: i64) -> Self {
+        self.mfa_at = Some(mfa_at);
+        self
+    }
+
+    pub fn user_token(&self, user_id: Uuid, email: &str) -> Result<String, ApiError> {
+        user_access_token(&self.config, user_id, email.to_string(), None, self.mfa_at)
+    }
+
+    pub fn admin_token(&self, admin_id: Uuid, email: &str, role: &str) -> Result<String, ApiError> {
+        admin_access_token(
+            &self.config,
+            admin_id,
+            email.to_string(),
+            role.to_string(),
+            None,
+            self.mfa_at,
+            Vec::new(),
+        )
+    }
+}
+
+/// Exactly one of `user_id` or `admin_id`.
+#[derive(Debug, Deserialize)]
+pub struct TestTokenRequest {
+    pub user_id: Option<Uuid>,
+    pub admin_id: Option<Uuid>,
+}
+
+/// Test auth: Issue an access token for an existing user or admin
+pub async fn issue_test_token(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<TestTokenRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let issuer = TestTokenIssuer::from_config(&state.config)?;
+    let token = match (req.user_id, req.admin_id) {
+        (Some(user_id), None) => {
+            let email: String = sqlx::query_scalar("SELECT email FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_optional(&state.db)
+                .await?
+                .ok_or_else(|| ApiError::NotFound(format!("User {user_id} not found")))?;
+            issuer.user_token(user_id, &email)?
+        }
+        (None, Some(admin_id)) => {
+            let (email, role): (String, String) =
+                sqlx::query_as("SELECT email, role FROM admins WHERE id = $1")
+                    .bind(admin_id)
+                    .fetch_optional(&state.db)
+                    .await?
+                    .ok_or_else(|| ApiError::NotFound(format!("Admin {admin_id} not found")))?;
+            issuer.admin_token(admin_id, &email, &role)?
+        }
+        _ => {
+            return Err(ApiError::BadRequest(
+                "Provide exactly one of user_id or admin_id".to_string(),
+            ))
+        }
+    };
+    Ok(Json(
+        json!({ "status": "success", "data": { "token": token } }),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(environment: Option<&str>, test_auth: bool) -> Config {
+        Config {
+            database_url: String::new(),
+            port: 0,
+            jwt_secret: "secret".to_string(),
+            environment: environment.map(str::to_string),
+            test_auth,
+            require_admin_passkeys: false,
+        }
+    }
+
+    #[test]
+    fn issuer_requires_test_auth_in_a_test_profile() {
+        assert!(TestTokenIssuer::from_config(&config(Some("development"), false)).is_err());
+        assert!(TestTokenIssuer::from_config(&config(Some("production"), true)).is_err());
+        assert!(TestTokenIssuer::from_config(&config(None, true)).is_err());
+        assert!(config(Some("Production"), true).validate().is_err());
+        assert!(config(Some("production"), false).validate().is_ok());
+
+        let issuer = TestTokenIssuer::from_config(&config(Some("test"), true)).unwrap();
+        let token = issuer.user_token(Uuid::new_v4(), "a@example.com").unwrap();
+        assert_eq!(token.split('.').count(), 3);
+    }
+}
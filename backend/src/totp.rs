@@ -0,0 +1,683 @@
+//! Authenticator-app two-factor authentication (RFC 6238 TOTP).
+//!
+//! Enrolling creates an unconfirmed credential and hands back its secret as
+//! base32 and as an `otpauth://` URI for the client to render as a QR code.
+//! The secret is encrypted under the active message data key with a key
+//! derived for [`SECRET_PURPOSE`]. The credential only becomes active once a
+//! first code is confirmed, at which point [`RECOVERY_CODE_COUNT`] single-use
+//! recovery codes are issued; they are stored as SHA-256 hashes.
+//!
+//! Sensitive routes use [`RequireStepUp`], which accepts a token only if its
+//! `mfa_at` claim is within [`STEP_UP_WINDOW_MINUTES`]. A fresh claim is
+//! obtained by presenting a code to `/api/auth/2fa/step-up`.
+
+use crate::api_error::ApiError;
+use crate::auth::{AuthenticatedUser, UserClaims};
+use crate::notifications::{audit_action, entity_type, AuditLogService};
+use crate::secure_messages::MessageEncryptionService;
+use crate::sessions::Principal;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use chrono::{DateTime, Duration, Utc};
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use sqlx::{FromRow, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+/// Length of a TOTP time step.
+pub const TOTP_STEP_SECONDS: i64 = 30;
+
+/// Digits in a TOTP code.
+pub const TOTP_DIGITS: u32 = 6;
+
+/// Codes from this many steps either side of now are accepted, to allow for
+/// clock drift on the authenticator.
+const TOTP_SKEW_STEPS: i64 = 1;
+
+/// Recovery codes issued on confirmation and regeneration.
+pub const RECOVERY_CODE_COUNT: usize = 10;
+
+/// How long a step-up verification satisfies [`RequireStepUp`].
+pub const STEP_UP_WINDOW_MINUTES: i64 = 5;
+
+/// Failed verifications in a row before the credential is locked.
+const MAX_FAILED_ATTEMPTS: i32 = 5;
+
+/// How long a credential stays locked after too many failures.
+const LOCKOUT_MINUTES: i64 = 15;
+
+const ISSUER: &str = "InheritX";
+
+const SECRET_PURPOSE: &[u8] = b"totp-secret";
+
+/// 160-bit secrets, the size RFC 4226 recommends for HMAC-SHA1.
+const SECRET_LEN: usize = 20;
+
+const RECOVERY_CODE_BYTES: usize = 10;
+
+/// Compute the HOTP value (RFC 4226) for `counter`.
+pub fn hotp(secret: &[u8], counter: u64) -> u32 {
+    let key = hmac::Key::new(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, secret);
+    let tag = hmac::sign(&key, &counter.to_be_bytes());
+    let digest = tag.as_ref();
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+    binary % 10u32.pow(TOTP_DIGITS)
+}
+
+/// The time step `unix_time` falls in.
+pub fn time_step(unix_time: i64) -> i64 {
+    unix_time.div_euclid(TOTP_STEP_SECONDS)
+}
+
+/// The TOTP code for `unix_time`, zero-padded to [`TOTP_DIGITS`].
+pub fn totp_code(secret: &[u8], unix_time: i64) -> String {
+    format!(
+        "{:0width$}",
+        hotp(secret, time_step(unix_time) as u64),
+        width = TOTP_DIGITS as usize
+    )
+}
+
+/// Find the step within the skew window that `code` was generated for.
+fn matching_step(secret: &[u8], code: &str, now: i64) -> Option<i64> {
+    let current = time_step(now);
+    (current - TOTP_SKEW_STEPS..=current + TOTP_SKEW_STEPS).find(|step| {
+        let expected = format!(
+            "{:0width$}",
+            hotp(secret, *step as u64),
+            width = TOTP_DIGITS as usize
+        );
+        constant_time_eq(expected.as_bytes(), code.as_bytes())
+    })
+}
+
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// RFC 4648 base32 without padding, as authenticator apps expect.
+pub fn base32_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+    let mut out = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = (buffer << 8) | u32::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    out
+}
+
+fn percent_encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'@' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
+
+/// The `otpauth://` URI authenticator apps read from a QR code.
+pub fn provisioning_uri(account: &str, secret_base32: &str) -> String {
+    format!(
+        "otpauth://totp/{issuer}:{account}?secret={secret_base32}&issuer={issuer}\
+         &algorithm=SHA1&digits={TOTP_DIGITS}&period={TOTP_STEP_SECONDS}",
+        issuer = percent_encode(ISSUER),
+        account = percent_encode(account),
+    )
+}
+
+/// Strip the separators users type or paste with a recovery code.
+fn normalize_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect::<String>()
+        .to_ascii_uppercase()
+}
+
+fn hash_recovery_code(code: &str) -> String {
+    hex::encode(Sha256::digest(normalize_code(code).as_bytes()))
+}
+
+fn random_bytes<const N: usize>() -> Result<[u8; N], ApiError> {
+    let mut bytes = [0u8; N];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| ApiError::Internal(anyhow::anyhow!("Failed to generate random bytes")))?;
+    Ok(bytes)
+}
+
+/// A recovery code formatted as `XXXX-XXXX-XXXX-XXXX`.
+fn generate_recovery_code() -> Result<String, ApiError> {
+    let encoded = base32_encode(&random_bytes::<RECOVERY_CODE_BYTES>()?);
+    Ok(encoded
+        .as_bytes()
+        .chunks(4)
+        .map(|chunk| std::str::from_utf8(chunk).expect("base32 is ascii"))
+        .collect::<Vec<_>>()
+        .join("-"))
+}
+
+/// Whether a step-up verified at `mfa_at` still counts at `now`.
+pub fn step_up_is_fresh(mfa_at: i64, now: i64) -> bool {
+    mfa_at <= now + TOTP_STEP_SECONDS && now - mfa_at <= STEP_UP_WINDOW_MINUTES * 60
+}
+
+#[derive(Debug, Serialize)]
+pub struct TotpEnrollment {
+    /// Base32 secret, for clients that cannot scan the QR code.
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TotpCodeRequest {
+    /// A TOTP code, or a recovery code where one is accepted.
+    pub code: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TwoFactorStatus {
+    pub totp_enabled: bool,
+    pub recovery_codes_remaining: i64,
+}
+
+#[derive(Debug, FromRow)]
+struct TotpCredential {
+    id: Uuid,
+    key_version: i32,
+    encrypted_secret: Vec<u8>,
+    secret_nonce: Vec<u8>,
+    confirmed_at: Option<DateTime<Utc>>,
+    last_used_step: Option<i64>,
+    failed_attempts: i32,
+    locked_until: Option<DateTime<Utc>>,
+}
+
+fn principal_entity(principal: Principal) -> (Uuid, &'static str) {
+    match principal {
+        Principal::User(id) => (id, entity_type::USER),
+        Principal::Admin(id) => (id, entity_type::ADMIN),
+    }
+}
+
+pub struct TotpService;
+
+impl TotpService {
+    /// Start enrollment, replacing any unconfirmed credential.
+    pub async fn enroll(
+        db: &PgPool,
+        principal: Principal,
+        account: &str,
+    ) -> Result<TotpEnrollment, ApiError> {
+        let (user_id, admin_id) = principal.ids();
+        let mut tx = db.begin().await?;
+        if let Some(existing) = Self::credential(&mut tx, principal).await? {
+            if existing.confirmed_at.is_some() {
+                return Err(ApiError::Conflict(
+                    "Two-factor authentication is already enabled".to_string(),
+                ));
+            }
+            sqlx::query("DELETE FROM totp_credentials WHERE id = $1")
+                .bind(existing.id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        let secret = random_bytes::<SECRET_LEN>()?;
+        let (key_version, encrypted_secret, secret_nonce) =
+            MessageEncryptionService::encrypt_for_purpose(db, SECRET_PURPOSE, &secret).await?;
+        sqlx::query(
+            r#"
+            INSERT INTO totp_credentials (user_id, admin_id, key_version, encrypted_secret, secret_nonce)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(user_id)
+        .bind(admin_id)
+        .bind(key_version)
+        .bind(&encrypted_secret)
+        .bind(&secret_nonce)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        let secret = base32_encode(&secret);
+        Ok(TotpEnrollment {
+            otpauth_uri: provisioning_uri(account, &secret),
+            secret,
+        })
+    }
+
+    /// Activate a pending credential with its first code. Returns the
+    /// recovery codes, which are never shown again.
+    pub async fn confirm(
+        db: &PgPool,
+        principal: Principal,
+        code: &str,
+    ) -> Result<Vec<String>, ApiError> {
+        let (user_id, admin_id) = principal.ids();
+        let mut tx = db.begin().await?;
+        let credential = Self::credential(&mut tx, principal).await?.ok_or_else(|| {
+            ApiError::NotFound("No two-factor enrollment in progress".to_string())
+        })?;
+        if credential.confirmed_at.is_some() {
+            return Err(ApiError::Conflict(
+                "Two-factor authentication is already enabled".to_string(),
+            ));
+        }
+
+        let secret = Self::secret(db, &credential).await?;
+        let step = matching_step(&secret, code.trim(), Utc::now().timestamp())
+            .ok_or_else(|| ApiError::BadRequest("Invalid authentication code".to_string()))?;
+        sqlx::query(
+            "UPDATE totp_credentials SET confirmed_at = NOW(), last_used_step = $2 WHERE id = $1",
+        )
+        .bind(credential.id)
+        .bind(step)
+        .execute(&mut *tx)
+        .await?;
+        let codes = Self::replace_recovery_codes(&mut tx, principal).await?;
+
+        let (entity_id, entity) = principal_entity(principal);
+        AuditLogService::log(
+            &mut *tx,
+            user_id,
+            admin_id,
+            audit_action::TOTP_ENABLED,
+            Some(entity_id),
+            Some(entity),
+            None,
+            None,
+            None,
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(codes)
+    }
+
+    /// Whether `principal` has a confirmed TOTP credential.
+    pub async fn is_enabled(db: &PgPool, principal: Principal) -> Result<bool, ApiError> {
+        let (user_id, admin_id) = principal.ids();
+        let enabled: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM totp_credentials
+                WHERE (user_id = $1 OR admin_id = $2) AND confirmed_at IS NOT NULL
+            )
+            "#,
+        )
+        .bind(user_id)
+        .bind(admin_id)
+        .fetch_one(db)
+        .await?;
+        Ok(enabled)
+    }
+
+    pub async fn status(db: &PgPool, principal: Principal) -> Result<TwoFactorStatus, ApiError> {
+        let (user_id, admin_id) = principal.ids();
+        let recovery_codes_remaining: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM recovery_codes
+            WHERE (user_id = $1 OR admin_id = $2) AND used_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(admin_id)
+        .fetch_one(db)
+        .await?;
+        Ok(TwoFactorStatus {
+            totp_enabled: Self::is_enabled(db, principal).await?,
+            recovery_codes_remaining,
+        })
+    }
+
+    /// Check a TOTP code or an unused recovery code. A TOTP code is accepted
+    /// once; repeated failures lock the credential for [`LOCKOUT_MINUTES`].
+    pub async fn verify(db: &PgPool, principal: Principal, code: &str) -> Result<(), ApiError> {
+        let (user_id, admin_id) = principal.ids();
+        let (entity_id, entity) = principal_entity(principal);
+        let mut tx = db.begin().await?;
+        let credential = Self::credential(&mut tx, principal)
+            .await?
+            .filter(|c| c.confirmed_at.is_some())
+            .ok_or_else(|| {
+                ApiError::Forbidden("Two-factor authentication is not enabled".to_string())
+            })?;
+        if credential
+            .locked_until
+            .is_some_and(|until| until > Utc::now())
+        {
+            return Err(ApiError::TooManyRequests(
+                "Too many failed two-factor attempts; try again later".to_string(),
+            ));
+        }
+
+        let code = code.trim();
+        let is_totp =
+            code.len() == TOTP_DIGITS as usize && code.bytes().all(|b| b.is_ascii_digit());
+        let verified = if is_totp {
+            let secret = Self::secret(db, &credential).await?;
+            match matching_step(&secret, code, Utc::now().timestamp()) {
+                Some(step) if credential.last_used_step.is_none_or(|last| step > last) => {
+                    sqlx::query("UPDATE totp_credentials SET last_used_step = $2 WHERE id = $1")
+                        .bind(credential.id)
+                        .bind(step)
+                        .execute(&mut *tx)
+                        .await?;
+                    true
+                }
+                _ => false,
+            }
+        } else {
+            let used: Option<Uuid> = sqlx::query_scalar(
+                r#"
+                UPDATE recovery_codes SET used_at = NOW()
+                WHERE code_hash = $1 AND (user_id = $2 OR admin_id = $3) AND used_at IS NULL
+                RETURNING id
+                "#,
+            )
+            .bind(hash_recovery_code(code))
+            .bind(user_id)
+            .bind(admin_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+            if used.is_some() {
+                AuditLogService::log(
+                    &mut *tx,
+                    user_id,
+                    admin_id,
+                    audit_action::RECOVERY_CODE_USED,
+                    Some(entity_id),
+                    Some(entity),
+                    None,
+                    None,
+                    None,
+                )
+                .await?;
+            }
+            used.is_some()
+        };
+
+        if verified {
+            sqlx::query(
+                "UPDATE totp_credentials SET failed_attempts = 0, locked_until = NULL WHERE id = $1",
+            )
+            .bind(credential.id)
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            return Ok(());
+        }
+
+        let failed_attempts = credential.failed_attempts + 1;
+        let locked_until = (failed_attempts >= MAX_FAILED_ATTEMPTS)
+            .then(|| Utc::now() + Duration::minutes(LOCKOUT_MINUTES));
+        sqlx::query(
+            "UPDATE totp_credentials SET failed_attempts = $2, locked_until = $3 WHERE id = $1",
+        )
+        .bind(credential.id)
+        .bind(if locked_until.is_some() {
+            0
+        } else {
+            failed_attempts
+        })
+        .bind(locked_until)
+        .execute(&mut *tx)
+        .await?;
+        AuditLogService::log(
+            &mut *tx,
+            user_id,
+            admin_id,
+            audit_action::TOTP_VERIFICATION_FAILED,
+            Some(entity_id),
+            Some(entity),
+            None,
+            None,
+            Some(json!({ "failed_attempts": failed_attempts, "locked": locked_until.is_some() })),
+        )
+        .await?;
+        tx.commit().await?;
+        Err(ApiError::Unauthorized)
+    }
+
+    /// Remove the credential and its recovery codes.
+    pub async fn disable(db: &PgPool, principal: Principal) -> Result<(), ApiError> {
+        let (user_id, admin_id) = principal.ids();
+        let (entity_id, entity) = principal_entity(principal);
+        let mut tx = db.begin().await?;
+        let removed =
+            sqlx::query("DELETE FROM totp_credentials WHERE user_id = $1 OR admin_id = $2")
+                .bind(user_id)
+                .bind(admin_id)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+        if removed == 0 {
+            return Err(ApiError::NotFound(
+                "Two-factor authentication is not enabled".to_string(),
+            ));
+        }
+        sqlx::query("DELETE FROM recovery_codes WHERE user_id = $1 OR admin_id = $2")
+            .bind(user_id)
+            .bind(admin_id)
+            .execute(&mut *tx)
+            .await?;
+        AuditLogService::log(
+            &mut *tx,
+            user_id,
+            admin_id,
+            audit_action::TOTP_DISABLED,
+            Some(entity_id),
+            Some(entity),
+            None,
+            None,
+            None,
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Invalidate every recovery code and issue a fresh set.
+    pub async fn regenerate_recovery_codes(
+        db: &PgPool,
+        principal: Principal,
+    ) -> Result<Vec<String>, ApiError> {
+        if !Self::is_enabled(db, principal).await? {
+            return Err(ApiError::BadRequest(
+                "Two-factor authentication is not enabled".to_string(),
+            ));
+        }
+        let (user_id, admin_id) = principal.ids();
+        let (entity_id, entity) = principal_entity(principal);
+        let mut tx = db.begin().await?;
+        let codes = Self::replace_recovery_codes(&mut tx, principal).await?;
+        AuditLogService::log(
+            &mut *tx,
+            user_id,
+            admin_id,
+            audit_action::RECOVERY_CODES_REGENERATED,
+            Some(entity_id),
+            Some(entity),
+            None,
+            None,
+            None,
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(codes)
+    }
+
+    async fn credential(
+        tx: &mut Transaction<'_, Postgres>,
+        principal: Principal,
+    ) -> Result<Option<TotpCredential>, ApiError> {
+        let (user_id, admin_id) = principal.ids();
+        let credential = sqlx::query_as::<_, TotpCredential>(
+            r#"
+            SELECT id, key_version, encrypted_secret, secret_nonce, confirmed_at,
+                   last_used_step, failed_attempts, locked_until
+            FROM totp_credentials
+            WHERE user_id = $1 OR admin_id = $2
+            FOR UPDATE
+            "#,
+        )
+        .bind(user_id)
+        .bind(admin_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+        Ok(credential)
+    }
+
+    async fn secret(db: &PgPool, credential: &TotpCredential) -> Result<Vec<u8>, ApiError> {
+        MessageEncryptionService::decrypt_for_purpose(
+            db,
+            SECRET_PURPOSE,
+            credential.key_version,
+            &credential.encrypted_secret,
+            &credential.secret_nonce,
+        )
+        .await
+    }
+
+    async fn replace_recovery_codes(
+        tx: &mut Transaction<'_, Postgres>,
+        principal: Principal,
+    ) -> Result<Vec<String>, ApiError> {
+        let (user_id, admin_id) = principal.ids();
+        sqlx::query("DELETE FROM recovery_codes WHERE user_id = $1 OR admin_id = $2")
+            .bind(user_id)
+            .bind(admin_id)
+            .execute(&mut **tx)
+            .await?;
+        let mut codes = Vec::with_capacity(RECOVERY_CODE_COUNT);
+        for _ in 0..RECOVERY_CODE_COUNT {
+            let code = generate_recovery_code()?;
+            sqlx::query(
+                "INSERT INTO recovery_codes (user_id, admin_id, code_hash) VALUES ($1, $2, $3)",
+            )
+            .bind(user_id)
+            .bind(admin_id)
+            .bind(hash_recovery_code(&code))
+            .execute(&mut **tx)
+            .await?;
+            codes.push(code);
+        }
+        Ok(codes)
+    }
+}
+
+/// A user whose token carries a recent two-factor verification.
+pub struct RequireStepUp(pub UserClaims);
+
+#[async_trait::async_trait]
+impl<S> FromRequestParts<S> for RequireStepUp
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let AuthenticatedUser(claims) = AuthenticatedUser::from_request_parts(parts, state).await?;
+        match claims.mfa_at {
+            Some(mfa_at) if step_up_is_fresh(mfa_at, Utc::now().timestamp()) => Ok(Self(claims)),
+            _ => Err(ApiError::Forbidden(
+                "Two-factor verification required: enable 2FA and verify a code at \
+                 /api/auth/2fa/step-up"
+                    .to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn matches_rfc_6238_sha1_vectors() {
+        // RFC 6238 appendix B, truncated to six digits.
+        assert_eq!(totp_code(RFC_SECRET, 59), "287082");
+        assert_eq!(totp_code(RFC_SECRET, 1_111_111_109), "081804");
+        assert_eq!(totp_code(RFC_SECRET, 1_234_567_890), "005924");
+        assert_eq!(totp_code(RFC_SECRET, 2_000_000_000), "279037");
+    }
+
+    #[test]
+    fn accepts_codes_within_the_skew_window() {
+        let now = 1_111_111_109;
+        let previous = totp_code(RFC_SECRET, now - TOTP_STEP_SECONDS);
+        assert_eq!(
+            matching_step(RFC_SECRET, &previous, now),
+            Some(time_step(now) - 1)
+        );
+        let stale = totp_code(RFC_SECRET, now - 3 * TOTP_STEP_SECONDS);
+        assert_eq!(matching_step(RFC_SECRET, &stale, now), None);
+    }
+
+    #[test]
+    fn encodes_base32_like_rfc_4648() {
+        assert_eq!(base32_encode(b""), "");
+        assert_eq!(base32_encode(b"f"), "MY");
+        assert_eq!(base32_encode(b"foob"), "MZXW6YQ");
+        assert_eq!(base32_encode(b"foobar"), "MZXW6YTBOI");
+        assert_eq!(
+            base32_encode(RFC_SECRET),
+            "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ"
+        );
+    }
+
+    #[test]
+    fn provisioning_uri_escapes_the_account() {
+        assert_eq!(
+            provisioning_uri("a b@example.com", "ABC"),
+            "otpauth://totp/InheritX:a%20b@example.com?secret=ABC&issuer=InheritX\
+             &algorithm=SHA1&digits=6&period=30"
+        );
+    }
+
+    #[test]
+    fn recovery_codes_hash_the_same_however_typed() {
+        let code = generate_recovery_code().unwrap();
+        assert_eq!(code.len(), 19);
+        assert_eq!(
+            hash_recovery_code(&code),
+            hash_recovery_code(&code.replace('-', " ").to_lowercase())
+        );
+    }
+
+    #[test]
+    fn step_up_expires_after_the_window() {
+        let now = 1_700_000_000;
+        assert!(step_up_is_fresh(now - 60, now));
+        assert!(!step_up_is_fresh(
+            now - STEP_UP_WINDOW_MINUTES * 60 - 1,
+            now
+        ));
+        assert!(!step_up_is_fresh(now + 3600, now));
+    }
+}
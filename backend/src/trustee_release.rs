@@ -0,0 +1,669 @@
+//! M-of-N trustee release of legacy message keys.
+//!
+//! A trustee message is encrypted under a random content key that is split
+//! with Shamir secret sharing over GF(256). Each share is sealed to one
+//! trustee's registered message key (see [`crate::sealed_messages`]) and the
+//! content key is discarded, so the server alone can never read the message.
+//!
+//! After an admin records a verified death event for the owner, trustees open
+//! their share and submit it with a request signed by their Stellar key. Once
+//! `threshold` shares are in, the content key is reconstructed, the message is
+//! sealed to the beneficiary's registered message key and becomes an ordinary
+//! end-to-end message for the delivery worker, and the submitted shares are
+//! wiped. Neither the content key nor the plaintext is ever stored, so the
+//! server cannot read the message after release either.
+
+use crate::api_error::ApiError;
+use crate::death_verification::DeathVerificationService;
+use crate::message_access_audit::{MessageAccessAction, MessageAccessAuditService};
+use crate::message_delivery::DeliveryChannel;
+use crate::sealed_messages::{
+    seal, verify_signed_request, RecipientKeyService, SealedMessage, SealedParts,
+};
+use crate::secure_messages::LegacyMessage;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{DateTime, Utc};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM};
+use ring::rand::{SecureRandom, SystemRandom};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+/// Upper bound on trustees per message.
+pub const MAX_TRUSTEES: usize = 10;
+
+/// The statement a trustee signs to fetch their sealed share.
+pub fn share_request_message(message_id: Uuid, signed_at: DateTime<Utc>) -> String {
+    format!(
+        "INHERITX_TRUSTEE_SHARE:{message_id}:{}",
+        signed_at.timestamp()
+    )
+}
+
+/// The statement a trustee signs to submit their share. It covers the share's
+/// SHA-256 so a captured signature cannot be replayed with another share.
+pub fn share_submission_message(
+    message_id: Uuid,
+    share_hash: &str,
+    signed_at: DateTime<Utc>,
+) -> String {
+    format!(
+        "INHERITX_TRUSTEE_SUBMIT:{message_id}:{share_hash}:{}",
+        signed_at.timestamp()
+    )
+}
+
+/// Hex SHA-256 of a raw share.
+pub fn share_hash(share: &[u8]) -> String {
+    hex::encode(Sha256::digest(share))
+}
+
+fn random_bytes<const N: usize>() -> Result<[u8; N], ApiError> {
+    let mut bytes = [0u8; N];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| ApiError::Internal(anyhow::anyhow!("Failed to generate random bytes")))?;
+    Ok(bytes)
+}
+
+fn content_key(key: &[u8]) -> Result<LessSafeKey, ApiError> {
+    let unbound = UnboundKey::new(&AES_256_GCM, key)
+        .map_err(|_| ApiError::Internal(anyhow::anyhow!("Unable to create key")))?;
+    Ok(LessSafeKey::new(unbound))
+}
+
+// ── Shamir secret sharing ─────────────────────────────────────────────────────
+
+/// Shamir secret sharing over GF(2^8) with the AES polynomial. A share is the
+/// x coordinate (1..=255) followed by one y byte per secret byte.
+mod shamir {
+    use crate::api_error::ApiError;
+    use ring::rand::{SecureRandom, SystemRandom};
+
+    fn mul(mut a: u8, mut b: u8) -> u8 {
+        let mut product = 0u8;
+        while b != 0 {
+            if b & 1 != 0 {
+                product ^= a;
+            }
+            let carry = a & 0x80 != 0;
+            a <<= 1;
+            if carry {
+                a ^= 0x1b;
+            }
+            b >>= 1;
+        }
+        product
+    }
+
+    fn inv(a: u8) -> u8 {
+        // a^254 = a^-1 in GF(2^8).
+        let mut result = 1u8;
+        let mut base = a;
+        let mut exp = 254u8;
+        while exp != 0 {
+            if exp & 1 != 0 {
+                result = mul(result, base);
+            }
+            base = mul(base, base);
+            exp >>= 1;
+        }
+        result
+    }
+
+    pub fn split(secret: &[u8], threshold: usize, shares: usize) -> Result<Vec<Vec<u8>>, ApiError> {
+        if threshold < 2 || threshold > shares || shares > 255 {
+            return Err(ApiError::Internal(anyhow::anyhow!(
+                "Invalid Shamir parameters"
+            )));
+        }
+        let rng = SystemRandom::new();
+        let mut coefficients = vec![0u8; secret.len() * (threshold - 1)];
+        rng.fill(&mut coefficients)
+            .map_err(|_| ApiError::Internal(anyhow::anyhow!("Failed to generate randomness")))?;
+
+        let mut out: Vec<Vec<u8>> = (1..=shares as u8).map(|x| vec![x]).collect();
+        for (i, &byte) in secret.iter().enumerate() {
+            let poly = &coefficients[i * (threshold - 1)..(i + 1) * (threshold - 1)];
+            for share in out.iter_mut() {
+                let x = share[0];
+                // Horner's rule, highest coefficient first.
+                let y = poly.iter().rev().fold(0u8, |acc, &c| mul(acc, x) ^ c);
+                share.push(mul(y, x) ^ byte);
+            }
+        }
+        Ok(out)
+    }
+
+    pub fn combine(shares: &[Vec<u8>]) -> Result<Vec<u8>, ApiError> {
+        let invalid = || ApiError::BadRequest("Invalid key shares".to_string());
+        let len = shares.first().map(Vec::len).ok_or_else(invalid)?;
+        if len < 2 || shares.iter().any(|s| s.len() != len) {
+            return Err(invalid());
+        }
+        let xs: Vec<u8> = shares.iter().map(|s| s[0]).collect();
+        for (i, &x) in xs.iter().enumerate() {
+            if x == 0 || xs[..i].contains(&x) {
+                return Err(invalid());
+            }
+        }
+
+        // Lagrange interpolation at x = 0; subtraction is XOR in GF(2^8).
+        let weights: Vec<u8> = xs
+            .iter()
+            .enumerate()
+            .map(|(i, &xi)| {
+                xs.iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .fold(1u8, |acc, (_, &xj)| mul(acc, mul(xj, inv(xj ^ xi))))
+            })
+            .collect();
+        Ok((1..len)
+            .map(|k| {
+                shares
+                    .iter()
+                    .zip(&weights)
+                    .fold(0u8, |acc, (share, &w)| acc ^ mul(share[k], w))
+            })
+            .collect())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn any_threshold_subset_recovers_the_secret() {
+            let secret = b"thirty-two byte content key!!!!!".to_vec();
+            let shares = split(&secret, 3, 5).unwrap();
+            assert_eq!(shares.len(), 5);
+            for subset in [[0, 1, 2], [0, 2, 4], [4, 3, 1]] {
+                let picked: Vec<Vec<u8>> = subset.iter().map(|&i| shares[i].clone()).collect();
+                assert_eq!(combine(&picked).unwrap(), secret);
+            }
+        }
+
+        #[test]
+        fn fewer_than_threshold_shares_do_not_recover_the_secret() {
+            let secret = [0x42u8; 32].to_vec();
+            let shares = split(&secret, 3, 5).unwrap();
+            assert_ne!(combine(&shares[..2]).unwrap(), secret);
+        }
+
+        #[test]
+        fn duplicate_or_malformed_shares_are_rejected() {
+            let shares = split(b"secret", 2, 3).unwrap();
+            assert!(combine(&[shares[0].clone(), shares[0].clone()]).is_err());
+            assert!(combine(&[shares[0].clone(), shares[1][..3].to_vec()]).is_err());
+        }
+
+        #[test]
+        fn multiplicative_inverse() {
+            for a in 1..=255u8 {
+                assert_eq!(mul(a, inv(a)), 1);
+            }
+        }
+    }
+}
+
+// ── Types ─────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CreateTrusteeMessageRequest {
+    pub vault_id: Option<i64>,
+    pub beneficiary_contact: String,
+    /// Stellar address of the beneficiary. The released message is sealed to
+    /// its registered message key.
+    pub recipient_stellar_address: String,
+    #[serde(default)]
+    pub delivery_channel: DeliveryChannel,
+    pub message: String,
+    /// Number of trustees that must submit their share to release the message.
+    pub threshold: usize,
+    /// Stellar addresses of the trustees; each needs a registered message key.
+    pub trustees: Vec<String>,
+    /// Earliest delivery time once released. Defaults to immediately.
+    pub unlock_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct TrusteeShareRequest {
+    pub stellar_address: String,
+    pub signed_at: DateTime<Utc>,
+    /// Hex Ed25519 signature over [`share_request_message`]
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct SubmitTrusteeShareRequest {
+    pub stellar_address: String,
+    pub signed_at: DateTime<Utc>,
+    /// Base64 share, as opened from the trustee's sealed share
+    pub share: String,
+    /// Hex Ed25519 signature over [`share_submission_message`]
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TrusteeShare {
+    pub message_id: Uuid,
+    pub trustee_stellar_address: String,
+    pub recipient_key_id: Uuid,
+    pub share_index: i16,
+    pub threshold: i16,
+    pub sealed_share: SealedMessage,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct TrusteeEntry {
+    pub trustee_stellar_address: String,
+    pub share_index: i16,
+    pub submitted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TrusteeReleaseStatus {
+    pub message_id: Uuid,
+    pub status: String,
+    pub threshold: i16,
+    pub submitted: usize,
+    pub death_verified_at: Option<DateTime<Utc>>,
+    pub trustees: Vec<TrusteeEntry>,
+}
+
+#[derive(sqlx::FromRow)]
+struct ShareRow {
+    owner_user_id: Uuid,
+    trustee_stellar_address: String,
+    recipient_key_id: Uuid,
+    share_index: i16,
+    sealed_share: serde_json::Value,
+    share_hash: String,
+    submitted_at: Option<DateTime<Utc>>,
+    trustee_threshold: i16,
+    status: String,
+}
+
+// ── Service ───────────────────────────────────────────────────────────────────
+
+pub struct TrusteeReleaseService;
+
+impl TrusteeReleaseService {
+    pub async fn create(
+        db: &PgPool,
+        owner_user_id: Uuid,
+        req: &CreateTrusteeMessageRequest,
+    ) -> Result<LegacyMessage, ApiError> {
+        if req.message.trim().is_empty() {
+            return Err(ApiError::BadRequest("message cannot be empty".to_string()));
+        }
+        if req.beneficiary_contact.trim().is_empty() {
+            return Err(ApiError::BadRequest(
+                "beneficiary_contact cannot be empty".to_string(),
+            ));
+        }
+        req.delivery_channel
+            .validate_contact(req.beneficiary_contact.trim())?;
+        if req.trustees.len() > MAX_TRUSTEES {
+            return Err(ApiError::BadRequest(format!(
+                "At most {MAX_TRUSTEES} trustees are allowed"
+            )));
+        }
+        if req.threshold < 2 || req.threshold > req.trustees.len() {
+            return Err(ApiError::BadRequest(
+                "threshold must be at least 2 and no more than the number of trustees".to_string(),
+            ));
+        }
+        let trustees: Vec<&str> = req.trustees.iter().map(|t| t.trim()).collect();
+        if trustees.iter().collect::<HashSet<_>>().len() != trustees.len() {
+            return Err(ApiError::BadRequest(
+                "trustees must be distinct".to_string(),
+            ));
+        }
+
+        let recipient = req.recipient_stellar_address.trim();
+        RecipientKeyService::active_row(db, recipient).await?;
+
+        let mut keys = Vec::with_capacity(trustees.len());
+        for address in &trustees {
+            keys.push(RecipientKeyService::active_row(db, address).await?);
+        }
+
+        let message_id = Uuid::new_v4();
+        let key = random_bytes::<KEY_LEN>()?;
+        let nonce = random_bytes::<NONCE_LEN>()?;
+        let mut encrypted_payload = req.message.as_bytes().to_vec();
+        content_key(&key)?
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(message_id.as_bytes()),
+                &mut encrypted_payload,
+            )
+            .map_err(|_| ApiError::Internal(anyhow::anyhow!("Encryption failed")))?;
+
+        let shares = shamir::split(&key, req.threshold, trustees.len())?;
+
+        let mut tx = db.begin().await?;
+        let message = sqlx::query_as::<_, LegacyMessage>(
+            "INSERT INTO legacy_messages \
+             (id, owner_user_id, vault_id, beneficiary_contact, encrypted_payload, payload_nonce, \
+              unlock_at, status, encryption_mode, recipient_stellar_address, trustee_threshold, \
+              delivery_channel) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, 'awaiting_trustees', 'trustee', $8, $9, $10) \
+             RETURNING id, owner_user_id, vault_id, beneficiary_contact, key_version, \
+              encryption_mode, recipient_stellar_address, delivery_channel, \
+              delivery_attempts, next_attempt_at, unlock_at, status, delivered_at, created_at",
+        )
+        .bind(message_id)
+        .bind(owner_user_id)
+        .bind(req.vault_id)
+        .bind(req.beneficiary_contact.trim())
+        .bind(&encrypted_payload)
+        .bind(&nonce[..])
+        .bind(req.unlock_at.unwrap_or_else(Utc::now))
+        .bind(recipient)
+        .bind(req.threshold as i16)
+        .bind(req.delivery_channel.as_str())
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for ((address, key), share) in trustees.iter().zip(&keys).zip(&shares) {
+            let sealed = seal(&key.public_key_bytes()?, address, share)?;
+            sqlx::query(
+                "INSERT INTO message_trustee_shares \
+                 (message_id, trustee_stellar_address, recipient_key_id, share_index, sealed_share, share_hash) \
+                 VALUES ($1, $2, $3, $4, $5, $6)",
+            )
+            .bind(message_id)
+            .bind(address)
+            .bind(key.id)
+            .bind(share[0] as i16)
+            .bind(serde_json::to_value(&sealed).map_err(|e| ApiError::Internal(e.into()))?)
+            .bind(share_hash(share))
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        MessageAccessAuditService::log_access(
+            db,
+            Some(message_id),
+            owner_user_id,
+            MessageAccessAction::SharesDistributed,
+            None,
+            None,
+            json!({ "threshold": req.threshold, "trustees": trustees }),
+        )
+        .await?;
+
+        Ok(message)
+    }
+
+    /// Trustees and their submission state, for the message owner.
+    pub async fn status(
+        db: &PgPool,
+        owner_user_id: Uuid,
+        message_id: Uuid,
+    ) -> Result<TrusteeReleaseStatus, ApiError> {
+        #[derive(sqlx::FromRow)]
+        struct Row {
+            status: String,
+            trustee_threshold: i16,
+        }
+
+        let message = sqlx::query_as::<_, Row>(
+            "SELECT status, trustee_threshold FROM legacy_messages \
+             WHERE id = $1 AND owner_user_id = $2 AND trustee_threshold IS NOT NULL",
+        )
+        .bind(message_id)
+        .bind(owner_user_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Trustee message {message_id} not found")))?;
+
+        let trustees = sqlx::query_as::<_, TrusteeEntry>(
+            "SELECT trustee_stellar_address, share_index, submitted_at \
+             FROM message_trustee_shares WHERE message_id = $1 ORDER BY share_index",
+        )
+        .bind(message_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(TrusteeReleaseStatus {
+            message_id,
+            status: message.status,
+            threshold: message.trustee_threshold,
+            submitted: trustees.iter().filter(|t| t.submitted_at.is_some()).count(),
+            death_verified_at: DeathVerificationService::verified_at(db, owner_user_id).await?,
+            trustees,
+        })
+    }
+
+    /// Hand a trustee their sealed share. The request must be signed by the
+    /// trustee's Stellar key; the share only opens with their message key.
+    pub async fn fetch_share(
+        db: &PgPool,
+        message_id: Uuid,
+        req: &TrusteeShareRequest,
+    ) -> Result<TrusteeShare, ApiError> {
+        verify_signed_request(
+            &req.stellar_address,
+            &share_request_message(message_id, req.signed_at),
+            req.signed_at,
+            &req.signature,
+        )?;
+        let row = Self::share_row(db, message_id, &req.stellar_address).await?;
+
+        MessageAccessAuditService::log_access(
+            db,
+            Some(message_id),
+            row.owner_user_id,
+            MessageAccessAction::ShareFetched,
+            None,
+            None,
+            json!({ "trustee_stellar_address": row.trustee_stellar_address }),
+        )
+        .await?;
+
+        Ok(TrusteeShare {
+            message_id,
+            trustee_stellar_address: row.trustee_stellar_address,
+            recipient_key_id: row.recipient_key_id,
+            share_index: row.share_index,
+            threshold: row.trustee_threshold,
+            sealed_share: serde_json::from_value(row.sealed_share)
+                .map_err(|e| ApiError::Internal(e.into()))?,
+        })
+    }
+
+    /// Accept a trustee's share once the owner's death has been verified, and
+    /// release the message when the threshold is reached.
+    pub async fn submit_share(
+        db: &PgPool,
+        message_id: Uuid,
+        req: &SubmitTrusteeShareRequest,
+    ) -> Result<TrusteeReleaseStatus, ApiError> {
+        let share = BASE64
+            .decode(req.share.trim())
+            .map_err(|_| ApiError::BadRequest("share must be base64".to_string()))?;
+        let hash = share_hash(&share);
+        verify_signed_request(
+            &req.stellar_address,
+            &share_submission_message(message_id, &hash, req.signed_at),
+            req.signed_at,
+            &req.signature,
+        )?;
+
+        let row = Self::share_row(db, message_id, &req.stellar_address).await?;
+        if row.status != "awaiting_trustees" {
+            return Err(ApiError::Conflict(
+                "Message has already been released".to_string(),
+            ));
+        }
+        if DeathVerificationService::verified_at(db, row.owner_user_id)
+            .await?
+            .is_none()
+        {
+            return Err(ApiError::Forbidden(
+                "Shares can only be submitted after a verified death event".to_string(),
+            ));
+        }
+        if row.submitted_at.is_some() {
+            return Err(ApiError::Conflict(
+                "Share has already been submitted".to_string(),
+            ));
+        }
+        if hash != row.share_hash {
+            return Err(ApiError::BadRequest(
+                "Share does not match the one issued to this trustee".to_string(),
+            ));
+        }
+
+        sqlx::query(
+            "UPDATE message_trustee_shares SET submitted_share = $1, submitted_at = NOW() \
+             WHERE message_id = $2 AND trustee_stellar_address = $3",
+        )
+        .bind(&share)
+        .bind(message_id)
+        .bind(&row.trustee_stellar_address)
+        .execute(db)
+        .await?;
+
+        MessageAccessAuditService::log_access(
+            db,
+            Some(message_id),
+            row.owner_user_id,
+            MessageAccessAction::ShareSubmitted,
+            None,
+            None,
+            json!({
+                "trustee_stellar_address": row.trustee_stellar_address,
+                "share_index": row.share_index,
+            }),
+        )
+        .await?;
+
+        Self::release_if_ready(db, message_id, row.owner_user_id).await?;
+        Self::status(db, row.owner_user_id, message_id).await
+    }
+
+    async fn share_row(
+        db: &PgPool,
+        message_id: Uuid,
+        stellar_address: &str,
+    ) -> Result<ShareRow, ApiError> {
+        sqlx::query_as::<_, ShareRow>(
+            "SELECT m.owner_user_id, s.trustee_stellar_address, s.recipient_key_id, s.share_index, \
+             s.sealed_share, s.share_hash, s.submitted_at, m.trustee_threshold, m.status \
+             FROM message_trustee_shares s JOIN legacy_messages m ON m.id = s.message_id \
+             WHERE s.message_id = $1 AND s.trustee_stellar_address = $2",
+        )
+        .bind(message_id)
+        .bind(stellar_address)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Trustee message {message_id} not found")))
+    }
+
+    /// Reconstruct the content key from the submitted shares and reseal the
+    /// message to the beneficiary so the delivery worker can release it.
+    async fn release_if_ready(
+        db: &PgPool,
+        message_id: Uuid,
+        owner_user_id: Uuid,
+    ) -> Result<(), ApiError> {
+        #[derive(sqlx::FromRow)]
+        struct Row {
+            encrypted_payload: Vec<u8>,
+            payload_nonce: Vec<u8>,
+            trustee_threshold: i16,
+            recipient_stellar_address: String,
+        }
+
+        let mut tx = db.begin().await?;
+        let Some(message) = sqlx::query_as::<_, Row>(
+            "SELECT encrypted_payload, payload_nonce, trustee_threshold, recipient_stellar_address \
+             FROM legacy_messages \
+             WHERE id = $1 AND status = 'awaiting_trustees' FOR UPDATE",
+        )
+        .bind(message_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(());
+        };
+
+        let shares: Vec<Vec<u8>> = sqlx::query_scalar(
+            "SELECT submitted_share FROM message_trustee_shares \
+             WHERE message_id = $1 AND submitted_share IS NOT NULL ORDER BY share_index",
+        )
+        .bind(message_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        if shares.len() < message.trustee_threshold as usize {
+            return Ok(());
+        }
+
+        let key = shamir::combine(&shares)?;
+        let nonce: [u8; NONCE_LEN] = message
+            .payload_nonce
+            .as_slice()
+            .try_into()
+            .map_err(|_| ApiError::Internal(anyhow::anyhow!("Invalid nonce length")))?;
+        let mut in_out = message.encrypted_payload;
+        let plaintext = content_key(&key)?
+            .open_in_place(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(message_id.as_bytes()),
+                &mut in_out,
+            )
+            .map_err(|_| ApiError::Internal(anyhow::anyhow!("Reconstructed key is invalid")))?;
+
+        let recipient =
+            RecipientKeyService::active_row(db, &message.recipient_stellar_address).await?;
+        let sealed = SealedParts::decode(&seal(
+            &recipient.public_key_bytes()?,
+            &message.recipient_stellar_address,
+            plaintext,
+        )?)?;
+        sqlx::query(
+            "UPDATE legacy_messages SET encryption_mode = 'e2e', recipient_key_id = $1, \
+             ephemeral_public_key = $2, encrypted_payload = $3, payload_nonce = $4, \
+             status = 'pending', updated_at = NOW() WHERE id = $5",
+        )
+        .bind(recipient.id)
+        .bind(&sealed.ephemeral_public_key[..])
+        .bind(&sealed.ciphertext)
+        .bind(&sealed.nonce[..])
+        .bind(message_id)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "UPDATE message_trustee_shares SET submitted_share = NULL WHERE message_id = $1",
+        )
+        .bind(message_id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        MessageAccessAuditService::log_access(
+            db,
+            Some(message_id),
+            owner_user_id,
+            MessageAccessAction::TrusteesReleased,
+            None,
+            None,
+            json!({ "shares_used": shares.len(), "recipient_key_id": recipient.id }),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
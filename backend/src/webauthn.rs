@@ -0,0 +1,1053 @@
+//! WebAuthn (passkey) registration and sign-in for users and admins.
+//!
+//! Both ceremonies are two requests: the server hands out options holding a
+//! random challenge, the browser passes them to `navigator.credentials`, and
+//! the resulting credential is posted back. Challenges are stored in
+//! `webauthn_challenges`, live for [`CHALLENGE_TTL_MINUTES`] and are consumed
+//! on first use.
+//!
+//! Registration requests `"none"` attestation, so attestation statements are
+//! not checked; what is checked is the client data (type, challenge, origin),
+//! the RP ID hash, the user-presence flag and that the credential's COSE key
+//! is one we can verify (ES256 or Ed25519). Sign-in verifies the assertion
+//! signature over `authenticatorData || SHA-256(clientDataJSON)`.
+//!
+//! Authenticators that keep a signature counter must report a higher value on
+//! every use. A counter that does not advance means two copies of the key
+//! exist, so the credential is flagged as cloned and refused from then on.
+//!
+//! The relying party is configured with `WEBAUTHN_RP_ID`, `WEBAUTHN_RP_NAME`
+//! and `WEBAUTHN_ORIGINS`; `ADMIN_PASSKEYS_REQUIRED` makes passkeys the only
+//! way for admins to sign in (see [`crate::auth::AuthenticatedAdmin`]).
+
+use crate::api_error::ApiError;
+use crate::notifications::{audit_action, entity_type, AuditLogService};
+use crate::sessions::Principal;
+use base64::Engine as _;
+use chrono::{DateTime, Duration, Utc};
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::signature;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// How long a ceremony's challenge can be answered.
+pub const CHALLENGE_TTL_MINUTES: i64 = 5;
+
+const CHALLENGE_BYTES: usize = 32;
+
+/// COSE algorithm identifiers we accept, in order of preference.
+pub const COSE_ALG_ES256: i64 = -7;
+pub const COSE_ALG_EDDSA: i64 = -8;
+
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_BACKUP_ELIGIBLE: u8 = 0x08;
+const FLAG_ATTESTED_CREDENTIAL: u8 = 0x40;
+
+const MAX_CREDENTIAL_ID_LEN: usize = 1023;
+const MAX_CBOR_DEPTH: usize = 8;
+
+pub mod ceremony {
+    pub const REGISTRATION: &str = "registration";
+    pub const AUTHENTICATION: &str = "authentication";
+}
+
+/// Which kind of account a ceremony is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountType {
+    User,
+    Admin,
+}
+
+impl AccountType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AccountType::User => "user",
+            AccountType::Admin => "admin",
+        }
+    }
+
+    fn of(principal: Principal) -> Self {
+        match principal {
+            Principal::User(_) => AccountType::User,
+            Principal::Admin(_) => AccountType::Admin,
+        }
+    }
+}
+
+/// The relying party this deployment presents to authenticators.
+#[derive(Debug, Clone)]
+pub struct WebAuthnConfig {
+    pub rp_id: String,
+    pub rp_name: String,
+    pub origins: Vec<String>,
+}
+
+impl WebAuthnConfig {
+    pub fn new(rp_id: impl Into<String>, rp_name: impl Into<String>, origins: Vec<String>) -> Self {
+        Self {
+            rp_id: rp_id.into(),
+            rp_name: rp_name.into(),
+            origins,
+        }
+    }
+
+    /// Read `WEBAUTHN_RP_ID` (default `localhost`), `WEBAUTHN_RP_NAME`
+    /// (default `InheritX`) and the comma-separated `WEBAUTHN_ORIGINS`
+    /// (default `https://<rp id>`, plus `http://localhost:3000` for localhost).
+    pub fn from_env() -> Self {
+        let rp_id = std::env::var("WEBAUTHN_RP_ID").unwrap_or_else(|_| "localhost".to_string());
+        let rp_name = std::env::var("WEBAUTHN_RP_NAME").unwrap_or_else(|_| "InheritX".to_string());
+        let origins = match std::env::var("WEBAUTHN_ORIGINS") {
+            Ok(value) => value
+                .split(',')
+                .map(|o| o.trim().trim_end_matches('/').to_string())
+                .filter(|o| !o.is_empty())
+                .collect(),
+            Err(_) if rp_id == "localhost" => vec![
+                "https://localhost".to_string(),
+                "http://localhost:3000".to_string(),
+            ],
+            Err(_) => vec![format!("https://{rp_id}")],
+        };
+        Self::new(rp_id, rp_name, origins)
+    }
+
+    fn rp_id_hash(&self) -> [u8; 32] {
+        Sha256::digest(self.rp_id.as_bytes()).into()
+    }
+}
+
+// -- Wire types ---------------------------------------------------------------
+
+/// A `PublicKeyCredential` from `navigator.credentials.create()`, with binary
+/// fields base64url encoded as in its `toJSON()` form.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RegistrationCredential {
+    pub id: String,
+    pub response: AttestationResponse,
+    /// Label for the passkey, e.g. "YubiKey 5".
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AttestationResponse {
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    #[serde(rename = "attestationObject")]
+    pub attestation_object: String,
+    #[serde(default)]
+    pub transports: Vec<String>,
+}
+
+/// A `PublicKeyCredential` from `navigator.credentials.get()`.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AuthenticationCredential {
+    pub id: String,
+    pub response: AssertionResponse,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AssertionResponse {
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    #[serde(rename = "authenticatorData")]
+    pub authenticator_data: String,
+    pub signature: String,
+    #[serde(rename = "userHandle", default)]
+    pub user_handle: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct PasskeyLoginOptionsRequest {
+    /// Restricts the ceremony to this account's passkeys. Without it the
+    /// browser offers any discoverable passkey for the site.
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClientData {
+    #[serde(rename = "type")]
+    kind: String,
+    challenge: String,
+    origin: String,
+    #[serde(rename = "crossOrigin", default)]
+    cross_origin: bool,
+}
+
+/// A registered passkey as shown to its owner.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Passkey {
+    pub id: Uuid,
+    pub name: Option<String>,
+    pub algorithm: i32,
+    pub sign_count: i64,
+    pub aaguid: Option<Uuid>,
+    pub transports: Vec<String>,
+    pub backup_eligible: bool,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub clone_detected_at: Option<DateTime<Utc>>,
+}
+
+const PASSKEY_COLUMNS: &str = "id, name, algorithm, sign_count, aaguid, transports, \
+     backup_eligible, created_at, last_used_at, clone_detected_at";
+
+/// The outcome of a successful sign-in ceremony.
+#[derive(Debug)]
+pub struct PasskeyAssertion {
+    pub principal: Principal,
+    /// The authenticator verified the user (PIN or biometric), not just
+    /// their presence.
+    pub user_verified: bool,
+}
+
+// -- Encoding helpers ---------------------------------------------------------
+
+fn b64url(bytes: &[u8]) -> String {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn from_b64url(value: &str, field: &str) -> Result<Vec<u8>, ApiError> {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(value.trim_end_matches('='))
+        .map_err(|_| ApiError::BadRequest(format!("{field} is not valid base64url")))
+}
+
+fn new_challenge() -> Result<String, ApiError> {
+    let mut bytes = [0u8; CHALLENGE_BYTES];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| ApiError::Internal(anyhow::anyhow!("Failed to generate challenge")))?;
+    Ok(b64url(&bytes))
+}
+
+/// The subset of CBOR (RFC 8949) used by attestation objects and COSE keys.
+#[derive(Debug, Clone, PartialEq)]
+enum Cbor {
+    Int(i64),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<Cbor>),
+    Map(Vec<(Cbor, Cbor)>),
+    Bool(bool),
+    Null,
+}
+
+impl Cbor {
+    fn get(&self, key: &Cbor) -> Option<&Cbor> {
+        match self {
+            Cbor::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn text_key(&self, key: &str) -> Option<&Cbor> {
+        self.get(&Cbor::Text(key.to_string()))
+    }
+
+    fn int_key(&self, key: i64) -> Option<&Cbor> {
+        self.get(&Cbor::Int(key))
+    }
+
+    fn as_int(&self) -> Option<i64> {
+        match self {
+            Cbor::Int(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Cbor::Bytes(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+fn malformed(what: &str) -> ApiError {
+    ApiError::BadRequest(format!("Malformed {what}"))
+}
+
+struct CborReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> CborReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ApiError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| malformed("CBOR"))?;
+        let slice = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn argument(&mut self, info: u8) -> Result<u64, ApiError> {
+        Ok(match info {
+            0..=23 => u64::from(info),
+            24 => u64::from(self.take(1)?[0]),
+            25 => u64::from(u16::from_be_bytes(self.take(2)?.try_into().unwrap())),
+            26 => u64::from(u32::from_be_bytes(self.take(4)?.try_into().unwrap())),
+            27 => u64::from_be_bytes(self.take(8)?.try_into().unwrap()),
+            // Indefinite lengths are not allowed in WebAuthn's canonical CBOR.
+            _ => return Err(malformed("CBOR")),
+        })
+    }
+
+    fn length(&mut self, info: u8) -> Result<usize, ApiError> {
+        let len = self.argument(info)?;
+        // Every item takes at least a byte, so longer claims are bogus.
+        if len > (self.data.len() - self.pos) as u64 {
+            return Err(malformed("CBOR"));
+        }
+        Ok(len as usize)
+    }
+
+    fn read(&mut self, depth: usize) -> Result<Cbor, ApiError> {
+        if depth > MAX_CBOR_DEPTH {
+            return Err(malformed("CBOR"));
+        }
+        let initial = self.take(1)?[0];
+        let (major, info) = (initial >> 5, initial & 0x1f);
+        Ok(match major {
+            0 => Cbor::Int(i64::try_from(self.argument(info)?).map_err(|_| malformed("CBOR"))?),
+            1 => {
+                let n = i64::try_from(self.argument(info)?).map_err(|_| malformed("CBOR"))?;
+                Cbor::Int(-1 - n)
+            }
+            2 => {
+                let len = self.length(info)?;
+                Cbor::Bytes(self.take(len)?.to_vec())
+            }
+            3 => {
+                let len = self.length(info)?;
+                Cbor::Text(
+                    String::from_utf8(self.take(len)?.to_vec()).map_err(|_| malformed("CBOR"))?,
+                )
+            }
+            4 => {
+                let len = self.length(info)?;
+                let mut items = Vec::with_capacity(len);
+                for _ in 0..len {
+                    items.push(self.read(depth + 1)?);
+                }
+                Cbor::Array(items)
+            }
+            5 => {
+                let len = self.length(info)?;
+                let mut entries = Vec::with_capacity(len);
+                for _ in 0..len {
+                    let key = self.read(depth + 1)?;
+                    let value = self.read(depth + 1)?;
+                    entries.push((key, value));
+                }
+                Cbor::Map(entries)
+            }
+            7 => match info {
+                20 => Cbor::Bool(false),
+                21 => Cbor::Bool(true),
+                22 => Cbor::Null,
+                _ => return Err(malformed("CBOR")),
+            },
+            _ => return Err(malformed("CBOR")),
+        })
+    }
+}
+
+/// Decode one CBOR item from the front of `data`, returning it and the
+/// number of bytes it took.
+fn decode_cbor(data: &[u8]) -> Result<(Cbor, usize), ApiError> {
+    let mut reader = CborReader::new(data);
+    let value = reader.read(0)?;
+    Ok((value, reader.pos))
+}
+
+// -- Authenticator data and keys ----------------------------------------------
+
+struct AttestedCredential {
+    aaguid: Uuid,
+    credential_id: Vec<u8>,
+    public_key: Vec<u8>,
+}
+
+struct AuthenticatorData {
+    rp_id_hash: [u8; 32],
+    flags: u8,
+    sign_count: u32,
+    attested: Option<AttestedCredential>,
+}
+
+impl AuthenticatorData {
+    fn parse(data: &[u8]) -> Result<Self, ApiError> {
+        if data.len() < 37 {
+            return Err(malformed("authenticator data"));
+        }
+        let rp_id_hash: [u8; 32] = data[..32].try_into().unwrap();
+        let flags = data[32];
+        let sign_count = u32::from_be_bytes(data[33..37].try_into().unwrap());
+        let attested = if flags & FLAG_ATTESTED_CREDENTIAL != 0 {
+            let rest = &data[37..];
+            if rest.len() < 18 {
+                return Err(malformed("authenticator data"));
+            }
+            let aaguid = Uuid::from_slice(&rest[..16]).unwrap();
+            let id_len = usize::from(u16::from_be_bytes([rest[16], rest[17]]));
+            if id_len > MAX_CREDENTIAL_ID_LEN || rest.len() < 18 + id_len {
+                return Err(malformed("authenticator data"));
+            }
+            let credential_id = rest[18..18 + id_len].to_vec();
+            let key_bytes = &rest[18 + id_len..];
+            let (_, key_len) = decode_cbor(key_bytes)?;
+            Some(AttestedCredential {
+                aaguid,
+                credential_id,
+                public_key: key_bytes[..key_len].to_vec(),
+            })
+        } else {
+            None
+        };
+        Ok(Self {
+            rp_id_hash,
+            flags,
+            sign_count,
+            attested,
+        })
+    }
+
+    fn has(&self, flag: u8) -> bool {
+        self.flags & flag != 0
+    }
+}
+
+/// A COSE public key we know how to verify with.
+enum CoseKey {
+    Es256(Vec<u8>),
+    Ed25519(Vec<u8>),
+}
+
+impl CoseKey {
+    fn parse(cose: &[u8]) -> Result<Self, ApiError> {
+        let (key, _) = decode_cbor(cose)?;
+        let int = |label| key.int_key(label).and_then(Cbor::as_int);
+        let bytes = |label| key.int_key(label).and_then(Cbor::as_bytes);
+        match (int(1), int(3), int(-1)) {
+            // kty EC2, crv P-256
+            (Some(2), Some(COSE_ALG_ES256), Some(1)) => match (bytes(-2), bytes(-3)) {
+                (Some(x), Some(y)) if x.len() == 32 && y.len() == 32 => {
+                    let mut point = Vec::with_capacity(65);
+                    point.push(0x04);
+                    point.extend_from_slice(x);
+                    point.extend_from_slice(y);
+                    Ok(CoseKey::Es256(point))
+                }
+                _ => Err(malformed("COSE key")),
+            },
+            // kty OKP, crv Ed25519
+            (Some(1), Some(COSE_ALG_EDDSA), Some(6)) => match bytes(-2) {
+                Some(x) if x.len() == 32 => Ok(CoseKey::Ed25519(x.to_vec())),
+                _ => Err(malformed("COSE key")),
+            },
+            _ => Err(ApiError::BadRequest(
+                "Unsupported passkey algorithm; use ES256 or Ed25519".to_string(),
+            )),
+        }
+    }
+
+    fn algorithm(&self) -> i64 {
+        match self {
+            CoseKey::Es256(_) => COSE_ALG_ES256,
+            CoseKey::Ed25519(_) => COSE_ALG_EDDSA,
+        }
+    }
+
+    fn verify(&self, message: &[u8], sig: &[u8]) -> bool {
+        let result = match self {
+            CoseKey::Es256(point) => {
+                signature::UnparsedPublicKey::new(&signature::ECDSA_P256_SHA256_ASN1, point)
+                    .verify(message, sig)
+            }
+            CoseKey::Ed25519(key) => {
+                signature::UnparsedPublicKey::new(&signature::ED25519, key).verify(message, sig)
+            }
+        };
+        result.is_ok()
+    }
+}
+
+/// Whether a signature counter of `reported` is consistent with the last
+/// one seen. Authenticators without a counter always report zero.
+pub fn sign_count_advanced(stored: i64, reported: u32) -> bool {
+    (stored == 0 && reported == 0) || i64::from(reported) > stored
+}
+
+// -- Service ------------------------------------------------------------------
+
+#[derive(Debug, FromRow)]
+struct StoredCredential {
+    id: Uuid,
+    user_id: Option<Uuid>,
+    admin_id: Option<Uuid>,
+    public_key: Vec<u8>,
+    sign_count: i64,
+    clone_detected_at: Option<DateTime<Utc>>,
+}
+
+impl StoredCredential {
+    fn principal(&self) -> Principal {
+        match (self.user_id, self.admin_id) {
+            (Some(user_id), _) => Principal::User(user_id),
+            (None, Some(admin_id)) => Principal::Admin(admin_id),
+            (None, None) => unreachable!("passkeys always belong to a user or an admin"),
+        }
+    }
+}
+
+fn principal_id(principal: Principal) -> Uuid {
+    match principal {
+        Principal::User(id) | Principal::Admin(id) => id,
+    }
+}
+
+pub struct PasskeyService;
+
+impl PasskeyService {
+    /// Options for `navigator.credentials.create()`.
+    pub async fn registration_options(
+        db: &PgPool,
+        config: &WebAuthnConfig,
+        principal: Principal,
+        account_name: &str,
+    ) -> Result<Value, ApiError> {
+        let challenge = Self::issue_challenge(
+            db,
+            ceremony::REGISTRATION,
+            AccountType::of(principal),
+            Some(principal),
+        )
+        .await?;
+        let exclude: Vec<Value> = Self::credential_ids(db, principal)
+            .await?
+            .iter()
+            .map(|id| json!({ "type": "public-key", "id": b64url(id) }))
+            .collect();
+        Ok(json!({
+            "challenge": challenge,
+            "rp": { "id": config.rp_id, "name": config.rp_name },
+            "user": {
+                "id": b64url(principal_id(principal).as_bytes()),
+                "name": account_name,
+                "displayName": account_name,
+            },
+            "pubKeyCredParams": [
+                { "type": "public-key", "alg": COSE_ALG_ES256 },
+                { "type": "public-key", "alg": COSE_ALG_EDDSA },
+            ],
+            "timeout": CHALLENGE_TTL_MINUTES * 60 * 1000,
+            "attestation": "none",
+            "excludeCredentials": exclude,
+            "authenticatorSelection": {
+                "residentKey": "preferred",
+                "userVerification": "preferred",
+            },
+        }))
+    }
+
+    /// Verify a registration response and store the new passkey.
+    pub async fn register(
+        db: &PgPool,
+        config: &WebAuthnConfig,
+        principal: Principal,
+        credential: &RegistrationCredential,
+    ) -> Result<Passkey, ApiError> {
+        let client_data_json =
+            from_b64url(&credential.response.client_data_json, "clientDataJSON")?;
+        let client_data = Self::check_client_data(config, &client_data_json, "webauthn.create")?;
+        let owner = Self::consume_challenge(
+            db,
+            &client_data.challenge,
+            ceremony::REGISTRATION,
+            AccountType::of(principal),
+        )
+        .await?;
+        if owner != Some(principal) {
+            return Err(ApiError::BadRequest(
+                "Challenge was not issued to this account".to_string(),
+            ));
+        }
+
+        let attestation =
+            from_b64url(&credential.response.attestation_object, "attestationObject")?;
+        let (attestation, _) = decode_cbor(&attestation)?;
+        let auth_data = attestation
+            .text_key("authData")
+            .and_then(Cbor::as_bytes)
+            .ok_or_else(|| malformed("attestation object"))?;
+        let auth_data = AuthenticatorData::parse(auth_data)?;
+        if auth_data.rp_id_hash != config.rp_id_hash() {
+            return Err(ApiError::BadRequest(
+                "Passkey was created for a different site".to_string(),
+            ));
+        }
+        if !auth_data.has(FLAG_USER_PRESENT) {
+            return Err(ApiError::BadRequest(
+                "User presence was not confirmed".to_string(),
+            ));
+        }
+        let attested = auth_data
+            .attested
+            .as_ref()
+            .ok_or_else(|| malformed("attestation object"))?;
+        if attested.credential_id != from_b64url(&credential.id, "id")? {
+            return Err(ApiError::BadRequest(
+                "Credential id does not match the authenticator data".to_string(),
+            ));
+        }
+        let key = CoseKey::parse(&attested.public_key)?;
+
+        let (user_id, admin_id) = principal.ids();
+        let name = credential
+            .name
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .map(|n| n.chars().take(100).collect::<String>());
+        let aaguid = (!attested.aaguid.is_nil()).then_some(attested.aaguid);
+        let mut tx = db.begin().await?;
+        let passkey = sqlx::query_as::<_, Passkey>(&format!(
+            "INSERT INTO webauthn_credentials \
+                 (user_id, admin_id, credential_id, public_key, algorithm, sign_count, aaguid, \
+                  transports, backup_eligible, name) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) \
+             ON CONFLICT (credential_id) DO NOTHING \
+             RETURNING {PASSKEY_COLUMNS}"
+        ))
+        .bind(user_id)
+        .bind(admin_id)
+        .bind(&attested.credential_id)
+        .bind(&attested.public_key)
+        .bind(key.algorithm() as i32)
+        .bind(i64::from(auth_data.sign_count))
+        .bind(aaguid)
+        .bind(&credential.response.transports)
+        .bind(auth_data.has(FLAG_BACKUP_ELIGIBLE))
+        .bind(name)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| ApiError::Conflict("This passkey is already registered".to_string()))?;
+
+        AuditLogService::log(
+            &mut *tx,
+            user_id,
+            admin_id,
+            audit_action::PASSKEY_REGISTERED,
+            Some(passkey.id),
+            Some(entity_type::PASSKEY),
+            None,
+            None,
+            Some(json!({
+                "algorithm": passkey.algorithm,
+                "aaguid": passkey.aaguid,
+                "backup_eligible": passkey.backup_eligible,
+            })),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(passkey)
+    }
+
+    /// Options for `navigator.credentials.get()`. An unknown email gets
+    /// ordinary-looking options, so the response does not reveal which
+    /// accounts exist.
+    pub async fn authentication_options(
+        db: &PgPool,
+        config: &WebAuthnConfig,
+        account_type: AccountType,
+        email: Option<&str>,
+    ) -> Result<Value, ApiError> {
+        let principal = match email {
+            Some(email) => Self::principal_by_email(db, account_type, email).await?,
+            None => None,
+        };
+        let challenge =
+            Self::issue_challenge(db, ceremony::AUTHENTICATION, account_type, principal).await?;
+        let allow: Vec<Value> = match principal {
+            Some(principal) => Self::credential_ids(db, principal)
+                .await?
+                .iter()
+                .map(|id| json!({ "type": "public-key", "id": b64url(id) }))
+                .collect(),
+            None => Vec::new(),
+        };
+        Ok(json!({
+            "challenge": challenge,
+            "rpId": config.rp_id,
+            "timeout": CHALLENGE_TTL_MINUTES * 60 * 1000,
+            "allowCredentials": allow,
+            "userVerification": "preferred",
+        }))
+    }
+
+    /// Verify a sign-in assertion. Any failure is reported as `Unauthorized`
+    /// except a detected clone, which is `Forbidden`.
+    pub async fn authenticate(
+        db: &PgPool,
+        config: &WebAuthnConfig,
+        account_type: AccountType,
+        credential: &AuthenticationCredential,
+    ) -> Result<PasskeyAssertion, ApiError> {
+        let unauthorized = |_| ApiError::Unauthorized;
+        let client_data_json = from_b64url(&credential.response.client_data_json, "clientDataJSON")
+            .map_err(unauthorized)?;
+        let client_data = Self::check_client_data(config, &client_data_json, "webauthn.get")
+            .map_err(unauthorized)?;
+        let challenge_owner = Self::consume_challenge(
+            db,
+            &client_data.challenge,
+            ceremony::AUTHENTICATION,
+            account_type,
+        )
+        .await
+        .map_err(|_| ApiError::Unauthorized)?;
+
+        let credential_id = from_b64url(&credential.id, "id").map_err(unauthorized)?;
+        let mut tx = db.begin().await?;
+        let stored = sqlx::query_as::<_, StoredCredential>(
+            "SELECT id, user_id, admin_id, public_key, sign_count, clone_detected_at \
+             FROM webauthn_credentials WHERE credential_id = $1 FOR UPDATE",
+        )
+        .bind(&credential_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(ApiError::Unauthorized)?;
+        let principal = stored.principal();
+        if AccountType::of(principal) != account_type
+            || challenge_owner.is_some_and(|owner| owner != principal)
+        {
+            return Err(ApiError::Unauthorized);
+        }
+        if let Some(handle) = &credential.response.user_handle {
+            let handle = from_b64url(handle, "userHandle").map_err(unauthorized)?;
+            if handle != principal_id(principal).as_bytes() {
+                return Err(ApiError::Unauthorized);
+            }
+        }
+        if stored.clone_detected_at.is_some() {
+            return Err(ApiError::Forbidden(
+                "This passkey has been disabled because it appears to have been cloned".to_string(),
+            ));
+        }
+
+        let auth_data_bytes =
+            from_b64url(&credential.response.authenticator_data, "authenticatorData")
+                .map_err(unauthorized)?;
+        let auth_data = AuthenticatorData::parse(&auth_data_bytes).map_err(unauthorized)?;
+        if auth_data.rp_id_hash != config.rp_id_hash() || !auth_data.has(FLAG_USER_PRESENT) {
+            return Err(ApiError::Unauthorized);
+        }
+        let signature =
+            from_b64url(&credential.response.signature, "signature").map_err(unauthorized)?;
+        let key = CoseKey::parse(&stored.public_key)?;
+        let mut signed = auth_data_bytes;
+        signed.extend_from_slice(&Sha256::digest(&client_data_json));
+        if !key.verify(&signed, &signature) {
+            return Err(ApiError::Unauthorized);
+        }
+
+        let (user_id, admin_id) = principal.ids();
+        if !sign_count_advanced(stored.sign_count, auth_data.sign_count) {
+            sqlx::query("UPDATE webauthn_credentials SET clone_detected_at = NOW() WHERE id = $1")
+                .bind(stored.id)
+                .execute(&mut *tx)
+                .await?;
+            AuditLogService::log(
+                &mut *tx,
+                user_id,
+                admin_id,
+                audit_action::PASSKEY_CLONE_DETECTED,
+                Some(stored.id),
+                Some(entity_type::PASSKEY),
+                Some(&stored.sign_count.to_string()),
+                Some(&auth_data.sign_count.to_string()),
+                None,
+            )
+            .await?;
+            tx.commit().await?;
+            return Err(ApiError::Forbidden(
+                "This passkey has been disabled because it appears to have been cloned".to_string(),
+            ));
+        }
+
+        sqlx::query(
+            "UPDATE webauthn_credentials SET sign_count = $2, last_used_at = NOW() WHERE id = $1",
+        )
+        .bind(stored.id)
+        .bind(i64::from(auth_data.sign_count))
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(PasskeyAssertion {
+            principal,
+            user_verified: auth_data.has(FLAG_USER_VERIFIED),
+        })
+    }
+
+    pub async fn list(db: &PgPool, principal: Principal) -> Result<Vec<Passkey>, ApiError> {
+        let (user_id, admin_id) = principal.ids();
+        let passkeys = sqlx::query_as::<_, Passkey>(&format!(
+            "SELECT {PASSKEY_COLUMNS} FROM webauthn_credentials \
+             WHERE user_id = $1 OR admin_id = $2 ORDER BY created_at"
+        ))
+        .bind(user_id)
+        .bind(admin_id)
+        .fetch_all(db)
+        .await?;
+        Ok(passkeys)
+    }
+
+    /// Whether `principal` has a passkey that can still be used.
+    pub async fn has_passkey(db: &PgPool, principal: Principal) -> Result<bool, ApiError> {
+        let (user_id, admin_id) = principal.ids();
+        let exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM webauthn_credentials \
+             WHERE (user_id = $1 OR admin_id = $2) AND clone_detected_at IS NULL)",
+        )
+        .bind(user_id)
+        .bind(admin_id)
+        .fetch_one(db)
+        .await?;
+        Ok(exists)
+    }
+
+    /// Remove a passkey. With `keep_one`, refuses to remove the owner's last
+    /// usable passkey.
+    pub async fn remove(
+        db: &PgPool,
+        principal: Principal,
+        passkey_id: Uuid,
+        keep_one: bool,
+    ) -> Result<(), ApiError> {
+        let (user_id, admin_id) = principal.ids();
+        let mut tx = db.begin().await?;
+        let usable: Vec<(Uuid, bool)> = sqlx::query_as(
+            "SELECT id, clone_detected_at IS NULL FROM webauthn_credentials \
+             WHERE user_id = $1 OR admin_id = $2 FOR UPDATE",
+        )
+        .bind(user_id)
+        .bind(admin_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        let Some((_, removing_usable)) = usable.iter().find(|(id, _)| *id == passkey_id) else {
+            return Err(ApiError::NotFound(format!(
+                "Passkey {passkey_id} not found"
+            )));
+        };
+        let usable_count = usable.iter().filter(|(_, ok)| *ok).count();
+        if keep_one && *removing_usable && usable_count == 1 {
+            return Err(ApiError::Conflict(
+                "Admins must keep at least one passkey".to_string(),
+            ));
+        }
+        sqlx::query("DELETE FROM webauthn_credentials WHERE id = $1")
+            .bind(passkey_id)
+            .execute(&mut *tx)
+            .await?;
+        AuditLogService::log(
+            &mut *tx,
+            user_id,
+            admin_id,
+            audit_action::PASSKEY_REMOVED,
+            Some(passkey_id),
+            Some(entity_type::PASSKEY),
+            None,
+            None,
+            None,
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    fn check_client_data(
+        config: &WebAuthnConfig,
+        client_data_json: &[u8],
+        expected_type: &str,
+    ) -> Result<ClientData, ApiError> {
+        let client_data: ClientData =
+            serde_json::from_slice(client_data_json).map_err(|_| malformed("client data"))?;
+        if client_data.kind != expected_type {
+            return Err(ApiError::BadRequest(format!(
+                "Expected a {expected_type} response"
+            )));
+        }
+        if client_data.cross_origin || !config.origins.contains(&client_data.origin) {
+            return Err(ApiError::BadRequest(format!(
+                "Origin {} is not allowed",
+                client_data.origin
+            )));
+        }
+        Ok(client_data)
+    }
+
+    async fn issue_challenge(
+        db: &PgPool,
+        ceremony: &str,
+        account_type: AccountType,
+        principal: Option<Principal>,
+    ) -> Result<String, ApiError> {
+        let challenge = new_challenge()?;
+        let (user_id, admin_id) = principal.map_or((None, None), Principal::ids);
+        sqlx::query("DELETE FROM webauthn_challenges WHERE expires_at < NOW()")
+            .execute(db)
+            .await?;
+        sqlx::query(
+            "INSERT INTO webauthn_challenges \
+                 (challenge, ceremony, account_type, user_id, admin_id, expires_at) \
+             VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+        .bind(&challenge)
+        .bind(ceremony)
+        .bind(account_type.as_str())
+        .bind(user_id)
+        .bind(admin_id)
+        .bind(Utc::now() + Duration::minutes(CHALLENGE_TTL_MINUTES))
+        .execute(db)
+        .await?;
+        Ok(challenge)
+    }
+
+    /// Use up a challenge, returning the account it was issued for, if any.
+    async fn consume_challenge(
+        db: &PgPool,
+        challenge: &str,
+        ceremony: &str,
+        account_type: AccountType,
+    ) -> Result<Option<Principal>, ApiError> {
+        let row: Option<(Option<Uuid>, Option<Uuid>)> = sqlx::query_as(
+            "DELETE FROM webauthn_challenges \
+             WHERE challenge = $1 AND ceremony = $2 AND account_type = $3 AND expires_at > NOW() \
+             RETURNING user_id, admin_id",
+        )
+        .bind(challenge)
+        .bind(ceremony)
+        .bind(account_type.as_str())
+        .fetch_optional(db)
+        .await?;
+        match row {
+            Some((Some(user_id), _)) => Ok(Some(Principal::User(user_id))),
+            Some((None, Some(admin_id))) => Ok(Some(Principal::Admin(admin_id))),
+            Some((None, None)) => Ok(None),
+            None => Err(ApiError::BadRequest(
+                "Unknown or expired challenge".to_string(),
+            )),
+        }
+    }
+
+    async fn credential_ids(db: &PgPool, principal: Principal) -> Result<Vec<Vec<u8>>, ApiError> {
+        let (user_id, admin_id) = principal.ids();
+        let ids = sqlx::query_scalar(
+            "SELECT credential_id FROM webauthn_credentials \
+             WHERE (user_id = $1 OR admin_id = $2) AND clone_detected_at IS NULL",
+        )
+        .bind(user_id)
+        .bind(admin_id)
+        .fetch_all(db)
+        .await?;
+        Ok(ids)
+    }
+
+    async fn principal_by_email(
+        db: &PgPool,
+        account_type: AccountType,
+        email: &str,
+    ) -> Result<Option<Principal>, ApiError> {
+        let principal = match account_type {
+            AccountType::User => sqlx::query_scalar("SELECT id FROM users WHERE email = $1")
+                .bind(email)
+                .fetch_optional(db)
+                .await?
+                .map(Principal::User),
+            AccountType::Admin => {
+                sqlx::query_scalar("SELECT id FROM admins WHERE email = $1 AND status = 'active'")
+                    .bind(email)
+                    .fetch_optional(db)
+                    .await?
+                    .map(Principal::Admin)
+            }
+        };
+        Ok(principal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_cbor_maps_with_int_and_text_keys() {
+        // {1: 2, 3: -7, "a": h'0102', "b": [true, null]}
+        let data = [
+            0xa4, 0x01, 0x02, 0x03, 0x26, 0x61, b'a', 0x42, 0x01, 0x02, 0x61, b'b', 0x82, 0xf5,
+            0xf6,
+        ];
+        let (value, used) = decode_cbor(&data).unwrap();
+        assert_eq!(used, data.len());
+        assert_eq!(value.int_key(1), Some(&Cbor::Int(2)));
+        assert_eq!(value.int_key(3), Some(&Cbor::Int(-7)));
+        assert_eq!(value.text_key("a"), Some(&Cbor::Bytes(vec![1, 2])));
+        assert_eq!(
+            value.text_key("b"),
+            Some(&Cbor::Array(vec![Cbor::Bool(true), Cbor::Null]))
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_and_indefinite_cbor() {
+        assert!(decode_cbor(&[0x42, 0x01]).is_err());
+        assert!(decode_cbor(&[0x5f, 0x41, 0x00, 0xff]).is_err());
+        assert!(decode_cbor(&[0x9b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]).is_err());
+    }
+
+    #[test]
+    fn parses_es256_cose_keys() {
+        let mut cose = vec![0xa5, 0x01, 0x02, 0x03, 0x26, 0x20, 0x01, 0x21, 0x58, 0x20];
+        cose.extend_from_slice(&[0x11; 32]);
+        cose.extend_from_slice(&[0x22, 0x58, 0x20]);
+        cose.extend_from_slice(&[0x33; 32]);
+        match CoseKey::parse(&cose).unwrap() {
+            CoseKey::Es256(point) => {
+                assert_eq!(point.len(), 65);
+                assert_eq!(point[0], 0x04);
+                assert_eq!(point[1], 0x11);
+                assert_eq!(point[64], 0x33);
+            }
+            CoseKey::Ed25519(_) => panic!("expected an ES256 key"),
+        }
+        // RS256 (alg -257) is not supported.
+        let rsa = [0xa2, 0x01, 0x03, 0x03, 0x39, 0x01, 0x00];
+        assert!(CoseKey::parse(&rsa).is_err());
+    }
+
+    #[test]
+    fn sign_counters_must_advance_unless_unused() {
+        assert!(sign_count_advanced(0, 0));
+        assert!(sign_count_advanced(0, 1));
+        assert!(sign_count_advanced(41, 42));
+        assert!(!sign_count_advanced(42, 42));
+        assert!(!sign_count_advanced(42, 7));
+        assert!(!sign_count_advanced(5, 0));
+    }
+}
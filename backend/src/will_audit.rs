@@ -5,13 +5,14 @@
 
 use crate::api_error::ApiError;
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, PgPool, Row};
 use uuid::Uuid;
 
 // ─── Audit Log Entry ──────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AuditLogEntry {
     pub id: Uuid,
     pub event_type: String,
@@ -44,7 +45,7 @@ impl<'r> FromRow<'r, sqlx::postgres::PgRow> for AuditLogEntry {
 
 // ─── Audit Log Summary ────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AuditLogSummary {
     pub total_events: i64,
     pub event_type_counts: Vec<EventTypeCount>,
@@ -53,7 +54,7 @@ pub struct AuditLogSummary {
     pub last_event_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
 pub struct EventTypeCount {
     pub event_type: String,
     pub count: i64,
@@ -61,7 +62,7 @@ pub struct EventTypeCount {
 
 // ─── Query Filters ────────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 pub struct AuditLogFilters {
     pub document_id: Option<Uuid>,
     pub plan_id: Option<Uuid>,
@@ -76,7 +77,7 @@ pub struct AuditLogFilters {
 
 // ─── User Activity Summary ────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct UserActivitySummary {
     pub user_id: Uuid,
     pub total_actions: i64,
@@ -337,7 +338,7 @@ impl WillAuditService {
 
 // ─── Admin Statistics ─────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AdminAuditStatistics {
     pub total_events: i64,
     pub unique_plans: i64,
@@ -4,6 +4,7 @@
 //! requirements including witness counts, required fields, and formatting rules.
 
 use rust_decimal::Decimal;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::will_pdf::{BeneficiaryEntry, WillDocumentInput};
@@ -71,7 +72,7 @@ fn global_rules() -> JurisdictionRules {
 
 // --- Validation Types ---
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ValidationResult {
     pub is_valid: bool,
     pub jurisdiction: String,
@@ -79,7 +80,7 @@ pub struct ValidationResult {
     pub warnings: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ValidationError {
     pub field: String,
     pub message: String,
@@ -109,11 +110,17 @@ impl WillComplianceService {
         ]
     }
 
-    pub fn validate(input: &WillDocumentInput, witness_count: u32) -> ValidationResult {
+    /// Check `input` against its jurisdiction's rules. `notarized` is whether
+    /// the will document carries a sealed notary certificate.
+    pub fn validate(
+        input: &WillDocumentInput,
+        witness_count: u32,
+        notarized: bool,
+    ) -> ValidationResult {
         let jurisdiction_key = input.jurisdiction.as_deref().unwrap_or("GLOBAL");
         let rules = Self::get_jurisdiction_rules(jurisdiction_key);
         let mut errors: Vec<ValidationError> = Vec::new();
-        let mut warnings: Vec<String> = Vec::new();
+        let warnings: Vec<String> = Vec::new();
 
         // Owner name required
         if input.owner_name.trim().is_empty() {
@@ -193,12 +200,16 @@ impl WillComplianceService {
             });
         }
 
-        // Notarization warning
-        if rules.require_notarization {
-            warnings.push(format!(
-                "{} jurisdiction requires notarization. Ensure the document is notarized before execution.",
-                rules.jurisdiction
-            ));
+        // Notarization
+        if rules.require_notarization && !notarized {
+            errors.push(ValidationError {
+                field: "notarization".to_string(),
+                message: format!(
+                    "{} jurisdiction requires the will to be notarized before execution",
+                    rules.jurisdiction
+                ),
+                severity: "error".to_string(),
+            });
         }
 
         let is_valid = errors.is_empty();
@@ -280,7 +291,7 @@ mod tests {
     #[test]
     fn test_valid_document_passes() {
         let input = valid_input();
-        let result = WillComplianceService::validate(&input, 2);
+        let result = WillComplianceService::validate(&input, 2, false);
         assert!(result.is_valid);
         assert!(result.errors.is_empty());
         assert_eq!(result.jurisdiction, "US");
@@ -290,7 +301,7 @@ mod tests {
     fn test_missing_owner_name() {
         let mut input = valid_input();
         input.owner_name = "".to_string();
-        let result = WillComplianceService::validate(&input, 2);
+        let result = WillComplianceService::validate(&input, 2, false);
         assert!(!result.is_valid);
         assert!(result.errors.iter().any(|e| e.field == "owner_name"));
     }
@@ -299,7 +310,7 @@ mod tests {
     fn test_missing_owner_wallet() {
         let mut input = valid_input();
         input.owner_wallet = "  ".to_string();
-        let result = WillComplianceService::validate(&input, 2);
+        let result = WillComplianceService::validate(&input, 2, false);
         assert!(!result.is_valid);
         assert!(result.errors.iter().any(|e| e.field == "owner_wallet"));
     }
@@ -307,7 +318,7 @@ mod tests {
     #[test]
     fn test_insufficient_witnesses_us() {
         let input = valid_input();
-        let result = WillComplianceService::validate(&input, 1);
+        let result = WillComplianceService::validate(&input, 1, false);
         assert!(!result.is_valid);
         assert!(result.errors.iter().any(|e| e.field == "witness_count"));
     }
@@ -316,7 +327,7 @@ mod tests {
     fn test_allocation_not_100() {
         let mut input = valid_input();
         input.beneficiaries[0].allocation_percent = dec!(50);
-        let result = WillComplianceService::validate(&input, 2);
+        let result = WillComplianceService::validate(&input, 2, false);
         assert!(!result.is_valid);
         assert!(result
             .errors
@@ -328,7 +339,7 @@ mod tests {
     fn test_empty_beneficiary_name() {
         let mut input = valid_input();
         input.beneficiaries[0].name = "".to_string();
-        let result = WillComplianceService::validate(&input, 2);
+        let result = WillComplianceService::validate(&input, 2, false);
         assert!(!result.is_valid);
         assert!(result
             .errors
@@ -340,7 +351,7 @@ mod tests {
     fn test_empty_beneficiary_wallet() {
         let mut input = valid_input();
         input.beneficiaries[0].wallet_address = "".to_string();
-        let result = WillComplianceService::validate(&input, 2);
+        let result = WillComplianceService::validate(&input, 2, false);
         assert!(!result.is_valid);
         assert!(result
             .errors
@@ -353,7 +364,7 @@ mod tests {
         let mut input = valid_input();
         input.jurisdiction = Some("UK".to_string());
         input.beneficiaries[0].relationship = None;
-        let result = WillComplianceService::validate(&input, 2);
+        let result = WillComplianceService::validate(&input, 2, false);
         assert!(!result.is_valid);
         assert!(result
             .errors
@@ -365,25 +376,27 @@ mod tests {
     fn test_uk_valid_with_relationship() {
         let mut input = valid_input();
         input.jurisdiction = Some("UK".to_string());
-        let result = WillComplianceService::validate(&input, 2);
+        let result = WillComplianceService::validate(&input, 2, false);
         assert!(result.is_valid);
     }
 
     #[test]
-    fn test_eu_notarization_warning() {
+    fn test_eu_requires_notarization() {
         let mut input = valid_input();
         input.jurisdiction = Some("EU".to_string());
-        let result = WillComplianceService::validate(&input, 2);
+        let result = WillComplianceService::validate(&input, 2, false);
+        assert!(!result.is_valid);
+        assert!(result.errors.iter().any(|e| e.field == "notarization"));
+
+        let result = WillComplianceService::validate(&input, 2, true);
         assert!(result.is_valid);
-        assert!(!result.warnings.is_empty());
-        assert!(result.warnings[0].contains("notarization"));
     }
 
     #[test]
     fn test_global_fallback_one_witness() {
         let mut input = valid_input();
         input.jurisdiction = None;
-        let result = WillComplianceService::validate(&input, 1);
+        let result = WillComplianceService::validate(&input, 1, false);
         assert!(result.is_valid);
         assert_eq!(result.jurisdiction, "GLOBAL");
     }
@@ -392,7 +405,7 @@ mod tests {
     fn test_no_beneficiaries() {
         let mut input = valid_input();
         input.beneficiaries.clear();
-        let result = WillComplianceService::validate(&input, 2);
+        let result = WillComplianceService::validate(&input, 2, false);
         assert!(!result.is_valid);
         assert!(result.errors.iter().any(|e| e.field == "beneficiaries"));
     }
@@ -427,7 +440,7 @@ mod tests {
         input.owner_name = "".to_string();
         input.owner_wallet = "".to_string();
         input.beneficiaries[0].allocation_percent = dec!(50);
-        let result = WillComplianceService::validate(&input, 0);
+        let result = WillComplianceService::validate(&input, 0, false);
         assert!(!result.is_valid);
         assert!(result.errors.len() >= 3);
     }
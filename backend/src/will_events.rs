@@ -4,6 +4,8 @@
 //! backend indexing, auditing, and transparency. Supports real-time frontend
 //! updates, audit trails, and monitoring/analytics.
 
+use crate::audit_chain::{self, AuditStream};
+use crate::will_audit::AuditLogEntry;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
@@ -103,6 +105,25 @@ pub enum WillEvent {
         witness_id: Uuid,
         timestamp: DateTime<Utc>,
     },
+    WillNotarized {
+        vault_id: String,
+        document_id: Uuid,
+        plan_id: Uuid,
+        session_id: Uuid,
+        notary_id: Uuid,
+        commission_number: String,
+        timestamp: DateTime<Utc>,
+    },
+    WillRevoked {
+        vault_id: String,
+        document_id: Uuid,
+        plan_id: Uuid,
+        revoked_by: Uuid,
+        will_type: String,
+        invalidated_signatures: u32,
+        reason: Option<String>,
+        timestamp: DateTime<Utc>,
+    },
 }
 
 impl WillEvent {
@@ -119,6 +140,8 @@ impl WillEvent {
             WillEvent::WillVerified { .. } => "will_verified",
             WillEvent::WitnessInvited { .. } => "witness_invited",
             WillEvent::WitnessDeclined { .. } => "witness_declined",
+            WillEvent::WillNotarized { .. } => "will_notarized",
+            WillEvent::WillRevoked { .. } => "will_revoked",
         }
     }
 
@@ -134,7 +157,9 @@ impl WillEvent {
             | WillEvent::WillBackupCreated { document_id, .. }
             | WillEvent::WillVerified { document_id, .. }
             | WillEvent::WitnessInvited { document_id, .. }
-            | WillEvent::WitnessDeclined { document_id, .. } => *document_id,
+            | WillEvent::WitnessDeclined { document_id, .. }
+            | WillEvent::WillNotarized { document_id, .. }
+            | WillEvent::WillRevoked { document_id, .. } => *document_id,
         }
     }
 
@@ -150,7 +175,9 @@ impl WillEvent {
             | WillEvent::WillBackupCreated { plan_id, .. }
             | WillEvent::WillVerified { plan_id, .. }
             | WillEvent::WitnessInvited { plan_id, .. }
-            | WillEvent::WitnessDeclined { plan_id, .. } => *plan_id,
+            | WillEvent::WitnessDeclined { plan_id, .. }
+            | WillEvent::WillNotarized { plan_id, .. }
+            | WillEvent::WillRevoked { plan_id, .. } => *plan_id,
         }
     }
 
@@ -166,7 +193,9 @@ impl WillEvent {
             | WillEvent::WillBackupCreated { vault_id, .. }
             | WillEvent::WillVerified { vault_id, .. }
             | WillEvent::WitnessInvited { vault_id, .. }
-            | WillEvent::WitnessDeclined { vault_id, .. } => vault_id,
+            | WillEvent::WitnessDeclined { vault_id, .. }
+            | WillEvent::WillNotarized { vault_id, .. }
+            | WillEvent::WillRevoked { vault_id, .. } => vault_id,
         }
     }
 
@@ -182,7 +211,9 @@ impl WillEvent {
             | WillEvent::WillBackupCreated { timestamp, .. }
             | WillEvent::WillVerified { timestamp, .. }
             | WillEvent::WitnessInvited { timestamp, .. }
-            | WillEvent::WitnessDeclined { timestamp, .. } => *timestamp,
+            | WillEvent::WitnessDeclined { timestamp, .. }
+            | WillEvent::WillNotarized { timestamp, .. }
+            | WillEvent::WillRevoked { timestamp, .. } => *timestamp,
         }
     }
 }
@@ -194,46 +225,51 @@ pub struct WillEventService;
 impl WillEventService {
     /// Emit a will event and persist it to the database for auditing and indexing
     pub async fn emit(db: &PgPool, event: WillEvent) -> Result<Uuid, crate::api_error::ApiError> {
-        let event_id = Uuid::new_v4();
-        let event_type = event.event_type();
-        let document_id = event.document_id();
-        let plan_id = event.plan_id();
-        let vault_id = event.vault_id();
-        let timestamp = event.timestamp();
-        let event_data = serde_json::to_value(&event).map_err(|e| {
-            crate::api_error::ApiError::Internal(anyhow::anyhow!(
-                "Failed to serialize event: {}",
-                e
-            ))
-        })?;
-
-        sqlx::query(
-            r#"
-            INSERT INTO will_event_log
-                (id, event_type, document_id, plan_id, vault_id, event_data, created_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
-            "#,
-        )
-        .bind(event_id)
-        .bind(event_type)
-        .bind(document_id)
-        .bind(plan_id)
-        .bind(vault_id)
-        .bind(event_data)
-        .bind(timestamp)
+        let entry = AuditLogEntry {
+            id: Uuid::new_v4(),
+            event_type: event.event_type().to_string(),
+            document_id: event.document_id(),
+            plan_id: event.plan_id(),
+            vault_id: event.vault_id().to_string(),
+            user_id: None,
+            event_data: serde_json::to_value(&event).map_err(|e| {
+                crate::api_error::ApiError::Internal(anyhow::anyhow!(
+                    "Failed to serialize event: {}",
+                    e
+                ))
+            })?,
+            ip_address: None,
+            user_agent: None,
+            created_at: audit_chain::entry_timestamp(event.timestamp()),
+        };
+        let content_hash = audit_chain::content_hash(&entry)?;
+
+        sqlx::query(&audit_chain::chained_insert_sql(
+            AuditStream::WillEvents,
+            "id, event_type, document_id, plan_id, vault_id, event_data, created_at",
+            "$2, $3, $4, $5, $6, $7, $8",
+        ))
+        .bind(content_hash.as_slice())
+        .bind(entry.id)
+        .bind(&entry.event_type)
+        .bind(entry.document_id)
+        .bind(entry.plan_id)
+        .bind(&entry.vault_id)
+        .bind(&entry.event_data)
+        .bind(entry.created_at)
         .execute(db)
         .await?;
 
         tracing::info!(
-            event_id = %event_id,
-            event_type = %event_type,
-            document_id = %document_id,
-            plan_id = %plan_id,
-            vault_id = %vault_id,
+            event_id = %entry.id,
+            event_type = %entry.event_type,
+            document_id = %entry.document_id,
+            plan_id = %entry.plan_id,
+            vault_id = %entry.vault_id,
             "Will event emitted"
         );
 
-        Ok(event_id)
+        Ok(entry.id)
     }
 
     /// Retrieve all events for a specific document
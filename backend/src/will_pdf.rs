@@ -7,13 +7,14 @@ use crate::api_error::ApiError;
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use chrono::{DateTime, Utc};
 use ring::digest::{digest, SHA256};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use uuid::Uuid;
 
 // ─── Template Types ───────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum WillTemplate {
     Simple,
@@ -61,7 +62,7 @@ impl std::str::FromStr for WillTemplate {
 
 // ─── Data Structures ──────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BeneficiaryEntry {
     pub name: String,
     pub wallet_address: String,
@@ -69,7 +70,7 @@ pub struct BeneficiaryEntry {
     pub relationship: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WillDocumentInput {
     pub plan_id: Uuid,
     pub owner_name: String,
@@ -82,7 +83,7 @@ pub struct WillDocumentInput {
     pub will_hash_reference: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct GeneratedWillDocument {
     pub document_id: Uuid,
     pub plan_id: Uuid,
@@ -233,7 +234,8 @@ impl TemplateEngine {
 
 /// Builds a minimal valid PDF containing the will text.
 /// Uses raw PDF syntax — no external crate required.
-fn build_pdf(content: &str) -> Vec<u8> {
+/// Page content stream drawing `content` line by line in 10pt Courier.
+fn text_stream(content: &str) -> String {
     // Escape special PDF string characters
     let escaped = content
         .replace('\\', "\\\\")
@@ -248,7 +250,11 @@ fn build_pdf(content: &str) -> Vec<u8> {
         stream_content.push_str(&format!("({line}) Tj T*\n"));
     }
     stream_content.push_str("ET\n");
+    stream_content
+}
 
+fn build_pdf(content: &str) -> Vec<u8> {
+    let stream_content = text_stream(content);
     let stream_bytes = stream_content.as_bytes();
     let stream_len = stream_bytes.len();
 
@@ -307,6 +313,96 @@ fn build_pdf(content: &str) -> Vec<u8> {
     pdf
 }
 
+/// Append a page carrying `certificate_text` to a generated will PDF, and
+/// record `certificate_json` in the document information dictionary under
+/// `/InheritXNotaryCertificate` for machine verification.
+pub fn embed_notary_certificate(
+    pdf: &[u8],
+    certificate_text: &str,
+    certificate_json: &str,
+) -> Result<Vec<u8>, ApiError> {
+    append_page(
+        pdf,
+        certificate_text,
+        "InheritXNotaryCertificate",
+        certificate_json,
+    )
+    .map_err(|e| ApiError::Internal(anyhow::anyhow!("Unable to embed notary certificate: {e}")))
+}
+
+/// Append a witness attestation page to a generated will PDF, recording
+/// `attestation_json` under `/InheritXWitnessAttestation-<witness_id>`.
+pub fn embed_witness_attestation(
+    pdf: &[u8],
+    witness_id: Uuid,
+    attestation_text: &str,
+    attestation_json: &str,
+) -> Result<Vec<u8>, ApiError> {
+    append_page(
+        pdf,
+        attestation_text,
+        &format!("InheritXWitnessAttestation-{witness_id}"),
+        attestation_json,
+    )
+    .map_err(|e| ApiError::Internal(anyhow::anyhow!("Unable to embed witness attestation: {e}")))
+}
+
+/// Append a text page and set `info_key` in the information dictionary,
+/// keeping any entries earlier pages recorded there.
+fn append_page(
+    pdf: &[u8],
+    text: &str,
+    info_key: &str,
+    info_value: &str,
+) -> Result<Vec<u8>, lopdf::Error> {
+    use lopdf::{dictionary, Dictionary, Object, Stream};
+
+    let mut document = lopdf::Document::load_mem(pdf)?;
+    let pages_id = document
+        .catalog()
+        .and_then(|catalog| catalog.get(b"Pages"))
+        .and_then(Object::as_reference)?;
+
+    let font_id = document.add_object(dictionary! {
+        "Type" => "Font",
+        "Subtype" => "Type1",
+        "BaseFont" => "Courier",
+    });
+    let content_id =
+        document.add_object(Stream::new(dictionary! {}, text_stream(text).into_bytes()));
+    let page_id = document.add_object(dictionary! {
+        "Type" => "Page",
+        "Parent" => pages_id,
+        "MediaBox" => vec![0.into(), 0.into(), 612.into(), 792.into()],
+        "Contents" => content_id,
+        "Resources" => dictionary! { "Font" => dictionary! { "F1" => font_id } },
+    });
+
+    let pages = document.get_dictionary_mut(pages_id)?;
+    let count = pages.get(b"Count").and_then(Object::as_i64).unwrap_or(0);
+    pages
+        .get_mut(b"Kids")
+        .and_then(Object::as_array_mut)?
+        .push(page_id.into());
+    pages.set("Count", count + 1);
+
+    let info_id = match document.trailer.get(b"Info").and_then(Object::as_reference) {
+        Ok(id) => id,
+        Err(_) => {
+            let id = document.add_object(Dictionary::new());
+            document.trailer.set("Info", id);
+            id
+        }
+    };
+    document
+        .get_dictionary_mut(info_id)?
+        .set(info_key, Object::string_literal(info_value));
+
+    let mut out = Vec::new();
+    document.save_to(&mut out)?;
+    Ok(out)
+}
+
 // ─── Will PDF Service ─────────────────────────────────────────────────────────
 
 pub struct WillPdfService;
@@ -569,6 +665,48 @@ mod tests {
         assert!(WillTemplate::from_str("unknown").is_err());
     }
 
+    #[test]
+    fn test_embed_notary_certificate_adds_page() {
+        let input = sample_input(WillTemplate::Formal);
+        let pdf = build_pdf(&TemplateEngine::render(&input, Utc::now(), 1));
+        let embedded =
+            embed_notary_certificate(&pdf, "NOTARIAL CERTIFICATE\nSeal: abc", r#"{"seal":"abc"}"#)
+                .unwrap();
+        let document = lopdf::Document::load_mem(&embedded).unwrap();
+        assert_eq!(document.get_pages().len(), 2);
+        assert!(document
+            .extract_text(&[2])
+            .unwrap()
+            .contains("NOTARIAL CERTIFICATE"));
+    }
+
+    #[test]
+    fn test_witness_attestation_keeps_notary_certificate() {
+        let input = sample_input(WillTemplate::Formal);
+        let pdf = build_pdf(&TemplateEngine::render(&input, Utc::now(), 1));
+        let notarized =
+            embed_notary_certificate(&pdf, "NOTARIAL CERTIFICATE", r#"{"seal":"abc"}"#).unwrap();
+        let witness_id = Uuid::new_v4();
+        let attested = embed_witness_attestation(
+            &notarized,
+            witness_id,
+            "WITNESS ATTESTATION",
+            r#"{"name":"Wes"}"#,
+        )
+        .unwrap();
+
+        let document = lopdf::Document::load_mem(&attested).unwrap();
+        assert_eq!(document.get_pages().len(), 3);
+        let info = document
+            .trailer
+            .get(b"Info")
+            .and_then(lopdf::Object::as_reference)
+            .and_then(|id| document.get_dictionary(id))
+            .unwrap();
+        assert!(info.has(b"InheritXNotaryCertificate"));
+        assert!(info.has(format!("InheritXWitnessAttestation-{witness_id}").as_bytes()));
+    }
+
     #[test]
     fn test_pdf_base64_roundtrip() {
         let input = sample_input(WillTemplate::Formal);
@@ -5,10 +5,12 @@
 //! and prevents replay attacks via a nonce system.
 
 use crate::api_error::ApiError;
+use crate::joint_will::JointWillService;
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use chrono::{DateTime, Duration, Utc};
 use ring::digest::{digest, SHA256};
 use ring::signature;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use stellar_strkey::Strkey;
@@ -16,14 +18,14 @@ use uuid::Uuid;
 
 // ─── Types ────────────────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SigningChallengeRequest {
     pub document_id: Uuid,
     pub vault_id: String,
     pub wallet_address: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SigningChallenge {
     pub challenge_id: Uuid,
     pub message: String,
@@ -31,7 +33,7 @@ pub struct SigningChallenge {
     pub expires_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SubmitSignatureRequest {
     pub challenge_id: Uuid,
     pub wallet_address: String,
@@ -39,7 +41,7 @@ pub struct SubmitSignatureRequest {
     pub signature_hex: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WillSignatureRecord {
     pub id: Uuid,
     pub document_id: Uuid,
@@ -48,6 +50,10 @@ pub struct WillSignatureRecord {
     pub document_hash: String,
     pub signature_hex: String,
     pub signed_at: DateTime<Utc>,
+    /// Testator party for joint and mirror wills
+    pub party_id: Option<Uuid>,
+    /// Set when a revocation withdrew this signature
+    pub invalidated_at: Option<DateTime<Utc>>,
 }
 
 // ─── Service ──────────────────────────────────────────────────────────────────
@@ -57,8 +63,10 @@ pub struct WillSignatureService;
 impl WillSignatureService {
     /// Step 1: Generate a signing challenge (message + nonce) for the user.
     /// The message binds document_hash + vault_id + nonce to prevent replay attacks.
+    /// On a multi-party will the challenge is bound to the user's testator party.
     pub async fn create_challenge(
         db: &PgPool,
+        user_id: Uuid,
         req: &SigningChallengeRequest,
     ) -> Result<SigningChallenge, ApiError> {
         // Fetch document hash from DB
@@ -72,6 +80,10 @@ impl WillSignatureService {
             ApiError::NotFound(format!("Will document {} not found", req.document_id))
         })?;
 
+        let party_id =
+            JointWillService::party_for_signing(db, req.document_id, user_id, &req.wallet_address)
+                .await?;
+
         let challenge_id = Uuid::new_v4();
         let nonce = Uuid::new_v4().to_string();
         let expires_at = Utc::now() + Duration::minutes(10);
@@ -90,8 +102,9 @@ impl WillSignatureService {
         sqlx::query(
             r#"
             INSERT INTO will_signing_challenges
-                (id, document_id, vault_id, wallet_address, message, message_hash, nonce, expires_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                (id, document_id, vault_id, wallet_address, message, message_hash, nonce, expires_at,
+                 party_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
             "#,
         )
         .bind(challenge_id)
@@ -102,6 +115,7 @@ impl WillSignatureService {
         .bind(&message_hash)
         .bind(&nonce)
         .bind(expires_at)
+        .bind(party_id)
         .execute(db)
         .await?;
 
@@ -130,11 +144,12 @@ impl WillSignatureService {
             message_hash: String,
             expires_at: DateTime<Utc>,
             used: bool,
+            party_id: Option<Uuid>,
         }
 
         let row = sqlx::query_as::<_, ChallengeRow>(
-            "SELECT document_id, vault_id, wallet_address, message, message_hash, expires_at, used \
-             FROM will_signing_challenges WHERE id = $1 FOR UPDATE",
+            "SELECT document_id, vault_id, wallet_address, message, message_hash, expires_at, used, \
+             party_id FROM will_signing_challenges WHERE id = $1 FOR UPDATE",
         )
         .bind(req.challenge_id)
         .fetch_optional(&mut *tx)
@@ -184,11 +199,29 @@ impl WillSignatureService {
         let record_id = Uuid::new_v4();
         let signed_at = Utc::now();
 
+        // A testator party signs once, and not after a revocation
+        if let Some(party_id) = row.party_id {
+            let signed = sqlx::query(
+                "UPDATE will_document_parties SET status = 'signed', signed_at = $2 \
+                 WHERE id = $1 AND status = 'pending'",
+            )
+            .bind(party_id)
+            .bind(signed_at)
+            .execute(&mut *tx)
+            .await?;
+            if signed.rows_affected() == 0 {
+                return Err(ApiError::Conflict(
+                    "Testator can no longer sign this will".to_string(),
+                ));
+            }
+        }
+
         sqlx::query(
             r#"
             INSERT INTO will_signatures
-                (id, document_id, vault_id, wallet_address, document_hash, signature_hex, signed_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+                (id, document_id, vault_id, wallet_address, document_hash, signature_hex, signed_at,
+                 party_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             "#,
         )
         .bind(record_id)
@@ -198,11 +231,14 @@ impl WillSignatureService {
         .bind(&row.message_hash)
         .bind(&req.signature_hex)
         .bind(signed_at)
+        .bind(row.party_id)
         .execute(&mut *tx)
         .await?;
 
         tx.commit().await?;
 
+        JointWillService::advance(db, row.document_id).await?;
+
         // Fetch plan_id for event
         let plan_id: Option<Uuid> =
             sqlx::query_scalar("SELECT plan_id FROM will_documents WHERE id = $1")
@@ -235,6 +271,8 @@ impl WillSignatureService {
             document_hash: row.message_hash,
             signature_hex: req.signature_hex.clone(),
             signed_at,
+            party_id: row.party_id,
+            invalidated_at: None,
         })
     }
 
@@ -244,9 +282,10 @@ impl WillSignatureService {
         document_id: Uuid,
         user_id: Uuid,
     ) -> Result<Vec<WillSignatureRecord>, ApiError> {
-        // Verify the document belongs to this user
+        // Verify the document belongs to this user or names them as a testator
         let exists: bool = sqlx::query_scalar(
-            "SELECT EXISTS(SELECT 1 FROM will_documents WHERE id = $1 AND user_id = $2)",
+            "SELECT EXISTS(SELECT 1 FROM will_documents WHERE id = $1 AND user_id = $2) \
+             OR EXISTS(SELECT 1 FROM will_document_parties WHERE document_id = $1 AND user_id = $2)",
         )
         .bind(document_id)
         .bind(user_id)
@@ -268,11 +307,13 @@ impl WillSignatureService {
             document_hash: String,
             signature_hex: String,
             signed_at: DateTime<Utc>,
+            party_id: Option<Uuid>,
+            invalidated_at: Option<DateTime<Utc>>,
         }
 
         let rows = sqlx::query_as::<_, Row>(
-            "SELECT id, document_id, vault_id, wallet_address, document_hash, signature_hex, signed_at \
-             FROM will_signatures WHERE document_id = $1 ORDER BY signed_at DESC",
+            "SELECT id, document_id, vault_id, wallet_address, document_hash, signature_hex, signed_at, \
+             party_id, invalidated_at FROM will_signatures WHERE document_id = $1 ORDER BY signed_at DESC",
         )
         .bind(document_id)
         .fetch_all(db)
@@ -288,12 +329,14 @@ impl WillSignatureService {
                 document_hash: r.document_hash,
                 signature_hex: r.signature_hex,
                 signed_at: r.signed_at,
+                party_id: r.party_id,
+                invalidated_at: r.invalidated_at,
             })
             .collect())
     }
 
     /// Decode a Stellar G-address or hex string into 32 raw public key bytes.
-    fn decode_public_key(wallet_address: &str) -> Result<[u8; 32], ApiError> {
+    pub(crate) fn decode_public_key(wallet_address: &str) -> Result<[u8; 32], ApiError> {
         if wallet_address.starts_with('G') && wallet_address.len() == 56 {
             let strkey = Strkey::from_string(wallet_address)
                 .map_err(|_| ApiError::BadRequest("Invalid Stellar address".to_string()))?;
@@ -0,0 +1,978 @@
+//! RFC 3161 trusted timestamps for finalized wills.
+//!
+//! When a will version is finalized, [`TimestampClient`] asks a Time-Stamp
+//! Authority to countersign the will hash. The returned token is validated
+//! (message imprint, nonce, CMS signature and signing-certificate binding)
+//! before it is stored in `will_timestamps`, and validated again whenever
+//! [`crate::document_verification::DocumentVerificationService`] reports on
+//! the document.
+//!
+//! Configured by `TSA_URL`. `TSA_CERTIFICATE_PATH` optionally pins the TSA's
+//! signing certificate (PEM or DER); without a pin the certificate embedded in
+//! the token is used and its SHA-256 fingerprint is reported so it can be
+//! compared with the certificate the TSA publishes. Wills finalized while the
+//! TSA is unreachable are picked up by [`WillTimestampService`].
+
+use crate::api_error::ApiError;
+use base64::Engine as _;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use ring::digest::{digest, SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::signature::{
+    UnparsedPublicKey, VerificationAlgorithm, ECDSA_P256_SHA256_ASN1, ECDSA_P384_SHA256_ASN1,
+    RSA_PKCS1_2048_8192_SHA256,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use thiserror::Error;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+const TIMESTAMP_BATCH_SIZE: i64 = 20;
+
+// DER-encoded object identifier contents.
+const OID_SHA256: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01];
+const OID_SIGNED_DATA: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x07, 0x02];
+const OID_TST_INFO: &[u8] = &[
+    0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x10, 0x01, 0x04,
+];
+const OID_CONTENT_TYPE: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x03];
+const OID_MESSAGE_DIGEST: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x04];
+const OID_SIGNING_CERTIFICATE_V2: &[u8] = &[
+    0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x10, 0x02, 0x2F,
+];
+const OID_RSA_ENCRYPTION: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x01];
+const OID_SHA256_WITH_RSA: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0B];
+const OID_EC_PUBLIC_KEY: &[u8] = &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x02, 0x01];
+const OID_ECDSA_WITH_SHA256: &[u8] = &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x02];
+const OID_P256: &[u8] = &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x03, 0x01, 0x07];
+const OID_P384: &[u8] = &[0x2B, 0x81, 0x04, 0x00, 0x22];
+
+// ── DER ───────────────────────────────────────────────────────────────────────
+
+/// The small subset of DER needed for RFC 3161 requests, responses and the
+/// CMS/X.509 structures inside them.
+mod der {
+    pub const BOOLEAN: u8 = 0x01;
+    pub const INTEGER: u8 = 0x02;
+    pub const BIT_STRING: u8 = 0x03;
+    pub const OCTET_STRING: u8 = 0x04;
+    pub const NULL: u8 = 0x05;
+    pub const OID: u8 = 0x06;
+    pub const UTC_TIME: u8 = 0x17;
+    pub const GENERALIZED_TIME: u8 = 0x18;
+    pub const SEQUENCE: u8 = 0x30;
+    pub const SET: u8 = 0x31;
+    pub const CONTEXT_0: u8 = 0xA0;
+    pub const CONTEXT_1: u8 = 0xA1;
+
+    /// One tag-length-value element; `raw` includes the header.
+    #[derive(Debug, Clone, Copy)]
+    pub struct Tlv<'a> {
+        pub tag: u8,
+        pub content: &'a [u8],
+        pub raw: &'a [u8],
+    }
+
+    impl<'a> Tlv<'a> {
+        pub fn reader(&self) -> Reader<'a> {
+            Reader::new(self.content)
+        }
+    }
+
+    pub struct Reader<'a> {
+        rest: &'a [u8],
+    }
+
+    impl<'a> Reader<'a> {
+        pub fn new(input: &'a [u8]) -> Self {
+            Self { rest: input }
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.rest.is_empty()
+        }
+
+        pub fn peek_tag(&self) -> Option<u8> {
+            self.rest.first().copied()
+        }
+
+        #[allow(clippy::should_implement_trait)]
+        pub fn next(&mut self) -> Result<Tlv<'a>, String> {
+            let input = self.rest;
+            if input.len() < 2 {
+                return Err("truncated DER element".into());
+            }
+            let tag = input[0];
+            if tag & 0x1F == 0x1F {
+                return Err("multi-byte DER tags are not supported".into());
+            }
+            let (len, header) = match input[1] {
+                n if n < 0x80 => (n as usize, 2),
+                0x80 => return Err("indefinite-length encoding is not DER".into()),
+                n => {
+                    let count = (n & 0x7F) as usize;
+                    if count > 4 || input.len() < 2 + count {
+                        return Err("unsupported DER length".into());
+                    }
+                    let len = input[2..2 + count]
+                        .iter()
+                        .fold(0usize, |acc, b| (acc << 8) | *b as usize);
+                    (len, 2 + count)
+                }
+            };
+            let end = header
+                .checked_add(len)
+                .filter(|end| *end <= input.len())
+                .ok_or_else(|| "truncated DER element".to_string())?;
+            self.rest = &input[end..];
+            Ok(Tlv {
+                tag,
+                content: &input[header..end],
+                raw: &input[..end],
+            })
+        }
+
+        pub fn expect(&mut self, tag: u8) -> Result<Tlv<'a>, String> {
+            let tlv = self.next()?;
+            if tlv.tag != tag {
+                return Err(format!(
+                    "expected DER tag {tag:#04x}, found {:#04x}",
+                    tlv.tag
+                ));
+            }
+            Ok(tlv)
+        }
+
+        pub fn optional(&mut self, tag: u8) -> Result<Option<Tlv<'a>>, String> {
+            if self.peek_tag() == Some(tag) {
+                self.next().map(Some)
+            } else {
+                Ok(None)
+            }
+        }
+    }
+
+    pub fn encode(tag: u8, content: &[u8]) -> Vec<u8> {
+        let mut out = vec![tag];
+        let len = content.len();
+        if len < 0x80 {
+            out.push(len as u8);
+        } else {
+            let bytes = len.to_be_bytes();
+            let skip = bytes.iter().take_while(|b| **b == 0).count();
+            out.push(0x80 | (bytes.len() - skip) as u8);
+            out.extend_from_slice(&bytes[skip..]);
+        }
+        out.extend_from_slice(content);
+        out
+    }
+
+    /// Dotted-decimal form of an OBJECT IDENTIFIER's content bytes.
+    pub fn oid_to_string(content: &[u8]) -> String {
+        let mut arcs = Vec::new();
+        let mut value: u64 = 0;
+        for byte in content {
+            value = (value << 7) | u64::from(byte & 0x7F);
+            if byte & 0x80 == 0 {
+                if arcs.is_empty() {
+                    let first = (value / 40).min(2);
+                    arcs.push(first);
+                    arcs.push(value - first * 40);
+                } else {
+                    arcs.push(value);
+                }
+                value = 0;
+            }
+        }
+        arcs.iter()
+            .map(u64::to_string)
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+}
+
+// ── Token validation ──────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TimestampError {
+    #[error("malformed timestamp token: {0}")]
+    Malformed(String),
+    #[error("timestamp request rejected by TSA: {0}")]
+    Rejected(String),
+    #[error("timestamp does not cover the will hash")]
+    ImprintMismatch,
+    #[error("timestamp nonce does not match the request")]
+    NonceMismatch,
+    #[error("timestamp signature is invalid: {0}")]
+    InvalidSignature(String),
+}
+
+impl From<String> for TimestampError {
+    fn from(reason: String) -> Self {
+        Self::Malformed(reason)
+    }
+}
+
+/// The facts a validated token attests to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TimestampInfo {
+    /// Time at which the TSA countersigned the hash.
+    pub gen_time: DateTime<Utc>,
+    /// TSA-assigned serial number (hex).
+    pub serial_number: String,
+    /// TSA policy under which the token was issued.
+    pub policy_oid: String,
+    /// SHA-256 fingerprint of the certificate that signed the token.
+    pub signer_certificate_sha256: String,
+}
+
+/// DER-encoded `TimeStampReq` for a SHA-256 message imprint.
+pub fn encode_request(imprint: &[u8], nonce: &[u8]) -> Vec<u8> {
+    let algorithm = der::encode(
+        der::SEQUENCE,
+        &[
+            der::encode(der::OID, OID_SHA256),
+            der::encode(der::NULL, &[]),
+        ]
+        .concat(),
+    );
+    let message_imprint = der::encode(
+        der::SEQUENCE,
+        &[algorithm, der::encode(der::OCTET_STRING, imprint)].concat(),
+    );
+    der::encode(
+        der::SEQUENCE,
+        &[
+            der::encode(der::INTEGER, &[1]),
+            message_imprint,
+            der::encode(der::INTEGER, nonce),
+            // certReq: ask the TSA to embed its signing certificate.
+            der::encode(der::BOOLEAN, &[0xFF]),
+        ]
+        .concat(),
+    )
+}
+
+/// Extract the `TimeStampToken` from a DER-encoded `TimeStampResp`.
+pub fn parse_response(response: &[u8]) -> Result<Vec<u8>, TimestampError> {
+    let resp = der::Reader::new(response).expect(der::SEQUENCE)?;
+    let mut fields = resp.reader();
+    let status_info = fields.expect(der::SEQUENCE)?;
+    let mut status_fields = status_info.reader();
+    let status = status_fields.expect(der::INTEGER)?;
+
+    // granted (0) or grantedWithMods (1)
+    if !matches!(status.content, [0] | [1]) {
+        let mut reason = format!("status {}", hex::encode(status.content));
+        if let Some(text) = status_fields.optional(der::SEQUENCE)? {
+            let mut strings = text.reader();
+            while !strings.is_empty() {
+                reason.push_str(": ");
+                reason.push_str(&String::from_utf8_lossy(strings.next()?.content));
+            }
+        }
+        return Err(TimestampError::Rejected(reason));
+    }
+
+    Ok(fields.expect(der::SEQUENCE)?.raw.to_vec())
+}
+
+struct TstInfo<'a> {
+    policy: &'a [u8],
+    imprint_algorithm: &'a [u8],
+    imprint: &'a [u8],
+    serial_number: &'a [u8],
+    gen_time: DateTime<Utc>,
+    nonce: Option<&'a [u8]>,
+}
+
+fn parse_tst_info(tst_der: &[u8]) -> Result<TstInfo<'_>, TimestampError> {
+    let mut r = der::Reader::new(tst_der).expect(der::SEQUENCE)?.reader();
+    r.expect(der::INTEGER)?;
+    let policy = r.expect(der::OID)?.content;
+    let mut imprint_fields = r.expect(der::SEQUENCE)?.reader();
+    let imprint_algorithm = imprint_fields
+        .expect(der::SEQUENCE)?
+        .reader()
+        .expect(der::OID)?
+        .content;
+    let imprint = imprint_fields.expect(der::OCTET_STRING)?.content;
+    let serial_number = r.expect(der::INTEGER)?.content;
+    let gen_time = parse_time(r.expect(der::GENERALIZED_TIME)?)?;
+    r.optional(der::SEQUENCE)?; // accuracy
+    r.optional(der::BOOLEAN)?; // ordering
+    let nonce = r.optional(der::INTEGER)?.map(|n| n.content);
+
+    Ok(TstInfo {
+        policy,
+        imprint_algorithm,
+        imprint,
+        serial_number,
+        gen_time,
+        nonce,
+    })
+}
+
+fn parse_time(tlv: der::Tlv<'_>) -> Result<DateTime<Utc>, TimestampError> {
+    let text = std::str::from_utf8(tlv.content)
+        .ok()
+        .and_then(|t| t.strip_suffix('Z'))
+        .ok_or_else(|| TimestampError::Malformed("time is not in UTC".to_string()))?;
+    let text = match tlv.tag {
+        der::GENERALIZED_TIME => text.to_string(),
+        der::UTC_TIME => {
+            let year: u32 = text
+                .get(..2)
+                .and_then(|y| y.parse().ok())
+                .ok_or_else(|| TimestampError::Malformed("invalid UTCTime".to_string()))?;
+            format!("{}{text}", if year < 50 { "20" } else { "19" })
+        }
+        tag => {
+            return Err(TimestampError::Malformed(format!(
+                "unexpected time tag {tag:#04x}"
+            )))
+        }
+    };
+    let format = if text.contains('.') {
+        "%Y%m%d%H%M%S%.f"
+    } else {
+        "%Y%m%d%H%M%S"
+    };
+    NaiveDateTime::parse_from_str(&text, format)
+        .map(|t| t.and_utc())
+        .map_err(|e| TimestampError::Malformed(format!("invalid time {text}: {e}")))
+}
+
+struct Certificate<'a> {
+    not_before: DateTime<Utc>,
+    not_after: DateTime<Utc>,
+    key_algorithm: &'a [u8],
+    key_parameters: Option<&'a [u8]>,
+    public_key: &'a [u8],
+}
+
+fn parse_certificate(cert_der: &[u8]) -> Result<Certificate<'_>, TimestampError> {
+    let cert = der::Reader::new(cert_der).expect(der::SEQUENCE)?;
+    let mut tbs = cert.reader().expect(der::SEQUENCE)?.reader();
+    tbs.optional(der::CONTEXT_0)?; // version
+    tbs.expect(der::INTEGER)?; // serialNumber
+    tbs.expect(der::SEQUENCE)?; // signature
+    tbs.expect(der::SEQUENCE)?; // issuer
+    let mut validity = tbs.expect(der::SEQUENCE)?.reader();
+    let not_before = parse_time(validity.next()?)?;
+    let not_after = parse_time(validity.next()?)?;
+    tbs.expect(der::SEQUENCE)?; // subject
+
+    let mut spki = tbs.expect(der::SEQUENCE)?.reader();
+    let mut algorithm = spki.expect(der::SEQUENCE)?.reader();
+    let key_algorithm = algorithm.expect(der::OID)?.content;
+    let key_parameters = algorithm.optional(der::OID)?.map(|p| p.content);
+    let public_key = match spki.expect(der::BIT_STRING)?.content.split_first() {
+        Some((0, key)) => key,
+        _ => {
+            return Err(TimestampError::Malformed(
+                "certificate public key is not byte-aligned".to_string(),
+            ))
+        }
+    };
+
+    Ok(Certificate {
+        not_before,
+        not_after,
+        key_algorithm,
+        key_parameters,
+        public_key,
+    })
+}
+
+fn verification_algorithm(
+    cert: &Certificate<'_>,
+    signature_algorithm: &[u8],
+) -> Result<&'static dyn VerificationAlgorithm, TimestampError> {
+    if cert.key_algorithm == OID_RSA_ENCRYPTION
+        && (signature_algorithm == OID_RSA_ENCRYPTION || signature_algorithm == OID_SHA256_WITH_RSA)
+    {
+        return Ok(&RSA_PKCS1_2048_8192_SHA256);
+    }
+    if cert.key_algorithm == OID_EC_PUBLIC_KEY && signature_algorithm == OID_ECDSA_WITH_SHA256 {
+        if cert.key_parameters == Some(OID_P256) {
+            return Ok(&ECDSA_P256_SHA256_ASN1);
+        }
+        if cert.key_parameters == Some(OID_P384) {
+            return Ok(&ECDSA_P384_SHA256_ASN1);
+        }
+    }
+    Err(TimestampError::InvalidSignature(format!(
+        "unsupported signature algorithm {}",
+        der::oid_to_string(signature_algorithm)
+    )))
+}
+
+/// `certHash` of the first `ESSCertIDv2` in a `SigningCertificateV2`
+/// attribute, when it uses SHA-256.
+fn ess_certificate_hash(value: der::Tlv<'_>) -> Result<Option<&[u8]>, TimestampError> {
+    let certs = value.reader().expect(der::SEQUENCE)?;
+    let mut cert_id = certs.reader().expect(der::SEQUENCE)?.reader();
+    if let Some(algorithm) = cert_id.optional(der::SEQUENCE)? {
+        if algorithm.reader().expect(der::OID)?.content != OID_SHA256 {
+            return Ok(None);
+        }
+    }
+    Ok(Some(cert_id.expect(der::OCTET_STRING)?.content))
+}
+
+/// Check the CMS signer info over `tst_der` against each candidate
+/// certificate and return the one that signed it.
+fn verify_signer<'c>(
+    signer_info: der::Tlv<'_>,
+    tst_der: &[u8],
+    gen_time: DateTime<Utc>,
+    candidates: &[&'c [u8]],
+) -> Result<&'c [u8], TimestampError> {
+    let mut r = signer_info.reader();
+    r.expect(der::INTEGER)?; // version
+    r.next()?; // sid
+    let digest_algorithm = r.expect(der::SEQUENCE)?.reader().expect(der::OID)?.content;
+    if digest_algorithm != OID_SHA256 {
+        return Err(TimestampError::InvalidSignature(format!(
+            "unsupported digest algorithm {}",
+            der::oid_to_string(digest_algorithm)
+        )));
+    }
+    let signed_attrs = r.optional(der::CONTEXT_0)?.ok_or_else(|| {
+        TimestampError::InvalidSignature("signed attributes are missing".to_string())
+    })?;
+    let signature_algorithm = r.expect(der::SEQUENCE)?.reader().expect(der::OID)?.content;
+    let signature = r.expect(der::OCTET_STRING)?.content;
+
+    let mut content_type_matches = false;
+    let mut message_digest = None;
+    let mut certificate_hash = None;
+    let mut attrs = signed_attrs.reader();
+    while !attrs.is_empty() {
+        let mut attr = attrs.expect(der::SEQUENCE)?.reader();
+        let oid = attr.expect(der::OID)?.content;
+        let value = attr.expect(der::SET)?.reader().next()?;
+        if oid == OID_CONTENT_TYPE {
+            content_type_matches = value.tag == der::OID && value.content == OID_TST_INFO;
+        } else if oid == OID_MESSAGE_DIGEST {
+            message_digest = Some(value.content);
+        } else if oid == OID_SIGNING_CERTIFICATE_V2 {
+            certificate_hash = ess_certificate_hash(value)?;
+        }
+    }
+    if !content_type_matches {
+        return Err(TimestampError::InvalidSignature(
+            "signed content type is not TSTInfo".to_string(),
+        ));
+    }
+    if message_digest != Some(digest(&SHA256, tst_der).as_ref()) {
+        return Err(TimestampError::InvalidSignature(
+            "message digest does not match TSTInfo".to_string(),
+        ));
+    }
+
+    // The signature covers the attributes re-tagged as a SET OF.
+    let mut signed = signed_attrs.raw.to_vec();
+    signed[0] = der::SET;
+
+    let mut last_error =
+        TimestampError::InvalidSignature("no TSA certificate available".to_string());
+    for candidate in candidates {
+        if certificate_hash.is_some_and(|hash| hash != digest(&SHA256, candidate).as_ref()) {
+            last_error = TimestampError::InvalidSignature(
+                "token is bound to a different signing certificate".to_string(),
+            );
+            continue;
+        }
+        let cert = parse_certificate(candidate)?;
+        if gen_time < cert.not_before || gen_time > cert.not_after {
+            last_error = TimestampError::InvalidSignature(
+                "signing certificate was not valid at the timestamp time".to_string(),
+            );
+            continue;
+        }
+        let algorithm = verification_algorithm(&cert, signature_algorithm)?;
+        match UnparsedPublicKey::new(algorithm, cert.public_key).verify(&signed, signature) {
+            Ok(()) => return Ok(candidate),
+            Err(_) => {
+                last_error = TimestampError::InvalidSignature(
+                    "signature does not verify against the TSA certificate".to_string(),
+                )
+            }
+        }
+    }
+    Err(last_error)
+}
+
+/// Validate a DER-encoded `TimeStampToken` over a SHA-256 `imprint`.
+///
+/// `nonce` is checked when validating a fresh response. With a
+/// `trusted_certificate` the token must be signed by exactly that
+/// certificate; otherwise the certificates embedded in the token are used.
+pub fn validate_token(
+    token: &[u8],
+    imprint: &[u8],
+    nonce: Option<&[u8]>,
+    trusted_certificate: Option<&[u8]>,
+) -> Result<TimestampInfo, TimestampError> {
+    let mut content_info = der::Reader::new(token).expect(der::SEQUENCE)?.reader();
+    if content_info.expect(der::OID)?.content != OID_SIGNED_DATA {
+        return Err(TimestampError::Malformed(
+            "token is not CMS SignedData".to_string(),
+        ));
+    }
+    let signed_data = content_info
+        .expect(der::CONTEXT_0)?
+        .reader()
+        .expect(der::SEQUENCE)?;
+    let mut sd = signed_data.reader();
+    sd.expect(der::INTEGER)?; // version
+    sd.expect(der::SET)?; // digestAlgorithms
+    let mut encap = sd.expect(der::SEQUENCE)?.reader();
+    if encap.expect(der::OID)?.content != OID_TST_INFO {
+        return Err(TimestampError::Malformed(
+            "token does not encapsulate TSTInfo".to_string(),
+        ));
+    }
+    let tst_der = encap
+        .expect(der::CONTEXT_0)?
+        .reader()
+        .expect(der::OCTET_STRING)?
+        .content;
+
+    let mut embedded = Vec::new();
+    if let Some(certificates) = sd.optional(der::CONTEXT_0)? {
+        let mut certs = certificates.reader();
+        while !certs.is_empty() {
+            let cert = certs.next()?;
+            if cert.tag == der::SEQUENCE {
+                embedded.push(cert.raw);
+            }
+        }
+    }
+    sd.optional(der::CONTEXT_1)?; // crls
+    let signer_info = sd.expect(der::SET)?.reader().expect(der::SEQUENCE)?;
+
+    let tst = parse_tst_info(tst_der)?;
+    if tst.imprint_algorithm != OID_SHA256 || tst.imprint != imprint {
+        return Err(TimestampError::ImprintMismatch);
+    }
+    if nonce.is_some() && tst.nonce != nonce {
+        return Err(TimestampError::NonceMismatch);
+    }
+
+    let candidates = match trusted_certificate {
+        Some(cert) => vec![cert],
+        None => embedded,
+    };
+    let signer = verify_signer(signer_info, tst_der, tst.gen_time, &candidates)?;
+
+    Ok(TimestampInfo {
+        gen_time: tst.gen_time,
+        serial_number: hex::encode(tst.serial_number),
+        policy_oid: der::oid_to_string(tst.policy),
+        signer_certificate_sha256: hex::encode(digest(&SHA256, signer)),
+    })
+}
+
+// ── Client ────────────────────────────────────────────────────────────────────
+
+/// RFC 3161 client speaking the HTTP transport (`application/timestamp-query`).
+pub struct TimestampClient {
+    url: String,
+    client: reqwest::Client,
+    trusted_certificate: Option<Vec<u8>>,
+}
+
+impl TimestampClient {
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .unwrap_or_default(),
+            trusted_certificate: None,
+        }
+    }
+
+    /// Only accept tokens signed by this DER-encoded certificate.
+    pub fn with_trusted_certificate(mut self, certificate: Vec<u8>) -> Self {
+        self.trusted_certificate = Some(certificate);
+        self
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Request and validate a token over a SHA-256 `imprint`. Returns the
+    /// DER-encoded token and what it attests to.
+    pub async fn timestamp(&self, imprint: &[u8]) -> Result<(Vec<u8>, TimestampInfo), ApiError> {
+        let mut nonce = [0u8; 8];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| ApiError::Internal(anyhow::anyhow!("Failed to generate TSA nonce")))?;
+        // Positive and minimally encoded as a DER INTEGER.
+        nonce[0] = (nonce[0] & 0x7F) | 0x40;
+
+        let response = self
+            .client
+            .post(&self.url)
+            .header("Content-Type", "application/timestamp-query")
+            .body(encode_request(imprint, &nonce))
+            .send()
+            .await
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("TSA request failed: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(ApiError::Internal(anyhow::anyhow!(
+                "TSA returned HTTP {}",
+                response.status()
+            )));
+        }
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("TSA response failed: {}", e)))?;
+
+        let token = parse_response(&body)
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("TSA response rejected: {}", e)))?;
+        let info = validate_token(
+            &token,
+            imprint,
+            Some(&nonce),
+            self.trusted_certificate.as_deref(),
+        )
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("TSA token rejected: {}", e)))?;
+        Ok((token, info))
+    }
+}
+
+/// Load the pinned TSA certificate from `TSA_CERTIFICATE_PATH`, if set.
+pub fn load_trusted_certificate() -> Result<Option<Vec<u8>>, ApiError> {
+    let Ok(path) = std::env::var("TSA_CERTIFICATE_PATH") else {
+        return Ok(None);
+    };
+    let bytes = std::fs::read(&path).map_err(|e| {
+        ApiError::Internal(anyhow::anyhow!(
+            "Failed to read TSA certificate {}: {}",
+            path,
+            e
+        ))
+    })?;
+    if !bytes.starts_with(b"-----BEGIN") {
+        return Ok(Some(bytes));
+    }
+    let body: String = String::from_utf8_lossy(&bytes)
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect();
+    base64::engine::general_purpose::STANDARD
+        .decode(body.trim())
+        .map(Some)
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Invalid TSA certificate PEM: {}", e)))
+}
+
+fn pinned_certificate() -> Option<&'static [u8]> {
+    static PINNED: OnceLock<Option<Vec<u8>>> = OnceLock::new();
+    PINNED
+        .get_or_init(|| {
+            load_trusted_certificate().unwrap_or_else(|e| {
+                error!("Ignoring TSA certificate pin: {}", e);
+                None
+            })
+        })
+        .as_deref()
+}
+
+/// Constructs the client selected by `TSA_URL`, or `None` when timestamping
+/// is not configured.
+pub fn build_timestamp_client() -> Result<Option<Arc<TimestampClient>>, ApiError> {
+    let Ok(url) = std::env::var("TSA_URL") else {
+        warn!("No TSA configured; finalized wills will not be timestamped");
+        return Ok(None);
+    };
+    let mut client = TimestampClient::new(url);
+    if let Some(certificate) = load_trusted_certificate()? {
+        client = client.with_trusted_certificate(certificate);
+    }
+    info!("Requesting RFC 3161 timestamps from {}", client.url());
+    Ok(Some(Arc::new(client)))
+}
+
+// ── Storage ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct WillTimestamp {
+    pub id: Uuid,
+    pub document_id: Uuid,
+    pub will_hash: String,
+    pub tsa_url: String,
+    #[serde(skip)]
+    pub token: Vec<u8>,
+    pub serial_number: String,
+    pub policy_oid: String,
+    pub gen_time: DateTime<Utc>,
+    pub signer_certificate_sha256: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Timestamp evidence as reported by document verification.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TimestampEvidence {
+    /// Whether the stored token still validates against the will hash
+    pub is_valid: bool,
+    /// Why validation failed, if it did
+    pub error: Option<String>,
+    /// TSA that issued the token
+    pub tsa_url: String,
+    #[serde(flatten)]
+    pub info: TimestampInfo,
+    /// DER-encoded RFC 3161 token, for independent verification
+    pub token_base64: String,
+}
+
+pub struct WillTimestampService {
+    db: PgPool,
+    client: Arc<TimestampClient>,
+}
+
+impl WillTimestampService {
+    pub fn new(db: PgPool, client: Arc<TimestampClient>) -> Self {
+        Self { db, client }
+    }
+
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(300));
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.timestamp_pending().await {
+                    error!("Will timestamping error: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Timestamp finalized wills that do not have a token yet. Returns how
+    /// many were timestamped.
+    pub async fn timestamp_pending(&self) -> Result<usize, ApiError> {
+        #[derive(sqlx::FromRow)]
+        struct Row {
+            id: Uuid,
+            will_hash: String,
+        }
+
+        let rows = sqlx::query_as::<_, Row>(
+            "SELECT d.id, d.will_hash FROM will_documents d \
+             WHERE d.status = 'finalized' \
+               AND NOT EXISTS (SELECT 1 FROM will_timestamps t WHERE t.document_id = d.id) \
+             ORDER BY d.generated_at ASC LIMIT $1",
+        )
+        .bind(TIMESTAMP_BATCH_SIZE)
+        .fetch_all(&self.db)
+        .await?;
+
+        let mut timestamped = 0;
+        for row in rows {
+            match Self::timestamp_document(&self.db, &self.client, row.id, &row.will_hash).await {
+                Ok(_) => timestamped += 1,
+                Err(e) => warn!("Failed to timestamp will document {}: {}", row.id, e),
+            }
+        }
+        Ok(timestamped)
+    }
+
+    /// Obtain and store a token for one will document. Idempotent: an
+    /// existing token is returned unchanged.
+    pub async fn timestamp_document(
+        db: &PgPool,
+        client: &TimestampClient,
+        document_id: Uuid,
+        will_hash: &str,
+    ) -> Result<WillTimestamp, ApiError> {
+        if let Some(existing) = Self::get_for_document(db, document_id).await? {
+            return Ok(existing);
+        }
+
+        let imprint = hex::decode(will_hash)
+            .map_err(|_| ApiError::BadRequest("Will hash is not hex-encoded".to_string()))?;
+        let (token, info) = client.timestamp(&imprint).await?;
+
+        sqlx::query(
+            "INSERT INTO will_timestamps \
+             (document_id, will_hash, tsa_url, token, serial_number, policy_oid, gen_time, \
+              signer_certificate_sha256) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+             ON CONFLICT (document_id) DO NOTHING",
+        )
+        .bind(document_id)
+        .bind(will_hash)
+        .bind(client.url())
+        .bind(&token)
+        .bind(&info.serial_number)
+        .bind(&info.policy_oid)
+        .bind(info.gen_time)
+        .bind(&info.signer_certificate_sha256)
+        .execute(db)
+        .await?;
+
+        info!(
+            "Timestamped will document {} at {} (serial {})",
+            document_id, info.gen_time, info.serial_number
+        );
+        Self::get_for_document(db, document_id)
+            .await?
+            .ok_or_else(|| ApiError::NotFound(format!("Timestamp for {document_id} not found")))
+    }
+
+    pub async fn get_for_document(
+        db: &PgPool,
+        document_id: Uuid,
+    ) -> Result<Option<WillTimestamp>, ApiError> {
+        let row = sqlx::query_as::<_, WillTimestamp>(
+            "SELECT id, document_id, will_hash, tsa_url, token, serial_number, policy_oid, \
+             gen_time, signer_certificate_sha256, created_at \
+             FROM will_timestamps WHERE document_id = $1",
+        )
+        .bind(document_id)
+        .fetch_optional(db)
+        .await?;
+        Ok(row)
+    }
+
+    /// Re-validate the stored token for a document against its will hash.
+    pub async fn evidence(
+        db: &PgPool,
+        document_id: Uuid,
+        will_hash: &str,
+    ) -> Result<Option<TimestampEvidence>, ApiError> {
+        let Some(stored) = Self::get_for_document(db, document_id).await? else {
+            return Ok(None);
+        };
+
+        let validated = hex::decode(will_hash)
+            .map_err(|_| TimestampError::ImprintMismatch)
+            .and_then(|imprint| {
+                validate_token(&stored.token, &imprint, None, pinned_certificate())
+            });
+        let (info, error) = match validated {
+            Ok(info) => (info, None),
+            Err(e) => (
+                TimestampInfo {
+                    gen_time: stored.gen_time,
+                    serial_number: stored.serial_number,
+                    policy_oid: stored.policy_oid,
+                    signer_certificate_sha256: stored.signer_certificate_sha256,
+                },
+                Some(e.to_string()),
+            ),
+        };
+
+        Ok(Some(TimestampEvidence {
+            is_valid: error.is_none(),
+            error,
+            tsa_url: stored.tsa_url,
+            info,
+            token_base64: base64::engine::general_purpose::STANDARD.encode(&stored.token),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_der_length_forms() {
+        let short = der::encode(der::OCTET_STRING, &[7; 3]);
+        assert_eq!(short[..2], [0x04, 0x03]);
+        let long = der::encode(der::OCTET_STRING, &[7; 300]);
+        assert_eq!(long[..4], [0x04, 0x82, 0x01, 0x2C]);
+
+        let tlv = der::Reader::new(&long).expect(der::OCTET_STRING).unwrap();
+        assert_eq!(tlv.content.len(), 300);
+        assert!(der::Reader::new(&long[..100]).next().is_err());
+        assert!(der::Reader::new(&[0x30, 0x80, 0x00, 0x00]).next().is_err());
+    }
+
+    #[test]
+    fn test_oid_to_string() {
+        assert_eq!(der::oid_to_string(OID_SHA256), "2.16.840.1.101.3.4.2.1");
+        assert_eq!(
+            der::oid_to_string(OID_TST_INFO),
+            "1.2.840.113549.1.9.16.1.4"
+        );
+    }
+
+    #[test]
+    fn test_encode_request() {
+        let imprint = [0xAB; 32];
+        let request = encode_request(&imprint, &[0x42, 0x01]);
+        let mut fields = der::Reader::new(&request)
+            .expect(der::SEQUENCE)
+            .unwrap()
+            .reader();
+        assert_eq!(fields.expect(der::INTEGER).unwrap().content, [1]);
+        let mut message_imprint = fields.expect(der::SEQUENCE).unwrap().reader();
+        let algorithm = message_imprint.expect(der::SEQUENCE).unwrap();
+        assert_eq!(
+            algorithm.reader().expect(der::OID).unwrap().content,
+            OID_SHA256
+        );
+        assert_eq!(
+            message_imprint.expect(der::OCTET_STRING).unwrap().content,
+            imprint
+        );
+        assert_eq!(fields.expect(der::INTEGER).unwrap().content, [0x42, 0x01]);
+        assert_eq!(fields.expect(der::BOOLEAN).unwrap().content, [0xFF]);
+        assert!(fields.is_empty());
+    }
+
+    #[test]
+    fn test_parse_time() {
+        let time = |tag, text: &str| {
+            let encoded = der::encode(tag, text.as_bytes());
+            parse_time(der::Reader::new(&encoded).next().unwrap())
+        };
+        assert_eq!(
+            time(der::GENERALIZED_TIME, "20260429101500Z")
+                .unwrap()
+                .to_rfc3339(),
+            "2026-04-29T10:15:00+00:00"
+        );
+        assert_eq!(
+            time(der::GENERALIZED_TIME, "20260429101500.25Z")
+                .unwrap()
+                .timestamp_millis()
+                % 1000,
+            250
+        );
+        assert_eq!(
+            time(der::UTC_TIME, "491231235959Z").unwrap().to_rfc3339(),
+            "2049-12-31T23:59:59+00:00"
+        );
+        assert!(time(der::GENERALIZED_TIME, "20260429101500+0100").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejection_response() {
+        let status_text = der::encode(der::SEQUENCE, &der::encode(0x0C, b"unsupported policy"));
+        let status_info = der::encode(
+            der::SEQUENCE,
+            &[der::encode(der::INTEGER, &[2]), status_text].concat(),
+        );
+        let response = der::encode(der::SEQUENCE, &status_info);
+        assert_eq!(
+            parse_response(&response),
+            Err(TimestampError::Rejected(
+                "status 02: unsupported policy".to_string()
+            ))
+        );
+    }
+}
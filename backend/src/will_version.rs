@@ -3,15 +3,18 @@
 //! Provides endpoints to list, retrieve, and finalize versioned will documents.
 
 use crate::api_error::ApiError;
+use crate::joint_will::JointWillService;
 use crate::will_pdf::GeneratedWillDocument;
+use crate::will_timestamp::{TimestampClient, WillTimestampService};
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use uuid::Uuid;
 
 // ---- Types ----
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WillVersionSummary {
     pub document_id: Uuid,
     pub plan_id: Uuid,
@@ -23,7 +26,7 @@ pub struct WillVersionSummary {
     pub generated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 pub struct PaginationParams {
     pub page: Option<u32>,
     pub per_page: Option<u32>,
@@ -190,9 +193,12 @@ impl WillVersionService {
         })
     }
 
-    /// Mark a draft version as finalized.
+    /// Mark a draft version as finalized and, when a TSA is configured,
+    /// obtain an RFC 3161 timestamp over its hash. A TSA outage does not block
+    /// finalization; the background timestamping job retries it.
     pub async fn finalize_version(
         db: &PgPool,
+        tsa: Option<&TimestampClient>,
         plan_id: Uuid,
         user_id: Uuid,
         version_number: u32,
@@ -226,6 +232,7 @@ impl WillVersionService {
                 "This version is already finalized".to_string(),
             ));
         }
+        JointWillService::ensure_ready_to_finalize(db, row.id).await?;
 
         sqlx::query(
             "UPDATE will_documents SET status = 'finalized' \
@@ -259,6 +266,14 @@ impl WillVersionService {
             }
         }
 
+        if let Some(tsa) = tsa {
+            if let Err(e) =
+                WillTimestampService::timestamp_document(db, tsa, row.id, &row.will_hash).await
+            {
+                tracing::warn!("Failed to timestamp will document {}: {}", row.id, e);
+            }
+        }
+
         Ok(WillVersionSummary {
             document_id: row.id,
             plan_id: row.plan_id,
@@ -7,6 +7,7 @@
 use crate::api_error::ApiError;
 use chrono::{DateTime, Utc};
 use ring::signature;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use stellar_strkey::Strkey;
@@ -14,7 +15,7 @@ use uuid::Uuid;
 
 // --- Types -------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WitnessRecord {
     pub id: Uuid,
     pub document_id: Uuid,
@@ -26,7 +27,7 @@ pub struct WitnessRecord {
     pub invited_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WitnessStatusSummary {
     pub document_id: Uuid,
     pub total: i64,
@@ -35,13 +36,13 @@ pub struct WitnessStatusSummary {
     pub declined: i64,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 pub struct InviteWitnessRequest {
     pub wallet_address: Option<String>,
     pub email: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 pub struct WitnessSignRequest {
     pub wallet_address: String,
     pub signature_hex: String,
@@ -269,6 +270,8 @@ impl WitnessService {
 
         tx.commit().await?;
 
+        crate::joint_will::JointWillService::advance(db, witness.document_id).await?;
+
         // Fetch plan_id and vault_id for event
         let (plan_id, vault_id): (Uuid, String) = sqlx::query_as(
             "SELECT p.id, COALESCE(p.title, p.id::text) \
@@ -0,0 +1,666 @@
+//! Email magic-link signing for witnesses without a wallet.
+//!
+//! A witness invited by email receives a single-use link whose token is the
+//! invitation id plus an HMAC over the invitation, witness and expiry, so a
+//! link cannot be forged or extended. Opening the link shows the will hash to
+//! be witnessed. The witness then attests with their name, postal address and
+//! a statement of presence, signed over [`attestation_message`] with either:
+//!
+//! - `server_key`: an Ed25519 key the server generates for that witness and
+//!   keeps encrypted under the message data key, or
+//! - `browser_key`: a key generated in the witness's browser, whose public key
+//!   and signature are submitted with the attestation.
+//!
+//! The attestation is stored with the client IP, user agent and time, and is
+//! appended to the will PDF as an attestation page.
+
+use crate::api_error::ApiError;
+use crate::joint_will::JointWillService;
+use crate::message_delivery::MessageChannels;
+use crate::secure_messages::MessageEncryptionService;
+use crate::will_events::{WillEvent, WillEventService};
+use crate::will_pdf::embed_witness_attestation;
+use crate::will_signature::WillSignatureService;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{DateTime, Duration, Utc};
+use ring::hmac;
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+/// How long an invitation link stays valid.
+pub const INVITATION_TTL_DAYS: i64 = 7;
+
+/// Statement of presence offered to the witness when they open their link.
+pub const PRESENCE_STATEMENT: &str = "I was present when the testator signed this will, \
+     and I believe they did so freely and while of sound mind.";
+
+/// The message a witness signs to attest to `will_hash`. The attestation
+/// details are bound by their SHA-256 so the message stays short.
+pub fn attestation_message(
+    witness_id: Uuid,
+    will_hash: &str,
+    full_name: &str,
+    postal_address: &str,
+    statement: &str,
+) -> String {
+    let details = serde_json::json!([full_name, postal_address, statement]).to_string();
+    format!(
+        "INHERITX_WITNESS_ATTESTATION:{witness_id}:{will_hash}:{}",
+        hex::encode(Sha256::digest(details.as_bytes()))
+    )
+}
+
+// --- Types -------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WitnessSigningMethod {
+    ServerKey,
+    BrowserKey,
+}
+
+impl WitnessSigningMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ServerKey => "server_key",
+            Self::BrowserKey => "browser_key",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
+pub struct WitnessInvitation {
+    pub id: Uuid,
+    pub witness_id: Uuid,
+    pub email: String,
+    pub expires_at: DateTime<Utc>,
+    pub opened_at: Option<DateTime<Utc>>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub superseded_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+const INVITATION_COLUMNS: &str =
+    "id, witness_id, email, expires_at, opened_at, used_at, superseded_at, created_at";
+
+/// What a witness sees when they open their link.
+#[derive(Debug, Clone, Serialize)]
+pub struct WitnessInvitationView {
+    pub invitation_id: Uuid,
+    pub witness_id: Uuid,
+    pub document_id: Uuid,
+    pub will_hash: String,
+    pub email: String,
+    pub expires_at: DateTime<Utc>,
+    pub statement: String,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct WitnessAttestationRequest {
+    pub full_name: String,
+    pub postal_address: String,
+    pub statement: String,
+    pub signing_method: WitnessSigningMethod,
+    /// `browser_key` only: the witness's Ed25519 public key, as a Stellar
+    /// address or hex.
+    pub public_key: Option<String>,
+    /// `browser_key` only: hex signature over [`attestation_message`].
+    pub signature_hex: Option<String>,
+}
+
+/// Where an attestation was submitted from.
+#[derive(Debug, Clone, Default)]
+pub struct ClientEvidence {
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, JsonSchema)]
+pub struct WitnessAttestation {
+    pub id: Uuid,
+    pub witness_id: Uuid,
+    pub invitation_id: Uuid,
+    pub document_id: Uuid,
+    pub full_name: String,
+    pub postal_address: String,
+    pub statement: String,
+    pub signing_method: String,
+    pub public_key_hex: String,
+    pub signature_hex: String,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub attested_at: DateTime<Utc>,
+}
+
+const ATTESTATION_COLUMNS: &str = "id, witness_id, invitation_id, document_id, full_name, \
+     postal_address, statement, signing_method, public_key_hex, signature_hex, \
+     host(ip_address) AS ip_address, user_agent, attested_at";
+
+impl WitnessAttestation {
+    /// Text of the attestation page appended to the will PDF.
+    fn render(&self, will_hash: &str) -> String {
+        format!(
+            "WITNESS ATTESTATION\n\
+             ===================\n\n\
+             {}, of\n{}\n\n\
+             attests to the will document {} bearing SHA-256 hash:\n\n\
+             {}\n\n\
+             Statement of presence:\n{}\n\n\
+             Attested at:    {}\n\
+             IP address:     {}\n\
+             Signing method: {}\n\
+             Public key:     {}\n\
+             Signature:      {}\n",
+            self.full_name,
+            self.postal_address,
+            self.document_id,
+            will_hash,
+            self.statement,
+            self.attested_at.format("%Y-%m-%d %H:%M:%S UTC"),
+            self.ip_address.as_deref().unwrap_or("unknown"),
+            self.signing_method,
+            self.public_key_hex,
+            self.signature_hex,
+        )
+    }
+}
+
+// --- Link signing ------------------------------------------------------------
+
+/// Signs and checks invitation link tokens.
+pub struct WitnessLinkSigner {
+    key: hmac::Key,
+}
+
+impl WitnessLinkSigner {
+    /// Derive the link key from a server secret, keeping it distinct from any
+    /// other use of that secret.
+    pub fn new(secret: &[u8]) -> Self {
+        let derivation = hmac::Key::new(hmac::HMAC_SHA256, b"inheritx-witness-invitation-links");
+        let key_bytes = hmac::sign(&derivation, secret);
+        Self {
+            key: hmac::Key::new(hmac::HMAC_SHA256, key_bytes.as_ref()),
+        }
+    }
+
+    fn payload(invitation_id: Uuid, witness_id: Uuid, expires_at: DateTime<Utc>) -> String {
+        format!(
+            "witness-invitation:{invitation_id}:{witness_id}:{}",
+            expires_at.timestamp()
+        )
+    }
+
+    pub fn token(
+        &self,
+        invitation_id: Uuid,
+        witness_id: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> String {
+        let payload = Self::payload(invitation_id, witness_id, expires_at);
+        let tag = hmac::sign(&self.key, payload.as_bytes());
+        format!("{invitation_id}.{}", hex::encode(tag.as_ref()))
+    }
+
+    /// Split a token into its invitation id and tag; the tag is checked by
+    /// [`Self::verify`] once the invitation has been loaded.
+    fn parse(token: &str) -> Result<(Uuid, Vec<u8>), ApiError> {
+        let (id, tag) = token.split_once('.').ok_or(ApiError::Unauthorized)?;
+        let id = Uuid::parse_str(id).map_err(|_| ApiError::Unauthorized)?;
+        let tag = hex::decode(tag).map_err(|_| ApiError::Unauthorized)?;
+        Ok((id, tag))
+    }
+
+    fn verify(&self, invitation: &WitnessInvitation, tag: &[u8]) -> Result<(), ApiError> {
+        let payload = Self::payload(invitation.id, invitation.witness_id, invitation.expires_at);
+        hmac::verify(&self.key, payload.as_bytes(), tag).map_err(|_| ApiError::Unauthorized)
+    }
+}
+
+// --- Service -----------------------------------------------------------------
+
+pub struct WitnessInvitationService;
+
+impl WitnessInvitationService {
+    /// Email a fresh link to a witness invited by email, superseding any
+    /// earlier unused link. Only the document owner can send invitations.
+    pub async fn send(
+        db: &PgPool,
+        channels: &MessageChannels,
+        signer: &WitnessLinkSigner,
+        user_id: Uuid,
+        witness_id: Uuid,
+    ) -> Result<WitnessInvitation, ApiError> {
+        let witness: Option<(Option<String>, String)> = sqlx::query_as(
+            "SELECT w.email, w.status FROM will_witnesses w \
+             JOIN will_documents d ON d.id = w.document_id \
+             WHERE w.id = $1 AND d.user_id = $2",
+        )
+        .bind(witness_id)
+        .bind(user_id)
+        .fetch_optional(db)
+        .await?;
+        let (email, status) =
+            witness.ok_or_else(|| ApiError::NotFound("Witness record not found".to_string()))?;
+        if status != "pending" {
+            return Err(ApiError::BadRequest(format!(
+                "Witness has already {status}"
+            )));
+        }
+        let email = email
+            .ok_or_else(|| ApiError::BadRequest("Witness was not invited by email".to_string()))?;
+
+        let mut tx = db.begin().await?;
+        sqlx::query(
+            "UPDATE will_witness_invitations SET superseded_at = NOW() \
+             WHERE witness_id = $1 AND used_at IS NULL AND superseded_at IS NULL",
+        )
+        .bind(witness_id)
+        .execute(&mut *tx)
+        .await?;
+        let invitation = sqlx::query_as::<_, WitnessInvitation>(&format!(
+            "INSERT INTO will_witness_invitations (witness_id, email, expires_at) \
+             VALUES ($1, $2, $3) RETURNING {INVITATION_COLUMNS}"
+        ))
+        .bind(witness_id)
+        .bind(&email)
+        .bind(Utc::now() + Duration::days(INVITATION_TTL_DAYS))
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let link = format!(
+            "{}/api/will/witness-invitations/{}",
+            channels.public_url(),
+            signer.token(invitation.id, witness_id, invitation.expires_at)
+        );
+        let body = format!(
+            "You have been asked to witness a will on InheritX.\n\n\
+             Open this link to review the document and sign your attestation:\n{link}\n\n\
+             The link can be used once and expires on {}.\n",
+            invitation.expires_at.format("%Y-%m-%d %H:%M UTC")
+        );
+        channels
+            .send_email(&email, "You have been asked to witness a will", &body)
+            .await?;
+
+        tx.commit().await?;
+        Ok(invitation)
+    }
+
+    /// Resolve a link token to its invitation, locking it and checking the
+    /// signature, expiry and that it has not been used or replaced.
+    async fn resolve(
+        conn: &mut PgConnection,
+        signer: &WitnessLinkSigner,
+        token: &str,
+    ) -> Result<WitnessInvitation, ApiError> {
+        let (invitation_id, tag) = WitnessLinkSigner::parse(token)?;
+        let invitation = sqlx::query_as::<_, WitnessInvitation>(&format!(
+            "SELECT {INVITATION_COLUMNS} FROM will_witness_invitations WHERE id = $1 FOR UPDATE"
+        ))
+        .bind(invitation_id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or(ApiError::Unauthorized)?;
+        signer.verify(&invitation, &tag)?;
+
+        if invitation.used_at.is_some() {
+            return Err(ApiError::Conflict(
+                "This invitation link has already been used".to_string(),
+            ));
+        }
+        if invitation.superseded_at.is_some() {
+            return Err(ApiError::Conflict(
+                "This invitation link has been replaced by a newer one".to_string(),
+            ));
+        }
+        if invitation.expires_at <= Utc::now() {
+            return Err(ApiError::BadRequest(
+                "This invitation link has expired".to_string(),
+            ));
+        }
+        Ok(invitation)
+    }
+
+    /// Show the witness what they are attesting to. The first opening is
+    /// recorded.
+    pub async fn open(
+        db: &PgPool,
+        signer: &WitnessLinkSigner,
+        token: &str,
+    ) -> Result<WitnessInvitationView, ApiError> {
+        let mut tx = db.begin().await?;
+        let invitation = Self::resolve(&mut tx, signer, token).await?;
+        sqlx::query(
+            "UPDATE will_witness_invitations SET opened_at = COALESCE(opened_at, NOW()) \
+             WHERE id = $1",
+        )
+        .bind(invitation.id)
+        .execute(&mut *tx)
+        .await?;
+        let (document_id, will_hash): (Uuid, String) = sqlx::query_as(
+            "SELECT d.id, d.will_hash FROM will_witnesses w \
+             JOIN will_documents d ON d.id = w.document_id WHERE w.id = $1",
+        )
+        .bind(invitation.witness_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(WitnessInvitationView {
+            invitation_id: invitation.id,
+            witness_id: invitation.witness_id,
+            document_id,
+            will_hash,
+            email: invitation.email,
+            expires_at: invitation.expires_at,
+            statement: PRESENCE_STATEMENT.to_string(),
+        })
+    }
+
+    /// Record and sign the witness's attestation, consuming the link.
+    pub async fn attest(
+        db: &PgPool,
+        signer: &WitnessLinkSigner,
+        token: &str,
+        req: &WitnessAttestationRequest,
+        evidence: &ClientEvidence,
+    ) -> Result<WitnessAttestation, ApiError> {
+        let full_name = req.full_name.trim();
+        let postal_address = req.postal_address.trim();
+        let statement = req.statement.trim();
+        if full_name.is_empty() || postal_address.is_empty() || statement.is_empty() {
+            return Err(ApiError::BadRequest(
+                "full_name, postal_address and statement are required".to_string(),
+            ));
+        }
+        if full_name.len() > 255 {
+            return Err(ApiError::BadRequest(
+                "full_name must be at most 255 characters".to_string(),
+            ));
+        }
+
+        // Generate and wrap a server key up front so the transaction below
+        // does not wait on the message key service.
+        let server_key = match req.signing_method {
+            WitnessSigningMethod::ServerKey => Some(ServerWitnessKey::generate(db).await?),
+            WitnessSigningMethod::BrowserKey => None,
+        };
+
+        let mut tx = db.begin().await?;
+        let invitation = Self::resolve(&mut tx, signer, token).await?;
+
+        #[derive(sqlx::FromRow)]
+        struct WitnessRow {
+            document_id: Uuid,
+            status: String,
+            will_hash: String,
+            pdf_base64: String,
+        }
+        let witness = sqlx::query_as::<_, WitnessRow>(
+            "SELECT w.document_id, w.status, d.will_hash, d.pdf_base64 \
+             FROM will_witnesses w JOIN will_documents d ON d.id = w.document_id \
+             WHERE w.id = $1 FOR UPDATE OF w, d",
+        )
+        .bind(invitation.witness_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if witness.status != "pending" {
+            return Err(ApiError::BadRequest(format!(
+                "Witness has already {}",
+                witness.status
+            )));
+        }
+
+        let message = attestation_message(
+            invitation.witness_id,
+            &witness.will_hash,
+            full_name,
+            postal_address,
+            statement,
+        );
+        let (public_key_hex, signature_hex) = match &server_key {
+            Some(key) => {
+                key.store(&mut tx, invitation.witness_id).await?;
+                (
+                    key.public_key_hex(),
+                    hex::encode(key.pair.sign(message.as_bytes()).as_ref()),
+                )
+            }
+            None => {
+                let (public_key, signature_hex) = req
+                    .public_key
+                    .as_deref()
+                    .zip(req.signature_hex.as_deref())
+                    .ok_or_else(|| {
+                        ApiError::BadRequest(
+                            "public_key and signature_hex are required for browser_key signing"
+                                .to_string(),
+                        )
+                    })?;
+                WillSignatureService::verify_signature(public_key, &message, signature_hex)?;
+                (
+                    hex::encode(WillSignatureService::decode_public_key(public_key)?),
+                    signature_hex.to_lowercase(),
+                )
+            }
+        };
+
+        let attestation = sqlx::query_as::<_, WitnessAttestation>(&format!(
+            "INSERT INTO will_witness_attestations \
+             (witness_id, invitation_id, document_id, full_name, postal_address, statement, \
+              signing_method, public_key_hex, signature_hex, ip_address, user_agent) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, CAST($10 AS INET), $11) \
+             RETURNING {ATTESTATION_COLUMNS}"
+        ))
+        .bind(invitation.witness_id)
+        .bind(invitation.id)
+        .bind(witness.document_id)
+        .bind(full_name)
+        .bind(postal_address)
+        .bind(statement)
+        .bind(req.signing_method.as_str())
+        .bind(&public_key_hex)
+        .bind(&signature_hex)
+        .bind(&evidence.ip_address)
+        .bind(&evidence.user_agent)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "UPDATE will_witnesses SET status = 'signed', signature_hex = $2, signed_at = $3 \
+             WHERE id = $1",
+        )
+        .bind(invitation.witness_id)
+        .bind(&signature_hex)
+        .bind(attestation.attested_at)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("UPDATE will_witness_invitations SET used_at = $2 WHERE id = $1")
+            .bind(invitation.id)
+            .bind(attestation.attested_at)
+            .execute(&mut *tx)
+            .await?;
+
+        let pdf = BASE64
+            .decode(&witness.pdf_base64)
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Stored PDF is not base64: {e}")))?;
+        let attestation_json = serde_json::to_string(&attestation)
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!(e)))?;
+        let attested_pdf = embed_witness_attestation(
+            &pdf,
+            invitation.witness_id,
+            &attestation.render(&witness.will_hash),
+            &attestation_json,
+        )?;
+        sqlx::query("UPDATE will_documents SET pdf_base64 = $2 WHERE id = $1")
+            .bind(witness.document_id)
+            .bind(BASE64.encode(attested_pdf))
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        JointWillService::advance(db, witness.document_id).await?;
+
+        let plan: Option<(Uuid, String)> = sqlx::query_as(
+            "SELECT p.id, COALESCE(p.title, p.id::text) \
+             FROM plans p \
+             JOIN will_documents d ON d.plan_id = p.id \
+             WHERE d.id = $1",
+        )
+        .bind(witness.document_id)
+        .fetch_optional(db)
+        .await?;
+        if let Some((plan_id, vault_id)) = plan {
+            let event = WillEvent::WitnessSigned {
+                vault_id,
+                document_id: witness.document_id,
+                plan_id,
+                witness: invitation.email,
+                witness_id: invitation.witness_id,
+                signature_hash: hex::encode(Sha256::digest(signature_hex.as_bytes())),
+                timestamp: attestation.attested_at,
+            };
+            if let Err(e) = WillEventService::emit(db, event).await {
+                tracing::warn!("Failed to emit WitnessSigned event: {}", e);
+            }
+        }
+
+        Ok(attestation)
+    }
+
+    /// Attestations recorded for a document, for its owner.
+    pub async fn list_for_document(
+        db: &PgPool,
+        user_id: Uuid,
+        document_id: Uuid,
+    ) -> Result<Vec<WitnessAttestation>, ApiError> {
+        let exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM will_documents WHERE id = $1 AND user_id = $2)",
+        )
+        .bind(document_id)
+        .bind(user_id)
+        .fetch_one(db)
+        .await?;
+        if !exists {
+            return Err(ApiError::NotFound(format!(
+                "Will document {document_id} not found"
+            )));
+        }
+
+        let attestations = sqlx::query_as::<_, WitnessAttestation>(&format!(
+            "SELECT {ATTESTATION_COLUMNS} FROM will_witness_attestations \
+             WHERE document_id = $1 ORDER BY attested_at"
+        ))
+        .bind(document_id)
+        .fetch_all(db)
+        .await?;
+        Ok(attestations)
+    }
+}
+
+/// A per-witness signing key held by the server.
+struct ServerWitnessKey {
+    pair: Ed25519KeyPair,
+    key_version: i32,
+    encrypted_private_key: Vec<u8>,
+    key_nonce: Vec<u8>,
+}
+
+impl ServerWitnessKey {
+    async fn generate(db: &PgPool) -> Result<Self, ApiError> {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+            .map_err(|_| ApiError::Internal(anyhow::anyhow!("Failed to generate witness key")))?;
+        let pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref())
+            .map_err(|_| ApiError::Internal(anyhow::anyhow!("Failed to load witness key")))?;
+        let (key_version, encrypted_private_key, key_nonce) =
+            MessageEncryptionService::encrypt_for_purpose(
+                db,
+                b"witness-signing-key",
+                pkcs8.as_ref(),
+            )
+            .await?;
+        Ok(Self {
+            pair,
+            key_version,
+            encrypted_private_key,
+            key_nonce,
+        })
+    }
+
+    fn public_key_hex(&self) -> String {
+        hex::encode(self.pair.public_key().as_ref())
+    }
+
+    async fn store(&self, conn: &mut PgConnection, witness_id: Uuid) -> Result<(), ApiError> {
+        sqlx::query(
+            "INSERT INTO will_witness_keys \
+             (witness_id, public_key_hex, encrypted_private_key, key_nonce, key_version) \
+             VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(witness_id)
+        .bind(self.public_key_hex())
+        .bind(&self.encrypted_private_key)
+        .bind(&self.key_nonce)
+        .bind(self.key_version)
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+}
+
+// --- Unit Tests --------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invitation(expires_at: DateTime<Utc>) -> WitnessInvitation {
+        WitnessInvitation {
+            id: Uuid::new_v4(),
+            witness_id: Uuid::new_v4(),
+            email: "wes@example.com".to_string(),
+            expires_at,
+            opened_at: None,
+            used_at: None,
+            superseded_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn link_tokens_are_bound_to_invitation_and_secret() {
+        let signer = WitnessLinkSigner::new(b"secret");
+        let invite = invitation(Utc::now() + Duration::days(1));
+        let token = signer.token(invite.id, invite.witness_id, invite.expires_at);
+
+        let (id, tag) = WitnessLinkSigner::parse(&token).unwrap();
+        assert_eq!(id, invite.id);
+        assert!(signer.verify(&invite, &tag).is_ok());
+
+        // Extending the expiry or using another secret breaks the tag.
+        let mut extended = invite.clone();
+        extended.expires_at = invite.expires_at + Duration::days(30);
+        assert!(signer.verify(&extended, &tag).is_err());
+        assert!(WitnessLinkSigner::new(b"other")
+            .verify(&invite, &tag)
+            .is_err());
+        assert!(WitnessLinkSigner::parse("not-a-token").is_err());
+    }
+
+    #[test]
+    fn attestation_message_binds_details() {
+        let witness_id = Uuid::new_v4();
+        let message = attestation_message(witness_id, "abc", "Wes", "1 Road", "present");
+        assert!(message.starts_with(&format!("INHERITX_WITNESS_ATTESTATION:{witness_id}:abc:")));
+        assert_ne!(
+            message,
+            attestation_message(witness_id, "abc", "Wes", "2 Road", "present")
+        );
+    }
+}
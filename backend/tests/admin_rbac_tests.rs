@@ -0,0 +1,241 @@
+//! Integration tests for admin roles and per-route permission checks.
+
+mod helpers;
+
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn admin_routes_require_the_role_permission() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let user_id = helpers::create_test_user(&ctx.pool, &format!("{}@rbac.test", Uuid::new_v4()))
+        .await
+        .unwrap();
+    let (reviewer_id, reviewer) = helpers::admin_with_role(&ctx, "kyc_reviewer").await;
+    let (super_id, super_admin) = helpers::admin_with_role(&ctx, "super_admin").await;
+    let (_, admin) = helpers::admin_with_role(&ctx, "admin").await;
+
+    let kyc_uri = format!("/api/admin/kyc/{user_id}");
+    let response = helpers::send(&ctx, "GET", &kyc_uri, &reviewer, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/admin/emergency/pause",
+        &reviewer,
+        Some(json!({})),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let denial: Value = sqlx::query_scalar(
+        "SELECT metadata FROM action_logs \
+         WHERE admin_id = $1 AND action = 'admin_permission_denied'",
+    )
+    .bind(reviewer_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(denial["permission"], "risk:pause");
+    assert_eq!(denial["role"], "kyc_reviewer");
+    assert_eq!(denial["path"], "/api/admin/emergency/pause");
+
+    // Role management is reserved for roles granting roles:manage.
+    for token in [&reviewer, &admin] {
+        let response = helpers::send(&ctx, "GET", "/api/admin/roles", token, None).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+    let response = helpers::send(&ctx, "GET", "/api/admin/roles", &super_admin, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let roles = helpers::json_body(response).await;
+    assert!(roles["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|r| r["name"] == "insurance_officer" && r["built_in"] == true));
+
+    // --- Custom roles take effect for their holders immediately -------------
+    let role = format!("clerk_{}", Uuid::new_v4().simple());
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/admin/roles",
+        &super_admin,
+        Some(json!({ "name": role, "permissions": ["kyc:grant_everything"] })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/admin/roles",
+        &super_admin,
+        Some(json!({ "name": role, "permissions": ["audit:read"] })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = helpers::send(
+        &ctx,
+        "PUT",
+        &format!("/api/admin/admins/{reviewer_id}/role"),
+        &super_admin,
+        Some(json!({ "role": role })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = helpers::send(&ctx, "GET", "/api/admin/logs", &reviewer, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = helpers::send(&ctx, "GET", &kyc_uri, &reviewer, None).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // --- Guard rails --------------------------------------------------------
+    let response = helpers::send(
+        &ctx,
+        "PUT",
+        &format!("/api/admin/admins/{super_id}/role"),
+        &super_admin,
+        Some(json!({ "role": "auditor" })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = helpers::send(
+        &ctx,
+        "DELETE",
+        &format!("/api/admin/roles/{role}"),
+        &super_admin,
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let response = helpers::send(
+        &ctx,
+        "DELETE",
+        "/api/admin/roles/auditor",
+        &super_admin,
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = helpers::send(
+        &ctx,
+        "PUT",
+        "/api/admin/roles/super_admin",
+        &super_admin,
+        Some(json!({ "permissions": [] })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let assigned: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM action_logs \
+         WHERE admin_id = $1 AND action = 'admin_role_assigned' AND entity_id = $2",
+    )
+    .bind(super_id)
+    .bind(reviewer_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(assigned, 1);
+}
+
+#[tokio::test]
+async fn role_managers_cannot_escalate_privileges() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let (_, super_admin) = helpers::admin_with_role(&ctx, "super_admin").await;
+    let manager_role = format!("manager_{}", Uuid::new_v4().simple());
+    let clerk_role = format!("clerk_{}", Uuid::new_v4().simple());
+    for (role, permissions) in [
+        (&manager_role, json!(["roles:manage"])),
+        (&clerk_role, json!(["audit:read"])),
+    ] {
+        let response = helpers::send(
+            &ctx,
+            "POST",
+            "/api/admin/roles",
+            &super_admin,
+            Some(json!({ "name": role, "permissions": permissions })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+    let (_, manager) = helpers::admin_with_role(&ctx, &manager_role).await;
+    let (other_id, _) = helpers::admin_with_role(&ctx, "auditor").await;
+
+    // The manager cannot widen the role they hold...
+    let response = helpers::send(
+        &ctx,
+        "PUT",
+        &format!("/api/admin/roles/{manager_role}"),
+        &manager,
+        Some(json!({ "permissions": ["*"] })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let permissions: Vec<String> =
+        sqlx::query_scalar("SELECT permissions FROM admin_roles WHERE name = $1")
+            .bind(&manager_role)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert_eq!(permissions, vec!["roles:manage"]);
+
+    // ...nor hand out the wildcard or role management through other roles.
+    for permissions in [json!(["*"]), json!(["audit:read", "roles:manage"])] {
+        let response = helpers::send(
+            &ctx,
+            "PUT",
+            &format!("/api/admin/roles/{clerk_role}"),
+            &manager,
+            Some(json!({ "permissions": permissions })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/admin/roles",
+        &manager,
+        Some(json!({
+            "name": format!("root_{}", Uuid::new_v4().simple()),
+            "permissions": ["*"],
+        })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = helpers::send(
+        &ctx,
+        "PUT",
+        &format!("/api/admin/admins/{other_id}/role"),
+        &manager,
+        Some(json!({ "role": manager_role })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // Ordinary permissions are still theirs to manage.
+    let response = helpers::send(
+        &ctx,
+        "PUT",
+        &format!("/api/admin/roles/{clerk_role}"),
+        &manager,
+        Some(json!({ "permissions": ["audit:read", "kyc:review"] })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = helpers::send(
+        &ctx,
+        "PUT",
+        &format!("/api/admin/admins/{other_id}/role"),
+        &manager,
+        Some(json!({ "role": clerk_role })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
@@ -0,0 +1,188 @@
+//! Integration tests for hash-chained audit logs and their Merkle anchors.
+
+mod helpers;
+
+use axum::http::StatusCode;
+use inheritx_backend::audit_chain::{self, AuditAnchorJob, MerkleProofStep};
+use inheritx_backend::message_access_audit::{MessageAccessAction, MessageAccessAuditService};
+use inheritx_backend::notifications::AuditLogService;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+async fn verify(ctx: &helpers::TestContext, token: &str, stream: &str, id: Uuid) -> Value {
+    let response = helpers::send(
+        ctx,
+        "GET",
+        &format!("/api/admin/audit/{stream}/{id}/verify"),
+        token,
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    helpers::json_body(response).await["data"].clone()
+}
+
+/// Write an action log entry and return its id.
+async fn log_action(ctx: &helpers::TestContext, entity_id: Uuid, new_value: &str) -> Uuid {
+    AuditLogService::log(
+        &ctx.pool,
+        None,
+        None,
+        "audit_chain_test",
+        Some(entity_id),
+        Some("plan"),
+        None,
+        Some(new_value),
+        Some(json!({ "note": new_value, "nested": { "b": 2, "a": 1 } })),
+    )
+    .await
+    .unwrap();
+    sqlx::query_scalar("SELECT id FROM action_logs WHERE entity_id = $1 AND new_value = $2")
+        .bind(entity_id)
+        .bind(new_value)
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn audit_entries_are_chained_anchored_and_tamper_evident() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let email = format!("{}@audit-chain.test", Uuid::new_v4());
+    let admin_id = helpers::create_test_admin(&ctx.pool, &email).await.unwrap();
+    let token = helpers::admin_token(admin_id, &email);
+
+    let entity_id = Uuid::new_v4();
+    let first = log_action(&ctx, entity_id, "first").await;
+    let second = log_action(&ctx, entity_id, "second").await;
+    let third = log_action(&ctx, entity_id, "third").await;
+    let message_entry = MessageAccessAuditService::log_access(
+        &ctx.pool,
+        None,
+        Uuid::new_v4(),
+        MessageAccessAction::Viewed,
+        Some("10.0.0.7".to_string()),
+        Some("audit-chain-test".to_string()),
+        json!({}),
+    )
+    .await
+    .unwrap();
+    assert_eq!(message_entry.ip_address.as_deref(), Some("10.0.0.7"));
+
+    // Entries chain onto each other as soon as they are written.
+    let data = verify(&ctx, &token, "actions", second).await;
+    assert_eq!(data["unaltered"], true);
+    assert_eq!(data["linked_to_previous"], true);
+    let first_data = verify(&ctx, &token, "actions", first).await;
+    assert_eq!(data["prev_hash"], first_data["entry_hash"]);
+    assert_eq!(
+        data["chain_seq"].as_i64().unwrap(),
+        first_data["chain_seq"].as_i64().unwrap() + 1
+    );
+
+    // Sealing anchors every stream's new entries under a Merkle root.
+    let anchors = AuditAnchorJob::new(ctx.pool.clone(), None)
+        .seal_pending()
+        .await
+        .unwrap();
+    assert!(anchors.iter().any(|a| a.stream == "actions"));
+    assert!(anchors.iter().all(|a| a.chain_status == "pending"));
+
+    for (stream, id) in [
+        ("actions", first),
+        ("actions", third),
+        ("message_access", message_entry.id),
+    ] {
+        let data = verify(&ctx, &token, stream, id).await;
+        assert_eq!(data["verified"], true, "{stream} {id}: {data}");
+        let proof: Vec<MerkleProofStep> =
+            serde_json::from_value(data["merkle_proof"].clone()).unwrap();
+        let entry_hash = hex::decode(data["entry_hash"].as_str().unwrap()).unwrap();
+        let root = audit_chain::root_from_proof(&entry_hash, &proof).unwrap();
+        assert_eq!(hex::encode(root), data["anchor"]["merkle_root"]);
+    }
+
+    // Editing an entry in place is detected.
+    sqlx::query("UPDATE action_logs SET new_value = 'rewritten' WHERE id = $1")
+        .bind(second)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    let data = verify(&ctx, &token, "actions", second).await;
+    assert_eq!(data["unaltered"], false);
+    assert_eq!(data["included"], false);
+    assert_eq!(data["verified"], false);
+
+    // Covering the edit by rewriting its hash breaks the next link instead.
+    let rewritten = AuditLogService::list_for_entity(&ctx.pool, "plan", entity_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|entry| entry.id == second)
+        .unwrap();
+    let prev_hash = hex::decode(data["prev_hash"].as_str().unwrap()).unwrap();
+    let forged =
+        audit_chain::chain_hash(&prev_hash, &audit_chain::content_hash(&rewritten).unwrap());
+    sqlx::query("UPDATE action_logs SET entry_hash = $2 WHERE id = $1")
+        .bind(second)
+        .bind(forged.as_slice())
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    let data = verify(&ctx, &token, "actions", second).await;
+    assert_eq!(data["unaltered"], true);
+    assert_eq!(data["included"], false);
+    let data = verify(&ctx, &token, "actions", third).await;
+    assert_eq!(data["unaltered"], true);
+    assert_eq!(data["linked_to_previous"], false);
+    assert_eq!(data["verified"], false);
+}
+
+#[tokio::test]
+async fn verification_rejects_unknown_entries_and_non_admins() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let email = format!("{}@audit-chain.test", Uuid::new_v4());
+    let admin_id = helpers::create_test_admin(&ctx.pool, &email).await.unwrap();
+    let token = helpers::admin_token(admin_id, &email);
+
+    let response = helpers::send(
+        &ctx,
+        "GET",
+        &format!("/api/admin/audit/action_logs/{}/verify", Uuid::new_v4()),
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = helpers::send(
+        &ctx,
+        "GET",
+        &format!("/api/admin/audit/actions/{}/verify", Uuid::new_v4()),
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let user_email = format!("{}@audit-chain.test", Uuid::new_v4());
+    let user_id = helpers::create_test_user(&ctx.pool, &user_email)
+        .await
+        .unwrap();
+    let response = helpers::send(
+        &ctx,
+        "GET",
+        &format!("/api/admin/audit/actions/{}/verify", Uuid::new_v4()),
+        &helpers::user_token(user_id, &user_email),
+        None,
+    )
+    .await;
+    assert!(matches!(
+        response.status(),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN
+    ));
+}
@@ -0,0 +1,185 @@
+//! Integration tests for time-delayed break-glass emergency access.
+
+mod helpers;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use uuid::Uuid;
+
+async fn audit_actions(ctx: &helpers::TestContext, token: &str, grant_id: &str) -> Vec<String> {
+    let response = helpers::send(
+        ctx,
+        "GET",
+        &format!("/api/emergency/access/audit-logs?grant_id={grant_id}"),
+        token,
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    helpers::json_body(response).await["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|log| log["action"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn break_glass_access_waits_for_the_owner_veto_window() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let (owner_id, _, owner_token) = helpers::user(&ctx, "owner.test").await;
+    let (_, contact_email, contact_token) = helpers::user(&ctx, "contact.test").await;
+    let (_, _, stranger_token) = helpers::user(&ctx, "stranger.test").await;
+    let plan_id = helpers::create_test_plan(&ctx.pool, owner_id)
+        .await
+        .unwrap();
+
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/emergency/contacts",
+        &owner_token,
+        Some(json!({ "name": "Sam Sibling", "relationship": "Sibling", "email": contact_email })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = helpers::send(
+        &ctx,
+        "PUT",
+        "/api/emergency/break-glass/settings",
+        &owner_token,
+        Some(json!({ "delay_hours": 24 })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = json!({ "plan_id": plan_id, "reason": "Owner is in hospital" });
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/emergency/break-glass",
+        &stranger_token,
+        Some(request.clone()),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // --- A vetoed request never grants access -----------------------------
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/emergency/break-glass",
+        &contact_token,
+        Some(request.clone()),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let grant = helpers::json_body(response).await["data"].clone();
+    assert_eq!(grant["status"], "pending");
+    let requested_at: chrono::DateTime<chrono::Utc> =
+        grant["requested_at"].as_str().unwrap().parse().unwrap();
+    let effective_at: chrono::DateTime<chrono::Utc> =
+        grant["effective_at"].as_str().unwrap().parse().unwrap();
+    assert!((effective_at - requested_at - chrono::Duration::hours(24)).num_seconds() < 60);
+    let grant_id = grant["id"].as_str().unwrap().to_string();
+
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/emergency/break-glass",
+        &contact_token,
+        Some(request.clone()),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let plan_uri = format!("/api/emergency/break-glass/{grant_id}/plan");
+    let response = helpers::send(&ctx, "GET", &plan_uri, &contact_token, None).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let notified: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM notifications WHERE user_id = $1 AND type = 'emergency_access_requested'",
+    )
+    .bind(owner_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(notified, 1);
+
+    let veto_uri = format!("/api/emergency/break-glass/{grant_id}/veto");
+    let veto = json!({ "reason": "I am fine" });
+    let response = helpers::send(&ctx, "POST", &veto_uri, &contact_token, Some(veto.clone())).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = helpers::send(&ctx, "POST", &veto_uri, &owner_token, Some(veto.clone())).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        helpers::json_body(response).await["data"]["status"],
+        "vetoed"
+    );
+    let response = helpers::send(&ctx, "POST", &veto_uri, &owner_token, Some(veto.clone())).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let actions = audit_actions(&ctx, &owner_token, &grant_id).await;
+    assert!(actions.contains(&"break_glass_requested".to_string()));
+    assert!(actions.contains(&"break_glass_owner_notified".to_string()));
+    assert!(actions.contains(&"break_glass_vetoed".to_string()));
+    let response = helpers::send(&ctx, "GET", &plan_uri, &contact_token, None).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // --- An unvetoed request takes effect after the waiting period ---------
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/emergency/break-glass",
+        &contact_token,
+        Some(request),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let grant_id = helpers::json_body(response).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    sqlx::query(
+        "UPDATE emergency_access_grants SET effective_at = NOW() - INTERVAL '1 minute' \
+         WHERE id = $1",
+    )
+    .bind(Uuid::parse_str(&grant_id).unwrap())
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    let veto_uri = format!("/api/emergency/break-glass/{grant_id}/veto");
+    let response = helpers::send(&ctx, "POST", &veto_uri, &owner_token, Some(veto)).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let plan_uri = format!("/api/emergency/break-glass/{grant_id}/plan");
+    let response = helpers::send(&ctx, "GET", &plan_uri, &stranger_token, None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = helpers::send(&ctx, "GET", &plan_uri, &contact_token, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        helpers::json_body(response).await["data"]["id"],
+        plan_id.to_string()
+    );
+
+    let response = helpers::send(
+        &ctx,
+        "GET",
+        "/api/emergency/break-glass",
+        &owner_token,
+        None,
+    )
+    .await;
+    let incoming = helpers::json_body(response).await["data"]["incoming"].clone();
+    let statuses: Vec<&str> = incoming
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|grant| grant["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(statuses, ["active", "vetoed"]);
+    let actions = audit_actions(&ctx, &owner_token, &grant_id).await;
+    assert!(actions.contains(&"break_glass_effective".to_string()));
+    assert!(actions.contains(&"break_glass_plan_viewed".to_string()));
+}
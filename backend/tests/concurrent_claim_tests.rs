@@ -1,4 +1,3 @@
-/*
 // Integration test: Concurrent Claim Race Condition
 // Tests that only one concurrent claim request succeeds and no duplicate payouts occur
 mod helpers;
@@ -8,6 +7,7 @@ use axum::{
     http::{Request, StatusCode},
 };
 use chrono::Utc;
+use rust_decimal::Decimal;
 use serde_json::json;
 use tokio::join;
 use tower::ServiceExt;
@@ -31,8 +31,8 @@ async fn insert_due_plan(pool: &sqlx::PgPool, user_id: Uuid) -> Uuid {
     .bind(user_id)
     .bind("Concurrent Claim Test Plan")
     .bind("Test plan for concurrent claim race condition")
-    .bind("10.00")
-    .bind("490.00")
+    .bind(Decimal::new(1000, 2))
+    .bind(Decimal::new(49000, 2))
     .bind("Test Beneficiary")
     .bind("1234567890")
     .bind("Test Bank")
@@ -85,9 +85,18 @@ async fn test_concurrent_claim_same_email_only_one_succeeds() {
             .method("POST")
             .uri(format!("/api/plans/{plan_id}/claim"))
             .header("Content-Type", "application/json")
-            .header("X-User-Id", user_id.to_string())
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    helpers::stepped_up_user_token(user_id, "test@example.com")
+                ),
+            )
             .body(Body::from(
-                serde_json::to_string(&json!({ "beneficiary_email": claim_email })).unwrap(),
+                serde_json::to_string(
+                    &json!({ "beneficiary_email": claim_email, "two_fa_code": "123456" }),
+                )
+                .unwrap(),
             ))
             .unwrap()
     };
@@ -160,9 +169,18 @@ async fn test_concurrent_claim_different_emails_only_one_succeeds() {
             .method("POST")
             .uri(format!("/api/plans/{plan_id}/claim"))
             .header("Content-Type", "application/json")
-            .header("X-User-Id", user_id.to_string())
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    helpers::stepped_up_user_token(user_id, "test@example.com")
+                ),
+            )
             .body(Body::from(
-                serde_json::to_string(&json!({ "beneficiary_email": email })).unwrap(),
+                serde_json::to_string(
+                    &json!({ "beneficiary_email": email, "two_fa_code": "123456" }),
+                )
+                .unwrap(),
             ))
             .unwrap()
     };
@@ -235,9 +253,18 @@ async fn test_concurrent_claim_updates_plan_status() {
         .method("POST")
         .uri(format!("/api/plans/{plan_id}/claim"))
         .header("Content-Type", "application/json")
-        .header("X-User-Id", user_id.to_string())
+        .header(
+            "Authorization",
+            format!(
+                "Bearer {}",
+                helpers::stepped_up_user_token(user_id, "test@example.com")
+            ),
+        )
         .body(Body::from(
-            serde_json::to_string(&json!({ "beneficiary_email": "test@beneficiary.com" })).unwrap(),
+            serde_json::to_string(
+                &json!({ "beneficiary_email": "test@beneficiary.com", "two_fa_code": "123456" }),
+            )
+            .unwrap(),
         ))
         .unwrap();
 
@@ -298,10 +325,18 @@ async fn test_claim_after_concurrent_claims_fails() {
         .method("POST")
         .uri(format!("/api/plans/{plan_id}/claim"))
         .header("Content-Type", "application/json")
-        .header("X-User-Id", user_id.to_string())
+        .header(
+            "Authorization",
+            format!(
+                "Bearer {}",
+                helpers::stepped_up_user_token(user_id, "test@example.com")
+            ),
+        )
         .body(Body::from(
-            serde_json::to_string(&json!({ "beneficiary_email": "first@beneficiary.com" }))
-                .unwrap(),
+            serde_json::to_string(
+                &json!({ "beneficiary_email": "first@beneficiary.com", "two_fa_code": "123456" }),
+            )
+            .unwrap(),
         ))
         .unwrap();
 
@@ -317,10 +352,18 @@ async fn test_claim_after_concurrent_claims_fails() {
         .method("POST")
         .uri(format!("/api/plans/{plan_id}/claim"))
         .header("Content-Type", "application/json")
-        .header("X-User-Id", user_id.to_string())
+        .header(
+            "Authorization",
+            format!(
+                "Bearer {}",
+                helpers::stepped_up_user_token(user_id, "test@example.com")
+            ),
+        )
         .body(Body::from(
-            serde_json::to_string(&json!({ "beneficiary_email": "second@beneficiary.com" }))
-                .unwrap(),
+            serde_json::to_string(
+                &json!({ "beneficiary_email": "second@beneficiary.com", "two_fa_code": "123456" }),
+            )
+            .unwrap(),
         ))
         .unwrap();
 
@@ -378,9 +421,18 @@ async fn test_concurrent_claim_creates_single_audit_log() {
             .method("POST")
             .uri(format!("/api/plans/{plan_id}/claim"))
             .header("Content-Type", "application/json")
-            .header("X-User-Id", user_id.to_string())
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    helpers::stepped_up_user_token(user_id, "test@example.com")
+                ),
+            )
             .body(Body::from(
-                serde_json::to_string(&json!({ "beneficiary_email": email })).unwrap(),
+                serde_json::to_string(
+                    &json!({ "beneficiary_email": email, "two_fa_code": "123456" }),
+                )
+                .unwrap(),
             ))
             .unwrap()
     };
@@ -400,10 +452,10 @@ async fn test_concurrent_claim_creates_single_audit_log() {
     let success_count = (status1 == StatusCode::OK) as i32 + (status2 == StatusCode::OK) as i32;
     assert_eq!(success_count, 1, "Exactly one claim should succeed");
 
-    // Check only one audit log for 'claim' action exists
+    // Check only one audit log for the claim exists
     // This verifies no duplicate payout was recorded
     let audit_count: (i64,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM action_logs WHERE entity_type = 'plan' AND action = 'claim' AND entity_id = $1"
+        "SELECT COUNT(*) FROM action_logs WHERE entity_type = 'plan' AND action = 'plan_claimed' AND entity_id = $1"
     )
         .bind(plan_id)
         .fetch_one(&pool)
@@ -414,4 +466,3 @@ async fn test_concurrent_claim_creates_single_audit_log() {
         "Only one audit log should exist for claim action (no duplicate payout)"
     );
 }
-*/
@@ -0,0 +1,229 @@
+//! Integration tests for the legacy content inspection pipeline.
+//!
+//! The clamd client is exercised against an in-process stand-in that speaks
+//! the `zINSTREAM` protocol and flags the EICAR test string.
+
+mod helpers;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use inheritx_backend::content_inspection::{ClamdScanner, MalwareScanner, ScanVerdict};
+use inheritx_backend::legacy_content::{LegacyContentService, UploadMetadata};
+use inheritx_backend::object_storage::{build_object_store, ObjectStorageService};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+const EICAR: &[u8] = b"X5O!P%@AP[4\\PZX54(P^)7CC)7}$EICAR-STANDARD-ANTIVIRUS-TEST-FILE!$H+H*";
+
+/// Start a clamd stand-in and return its address.
+async fn start_clamd_stand_in() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            tokio::spawn(async move {
+                let mut command = [0u8; 10];
+                socket.read_exact(&mut command).await.unwrap();
+                assert_eq!(&command, b"zINSTREAM\0");
+
+                let mut data = Vec::new();
+                loop {
+                    let len = socket.read_u32().await.unwrap() as usize;
+                    if len == 0 {
+                        break;
+                    }
+                    let mut chunk = vec![0u8; len];
+                    socket.read_exact(&mut chunk).await.unwrap();
+                    data.extend_from_slice(&chunk);
+                }
+
+                let reply: &[u8] = if data.windows(EICAR.len()).any(|w| w == EICAR) {
+                    b"stream: Eicar-Test-Signature FOUND\0"
+                } else {
+                    b"stream: OK\0"
+                };
+                socket.write_all(reply).await.unwrap();
+            });
+        }
+    });
+
+    addr.to_string()
+}
+
+#[tokio::test]
+async fn clamd_client_reports_clean_and_infected_streams() {
+    let scanner = ClamdScanner::new(start_clamd_stand_in().await);
+
+    assert_eq!(
+        scanner.scan(b"a harmless letter").await.unwrap(),
+        ScanVerdict::Clean
+    );
+
+    // Large enough to span several INSTREAM chunks.
+    let mut infected = vec![b'a'; 200 * 1024];
+    infected.extend_from_slice(EICAR);
+    assert_eq!(
+        scanner.scan(&infected).await.unwrap(),
+        ScanVerdict::Infected("Eicar-Test-Signature".to_string())
+    );
+}
+
+#[tokio::test]
+async fn clamd_client_fails_when_daemon_is_unreachable() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    drop(listener);
+
+    assert!(ClamdScanner::new(addr).scan(b"data").await.is_err());
+}
+
+async fn create_content(
+    ctx: &helpers::TestContext,
+    user_id: Uuid,
+    filename: &str,
+    content_type: &str,
+    bytes: &[u8],
+) -> Uuid {
+    let store = build_object_store().unwrap();
+    let object = ObjectStorageService::store(&ctx.pool, store.as_ref(), bytes)
+        .await
+        .unwrap();
+    LegacyContentService::create_content_record(
+        &ctx.pool,
+        user_id,
+        &UploadMetadata {
+            original_filename: filename.to_string(),
+            content_type: content_type.to_string(),
+            file_size: bytes.len(),
+            description: None,
+        },
+        object.object_key,
+        object.sha256,
+    )
+    .await
+    .unwrap()
+    .id
+}
+
+async fn download_status(ctx: &helpers::TestContext, token: &str, uri: String) -> StatusCode {
+    ctx.app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(uri)
+                .header("Authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn photos_are_stripped_of_exif_and_get_thumbnails() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let email = format!("{}@inspection.test", Uuid::new_v4());
+    let user_id = helpers::create_test_user(&ctx.pool, &email).await.unwrap();
+    let token = helpers::user_token(user_id, &email);
+
+    // A unique JPEG with an APP1 Exif segment carrying location data.
+    let pixel = Uuid::new_v4().as_bytes()[0];
+    let image = image::RgbImage::from_pixel(300, 200, image::Rgb([pixel, 90, 160]));
+    let mut encoded = std::io::Cursor::new(Vec::new());
+    image
+        .write_to(&mut encoded, image::ImageFormat::Jpeg)
+        .unwrap();
+    let encoded = encoded.into_inner();
+    let exif = format!("Exif\0\0MM\0*\0\0\0\x08GPSLatitude={}", Uuid::new_v4());
+    let mut photo = vec![0xFF, 0xD8, 0xFF, 0xE1];
+    photo.extend_from_slice(&((exif.len() + 2) as u16).to_be_bytes());
+    photo.extend_from_slice(exif.as_bytes());
+    photo.extend_from_slice(&encoded[2..]);
+
+    let content_id = create_content(&ctx, user_id, "family.jpg", "image/jpeg", &photo).await;
+    assert_eq!(
+        helpers::inspect_content(&ctx.pool, content_id).await,
+        "passed"
+    );
+
+    let content = LegacyContentService::get_content_by_id(&ctx.pool, content_id, user_id)
+        .await
+        .unwrap();
+    let details = content.inspection_details.unwrap();
+    assert_eq!(details["metadata_removed"], serde_json::json!(["exif"]));
+    assert_eq!(
+        details["original_hash"],
+        LegacyContentService::calculate_file_hash(&photo)
+    );
+    assert!(content.file_size < photo.len() as i64);
+
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/content/{content_id}/download"))
+                .header("Authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let delivered = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert!(!delivered
+        .windows(b"GPSLatitude".len())
+        .any(|w| w == b"GPSLatitude"));
+
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/content/{content_id}/thumbnail"))
+                .header("Authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let thumbnail = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let thumbnail = image::load_from_memory(&thumbnail).unwrap();
+    assert_eq!((thumbnail.width(), thumbnail.height()), (256, 171));
+}
+
+#[tokio::test]
+async fn mismatched_content_type_is_rejected_and_never_delivered() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let email = format!("{}@inspection.test", Uuid::new_v4());
+    let user_id = helpers::create_test_user(&ctx.pool, &email).await.unwrap();
+    let token = helpers::user_token(user_id, &email);
+
+    let bytes = format!("MZ\u{90} definitely not a PDF {}", Uuid::new_v4()).into_bytes();
+    let content_id =
+        create_content(&ctx, user_id, "statement.pdf", "application/pdf", &bytes).await;
+
+    assert_eq!(
+        helpers::inspect_content(&ctx.pool, content_id).await,
+        "rejected"
+    );
+    assert_eq!(
+        download_status(&ctx, &token, format!("/api/content/{content_id}/download")).await,
+        StatusCode::FORBIDDEN
+    );
+}
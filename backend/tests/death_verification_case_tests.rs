@@ -0,0 +1,561 @@
+//! Integration tests for death verification cases and the submission of the
+//! resulting inheritance triggers through the contract relayer.
+
+mod helpers;
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Request, Response, StatusCode},
+    routing::post,
+    Json, Router,
+};
+use chrono::{Duration, Utc};
+use inheritx_backend::contract_relayer::ContractRelayer;
+use inheritx_backend::inheritance_trigger::InheritanceTriggerSubmitter;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+async fn upload(
+    ctx: &helpers::TestContext,
+    uri: &str,
+    token: &str,
+    content_type: &str,
+    contents: &str,
+) -> Response<Body> {
+    let boundary = "inheritx-test-boundary";
+    let body = format!(
+        "--{boundary}\r\n\
+         Content-Disposition: form-data; name=\"file\"; filename=\"certificate.pdf\"\r\n\
+         Content-Type: {content_type}\r\n\r\n\
+         {contents}\r\n\
+         --{boundary}--\r\n"
+    );
+    ctx.app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("Authorization", format!("Bearer {token}"))
+                .header(
+                    "Content-Type",
+                    format!("multipart/form-data; boundary={boundary}"),
+                )
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+/// Relayer stand-in that records each invocation.
+async fn start_relayer() -> (String, Arc<Mutex<Vec<Value>>>) {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let app = Router::new()
+        .route(
+            "/invoke",
+            post(
+                |State(calls): State<Arc<Mutex<Vec<Value>>>>, Json(body): Json<Value>| async move {
+                    calls.lock().unwrap().push(body);
+                    Json(json!({ "tx_hash": "f00dfeed" }))
+                },
+            ),
+        )
+        .with_state(calls.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (url, calls)
+}
+
+#[tokio::test]
+async fn verified_death_case_triggers_inheritance_after_objection_window() {
+    std::env::set_var("DOCUMENT_MASTER_KEY_VERSION", "1");
+    std::env::set_var(
+        "DOCUMENT_MASTER_KEY_V1",
+        "death-verification-test-master-key-material",
+    );
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let (owner_id, owner_email, owner_token) = helpers::user(&ctx, "owner.test").await;
+    let (heir_id, _, heir_token) = helpers::user(&ctx, "heir.test").await;
+    let (_, sibling_email, sibling_token) = helpers::user(&ctx, "sibling.test").await;
+    let (_, _, stranger_token) = helpers::user(&ctx, "stranger.test").await;
+    let (first_admin, first_admin_token) = helpers::admin(&ctx).await;
+    let (_, second_admin_token) = helpers::admin(&ctx).await;
+
+    let plan_id = helpers::create_test_plan(&ctx.pool, owner_id)
+        .await
+        .unwrap();
+    let heir_wallet = format!("G{}", Uuid::new_v4().simple()).to_uppercase();
+    let contract_plan_id = rand_contract_plan_id();
+    sqlx::query("UPDATE users SET wallet_address = $2 WHERE id = $1")
+        .bind(heir_id)
+        .bind(&heir_wallet)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE plans SET contract_plan_id = $2 WHERE id = $1")
+        .bind(plan_id)
+        .bind(contract_plan_id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO plan_beneficiaries (plan_id, wallet_address, allocation_percent) \
+         VALUES ($1, $2, 100)",
+    )
+    .bind(plan_id)
+    .bind(&heir_wallet)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/emergency/contacts",
+        &owner_token,
+        Some(json!({ "name": "Sam Sibling", "relationship": "Sibling", "email": sibling_email })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/messages/legacy",
+        &owner_token,
+        Some(json!({
+            "beneficiary_contact": "heir@example.com",
+            "message": "Look after the garden",
+            "unlock_at": Utc::now() + Duration::days(365),
+        })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // --- Opening a case ---------------------------------------------------
+    let open = json!({
+        "subject_email": owner_email,
+        "statement": "My father passed away last week",
+        "date_of_death": (Utc::now() - Duration::days(7)).date_naive(),
+    });
+    // A contact who is neither a beneficiary nor the executor cannot open one.
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/death-verification/cases",
+        &sibling_token,
+        Some(open.clone()),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/death-verification/cases",
+        &heir_token,
+        Some(open.clone()),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let case = helpers::json_body(response).await["data"].clone();
+    assert_eq!(case["opener_role"], "beneficiary");
+    assert_eq!(case["status"], "awaiting_certificate");
+    let case_id = case["id"].as_str().unwrap().to_string();
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/death-verification/cases",
+        &heir_token,
+        Some(open),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    // --- Certificate upload ------------------------------------------------
+    let certificate_uri = format!("/api/death-verification/cases/{case_id}/certificate");
+    let response = upload(&ctx, &certificate_uri, &heir_token, "text/plain", "hello").await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = upload(
+        &ctx,
+        &certificate_uri,
+        &stranger_token,
+        "application/pdf",
+        "%PDF-1.4 certificate",
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = upload(
+        &ctx,
+        &certificate_uri,
+        &heir_token,
+        "application/pdf",
+        "%PDF-1.4 certificate",
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let stored: Vec<u8> =
+        sqlx::query_scalar("SELECT encrypted_content FROM death_certificates WHERE case_id = $1")
+            .bind(Uuid::parse_str(&case_id).unwrap())
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert!(!stored
+        .windows(b"%PDF".len())
+        .any(|window| window == b"%PDF"));
+
+    let admin_case = format!("/api/admin/death-verification/cases/{case_id}");
+    let response = helpers::send(
+        &ctx,
+        "GET",
+        &format!("{admin_case}/certificate"),
+        &first_admin_token,
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(&bytes[..], b"%PDF-1.4 certificate");
+
+    // --- Two different admins must verify ---------------------------------
+    let verify = json!({ "decision": "verify", "notes": "Certificate checked" });
+    let reviews = format!("{admin_case}/reviews");
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &reviews,
+        &first_admin_token,
+        Some(verify.clone()),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        helpers::json_body(response).await["data"]["status"],
+        "under_review"
+    );
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &reviews,
+        &first_admin_token,
+        Some(verify.clone()),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let response = helpers::send(&ctx, "POST", &reviews, &second_admin_token, Some(verify)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let reviewed = helpers::json_body(response).await["data"].clone();
+    assert_eq!(reviewed["status"], "objection_window");
+    assert_eq!(reviewed["reviews"].as_array().unwrap().len(), 2);
+
+    let notified: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM notifications \
+         WHERE user_id = $1 AND type = 'death_verification_objection_window'",
+    )
+    .bind(owner_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(notified, 1);
+
+    // --- Objections --------------------------------------------------------
+    let objections = format!("/api/death-verification/cases/{case_id}/objections");
+    let objection = json!({ "reason": "I spoke to him this morning" });
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &objections,
+        &heir_token,
+        Some(objection.clone()),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = helpers::send(&ctx, "POST", &objections, &sibling_token, Some(objection)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let objection_id = helpers::json_body(response).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // A disputed case is not finalized even once its window has ended.
+    sqlx::query(
+        "UPDATE death_verification_cases \
+         SET objection_window_ends_at = NOW() - INTERVAL '1 minute' WHERE id = $1",
+    )
+    .bind(Uuid::parse_str(&case_id).unwrap())
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    let finalize = "/api/admin/death-verification/cases/finalize";
+    let response = helpers::send(&ctx, "POST", finalize, &first_admin_token, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = helpers::send(&ctx, "GET", &admin_case, &first_admin_token, None).await;
+    assert_eq!(
+        helpers::json_body(response).await["data"]["status"],
+        "disputed"
+    );
+
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &format!("{admin_case}/objections/{objection_id}/resolve"),
+        &first_admin_token,
+        Some(json!({ "decision": "dismiss", "note": "Confirmed with the registrar" })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        helpers::json_body(response).await["data"]["status"],
+        "objection_window"
+    );
+
+    // --- Approval ----------------------------------------------------------
+    let response = helpers::send(&ctx, "POST", finalize, &first_admin_token, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = helpers::send(
+        &ctx,
+        "GET",
+        &format!("/api/death-verification/cases/{case_id}"),
+        &sibling_token,
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        helpers::json_body(response).await["data"]["status"],
+        "approved"
+    );
+
+    let verified_by: Uuid = sqlx::query_scalar(
+        "SELECT verified_by_admin_id FROM death_verifications WHERE user_id = $1",
+    )
+    .bind(owner_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_ne!(verified_by, first_admin);
+    let (source, chain_status): (String, String) =
+        sqlx::query_as("SELECT source, chain_status FROM inheritance_triggers WHERE plan_id = $1")
+            .bind(plan_id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert_eq!(source, "death_verification");
+    assert_eq!(chain_status, "pending");
+    let locked: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM legacy_messages WHERE owner_user_id = $1 AND unlock_at > NOW()",
+    )
+    .bind(owner_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(locked, 0);
+
+    // --- On-chain submission ----------------------------------------------
+    let (url, calls) = start_relayer().await;
+    let submitter = InheritanceTriggerSubmitter::new(
+        ctx.pool.clone(),
+        Arc::new(ContractRelayer::new(url).with_token("relayer-token".to_string())),
+    );
+    submitter.submit_pending().await.unwrap();
+    let (chain_status, tx_hash): (String, Option<String>) = sqlx::query_as(
+        "SELECT chain_status, chain_tx_hash FROM inheritance_triggers WHERE plan_id = $1",
+    )
+    .bind(plan_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(chain_status, "submitted");
+    assert_eq!(tx_hash.as_deref(), Some("f00dfeed"));
+    assert!(calls.lock().unwrap().contains(&json!({
+        "function": "trigger_inheritance",
+        "args": { "plan_id": contract_plan_id },
+    })));
+}
+
+#[tokio::test]
+async fn rejected_case_stays_rejected_when_another_objection_is_dismissed() {
+    std::env::set_var("DOCUMENT_MASTER_KEY_VERSION", "1");
+    std::env::set_var(
+        "DOCUMENT_MASTER_KEY_V1",
+        "death-verification-test-master-key-material",
+    );
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let (owner_id, owner_email, owner_token) = helpers::user(&ctx, "owner.test").await;
+    let (heir_id, _, heir_token) = helpers::user(&ctx, "heir.test").await;
+    let (_, sister_email, sister_token) = helpers::user(&ctx, "sister.test").await;
+    let (_, brother_email, brother_token) = helpers::user(&ctx, "brother.test").await;
+    let (_, first_admin_token) = helpers::admin(&ctx).await;
+    let (_, second_admin_token) = helpers::admin(&ctx).await;
+
+    let plan_id = helpers::create_test_plan(&ctx.pool, owner_id)
+        .await
+        .unwrap();
+    let heir_wallet = format!("G{}", Uuid::new_v4().simple()).to_uppercase();
+    sqlx::query("UPDATE users SET wallet_address = $2 WHERE id = $1")
+        .bind(heir_id)
+        .bind(&heir_wallet)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO plan_beneficiaries (plan_id, wallet_address, allocation_percent) \
+         VALUES ($1, $2, 100)",
+    )
+    .bind(plan_id)
+    .bind(&heir_wallet)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    for (name, email) in [
+        ("Sue Sister", &sister_email),
+        ("Bob Brother", &brother_email),
+    ] {
+        let response = helpers::send(
+            &ctx,
+            "POST",
+            "/api/emergency/contacts",
+            &owner_token,
+            Some(json!({ "name": name, "relationship": "Sibling", "email": email })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/death-verification/cases",
+        &heir_token,
+        Some(json!({
+            "subject_email": owner_email,
+            "statement": "My father passed away last week",
+            "date_of_death": (Utc::now() - Duration::days(7)).date_naive(),
+        })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let case_id = helpers::json_body(response).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let response = upload(
+        &ctx,
+        &format!("/api/death-verification/cases/{case_id}/certificate"),
+        &heir_token,
+        "application/pdf",
+        "%PDF-1.4 certificate",
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let admin_case = format!("/api/admin/death-verification/cases/{case_id}");
+    let verify = json!({ "decision": "verify", "notes": "Certificate checked" });
+    for token in [&first_admin_token, &second_admin_token] {
+        let response = helpers::send(
+            &ctx,
+            "POST",
+            &format!("{admin_case}/reviews"),
+            token,
+            Some(verify.clone()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    // Both siblings object, then one objection is upheld.
+    let objections = format!("/api/death-verification/cases/{case_id}/objections");
+    let mut objection_ids = Vec::new();
+    for token in [&sister_token, &brother_token] {
+        let response = helpers::send(
+            &ctx,
+            "POST",
+            &objections,
+            token,
+            Some(json!({ "reason": "I spoke to him this morning" })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let id = helpers::json_body(response).await["data"]["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        objection_ids.push(id);
+    }
+    let resolve = |objection_id: &str| format!("{admin_case}/objections/{objection_id}/resolve");
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &resolve(&objection_ids[0]),
+        &first_admin_token,
+        Some(json!({ "decision": "uphold", "note": "He is alive" })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let rejected = helpers::json_body(response).await["data"].clone();
+    assert_eq!(rejected["status"], "rejected");
+    assert!(rejected["objections"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|objection| objection["status"] != "open"));
+
+    // Dismissing the other objection cannot reopen the rejected case.
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &resolve(&objection_ids[1]),
+        &first_admin_token,
+        Some(json!({ "decision": "dismiss", "note": "Mistaken" })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    sqlx::query(
+        "UPDATE death_verification_cases \
+         SET objection_window_ends_at = NOW() - INTERVAL '1 minute' WHERE id = $1",
+    )
+    .bind(Uuid::parse_str(&case_id).unwrap())
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/admin/death-verification/cases/finalize",
+        &first_admin_token,
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = helpers::send(&ctx, "GET", &admin_case, &first_admin_token, None).await;
+    assert_eq!(
+        helpers::json_body(response).await["data"]["status"],
+        "rejected"
+    );
+    let verified: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM death_verifications WHERE user_id = $1")
+            .bind(owner_id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert_eq!(verified, 0);
+    let triggers: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM inheritance_triggers WHERE plan_id = $1")
+            .bind(plan_id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert_eq!(triggers, 0);
+}
+
+fn rand_contract_plan_id() -> i64 {
+    (Uuid::new_v4().as_u128() % 1_000_000_000) as i64
+}
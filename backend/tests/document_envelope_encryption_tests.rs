@@ -0,0 +1,307 @@
+//! Integration tests for envelope encryption of will documents and backups.
+
+mod helpers;
+
+use async_trait::async_trait;
+use inheritx_backend::document_storage::{
+    DocumentKeyRing, DocumentKeyRotationService, DocumentStorageService, MASTER_KEY_VERSION_SECRET,
+};
+use inheritx_backend::object_storage::{LocalObjectStore, ObjectStore};
+use inheritx_backend::secrets::SecretsProvider;
+use inheritx_backend::ApiError;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM};
+use ring::hkdf::{Salt, HKDF_SHA256};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const MASTER_V1: &str = "integration-test-master-key-version-one";
+const MASTER_V2: &str = "integration-test-master-key-version-two";
+const LEGACY_KEY: &str = "integration-test-legacy-document-key";
+
+struct StaticSecrets(HashMap<String, String>);
+
+#[async_trait]
+impl SecretsProvider for StaticSecrets {
+    async fn get_secret(&self, name: &str) -> Result<String, ApiError> {
+        self.0
+            .get(name)
+            .cloned()
+            .ok_or_else(|| ApiError::Internal(anyhow::anyhow!("missing {name}")))
+    }
+
+    async fn rotate_secret(&self, _name: &str, _new_value: &str) -> Result<(), ApiError> {
+        Ok(())
+    }
+}
+
+fn keyring(current_version: i32) -> Arc<DocumentKeyRing> {
+    let mut secrets = HashMap::new();
+    secrets.insert(
+        MASTER_KEY_VERSION_SECRET.to_string(),
+        current_version.to_string(),
+    );
+    // Both versions stay resolvable so rows rotated by a concurrently running
+    // test remain readable.
+    secrets.insert("DOCUMENT_MASTER_KEY_V1".to_string(), MASTER_V1.to_string());
+    secrets.insert("DOCUMENT_MASTER_KEY_V2".to_string(), MASTER_V2.to_string());
+    secrets.insert(
+        "DOCUMENT_ENCRYPTION_KEY".to_string(),
+        LEGACY_KEY.to_string(),
+    );
+    Arc::new(DocumentKeyRing::new(Arc::new(StaticSecrets(secrets))))
+}
+
+/// Encrypt the way rows were written before envelope encryption: directly
+/// with a key derived from `DOCUMENT_ENCRYPTION_KEY`.
+fn legacy_encrypt(plaintext: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let prk =
+        Salt::new(HKDF_SHA256, b"inheritx-document-encryption").extract(LEGACY_KEY.as_bytes());
+    let info: [&[u8]; 1] = [b"aes-256-gcm-key"];
+    let okm = prk.expand(&info, &AES_256_GCM).unwrap();
+    let mut key = [0u8; 32];
+    okm.fill(&mut key).unwrap();
+    let key = LessSafeKey::new(UnboundKey::new(&AES_256_GCM, &key).unwrap());
+    let nonce = [7u8; 12];
+    let mut ciphertext = plaintext.to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::empty(),
+        &mut ciphertext,
+    )
+    .unwrap();
+    (ciphertext, nonce.to_vec())
+}
+
+async fn key_version_of(pool: &sqlx::PgPool, table: &str, id: Uuid) -> Option<i32> {
+    sqlx::query_scalar(&format!("SELECT key_version FROM {table} WHERE id = $1"))
+        .bind(id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn stores_document_with_wrapped_data_key_and_version() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+
+    let user_id =
+        helpers::create_test_user(&ctx.pool, &format!("{}@envelope.test", Uuid::new_v4()))
+            .await
+            .unwrap();
+    let plan_id = helpers::create_test_plan(&ctx.pool, user_id).await.unwrap();
+    let document_id = helpers::create_test_will_document(&ctx.pool, user_id, plan_id)
+        .await
+        .unwrap();
+
+    let ring = keyring(1);
+    let content = b"Last will and testament of the envelope test";
+    DocumentStorageService::store_encrypted(&ctx.pool, &ring, user_id, document_id, content)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        key_version_of(&ctx.pool, "will_documents", document_id).await,
+        Some(1)
+    );
+    let wrapped: Option<Vec<u8>> =
+        sqlx::query_scalar("SELECT wrapped_data_key FROM will_documents WHERE id = $1")
+            .bind(document_id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert!(wrapped.is_some_and(|k| !k.is_empty()));
+
+    let decrypted =
+        DocumentStorageService::retrieve_decrypted(&ctx.pool, &ring, user_id, document_id)
+            .await
+            .unwrap();
+    assert_eq!(decrypted, content);
+}
+
+#[tokio::test]
+async fn rewrap_job_moves_documents_and_backups_to_new_master_key() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+
+    let user_id =
+        helpers::create_test_user(&ctx.pool, &format!("{}@envelope.test", Uuid::new_v4()))
+            .await
+            .unwrap();
+    let plan_id = helpers::create_test_plan(&ctx.pool, user_id).await.unwrap();
+    let document_id = helpers::create_test_will_document(&ctx.pool, user_id, plan_id)
+        .await
+        .unwrap();
+
+    let content = b"Content that outlives a master key rotation";
+    DocumentStorageService::store_encrypted(&ctx.pool, &keyring(1), user_id, document_id, content)
+        .await
+        .unwrap();
+    let store = LocalObjectStore::new(
+        std::env::temp_dir().join(format!("inheritx-backups-{}", Uuid::new_v4())),
+    );
+    let backup = DocumentStorageService::create_backup(&ctx.pool, &store, user_id, document_id)
+        .await
+        .unwrap();
+    assert_eq!(backup.key_version, Some(1));
+
+    let storage_key: String =
+        sqlx::query_scalar("SELECT storage_key FROM document_backups WHERE id = $1")
+            .bind(backup.id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    let stored_ciphertext = store.get(&storage_key).await.unwrap();
+
+    let rotated_ring = keyring(2);
+    let rotation = DocumentKeyRotationService::new(ctx.pool.clone(), rotated_ring.clone());
+    // Other tests may leave outdated rows behind; run until ours are rotated.
+    for _ in 0..10 {
+        let result = rotation.rewrap_outdated_keys().await.unwrap();
+        assert_eq!(result.current_key_version, 2);
+        if key_version_of(&ctx.pool, "will_documents", document_id).await == Some(2)
+            && key_version_of(&ctx.pool, "document_backups", backup.id).await == Some(2)
+        {
+            break;
+        }
+    }
+
+    assert_eq!(
+        key_version_of(&ctx.pool, "will_documents", document_id).await,
+        Some(2)
+    );
+    assert_eq!(
+        key_version_of(&ctx.pool, "document_backups", backup.id).await,
+        Some(2)
+    );
+
+    // Re-wrapping only touches the data key, so the stored backup object is
+    // still the content-addressed ciphertext it was written as.
+    assert_eq!(store.get(&storage_key).await.unwrap(), stored_ciphertext);
+
+    let decrypted =
+        DocumentStorageService::retrieve_decrypted(&ctx.pool, &rotated_ring, user_id, document_id)
+            .await
+            .unwrap();
+    assert_eq!(decrypted, content);
+}
+
+#[tokio::test]
+async fn resealing_a_legacy_backup_recomputes_its_hash() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+
+    let user_id =
+        helpers::create_test_user(&ctx.pool, &format!("{}@envelope.test", Uuid::new_v4()))
+            .await
+            .unwrap();
+    let plan_id = helpers::create_test_plan(&ctx.pool, user_id).await.unwrap();
+    let document_id = helpers::create_test_will_document(&ctx.pool, user_id, plan_id)
+        .await
+        .unwrap();
+
+    let (ciphertext, nonce) = legacy_encrypt(b"Backup written before key versioning");
+    let backup_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO document_backups \
+         (document_id, user_id, backup_hash, encrypted_content, encryption_nonce) \
+         VALUES ($1, $2, $3, $4, $5) RETURNING id",
+    )
+    .bind(document_id)
+    .bind(user_id)
+    .bind(hex::encode(Sha256::digest(&ciphertext)))
+    .bind(&ciphertext)
+    .bind(&nonce)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+
+    let rotation = DocumentKeyRotationService::new(ctx.pool.clone(), keyring(2));
+    for _ in 0..10 {
+        rotation.rewrap_outdated_keys().await.unwrap();
+        if key_version_of(&ctx.pool, "document_backups", backup_id).await == Some(2) {
+            break;
+        }
+    }
+    assert_eq!(
+        key_version_of(&ctx.pool, "document_backups", backup_id).await,
+        Some(2)
+    );
+
+    let (resealed, backup_hash): (Vec<u8>, String) =
+        sqlx::query_as("SELECT encrypted_content, backup_hash FROM document_backups WHERE id = $1")
+            .bind(backup_id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert_ne!(resealed, ciphertext);
+    assert_eq!(backup_hash, hex::encode(Sha256::digest(&resealed)));
+}
+
+#[tokio::test]
+async fn rows_that_fail_to_rewrap_do_not_hold_back_newer_rows() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+
+    let user_id =
+        helpers::create_test_user(&ctx.pool, &format!("{}@envelope.test", Uuid::new_v4()))
+            .await
+            .unwrap();
+    let plan_id = helpers::create_test_plan(&ctx.pool, user_id).await.unwrap();
+    let document_id = helpers::create_test_will_document(&ctx.pool, user_id, plan_id)
+        .await
+        .unwrap();
+
+    // More unreadable backups than fit in one batch, all older than ours:
+    // their master key version is not configured.
+    let stuck: Vec<Uuid> = sqlx::query_scalar(
+        "INSERT INTO document_backups \
+         (document_id, user_id, backup_hash, encrypted_content, encryption_nonce, \
+          wrapped_data_key, data_key_nonce, key_version, created_at) \
+         SELECT $1, $2, repeat('0', 64), '\\x00', '\\x00', '\\x00', '\\x00', 99, \
+                TIMESTAMPTZ '2000-01-01' + n * INTERVAL '1 second' \
+         FROM generate_series(1, 150) AS n RETURNING id",
+    )
+    .bind(document_id)
+    .bind(user_id)
+    .fetch_all(&ctx.pool)
+    .await
+    .unwrap();
+
+    DocumentStorageService::store_encrypted(
+        &ctx.pool,
+        &keyring(1),
+        user_id,
+        document_id,
+        b"Rotated despite the stuck rows",
+    )
+    .await
+    .unwrap();
+    let store = LocalObjectStore::new(
+        std::env::temp_dir().join(format!("inheritx-backups-{}", Uuid::new_v4())),
+    );
+    let backup = DocumentStorageService::create_backup(&ctx.pool, &store, user_id, document_id)
+        .await
+        .unwrap();
+
+    let result = DocumentKeyRotationService::new(ctx.pool.clone(), keyring(2))
+        .rewrap_outdated_keys()
+        .await
+        .unwrap();
+    assert!(result.failed >= stuck.len());
+    assert_eq!(
+        key_version_of(&ctx.pool, "document_backups", backup.id).await,
+        Some(2)
+    );
+
+    sqlx::query("DELETE FROM document_backups WHERE id = ANY($1)")
+        .bind(&stuck)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+}
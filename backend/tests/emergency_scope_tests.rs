@@ -0,0 +1,265 @@
+//! Integration tests for scoped emergency access grants.
+
+mod helpers;
+
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn emergency_grants_only_allow_their_scope() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let (owner_id, _, owner_token) = helpers::user(&ctx, "owner.test").await;
+    let (_, contact_email, contact_token) = helpers::user(&ctx, "contact.test").await;
+    let plan_id = helpers::create_test_plan(&ctx.pool, owner_id)
+        .await
+        .unwrap();
+    helpers::create_test_will_document(&ctx.pool, owner_id, plan_id)
+        .await
+        .unwrap();
+
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/emergency/contacts",
+        &owner_token,
+        Some(json!({ "name": "Sam Sibling", "relationship": "Sibling", "email": contact_email })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/messages/legacy",
+        &owner_token,
+        Some(json!({
+            "beneficiary_contact": "heir@example.com",
+            "message": "The spare key is under the mat",
+            "unlock_at": Utc::now() + Duration::days(30),
+        })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // pay_bills needs payees and a cap
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/emergency/break-glass",
+        &contact_token,
+        Some(json!({ "plan_id": plan_id, "reason": "Hospital", "permissions": ["pay_bills"] })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // A default grant may only view documents.
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/emergency/break-glass",
+        &contact_token,
+        Some(json!({ "plan_id": plan_id, "reason": "Hospital" })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let grant = helpers::json_body(response).await["data"].clone();
+    assert_eq!(grant["permissions"], json!(["view_documents"]));
+    let grant_id = grant["id"].as_str().unwrap().to_string();
+    sqlx::query(
+        "UPDATE emergency_access_grants SET effective_at = NOW() - INTERVAL '1 minute' \
+         WHERE id = $1",
+    )
+    .bind(Uuid::parse_str(&grant_id).unwrap())
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    let base = format!("/api/emergency/break-glass/{grant_id}");
+    let response = helpers::send(
+        &ctx,
+        "GET",
+        &format!("{base}/documents"),
+        &contact_token,
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(helpers::json_body(response).await["count"], 1);
+    let response = helpers::send(
+        &ctx,
+        "GET",
+        &format!("{base}/messages"),
+        &contact_token,
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let bill = json!({ "payee": "GUTILITYCO", "amount": "60" });
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &format!("{base}/bill-payments"),
+        &contact_token,
+        Some(bill.clone()),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // --- The owner widens the scope ---------------------------------------
+    let scope = json!({
+        "permissions": ["view_documents", "read_messages", "pay_bills"],
+        "allowed_payees": ["GUTILITYCO"],
+        "spending_cap": "100",
+    });
+    let response = helpers::send(
+        &ctx,
+        "PUT",
+        &format!("{base}/scope"),
+        &contact_token,
+        Some(scope.clone()),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = helpers::send(
+        &ctx,
+        "PUT",
+        &format!("{base}/scope"),
+        &owner_token,
+        Some(scope),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/emergency/access/sessions",
+        &contact_token,
+        Some(json!({ "grant_id": grant_id })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let session_id = helpers::json_body(response).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let heartbeat_uri = format!("/api/emergency/access/sessions/{session_id}/heartbeat");
+    let response = helpers::send(&ctx, "PUT", &heartbeat_uri, &contact_token, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let heartbeat = helpers::json_body(response).await["data"].clone();
+    assert_eq!(heartbeat["id"], session_id);
+    assert_eq!(
+        heartbeat["scope"]["permissions"],
+        json!(["pay_bills", "read_messages", "view_documents"])
+    );
+    assert_eq!(heartbeat["scope"]["allowed_payees"], json!(["GUTILITYCO"]));
+
+    let response = helpers::send(
+        &ctx,
+        "GET",
+        &format!("{base}/messages"),
+        &contact_token,
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let messages = helpers::json_body(response).await["data"].clone();
+    assert_eq!(messages[0]["body"], "The spare key is under the mat");
+
+    let bills = format!("{base}/bill-payments");
+    let response = helpers::send(&ctx, "POST", &bills, &contact_token, Some(bill.clone())).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &bills,
+        &contact_token,
+        Some(json!({ "payee": "GSOMEONEELSE", "amount": "10" })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = helpers::send(&ctx, "POST", &bills, &contact_token, Some(bill)).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let spent: rust_decimal::Decimal =
+        sqlx::query_scalar("SELECT spent_amount FROM emergency_access_grants WHERE id = $1")
+            .bind(Uuid::parse_str(&grant_id).unwrap())
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert_eq!(spent, rust_decimal::Decimal::from(60));
+
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &format!("{base}/trigger-inheritance"),
+        &contact_token,
+        Some(json!({ "reason": "Owner has passed away" })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = helpers::send(
+        &ctx,
+        "PUT",
+        &format!("{base}/scope"),
+        &owner_token,
+        Some(json!({ "permissions": ["trigger_inheritance"] })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let trigger = json!({ "reason": "Owner has passed away" });
+    let trigger_uri = format!("{base}/trigger-inheritance");
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &trigger_uri,
+        &contact_token,
+        Some(trigger.clone()),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        helpers::json_body(response).await["data"]["source"],
+        "emergency_access"
+    );
+    let response = helpers::send(&ctx, "POST", &trigger_uri, &contact_token, Some(trigger)).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    // --- Every use is logged with the permission it relied on ---------------
+    let response = helpers::send(
+        &ctx,
+        "GET",
+        &format!("/api/emergency/access/audit-logs?grant_id={grant_id}"),
+        &owner_token,
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let logs = helpers::json_body(response).await["data"].clone();
+    let permission_of = |action: &str| {
+        logs.as_array()
+            .unwrap()
+            .iter()
+            .find(|log| log["action"] == action)
+            .map(|log| log["permission"].clone())
+    };
+    assert_eq!(
+        permission_of("emergency_documents_viewed"),
+        Some(json!("view_documents"))
+    );
+    assert_eq!(
+        permission_of("emergency_messages_read"),
+        Some(json!("read_messages"))
+    );
+    assert_eq!(
+        permission_of("emergency_bill_paid"),
+        Some(json!("pay_bills"))
+    );
+    assert_eq!(
+        permission_of("emergency_inheritance_triggered"),
+        Some(json!("trigger_inheritance"))
+    );
+    assert_eq!(permission_of("emergency_scope_updated"), Some(Value::Null));
+}
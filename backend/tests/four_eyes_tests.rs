@@ -0,0 +1,240 @@
+//! Integration tests for four-eyes approval of high-risk admin actions.
+
+mod helpers;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use uuid::Uuid;
+
+async fn kyc_status(ctx: &helpers::TestContext, user_id: Uuid) -> String {
+    sqlx::query_scalar("SELECT status FROM kyc_status WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn high_risk_actions_need_a_second_admin() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let user_id =
+        helpers::create_test_user(&ctx.pool, &format!("{}@four-eyes.test", Uuid::new_v4()))
+            .await
+            .unwrap();
+    sqlx::query("UPDATE kyc_status SET status = 'pending' WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    let (proposer_id, proposer) = helpers::admin_with_role(&ctx, "admin").await;
+    let (approver_id, approver) = helpers::admin_with_role(&ctx, "kyc_reviewer").await;
+    let (_, risk_officer) = helpers::admin_with_role(&ctx, "risk_officer").await;
+
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/admin/kyc/approve",
+        &proposer,
+        Some(json!({ "user_id": user_id })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body = helpers::json_body(response).await;
+    assert_eq!(body["status"], "pending_approval");
+    assert_eq!(body["data"]["action_type"], "approve_kyc");
+    assert_eq!(body["data"]["required_permission"], "kyc:review");
+    let action_id = body["data"]["id"].as_str().unwrap().to_string();
+    assert_eq!(kyc_status(&ctx, user_id).await, "pending");
+
+    // Only admins who could approve it see it.
+    let response = helpers::send(
+        &ctx,
+        "GET",
+        "/api/admin/pending-actions?status=pending",
+        &approver,
+        None,
+    )
+    .await;
+    let listed = helpers::json_body(response).await;
+    assert!(listed["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|a| a["id"] == action_id.as_str()));
+    let response = helpers::send(
+        &ctx,
+        "GET",
+        "/api/admin/pending-actions",
+        &risk_officer,
+        None,
+    )
+    .await;
+    let listed = helpers::json_body(response).await;
+    assert!(!listed["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|a| a["id"] == action_id.as_str()));
+
+    let approve_uri = format!("/api/admin/pending-actions/{action_id}/approve");
+    let response = helpers::send(&ctx, "POST", &approve_uri, &proposer, None).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = helpers::send(&ctx, "POST", &approve_uri, &risk_officer, None).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(kyc_status(&ctx, user_id).await, "pending");
+
+    let response = helpers::send(&ctx, "POST", &approve_uri, &approver, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = helpers::json_body(response).await;
+    assert_eq!(body["data"]["status"], "executed");
+    assert_eq!(body["data"]["decided_by"], approver_id.to_string());
+    assert_eq!(kyc_status(&ctx, user_id).await, "approved");
+
+    let response = helpers::send(&ctx, "POST", &approve_uri, &approver, None).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let response = helpers::send(
+        &ctx,
+        "GET",
+        &format!("/api/admin/pending-actions/{action_id}"),
+        &approver,
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = helpers::json_body(response).await;
+    let trail: Vec<(String, String)> = body["data"]["audit_trail"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| {
+            (
+                e["action"].as_str().unwrap().to_string(),
+                e["admin_id"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        trail,
+        vec![
+            (
+                "pending_action_proposed".to_string(),
+                proposer_id.to_string()
+            ),
+            (
+                "pending_action_approved".to_string(),
+                approver_id.to_string()
+            ),
+            (
+                "pending_action_executed".to_string(),
+                approver_id.to_string()
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn pending_actions_can_be_rejected_or_expire() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let user_id =
+        helpers::create_test_user(&ctx.pool, &format!("{}@four-eyes.test", Uuid::new_v4()))
+            .await
+            .unwrap();
+    let plan_id = helpers::create_test_plan(&ctx.pool, user_id).await.unwrap();
+    let (_, proposer) = helpers::admin_with_role(&ctx, "risk_officer").await;
+    let (_, approver) = helpers::admin_with_role(&ctx, "admin").await;
+
+    let pause = json!({ "plan_id": plan_id, "reason": "suspicious withdrawals" });
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/admin/emergency/pause",
+        &proposer,
+        Some(pause.clone()),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let rejected_id = helpers::json_body(response).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &format!("/api/admin/pending-actions/{rejected_id}/reject"),
+        &approver,
+        Some(json!({ "reason": "false positive" })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = helpers::json_body(response).await;
+    assert_eq!(body["data"]["status"], "rejected");
+    assert_eq!(body["data"]["decision_reason"], "false positive");
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &format!("/api/admin/pending-actions/{rejected_id}/approve"),
+        &approver,
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    // --- Proposals that outlive their deadline cannot be approved -----------
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/admin/emergency/pause",
+        &proposer,
+        Some(pause),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let expired_id = Uuid::parse_str(
+        helpers::json_body(response).await["data"]["id"]
+            .as_str()
+            .unwrap(),
+    )
+    .unwrap();
+    sqlx::query(
+        "UPDATE pending_admin_actions SET expires_at = NOW() - INTERVAL '1 minute' WHERE id = $1",
+    )
+    .bind(expired_id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &format!("/api/admin/pending-actions/{expired_id}/approve"),
+        &approver,
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let (status, paused): (String, Option<bool>) = sqlx::query_as(
+        "SELECT a.status, p.is_paused FROM pending_admin_actions a, plans p \
+         WHERE a.id = $1 AND p.id = $2",
+    )
+    .bind(expired_id)
+    .bind(plan_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(status, "expired");
+    assert_ne!(paused, Some(true));
+    let expired_logged: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM action_logs \
+         WHERE entity_id = $1 AND action = 'pending_action_expired'",
+    )
+    .bind(expired_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(expired_logged, 1);
+}
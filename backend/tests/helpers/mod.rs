@@ -1,5 +1,6 @@
 // This file is a placeholder for helper functions and structs.
 use axum::{body::Body, http::Request, Router};
+use inheritx_backend::test_auth::TestTokenIssuer;
 use inheritx_backend::{create_app, Config};
 use serde_json::json;
 use sqlx::{postgres::PgPoolOptions, PgPool};
@@ -15,6 +16,12 @@ pub struct TestContext {
 impl TestContext {
     #[allow(dead_code)]
     pub async fn from_env() -> Option<Self> {
+        Self::from_env_with(|_| {}).await
+    }
+
+    /// Like [`Self::from_env`], with `configure` applied to the test config.
+    #[allow(dead_code)]
+    pub async fn from_env_with(configure: impl FnOnce(&mut Config)) -> Option<Self> {
         // Use a static to ensure tracing is only initialized once
         static INIT: std::sync::Once = std::sync::Once::new();
         INIT.call_once(|| {
@@ -41,11 +48,8 @@ impl TestContext {
             }
         };
 
-        let config = Config {
-            database_url,
-            port: 0,
-            jwt_secret: env::var("JWT_SECRET").unwrap_or_else(|_| "test-jwt-secret".to_string()),
-        };
+        let mut config = test_config(database_url);
+        configure(&mut config);
 
         // Run migrations
         inheritx_backend::db::run_migrations(&pool)
@@ -80,10 +84,11 @@ impl TestContext {
 #[allow(dead_code)]
 pub async fn create_test_user(pool: &PgPool, email: &str) -> sqlx::Result<uuid::Uuid> {
     let user_id = uuid::Uuid::new_v4();
-    let wallet = format!("G{}", &user_id.to_string().replace("-", "")[..55]);
+    let hex = user_id.simple().to_string().to_uppercase();
+    let wallet = format!("G{}", &hex.repeat(2)[..55]);
 
     sqlx::query(
-        "INSERT INTO users (id, email, wallet_address, kyc_status) VALUES ($1, $2, $3, 'approved')",
+        "INSERT INTO users (id, email, password_hash, wallet_address) VALUES ($1, $2, 'test-hash', $3)",
     )
     .bind(user_id)
     .bind(email)
@@ -91,6 +96,11 @@ pub async fn create_test_user(pool: &PgPool, email: &str) -> sqlx::Result<uuid::
     .execute(pool)
     .await?;
 
+    sqlx::query("INSERT INTO kyc_status (user_id, status) VALUES ($1, 'approved')")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
     Ok(user_id)
 }
 
@@ -100,7 +110,7 @@ pub async fn create_test_admin(pool: &PgPool, email: &str) -> sqlx::Result<uuid:
     let password_hash = bcrypt::hash("test_password", bcrypt::DEFAULT_COST).unwrap();
 
     sqlx::query(
-        "INSERT INTO admins (id, email, password_hash, status) VALUES ($1, $2, $3, 'active')",
+        "INSERT INTO admins (id, email, password_hash, role, status) VALUES ($1, $2, $3, 'admin', 'active')",
     )
     .bind(admin_id)
     .bind(email)
@@ -162,3 +172,196 @@ pub async fn create_admin_and_get_token(ctx: &mut TestContext) -> String {
 
     json["token"].as_str().unwrap().to_string()
 }
+
+#[allow(dead_code)]
+pub async fn create_test_plan(pool: &PgPool, user_id: uuid::Uuid) -> sqlx::Result<uuid::Uuid> {
+    let plan_id = uuid::Uuid::new_v4();
+
+    sqlx::query("INSERT INTO plans (id, user_id, title, status) VALUES ($1, $2, $3, 'active')")
+        .bind(plan_id)
+        .bind(user_id)
+        .bind(format!("Test plan {plan_id}"))
+        .execute(pool)
+        .await?;
+
+    Ok(plan_id)
+}
+
+#[allow(dead_code)]
+pub async fn create_test_will_document(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+    plan_id: uuid::Uuid,
+) -> sqlx::Result<uuid::Uuid> {
+    let document_id = uuid::Uuid::new_v4();
+
+    sqlx::query(
+        "INSERT INTO will_documents (id, plan_id, user_id, template, will_hash, version, filename, pdf_base64) \
+         VALUES ($1, $2, $3, 'formal', $4, 1, 'will_v1.pdf', 'JVBERi0xLjQK')",
+    )
+    .bind(document_id)
+    .bind(plan_id)
+    .bind(user_id)
+    .bind("a".repeat(64))
+    .execute(pool)
+    .await?;
+
+    Ok(document_id)
+}
+
+/// Configuration for the test app: test auth enabled, signing with
+/// `JWT_SECRET` or the default test secret.
+#[allow(dead_code)]
+pub fn test_config(database_url: String) -> Config {
+    Config {
+        database_url,
+        port: 0,
+        jwt_secret: env::var("JWT_SECRET").unwrap_or_else(|_| "test-jwt-secret".to_string()),
+        environment: Some("test".to_string()),
+        test_auth: true,
+        require_admin_passkeys: false,
+    }
+}
+
+#[allow(dead_code)]
+fn token_issuer() -> TestTokenIssuer {
+    TestTokenIssuer::from_config(&test_config(String::new())).expect("test auth is enabled")
+}
+
+/// Issue a user access token through the test token issuer.
+#[allow(dead_code)]
+pub fn user_token(user_id: uuid::Uuid, email: &str) -> String {
+    token_issuer()
+        .user_token(user_id, email)
+        .expect("failed to sign test token")
+}
+
+/// Issue a user access token that has just completed a step-up, for routes
+/// that require a recent second factor.
+#[allow(dead_code)]
+pub fn stepped_up_user_token(user_id: uuid::Uuid, email: &str) -> String {
+    token_issuer()
+        .with_mfa_at(chrono::Utc::now().timestamp())
+        .user_token(user_id, email)
+        .expect("failed to sign test token")
+}
+
+/// Issue an admin access token through the test token issuer.
+#[allow(dead_code)]
+pub fn admin_token(admin_id: uuid::Uuid, email: &str) -> String {
+    token_issuer()
+        .admin_token(admin_id, email, "admin")
+        .expect("failed to sign test token")
+}
+
+/// Run content inspection for an upload so it becomes deliverable. The app's
+/// own inspection job may get there first, which is fine.
+#[allow(dead_code)]
+pub async fn inspect_content(pool: &PgPool, content_id: uuid::Uuid) -> String {
+    use inheritx_backend::content_inspection::{ContentInspectionService, DisabledScanner};
+
+    let service = ContentInspectionService::new(
+        pool.clone(),
+        inheritx_backend::object_storage::build_object_store().unwrap(),
+        std::sync::Arc::new(DisabledScanner),
+    );
+    match service.inspect_content(content_id).await {
+        Ok(_) | Err(inheritx_backend::ApiError::NotFound(_)) => {}
+        Err(e) => panic!("content inspection failed: {e}"),
+    }
+
+    // Wait out a concurrent inspection by the app's job.
+    for _ in 0..50 {
+        let status: String =
+            sqlx::query_scalar("SELECT inspection_status FROM legacy_content WHERE id = $1")
+                .bind(content_id)
+                .fetch_one(pool)
+                .await
+                .unwrap();
+        if status != "pending" {
+            return status;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    panic!("content {content_id} was never inspected");
+}
+
+/// Read a response body as JSON.
+#[allow(dead_code)]
+pub async fn json_body(response: axum::http::Response<Body>) -> serde_json::Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+/// A JSON request to `uri`, carrying `token` as a bearer token when given.
+/// Tests add their own headers or extensions before passing it to
+/// [`dispatch`].
+#[allow(dead_code)]
+pub fn request(method: &str, uri: &str, token: Option<&str>) -> axum::http::request::Builder {
+    let builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    match token {
+        Some(token) => builder.header("Authorization", format!("Bearer {token}")),
+        None => builder,
+    }
+}
+
+/// Send a request built by [`request`] to the app, with an optional JSON body.
+#[allow(dead_code)]
+pub async fn dispatch(
+    ctx: &TestContext,
+    request: axum::http::request::Builder,
+    body: Option<serde_json::Value>,
+) -> axum::http::Response<Body> {
+    let body = body.map_or_else(Body::empty, |b| Body::from(b.to_string()));
+    ctx.app
+        .clone()
+        .oneshot(request.body(body).unwrap())
+        .await
+        .unwrap()
+}
+
+/// Send an authenticated JSON request to the app.
+#[allow(dead_code)]
+pub async fn send(
+    ctx: &TestContext,
+    method: &str,
+    uri: &str,
+    token: &str,
+    body: Option<serde_json::Value>,
+) -> axum::http::Response<Body> {
+    dispatch(ctx, request(method, uri, Some(token)), body).await
+}
+
+/// Create a user under `domain` and issue them a token.
+#[allow(dead_code)]
+pub async fn user(ctx: &TestContext, domain: &str) -> (uuid::Uuid, String, String) {
+    let email = format!("{}@{domain}", uuid::Uuid::new_v4());
+    let user_id = create_test_user(&ctx.pool, &email).await.unwrap();
+    let token = user_token(user_id, &email);
+    (user_id, email, token)
+}
+
+/// Create an admin and issue them a token.
+#[allow(dead_code)]
+pub async fn admin(ctx: &TestContext) -> (uuid::Uuid, String) {
+    admin_with_role(ctx, "admin").await
+}
+
+/// Create an admin holding `role` and issue them a token.
+#[allow(dead_code)]
+pub async fn admin_with_role(ctx: &TestContext, role: &str) -> (uuid::Uuid, String) {
+    let email = format!("{}@admins.test", uuid::Uuid::new_v4());
+    let admin_id = create_test_admin(&ctx.pool, &email).await.unwrap();
+    sqlx::query("UPDATE admins SET role = $2 WHERE id = $1")
+        .bind(admin_id)
+        .bind(role)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    (admin_id, admin_token(admin_id, &email))
+}
@@ -0,0 +1,236 @@
+//! Integration tests for Idempotency-Key handling on money-moving POSTs.
+
+mod helpers;
+
+use axum::{
+    body::Body,
+    http::{Request, Response, StatusCode},
+};
+use inheritx_backend::idempotency::{IdempotencyService, KeyClaim};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+async fn body_bytes(response: Response<Body>) -> Vec<u8> {
+    axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap()
+        .to_vec()
+}
+
+async fn claim_plan(
+    ctx: &helpers::TestContext,
+    token: &str,
+    key: Option<&str>,
+    plan_id: Uuid,
+    body: &Value,
+) -> Response<Body> {
+    let mut request = Request::builder()
+        .method("POST")
+        .uri(format!("/api/plans/{plan_id}/claim"))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {token}"));
+    if let Some(key) = key {
+        request = request.header("Idempotency-Key", key);
+    }
+    ctx.app
+        .clone()
+        .oneshot(request.body(Body::from(body.to_string())).unwrap())
+        .await
+        .unwrap()
+}
+
+fn claim_request(email: &str) -> Value {
+    json!({ "beneficiary_email": email, "two_fa_code": "123456" })
+}
+
+/// A user with a freshly stepped-up token.
+async fn stepped_up_user(ctx: &helpers::TestContext) -> (Uuid, String) {
+    let email = format!("{}@idempotency.test", Uuid::new_v4());
+    let user_id = helpers::create_test_user(&ctx.pool, &email).await.unwrap();
+    (user_id, helpers::stepped_up_user_token(user_id, &email))
+}
+
+async fn stored_keys(ctx: &helpers::TestContext, user_id: Uuid) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM idempotency_keys WHERE principal = $1")
+        .bind(format!("user:{user_id}"))
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap()
+}
+
+async fn set_kyc_status(ctx: &helpers::TestContext, user_id: Uuid, status: &str) {
+    sqlx::query("UPDATE kyc_status SET status = $2 WHERE user_id = $1")
+        .bind(user_id)
+        .bind(status)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn retries_replay_the_first_outcome() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let (_, token) = stepped_up_user(&ctx).await;
+    let plan_id = Uuid::new_v4();
+    let key = Uuid::new_v4().to_string();
+    let body = claim_request("heir@idempotency.test");
+
+    let first = claim_plan(&ctx, &token, Some(&key), plan_id, &body).await;
+    assert_eq!(first.status(), StatusCode::NOT_FOUND);
+    assert!(first.headers().get("idempotent-replayed").is_none());
+    let first_body = body_bytes(first).await;
+
+    let retry = claim_plan(&ctx, &token, Some(&key), plan_id, &body).await;
+    assert_eq!(retry.status(), StatusCode::NOT_FOUND);
+    assert_eq!(retry.headers()["idempotent-replayed"], "true");
+    assert_eq!(retry.headers()["content-type"], "application/json");
+    assert_eq!(body_bytes(retry).await, first_body);
+
+    // Without a key the request runs again.
+    let unkeyed = claim_plan(&ctx, &token, None, plan_id, &body).await;
+    assert_eq!(unkeyed.status(), StatusCode::NOT_FOUND);
+    assert!(unkeyed.headers().get("idempotent-replayed").is_none());
+
+    // The same key with a different body or path is rejected.
+    let changed = claim_plan(
+        &ctx,
+        &token,
+        Some(&key),
+        plan_id,
+        &claim_request("other@idempotency.test"),
+    )
+    .await;
+    assert_eq!(changed.status(), StatusCode::CONFLICT);
+    let other_plan = claim_plan(&ctx, &token, Some(&key), Uuid::new_v4(), &body).await;
+    assert_eq!(other_plan.status(), StatusCode::CONFLICT);
+
+    // Keys belong to their caller: another user's key of the same name is
+    // unrelated.
+    let (_, other_token) = stepped_up_user(&ctx).await;
+    let other = claim_plan(&ctx, &other_token, Some(&key), plan_id, &body).await;
+    assert_eq!(other.status(), StatusCode::NOT_FOUND);
+    assert!(other.headers().get("idempotent-replayed").is_none());
+}
+
+#[tokio::test]
+async fn step_up_rejections_release_the_key_for_a_retry() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let email = format!("{}@idempotency.test", Uuid::new_v4());
+    let user_id = helpers::create_test_user(&ctx.pool, &email).await.unwrap();
+    let plan_id = Uuid::new_v4();
+    let key = Uuid::new_v4().to_string();
+    let body = claim_request("heir@idempotency.test");
+
+    // Without a recent second factor the claim is refused, and the refusal
+    // is not what later retries get.
+    let token = helpers::user_token(user_id, &email);
+    let refused = claim_plan(&ctx, &token, Some(&key), plan_id, &body).await;
+    assert_eq!(refused.status(), StatusCode::FORBIDDEN);
+    assert_eq!(stored_keys(&ctx, user_id).await, 0);
+
+    // After the step-up the same key runs the request and stores its outcome.
+    let token = helpers::stepped_up_user_token(user_id, &email);
+    let retry = claim_plan(&ctx, &token, Some(&key), plan_id, &body).await;
+    assert_eq!(retry.status(), StatusCode::NOT_FOUND);
+    assert!(retry.headers().get("idempotent-replayed").is_none());
+    assert_eq!(stored_keys(&ctx, user_id).await, 1);
+    let replay = claim_plan(&ctx, &token, Some(&key), plan_id, &body).await;
+    assert_eq!(replay.status(), StatusCode::NOT_FOUND);
+    assert_eq!(replay.headers()["idempotent-replayed"], "true");
+
+    // Other refusals release the key too: a user whose KYC is pending can
+    // retry with the same key once it is approved.
+    let (user_id, token) = stepped_up_user(&ctx).await;
+    set_kyc_status(&ctx, user_id, "pending").await;
+    let key = Uuid::new_v4().to_string();
+    let refused = claim_plan(&ctx, &token, Some(&key), plan_id, &body).await;
+    assert_eq!(refused.status(), StatusCode::FORBIDDEN);
+    set_kyc_status(&ctx, user_id, "approved").await;
+    let retry = claim_plan(&ctx, &token, Some(&key), plan_id, &body).await;
+    assert_eq!(retry.status(), StatusCode::NOT_FOUND);
+    assert!(retry.headers().get("idempotent-replayed").is_none());
+}
+
+#[tokio::test]
+async fn expired_keys_can_be_reused_and_are_purged() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let (user_id, token) = stepped_up_user(&ctx).await;
+    let plan_id = Uuid::new_v4();
+    let key = Uuid::new_v4().to_string();
+    let body = claim_request("heir@idempotency.test");
+    let expire = || {
+        sqlx::query(
+            "UPDATE idempotency_keys SET expires_at = NOW() - INTERVAL '1 minute' \
+             WHERE principal = $1 AND idempotency_key = $2",
+        )
+        .bind(format!("user:{user_id}"))
+        .bind(&key)
+        .execute(&ctx.pool)
+    };
+
+    let first = claim_plan(&ctx, &token, Some(&key), plan_id, &body).await;
+    assert_eq!(first.status(), StatusCode::NOT_FOUND);
+
+    expire().await.unwrap();
+    let reused = claim_plan(&ctx, &token, Some(&key), plan_id, &body).await;
+    assert_eq!(reused.status(), StatusCode::NOT_FOUND);
+    assert!(reused.headers().get("idempotent-replayed").is_none());
+
+    expire().await.unwrap();
+    assert!(IdempotencyService::purge_expired(&ctx.pool).await.unwrap() >= 1);
+    assert_eq!(stored_keys(&ctx, user_id).await, 0);
+}
+
+#[tokio::test]
+async fn keys_in_use_conflict_until_completed_or_released() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let principal = format!("user:{}", Uuid::new_v4());
+    let key = Uuid::new_v4().to_string();
+    let expires_at = chrono::Utc::now() + chrono::Duration::hours(1);
+    let claim = || {
+        IdempotencyService::claim(
+            &ctx.pool,
+            &principal,
+            &key,
+            "POST",
+            "/api/plans",
+            "fingerprint",
+            expires_at,
+        )
+    };
+
+    assert!(matches!(claim().await.unwrap(), KeyClaim::Acquired));
+    assert!(claim().await.is_err());
+
+    // A server error releases the key so the request can be retried.
+    IdempotencyService::release(&ctx.pool, &principal, &key)
+        .await
+        .unwrap();
+    assert!(matches!(claim().await.unwrap(), KeyClaim::Acquired));
+}
+
+#[tokio::test]
+async fn malformed_keys_are_rejected() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let (_, token) = stepped_up_user(&ctx).await;
+    let response = claim_plan(
+        &ctx,
+        &token,
+        Some(&"k".repeat(256)),
+        Uuid::new_v4(),
+        &claim_request("heir@idempotency.test"),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
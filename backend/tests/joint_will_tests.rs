@@ -0,0 +1,273 @@
+//! Integration tests for joint, mutual and mirror wills.
+
+mod helpers;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+struct Testator {
+    user_id: Uuid,
+    token: String,
+    key: Ed25519KeyPair,
+    wallet: String,
+}
+
+impl Testator {
+    async fn create(ctx: &helpers::TestContext) -> Self {
+        let email = format!("{}@joint.test", Uuid::new_v4());
+        let user_id = helpers::create_test_user(&ctx.pool, &email).await.unwrap();
+        let (key, wallet) = keypair();
+        Self {
+            user_id,
+            token: helpers::user_token(user_id, &email),
+            key,
+            wallet,
+        }
+    }
+
+    fn sign(&self, message: &str) -> String {
+        hex::encode(self.key.sign(message.as_bytes()).as_ref())
+    }
+}
+
+fn keypair() -> (Ed25519KeyPair, String) {
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+    let key = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+    let public_key: [u8; 32] = key.public_key().as_ref().try_into().unwrap();
+    let wallet = stellar_strkey::ed25519::PublicKey(public_key)
+        .to_string()
+        .as_str()
+        .to_string();
+    (key, wallet)
+}
+
+/// Create a document owned by `owner` with `spouse` as co-testator.
+async fn joint_document(
+    ctx: &helpers::TestContext,
+    owner: &Testator,
+    spouse: &Testator,
+    will_type: &str,
+    required_witnesses: u32,
+) -> (Uuid, Uuid) {
+    let plan_id = helpers::create_test_plan(&ctx.pool, owner.user_id)
+        .await
+        .unwrap();
+    let document_id = helpers::create_test_will_document(&ctx.pool, owner.user_id, plan_id)
+        .await
+        .unwrap();
+    let response = helpers::send(
+        ctx,
+        "POST",
+        &format!("/api/will/documents/{document_id}/testators"),
+        &owner.token,
+        Some(json!({
+            "will_type": will_type,
+            "wallet_address": owner.wallet,
+            "co_testators": [{ "user_id": spouse.user_id, "wallet_address": spouse.wallet }],
+            "required_witnesses": required_witnesses,
+        })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    (plan_id, document_id)
+}
+
+async fn sign(ctx: &helpers::TestContext, document_id: Uuid, testator: &Testator) -> StatusCode {
+    let response = helpers::send(
+        ctx,
+        "POST",
+        &format!("/api/will/documents/{document_id}/sign/challenge"),
+        &testator.token,
+        Some(json!({
+            "document_id": document_id,
+            "vault_id": "vault-joint",
+            "wallet_address": testator.wallet,
+        })),
+    )
+    .await;
+    if response.status() != StatusCode::OK {
+        return response.status();
+    }
+    let challenge = helpers::json_body(response).await["data"].clone();
+    let response = helpers::send(
+        ctx,
+        "POST",
+        "/api/will/sign",
+        &testator.token,
+        Some(json!({
+            "challenge_id": challenge["challenge_id"],
+            "wallet_address": testator.wallet,
+            "signature_hex": testator.sign(challenge["message"].as_str().unwrap()),
+        })),
+    )
+    .await;
+    response.status()
+}
+
+async fn status(ctx: &helpers::TestContext, document_id: Uuid, testator: &Testator) -> Value {
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/will/documents/{document_id}/testators"))
+                .header("Authorization", format!("Bearer {}", testator.token))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    helpers::json_body(response).await["data"].clone()
+}
+
+async fn valid_signatures(ctx: &helpers::TestContext, document_id: Uuid) -> i64 {
+    sqlx::query_scalar(
+        "SELECT COUNT(*) FROM will_signatures WHERE document_id = $1 AND invalidated_at IS NULL",
+    )
+    .bind(document_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn joint_will_finalizes_after_all_testators_and_witnesses_sign() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let owner = Testator::create(&ctx).await;
+    let spouse = Testator::create(&ctx).await;
+    let stranger = Testator::create(&ctx).await;
+    let (plan_id, document_id) = joint_document(&ctx, &owner, &spouse, "joint", 1).await;
+    let finalize = format!("/api/plans/{plan_id}/will/versions/1/finalize");
+
+    // Only named testators can sign, each with their registered wallet.
+    assert_eq!(
+        sign(&ctx, document_id, &stranger).await,
+        StatusCode::FORBIDDEN
+    );
+
+    assert_eq!(sign(&ctx, document_id, &owner).await, StatusCode::OK);
+    assert_eq!(
+        status(&ctx, document_id, &spouse).await["status"],
+        "signing"
+    );
+    assert_eq!(sign(&ctx, document_id, &owner).await, StatusCode::CONFLICT);
+    let response = helpers::send(&ctx, "PUT", &finalize, &owner.token, Some(json!({}))).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    assert_eq!(sign(&ctx, document_id, &spouse).await, StatusCode::OK);
+    assert_eq!(
+        status(&ctx, document_id, &owner).await["status"],
+        "awaiting_witnesses"
+    );
+
+    let (witness_key, witness_wallet) = keypair();
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &format!("/api/will/documents/{document_id}/witnesses"),
+        &owner.token,
+        Some(json!({ "wallet_address": witness_wallet })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let witness_id = helpers::json_body(response).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &format!("/api/will/witnesses/{witness_id}/sign"),
+        &owner.token,
+        Some(json!({
+            "wallet_address": witness_wallet,
+            "signature_hex": hex::encode(witness_key.sign("a".repeat(64).as_bytes()).as_ref()),
+        })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let joint = status(&ctx, document_id, &owner).await;
+    assert_eq!(joint["status"], "signed");
+    assert_eq!(joint["signed_witnesses"], 1);
+    let response = helpers::send(&ctx, "PUT", &finalize, &owner.token, Some(json!({}))).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Revoking a joint will withdraws every testator's signature.
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &format!("/api/will/documents/{document_id}/revoke"),
+        &spouse.token,
+        Some(json!({ "reason": "separation" })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let revoked = helpers::json_body(response).await["data"].clone();
+    assert_eq!(revoked["status"], "revoked");
+    assert_eq!(valid_signatures(&ctx, document_id).await, 0);
+    let events: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM will_event_log WHERE document_id = $1 AND event_type = 'will_revoked'",
+    )
+    .bind(document_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(events, 1);
+}
+
+#[tokio::test]
+async fn mirror_will_revocation_keeps_the_other_testators_signature() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let owner = Testator::create(&ctx).await;
+    let spouse = Testator::create(&ctx).await;
+    let (_, document_id) = joint_document(&ctx, &owner, &spouse, "mirror", 0).await;
+
+    assert_eq!(sign(&ctx, document_id, &owner).await, StatusCode::OK);
+    assert_eq!(sign(&ctx, document_id, &spouse).await, StatusCode::OK);
+    assert_eq!(status(&ctx, document_id, &owner).await["status"], "signed");
+
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &format!("/api/will/documents/{document_id}/revoke"),
+        &spouse.token,
+        Some(json!({})),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let mirror = helpers::json_body(response).await["data"].clone();
+    assert_eq!(mirror["status"], "partially_revoked");
+    let parties = mirror["parties"].as_array().unwrap();
+    assert!(parties
+        .iter()
+        .any(|p| p["user_id"] == owner.user_id.to_string() && p["status"] == "signed"));
+    assert!(parties
+        .iter()
+        .any(|p| p["user_id"] == spouse.user_id.to_string() && p["status"] == "revoked"));
+    assert_eq!(valid_signatures(&ctx, document_id).await, 1);
+
+    // The revoking testator cannot sign again or revoke twice.
+    assert_eq!(sign(&ctx, document_id, &spouse).await, StatusCode::CONFLICT);
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &format!("/api/will/documents/{document_id}/revoke"),
+        &spouse.token,
+        Some(json!({})),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+}
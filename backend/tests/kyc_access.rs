@@ -1,41 +1,44 @@
-/*
 // Integration tests for KYC-protected endpoints
 mod helpers;
-use axum::{
-    body::Body,
-    http::{Request, StatusCode},
-};
+use axum::http::StatusCode;
 use helpers::TestContext;
-use serde_json::json;
-use tower::ServiceExt;
+use serde_json::{json, Value};
 use uuid::Uuid;
 
+/// A user whose KYC is pending, with a stepped-up token.
+async fn pending_user(ctx: &TestContext) -> (Uuid, String) {
+    let email = format!("{}@kyc-access.test", Uuid::new_v4());
+    let user_id = helpers::create_test_user(&ctx.pool, &email).await.unwrap();
+    sqlx::query("UPDATE kyc_status SET status = 'pending' WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    (user_id, helpers::stepped_up_user_token(user_id, &email))
+}
+
+fn plan_request() -> Value {
+    json!({
+        "title": "Test Plan",
+        "net_amount": "100",
+        "fee": "2",
+        "currency_preference": "USDC",
+        "two_fa_code": "123456"
+    })
+}
+
 #[tokio::test]
 async fn kyc_pending_forbidden() {
     let ctx = match TestContext::from_env().await {
         Some(ctx) => ctx,
         None => return,
     };
-    // Create a user with pending KYC (default)
-    let user_id = Uuid::new_v4();
     // No KYC approval or rejection, so status is pending
-    // Try to access a protected endpoint (e.g., create_plan)
-    let req = Request::builder()
-        .method("POST")
-        .uri("/api/plans")
-        .header("Content-Type", "application/json")
-        .header("X-User-Id", user_id.to_string())
-        .body(Body::from(
-            serde_json::to_string(&json!({
-                "title": "Test Plan",
-                "net_amount": 100,
-                "fee": 2
-            }))
-            .unwrap(),
-        ))
-        .unwrap();
-    let resp = ctx.app.clone().oneshot(req).await.expect("request failed");
+    let (_, token) = pending_user(&ctx).await;
+    let resp = helpers::send(&ctx, "POST", "/api/plans", &token, Some(plan_request())).await;
     assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    let body = helpers::json_body(resp).await;
+    assert!(body["error"].as_str().unwrap().contains("KYC"), "{body}");
 }
 
 #[tokio::test]
@@ -44,40 +47,20 @@ async fn kyc_rejected_forbidden() {
         Some(ctx) => ctx,
         None => return,
     };
-    let user_id = Uuid::new_v4();
+    let (user_id, token) = pending_user(&ctx).await;
     // Set KYC to rejected
-    let admin_id = Uuid::new_v4();
-    let req_reject = Request::builder()
-        .method("POST")
-        .uri("/api/admin/kyc/reject")
-        .header("Content-Type", "application/json")
-        .header("X-Admin-Id", admin_id.to_string())
-        .body(Body::from(
-            serde_json::to_string(&json!({ "user_id": user_id })).unwrap(),
-        ))
-        .unwrap();
-    let _ = ctx
-        .app
-        .clone()
-        .oneshot(req_reject)
-        .await
-        .expect("reject failed");
+    let (_, reviewer) = helpers::admin_with_role(&ctx, "kyc_reviewer").await;
+    let resp = helpers::send(
+        &ctx,
+        "POST",
+        "/api/admin/kyc/reject",
+        &reviewer,
+        Some(json!({ "user_id": user_id })),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
     // Try to access a protected endpoint
-    let req = Request::builder()
-        .method("POST")
-        .uri("/api/plans")
-        .header("Content-Type", "application/json")
-        .header("X-User-Id", user_id.to_string())
-        .body(Body::from(
-            serde_json::to_string(&json!({
-                "title": "Test Plan",
-                "net_amount": 100,
-                "fee": 2
-            }))
-            .unwrap(),
-        ))
-        .unwrap();
-    let resp = ctx.app.clone().oneshot(req).await.expect("request failed");
+    let resp = helpers::send(&ctx, "POST", "/api/plans", &token, Some(plan_request())).await;
     assert_eq!(resp.status(), StatusCode::FORBIDDEN);
 }
 
@@ -87,40 +70,33 @@ async fn kyc_approved_success() {
         Some(ctx) => ctx,
         None => return,
     };
-    let user_id = Uuid::new_v4();
-    let admin_id = Uuid::new_v4();
-    // Approve KYC
-    let req_approve = Request::builder()
-        .method("POST")
-        .uri("/api/admin/kyc/approve")
-        .header("Content-Type", "application/json")
-        .header("X-Admin-Id", admin_id.to_string())
-        .body(Body::from(
-            serde_json::to_string(&json!({ "user_id": user_id })).unwrap(),
-        ))
-        .unwrap();
-    let _ = ctx
-        .app
-        .clone()
-        .oneshot(req_approve)
-        .await
-        .expect("approve failed");
+    let (user_id, token) = pending_user(&ctx).await;
+    // Approve KYC, which takes a second reviewer
+    let (_, proposer) = helpers::admin_with_role(&ctx, "kyc_reviewer").await;
+    let (_, approver) = helpers::admin_with_role(&ctx, "kyc_reviewer").await;
+    let resp = helpers::send(
+        &ctx,
+        "POST",
+        "/api/admin/kyc/approve",
+        &proposer,
+        Some(json!({ "user_id": user_id })),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+    let action_id = helpers::json_body(resp).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let resp = helpers::send(
+        &ctx,
+        "POST",
+        &format!("/api/admin/pending-actions/{action_id}/approve"),
+        &approver,
+        None,
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
     // Try to access a protected endpoint
-    let req = Request::builder()
-        .method("POST")
-        .uri("/api/plans")
-        .header("Content-Type", "application/json")
-        .header("X-User-Id", user_id.to_string())
-        .body(Body::from(
-            serde_json::to_string(&json!({
-                "title": "Test Plan",
-                "net_amount": 100,
-                "fee": 2
-            }))
-            .unwrap(),
-        ))
-        .unwrap();
-    let resp = ctx.app.clone().oneshot(req).await.expect("request failed");
+    let resp = helpers::send(&ctx, "POST", "/api/plans", &token, Some(plan_request())).await;
     assert_eq!(resp.status(), StatusCode::OK);
 }
-*/
@@ -0,0 +1,451 @@
+//! Integration tests for legacy message delivery channels.
+//!
+//! Email is delivered to an in-process SMTP sink and webhooks to an in-process
+//! receiver, so no external services are needed.
+
+mod helpers;
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{HeaderMap, Request, Response, StatusCode},
+    routing::post,
+    Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use inheritx_backend::message_delivery::{
+    MessageChannels, SmtpEmailTransport, MAX_DELIVERY_ATTEMPTS,
+};
+use inheritx_backend::secure_messages::LegacyMessageDeliveryService;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+const PUBLIC_URL: &str = "http://inheritx.test";
+
+/// An email accepted by the SMTP sink.
+struct SunkEmail {
+    recipients: Vec<String>,
+    data: String,
+}
+
+/// Start a minimal SMTP server that accepts and records every message.
+async fn start_smtp_sink() -> (String, Arc<Mutex<Vec<SunkEmail>>>) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let inbox = Arc::new(Mutex::new(Vec::new()));
+    let sink = inbox.clone();
+
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            let sink = sink.clone();
+            tokio::spawn(async move {
+                let (reader, mut writer) = socket.into_split();
+                let mut lines = BufReader::new(reader).lines();
+                writer.write_all(b"220 sink ESMTP\r\n").await.unwrap();
+                let mut recipients = Vec::new();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let command = line.to_ascii_uppercase();
+                    let reply = if command.starts_with("EHLO") || command.starts_with("HELO") {
+                        "250 sink\r\n".to_string()
+                    } else if command.starts_with("RCPT TO:") {
+                        recipients
+                            .push(line[8..].trim_matches(|c| c == '<' || c == '>').to_string());
+                        "250 OK\r\n".to_string()
+                    } else if command == "DATA" {
+                        writer
+                            .write_all(b"354 End data with <CR><LF>.<CR><LF>\r\n")
+                            .await
+                            .unwrap();
+                        let mut data = String::new();
+                        while let Ok(Some(line)) = lines.next_line().await {
+                            if line == "." {
+                                break;
+                            }
+                            data.push_str(&line);
+                            data.push('\n');
+                        }
+                        let mut sink = sink.lock().unwrap();
+                        sink.push(SunkEmail {
+                            recipients: std::mem::take(&mut recipients),
+                            data,
+                        });
+                        format!("250 2.0.0 queued as {}\r\n", sink.len())
+                    } else if command == "QUIT" {
+                        writer.write_all(b"221 bye\r\n").await.unwrap();
+                        break;
+                    } else {
+                        "250 OK\r\n".to_string()
+                    };
+                    writer.write_all(reply.as_bytes()).await.unwrap();
+                }
+            });
+        }
+    });
+
+    (addr.to_string(), inbox)
+}
+
+#[derive(Default)]
+struct WebhookReceiver {
+    calls: AtomicUsize,
+    accepted: Mutex<Vec<(HeaderMap, String)>>,
+}
+
+/// Start a webhook receiver that is unavailable for its first call.
+async fn start_flaky_webhook() -> (String, Arc<WebhookReceiver>) {
+    async fn receive(
+        State(receiver): State<Arc<WebhookReceiver>>,
+        headers: HeaderMap,
+        body: String,
+    ) -> StatusCode {
+        if receiver.calls.fetch_add(1, Ordering::SeqCst) == 0 {
+            return StatusCode::SERVICE_UNAVAILABLE;
+        }
+        receiver.accepted.lock().unwrap().push((headers, body));
+        StatusCode::NO_CONTENT
+    }
+
+    let receiver = Arc::new(WebhookReceiver::default());
+    let app = Router::new()
+        .route("/hook", post(receive))
+        .with_state(receiver.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    (format!("http://{addr}/hook"), receiver)
+}
+
+async fn get(ctx: &helpers::TestContext, uri: &str, token: Option<&str>) -> Response<Body> {
+    let mut request = Request::builder().uri(uri);
+    if let Some(token) = token {
+        request = request.header("Authorization", format!("Bearer {token}"));
+    }
+    ctx.app
+        .clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+async fn new_user(ctx: &helpers::TestContext) -> (Uuid, String, String) {
+    let email = format!("{}@delivery.test", Uuid::new_v4());
+    let user_id = helpers::create_test_user(&ctx.pool, &email).await.unwrap();
+    let token = helpers::user_token(user_id, &email);
+    (user_id, email, token)
+}
+
+/// Create a legacy message through the API and unlock it.
+async fn create_unlocked_message(
+    ctx: &helpers::TestContext,
+    token: &str,
+    channel: &str,
+    contact: &str,
+    message: &str,
+) -> Uuid {
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/messages/legacy")
+                .header("Authorization", format!("Bearer {token}"))
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    json!({
+                        "beneficiary_contact": contact,
+                        "message": message,
+                        "unlock_at": Utc::now() + Duration::days(1),
+                        "delivery_channel": channel,
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let created = helpers::json_body(response).await;
+    assert_eq!(created["data"]["delivery_channel"], channel);
+    let message_id = Uuid::parse_str(created["data"]["id"].as_str().unwrap()).unwrap();
+
+    sqlx::query("UPDATE legacy_messages SET unlock_at = NOW() - INTERVAL '1 minute' WHERE id = $1")
+        .bind(message_id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    message_id
+}
+
+async fn audit_actions(ctx: &helpers::TestContext, message_id: Uuid) -> Vec<(String, Value)> {
+    sqlx::query_as(
+        "SELECT action, metadata FROM message_access_logs \
+         WHERE message_id = $1 ORDER BY created_at",
+    )
+    .bind(message_id)
+    .fetch_all(&ctx.pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn email_delivery_reaches_smtp_sink_and_confirms_reads() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let (_, _, token) = new_user(&ctx).await;
+    let (smtp_addr, sink) = start_smtp_sink().await;
+    let transport = SmtpEmailTransport::new(
+        &format!("smtp://{smtp_addr}"),
+        "InheritX <no-reply@inheritx.test>",
+    )
+    .unwrap();
+    let channels = Arc::new(MessageChannels::new(Some(Arc::new(transport)), PUBLIC_URL));
+
+    let contact = format!("heir-{}@example.com", Uuid::new_v4());
+    let plaintext = format!("The deeds are in the blue folder {}", Uuid::new_v4());
+    let message_id = create_unlocked_message(&ctx, &token, "email", &contact, &plaintext).await;
+
+    let message = LegacyMessageDeliveryService::new(ctx.pool.clone(), channels)
+        .deliver_now(message_id)
+        .await
+        .unwrap();
+    assert_eq!(message.status, "delivered");
+    assert_eq!(message.delivery_attempts, 1);
+
+    let (recipients, data) = {
+        let sink = sink.lock().unwrap();
+        assert_eq!(sink.len(), 1);
+        (sink[0].recipients.clone(), sink[0].data.clone())
+    };
+    assert_eq!(recipients, vec![contact.clone()]);
+    // Long lines arrive quoted-printable; undo soft breaks and `=3D`.
+    let data = data.replace("=\n", "").replace("=3D", "=");
+    assert!(data.contains(&plaintext));
+
+    let (delivery_id, receipt, payload): (Uuid, String, Option<String>) = sqlx::query_as(
+        "SELECT id, receipt, decrypted_payload FROM legacy_message_deliveries \
+         WHERE message_id = $1 AND channel = 'email'",
+    )
+    .bind(message_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert!(receipt.starts_with("250"));
+    assert!(payload.is_none());
+
+    let link = format!("{PUBLIC_URL}/api/messages/deliveries/{delivery_id}/read?token=");
+    let start = data.find(&link).expect("email carries a read link") + link.len();
+    let receipt_token = &data[start..start + 64];
+
+    let read_uri = format!("/api/messages/deliveries/{delivery_id}/read");
+    let response = get(&ctx, &format!("{read_uri}?token={}", "0".repeat(64)), None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = get(&ctx, &format!("{read_uri}?token={receipt_token}"), None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let read_at = helpers::json_body(response).await["data"]["read_at"].clone();
+    assert!(read_at.is_string());
+
+    // Confirming again is harmless and keeps the first read time.
+    let response = get(&ctx, &format!("{read_uri}?token={receipt_token}"), None).await;
+    assert_eq!(
+        helpers::json_body(response).await["data"]["read_at"],
+        read_at
+    );
+
+    let actions = audit_actions(&ctx, message_id).await;
+    let delivered: Vec<_> = actions.iter().filter(|(a, _)| a == "delivered").collect();
+    assert_eq!(delivered.len(), 1);
+    assert_eq!(delivered[0].1["channel"], "email");
+    assert_eq!(delivered[0].1["receipt"], receipt);
+    assert_eq!(actions.iter().filter(|(a, _)| a == "read").count(), 1);
+}
+
+#[tokio::test]
+async fn failed_webhook_is_retried_with_backoff() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let (_, _, token) = new_user(&ctx).await;
+    let (hook_url, receiver) = start_flaky_webhook().await;
+    let channels = Arc::new(
+        MessageChannels::new(None, PUBLIC_URL).with_webhook_secret(b"webhook-test-secret"),
+    );
+    let service = LegacyMessageDeliveryService::new(ctx.pool.clone(), channels);
+
+    let plaintext = format!("Call the solicitor {}", Uuid::new_v4());
+    let message_id = create_unlocked_message(&ctx, &token, "webhook", &hook_url, &plaintext).await;
+
+    let message = service.deliver_now(message_id).await.unwrap();
+    assert_eq!(message.status, "pending");
+    assert_eq!(message.delivery_attempts, 1);
+    let retry_in = message.next_attempt_at.unwrap() - Utc::now();
+    assert!(retry_in > Duration::seconds(50) && retry_in <= Duration::seconds(60));
+
+    let message = service.deliver_now(message_id).await.unwrap();
+    assert_eq!(message.status, "delivered");
+    assert_eq!(message.delivery_attempts, 2);
+    assert!(message.next_attempt_at.is_none());
+
+    let (headers, body) = receiver.accepted.lock().unwrap().pop().unwrap();
+    let signature = headers["x-inheritx-signature"].to_str().unwrap();
+    let key = ring::hmac::Key::new(ring::hmac::HMAC_SHA256, b"webhook-test-secret");
+    ring::hmac::verify(
+        &key,
+        body.as_bytes(),
+        &hex::decode(signature.strip_prefix("sha256=").unwrap()).unwrap(),
+    )
+    .unwrap();
+    let payload: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(payload["message"], plaintext);
+    assert!(payload["read_confirmation_url"]
+        .as_str()
+        .unwrap()
+        .starts_with(PUBLIC_URL));
+
+    let actions = audit_actions(&ctx, message_id).await;
+    let failures: Vec<_> = actions
+        .iter()
+        .filter(|(a, _)| a == "delivery_failed")
+        .collect();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].1["attempt"], 1);
+    let next_attempt_at: DateTime<Utc> =
+        serde_json::from_value(failures[0].1["next_attempt_at"].clone()).unwrap();
+    assert!(next_attempt_at > Utc::now());
+    assert_eq!(actions.iter().filter(|(a, _)| a == "delivered").count(), 1);
+}
+
+#[tokio::test]
+async fn delivery_gives_up_after_the_last_attempt() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let (_, _, token) = new_user(&ctx).await;
+    let admin_email = format!("{}@delivery-admin.test", Uuid::new_v4());
+    let admin_id = helpers::create_test_admin(&ctx.pool, &admin_email)
+        .await
+        .unwrap();
+    let admin_token = helpers::admin_token(admin_id, &admin_email);
+
+    // Nothing listens on a released port.
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let hook_url = format!("http://{}/hook", listener.local_addr().unwrap());
+    drop(listener);
+
+    let message_id =
+        create_unlocked_message(&ctx, &token, "webhook", &hook_url, "never arrives").await;
+    sqlx::query("UPDATE legacy_messages SET delivery_attempts = $2 WHERE id = $1")
+        .bind(message_id)
+        .bind(MAX_DELIVERY_ATTEMPTS - 1)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/api/admin/messages/legacy/{message_id}/deliver"))
+                .header("Authorization", format!("Bearer {admin_token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let message = helpers::json_body(response).await;
+    assert_eq!(message["data"]["status"], "failed");
+    assert_eq!(message["data"]["delivery_attempts"], MAX_DELIVERY_ATTEMPTS);
+    assert!(message["data"]["next_attempt_at"].is_null());
+}
+
+#[tokio::test]
+async fn inbox_delivery_is_visible_only_to_the_beneficiary() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let (_, _, owner_token) = new_user(&ctx).await;
+    let (beneficiary_id, beneficiary_email, beneficiary_token) = new_user(&ctx).await;
+    let (_, _, stranger_token) = new_user(&ctx).await;
+
+    // Webhook deliveries need a URL, not an email address.
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/messages/legacy")
+                .header("Authorization", format!("Bearer {owner_token}"))
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    json!({
+                        "beneficiary_contact": beneficiary_email,
+                        "message": "misrouted",
+                        "unlock_at": Utc::now() + Duration::days(1),
+                        "delivery_channel": "webhook",
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let plaintext = format!("With love {}", Uuid::new_v4());
+    let message_id = create_unlocked_message(
+        &ctx,
+        &owner_token,
+        "inbox",
+        &beneficiary_email.to_uppercase(),
+        &plaintext,
+    )
+    .await;
+    let channels = Arc::new(MessageChannels::new(None, PUBLIC_URL));
+    LegacyMessageDeliveryService::new(ctx.pool.clone(), channels)
+        .deliver_now(message_id)
+        .await
+        .unwrap();
+
+    let inbox =
+        helpers::json_body(get(&ctx, "/api/messages/inbox", Some(&beneficiary_token)).await).await;
+    assert_eq!(inbox["count"], 1);
+    assert_eq!(inbox["data"][0]["message"], plaintext);
+    assert!(inbox["data"][0]["read_at"].is_null());
+    let delivery_id = inbox["data"][0]["delivery_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let inbox =
+        helpers::json_body(get(&ctx, "/api/messages/inbox", Some(&stranger_token)).await).await;
+    assert_eq!(inbox["count"], 0);
+    let uri = format!("/api/messages/inbox/{delivery_id}");
+    let response = get(&ctx, &uri, Some(&stranger_token)).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = get(&ctx, &uri, Some(&beneficiary_token)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(helpers::json_body(response).await["data"]["read_at"].is_string());
+
+    let read_by: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT user_id FROM message_access_logs WHERE message_id = $1 AND action = 'read'",
+    )
+    .bind(message_id)
+    .fetch_all(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(read_by, vec![beneficiary_id]);
+}
@@ -0,0 +1,194 @@
+//! Integration tests for scheduled posthumous legacy messages.
+
+mod helpers;
+
+use axum::http::StatusCode;
+use chrono::{DateTime, Duration, Utc};
+use inheritx_backend::message_delivery::MessageChannels;
+use inheritx_backend::message_schedule::MessageScheduleService;
+use inheritx_backend::secure_messages::LegacyMessageDeliveryService;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+async fn create_message(ctx: &helpers::TestContext, token: &str, body: Value) -> Uuid {
+    let response = helpers::send(ctx, "POST", "/api/messages/legacy", token, Some(body)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let created = helpers::json_body(response).await;
+    Uuid::parse_str(created["data"]["id"].as_str().unwrap()).unwrap()
+}
+
+async fn message_state(
+    ctx: &helpers::TestContext,
+    message_id: Uuid,
+) -> (String, DateTime<Utc>, i32) {
+    sqlx::query_as(
+        "SELECT status, unlock_at, occurrences_delivered FROM legacy_messages WHERE id = $1",
+    )
+    .bind(message_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap()
+}
+
+async fn timeline(ctx: &helpers::TestContext, token: &str) -> Vec<Value> {
+    let response = helpers::send(ctx, "GET", "/api/messages/legacy/timeline", token, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    helpers::json_body(response).await["data"]
+        .as_array()
+        .unwrap()
+        .clone()
+}
+
+fn entry(timeline: &[Value], message_id: Uuid) -> &Value {
+    timeline
+        .iter()
+        .find(|e| e["message_id"] == message_id.to_string())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn scheduled_messages_wait_for_death_verification() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let email = format!("{}@schedule.test", Uuid::new_v4());
+    let user_id = helpers::create_test_user(&ctx.pool, &email).await.unwrap();
+    let token = helpers::user_token(user_id, &email);
+    let admin_email = format!("{}@admin.test", Uuid::new_v4());
+    let admin_id = helpers::create_test_admin(&ctx.pool, &admin_email)
+        .await
+        .unwrap();
+    let admin_token = helpers::admin_token(admin_id, &admin_email);
+
+    // Exactly one of unlock_at and schedule is required.
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/messages/legacy",
+        &token,
+        Some(json!({ "beneficiary_contact": "heir@example.com", "message": "hi" })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // Whole seconds, so the value survives Postgres' microsecond precision.
+    let birthday =
+        DateTime::<Utc>::from_timestamp((Utc::now() + Duration::days(3650)).timestamp(), 0)
+            .unwrap();
+    let on_date = create_message(
+        &ctx,
+        &token,
+        json!({
+            "beneficiary_contact": "heir@example.com",
+            "delivery_channel": "inbox",
+            "message": "Happy 21st birthday",
+            "schedule": { "type": "on_date", "at": birthday },
+        }),
+    )
+    .await;
+    let after_death = create_message(
+        &ctx,
+        &token,
+        json!({
+            "beneficiary_contact": "heir@example.com",
+            "delivery_channel": "inbox",
+            "message": "Read this first",
+            "schedule": { "type": "after_death", "days": 0 },
+        }),
+    )
+    .await;
+    let annual = create_message(
+        &ctx,
+        &token,
+        json!({
+            "beneficiary_contact": "heir@example.com",
+            "delivery_channel": "inbox",
+            "message": "Thinking of you on our anniversary",
+            "schedule": { "type": "annually", "month": 6, "day": 1, "occurrences": 2 },
+        }),
+    )
+    .await;
+
+    let before = timeline(&ctx, &token).await;
+    assert_eq!(before.len(), 3);
+    assert_eq!(
+        entry(&before, on_date)["next_delivery_at"]
+            .as_str()
+            .unwrap()
+            .parse::<DateTime<Utc>>()
+            .unwrap(),
+        birthday
+    );
+    assert!(entry(&before, after_death)["next_delivery_at"].is_null());
+    assert_eq!(entry(&before, annual)["awaiting_death_verification"], true);
+
+    // Nothing is queued until the death is verified.
+    MessageScheduleService::activate_scheduled(&ctx.pool)
+        .await
+        .unwrap();
+    assert_eq!(message_state(&ctx, after_death).await.0, "scheduled");
+
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &format!("/api/admin/users/{user_id}/death-verification"),
+        &admin_token,
+        Some(json!({ "evidence": "Death certificate #42" })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    MessageScheduleService::activate_scheduled(&ctx.pool)
+        .await
+        .unwrap();
+
+    let (status, unlock_at, _) = message_state(&ctx, after_death).await;
+    assert_eq!(status, "pending");
+    assert!(unlock_at <= Utc::now());
+    let (status, unlock_at, _) = message_state(&ctx, on_date).await;
+    assert_eq!(status, "pending");
+    assert_eq!(unlock_at, birthday);
+    let (status, first_anniversary, _) = message_state(&ctx, annual).await;
+    assert_eq!(status, "pending");
+    assert!(first_anniversary > Utc::now());
+
+    let after = timeline(&ctx, &token).await;
+    let annual_entry = entry(&after, annual);
+    assert_eq!(annual_entry["awaiting_death_verification"], false);
+    assert_eq!(annual_entry["upcoming"].as_array().unwrap().len(), 1);
+
+    let delivery = LegacyMessageDeliveryService::new(
+        ctx.pool.clone(),
+        Arc::new(MessageChannels::new(None, "http://localhost:3000")),
+    );
+    let message = delivery.deliver_now(after_death).await.unwrap();
+    assert_eq!(message.status, "delivered");
+
+    // A recurring message returns to pending with the next anniversary until
+    // its occurrences are used up.
+    sqlx::query("UPDATE legacy_messages SET unlock_at = NOW() - INTERVAL '1 minute' WHERE id = $1")
+        .bind(annual)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    let message = delivery.deliver_now(annual).await.unwrap();
+    assert_eq!(message.status, "pending");
+    let (_, next, delivered) = message_state(&ctx, annual).await;
+    assert_eq!(delivered, 1);
+    assert!(next > first_anniversary + Duration::days(364));
+
+    sqlx::query("UPDATE legacy_messages SET unlock_at = NOW() - INTERVAL '1 minute' WHERE id = $1")
+        .bind(annual)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    let message = delivery.deliver_now(annual).await.unwrap();
+    assert_eq!(message.status, "delivered");
+    let deliveries: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM legacy_message_deliveries WHERE message_id = $1")
+            .bind(annual)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert_eq!(deliveries, 2);
+}
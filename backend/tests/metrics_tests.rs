@@ -0,0 +1,91 @@
+//! Integration tests for the Prometheus `/metrics` endpoint.
+
+mod helpers;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use tower::ServiceExt;
+
+async fn get(ctx: &helpers::TestContext, uri: &str) -> (StatusCode, String, String) {
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (
+        status,
+        content_type,
+        String::from_utf8(bytes.to_vec()).unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn metrics_report_requests_by_matched_route() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+
+    let (status, _, _) = get(&ctx, "/api/openapi.json").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, content_type, body) = get(&ctx, "/metrics").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(content_type.starts_with("text/plain"), "{content_type}");
+    assert!(
+        body.contains(
+            r#"inheritx_http_requests_total{method="GET",route="/api/openapi.json",status="200"}"#
+        ),
+        "{body}"
+    );
+    assert!(body.contains(
+        r#"inheritx_http_request_duration_seconds_bucket{method="GET",route="/api/openapi.json",status="200""#
+    ));
+}
+
+#[tokio::test]
+async fn metrics_report_pool_and_domain_gauges() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+
+    let (status, _, body) = get(&ctx, "/metrics").await;
+    assert_eq!(status, StatusCode::OK);
+    for metric in [
+        r#"inheritx_db_pool_connections{state="idle"}"#,
+        r#"inheritx_db_pool_connections{state="in_use"}"#,
+        "inheritx_db_pool_max_connections ",
+        "inheritx_active_plans ",
+        "inheritx_active_loans ",
+        "inheritx_total_value_locked ",
+        // Seeded by the insurance fund migration.
+        r#"inheritx_insurance_coverage_ratio{fund="Platform Reserve Fund"}"#,
+    ] {
+        assert!(body.contains(metric), "missing {metric} in:\n{body}");
+    }
+}
+
+#[tokio::test]
+async fn unmatched_requests_share_one_route_label() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+
+    let (status, _, _) = get(&ctx, "/api/no-such-route/12345").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (_, _, body) = get(&ctx, "/metrics").await;
+    assert!(!body.contains("no-such-route"));
+}
@@ -0,0 +1,256 @@
+//! Integration tests for remote notarization of will documents.
+
+mod helpers;
+
+use axum::http::StatusCode;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{Duration, Utc};
+use inheritx_backend::notarization::seal_message;
+use inheritx_backend::will_pdf::{
+    BeneficiaryEntry, WillDocumentInput, WillPdfService, WillTemplate,
+};
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+fn seal_key() -> (Ed25519KeyPair, String) {
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+    let key = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+    let public_key: [u8; 32] = key.public_key().as_ref().try_into().unwrap();
+    let address = stellar_strkey::ed25519::PublicKey(public_key)
+        .to_string()
+        .as_str()
+        .to_string();
+    (key, address)
+}
+
+fn eu_will(plan_id: Uuid) -> WillDocumentInput {
+    WillDocumentInput {
+        plan_id,
+        owner_name: "Ada Testator".to_string(),
+        owner_wallet: "GABC".to_string(),
+        vault_id: "vault-1".to_string(),
+        beneficiaries: vec![BeneficiaryEntry {
+            name: "Heir".to_string(),
+            wallet_address: "GHEIR".to_string(),
+            allocation_percent: Decimal::new(100, 0),
+            relationship: Some("child".to_string()),
+        }],
+        execution_rules: Some("Distribute on death".to_string()),
+        template: WillTemplate::Formal,
+        jurisdiction: Some("EU".to_string()),
+        will_hash_reference: None,
+    }
+}
+
+async fn journal_verification(
+    ctx: &helpers::TestContext,
+    admin_token: &str,
+    notary_id: &str,
+) -> Value {
+    let response = helpers::send(
+        ctx,
+        "GET",
+        &format!("/api/admin/notaries/{notary_id}/journal/verify"),
+        admin_token,
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    helpers::json_body(response).await["data"].clone()
+}
+
+#[tokio::test]
+async fn notary_seals_will_and_journal_detects_tampering() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let email = format!("{}@testator.test", Uuid::new_v4());
+    let testator_id = helpers::create_test_user(&ctx.pool, &email).await.unwrap();
+    let token = helpers::user_token(testator_id, &email);
+    let notary_email = format!("{}@notary.test", Uuid::new_v4());
+    let notary_user_id = helpers::create_test_user(&ctx.pool, &notary_email)
+        .await
+        .unwrap();
+    let notary_token = helpers::user_token(notary_user_id, &notary_email);
+    let admin_email = format!("{}@admin.test", Uuid::new_v4());
+    let admin_id = helpers::create_test_admin(&ctx.pool, &admin_email)
+        .await
+        .unwrap();
+    let admin_token = helpers::admin_token(admin_id, &admin_email);
+
+    let plan_id = helpers::create_test_plan(&ctx.pool, testator_id)
+        .await
+        .unwrap();
+    let will = eu_will(plan_id);
+    let document = WillPdfService::generate(&ctx.pool, testator_id, &will)
+        .await
+        .unwrap();
+
+    // Commission the notary.
+    let (key, seal_address) = seal_key();
+    let commission_number = format!("EU-{}", Uuid::new_v4());
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/admin/notaries",
+        &admin_token,
+        Some(json!({
+            "user_id": notary_user_id,
+            "full_name": "Nora Notary",
+            "commission_number": commission_number,
+            "commission_jurisdiction": "eu",
+            "commission_expires_at": Utc::now() + Duration::days(365),
+            "seal_stellar_address": seal_address,
+        })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let notary = helpers::json_body(response).await["data"].clone();
+    assert_eq!(notary["commission_jurisdiction"], "EU");
+    let notary_id = notary["id"].as_str().unwrap().to_string();
+
+    // Without notarization the EU will fails validation.
+    let mut validate = serde_json::to_value(&will).unwrap();
+    validate["witness_count"] = json!(2);
+    validate["document_id"] = json!(document.document_id);
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/will/validate",
+        &token,
+        Some(validate.clone()),
+    )
+    .await;
+    let result = helpers::json_body(response).await;
+    assert_eq!(result["data"]["is_valid"], false);
+    assert!(result["data"]["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|e| e["field"] == "notarization"));
+
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &format!("/api/will/documents/{}/notarizations", document.document_id),
+        &token,
+        Some(json!({ "notary_id": notary_id })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let session_id = helpers::json_body(response).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // Sealing before the identity check is refused.
+    let seal = |signature: String| json!({ "signature": signature });
+    let statement = seal_message(
+        session_id.parse().unwrap(),
+        &document.will_hash,
+        &commission_number,
+    );
+    let signature = hex::encode(key.sign(statement.as_bytes()).as_ref());
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &format!("/api/notary/sessions/{session_id}/seal"),
+        &notary_token,
+        Some(seal(signature.clone())),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // Only the assigned notary can act on the session.
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &format!("/api/notary/sessions/{session_id}/identity"),
+        &token,
+        Some(json!({ "method": "video_id_check", "evidence": "passport 123" })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &format!("/api/notary/sessions/{session_id}/identity"),
+        &notary_token,
+        Some(json!({ "method": "video_id_check", "evidence": "passport 123" })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        helpers::json_body(response).await["data"]["status"],
+        "identity_verified"
+    );
+
+    // A seal over a different statement is rejected.
+    let forged = hex::encode(key.sign(b"something else").as_ref());
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &format!("/api/notary/sessions/{session_id}/seal"),
+        &notary_token,
+        Some(seal(forged)),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        &format!("/api/notary/sessions/{session_id}/seal"),
+        &notary_token,
+        Some(seal(signature.clone())),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let sealed = helpers::json_body(response).await["data"].clone();
+    assert_eq!(sealed["status"], "sealed");
+    assert_eq!(sealed["certificate"]["will_hash"], document.will_hash);
+    assert_eq!(sealed["certificate"]["seal_signature"], signature);
+
+    // The certificate is embedded into the stored will document.
+    let stored = WillPdfService::get_document(&ctx.pool, document.document_id, testator_id)
+        .await
+        .unwrap();
+    let pdf = lopdf::Document::load_mem(&BASE64.decode(stored.pdf_base64).unwrap()).unwrap();
+    assert_eq!(pdf.get_pages().len(), 2);
+    assert!(pdf.extract_text(&[2]).unwrap().contains(&commission_number));
+
+    let response = helpers::send(&ctx, "POST", "/api/will/validate", &token, Some(validate)).await;
+    assert_eq!(helpers::json_body(response).await["data"]["is_valid"], true);
+
+    let events: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM will_event_log WHERE document_id = $1 AND event_type = 'will_notarized'",
+    )
+    .bind(document.document_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(events, 1);
+
+    // commissioned, session_requested, identity_verified, seal_applied
+    let response = helpers::send(&ctx, "GET", "/api/notary/journal", &notary_token, None).await;
+    assert_eq!(helpers::json_body(response).await["count"], 4);
+    let verification = journal_verification(&ctx, &admin_token, &notary_id).await;
+    assert_eq!(verification["valid"], true);
+    assert_eq!(verification["entries"], 4);
+
+    sqlx::query(
+        "UPDATE notary_journal_entries SET details = '{\"method\": \"none\"}' \
+         WHERE notary_id = $1 AND sequence = 3",
+    )
+    .bind(Uuid::parse_str(&notary_id).unwrap())
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    let verification = journal_verification(&ctx, &admin_token, &notary_id).await;
+    assert_eq!(verification["valid"], false);
+    assert_eq!(verification["first_invalid_sequence"], 3);
+}
@@ -0,0 +1,225 @@
+//! Integration tests for the served OpenAPI document.
+
+mod helpers;
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{header, Request, StatusCode},
+};
+use serde_json::Value;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn openapi_document_is_served_without_auth() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/api/openapi.json")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let document: Value = serde_json::from_slice(&bytes).unwrap();
+
+    assert_eq!(document["openapi"], "3.1.0");
+    let claim = &document["paths"]["/api/plans/{plan_id}/claim"]["post"];
+    assert_eq!(
+        claim["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/ClaimPlanRequest"
+    );
+    assert!(
+        document["components"]["schemas"]["ClaimPlanRequest"]["properties"]
+            .get("beneficiary_email")
+            .is_some()
+    );
+    assert!(document["paths"]["/api/openapi.json"]["get"].is_object());
+}
+
+const METHODS: [&str; 5] = ["get", "post", "put", "patch", "delete"];
+
+static NEXT_PEER: AtomicU32 = AtomicU32::new(1);
+
+/// Send a request without a body. Login routes are rate limited per peer
+/// address, so each request comes from a fresh one.
+async fn probe(
+    ctx: &helpers::TestContext,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+) -> (StatusCode, Option<String>) {
+    let [_, _, hi, lo] = NEXT_PEER.fetch_add(1, Ordering::Relaxed).to_be_bytes();
+    let request = helpers::request(&method.to_uppercase(), uri, token)
+        .extension(ConnectInfo(SocketAddr::from(([10, 0, hi, lo], 4000))));
+    let response = helpers::dispatch(ctx, request, None).await;
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let error = serde_json::from_slice::<Value>(&bytes)
+        .ok()
+        .and_then(|body| body["error"].as_str().map(str::to_string));
+    (status, error)
+}
+
+/// A value the schema accepts, as it would appear in a path or query string.
+fn sample(schema: &Value, schemas: &Value) -> String {
+    if let Some(name) = schema["$ref"]
+        .as_str()
+        .and_then(|r| r.strip_prefix("#/components/schemas/"))
+    {
+        return sample(&schemas[name], schemas);
+    }
+    if let Some(value) = schema["enum"].get(0).or(schema.get("const")) {
+        return value
+            .as_str()
+            .map_or_else(|| value.to_string(), str::to_string);
+    }
+    if let Some(first) = schema["oneOf"].get(0).or(schema["anyOf"].get(0)) {
+        return sample(first, schemas);
+    }
+    let ty = match &schema["type"] {
+        Value::Array(types) => types.iter().find(|t| *t != "null").cloned(),
+        ty => Some(ty.clone()),
+    };
+    match (
+        ty.as_ref().and_then(Value::as_str),
+        schema["format"].as_str(),
+    ) {
+        (Some("integer" | "number"), _) => "1".to_string(),
+        (Some("boolean"), _) => "true".to_string(),
+        (_, Some("uuid")) => Uuid::nil().to_string(),
+        (_, Some("date-time")) => "2024-01-01T00:00:00Z".to_string(),
+        (_, Some("date")) => "2024-01-01".to_string(),
+        _ => "x".to_string(),
+    }
+}
+
+/// The operation's path with sample path parameters and required query
+/// parameters filled in.
+fn sample_uri(path: &str, operation: &Value, schemas: &Value) -> String {
+    let mut uri = path.to_string();
+    let mut query = Vec::new();
+    for param in operation["parameters"].as_array().into_iter().flatten() {
+        let name = param["name"].as_str().unwrap();
+        let value = sample(&param["schema"], schemas);
+        match param["in"].as_str() {
+            Some("path") => uri = uri.replace(&format!("{{{name}}}"), &value),
+            Some("query") if param["required"] == true => query.push(format!("{name}={value}")),
+            _ => {}
+        }
+    }
+    if !query.is_empty() {
+        uri = format!("{uri}?{}", query.join("&"));
+    }
+    uri
+}
+
+/// Checks the documented security of every operation against the extractor
+/// its handler actually uses, and that the router serves exactly the
+/// documented methods on every documented path.
+#[tokio::test]
+async fn documented_operations_match_the_router() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let (_, _, user) = helpers::user(&ctx, "openapi.test").await;
+    let (_, admin) = helpers::admin(&ctx).await;
+    // A role without permissions, so every permission check fails.
+    sqlx::query("INSERT INTO admin_roles (name) VALUES ('openapi_probe') ON CONFLICT DO NOTHING")
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    let (_, unprivileged) = helpers::admin_with_role(&ctx, "openapi_probe").await;
+
+    let document = inheritx_backend::openapi::build_document();
+    let schemas = &document["components"]["schemas"];
+    let mut mismatches = Vec::new();
+    for (path, item) in document["paths"].as_object().unwrap() {
+        for method in METHODS {
+            let Some(operation) = item.get(method) else {
+                let uri = sample_uri(path, &Value::Null, schemas);
+                let (status, _) = probe(&ctx, method, &uri, None).await;
+                if status != StatusCode::METHOD_NOT_ALLOWED {
+                    mismatches.push(format!("{method} {path}: routed but not documented"));
+                }
+                continue;
+            };
+            let uri = sample_uri(path, operation, schemas);
+            let mut expect = |ok: bool, what: &str| {
+                if !ok {
+                    mismatches.push(format!("{method} {path}: {what}"));
+                }
+            };
+
+            let (status, error) = probe(&ctx, method, &uri, None).await;
+            expect(
+                status != StatusCode::METHOD_NOT_ALLOWED
+                    && (status != StatusCode::NOT_FOUND || error.is_some()),
+                "documented but not routed",
+            );
+            let scheme = operation["security"]
+                .as_array()
+                .and_then(|s| s.last())
+                .and_then(Value::as_object)
+                .and_then(|s| s.keys().next().cloned());
+            match scheme.as_deref() {
+                None => {
+                    // A handler that takes no token answers the same with one.
+                    for token in [&user, &admin] {
+                        let (with_token, _) = probe(&ctx, method, &uri, Some(token)).await;
+                        expect(with_token == status, "public but needs a token");
+                    }
+                }
+                Some("metricsBearer") => {}
+                Some(_) => expect(status == StatusCode::UNAUTHORIZED, "accepts no token"),
+            }
+
+            match scheme.as_deref() {
+                Some("userBearer") => {
+                    let (status, _) = probe(&ctx, method, &uri, Some(&admin)).await;
+                    expect(status == StatusCode::UNAUTHORIZED, "accepts an admin token");
+                    let (_, error) = probe(&ctx, method, &uri, Some(&user)).await;
+                    let step_up = error.as_deref().is_some_and(|e| {
+                        e.starts_with("Forbidden: Two-factor verification required")
+                    });
+                    expect(
+                        step_up == (operation["x-step-up"] == true),
+                        "step-up requirement differs",
+                    );
+                }
+                Some("adminBearer") => {
+                    let (status, _) = probe(&ctx, method, &uri, Some(&user)).await;
+                    expect(status == StatusCode::UNAUTHORIZED, "accepts a user token");
+                    let (_, error) = probe(&ctx, method, &uri, Some(&unprivileged)).await;
+                    let required = error
+                        .as_deref()
+                        .and_then(|e| e.strip_prefix("Forbidden: Missing permission '"))
+                        .and_then(|e| e.strip_suffix('\''));
+                    expect(
+                        required == operation["x-required-permission"].as_str(),
+                        &format!("requires permission {required:?}"),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+    assert!(mismatches.is_empty(), "{mismatches:#?}");
+}
@@ -0,0 +1,327 @@
+//! Integration tests for resumable chunked uploads of legacy content.
+
+mod helpers;
+
+use axum::{
+    body::Body,
+    http::{Request, Response, StatusCode},
+};
+use base64::Engine as _;
+use inheritx_backend::object_storage::build_object_store;
+use inheritx_backend::resumable_upload::{
+    CreateUploadSessionRequest, ResumableUploadService, UploadSessionCleanupService,
+    MAX_RESUMABLE_FILE_SIZE,
+};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+fn upload_checksum(bytes: &[u8]) -> String {
+    format!(
+        "sha256 {}",
+        base64::engine::general_purpose::STANDARD.encode(Sha256::digest(bytes))
+    )
+}
+
+async fn new_user(ctx: &helpers::TestContext) -> (Uuid, String) {
+    let email = format!("{}@uploads.test", Uuid::new_v4());
+    let user_id = helpers::create_test_user(&ctx.pool, &email).await.unwrap();
+    (user_id, helpers::user_token(user_id, &email))
+}
+
+async fn create_session(
+    ctx: &helpers::TestContext,
+    token: &str,
+    total_size: i64,
+) -> Response<Body> {
+    ctx.app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/content/uploads")
+                .header("Authorization", format!("Bearer {token}"))
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    json!({
+                        "original_filename": "message.mp4",
+                        "content_type": "video/mp4",
+                        "total_size": total_size,
+                        "description": "For my heirs"
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+async fn patch_chunk(
+    ctx: &helpers::TestContext,
+    token: &str,
+    upload_id: &str,
+    offset: usize,
+    checksum: &str,
+    chunk: &[u8],
+) -> Response<Body> {
+    ctx.app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("PATCH")
+                .uri(format!("/api/content/uploads/{upload_id}"))
+                .header("Authorization", format!("Bearer {token}"))
+                .header("Content-Type", "application/offset+octet-stream")
+                .header("Upload-Offset", offset.to_string())
+                .header("Upload-Checksum", checksum)
+                .body(Body::from(chunk.to_vec()))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn chunks_resume_from_server_offset_and_finalize_with_file_hash() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let (_, token) = new_user(&ctx).await;
+
+    // An MP4 `ftyp` header so the upload passes content sniffing.
+    let mut file = b"\0\0\0\x18ftypmp42".to_vec();
+    file.extend((file.len() as u32..250_000).map(|i| (i % 251) as u8));
+    let (first, second) = file.split_at(100_000);
+
+    let response = create_session(&ctx, &token, file.len() as i64).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let upload_id = helpers::json_body(response).await["data"]["session"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let response = patch_chunk(&ctx, &token, &upload_id, 0, &upload_checksum(first), first).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["upload-offset"], "100000");
+
+    // A corrupted chunk is rejected and does not advance the offset.
+    let response = patch_chunk(
+        &ctx,
+        &token,
+        &upload_id,
+        first.len(),
+        &upload_checksum(b"something else"),
+        second,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // A client that lost track of the offset is told to resync.
+    let response = patch_chunk(
+        &ctx,
+        &token,
+        &upload_id,
+        0,
+        &upload_checksum(second),
+        second,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/content/uploads/{upload_id}"))
+                .header("Authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let progress = helpers::json_body(response).await;
+    assert_eq!(progress["data"]["session"]["upload_offset"], 100_000);
+    assert_eq!(progress["data"]["progress_percent"], 40.0);
+
+    let response = patch_chunk(
+        &ctx,
+        &token,
+        &upload_id,
+        first.len(),
+        &upload_checksum(second),
+        second,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/api/content/uploads/{upload_id}/finalize"))
+                .header("Authorization", format!("Bearer {token}"))
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    json!({ "sha256": hex::encode(Sha256::digest(&file)) }).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let content = helpers::json_body(response).await;
+    assert_eq!(content["data"]["file_size"], file.len());
+    let content_id = content["data"]["id"].as_str().unwrap();
+    assert_eq!(
+        helpers::inspect_content(&ctx.pool, Uuid::parse_str(content_id).unwrap()).await,
+        "passed"
+    );
+
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/content/{content_id}/download"))
+                .header("Authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let downloaded = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(downloaded.as_ref(), file.as_slice());
+}
+
+#[tokio::test]
+async fn finalize_rejects_mismatched_file_hash() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let (_, token) = new_user(&ctx).await;
+
+    let file = b"a short audio note";
+    let response = create_session(&ctx, &token, file.len() as i64).await;
+    let upload_id = helpers::json_body(response).await["data"]["session"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let response = patch_chunk(&ctx, &token, &upload_id, 0, &upload_checksum(file), file).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/api/content/uploads/{upload_id}/finalize"))
+                .header("Authorization", format!("Bearer {token}"))
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    json!({ "sha256": hex::encode(Sha256::digest(b"another file")) }).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let status: String = sqlx::query_scalar("SELECT status FROM upload_sessions WHERE id = $1")
+        .bind(Uuid::parse_str(&upload_id).unwrap())
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap();
+    assert_eq!(status, "failed");
+}
+
+#[tokio::test]
+async fn sessions_reserve_quota_from_storage_stats() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let (_, token) = new_user(&ctx).await;
+
+    // The default 10 GiB quota fits five maximum-size sessions.
+    for _ in 0..5 {
+        let response = create_session(&ctx, &token, MAX_RESUMABLE_FILE_SIZE).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+    let response = create_session(&ctx, &token, MAX_RESUMABLE_FILE_SIZE).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn expired_sessions_are_garbage_collected() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let (user_id, _) = new_user(&ctx).await;
+
+    // Same store the app is configured with, since the app's own cleanup job
+    // may collect this session before ours does.
+    let store = build_object_store().unwrap();
+    let session = ResumableUploadService::create_session(
+        &ctx.pool,
+        user_id,
+        &CreateUploadSessionRequest {
+            original_filename: "abandoned.mp4".to_string(),
+            content_type: "video/mp4".to_string(),
+            total_size: 1024,
+            description: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let chunk = [7u8; 512];
+    ResumableUploadService::append_chunk(
+        &ctx.pool,
+        store.as_ref(),
+        user_id,
+        session.id,
+        0,
+        &Sha256::digest(chunk),
+        &chunk,
+    )
+    .await
+    .unwrap();
+    let chunk_key: String =
+        sqlx::query_scalar("SELECT storage_key FROM upload_chunks WHERE session_id = $1")
+            .bind(session.id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert!(store.exists(&chunk_key).await.unwrap());
+
+    sqlx::query(
+        "UPDATE upload_sessions SET expires_at = NOW() - INTERVAL '1 minute' WHERE id = $1",
+    )
+    .bind(session.id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    let cleanup = UploadSessionCleanupService::new(ctx.pool.clone(), store.clone());
+    // Other tests' expired sessions may be collected in the same pass.
+    for _ in 0..10 {
+        cleanup.collect_expired().await.unwrap();
+        let session = ResumableUploadService::get_session(&ctx.pool, user_id, session.id)
+            .await
+            .unwrap();
+        if session.status == "expired" && !store.exists(&chunk_key).await.unwrap() {
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    panic!("expired upload session was not garbage-collected");
+}
@@ -0,0 +1,323 @@
+//! Integration tests for end-to-end encrypted legacy messages.
+//!
+//! Beneficiaries are simulated with a Stellar key pair derived from a random
+//! seed; their message key is derived from the same seed, as a wallet would.
+
+mod helpers;
+
+use axum::{
+    body::Body,
+    http::{Request, Response, StatusCode},
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{Duration, Utc};
+use inheritx_backend::message_delivery::MessageChannels;
+use inheritx_backend::sealed_messages::{
+    key_binding_message, release_message, seal, RecipientKeyPair, SealedMessage,
+};
+use inheritx_backend::secure_messages::LegacyMessageDeliveryService;
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+struct Beneficiary {
+    signer: Ed25519KeyPair,
+    address: String,
+    message_key: RecipientKeyPair,
+}
+
+impl Beneficiary {
+    fn generate() -> Self {
+        let mut seed = [0u8; 32];
+        SystemRandom::new().fill(&mut seed).unwrap();
+        let signer = Ed25519KeyPair::from_seed_unchecked(&seed).unwrap();
+        let public_key: [u8; 32] = signer.public_key().as_ref().try_into().unwrap();
+        Self {
+            address: stellar_strkey::ed25519::PublicKey(public_key)
+                .to_string()
+                .as_str()
+                .to_string(),
+            signer,
+            message_key: RecipientKeyPair::from_seed(&seed).unwrap(),
+        }
+    }
+
+    fn sign(&self, message: &str) -> String {
+        hex::encode(self.signer.sign(message.as_bytes()).as_ref())
+    }
+}
+
+async fn post(
+    ctx: &helpers::TestContext,
+    uri: &str,
+    token: Option<&str>,
+    body: Value,
+) -> Response<Body> {
+    let mut request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    if let Some(token) = token {
+        request = request.header("Authorization", format!("Bearer {token}"));
+    }
+    ctx.app
+        .clone()
+        .oneshot(request.body(Body::from(body.to_string())).unwrap())
+        .await
+        .unwrap()
+}
+
+/// Register the beneficiary's message key and return its id.
+async fn register_key(ctx: &helpers::TestContext, beneficiary: &Beneficiary) -> String {
+    let public_key = beneficiary.message_key.public_key();
+    let response = post(
+        ctx,
+        "/api/messages/recipient-keys",
+        None,
+        json!({
+            "stellar_address": beneficiary.address,
+            "public_key": BASE64.encode(public_key),
+            "signature": beneficiary.sign(&key_binding_message(&beneficiary.address, &public_key)),
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    helpers::json_body(response).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+/// Move a message's unlock time into the past and deliver it.
+async fn deliver(ctx: &helpers::TestContext, message_id: Uuid) {
+    sqlx::query("UPDATE legacy_messages SET unlock_at = NOW() - INTERVAL '1 minute' WHERE id = $1")
+        .bind(message_id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    let channels = Arc::new(MessageChannels::new(None, "http://localhost:3000"));
+    let message = LegacyMessageDeliveryService::new(ctx.pool.clone(), channels)
+        .deliver_now(message_id)
+        .await
+        .unwrap();
+    assert_eq!(message.status, "delivered");
+}
+
+async fn release(
+    ctx: &helpers::TestContext,
+    message_id: Uuid,
+    address: &str,
+    signer: &Beneficiary,
+) -> Response<Body> {
+    let signed_at = Utc::now();
+    post(
+        ctx,
+        &format!("/api/messages/legacy/{message_id}/release"),
+        None,
+        json!({
+            "stellar_address": address,
+            "signed_at": signed_at,
+            "signature": signer.sign(&release_message(message_id, signed_at)),
+        }),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn key_registration_requires_a_signature_from_the_address() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let beneficiary = Beneficiary::generate();
+    let impostor = Beneficiary::generate();
+    let public_key = impostor.message_key.public_key();
+
+    let response = post(
+        &ctx,
+        "/api/messages/recipient-keys",
+        None,
+        json!({
+            "stellar_address": beneficiary.address,
+            "public_key": BASE64.encode(public_key),
+            "signature": impostor.sign(&key_binding_message(&beneficiary.address, &public_key)),
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let key_id = register_key(&ctx, &beneficiary).await;
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!(
+                    "/api/messages/recipient-keys/{}",
+                    beneficiary.address
+                ))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let key = helpers::json_body(response).await;
+    assert_eq!(key["data"]["id"], key_id);
+    assert_eq!(
+        key["data"]["public_key"],
+        BASE64.encode(beneficiary.message_key.public_key())
+    );
+}
+
+#[tokio::test]
+async fn server_sealed_message_is_only_released_as_ciphertext() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let email = format!("{}@sealed.test", Uuid::new_v4());
+    let user_id = helpers::create_test_user(&ctx.pool, &email).await.unwrap();
+    let token = helpers::user_token(user_id, &email);
+    let beneficiary = Beneficiary::generate();
+    register_key(&ctx, &beneficiary).await;
+
+    let plaintext = format!("The safe combination is {}", Uuid::new_v4());
+    let response = post(
+        &ctx,
+        "/api/messages/legacy/sealed",
+        Some(&token),
+        json!({
+            "beneficiary_contact": "heir@example.com",
+            "delivery_channel": "inbox",
+            "recipient_stellar_address": beneficiary.address,
+            "unlock_at": Utc::now() + Duration::days(1),
+            "message": plaintext,
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let created = helpers::json_body(response).await;
+    assert_eq!(created["data"]["encryption_mode"], "e2e");
+    let message_id = Uuid::parse_str(created["data"]["id"].as_str().unwrap()).unwrap();
+
+    let stored: Vec<u8> =
+        sqlx::query_scalar("SELECT encrypted_payload FROM legacy_messages WHERE id = $1")
+            .bind(message_id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert!(!stored
+        .windows(plaintext.len())
+        .any(|w| w == plaintext.as_bytes()));
+
+    // Not releasable before delivery.
+    let response = release(&ctx, message_id, &beneficiary.address, &beneficiary).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    deliver(&ctx, message_id).await;
+    let payload: Option<String> = sqlx::query_scalar(
+        "SELECT decrypted_payload FROM legacy_message_deliveries WHERE message_id = $1",
+    )
+    .bind(message_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert!(payload.is_none());
+
+    let response = release(
+        &ctx,
+        message_id,
+        &beneficiary.address,
+        &Beneficiary::generate(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = release(&ctx, message_id, &beneficiary.address, &beneficiary).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let released = helpers::json_body(response).await;
+    let sealed: SealedMessage = serde_json::from_value(released["data"]["sealed"].clone()).unwrap();
+    let opened = beneficiary
+        .message_key
+        .open(&sealed, &beneficiary.address)
+        .unwrap();
+    assert_eq!(opened, plaintext.as_bytes());
+
+    let released_events: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM message_access_logs WHERE message_id = $1 AND action = 'released'",
+    )
+    .bind(message_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(released_events, 1);
+}
+
+#[tokio::test]
+async fn client_sealed_message_round_trips_untouched() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let email = format!("{}@sealed.test", Uuid::new_v4());
+    let user_id = helpers::create_test_user(&ctx.pool, &email).await.unwrap();
+    let token = helpers::user_token(user_id, &email);
+    let beneficiary = Beneficiary::generate();
+    let key_id = register_key(&ctx, &beneficiary).await;
+
+    let sealed = seal(
+        &beneficiary.message_key.public_key(),
+        &beneficiary.address,
+        b"sealed in the browser",
+    )
+    .unwrap();
+
+    // A key that is not the recipient's is refused.
+    let response = post(
+        &ctx,
+        "/api/messages/legacy/sealed",
+        Some(&token),
+        json!({
+            "beneficiary_contact": "heir@example.com",
+            "delivery_channel": "inbox",
+            "recipient_stellar_address": beneficiary.address,
+            "unlock_at": Utc::now() + Duration::days(1),
+            "sealed": sealed,
+            "recipient_key_id": Uuid::new_v4(),
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = post(
+        &ctx,
+        "/api/messages/legacy/sealed",
+        Some(&token),
+        json!({
+            "beneficiary_contact": "heir@example.com",
+            "delivery_channel": "inbox",
+            "recipient_stellar_address": beneficiary.address,
+            "unlock_at": Utc::now() + Duration::days(1),
+            "sealed": sealed,
+            "recipient_key_id": key_id,
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let message_id = Uuid::parse_str(
+        helpers::json_body(response).await["data"]["id"]
+            .as_str()
+            .unwrap(),
+    )
+    .unwrap();
+
+    deliver(&ctx, message_id).await;
+    let response = release(&ctx, message_id, &beneficiary.address, &beneficiary).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let released = helpers::json_body(response).await;
+    assert_eq!(released["data"]["recipient_key_id"], key_id);
+    let returned: SealedMessage =
+        serde_json::from_value(released["data"]["sealed"].clone()).unwrap();
+    assert_eq!(returned, sealed);
+}
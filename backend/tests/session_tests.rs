@@ -0,0 +1,219 @@
+//! Integration tests for login sessions, refresh token rotation and revocation.
+
+mod helpers;
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{Response, StatusCode},
+};
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use uuid::Uuid;
+
+async fn send(
+    ctx: &helpers::TestContext,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+) -> Response<Body> {
+    let request = helpers::request(method, uri, token)
+        .header("User-Agent", "session-tests/1.0")
+        .header("X-Forwarded-For", "203.0.113.7")
+        // Login routes are rate limited per peer address.
+        .extension(ConnectInfo(SocketAddr::from(([192, 0, 2, 41], 4000))));
+    helpers::dispatch(ctx, request, body).await
+}
+
+async fn login(ctx: &helpers::TestContext, email: &str, device: &str) -> (String, String) {
+    let response = send(
+        ctx,
+        "POST",
+        "/api/auth/login",
+        None,
+        Some(json!({ "email": email, "password": "correct horse", "device_name": device })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = helpers::json_body(response).await;
+    assert_eq!(body["expires_in"], 900);
+    (
+        body["token"].as_str().unwrap().to_string(),
+        body["refresh_token"].as_str().unwrap().to_string(),
+    )
+}
+
+#[tokio::test]
+async fn sessions_rotate_refresh_tokens_and_can_be_revoked() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let email = format!("{}@sessions.test", Uuid::new_v4());
+    let user_id = helpers::create_test_user(&ctx.pool, &email).await.unwrap();
+    sqlx::query("UPDATE users SET password_hash = $2 WHERE id = $1")
+        .bind(user_id)
+        .bind(bcrypt::hash("correct horse", 4).unwrap())
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    let admin_email = format!("{}@admins.test", Uuid::new_v4());
+    let admin_id = helpers::create_test_admin(&ctx.pool, &admin_email)
+        .await
+        .unwrap();
+    let admin_token = helpers::admin_token(admin_id, &admin_email);
+
+    let response = send(
+        &ctx,
+        "POST",
+        "/api/auth/login",
+        None,
+        Some(json!({ "email": email, "password": "wrong" })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let (laptop_token, laptop_refresh) = login(&ctx, &email, "Laptop").await;
+    let (phone_token, _) = login(&ctx, &email, "Phone").await;
+
+    let response = send(&ctx, "GET", "/api/auth/sessions", Some(&laptop_token), None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = helpers::json_body(response).await;
+    assert_eq!(body["count"], 2);
+    let laptop = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["device_name"] == "Laptop")
+        .unwrap()
+        .clone();
+    assert_eq!(laptop["current"], true);
+    assert_eq!(laptop["user_agent"], "session-tests/1.0");
+    assert_eq!(laptop["ip_address"], "203.0.113.7");
+    let stored: String =
+        sqlx::query_scalar("SELECT refresh_token_hash FROM sessions WHERE id = $1")
+            .bind(Uuid::parse_str(laptop["id"].as_str().unwrap()).unwrap())
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert_ne!(stored, laptop_refresh);
+
+    // --- Refresh tokens rotate, and replaying one revokes the session --------
+    let refresh = json!({ "refresh_token": laptop_refresh });
+    let response = send(
+        &ctx,
+        "POST",
+        "/api/auth/refresh",
+        None,
+        Some(refresh.clone()),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = helpers::json_body(response).await;
+    let rotated_token = body["token"].as_str().unwrap().to_string();
+    let rotated_refresh = body["refresh_token"].as_str().unwrap().to_string();
+    assert_ne!(rotated_refresh, laptop_refresh);
+    let response = send(
+        &ctx,
+        "GET",
+        "/api/auth/sessions",
+        Some(&rotated_token),
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = send(&ctx, "POST", "/api/auth/refresh", None, Some(refresh)).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = send(
+        &ctx,
+        "GET",
+        "/api/auth/sessions",
+        Some(&rotated_token),
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = send(
+        &ctx,
+        "POST",
+        "/api/auth/refresh",
+        None,
+        Some(json!({ "refresh_token": rotated_refresh })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // --- Users revoke their own devices; admins can force a logout ----------
+    let response = send(&ctx, "GET", "/api/auth/sessions", Some(&phone_token), None).await;
+    let body = helpers::json_body(response).await;
+    assert_eq!(body["count"], 1);
+    let phone_session = body["data"][0]["id"].as_str().unwrap().to_string();
+    let (tablet_token, _) = login(&ctx, &email, "Tablet").await;
+    let response = send(
+        &ctx,
+        "DELETE",
+        &format!("/api/auth/sessions/{phone_session}"),
+        Some(&tablet_token),
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = send(&ctx, "GET", "/api/auth/sessions", Some(&phone_token), None).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = send(
+        &ctx,
+        "POST",
+        &format!("/api/admin/users/{user_id}/sessions/revoke"),
+        Some(&admin_token),
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        helpers::json_body(response).await["data"]["sessions_revoked"],
+        1
+    );
+    let response = send(&ctx, "GET", "/api/auth/sessions", Some(&tablet_token), None).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let reused: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM action_logs WHERE user_id = $1 AND action = 'session_refresh_reused'",
+    )
+    .bind(user_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(reused, 1);
+}
+
+#[tokio::test]
+async fn logout_ends_the_current_session() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let email = format!("{}@sessions.test", Uuid::new_v4());
+    let user_id = helpers::create_test_user(&ctx.pool, &email).await.unwrap();
+    sqlx::query("UPDATE users SET password_hash = $2 WHERE id = $1")
+        .bind(user_id)
+        .bind(bcrypt::hash("correct horse", 4).unwrap())
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+
+    let (token, refresh_token) = login(&ctx, &email, "Laptop").await;
+    let response = send(&ctx, "POST", "/api/auth/logout", Some(&token), None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = send(&ctx, "GET", "/api/auth/sessions", Some(&token), None).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = send(
+        &ctx,
+        "POST",
+        "/api/auth/refresh",
+        None,
+        Some(json!({ "refresh_token": refresh_token })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
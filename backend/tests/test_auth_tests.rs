@@ -0,0 +1,119 @@
+//! Integration tests for the opt-in test authentication mode and the removal
+//! of header impersonation.
+
+mod helpers;
+
+use axum::http::StatusCode;
+use inheritx_backend::Config;
+use serde_json::json;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn identity_headers_without_a_bearer_token_are_rejected() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let (user_id, _, _) = helpers::user(&ctx, "test-auth.test").await;
+    let (admin_id, _) = helpers::admin_with_role(&ctx, "super_admin").await;
+
+    let request = helpers::request("GET", "/api/plans/due-for-claim", None)
+        .header("X-User-Id", user_id.to_string());
+    let response = helpers::dispatch(&ctx, request, None).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let request =
+        helpers::request("GET", "/api/admin/logs", None).header("X-Admin-Id", admin_id.to_string());
+    let response = helpers::dispatch(&ctx, request, None).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_token_route_is_only_mounted_with_test_auth() {
+    let Some(ctx) = helpers::TestContext::from_env_with(|config| config.test_auth = false).await
+    else {
+        return;
+    };
+    let (user_id, _, _) = helpers::user(&ctx, "test-auth.test").await;
+
+    let request = helpers::request("POST", "/api/test-auth/token", None);
+    let response = helpers::dispatch(&ctx, request, Some(json!({ "user_id": user_id }))).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn tokens_without_a_session_need_test_auth() {
+    let Some(ctx) = helpers::TestContext::from_env_with(|config| config.test_auth = false).await
+    else {
+        return;
+    };
+    let (user_id, email, _) = helpers::user(&ctx, "test-auth.test").await;
+    let (_, admin_token) = helpers::admin_with_role(&ctx, "super_admin").await;
+
+    let token = helpers::user_token(user_id, &email);
+    let response = helpers::send(&ctx, "GET", "/api/plans/due-for-claim", &token, None).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = helpers::send(&ctx, "GET", "/api/admin/logs", &admin_token, None).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_tokens_are_issued_for_existing_accounts() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let (user_id, _, _) = helpers::user(&ctx, "test-auth.test").await;
+
+    let request = helpers::request("POST", "/api/test-auth/token", None);
+    let response = helpers::dispatch(&ctx, request, Some(json!({ "user_id": user_id }))).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = helpers::json_body(response).await;
+    let token = body["data"]["token"].as_str().unwrap();
+    let response = helpers::send(&ctx, "GET", "/api/plans/due-for-claim", token, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = helpers::request("POST", "/api/test-auth/token", None);
+    let response =
+        helpers::dispatch(&ctx, request, Some(json!({ "user_id": Uuid::new_v4() }))).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn production_config_refuses_test_auth() {
+    let config = |environment: Option<&str>, test_auth: bool| Config {
+        environment: environment.map(str::to_string),
+        test_auth,
+        ..helpers::test_config(String::new())
+    };
+    assert!(config(Some("production"), true).validate().is_err());
+    assert!(config(Some("PRODUCTION"), true).validate().is_err());
+    assert!(config(Some("production"), false).validate().is_ok());
+    // Only an explicit development or test profile allows test auth, so an
+    // unset, misspelled or unfamiliar APP_ENV fails closed.
+    assert!(config(None, true).validate().is_err());
+    for environment in ["prod", "live", "staging"] {
+        assert!(config(Some(environment), true).validate().is_err());
+    }
+    assert!(config(Some("development"), true).validate().is_ok());
+    assert!(config(Some("Test"), true).validate().is_ok());
+
+    // The same settings from the environment stop the server from loading
+    // its configuration. Other tests in this binary read DATABASE_URL and
+    // JWT_SECRET, so those are only filled in when missing.
+    for (name, value) in [
+        ("DATABASE_URL", "postgres://localhost/unused"),
+        ("JWT_SECRET", "test-jwt-secret"),
+    ] {
+        if std::env::var(name).is_err() {
+            std::env::set_var(name, value);
+        }
+    }
+    std::env::set_var("TEST_AUTH_ENABLED", "true");
+    std::env::remove_var("APP_ENV");
+    let unset = Config::load();
+    std::env::set_var("APP_ENV", "production");
+    let production = Config::load();
+    std::env::remove_var("APP_ENV");
+    std::env::remove_var("TEST_AUTH_ENABLED");
+    assert!(unset.is_err());
+    assert!(production.is_err());
+}
@@ -0,0 +1,230 @@
+//! Integration tests for authenticator-app 2FA, recovery codes and step-up.
+
+mod helpers;
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{Request, Response, StatusCode},
+};
+use inheritx_backend::totp::totp_code;
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+async fn admin_login(ctx: &helpers::TestContext, body: &Value) -> Response<Body> {
+    ctx.app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/admin/login")
+                .header("Content-Type", "application/json")
+                // Login routes are rate limited per peer address.
+                .extension(ConnectInfo(SocketAddr::from(([192, 0, 2, 45], 4000))))
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+fn base32_decode(encoded: &str) -> Vec<u8> {
+    const ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+    let mut out = Vec::new();
+    let (mut buffer, mut bits) = (0u32, 0);
+    for c in encoded.chars() {
+        buffer = (buffer << 5) | ALPHABET.find(c).unwrap() as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    out
+}
+
+fn current_code(secret: &[u8]) -> String {
+    totp_code(secret, chrono::Utc::now().timestamp())
+}
+
+/// Enroll and confirm TOTP, returning the secret and the recovery codes.
+async fn enable_totp(
+    ctx: &helpers::TestContext,
+    prefix: &str,
+    token: &str,
+) -> (Vec<u8>, Vec<String>) {
+    let response = helpers::send(ctx, "POST", &format!("{prefix}/totp/enroll"), token, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let enrollment = helpers::json_body(response).await;
+    let secret = enrollment["data"]["secret"].as_str().unwrap();
+    assert!(enrollment["data"]["otpauth_uri"]
+        .as_str()
+        .unwrap()
+        .starts_with("otpauth://totp/InheritX:"));
+    let secret = base32_decode(secret);
+
+    let response = helpers::send(
+        ctx,
+        "POST",
+        &format!("{prefix}/totp/confirm"),
+        token,
+        Some(json!({ "code": "000000x" })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = helpers::send(
+        ctx,
+        "POST",
+        &format!("{prefix}/totp/confirm"),
+        token,
+        Some(json!({ "code": current_code(&secret) })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let codes = helpers::json_body(response).await["data"]["recovery_codes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c.as_str().unwrap().to_string())
+        .collect();
+    (secret, codes)
+}
+
+#[tokio::test]
+async fn sensitive_user_actions_need_a_recent_second_factor() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let email = format!("{}@totp.test", Uuid::new_v4());
+    let user_id = helpers::create_test_user(&ctx.pool, &email).await.unwrap();
+    let token = helpers::user_token(user_id, &email);
+
+    let response = helpers::send(&ctx, "POST", "/api/plans", &token, Some(json!({}))).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/auth/2fa/step-up",
+        &token,
+        Some(json!({ "code": "123456" })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let (secret, recovery_codes) = enable_totp(&ctx, "/api/auth/2fa", &token).await;
+    assert_eq!(recovery_codes.len(), 10);
+
+    // The code used to confirm cannot be replayed.
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/auth/2fa/step-up",
+        &token,
+        Some(json!({ "code": current_code(&secret) })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let recovery_code = recovery_codes[0].to_lowercase();
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/auth/2fa/step-up",
+        &token,
+        Some(json!({ "code": recovery_code })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let stepped_up = helpers::json_body(response).await["data"]["token"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let response = helpers::send(&ctx, "POST", "/api/plans", &stepped_up, Some(json!({}))).await;
+    assert_ne!(response.status(), StatusCode::FORBIDDEN);
+    assert_ne!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Recovery codes are single use.
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/auth/2fa/step-up",
+        &token,
+        Some(json!({ "code": recovery_code })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = helpers::send(&ctx, "GET", "/api/auth/2fa/status", &token, None).await;
+    let status = helpers::json_body(response).await;
+    assert_eq!(status["data"]["totp_enabled"], true);
+    assert_eq!(status["data"]["recovery_codes_remaining"], 9);
+
+    // Repeated failures lock the credential.
+    for _ in 0..4 {
+        let response = helpers::send(
+            &ctx,
+            "POST",
+            "/api/auth/2fa/step-up",
+            &token,
+            Some(json!({ "code": "AAAA-AAAA-AAAA-AAAA" })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+    let response = helpers::send(
+        &ctx,
+        "POST",
+        "/api/auth/2fa/step-up",
+        &token,
+        Some(json!({ "code": recovery_codes[1] })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    let audited: Vec<String> = sqlx::query_scalar(
+        "SELECT action FROM action_logs WHERE user_id = $1 AND action IN \
+         ('totp_enabled', 'recovery_code_used', 'step_up_completed') ORDER BY timestamp",
+    )
+    .bind(user_id)
+    .fetch_all(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        audited,
+        vec!["totp_enabled", "recovery_code_used", "step_up_completed"]
+    );
+    let stored: Vec<u8> =
+        sqlx::query_scalar("SELECT encrypted_secret FROM totp_credentials WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert_ne!(stored, secret);
+}
+
+#[tokio::test]
+async fn enrolled_admins_must_present_a_code_at_login() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let email = format!("{}@totp.test", Uuid::new_v4());
+    let admin_id = helpers::create_test_admin(&ctx.pool, &email).await.unwrap();
+    let token = helpers::admin_token(admin_id, &email);
+    let login = json!({ "email": email, "password": "test_password" });
+
+    let response = admin_login(&ctx, &login).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let (_, recovery_codes) = enable_totp(&ctx, "/admin/2fa", &token).await;
+    let response = admin_login(&ctx, &login).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let mut with_code = login;
+    with_code["otp"] = json!(recovery_codes[0]);
+    let response = admin_login(&ctx, &with_code).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = admin_login(&ctx, &with_code).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,386 @@
+//! Integration tests for M-of-N trustee release of legacy message keys.
+//!
+//! Trustees and beneficiaries are simulated as in the sealed message tests: a
+//! Stellar key pair and a message key derived from the same random seed.
+
+mod helpers;
+
+use axum::{
+    body::Body,
+    http::{Request, Response, StatusCode},
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::Utc;
+use inheritx_backend::message_delivery::MessageChannels;
+use inheritx_backend::sealed_messages::{
+    key_binding_message, release_message, RecipientKeyPair, SealedMessage,
+};
+use inheritx_backend::secure_messages::LegacyMessageDeliveryService;
+use inheritx_backend::trustee_release::{
+    share_hash, share_request_message, share_submission_message,
+};
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+struct Trustee {
+    signer: Ed25519KeyPair,
+    address: String,
+    message_key: RecipientKeyPair,
+}
+
+impl Trustee {
+    fn generate() -> Self {
+        let mut seed = [0u8; 32];
+        SystemRandom::new().fill(&mut seed).unwrap();
+        let signer = Ed25519KeyPair::from_seed_unchecked(&seed).unwrap();
+        let public_key: [u8; 32] = signer.public_key().as_ref().try_into().unwrap();
+        Self {
+            address: stellar_strkey::ed25519::PublicKey(public_key)
+                .to_string()
+                .as_str()
+                .to_string(),
+            signer,
+            message_key: RecipientKeyPair::from_seed(&seed).unwrap(),
+        }
+    }
+
+    fn sign(&self, message: &str) -> String {
+        hex::encode(self.signer.sign(message.as_bytes()).as_ref())
+    }
+}
+
+async fn post(
+    ctx: &helpers::TestContext,
+    uri: &str,
+    token: Option<&str>,
+    body: Value,
+) -> Response<Body> {
+    let mut request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    if let Some(token) = token {
+        request = request.header("Authorization", format!("Bearer {token}"));
+    }
+    ctx.app
+        .clone()
+        .oneshot(request.body(Body::from(body.to_string())).unwrap())
+        .await
+        .unwrap()
+}
+
+async fn register_key(ctx: &helpers::TestContext, trustee: &Trustee) {
+    let public_key = trustee.message_key.public_key();
+    let response = post(
+        ctx,
+        "/api/messages/recipient-keys",
+        None,
+        json!({
+            "stellar_address": trustee.address,
+            "public_key": BASE64.encode(public_key),
+            "signature": trustee.sign(&key_binding_message(&trustee.address, &public_key)),
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+/// Fetch and open the trustee's share.
+async fn open_share(ctx: &helpers::TestContext, message_id: Uuid, trustee: &Trustee) -> Vec<u8> {
+    let signed_at = Utc::now();
+    let response = post(
+        ctx,
+        &format!("/api/messages/legacy/{message_id}/trustee-share"),
+        None,
+        json!({
+            "stellar_address": trustee.address,
+            "signed_at": signed_at,
+            "signature": trustee.sign(&share_request_message(message_id, signed_at)),
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let share = helpers::json_body(response).await;
+    let sealed: SealedMessage =
+        serde_json::from_value(share["data"]["sealed_share"].clone()).unwrap();
+    trustee.message_key.open(&sealed, &trustee.address).unwrap()
+}
+
+async fn submit_share(
+    ctx: &helpers::TestContext,
+    message_id: Uuid,
+    trustee: &Trustee,
+    share: &[u8],
+) -> Response<Body> {
+    let signed_at = Utc::now();
+    post(
+        ctx,
+        &format!("/api/messages/legacy/{message_id}/trustee-shares"),
+        None,
+        json!({
+            "stellar_address": trustee.address,
+            "signed_at": signed_at,
+            "share": BASE64.encode(share),
+            "signature": trustee.sign(&share_submission_message(
+                message_id,
+                &share_hash(share),
+                signed_at,
+            )),
+        }),
+    )
+    .await
+}
+
+async fn audit_count(ctx: &helpers::TestContext, message_id: Uuid, action: &str) -> i64 {
+    sqlx::query_scalar(
+        "SELECT COUNT(*) FROM message_access_logs WHERE message_id = $1 AND action = $2",
+    )
+    .bind(message_id)
+    .bind(action)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn trustee_message_requires_registered_distinct_trustees() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let email = format!("{}@trustee.test", Uuid::new_v4());
+    let user_id = helpers::create_test_user(&ctx.pool, &email).await.unwrap();
+    let token = helpers::user_token(user_id, &email);
+    let registered = Trustee::generate();
+    register_key(&ctx, &registered).await;
+    let unregistered = Trustee::generate();
+    let beneficiary = Trustee::generate();
+    register_key(&ctx, &beneficiary).await;
+
+    let create = |threshold: usize, trustees: Vec<&str>| {
+        json!({
+            "beneficiary_contact": "heir@example.com",
+            "recipient_stellar_address": beneficiary.address,
+            "message": "hello",
+            "threshold": threshold,
+            "trustees": trustees,
+        })
+    };
+
+    let response = post(
+        &ctx,
+        "/api/messages/legacy/trustee",
+        Some(&token),
+        create(3, vec![&registered.address, &unregistered.address]),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = post(
+        &ctx,
+        "/api/messages/legacy/trustee",
+        Some(&token),
+        create(2, vec![&registered.address, &registered.address]),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = post(
+        &ctx,
+        "/api/messages/legacy/trustee",
+        Some(&token),
+        create(2, vec![&registered.address, &unregistered.address]),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // The beneficiary needs a message key to seal the released message to.
+    let mut request = create(2, vec![&registered.address, &beneficiary.address]);
+    request["recipient_stellar_address"] = json!(unregistered.address);
+    let response = post(&ctx, "/api/messages/legacy/trustee", Some(&token), request).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn threshold_of_trustees_releases_message_after_verified_death() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let email = format!("{}@trustee.test", Uuid::new_v4());
+    let user_id = helpers::create_test_user(&ctx.pool, &email).await.unwrap();
+    let token = helpers::user_token(user_id, &email);
+    let admin_email = format!("{}@admin.test", Uuid::new_v4());
+    let admin_id = helpers::create_test_admin(&ctx.pool, &admin_email)
+        .await
+        .unwrap();
+    let admin_token = helpers::admin_token(admin_id, &admin_email);
+
+    let trustees: Vec<Trustee> = (0..3).map(|_| Trustee::generate()).collect();
+    for trustee in &trustees {
+        register_key(&ctx, trustee).await;
+    }
+    let beneficiary = Trustee::generate();
+    register_key(&ctx, &beneficiary).await;
+
+    let plaintext = format!("The deed is in box {}", Uuid::new_v4());
+    let response = post(
+        &ctx,
+        "/api/messages/legacy/trustee",
+        Some(&token),
+        json!({
+            "beneficiary_contact": "heir@example.com",
+            "recipient_stellar_address": beneficiary.address,
+            "delivery_channel": "inbox",
+            "message": plaintext,
+            "threshold": 2,
+            "trustees": trustees.iter().map(|t| t.address.clone()).collect::<Vec<_>>(),
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let created = helpers::json_body(response).await;
+    assert_eq!(created["data"]["encryption_mode"], "trustee");
+    assert_eq!(created["data"]["status"], "awaiting_trustees");
+    let message_id = Uuid::parse_str(created["data"]["id"].as_str().unwrap()).unwrap();
+    assert_eq!(audit_count(&ctx, message_id, "shares_distributed").await, 1);
+
+    let shares = [
+        open_share(&ctx, message_id, &trustees[0]).await,
+        open_share(&ctx, message_id, &trustees[1]).await,
+    ];
+
+    // Shares are refused until the owner's death is verified.
+    let response = submit_share(&ctx, message_id, &trustees[0], &shares[0]).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = post(
+        &ctx,
+        &format!("/api/admin/users/{user_id}/death-verification"),
+        Some(&admin_token),
+        json!({ "evidence": "Death certificate #1234", "date_of_death": "2026-01-02" }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let verified: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM action_logs WHERE entity_id = $1 AND action = 'death_verified'",
+    )
+    .bind(user_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(verified, 1);
+
+    // Another trustee's share is rejected, and so is a forged signature.
+    let response = submit_share(&ctx, message_id, &trustees[0], &shares[1]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let signed_at = Utc::now();
+    let response = post(
+        &ctx,
+        &format!("/api/messages/legacy/{message_id}/trustee-shares"),
+        None,
+        json!({
+            "stellar_address": trustees[0].address,
+            "signed_at": signed_at,
+            "share": BASE64.encode(&shares[0]),
+            "signature": trustees[1].sign(&share_submission_message(
+                message_id,
+                &share_hash(&shares[0]),
+                signed_at,
+            )),
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // One share is not enough.
+    let response = submit_share(&ctx, message_id, &trustees[0], &shares[0]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let status = helpers::json_body(response).await;
+    assert_eq!(status["data"]["status"], "awaiting_trustees");
+    assert_eq!(status["data"]["submitted"], 1);
+
+    let response = submit_share(&ctx, message_id, &trustees[1], &shares[1]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let status = helpers::json_body(response).await;
+    assert_eq!(status["data"]["status"], "pending");
+    assert_eq!(status["data"]["submitted"], 2);
+    assert_eq!(audit_count(&ctx, message_id, "share_submitted").await, 2);
+    assert_eq!(audit_count(&ctx, message_id, "trustees_released").await, 1);
+
+    let leftover: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM message_trustee_shares \
+         WHERE message_id = $1 AND submitted_share IS NOT NULL",
+    )
+    .bind(message_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(leftover, 0);
+
+    // The message is now sealed to the beneficiary, not held under a server key.
+    let (mode, key_version): (String, Option<i32>) =
+        sqlx::query_as("SELECT encryption_mode, key_version FROM legacy_messages WHERE id = $1")
+            .bind(message_id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert_eq!(mode, "e2e");
+    assert_eq!(key_version, None);
+
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/messages/legacy/{message_id}/trustees"))
+                .header("Authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let status = helpers::json_body(response).await;
+    assert_eq!(status["data"]["threshold"], 2);
+    assert_eq!(status["data"]["trustees"].as_array().unwrap().len(), 3);
+    assert!(!status["data"]["death_verified_at"].is_null());
+
+    let channels = Arc::new(MessageChannels::new(None, "http://localhost:3000"));
+    let message = LegacyMessageDeliveryService::new(ctx.pool.clone(), channels)
+        .deliver_now(message_id)
+        .await
+        .unwrap();
+    assert_eq!(message.status, "delivered");
+    let payload: Option<String> = sqlx::query_scalar(
+        "SELECT decrypted_payload FROM legacy_message_deliveries WHERE message_id = $1",
+    )
+    .bind(message_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(payload, None);
+
+    let signed_at = Utc::now();
+    let response = post(
+        &ctx,
+        &format!("/api/messages/legacy/{message_id}/release"),
+        None,
+        json!({
+            "stellar_address": beneficiary.address,
+            "signed_at": signed_at,
+            "signature": beneficiary.sign(&release_message(message_id, signed_at)),
+        }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let released = helpers::json_body(response).await;
+    let sealed: SealedMessage = serde_json::from_value(released["data"]["sealed"].clone()).unwrap();
+    let opened = beneficiary
+        .message_key
+        .open(&sealed, &beneficiary.address)
+        .unwrap();
+    assert_eq!(opened, plaintext.as_bytes());
+}
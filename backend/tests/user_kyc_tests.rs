@@ -1,4 +1,3 @@
-/*
 mod helpers;
 
 use axum::{
@@ -6,10 +5,12 @@ use axum::{
     http::{Request, StatusCode},
 };
 use helpers::TestContext;
-use serde_json::Value;
 use tower::ServiceExt;
 use uuid::Uuid;
 
+// Users have no route to read their own KYC record; reviewers read it at
+// /api/admin/kyc/:user_id.
+
 #[tokio::test]
 async fn test_get_user_kyc_pending() {
     let ctx = match TestContext::from_env().await {
@@ -17,25 +18,21 @@ async fn test_get_user_kyc_pending() {
         None => return,
     };
 
+    // A user without a KYC record yet reads as pending.
     let user_id = Uuid::new_v4();
+    let (_, reviewer) = helpers::admin_with_role(&ctx, "kyc_reviewer").await;
 
-    // The current implementation seems to have a bypass for tests using X-User-Id
-    // as seen in kyc_access.rs and double_claim.rs
-    let req = Request::builder()
-        .method("GET")
-        .uri("/api/kyc")
-        .header("X-User-Id", user_id.to_string())
-        .body(Body::empty())
-        .unwrap();
-
-    let resp = ctx.app.clone().oneshot(req).await.expect("request failed");
+    let resp = helpers::send(
+        &ctx,
+        "GET",
+        &format!("/api/admin/kyc/{user_id}"),
+        &reviewer,
+        None,
+    )
+    .await;
     assert_eq!(resp.status(), StatusCode::OK);
 
-    let body_bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
-        .await
-        .unwrap();
-    let body: Value = serde_json::from_slice(&body_bytes).unwrap();
-
+    let body = helpers::json_body(resp).await;
     assert_eq!(body["user_id"], user_id.to_string());
     assert_eq!(body["status"], "pending");
 }
@@ -49,11 +46,22 @@ async fn test_get_user_kyc_unauthorized() {
 
     let req = Request::builder()
         .method("GET")
-        .uri("/api/kyc")
+        .uri(format!("/api/admin/kyc/{}", Uuid::new_v4()))
         .body(Body::empty())
         .unwrap();
 
     let resp = ctx.app.clone().oneshot(req).await.expect("request failed");
     assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    // A user's token does not reach it either.
+    let user_token = helpers::user_token(Uuid::new_v4(), "test@example.com");
+    let resp = helpers::send(
+        &ctx,
+        "GET",
+        &format!("/api/admin/kyc/{}", Uuid::new_v4()),
+        &user_token,
+        None,
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
 }
-*/
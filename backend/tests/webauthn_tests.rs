@@ -0,0 +1,405 @@
+//! Integration tests for passkey registration and sign-in, driven by a
+//! software authenticator.
+
+mod helpers;
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{Response, StatusCode},
+};
+use base64::Engine as _;
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_ASN1_SIGNING};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU8, Ordering};
+use uuid::Uuid;
+
+const ORIGIN: &str = "http://localhost:3000";
+
+static NEXT_PEER: AtomicU8 = AtomicU8::new(1);
+
+async fn send(
+    ctx: &helpers::TestContext,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+) -> Response<Body> {
+    // Login routes are rate limited per peer address, so each request comes
+    // from a fresh one.
+    let request = helpers::request(method, uri, token).extension(ConnectInfo(SocketAddr::from((
+        [198, 51, 100, NEXT_PEER.fetch_add(1, Ordering::Relaxed)],
+        4000,
+    ))));
+    helpers::dispatch(ctx, request, body).await
+}
+
+fn b64url(bytes: &[u8]) -> String {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn from_b64url(value: &str) -> Vec<u8> {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(value)
+        .unwrap()
+}
+
+/// CBOR header for a major type and length.
+fn cbor_head(major: u8, len: usize) -> Vec<u8> {
+    match len {
+        0..=23 => vec![(major << 5) | len as u8],
+        24..=255 => vec![(major << 5) | 24, len as u8],
+        _ => {
+            let mut head = vec![(major << 5) | 25];
+            head.extend_from_slice(&(len as u16).to_be_bytes());
+            head
+        }
+    }
+}
+
+fn cbor_bytes(bytes: &[u8]) -> Vec<u8> {
+    let mut out = cbor_head(2, bytes.len());
+    out.extend_from_slice(bytes);
+    out
+}
+
+fn cbor_text(text: &str) -> Vec<u8> {
+    let mut out = cbor_head(3, text.len());
+    out.extend_from_slice(text.as_bytes());
+    out
+}
+
+/// A platform authenticator in software: one P-256 key, an optional
+/// signature counter, user verification always performed.
+struct SoftAuthenticator {
+    key: EcdsaKeyPair,
+    pkcs8: Vec<u8>,
+    credential_id: Vec<u8>,
+    user_handle: Option<Vec<u8>>,
+    counter: u32,
+    rng: SystemRandom,
+}
+
+impl SoftAuthenticator {
+    fn new() -> Self {
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng)
+            .unwrap()
+            .as_ref()
+            .to_vec();
+        let key = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &pkcs8, &rng).unwrap();
+        let mut credential_id = vec![0u8; 16];
+        rng.fill(&mut credential_id).unwrap();
+        Self {
+            key,
+            pkcs8,
+            credential_id,
+            user_handle: None,
+            counter: 0,
+            rng,
+        }
+    }
+
+    /// A copy of the key and its current counter, as a cloned device would be.
+    fn clone_device(&self) -> Self {
+        Self {
+            key: EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &self.pkcs8, &self.rng)
+                .unwrap(),
+            pkcs8: self.pkcs8.clone(),
+            credential_id: self.credential_id.clone(),
+            user_handle: self.user_handle.clone(),
+            counter: self.counter,
+            rng: SystemRandom::new(),
+        }
+    }
+
+    fn cose_key(&self) -> Vec<u8> {
+        let point = self.key.public_key().as_ref();
+        // {1: 2 (EC2), 3: -7 (ES256), -1: 1 (P-256), -2: x, -3: y}
+        let mut cose = vec![0xa5, 0x01, 0x02, 0x03, 0x26, 0x20, 0x01, 0x21];
+        cose.extend(cbor_bytes(&point[1..33]));
+        cose.push(0x22);
+        cose.extend(cbor_bytes(&point[33..65]));
+        cose
+    }
+
+    fn client_data(kind: &str, options: &Value, origin: &str) -> Vec<u8> {
+        json!({
+            "type": kind,
+            "challenge": options["challenge"],
+            "origin": origin,
+            "crossOrigin": false,
+        })
+        .to_string()
+        .into_bytes()
+    }
+
+    fn rp_id_hash(rp_id: &str) -> Vec<u8> {
+        Sha256::digest(rp_id.as_bytes()).to_vec()
+    }
+
+    fn create(&mut self, options: &Value, origin: &str) -> Value {
+        self.user_handle = Some(from_b64url(options["user"]["id"].as_str().unwrap()));
+        let client_data = Self::client_data("webauthn.create", options, origin);
+        let mut auth_data = Self::rp_id_hash(options["rp"]["id"].as_str().unwrap());
+        auth_data.push(0x45); // UP | UV | AT
+        auth_data.extend_from_slice(&self.counter.to_be_bytes());
+        auth_data.extend_from_slice(&[0u8; 16]);
+        auth_data.extend_from_slice(&(self.credential_id.len() as u16).to_be_bytes());
+        auth_data.extend_from_slice(&self.credential_id);
+        auth_data.extend(self.cose_key());
+
+        let mut attestation = vec![0xa3];
+        attestation.extend(cbor_text("fmt"));
+        attestation.extend(cbor_text("none"));
+        attestation.extend(cbor_text("attStmt"));
+        attestation.push(0xa0);
+        attestation.extend(cbor_text("authData"));
+        attestation.extend(cbor_bytes(&auth_data));
+        json!({
+            "id": b64url(&self.credential_id),
+            "rawId": b64url(&self.credential_id),
+            "type": "public-key",
+            "name": "Test key",
+            "response": {
+                "clientDataJSON": b64url(&client_data),
+                "attestationObject": b64url(&attestation),
+                "transports": ["internal"],
+            },
+        })
+    }
+
+    fn get(&mut self, options: &Value, origin: &str) -> Value {
+        self.counter += 1;
+        let client_data = Self::client_data("webauthn.get", options, origin);
+        let mut auth_data = Self::rp_id_hash(options["rpId"].as_str().unwrap());
+        auth_data.push(0x05); // UP | UV
+        auth_data.extend_from_slice(&self.counter.to_be_bytes());
+        let mut signed = auth_data.clone();
+        signed.extend_from_slice(&Sha256::digest(&client_data));
+        let signature = self.key.sign(&self.rng, &signed).unwrap();
+        json!({
+            "id": b64url(&self.credential_id),
+            "type": "public-key",
+            "response": {
+                "clientDataJSON": b64url(&client_data),
+                "authenticatorData": b64url(&auth_data),
+                "signature": b64url(signature.as_ref()),
+                "userHandle": self.user_handle.as_deref().map(b64url),
+            },
+        })
+    }
+}
+
+async fn register(
+    ctx: &helpers::TestContext,
+    prefix: &str,
+    token: &str,
+    authenticator: &mut SoftAuthenticator,
+) -> Response<Body> {
+    let response = send(
+        ctx,
+        "POST",
+        &format!("{prefix}/passkeys/register/options"),
+        Some(token),
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let options = helpers::json_body(response).await["data"].clone();
+    let credential = authenticator.create(&options, ORIGIN);
+    send(
+        ctx,
+        "POST",
+        &format!("{prefix}/passkeys/register"),
+        Some(token),
+        Some(credential),
+    )
+    .await
+}
+
+async fn passkey_login(
+    ctx: &helpers::TestContext,
+    prefix: &str,
+    email: Option<&str>,
+    authenticator: &mut SoftAuthenticator,
+) -> Response<Body> {
+    let response = send(
+        ctx,
+        "POST",
+        &format!("{prefix}/passkeys/login/options"),
+        None,
+        Some(json!({ "email": email })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let options = helpers::json_body(response).await["data"].clone();
+    let assertion = authenticator.get(&options, ORIGIN);
+    send(
+        ctx,
+        "POST",
+        &format!("{prefix}/passkeys/login"),
+        None,
+        Some(assertion),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn users_sign_in_with_passkeys_and_clones_are_caught() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let email = format!("{}@passkey.test", Uuid::new_v4());
+    let user_id = helpers::create_test_user(&ctx.pool, &email).await.unwrap();
+    let token = helpers::user_token(user_id, &email);
+    let mut authenticator = SoftAuthenticator::new();
+
+    // A response for another origin is refused.
+    let response = send(
+        &ctx,
+        "POST",
+        "/api/auth/passkeys/register/options",
+        Some(&token),
+        None,
+    )
+    .await;
+    let options = helpers::json_body(response).await["data"].clone();
+    assert_eq!(options["pubKeyCredParams"][0]["alg"], -7);
+    let phished = authenticator.create(&options, "https://inheritx.example.evil");
+    let response = send(
+        &ctx,
+        "POST",
+        "/api/auth/passkeys/register",
+        Some(&token),
+        Some(phished),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = register(&ctx, "/api/auth", &token, &mut authenticator).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let passkey = helpers::json_body(response).await["data"].clone();
+    assert_eq!(passkey["algorithm"], -7);
+    assert_eq!(passkey["name"], "Test key");
+
+    // Signed in by email, and by discoverable credential alone.
+    let response = passkey_login(&ctx, "/api/auth", Some(&email), &mut authenticator).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = passkey_login(&ctx, "/api/auth", None, &mut authenticator).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let session_token = helpers::json_body(response).await["token"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // User verification counts as a step-up.
+    let response = send(
+        &ctx,
+        "POST",
+        "/api/plans",
+        Some(&session_token),
+        Some(json!({})),
+    )
+    .await;
+    assert_ne!(response.status(), StatusCode::FORBIDDEN);
+    let methods: Vec<String> = sqlx::query_scalar(
+        "SELECT auth_methods FROM sessions WHERE user_id = $1 ORDER BY created_at DESC LIMIT 1",
+    )
+    .bind(user_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(methods, vec!["passkey"]);
+
+    // A copy of the key replaying an old counter gets the passkey disabled.
+    let mut clone = authenticator.clone_device();
+    let response = passkey_login(&ctx, "/api/auth", Some(&email), &mut authenticator).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = passkey_login(&ctx, "/api/auth", Some(&email), &mut clone).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = passkey_login(&ctx, "/api/auth", None, &mut authenticator).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = send(&ctx, "GET", "/api/auth/passkeys", Some(&token), None).await;
+    let listed = helpers::json_body(response).await;
+    assert_eq!(listed["count"], 1);
+    assert!(listed["data"][0]["clone_detected_at"].is_string());
+    assert_eq!(listed["data"][0]["sign_count"], 3);
+    let clone_logged: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM action_logs \
+         WHERE user_id = $1 AND action = 'passkey_clone_detected'",
+    )
+    .bind(user_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(clone_logged, 1);
+}
+
+#[tokio::test]
+async fn required_passkeys_lock_admins_out_of_password_sessions() {
+    let Some(ctx) =
+        helpers::TestContext::from_env_with(|config| config.require_admin_passkeys = true).await
+    else {
+        return;
+    };
+    let email = format!("{}@passkey.test", Uuid::new_v4());
+    helpers::create_test_admin(&ctx.pool, &email).await.unwrap();
+    let password_login = json!({ "email": email, "password": "test_password" });
+
+    // Without a passkey the password still works, but only to enroll one.
+    let response = send(
+        &ctx,
+        "POST",
+        "/admin/login",
+        None,
+        Some(password_login.clone()),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let password_token = helpers::json_body(response).await["token"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let response = send(&ctx, "GET", "/api/admin/logs", Some(&password_token), None).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let mut authenticator = SoftAuthenticator::new();
+    let response = register(&ctx, "/admin", &password_token, &mut authenticator).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let passkey_id = helpers::json_body(response).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let response = send(&ctx, "POST", "/admin/login", None, Some(password_login)).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // A user's sign-in route does not accept an admin's passkey.
+    let mut copy = authenticator.clone_device();
+    let response = passkey_login(&ctx, "/api/auth", None, &mut copy).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = passkey_login(&ctx, "/admin", Some(&email), &mut authenticator).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let passkey_token = helpers::json_body(response).await["token"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let response = send(&ctx, "GET", "/api/admin/logs", Some(&passkey_token), None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = send(
+        &ctx,
+        "DELETE",
+        &format!("/admin/passkeys/{passkey_id}"),
+        Some(&passkey_token),
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+}
@@ -0,0 +1,385 @@
+//! Integration tests for RFC 3161 timestamps on finalized wills.
+//!
+//! The client is exercised against an in-process Time-Stamp Authority that
+//! issues ECDSA P-256 tokens from a self-signed TSA certificate.
+
+mod helpers;
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{Path, State},
+    http::{Request, StatusCode},
+    routing::post,
+    Router,
+};
+use inheritx_backend::will_timestamp::{TimestampClient, WillTimestampService};
+use inheritx_backend::will_version::WillVersionService;
+use ring::digest::{digest, SHA256};
+use ring::rand::SystemRandom;
+use ring::signature::{EcdsaKeyPair, ECDSA_P256_SHA256_ASN1_SIGNING};
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+use yasna::models::ObjectIdentifier;
+use yasna::Tag;
+
+const SHA256_OID: &[u64] = &[2, 16, 840, 1, 101, 3, 4, 2, 1];
+const SIGNED_DATA_OID: &[u64] = &[1, 2, 840, 113549, 1, 7, 2];
+const TST_INFO_OID: &[u64] = &[1, 2, 840, 113549, 1, 9, 16, 1, 4];
+const CONTENT_TYPE_OID: &[u64] = &[1, 2, 840, 113549, 1, 9, 3];
+const MESSAGE_DIGEST_OID: &[u64] = &[1, 2, 840, 113549, 1, 9, 4];
+const SIGNING_CERTIFICATE_V2_OID: &[u64] = &[1, 2, 840, 113549, 1, 9, 16, 2, 47];
+const ECDSA_WITH_SHA256_OID: &[u64] = &[1, 2, 840, 10045, 4, 3, 2];
+const POLICY_OID: &[u64] = &[1, 3, 6, 1, 4, 1, 99999, 1];
+
+struct TestTsa {
+    signing_key: EcdsaKeyPair,
+    certificate: Vec<u8>,
+}
+
+impl TestTsa {
+    fn new() -> Self {
+        let key_pair = rcgen::KeyPair::generate().unwrap();
+        let mut params = rcgen::CertificateParams::new(vec!["tsa.inheritx.test".into()]).unwrap();
+        params.extended_key_usages = vec![rcgen::ExtendedKeyUsagePurpose::TimeStamping];
+        let certificate = params.self_signed(&key_pair).unwrap().der().to_vec();
+        let signing_key = EcdsaKeyPair::from_pkcs8(
+            &ECDSA_P256_SHA256_ASN1_SIGNING,
+            &key_pair.serialize_der(),
+            &SystemRandom::new(),
+        )
+        .unwrap();
+        Self {
+            signing_key,
+            certificate,
+        }
+    }
+
+    /// Issue a `TimeStampToken` over `imprint`.
+    fn issue(&self, imprint: &[u8], nonce: Option<u64>) -> Vec<u8> {
+        let gen_time = chrono::Utc::now().format("%Y%m%d%H%M%SZ").to_string();
+        let tst_info = yasna::construct_der(|w| {
+            w.write_sequence(|w| {
+                w.next().write_u8(1);
+                w.next()
+                    .write_oid(&ObjectIdentifier::from_slice(POLICY_OID));
+                w.next().write_sequence(|w| {
+                    w.next().write_sequence(|w| {
+                        w.next()
+                            .write_oid(&ObjectIdentifier::from_slice(SHA256_OID));
+                        w.next().write_null();
+                    });
+                    w.next().write_bytes(imprint);
+                });
+                w.next().write_u64(rand_serial());
+                w.next()
+                    .write_tagged_implicit(yasna::tags::TAG_GENERALIZEDTIME, |w| {
+                        w.write_bytes(gen_time.as_bytes())
+                    });
+                if let Some(nonce) = nonce {
+                    w.next().write_u64(nonce);
+                }
+            })
+        });
+
+        let signed_attrs = yasna::construct_der(|w| {
+            w.write_set(|w| {
+                w.next().write_sequence(|w| {
+                    w.next()
+                        .write_oid(&ObjectIdentifier::from_slice(CONTENT_TYPE_OID));
+                    w.next().write_set(|w| {
+                        w.next()
+                            .write_oid(&ObjectIdentifier::from_slice(TST_INFO_OID))
+                    });
+                });
+                w.next().write_sequence(|w| {
+                    w.next()
+                        .write_oid(&ObjectIdentifier::from_slice(MESSAGE_DIGEST_OID));
+                    w.next()
+                        .write_set(|w| w.next().write_bytes(digest(&SHA256, &tst_info).as_ref()));
+                });
+                w.next().write_sequence(|w| {
+                    w.next()
+                        .write_oid(&ObjectIdentifier::from_slice(SIGNING_CERTIFICATE_V2_OID));
+                    w.next().write_set(|w| {
+                        w.next().write_sequence(|w| {
+                            w.next().write_sequence(|w| {
+                                w.next().write_sequence(|w| {
+                                    w.next()
+                                        .write_bytes(digest(&SHA256, &self.certificate).as_ref())
+                                })
+                            })
+                        })
+                    });
+                });
+            })
+        });
+        let signature = self
+            .signing_key
+            .sign(&SystemRandom::new(), &signed_attrs)
+            .unwrap();
+        let mut implicit_attrs = signed_attrs.clone();
+        implicit_attrs[0] = 0xA0;
+
+        let mut certificates = vec![0xA0];
+        certificates.extend(der_length(self.certificate.len()));
+        certificates.extend(&self.certificate);
+
+        yasna::construct_der(|w| {
+            w.write_sequence(|w| {
+                w.next()
+                    .write_oid(&ObjectIdentifier::from_slice(SIGNED_DATA_OID));
+                w.next().write_tagged(Tag::context(0), |w| {
+                    w.write_sequence(|w| {
+                        w.next().write_u8(3);
+                        w.next().write_set(|w| {
+                            w.next().write_sequence(|w| {
+                                w.next()
+                                    .write_oid(&ObjectIdentifier::from_slice(SHA256_OID))
+                            })
+                        });
+                        w.next().write_sequence(|w| {
+                            w.next()
+                                .write_oid(&ObjectIdentifier::from_slice(TST_INFO_OID));
+                            w.next()
+                                .write_tagged(Tag::context(0), |w| w.write_bytes(&tst_info));
+                        });
+                        w.next().write_der(&certificates);
+                        w.next().write_set(|w| {
+                            w.next().write_sequence(|w| {
+                                w.next().write_u8(3);
+                                w.next().write_tagged_implicit(Tag::context(0), |w| {
+                                    w.write_bytes(b"inheritx-test-tsa")
+                                });
+                                w.next().write_sequence(|w| {
+                                    w.next()
+                                        .write_oid(&ObjectIdentifier::from_slice(SHA256_OID))
+                                });
+                                w.next().write_der(&implicit_attrs);
+                                w.next().write_sequence(|w| {
+                                    w.next().write_oid(&ObjectIdentifier::from_slice(
+                                        ECDSA_WITH_SHA256_OID,
+                                    ))
+                                });
+                                w.next().write_bytes(signature.as_ref());
+                            })
+                        });
+                    })
+                });
+            })
+        })
+    }
+}
+
+fn der_length(len: usize) -> Vec<u8> {
+    if len < 0x80 {
+        vec![len as u8]
+    } else {
+        let bytes = (len as u32).to_be_bytes();
+        let skip = bytes.iter().take_while(|b| **b == 0).count();
+        let mut out = vec![0x80 | (4 - skip) as u8];
+        out.extend(&bytes[skip..]);
+        out
+    }
+}
+
+fn rand_serial() -> u64 {
+    u64::from_be_bytes(Uuid::new_v4().as_bytes()[..8].try_into().unwrap()) >> 1
+}
+
+/// Message imprint and nonce from a `TimeStampReq`.
+fn parse_request(request: &[u8]) -> (Vec<u8>, Option<u64>) {
+    yasna::parse_der(request, |r| {
+        r.read_sequence(|r| {
+            r.next().read_u8()?;
+            let imprint = r.next().read_sequence(|r| {
+                r.next().read_sequence(|r| {
+                    r.next().read_oid()?;
+                    r.read_optional(|r| r.read_null())?;
+                    Ok(())
+                })?;
+                r.next().read_bytes()
+            })?;
+            let nonce = r.read_optional(|r| r.read_u64())?;
+            r.read_optional(|r| r.read_bool())?;
+            Ok((imprint, nonce))
+        })
+    })
+    .unwrap()
+}
+
+fn granted(token: &[u8]) -> Vec<u8> {
+    yasna::construct_der(|w| {
+        w.write_sequence(|w| {
+            w.next().write_sequence(|w| w.next().write_u8(0));
+            w.next().write_der(token);
+        })
+    })
+}
+
+async fn respond(
+    State(tsa): State<Arc<TestTsa>>,
+    Path(mode): Path<String>,
+    body: Bytes,
+) -> Vec<u8> {
+    let (imprint, nonce) = parse_request(&body);
+    match mode.as_str() {
+        "honest" => granted(&tsa.issue(&imprint, nonce)),
+        "wrong-imprint" => granted(&tsa.issue(&[0u8; 32], nonce)),
+        "replayed" => granted(&tsa.issue(&imprint, Some(42))),
+        _ => yasna::construct_der(|w| {
+            w.write_sequence(|w| {
+                w.next().write_sequence(|w| {
+                    w.next().write_u8(2);
+                    w.next()
+                        .write_sequence(|w| w.next().write_utf8_string("policy not supported"));
+                })
+            })
+        }),
+    }
+}
+
+/// Start the TSA stand-in and return its base URL and signing certificate.
+async fn start_tsa_stand_in() -> (String, Vec<u8>) {
+    let tsa = Arc::new(TestTsa::new());
+    let certificate = tsa.certificate.clone();
+    let app = Router::new()
+        .route("/tsa/:mode", post(respond))
+        .with_state(tsa);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    (format!("http://{addr}/tsa"), certificate)
+}
+
+#[tokio::test]
+async fn client_accepts_only_valid_tokens_over_the_requested_hash() {
+    let (url, certificate) = start_tsa_stand_in().await;
+    let imprint = digest(&SHA256, b"last will and testament");
+
+    let client =
+        TimestampClient::new(format!("{url}/honest")).with_trusted_certificate(certificate.clone());
+    let (_, info) = client.timestamp(imprint.as_ref()).await.unwrap();
+    assert_eq!(
+        info.signer_certificate_sha256,
+        hex::encode(digest(&SHA256, &certificate))
+    );
+    assert_eq!(info.policy_oid, "1.3.6.1.4.1.99999.1");
+    assert!((chrono::Utc::now() - info.gen_time).num_seconds() < 60);
+
+    // A token from a different TSA key is refused when the certificate is pinned.
+    let (other_url, _) = start_tsa_stand_in().await;
+    let pinned =
+        TimestampClient::new(format!("{other_url}/honest")).with_trusted_certificate(certificate);
+    assert!(pinned.timestamp(imprint.as_ref()).await.is_err());
+
+    for mode in ["wrong-imprint", "replayed", "rejection"] {
+        let client = TimestampClient::new(format!("{url}/{mode}"));
+        assert!(
+            client.timestamp(imprint.as_ref()).await.is_err(),
+            "{mode} response was accepted"
+        );
+    }
+}
+
+async fn verify(ctx: &helpers::TestContext, document_id: Uuid) -> serde_json::Value {
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/will/documents/{document_id}/verify"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice::<serde_json::Value>(&body).unwrap()["data"].clone()
+}
+
+async fn create_will(ctx: &helpers::TestContext) -> (Uuid, Uuid, Uuid) {
+    let email = format!("{}@timestamp.test", Uuid::new_v4());
+    let user_id = helpers::create_test_user(&ctx.pool, &email).await.unwrap();
+    let plan_id = helpers::create_test_plan(&ctx.pool, user_id).await.unwrap();
+    let document_id = helpers::create_test_will_document(&ctx.pool, user_id, plan_id)
+        .await
+        .unwrap();
+    (user_id, plan_id, document_id)
+}
+
+#[tokio::test]
+async fn finalized_will_carries_timestamp_in_verification_results() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let (url, _) = start_tsa_stand_in().await;
+    let client = TimestampClient::new(format!("{url}/honest"));
+    let (user_id, plan_id, document_id) = create_will(&ctx).await;
+
+    WillVersionService::finalize_version(&ctx.pool, Some(&client), plan_id, user_id, 1)
+        .await
+        .unwrap();
+
+    let stored = WillTimestampService::get_for_document(&ctx.pool, document_id)
+        .await
+        .unwrap()
+        .expect("finalized will was not timestamped");
+
+    let result = verify(&ctx, document_id).await;
+    let timestamp = &result["timestamp"];
+    assert_eq!(timestamp["is_valid"], true);
+    assert_eq!(timestamp["serial_number"], stored.serial_number);
+    assert_eq!(timestamp["tsa_url"], format!("{url}/honest"));
+    assert!(timestamp["token_base64"].as_str().unwrap().len() > 100);
+
+    // Once the stored hash changes, the token no longer covers it.
+    sqlx::query("UPDATE will_documents SET will_hash = $1 WHERE id = $2")
+        .bind("b".repeat(64))
+        .bind(document_id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    let result = verify(&ctx, document_id).await;
+    assert_eq!(result["timestamp"]["is_valid"], false);
+    assert_eq!(
+        result["timestamp"]["error"],
+        "timestamp does not cover the will hash"
+    );
+}
+
+#[tokio::test]
+async fn wills_finalized_during_tsa_outage_are_timestamped_later() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let (url, _) = start_tsa_stand_in().await;
+    let (user_id, plan_id, document_id) = create_will(&ctx).await;
+
+    let unreachable = TimestampClient::new("http://127.0.0.1:9/tsa".to_string());
+    let summary =
+        WillVersionService::finalize_version(&ctx.pool, Some(&unreachable), plan_id, user_id, 1)
+            .await
+            .unwrap();
+    assert_eq!(summary.status, "finalized");
+    assert!(verify(&ctx, document_id).await["timestamp"].is_null());
+
+    let service = WillTimestampService::new(
+        ctx.pool.clone(),
+        Arc::new(TimestampClient::new(format!("{url}/honest"))),
+    );
+    for _ in 0..20 {
+        service.timestamp_pending().await.unwrap();
+        if !verify(&ctx, document_id).await["timestamp"].is_null() {
+            return;
+        }
+    }
+    panic!("finalized will was never timestamped by the backfill job");
+}
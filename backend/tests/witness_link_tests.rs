@@ -0,0 +1,274 @@
+//! Integration tests for email magic-link witness attestations.
+
+mod helpers;
+
+use async_trait::async_trait;
+use axum::{
+    body::Body,
+    http::{Response, StatusCode},
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use inheritx_backend::api_error::ApiError;
+use inheritx_backend::message_delivery::{EmailTransport, MessageChannels};
+use inheritx_backend::will_pdf::{
+    BeneficiaryEntry, WillDocumentInput, WillPdfService, WillTemplate,
+};
+use inheritx_backend::witness_link::{
+    attestation_message, WitnessInvitationService, WitnessLinkSigner,
+};
+use ring::rand::SystemRandom;
+use ring::signature::{self, Ed25519KeyPair, KeyPair};
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Records every email instead of sending it.
+#[derive(Default)]
+struct CapturedEmails(Mutex<Vec<(String, String)>>);
+
+#[async_trait]
+impl EmailTransport for CapturedEmails {
+    async fn send(&self, to: &str, _subject: &str, body: &str) -> Result<String, ApiError> {
+        self.0
+            .lock()
+            .unwrap()
+            .push((to.to_string(), body.to_string()));
+        Ok("250 captured".to_string())
+    }
+}
+
+impl CapturedEmails {
+    /// The link token from the most recent email to `to`.
+    fn last_token(&self, to: &str) -> String {
+        let emails = self.0.lock().unwrap();
+        let (_, body) = emails.iter().rev().find(|(rcpt, _)| rcpt == to).unwrap();
+        let start = body.find("/api/will/witness-invitations/").unwrap()
+            + "/api/will/witness-invitations/".len();
+        body[start..].lines().next().unwrap().to_string()
+    }
+}
+
+fn link_signer() -> WitnessLinkSigner {
+    let secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "test-jwt-secret".to_string());
+    WitnessLinkSigner::new(secret.as_bytes())
+}
+
+async fn send(
+    ctx: &helpers::TestContext,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+) -> Response<Body> {
+    let request = helpers::request(method, uri, token)
+        .header("X-Forwarded-For", "203.0.113.7, 10.0.0.1")
+        .header("User-Agent", "WitnessBrowser/1.0");
+    helpers::dispatch(ctx, request, body).await
+}
+
+async fn invite(ctx: &helpers::TestContext, token: &str, document_id: Uuid, email: &str) -> Uuid {
+    let response = send(
+        ctx,
+        "POST",
+        &format!("/api/will/documents/{document_id}/witnesses"),
+        Some(token),
+        Some(json!({ "email": email })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    helpers::json_body(response).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap()
+}
+
+fn attestation(name: &str) -> Value {
+    json!({
+        "full_name": name,
+        "postal_address": "1 Witness Lane, Springfield",
+        "statement": "I saw the testator sign this will.",
+    })
+}
+
+#[tokio::test]
+async fn email_witnesses_attest_through_single_use_links() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let email = format!("{}@testator.test", Uuid::new_v4());
+    let owner_id = helpers::create_test_user(&ctx.pool, &email).await.unwrap();
+    let owner_token = helpers::user_token(owner_id, &email);
+    let plan_id = helpers::create_test_plan(&ctx.pool, owner_id)
+        .await
+        .unwrap();
+    let document = WillPdfService::generate(
+        &ctx.pool,
+        owner_id,
+        &WillDocumentInput {
+            plan_id,
+            owner_name: "Ada Testator".to_string(),
+            owner_wallet: "GABC".to_string(),
+            vault_id: "vault-1".to_string(),
+            beneficiaries: vec![BeneficiaryEntry {
+                name: "Heir".to_string(),
+                wallet_address: "GHEIR".to_string(),
+                allocation_percent: Decimal::new(100, 0),
+                relationship: None,
+            }],
+            execution_rules: None,
+            template: WillTemplate::UkJurisdiction,
+            jurisdiction: Some("UK".to_string()),
+            will_hash_reference: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let emails = Arc::new(CapturedEmails::default());
+    let channels = MessageChannels::new(Some(emails.clone()), "http://inheritx.test");
+    let signer = link_signer();
+
+    // --- A server-managed key signs for the first witness -----------------
+    let neighbour = format!("{}@neighbour.test", Uuid::new_v4());
+    let neighbour_id = invite(&ctx, &owner_token, document.document_id, &neighbour).await;
+    WitnessInvitationService::send(&ctx.pool, &channels, &signer, owner_id, neighbour_id)
+        .await
+        .unwrap();
+    let link = emails.last_token(&neighbour);
+
+    let mut forged = link.clone();
+    let last = if forged.ends_with('0') { "1" } else { "0" };
+    forged.replace_range(forged.len() - 1.., last);
+    let uri = format!("/api/will/witness-invitations/{forged}");
+    let response = send(&ctx, "GET", &uri, None, None).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let uri = format!("/api/will/witness-invitations/{link}");
+    let response = send(&ctx, "GET", &uri, None, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let view = helpers::json_body(response).await["data"].clone();
+    assert_eq!(view["will_hash"], document.will_hash);
+    assert_eq!(view["witness_id"], neighbour_id.to_string());
+
+    let mut request = attestation("Nell Neighbour");
+    request["signing_method"] = json!("server_key");
+    let attest_uri = format!("/api/will/witness-invitations/{link}/attest");
+    let response = send(&ctx, "POST", &attest_uri, None, Some(request.clone())).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let attested = helpers::json_body(response).await["data"].clone();
+    assert_eq!(attested["ip_address"], "203.0.113.7");
+    assert_eq!(attested["user_agent"], "WitnessBrowser/1.0");
+    assert_eq!(attested["signing_method"], "server_key");
+
+    let message = attestation_message(
+        neighbour_id,
+        &document.will_hash,
+        "Nell Neighbour",
+        "1 Witness Lane, Springfield",
+        "I saw the testator sign this will.",
+    );
+    let public_key = hex::decode(attested["public_key_hex"].as_str().unwrap()).unwrap();
+    let signature_bytes = hex::decode(attested["signature_hex"].as_str().unwrap()).unwrap();
+    signature::UnparsedPublicKey::new(&signature::ED25519, &public_key)
+        .verify(message.as_bytes(), &signature_bytes)
+        .unwrap();
+    let stored_keys: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM will_witness_keys WHERE witness_id = $1")
+            .bind(neighbour_id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert_eq!(stored_keys, 1);
+
+    // The link is single use.
+    let response = send(&ctx, "POST", &attest_uri, None, Some(request)).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    // --- A browser key signs for the second witness -----------------------
+    let colleague = format!("{}@colleague.test", Uuid::new_v4());
+    let colleague_id = invite(&ctx, &owner_token, document.document_id, &colleague).await;
+    WitnessInvitationService::send(&ctx.pool, &channels, &signer, owner_id, colleague_id)
+        .await
+        .unwrap();
+    let stale = emails.last_token(&colleague);
+    let response = send(
+        &ctx,
+        "POST",
+        &format!("/api/will/witnesses/{colleague_id}/invitation"),
+        Some(&owner_token),
+        None,
+    )
+    .await;
+    // The app has no mail transport configured, so the resend fails but a
+    // resend through our capture still supersedes the first link.
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    WitnessInvitationService::send(&ctx.pool, &channels, &signer, owner_id, colleague_id)
+        .await
+        .unwrap();
+    let link = emails.last_token(&colleague);
+    let response = send(
+        &ctx,
+        "GET",
+        &format!("/api/will/witness-invitations/{stale}"),
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+    let browser_key = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+    let message = attestation_message(
+        colleague_id,
+        &document.will_hash,
+        "Cole League",
+        "1 Witness Lane, Springfield",
+        "I saw the testator sign this will.",
+    );
+    let mut request = attestation("Cole League");
+    request["signing_method"] = json!("browser_key");
+    request["public_key"] = json!(hex::encode(browser_key.public_key().as_ref()));
+    request["signature_hex"] = json!(hex::encode(browser_key.sign(b"something else").as_ref()));
+    let attest_uri = format!("/api/will/witness-invitations/{link}/attest");
+    let response = send(&ctx, "POST", &attest_uri, None, Some(request.clone())).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    request["signature_hex"] = json!(hex::encode(browser_key.sign(message.as_bytes()).as_ref()));
+    let response = send(&ctx, "POST", &attest_uri, None, Some(request)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // --- Both attestations are recorded and on the will's pages -----------
+    let response = send(
+        &ctx,
+        "GET",
+        &format!(
+            "/api/will/documents/{}/witnesses/status",
+            document.document_id
+        ),
+        Some(&owner_token),
+        None,
+    )
+    .await;
+    assert_eq!(helpers::json_body(response).await["data"]["signed"], 2);
+    let response = send(
+        &ctx,
+        "GET",
+        &format!("/api/will/documents/{}/attestations", document.document_id),
+        Some(&owner_token),
+        None,
+    )
+    .await;
+    assert_eq!(helpers::json_body(response).await["count"], 2);
+
+    let stored = WillPdfService::get_document(&ctx.pool, document.document_id, owner_id)
+        .await
+        .unwrap();
+    let pdf = lopdf::Document::load_mem(&BASE64.decode(stored.pdf_base64).unwrap()).unwrap();
+    assert_eq!(pdf.get_pages().len(), 3);
+    let page = pdf.extract_text(&[2]).unwrap();
+    assert!(page.contains("WITNESS ATTESTATION"));
+    assert!(page.contains("Nell Neighbour"));
+    assert!(page.contains("203.0.113.7"));
+}
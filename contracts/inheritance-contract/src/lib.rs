@@ -1,4 +1,5 @@
 #![no_std]
+use soroban_sdk::xdr::ToXdr;
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, log, symbol_short, token, vec, Address,
     Bytes, BytesN, Env, FromVal, IntoVal, InvokeError, String, Symbol, Val, Vec,
@@ -13,6 +14,9 @@ const EMERGENCY_TRANSFER_LIMIT_BP: u32 = 1000;
 /// Emergency cooldown period in seconds (24 hours)
 const EMERGENCY_COOLDOWN_PERIOD: u64 = 86400;
 
+/// Default break-glass waiting period in seconds (72 hours)
+const DEFAULT_BREAK_GLASS_DELAY: u64 = 259200;
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DistributionMethod {
@@ -153,6 +157,13 @@ pub enum DataKey {
     WillFinalizedAt(u64, u32),        // (plan_id, version) -> u64 timestamp
     WillWitnesses(u64),               // plan_id -> Vec<Address>
     WitnessSignature(u64, Address),   // (plan_id, witness) -> u64 (signed_at)
+    WillTestators(u64),               // plan_id -> WillTestatorConfig (multi-party wills)
+    TestatorSignature(u64, Address),  // (plan_id, testator) -> WillSignatureProof
+    BreakGlassDelay(u64),             // plan_id -> u64 (waiting period in seconds)
+    BreakGlassRequest(u64),           // plan_id -> BreakGlassRequest (pending request)
+    EmergencyScope(u64),              // plan_id -> EmergencyScope (what emergency access may do)
+    AuditAnchorHead(String),          // stream -> u64 (last audit entry anchored)
+    AuditAnchor(String, u64),         // (stream, first_seq) -> AuditAnchor
     LendingContract,
     GovernanceContract,
 }
@@ -203,6 +214,26 @@ pub struct EmergencyAccessRecord {
     pub activated_at: u64,
 }
 
+/// What a trusted contact with emergency access may withdraw: payments to
+/// the listed payees only, up to a total spending cap.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmergencyScope {
+    pub payees: Vec<Address>,
+    pub spending_cap: u64,
+    pub spent: u64,
+}
+
+/// A break-glass access request waiting out the owner's veto window.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BreakGlassRequest {
+    pub plan_id: u64,
+    pub trusted_contact: Address,
+    pub requested_at: u64,
+    pub effective_at: u64,
+}
+
 // Events for beneficiary operations
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -462,6 +493,33 @@ pub struct EmergencyContactRemovedEvent {
     pub plan_id: u64,
     pub contact: Address,
 }
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BreakGlassRequestedEvent {
+    pub plan_id: u64,
+    pub trusted_contact: Address,
+    pub effective_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmergencyBillPaidEvent {
+    pub plan_id: u64,
+    pub trusted_contact: Address,
+    pub payee: Address,
+    pub amount: u64,
+    pub spent: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BreakGlassVetoedEvent {
+    pub plan_id: u64,
+    pub trusted_contact: Address,
+    pub vetoed_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct WillVersionInfo {
@@ -471,6 +529,25 @@ pub struct WillVersionInfo {
     pub is_active: bool,
 }
 
+/// Merkle root over a contiguous run of an off-chain audit log stream.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditAnchor {
+    pub first_seq: u64,
+    pub last_seq: u64,
+    pub root: BytesN<32>,
+    pub anchored_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditRootAnchoredEvent {
+    pub stream: String,
+    pub first_seq: u64,
+    pub last_seq: u64,
+    pub root: BytesN<32>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct WillHashStoredEvent {
@@ -517,6 +594,40 @@ pub struct WillSignatureProof {
     pub signed_at: u64,
 }
 
+/// How a will with several testators behaves when one of them revokes it.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WillType {
+    /// One instrument for all testators: revocation by any party revokes it.
+    Joint,
+    /// Reciprocal wills bound by agreement: revocation by any party
+    /// invalidates every party's signature.
+    Mutual,
+    /// Separate, mirrored provisions: revocation only withdraws the revoking
+    /// testator's signature.
+    Mirror,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WillTestatorConfig {
+    pub will_type: WillType,
+    /// Every testator, starting with the plan owner.
+    pub testators: Vec<Address>,
+    /// Testators who have revoked. For joint and mutual wills any entry
+    /// revokes the whole will.
+    pub revoked: Vec<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WillRevokedEvent {
+    pub vault_id: u64,
+    pub revoked_by: Address,
+    pub will_type: WillType,
+    pub invalidated_signatures: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct WillSignedEvent {
@@ -2083,6 +2194,18 @@ impl InheritanceContract {
             .unwrap_or(Vec::new(&env))
     }
 
+    /// Approve emergency access for a trusted contact as one of the plan's
+    /// guardians.
+    ///
+    /// Once the guardian threshold is met this opens a break-glass request, as
+    /// `request_break_glass` does: access only starts through
+    /// `complete_break_glass` after the plan's waiting period, and the owner
+    /// can veto it until then.
+    ///
+    /// # Errors
+    /// - Unauthorized: If the caller is not a guardian of the plan
+    /// - AlreadyApproved: If the guardian already approved this contact
+    /// - EmergencyAccessAlreadyActive: If access is active or a request is already pending
     pub fn approve_emergency_access(
         env: Env,
         guardian: Address,
@@ -2092,8 +2215,15 @@ impl InheritanceContract {
         guardian.require_auth();
         let _plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
 
-        let key_access = DataKey::EmergencyAccess(plan_id);
-        if env.storage().persistent().has(&key_access) {
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::EmergencyAccess(plan_id))
+            || env
+                .storage()
+                .persistent()
+                .has(&DataKey::BreakGlassRequest(plan_id))
+        {
             return Err(InheritanceError::EmergencyAccessAlreadyActive);
         }
 
@@ -2147,30 +2277,10 @@ impl InheritanceContract {
         );
 
         if approvals.len() >= config.threshold {
-            let now = env.ledger().timestamp();
-            let emergency_access = EmergencyAccessRecord {
-                plan_id,
-                trusted_contact: trusted_contact.clone(),
-                activated_at: now,
-            };
-            env.storage()
-                .persistent()
-                .set(&key_access, &emergency_access);
-
-            env.events().publish(
-                (symbol_short!("EMERG"), symbol_short!("ACTIV")),
-                EmergencyAccessActivatedEvent {
-                    plan_id,
-                    trusted_contact,
-                    activated_at: now,
-                },
-            );
-            log!(
-                &env,
-                "Emergency access activated for plan {} at timestamp {}",
-                plan_id,
-                now
-            );
+            // The quorum is spent on this request; a vetoed request needs a
+            // fresh round of approvals.
+            env.storage().persistent().remove(&key_approvals);
+            Self::open_break_glass_request(&env, plan_id, trusted_contact);
         }
         Ok(())
     }
@@ -2263,6 +2373,317 @@ impl InheritanceContract {
         Ok(())
     }
 
+    /// Set the break-glass waiting period for a plan, in seconds.
+    /// Only the plan owner can change it; it applies to requests made afterwards.
+    pub fn set_break_glass_delay(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+        delay_seconds: u64,
+    ) -> Result<(), InheritanceError> {
+        owner.require_auth();
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if plan.owner != owner {
+            return Err(InheritanceError::Unauthorized);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::BreakGlassDelay(plan_id), &delay_seconds);
+        Ok(())
+    }
+
+    /// The break-glass waiting period for a plan, in seconds (72 hours unless set).
+    pub fn get_break_glass_delay(env: Env, plan_id: u64) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BreakGlassDelay(plan_id))
+            .unwrap_or(DEFAULT_BREAK_GLASS_DELAY)
+    }
+
+    /// Request break-glass emergency access as a registered emergency contact.
+    ///
+    /// Access does not start here: the request only becomes effective through
+    /// `complete_break_glass` once the plan's waiting period has passed, and the
+    /// owner can veto it until then.
+    ///
+    /// # Errors
+    /// - EmergencyContactNotFound: If the caller is not an emergency contact of the plan
+    /// - EmergencyAccessAlreadyActive: If access is active or a request is already pending
+    pub fn request_break_glass(
+        env: Env,
+        trusted_contact: Address,
+        plan_id: u64,
+    ) -> Result<BreakGlassRequest, InheritanceError> {
+        trusted_contact.require_auth();
+        let _plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+
+        if !Self::get_emergency_contacts(env.clone(), plan_id).contains(&trusted_contact) {
+            return Err(InheritanceError::EmergencyContactNotFound);
+        }
+        let key = DataKey::BreakGlassRequest(plan_id);
+        if Self::get_emergency_access(env.clone(), plan_id).is_some()
+            || env.storage().persistent().has(&key)
+        {
+            return Err(InheritanceError::EmergencyAccessAlreadyActive);
+        }
+
+        Ok(Self::open_break_glass_request(
+            &env,
+            plan_id,
+            trusted_contact,
+        ))
+    }
+
+    /// Record a break-glass request that becomes effective after the plan's
+    /// waiting period.
+    fn open_break_glass_request(
+        env: &Env,
+        plan_id: u64,
+        trusted_contact: Address,
+    ) -> BreakGlassRequest {
+        let now = env.ledger().timestamp();
+        let request = BreakGlassRequest {
+            plan_id,
+            trusted_contact: trusted_contact.clone(),
+            requested_at: now,
+            effective_at: now.saturating_add(Self::get_break_glass_delay(env.clone(), plan_id)),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::BreakGlassRequest(plan_id), &request);
+
+        env.events().publish(
+            (symbol_short!("EMERG"), symbol_short!("BG_REQ")),
+            BreakGlassRequestedEvent {
+                plan_id,
+                trusted_contact,
+                effective_at: request.effective_at,
+            },
+        );
+        log!(
+            env,
+            "Break-glass access requested for plan {}, effective at {}",
+            plan_id,
+            request.effective_at
+        );
+
+        request
+    }
+
+    /// Veto a pending break-glass request. Only the plan owner can veto.
+    ///
+    /// # Errors
+    /// - Unauthorized: If caller is not the plan owner
+    /// - EmergencyContactNotFound: If no request is pending
+    pub fn veto_break_glass(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+    ) -> Result<(), InheritanceError> {
+        owner.require_auth();
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if plan.owner != owner {
+            return Err(InheritanceError::Unauthorized);
+        }
+
+        let key = DataKey::BreakGlassRequest(plan_id);
+        let request: BreakGlassRequest = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(InheritanceError::EmergencyContactNotFound)?;
+        env.storage().persistent().remove(&key);
+
+        env.events().publish(
+            (symbol_short!("EMERG"), symbol_short!("BG_VETO")),
+            BreakGlassVetoedEvent {
+                plan_id,
+                trusted_contact: request.trusted_contact,
+                vetoed_at: env.ledger().timestamp(),
+            },
+        );
+        log!(&env, "Break-glass request vetoed for plan {}", plan_id);
+
+        Ok(())
+    }
+
+    /// Turn a pending break-glass request into emergency access once its
+    /// waiting period has passed without a veto.
+    ///
+    /// # Errors
+    /// - EmergencyContactNotFound: If no request is pending
+    /// - Unauthorized: If caller is not the contact who made the request
+    /// - EmergencyCooldownActive: If the waiting period has not yet passed
+    pub fn complete_break_glass(
+        env: Env,
+        trusted_contact: Address,
+        plan_id: u64,
+    ) -> Result<EmergencyAccessRecord, InheritanceError> {
+        trusted_contact.require_auth();
+
+        let key = DataKey::BreakGlassRequest(plan_id);
+        let request: BreakGlassRequest = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(InheritanceError::EmergencyContactNotFound)?;
+        if request.trusted_contact != trusted_contact {
+            return Err(InheritanceError::Unauthorized);
+        }
+        let now = env.ledger().timestamp();
+        if now < request.effective_at {
+            return Err(InheritanceError::EmergencyCooldownActive);
+        }
+
+        env.storage().persistent().remove(&key);
+        let emergency_access = EmergencyAccessRecord {
+            plan_id,
+            trusted_contact: trusted_contact.clone(),
+            activated_at: now,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::EmergencyAccess(plan_id), &emergency_access);
+
+        env.events().publish(
+            (symbol_short!("EMERG"), symbol_short!("ACTIV")),
+            EmergencyAccessActivatedEvent {
+                plan_id,
+                trusted_contact,
+                activated_at: now,
+            },
+        );
+        log!(
+            &env,
+            "Emergency access activated for plan {} at timestamp {}",
+            plan_id,
+            now
+        );
+
+        Ok(emergency_access)
+    }
+
+    /// The pending break-glass request for a plan, if any.
+    pub fn get_break_glass_request(env: Env, plan_id: u64) -> Option<BreakGlassRequest> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BreakGlassRequest(plan_id))
+    }
+
+    /// Limit what emergency access may withdraw from a plan: payments to the
+    /// given payees, up to `spending_cap` in total. Replacing the scope resets
+    /// the amount spent; a cap of 0 allows no withdrawals.
+    pub fn set_emergency_scope(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+        payees: Vec<Address>,
+        spending_cap: u64,
+    ) -> Result<(), InheritanceError> {
+        owner.require_auth();
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if plan.owner != owner {
+            return Err(InheritanceError::Unauthorized);
+        }
+        env.storage().persistent().set(
+            &DataKey::EmergencyScope(plan_id),
+            &EmergencyScope {
+                payees,
+                spending_cap,
+                spent: 0,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn get_emergency_scope(env: Env, plan_id: u64) -> Option<EmergencyScope> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::EmergencyScope(plan_id))
+    }
+
+    /// Pay a bill from the plan as the trusted contact holding emergency access.
+    ///
+    /// # Errors
+    /// - Unauthorized: If the caller has no emergency access, the plan has no
+    ///   emergency scope, or the payee is not one the owner allowed
+    /// - AllocationExceedsLimit: If the payment would exceed the spending cap
+    /// - InsufficientLiquidity: If the plan cannot cover the payment
+    pub fn emergency_withdraw(
+        env: Env,
+        trusted_contact: Address,
+        token: Address,
+        plan_id: u64,
+        payee: Address,
+        amount: u64,
+    ) -> Result<(), InheritanceError> {
+        trusted_contact.require_auth();
+        if amount == 0 {
+            return Err(InheritanceError::InvalidTotalAmount);
+        }
+        let mut plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+
+        match Self::get_emergency_access(env.clone(), plan_id) {
+            Some(record) if record.trusted_contact == trusted_contact => {}
+            _ => return Err(InheritanceError::Unauthorized),
+        }
+        let key = DataKey::EmergencyScope(plan_id);
+        let mut scope: EmergencyScope = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(InheritanceError::Unauthorized)?;
+        if !scope.payees.contains(&payee) {
+            return Err(InheritanceError::Unauthorized);
+        }
+        let spent = scope
+            .spent
+            .checked_add(amount)
+            .filter(|spent| *spent <= scope.spending_cap)
+            .ok_or(InheritanceError::AllocationExceedsLimit)?;
+
+        let available = plan.total_amount.saturating_sub(plan.total_loaned);
+        if amount > available {
+            return Err(InheritanceError::InsufficientLiquidity);
+        }
+
+        let contract_id = env.current_contract_address();
+        let args: Vec<Val> = vec![
+            &env,
+            contract_id.into_val(&env),
+            payee.clone().into_val(&env),
+            (amount as i128).into_val(&env),
+        ];
+        let res =
+            env.try_invoke_contract::<(), InvokeError>(&token, &symbol_short!("transfer"), args);
+        if res.is_err() {
+            return Err(InheritanceError::FeeTransferFailed);
+        }
+
+        plan.total_amount -= amount;
+        Self::store_plan(&env, plan_id, &plan);
+        scope.spent = spent;
+        env.storage().persistent().set(&key, &scope);
+
+        env.events().publish(
+            (symbol_short!("EMERG"), symbol_short!("BILL_PAY")),
+            EmergencyBillPaidEvent {
+                plan_id,
+                trusted_contact,
+                payee,
+                amount,
+                spent,
+            },
+        );
+        log!(
+            &env,
+            "Emergency bill of {} paid from plan {}",
+            amount,
+            plan_id
+        );
+        Ok(())
+    }
+
     /// Retrieve a specific deactivated plan (User)
     ///
     /// # Arguments
@@ -2796,6 +3217,70 @@ impl InheritanceContract {
         Ok(())
     }
 
+    // ── Audit Log Anchoring ──
+
+    /// Anchor the Merkle root of audit entries `first_seq..=last_seq` of an
+    /// off-chain audit log stream. Anchors must cover each stream contiguously,
+    /// so once a range is anchored it cannot be replaced or skipped.
+    ///
+    /// # Errors
+    /// - NotAdmin: If the caller is not the contract admin
+    /// - VerificationFailed: If the range does not start right after the
+    ///   stream's last anchored entry
+    pub fn anchor_audit_root(
+        env: Env,
+        admin: Address,
+        stream: String,
+        first_seq: u64,
+        last_seq: u64,
+        root: BytesN<32>,
+    ) -> Result<(), InheritanceError> {
+        Self::require_admin(&env, &admin)?;
+
+        let head_key = DataKey::AuditAnchorHead(stream.clone());
+        let head: u64 = env.storage().persistent().get(&head_key).unwrap_or(0);
+        if first_seq != head + 1 || last_seq < first_seq {
+            return Err(InheritanceError::VerificationFailed);
+        }
+
+        env.storage().persistent().set(
+            &DataKey::AuditAnchor(stream.clone(), first_seq),
+            &AuditAnchor {
+                first_seq,
+                last_seq,
+                root: root.clone(),
+                anchored_at: env.ledger().timestamp(),
+            },
+        );
+        env.storage().persistent().set(&head_key, &last_seq);
+
+        env.events().publish(
+            (symbol_short!("AUDIT"), symbol_short!("ANCHORED")),
+            AuditRootAnchoredEvent {
+                stream,
+                first_seq,
+                last_seq,
+                root,
+            },
+        );
+        Ok(())
+    }
+
+    /// Retrieve the anchor of a stream's range starting at `first_seq`.
+    pub fn get_audit_anchor(env: Env, stream: String, first_seq: u64) -> Option<AuditAnchor> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AuditAnchor(stream, first_seq))
+    }
+
+    /// The last audit entry of a stream covered by an anchor (0 if none).
+    pub fn get_audit_anchor_head(env: Env, stream: String) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AuditAnchorHead(stream))
+            .unwrap_or(0)
+    }
+
     // ── Will Management System (Issues #314–#317) ──
 
     /// Store a SHA-256 hash of a will document on-chain, mapped to a plan_id.
@@ -3041,11 +3526,128 @@ impl InheritanceContract {
 
     // ── Will Signature Verification (Issue #318) ──
 
-    /// Record that the vault owner has approved and signed a will.
+    /// Declare a joint, mutual or mirror will with the plan owner and
+    /// `co_testators` as its testators. Must be set before anyone signs.
+    pub fn set_will_testators(
+        env: Env,
+        owner: Address,
+        vault_id: u64,
+        will_type: WillType,
+        co_testators: Vec<Address>,
+    ) -> Result<(), InheritanceError> {
+        owner.require_auth();
+
+        let plan = Self::get_plan(&env, vault_id).ok_or(InheritanceError::PlanNotFound)?;
+        if plan.owner != owner {
+            return Err(InheritanceError::Unauthorized);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::WillSignature(vault_id))
+            || env
+                .storage()
+                .persistent()
+                .has(&DataKey::WillTestators(vault_id))
+        {
+            return Err(InheritanceError::WillAlreadyLinked);
+        }
+        if co_testators.is_empty() {
+            return Err(InheritanceError::MissingRequiredField);
+        }
+
+        let mut testators = Vec::new(&env);
+        testators.push_back(owner);
+        for testator in co_testators.iter() {
+            if testators.contains(&testator) {
+                return Err(InheritanceError::EmergencyContactAlreadyExists);
+            }
+            testators.push_back(testator);
+        }
+
+        env.storage().persistent().set(
+            &DataKey::WillTestators(vault_id),
+            &WillTestatorConfig {
+                will_type,
+                testators,
+                revoked: Vec::new(&env),
+            },
+        );
+        Ok(())
+    }
+
+    /// Get the testators of a multi-party will, if it has been declared.
+    pub fn get_will_testators(env: Env, vault_id: u64) -> Option<WillTestatorConfig> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::WillTestators(vault_id))
+    }
+
+    /// Get a testator's signature on a multi-party will.
+    pub fn get_testator_signature(
+        env: Env,
+        vault_id: u64,
+        testator: Address,
+    ) -> Option<WillSignatureProof> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TestatorSignature(vault_id, testator))
+    }
+
+    /// Withdraw a testator's consent to a multi-party will. Joint and mutual
+    /// wills lose every signature; a mirror will only loses the caller's.
+    /// Revoked testators cannot sign the vault's will again.
+    pub fn revoke_will(env: Env, testator: Address, vault_id: u64) -> Result<(), InheritanceError> {
+        testator.require_auth();
+
+        let key = DataKey::WillTestators(vault_id);
+        let mut config: WillTestatorConfig = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(InheritanceError::VaultNotFound)?;
+        if !config.testators.contains(&testator) {
+            return Err(InheritanceError::Unauthorized);
+        }
+        if config.revoked.contains(&testator)
+            || (config.will_type != WillType::Mirror && !config.revoked.is_empty())
+        {
+            return Err(InheritanceError::PlanNotActive); // Reuse for revoked will
+        }
+
+        let mut invalidated_signatures = 0u32;
+        for party in config.testators.iter() {
+            if config.will_type == WillType::Mirror && party != testator {
+                continue;
+            }
+            let sig_key = DataKey::TestatorSignature(vault_id, party);
+            if env.storage().persistent().has(&sig_key) {
+                env.storage().persistent().remove(&sig_key);
+                invalidated_signatures += 1;
+            }
+        }
+        config.revoked.push_back(testator.clone());
+        env.storage().persistent().set(&key, &config);
+
+        env.events().publish(
+            (symbol_short!("WILL"), symbol_short!("REVOKED")),
+            WillRevokedEvent {
+                vault_id,
+                revoked_by: testator,
+                will_type: config.will_type,
+                invalidated_signatures,
+            },
+        );
+        Ok(())
+    }
+
+    /// Record that a testator has approved and signed a will.
     ///
-    /// The caller must be the plan owner. A composite sig_hash is derived from
-    /// (vault_id, will_hash) to bind the signature to a specific will version and
-    /// prevent replay across different vaults or will documents.
+    /// The caller must be the plan owner, or one of the testators when a
+    /// multi-party will has been declared with [`Self::set_will_testators`].
+    /// A composite sig_hash is derived from (vault_id, will_hash), plus the
+    /// signer for multi-party wills, to bind the signature to a specific will
+    /// version and prevent replay across different vaults or will documents.
     pub fn sign_will(
         env: Env,
         owner: Address,
@@ -3054,8 +3656,16 @@ impl InheritanceContract {
     ) -> Result<(), InheritanceError> {
         owner.require_auth();
 
-        // Verify the plan exists and caller is the owner
         let plan = Self::get_plan(&env, vault_id).ok_or(InheritanceError::PlanNotFound)?;
+        if let Some(config) = env
+            .storage()
+            .persistent()
+            .get::<_, WillTestatorConfig>(&DataKey::WillTestators(vault_id))
+        {
+            return Self::sign_multi_party_will(&env, config, owner, vault_id, will_hash);
+        }
+
+        // Verify the caller is the owner
         if plan.owner != owner {
             return Err(InheritanceError::Unauthorized);
         }
@@ -3108,6 +3718,57 @@ impl InheritanceContract {
         Ok(())
     }
 
+    fn sign_multi_party_will(
+        env: &Env,
+        config: WillTestatorConfig,
+        signer: Address,
+        vault_id: u64,
+        will_hash: BytesN<32>,
+    ) -> Result<(), InheritanceError> {
+        if !config.testators.contains(&signer) {
+            return Err(InheritanceError::Unauthorized);
+        }
+        if config.revoked.contains(&signer)
+            || (config.will_type != WillType::Mirror && !config.revoked.is_empty())
+        {
+            return Err(InheritanceError::PlanNotActive); // Reuse for revoked will
+        }
+
+        let mut sig_input = Bytes::new(env);
+        for b in vault_id.to_be_bytes() {
+            sig_input.push_back(b);
+        }
+        for b in will_hash.to_array() {
+            sig_input.push_back(b);
+        }
+        sig_input.append(&signer.clone().to_xdr(env));
+        let sig_hash: BytesN<32> = env.crypto().sha256(&sig_input).into();
+
+        let used_key = DataKey::SignatureUsed(sig_hash.clone());
+        if env.storage().persistent().has(&used_key) {
+            return Err(InheritanceError::WillAlreadyFinalized);
+        }
+        env.storage().persistent().set(&used_key, &true);
+
+        let proof = WillSignatureProof {
+            vault_id,
+            will_hash,
+            signer: signer.clone(),
+            sig_hash,
+            signed_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(
+            &DataKey::TestatorSignature(vault_id, signer.clone()),
+            &proof,
+        );
+
+        env.events().publish(
+            (symbol_short!("WILL"), symbol_short!("SIGNED")),
+            WillSignedEvent { vault_id, signer },
+        );
+        Ok(())
+    }
+
     /// Retrieve the stored will signature proof for a vault.
     pub fn get_will_signature(env: Env, vault_id: u64) -> Option<WillSignatureProof> {
         env.storage()
@@ -3541,7 +4202,9 @@ impl InheritanceContract {
     /// Requirements:
     /// - Caller must be the plan owner.
     /// - The will version must exist.
-    /// - The owner must have signed the will (WillSignature must exist).
+    /// - The owner must have signed the will (WillSignature must exist), or
+    ///   for a multi-party will every testator must have signed and none
+    ///   revoked.
     /// - If witnesses are assigned, all must have signed.
     /// - Cannot finalize an already-finalized version.
     pub fn finalize_will(
@@ -3575,14 +4238,36 @@ impl InheritanceContract {
             return Err(InheritanceError::WillAlreadyFinalized);
         }
 
-        // Owner must have signed the will
-        if env
+        // Every testator must have signed the will
+        match env
             .storage()
             .persistent()
-            .get::<_, WillSignatureProof>(&DataKey::WillSignature(vault_id))
-            .is_none()
+            .get::<_, WillTestatorConfig>(&DataKey::WillTestators(vault_id))
         {
-            return Err(InheritanceError::WillNotVerified);
+            Some(config) => {
+                if !config.revoked.is_empty() {
+                    return Err(InheritanceError::PlanNotActive); // Reuse for revoked will
+                }
+                for testator in config.testators.iter() {
+                    if !env
+                        .storage()
+                        .persistent()
+                        .has(&DataKey::TestatorSignature(vault_id, testator))
+                    {
+                        return Err(InheritanceError::WillNotVerified);
+                    }
+                }
+            }
+            None => {
+                if env
+                    .storage()
+                    .persistent()
+                    .get::<_, WillSignatureProof>(&DataKey::WillSignature(vault_id))
+                    .is_none()
+                {
+                    return Err(InheritanceError::WillNotVerified);
+                }
+            }
         }
 
         // All assigned witnesses must have signed
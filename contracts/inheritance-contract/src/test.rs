@@ -2867,6 +2867,24 @@ fn test_claim_emergency_limit() {
     assert!(result.is_ok());
 }
 
+/// Grant emergency access with the owner as sole guardian, waiting out the
+/// break-glass delay that follows the guardians' approval.
+fn grant_emergency_access(
+    env: &Env,
+    client: &InheritanceContractClient,
+    owner: &Address,
+    plan_id: u64,
+    trusted_contact: &Address,
+) {
+    let guardians = soroban_sdk::vec![env, owner.clone()];
+    client.set_guardians(owner, &plan_id, &guardians, &1);
+    client.approve_emergency_access(owner, &plan_id, trusted_contact);
+    let request = client.get_break_glass_request(&plan_id).unwrap();
+    env.ledger()
+        .with_mut(|li| li.timestamp = request.effective_at);
+    client.complete_break_glass(trusted_contact, &plan_id);
+}
+
 #[test]
 fn test_emergency_access_events() {
     let env = Env::default();
@@ -2889,9 +2907,7 @@ fn test_emergency_access_events() {
     let plan_id = 1u64;
 
     // 1. Test Activation Event
-    let guardians: soroban_sdk::Vec<soroban_sdk::Address> = soroban_sdk::vec![&env, user.clone()];
-    client.set_guardians(&user, &plan_id, &guardians, &1);
-    client.approve_emergency_access(&user, &plan_id, &trusted_contact);
+    grant_emergency_access(&env, &client, &user, plan_id, &trusted_contact);
 
     let events = env.events().all();
     let activation_event = events.get(events.len() - 1).unwrap();
@@ -2935,13 +2951,11 @@ fn test_emergency_access_expiration() {
     let plan_id = 1u64;
 
     // Activate
-    let guardians: soroban_sdk::Vec<soroban_sdk::Address> = soroban_sdk::vec![&env, user.clone()];
-    client.set_guardians(&user, &plan_id, &guardians, &1);
-    client.approve_emergency_access(&user, &plan_id, &trusted_contact);
+    grant_emergency_access(&env, &client, &user, plan_id, &trusted_contact);
     assert!(client.get_emergency_access(&plan_id).is_some());
 
     // Fast forward 604801 seconds (7 days + 1s)
-    env.ledger().set_timestamp(604801);
+    env.ledger().with_mut(|li| li.timestamp += 604801);
 
     // Call any function that triggers the check (e.g. get_emergency_access)
     assert!(client.get_emergency_access(&plan_id).is_none());
@@ -2982,9 +2996,7 @@ fn test_emergency_withdrawal_fails() {
     assert_eq!(token_helper.balance(&user), 10_000_000 - 10000 - 5000);
 
     // Activate emergency access
-    let guardians: soroban_sdk::Vec<soroban_sdk::Address> = soroban_sdk::vec![&env, user.clone()];
-    client.set_guardians(&user, &plan_id, &guardians, &1);
-    client.approve_emergency_access(&user, &plan_id, &trusted_contact);
+    grant_emergency_access(&env, &client, &user, plan_id, &trusted_contact);
 
     // Trusted contact withdraws (should fail)
     let result = client.try_withdraw(&trusted_contact, &token_id, &plan_id, &2000);
@@ -3021,12 +3033,10 @@ fn test_emergency_withdrawal_fails_after_expiration() {
     client.deposit(&user, &token_id, &plan_id, &5000);
 
     // Activate
-    let guardians: soroban_sdk::Vec<soroban_sdk::Address> = soroban_sdk::vec![&env, user.clone()];
-    client.set_guardians(&user, &plan_id, &guardians, &1);
-    client.approve_emergency_access(&user, &plan_id, &trusted_contact);
+    grant_emergency_access(&env, &client, &user, plan_id, &trusted_contact);
 
     // Fast forward 7 days + 1s
-    env.ledger().set_timestamp(604801);
+    env.ledger().with_mut(|li| li.timestamp += 604801);
 
     // Withdrawal should fail
     let result = client.try_withdraw(&trusted_contact, &token_id, &plan_id, &2000);
@@ -3060,9 +3070,7 @@ fn test_emergency_deposit_fails() {
     let plan_id = 1u64;
 
     // Activate
-    let guardians: soroban_sdk::Vec<soroban_sdk::Address> = soroban_sdk::vec![&env, user.clone()];
-    client.set_guardians(&user, &plan_id, &guardians, &1);
-    client.approve_emergency_access(&user, &plan_id, &trusted_contact);
+    grant_emergency_access(&env, &client, &user, plan_id, &trusted_contact);
 
     // Trusted contact deposits (should fail)
     let result = client.try_deposit(&trusted_contact, &token_id, &plan_id, &500);
@@ -3097,9 +3105,7 @@ fn test_emergency_view_plan_success() {
     let plan_id = 1u64;
 
     // Activate emergency access
-    let guardians: soroban_sdk::Vec<soroban_sdk::Address> = soroban_sdk::vec![&env, user.clone()];
-    client.set_guardians(&user, &plan_id, &guardians, &1);
-    client.approve_emergency_access(&user, &plan_id, &trusted_contact);
+    grant_emergency_access(&env, &client, &user, plan_id, &trusted_contact);
 
     // Trusted contact can view plan
     let plan = client.get_user_plan(&trusted_contact, &plan_id);
@@ -3127,9 +3133,7 @@ fn test_emergency_trigger_inheritance_success() {
     let plan_id = 1u64;
 
     // Activate emergency access
-    let guardians: soroban_sdk::Vec<soroban_sdk::Address> = soroban_sdk::vec![&env, user.clone()];
-    client.set_guardians(&user, &plan_id, &guardians, &1);
-    client.approve_emergency_access(&user, &plan_id, &trusted_contact);
+    grant_emergency_access(&env, &client, &user, plan_id, &trusted_contact);
 
     // Trusted contact can trigger inheritance
     client.trigger_inheritance(&trusted_contact, &plan_id);
@@ -3187,9 +3191,7 @@ fn test_instant_revocation_by_owner() {
     let plan_id = 1u64;
 
     // 1. Activate emergency access
-    let guardians: soroban_sdk::Vec<soroban_sdk::Address> = soroban_sdk::vec![&env, user.clone()];
-    client.set_guardians(&user, &plan_id, &guardians, &1);
-    client.approve_emergency_access(&user, &plan_id, &trusted_contact);
+    grant_emergency_access(&env, &client, &user, plan_id, &trusted_contact);
 
     // 2. Verify it's active
     assert!(client.get_emergency_access(&plan_id).is_some());
@@ -3243,11 +3245,68 @@ fn test_multi_guardian_approval_success() {
     client.approve_emergency_access(&guardian_1, &plan_id, &trusted_contact);
     assert!(client.get_emergency_access(&plan_id).is_none());
 
-    // Second guardian approves
+    // Second guardian approves, which opens a break-glass request
     client.approve_emergency_access(&guardian_2, &plan_id, &trusted_contact);
+    let request = client.get_break_glass_request(&plan_id).unwrap();
+    assert_eq!(request.trusted_contact, trusted_contact);
+    assert_eq!(
+        request.effective_at,
+        request.requested_at + client.get_break_glass_delay(&plan_id)
+    );
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = request.effective_at);
+    client.complete_break_glass(&trusted_contact, &plan_id);
     assert!(client.get_emergency_access(&plan_id).is_some());
 }
 
+#[test]
+fn test_guardian_approval_waits_out_break_glass_delay() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_id, _admin, user) = setup_with_token_and_admin(&env);
+    let guardian_1 = create_test_address(&env, 101);
+    let guardian_2 = create_test_address(&env, 102);
+    let trusted_contact = create_test_address(&env, 99);
+    let plan_id = create_plan_and_get_id(&env, &client, &token_id, &user);
+
+    let guardians = soroban_sdk::vec![&env, guardian_1.clone(), guardian_2.clone()];
+    client.set_guardians(&user, &plan_id, &guardians, &2);
+    client.approve_emergency_access(&guardian_1, &plan_id, &trusted_contact);
+    client.approve_emergency_access(&guardian_2, &plan_id, &trusted_contact);
+
+    // A guardian quorum alone grants nothing before the waiting period.
+    let request = client.get_break_glass_request(&plan_id).unwrap();
+    assert!(client.get_emergency_access(&plan_id).is_none());
+    let res = client.try_get_user_plan(&trusted_contact, &plan_id);
+    assert_eq!(res.err().unwrap(), Ok(InheritanceError::Unauthorized));
+    env.ledger()
+        .with_mut(|li| li.timestamp = request.effective_at - 1);
+    let res = client.try_complete_break_glass(&trusted_contact, &plan_id);
+    assert_eq!(
+        res.err().unwrap(),
+        Ok(InheritanceError::EmergencyCooldownActive)
+    );
+    let res = client.try_approve_emergency_access(&guardian_1, &plan_id, &trusted_contact);
+    assert_eq!(
+        res.err().unwrap(),
+        Ok(InheritanceError::EmergencyAccessAlreadyActive)
+    );
+
+    // The owner can still veto, and the approvals do not carry over.
+    client.veto_break_glass(&user, &plan_id);
+    env.ledger()
+        .with_mut(|li| li.timestamp = request.effective_at);
+    let res = client.try_complete_break_glass(&trusted_contact, &plan_id);
+    assert_eq!(
+        res.err().unwrap(),
+        Ok(InheritanceError::EmergencyContactNotFound)
+    );
+    client.approve_emergency_access(&guardian_1, &plan_id, &trusted_contact);
+    assert!(client.get_break_glass_request(&plan_id).is_none());
+    assert!(client.get_emergency_access(&plan_id).is_none());
+}
+
 #[test]
 fn test_invalid_guardian_rejection() {
     let env = Env::default();
@@ -3572,6 +3631,119 @@ fn test_get_emergency_contacts_empty() {
     assert_eq!(contacts.len(), 0);
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Break-Glass Emergency Access Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_break_glass_access_starts_after_waiting_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_id, _admin, user) = setup_with_token_and_admin(&env);
+    let contact = create_test_address(&env, 60);
+    let stranger = create_test_address(&env, 61);
+    let plan_id = create_plan_and_get_id(&env, &client, &token_id, &user);
+    client.add_emergency_contact(&user, &plan_id, &contact);
+
+    let res = client.try_request_break_glass(&stranger, &plan_id);
+    assert_eq!(
+        res.err().unwrap(),
+        Ok(InheritanceError::EmergencyContactNotFound)
+    );
+
+    client.set_break_glass_delay(&user, &plan_id, &3600);
+    let request = client.request_break_glass(&contact, &plan_id);
+    assert_eq!(request.effective_at, request.requested_at + 3600);
+    let res = client.try_request_break_glass(&contact, &plan_id);
+    assert_eq!(
+        res.err().unwrap(),
+        Ok(InheritanceError::EmergencyAccessAlreadyActive)
+    );
+
+    // Nothing is granted while the owner can still veto.
+    let res = client.try_complete_break_glass(&contact, &plan_id);
+    assert_eq!(
+        res.err().unwrap(),
+        Ok(InheritanceError::EmergencyCooldownActive)
+    );
+    assert!(client.get_emergency_access(&plan_id).is_none());
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = request.effective_at);
+    let record = client.complete_break_glass(&contact, &plan_id);
+    assert_eq!(record.trusted_contact, contact);
+    assert_eq!(client.get_emergency_access(&plan_id), Some(record));
+    assert!(client.get_break_glass_request(&plan_id).is_none());
+}
+
+#[test]
+fn test_emergency_withdraw_is_limited_to_scope() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_id, _admin, user) = setup_with_token_and_admin(&env);
+    let token_helper = TestTokenHelper::new(&env, &token_id);
+    let contact = create_test_address(&env, 63);
+    let utility = create_test_address(&env, 64);
+    let stranger = create_test_address(&env, 65);
+    let plan_id = create_plan_and_get_id(&env, &client, &token_id, &user);
+    client.deposit(&user, &token_id, &plan_id, &5000);
+
+    // Emergency access alone does not allow withdrawals.
+    client.activate_emergency_access(&user, &plan_id, &contact);
+    let res = client.try_emergency_withdraw(&contact, &token_id, &plan_id, &utility, &100);
+    assert_eq!(res.err().unwrap(), Ok(InheritanceError::Unauthorized));
+
+    let payees = soroban_sdk::vec![&env, utility.clone()];
+    client.set_emergency_scope(&user, &plan_id, &payees, &500);
+    let res = client.try_emergency_withdraw(&contact, &token_id, &plan_id, &stranger, &100);
+    assert_eq!(res.err().unwrap(), Ok(InheritanceError::Unauthorized));
+    let res = client.try_emergency_withdraw(&stranger, &token_id, &plan_id, &utility, &100);
+    assert_eq!(res.err().unwrap(), Ok(InheritanceError::Unauthorized));
+
+    client.emergency_withdraw(&contact, &token_id, &plan_id, &utility, &300);
+    assert_eq!(token_helper.balance(&utility), 300);
+    assert_eq!(client.get_emergency_scope(&plan_id).unwrap().spent, 300);
+    let res = client.try_emergency_withdraw(&contact, &token_id, &plan_id, &utility, &201);
+    assert_eq!(
+        res.err().unwrap(),
+        Ok(InheritanceError::AllocationExceedsLimit)
+    );
+    client.emergency_withdraw(&contact, &token_id, &plan_id, &utility, &200);
+    assert_eq!(token_helper.balance(&utility), 500);
+}
+
+#[test]
+fn test_break_glass_veto_cancels_request() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_id, _admin, user) = setup_with_token_and_admin(&env);
+    let contact = create_test_address(&env, 62);
+    let plan_id = create_plan_and_get_id(&env, &client, &token_id, &user);
+    client.add_emergency_contact(&user, &plan_id, &contact);
+    assert_eq!(client.get_break_glass_delay(&plan_id), 259200);
+
+    let request = client.request_break_glass(&contact, &plan_id);
+    let res = client.try_veto_break_glass(&contact, &plan_id);
+    assert_eq!(res.err().unwrap(), Ok(InheritanceError::Unauthorized));
+    client.veto_break_glass(&user, &plan_id);
+
+    let events = env.events().all();
+    let veto_event = events.get(events.len() - 1).unwrap();
+    assert_eq!(
+        veto_event.1,
+        (symbol_short!("EMERG"), symbol_short!("BG_VETO")).into_val(&env)
+    );
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = request.effective_at);
+    let res = client.try_complete_break_glass(&contact, &plan_id);
+    assert_eq!(
+        res.err().unwrap(),
+        Ok(InheritanceError::EmergencyContactNotFound)
+    );
+    assert!(client.get_emergency_access(&plan_id).is_none());
+}
+
 // ── Will Management System Tests (Issues #314–#317) ──
 
 fn create_plan_and_get_id(
@@ -3604,6 +3776,35 @@ fn test_will_hash_2(env: &Env) -> BytesN<32> {
 
 // --- Issue #314: Legal Will Hash Storage ---
 
+#[test]
+fn test_audit_anchors_cover_streams_contiguously() {
+    let env = Env::default();
+    let (client, _token_id, admin, owner) = setup_with_token_and_admin(&env);
+    let stream = String::from_str(&env, "actions");
+    let root = BytesN::from_array(&env, &[7u8; 32]);
+
+    let res = client.try_anchor_audit_root(&owner, &stream, &1, &10, &root);
+    assert_eq!(res.err().unwrap(), Ok(InheritanceError::NotAdmin));
+    let res = client.try_anchor_audit_root(&admin, &stream, &2, &10, &root);
+    assert_eq!(res.err().unwrap(), Ok(InheritanceError::VerificationFailed));
+
+    client.anchor_audit_root(&admin, &stream, &1, &10, &root);
+    assert_eq!(client.get_audit_anchor_head(&stream), 10);
+    let anchor = client.get_audit_anchor(&stream, &1).unwrap();
+    assert_eq!(anchor.last_seq, 10);
+    assert_eq!(anchor.root, root);
+
+    // Anchored ranges cannot be replaced.
+    let res = client.try_anchor_audit_root(&admin, &stream, &1, &12, &root);
+    assert_eq!(res.err().unwrap(), Ok(InheritanceError::VerificationFailed));
+    client.anchor_audit_root(&admin, &stream, &11, &12, &root);
+
+    // Streams are independent.
+    let other = String::from_str(&env, "will_events");
+    assert_eq!(client.get_audit_anchor_head(&other), 0);
+    client.anchor_audit_root(&admin, &other, &1, &1, &root);
+}
+
 #[test]
 fn test_store_will_hash_success() {
     let env = Env::default();
@@ -4014,6 +4215,109 @@ fn test_get_will_signature_none() {
     assert_eq!(result, None);
 }
 
+// --- Joint and mirror wills ---
+
+fn setup_multi_party_will(
+    env: &Env,
+    will_type: WillType,
+) -> (InheritanceContractClient<'_>, u64, Address, Address) {
+    let (client, token_id, _admin, owner) = setup_with_token_and_admin(env);
+    let plan_id = create_plan_and_get_id(env, &client, &token_id, &owner);
+    let spouse = Address::generate(env);
+    client.set_will_testators(&owner, &plan_id, &will_type, &vec![env, spouse.clone()]);
+    (client, plan_id, owner, spouse)
+}
+
+#[test]
+fn test_joint_will_requires_every_testator_to_finalize() {
+    let env = Env::default();
+    let (client, plan_id, owner, spouse) = setup_multi_party_will(&env, WillType::Joint);
+    let will_hash = test_will_hash(&env);
+    let version = client.create_will_version(&owner, &plan_id, &will_hash);
+
+    let stranger = Address::generate(&env);
+    assert!(client
+        .try_sign_will(&stranger, &plan_id, &will_hash)
+        .is_err());
+
+    client.sign_will(&owner, &plan_id, &will_hash);
+    assert!(client
+        .try_finalize_will(&owner, &plan_id, &version)
+        .is_err());
+
+    // Each testator's signature is bound to the signer, so both can sign the
+    // same will hash.
+    client.sign_will(&spouse, &plan_id, &will_hash);
+    assert!(client.try_sign_will(&spouse, &plan_id, &will_hash).is_err());
+    let proof = client.get_testator_signature(&plan_id, &spouse).unwrap();
+    assert_eq!(proof.signer, spouse);
+
+    client.finalize_will(&owner, &plan_id, &version);
+    assert!(client.is_will_finalized(&plan_id, &version));
+}
+
+#[test]
+fn test_set_will_testators_rejects_duplicates_and_late_changes() {
+    let env = Env::default();
+    let (client, token_id, _admin, owner) = setup_with_token_and_admin(&env);
+    let plan_id = create_plan_and_get_id(&env, &client, &token_id, &owner);
+
+    let result = client.try_set_will_testators(
+        &owner,
+        &plan_id,
+        &WillType::Mutual,
+        &vec![&env, owner.clone()],
+    );
+    assert!(result.is_err());
+
+    client.sign_will(&owner, &plan_id, &test_will_hash(&env));
+    let spouse = Address::generate(&env);
+    let result =
+        client.try_set_will_testators(&owner, &plan_id, &WillType::Mutual, &vec![&env, spouse]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_mutual_will_revocation_invalidates_all_signatures() {
+    let env = Env::default();
+    let (client, plan_id, owner, spouse) = setup_multi_party_will(&env, WillType::Mutual);
+    let will_hash = test_will_hash(&env);
+    let version = client.create_will_version(&owner, &plan_id, &will_hash);
+    client.sign_will(&owner, &plan_id, &will_hash);
+    client.sign_will(&spouse, &plan_id, &will_hash);
+
+    client.revoke_will(&spouse, &plan_id);
+
+    assert_eq!(client.get_testator_signature(&plan_id, &owner), None);
+    assert_eq!(client.get_testator_signature(&plan_id, &spouse), None);
+    assert!(client
+        .try_finalize_will(&owner, &plan_id, &version)
+        .is_err());
+    assert!(client
+        .try_sign_will(&owner, &plan_id, &test_will_hash_2(&env))
+        .is_err());
+    assert!(client.try_revoke_will(&owner, &plan_id).is_err());
+}
+
+#[test]
+fn test_mirror_will_revocation_only_withdraws_revoker() {
+    let env = Env::default();
+    let (client, plan_id, owner, spouse) = setup_multi_party_will(&env, WillType::Mirror);
+    let will_hash = test_will_hash(&env);
+    client.sign_will(&owner, &plan_id, &will_hash);
+    client.sign_will(&spouse, &plan_id, &will_hash);
+
+    client.revoke_will(&spouse, &plan_id);
+
+    assert!(client.get_testator_signature(&plan_id, &owner).is_some());
+    assert_eq!(client.get_testator_signature(&plan_id, &spouse), None);
+    assert!(client
+        .try_sign_will(&spouse, &plan_id, &test_will_hash_2(&env))
+        .is_err());
+    let config = client.get_will_testators(&plan_id).unwrap();
+    assert_eq!(config.revoked, vec![&env, spouse]);
+}
+
 // --- Issue #319: Will Finalization ---
 
 #[test]